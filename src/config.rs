@@ -1,54 +1,305 @@
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::env;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+use crate::config_file::{ConfigFile, TomlValue, CONFIG_FILE_NAME};
 use crate::error::{EmpathicError, EmpathicResult};
-use crate::lsp::LspManager;
+use crate::limiter::{ToolLimiter, DEFAULT_MAX_CONCURRENT_TOOLS};
+use crate::logging::LogFormat;
+use crate::lsp::{LspManager, LspTimeoutConfig, ResourceAction, ResourceConfig, ServerConfig, ServerOverride};
+use crate::lsp::manager::core::{default_state_file, DEFAULT_HEALTH_TIMEOUT};
+use crate::lsp::manager::tracker::DEFAULT_CHANGE_DEBOUNCE;
+use crate::lsp::types::CacheConfig;
+use crate::fs::SkipDirs;
+use crate::watcher::{FileWatcher, DEFAULT_MAX_WATCHERS, DEFAULT_POLL_INTERVAL};
+
+/// 🛑 Default grace period for shutting down on EOF or a signal
+pub const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 📏 Default cap on the text a single tool call may return (512 KiB)
+pub const DEFAULT_MAX_RESPONSE_BYTES: usize = 512 * 1024;
+
+/// 📖 Default size above which `read_file` refuses whole-file reads (64 MiB)
+pub const DEFAULT_MAX_READ_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Keys understood in `empathic.toml`
+const FILE_KEYS: &[&str] = &[
+    "root_dir",
+    "allowed_dirs",
+    "add_path",
+    "log_level",
+    "log_format",
+    "request_timeout",
+    "shutdown_timeout",
+    "max_response_bytes",
+    "max_read_bytes",
+    "watch.max_paths",
+    "watch.poll_ms",
+    "tools.enabled",
+    "tools.disabled",
+    "tools.read_only",
+    "delete.soft",
+    "env.allow",
+    "env.deny",
+    "env.show_secrets",
+    "concurrency.max_tools",
+    "concurrency.per_tool",
+    "ignore.dirs",
+    "ignore.extra_dirs",
+    "lsp.prewarm",
+    "lsp.change_debounce_ms",
+    "lsp.max_rss_mb",
+    "lsp.max_cpu_percent",
+    "lsp.resource_sustain",
+    "lsp.resource_action",
+    "lsp.restart_delay",
+    "lsp.health_timeout",
+    "lsp.state_file",
+    "lsp.timeout",
+    "lsp.cache.persist",
+    "lsp.cache.dir",
+    "lsp.cache.ttl",
+    "lsp.cache.max_entries",
+    "lsp.cache.max_bytes",
+];
+
+/// Keys of an `[lsp.<language>]` table
+const LSP_SERVER_KEYS: &[&str] = &["path", "args", "init_options"];
 
 #[derive(Debug, Clone)]
 pub struct Config {
     pub root_dir: PathBuf,
+    /// 📂 Extra directories paths may resolve into, besides `root_dir`
+    pub allowed_dirs: Vec<PathBuf>,
     pub add_path: Vec<PathBuf>,
     pub log_level: String,
+    /// 🧾 Plain text or one JSON object per log line
+    pub log_format: LogFormat,
     /// ⏱️ Hard timeout for all MCP tool requests (default: 55s, safe for Claude Desktop's 60s limit)
     pub request_timeout: Duration,
+    /// 🛑 How long graceful shutdown may take before LSP servers are force-killed
+    pub shutdown_timeout: Duration,
+    /// 📏 Tool output beyond this many bytes is truncated (0 = unlimited)
+    pub max_response_bytes: usize,
+    /// 📖 Files larger than this are not loaded whole; `read_file_chunk` pages through them (0 = unlimited)
+    pub max_read_bytes: u64,
     /// 🧠 LSP manager for file synchronization with language servers
     pub lsp_manager: Option<Arc<LspManager>>,
+    /// 👀 Paths watched for external changes (shared with the server poll loop)
+    pub file_watcher: Arc<FileWatcher>,
+    /// 🔒 Which tools may be advertised and called
+    pub tool_policy: ToolPolicy,
+    /// 🚦 Concurrency limits for tool execution (shared by all calls)
+    pub tool_limiter: Arc<ToolLimiter>,
+    /// 🗑️ Whether `delete_file` moves targets to the trash unless told otherwise
+    pub soft_delete: bool,
+    /// 🛠️ Language server binary, args and init options per language ("rust", "java", "python")
+    pub lsp_servers: HashMap<String, ServerOverride>,
+    /// 🌍 Which environment variables the `env` tool may reveal
+    pub env_policy: EnvPolicy,
+    /// 🙈 Directories recursive listing and search never descend into
+    pub skip_dirs: SkipDirs,
+    /// 🔥 Detected Rust projects whose servers start with the MCP server (0 = start on first use)
+    pub lsp_prewarm: usize,
+    /// ⏱️ Window for coalescing rapid didChange notifications per document (zero = send every change)
+    pub lsp_change_debounce: Duration,
+    /// 📊 Memory/CPU limits for language servers and what to do about sustained violations
+    pub lsp_resources: ResourceConfig,
+    /// 💾 Where the open-document set is persisted across restarts
+    pub lsp_state_file: PathBuf,
+    /// 🏓 How long a language server has to answer a health-check ping
+    pub lsp_health_timeout: Duration,
+    /// ⏱️ Default and per-method LSP request timeouts
+    pub lsp_timeouts: LspTimeoutConfig,
+    /// 🗄️ LSP response cache TTLs, size bounds and persistence
+    pub lsp_cache: CacheConfig,
+    /// 🏓 When this server's config was built, for `ping` uptime
+    pub started_at: Instant,
+}
+
+/// 🔒 Tool allowlist/denylist for read-only or sandboxed deployments
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ToolPolicy {
+    /// Only these tools are available when set
+    pub enabled: Option<Vec<String>>,
+    /// Never available, even if listed in `enabled`
+    pub disabled: Vec<String>,
+    /// Disable every tool in `MUTATING_TOOLS`
+    pub read_only: bool,
+}
+
+impl ToolPolicy {
+    /// Whether `tool_name` may be listed and executed
+    pub fn allows(&self, tool_name: &str) -> bool {
+        if self.read_only && crate::tools::MUTATING_TOOLS.contains(&tool_name) {
+            return false;
+        }
+        if self.disabled.iter().any(|name| name == tool_name) {
+            return false;
+        }
+        match &self.enabled {
+            Some(enabled) => enabled.iter().any(|name| name == tool_name),
+            None => true,
+        }
+    }
+}
+
+/// Secret-looking variable names, masked by the `env` tool unless allowed
+const SECRET_PATTERNS: &[&str] = &["*_KEY", "*_TOKEN", "*_SECRET", "*_PASSWORD", "*_PASSWD", "*_CREDENTIALS"];
+
+/// 🌍 Environment variable allowlist/denylist for the `env` tool
+///
+/// Patterns are names with `*` wildcards, e.g. `CARGO_*`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EnvPolicy {
+    /// Only matching variables are shown when set
+    pub allow: Option<Vec<String>>,
+    /// Never shown, even if allowed
+    pub deny: Vec<String>,
+    /// Callers may ask for secret-looking values unmasked
+    pub show_secrets: bool,
+}
+
+impl EnvPolicy {
+    /// Whether variable `name` may be shown at all
+    pub fn allows(&self, name: &str) -> bool {
+        let matches = |pattern: &String| glob::Pattern::new(pattern).is_ok_and(|pattern| pattern.matches(name));
+        if self.deny.iter().any(matches) {
+            return false;
+        }
+        self.allow.as_ref().is_none_or(|allow| allow.iter().any(matches))
+    }
+
+    /// Whether `name` looks like it holds a credential (case-insensitive)
+    pub fn is_secret(name: &str) -> bool {
+        let name = name.to_uppercase();
+        SECRET_PATTERNS.iter().any(|pattern| glob::Pattern::new(pattern).is_ok_and(|pattern| pattern.matches(&name)))
+    }
 }
 
 impl Config {
     /// Create a new Config for testing purposes
     pub fn new(root_dir: PathBuf) -> Self {
         Self {
+            lsp_state_file: default_state_file(&root_dir),
             root_dir,
+            allowed_dirs: Vec::new(),
             add_path: Vec::new(),
             log_level: "warn".to_string(),
+            log_format: LogFormat::Text,
             request_timeout: Duration::from_secs(55),
+            shutdown_timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+            max_read_bytes: DEFAULT_MAX_READ_BYTES,
             lsp_manager: None,
+            file_watcher: Arc::new(FileWatcher::default()),
+            tool_policy: ToolPolicy::default(),
+            tool_limiter: Arc::new(ToolLimiter::default()),
+            soft_delete: false,
+            lsp_servers: HashMap::new(),
+            env_policy: EnvPolicy::default(),
+            skip_dirs: SkipDirs::default(),
+            lsp_prewarm: 0,
+            lsp_change_debounce: DEFAULT_CHANGE_DEBOUNCE,
+            lsp_resources: ResourceConfig::default(),
+            lsp_health_timeout: DEFAULT_HEALTH_TIMEOUT,
+            lsp_timeouts: LspTimeoutConfig::default(),
+            lsp_cache: CacheConfig::default(),
+            started_at: Instant::now(),
         }
     }
 
     /// Create a new Config with LSP manager for production use
     pub fn new_with_lsp(root_dir: PathBuf, lsp_manager: Arc<LspManager>) -> Self {
         Self {
-            root_dir: root_dir.clone(),
+            lsp_state_file: default_state_file(&root_dir),
+            root_dir,
+            allowed_dirs: Vec::new(),
             add_path: Vec::new(),
             log_level: "warn".to_string(),
+            log_format: LogFormat::Text,
             request_timeout: Duration::from_secs(55),
+            shutdown_timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+            max_read_bytes: DEFAULT_MAX_READ_BYTES,
             lsp_manager: Some(lsp_manager),
+            file_watcher: Arc::new(FileWatcher::default()),
+            tool_policy: ToolPolicy::default(),
+            tool_limiter: Arc::new(ToolLimiter::default()),
+            soft_delete: false,
+            lsp_servers: HashMap::new(),
+            env_policy: EnvPolicy::default(),
+            skip_dirs: SkipDirs::default(),
+            lsp_prewarm: 0,
+            lsp_change_debounce: DEFAULT_CHANGE_DEBOUNCE,
+            lsp_resources: ResourceConfig::default(),
+            lsp_health_timeout: DEFAULT_HEALTH_TIMEOUT,
+            lsp_timeouts: LspTimeoutConfig::default(),
+            lsp_cache: CacheConfig::default(),
+            started_at: Instant::now(),
         }
     }
 
     /// 🔧 Create Config from environment variables with proper validation
     pub fn from_env() -> EmpathicResult<Self> {
+        Self::from_sources(None, |name| env::var(name).ok()).map(Self::with_env_timeouts)
+    }
+
+    /// 📄 Create Config from a TOML file, with environment variables taking precedence
+    ///
+    /// Precedence per setting: env var > file value > built-in default. A
+    /// relative `root_dir` is resolved against the file's directory, and the
+    /// directory itself is the root when neither ROOT_DIR nor `root_dir` is set.
+    pub fn from_file(path: &Path) -> EmpathicResult<Self> {
+        let file = ConfigFile::load(path)?;
+        Self::from_sources(Some(&file), |name| env::var(name).ok()).map(Self::with_env_timeouts)
+    }
+
+    /// ⏱️ Layer LSP_TIMEOUT / LSP_TIMEOUT_<method> over the file's timeouts
+    ///
+    /// Method names are open-ended, so these are scanned rather than looked up by name.
+    fn with_env_timeouts(mut self) -> Self {
+        self.lsp_timeouts = self.lsp_timeouts.with_vars(env::vars());
+        self
+    }
+
+    /// 🔎 Load `$EMPATHIC_CONFIG`, else `$ROOT_DIR/empathic.toml` if present, else env only
+    pub fn load() -> EmpathicResult<Self> {
+        if let Ok(path) = env::var("EMPATHIC_CONFIG") {
+            return Self::from_file(Path::new(&path));
+        }
+        if let Ok(root) = env::var("ROOT_DIR") {
+            let path = Path::new(&root).join(CONFIG_FILE_NAME);
+            if path.is_file() {
+                return Self::from_file(&path);
+            }
+        }
+        Self::from_env()
+    }
+
+    /// Merge an optional config file with variables from `lookup`, then validate
+    fn from_sources(file: Option<&ConfigFile>, lookup: impl Fn(&str) -> Option<String>) -> EmpathicResult<Self> {
+        if let Some(file) = file {
+            for key in file.keys() {
+                if !FILE_KEYS.contains(&key) && !is_lsp_server_key(key) && !key.starts_with("lsp.timeouts.") {
+                    log::warn!("📄 Unknown key '{}' in {}", key, file.path().display());
+                }
+            }
+        }
+        
         // Get and validate ROOT_DIR
-        let root_dir_str = env::var("ROOT_DIR")
-            .map_err(|_| EmpathicError::MissingEnvVar { 
+        let root_dir = match (lookup("ROOT_DIR"), file) {
+            (Some(root), _) => PathBuf::from(root),
+            (None, Some(file)) => match file.get_str("root_dir")? {
+                Some(root) => file.resolve_path(&root),
+                None => file.resolve_path("."),
+            },
+            (None, None) => return Err(EmpathicError::MissingEnvVar { 
                 name: "ROOT_DIR".to_string() 
-            })?;
-        
-        let root_dir = PathBuf::from(&root_dir_str);
+            }),
+        };
         
         // Validate that root directory exists
         if !root_dir.exists() {
@@ -60,22 +311,54 @@ impl Config {
         if !root_dir.is_dir() {
             return Err(EmpathicError::InvalidConfigValue {
                 field: "ROOT_DIR".to_string(),
-                value: format!("{} (not a directory)", root_dir_str),
+                value: format!("{} (not a directory)", root_dir.display()),
             });
         }
         
+        // 📂 ALLOWED_DIRS: extra roots (colon-separated, like ADD_PATH)
+        let allowed_dirs = match (lookup("ALLOWED_DIRS"), file) {
+            (Some(dirs), _) => dirs
+                .split(':')
+                .filter(|s| !s.is_empty())
+                .map(PathBuf::from)
+                .collect::<Vec<_>>(),
+            (None, Some(file)) => file.get_str_list("allowed_dirs")?
+                .unwrap_or_default()
+                .iter()
+                .map(|dir| file.resolve_path(dir))
+                .collect(),
+            (None, None) => Vec::new(),
+        };
+        for dir in &allowed_dirs {
+            if !dir.is_dir() {
+                return Err(EmpathicError::InvalidConfigValue {
+                    field: "ALLOWED_DIRS".to_string(),
+                    value: format!("{} (not an existing directory)", dir.display()),
+                });
+            }
+        }
+        
         // Parse ADD_PATH with validation
-        let add_path = env::var("ADD_PATH")
-            .unwrap_or_default()
-            .split(':')
-            .filter(|s| !s.is_empty())
-            .map(PathBuf::from)
-            .collect::<Vec<_>>();
+        let add_path = match (lookup("ADD_PATH"), file) {
+            (Some(paths), _) => paths
+                .split(':')
+                .filter(|s| !s.is_empty())
+                .map(PathBuf::from)
+                .collect::<Vec<_>>(),
+            (None, Some(file)) => file.get_str_list("add_path")?
+                .unwrap_or_default()
+                .iter()
+                .map(|path| file.resolve_path(path))
+                .collect(),
+            (None, None) => Vec::new(),
+        };
         
         // Validate and normalize log level
-        let log_level = env::var("LOGLEVEL")
-            .unwrap_or_else(|_| "warn".to_string())
-            .to_lowercase();
+        let log_level = match (lookup("LOGLEVEL"), file) {
+            (Some(level), _) => level,
+            (None, Some(file)) => file.get_str("log_level")?.unwrap_or_else(|| "warn".to_string()),
+            (None, None) => "warn".to_string(),
+        }.to_lowercase();
         
         // Validate log level
         match log_level.as_str() {
@@ -86,12 +369,28 @@ impl Config {
             }),
         }
         
+        // 🧾 LOG_FORMAT or log_format: text (default) or json
+        let (field, log_format) = match (lookup("LOG_FORMAT"), file) {
+            (Some(name), _) => ("LOG_FORMAT", Some(name)),
+            (None, Some(file)) => ("log_format", file.get_str("log_format")?),
+            (None, None) => ("", None),
+        };
+        let log_format = match log_format.filter(|name| !name.is_empty()) {
+            Some(name) => LogFormat::from_name(&name).ok_or_else(|| EmpathicError::InvalidConfigValue {
+                field: field.to_string(),
+                value: name,
+            })?,
+            None => LogFormat::Text,
+        };
+        
         // ⏱️ Parse MCP_REQUEST_TIMEOUT (default: 55s to stay under Claude Desktop's 60s limit)
-        let request_timeout = env::var("MCP_REQUEST_TIMEOUT")
-            .ok()
-            .and_then(|s| s.parse::<u64>().ok())
-            .map(Duration::from_secs)
-            .unwrap_or_else(|| Duration::from_secs(55));
+        let request_timeout = match (lookup("MCP_REQUEST_TIMEOUT"), file) {
+            (Some(secs), _) => secs.parse::<u64>().ok(),
+            (None, Some(file)) => file.get_int("request_timeout")?.and_then(|secs| u64::try_from(secs).ok()),
+            (None, None) => None,
+        }
+        .map(Duration::from_secs)
+        .unwrap_or_else(|| Duration::from_secs(55));
         
         // Validate timeout is reasonable (1s - 300s)
         if request_timeout < Duration::from_secs(1) || request_timeout > Duration::from_secs(300) {
@@ -101,12 +400,187 @@ impl Config {
             });
         }
         
+        // 🛑 Parse SHUTDOWN_TIMEOUT (seconds before stuck LSP servers are force-killed)
+        let shutdown_timeout = match (lookup("SHUTDOWN_TIMEOUT"), file) {
+            (Some(secs), _) => secs.parse::<u64>().ok(),
+            (None, Some(file)) => file.get_int("shutdown_timeout")?.and_then(|secs| u64::try_from(secs).ok()),
+            (None, None) => None,
+        }
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_SHUTDOWN_TIMEOUT);
+        
+        if shutdown_timeout < Duration::from_secs(1) || shutdown_timeout > Duration::from_secs(300) {
+            return Err(EmpathicError::InvalidConfigValue {
+                field: "SHUTDOWN_TIMEOUT".to_string(),
+                value: format!("{}s (must be 1-300)", shutdown_timeout.as_secs()),
+            });
+        }
+        
+        // 📏 MAX_RESPONSE_BYTES or max_response_bytes (0 disables truncation)
+        let max_response_bytes = match (lookup("MAX_RESPONSE_BYTES"), file) {
+            (Some(bytes), _) => Some(bytes.parse::<usize>().map_err(|_| EmpathicError::InvalidConfigValue {
+                field: "MAX_RESPONSE_BYTES".to_string(),
+                value: bytes.clone(),
+            })?),
+            (None, Some(file)) => file.get_int("max_response_bytes")?.and_then(|bytes| usize::try_from(bytes).ok()),
+            (None, None) => None,
+        }
+        .unwrap_or(DEFAULT_MAX_RESPONSE_BYTES);
+
+        // 📖 MAX_READ_BYTES or max_read_bytes (0 reads files of any size)
+        let max_read_bytes = match (lookup("MAX_READ_BYTES"), file) {
+            (Some(bytes), _) => Some(bytes.parse::<u64>().map_err(|_| EmpathicError::InvalidConfigValue {
+                field: "MAX_READ_BYTES".to_string(),
+                value: bytes.clone(),
+            })?),
+            (None, Some(file)) => file.get_int("max_read_bytes")?.and_then(|bytes| u64::try_from(bytes).ok()),
+            (None, None) => None,
+        }
+        .unwrap_or(DEFAULT_MAX_READ_BYTES);
+        
+        // 👀 Watch limits: WATCH_MAX_PATHS / WATCH_POLL_MS or the [watch] table
+        let file_int = |key: &str| -> EmpathicResult<Option<u64>> {
+            Ok(match file {
+                Some(file) => file.get_int(key)?.and_then(|value| u64::try_from(value).ok()),
+                None => None,
+            })
+        };
+        let max_watchers = match lookup("WATCH_MAX_PATHS") {
+            Some(value) => value.parse().ok(),
+            None => file_int("watch.max_paths")?.map(|value| value as usize),
+        };
+        let poll_interval = match lookup("WATCH_POLL_MS") {
+            Some(value) => value.parse().ok(),
+            None => file_int("watch.poll_ms")?,
+        }.map(Duration::from_millis);
+        
+        // 🔒 Tool policy: ENABLED_TOOLS / DISABLED_TOOLS (comma-separated), READ_ONLY or the [tools] table
+        let tool_list = |var: &str, key: &str| -> EmpathicResult<Option<Vec<String>>> {
+            Ok(match (lookup(var), file) {
+                (Some(names), _) => Some(names.split(',')
+                    .map(|name| name.trim().to_string())
+                    .filter(|name| !name.is_empty())
+                    .collect()),
+                (None, Some(file)) => file.get_str_list(key)?,
+                (None, None) => None,
+            })
+        };
+        let flag = |var: &str, key: &str| flag_value(file, &lookup, var, key);
+        let read_only = flag("READ_ONLY", "tools.read_only")?;
+        let tool_policy = ToolPolicy {
+            enabled: tool_list("ENABLED_TOOLS", "tools.enabled")?,
+            disabled: tool_list("DISABLED_TOOLS", "tools.disabled")?.unwrap_or_default(),
+            read_only,
+        };
+        let known_tools: Vec<&str> = crate::tools::get_all_tools().iter().map(|tool| tool.name()).collect();
+        for name in tool_policy.enabled.iter().flatten().chain(&tool_policy.disabled) {
+            if !known_tools.contains(&name.as_str()) {
+                log::warn!("🔒 Tool policy names unknown tool '{}'", name);
+            }
+        }
+        
+        // 🚦 Concurrency: MAX_CONCURRENT_TOOLS / TOOL_CONCURRENCY ("cargo=1,npm=2") or the [concurrency] table
+        let max_concurrent_tools = match lookup("MAX_CONCURRENT_TOOLS") {
+            Some(value) => Some(value.parse::<u64>().map_err(|_| EmpathicError::InvalidConfigValue {
+                field: "MAX_CONCURRENT_TOOLS".to_string(),
+                value: value.clone(),
+            })?),
+            None => file_int("concurrency.max_tools")?,
+        }.map(|value| value as usize).unwrap_or(DEFAULT_MAX_CONCURRENT_TOOLS);
+        if max_concurrent_tools == 0 {
+            return Err(EmpathicError::InvalidConfigValue {
+                field: "MAX_CONCURRENT_TOOLS".to_string(),
+                value: "0 (must be at least 1)".to_string(),
+            });
+        }
+        // 🔥 LSP_PREWARM or lsp.prewarm: how many detected projects get a server at startup
+        let lsp_prewarm = match lookup("LSP_PREWARM") {
+            Some(value) => Some(value.parse::<u64>().map_err(|_| EmpathicError::InvalidConfigValue {
+                field: "LSP_PREWARM".to_string(),
+                value: value.clone(),
+            })?),
+            None => file_int("lsp.prewarm")?,
+        }.map_or(0, |value| value as usize);
+        // ⏱️ LSP_CHANGE_DEBOUNCE_MS or lsp.change_debounce_ms: didChange coalescing window
+        let lsp_change_debounce = match lookup("LSP_CHANGE_DEBOUNCE_MS") {
+            Some(value) => Some(value.parse::<u64>().map_err(|_| EmpathicError::InvalidConfigValue {
+                field: "LSP_CHANGE_DEBOUNCE_MS".to_string(),
+                value: value.clone(),
+            })?),
+            None => file_int("lsp.change_debounce_ms")?,
+        }.map_or(DEFAULT_CHANGE_DEBOUNCE, Duration::from_millis);
+        // 🏓 LSP_HEALTH_TIMEOUT or lsp.health_timeout: seconds a server has to answer a ping
+        let lsp_health_timeout = match lookup("LSP_HEALTH_TIMEOUT") {
+            Some(value) => Some(value.parse::<u64>().map_err(|_| EmpathicError::InvalidConfigValue {
+                field: "LSP_HEALTH_TIMEOUT".to_string(),
+                value: value.clone(),
+            })?),
+            None => file_int("lsp.health_timeout")?,
+        };
+        if lsp_health_timeout == Some(0) {
+            return Err(EmpathicError::InvalidConfigValue {
+                field: "LSP_HEALTH_TIMEOUT".to_string(),
+                value: "0 (must be at least 1)".to_string(),
+            });
+        }
+        // 💾 LSP_STATE_FILE or lsp.state_file: open-document snapshot
+        let lsp_state_file = match (lookup("LSP_STATE_FILE"), file) {
+            (Some(path), _) => Some(PathBuf::from(path)),
+            (None, Some(file)) => file.get_str("lsp.state_file")?.map(|path| file.resolve_path(&path)),
+            (None, None) => None,
+        }.unwrap_or_else(|| default_state_file(&root_dir));
+
+        let tool_limits = match (lookup("TOOL_CONCURRENCY"), file) {
+            (Some(value), _) => ToolLimiter::parse_tool_limits(
+                &value.split(',').map(str::to_string).collect::<Vec<_>>(),
+                "TOOL_CONCURRENCY",
+            )?,
+            (None, Some(file)) => ToolLimiter::parse_tool_limits(
+                &file.get_str_list("concurrency.per_tool")?.unwrap_or_default(),
+                "concurrency.per_tool",
+            )?,
+            (None, None) => Vec::new(),
+        };
+        
         let config = Config {
+            lsp_cache: lsp_cache_config(file, &lookup, &root_dir)?,
             root_dir,
+            allowed_dirs,
             add_path,
             log_level,
+            log_format,
             request_timeout,
+            shutdown_timeout,
+            max_response_bytes,
+            max_read_bytes,
+            tool_policy,
             lsp_manager: None, // Will be set later by McpServer
+            file_watcher: Arc::new(FileWatcher::new(
+                max_watchers.unwrap_or(DEFAULT_MAX_WATCHERS),
+                poll_interval.unwrap_or(DEFAULT_POLL_INTERVAL),
+            )),
+            tool_limiter: Arc::new(ToolLimiter::with_overrides(max_concurrent_tools, &tool_limits)),
+            // 🗑️ SOFT_DELETE or [delete] soft: trash instead of unlinking by default
+            soft_delete: flag("SOFT_DELETE", "delete.soft")?,
+            lsp_servers: lsp_server_overrides(file, &lookup)?,
+            // 🌍 ENV_ALLOW / ENV_DENY (comma-separated patterns), ENV_SHOW_SECRETS or the [env] table
+            env_policy: EnvPolicy {
+                allow: tool_list("ENV_ALLOW", "env.allow")?,
+                deny: tool_list("ENV_DENY", "env.deny")?.unwrap_or_default(),
+                show_secrets: flag("ENV_SHOW_SECRETS", "env.show_secrets")?,
+            },
+            // 🙈 IGNORE_DIRS replaces the default skip set, EXTRA_IGNORE_DIRS extends it (or the [ignore] table)
+            skip_dirs: SkipDirs::new(
+                tool_list("IGNORE_DIRS", "ignore.dirs")?.as_deref(),
+                &tool_list("EXTRA_IGNORE_DIRS", "ignore.extra_dirs")?.unwrap_or_default(),
+            )?,
+            lsp_prewarm,
+            lsp_change_debounce,
+            lsp_resources: lsp_resource_config(file, &lookup)?,
+            lsp_state_file,
+            lsp_health_timeout: lsp_health_timeout.map_or(DEFAULT_HEALTH_TIMEOUT, Duration::from_secs),
+            lsp_timeouts: lsp_timeout_config(file)?,
+            started_at: Instant::now(),
         };
         
         // Perform final validation
@@ -170,10 +644,9 @@ impl Config {
             None => self.root_dir.clone(),
         };
         
-        // Ensure the path is within root_dir (security check)
-        if let Ok(canonical_root) = self.root_dir.canonicalize()
-            && let Ok(canonical_path) = path.canonicalize()
-            && !canonical_path.starts_with(canonical_root)
+        // Ensure the path is within an allowed root (security check)
+        if let Ok(canonical_path) = path.canonicalize()
+            && !self.is_path_allowed(&canonical_path)
         {
             return Err(EmpathicError::InvalidPath { 
                 path: path.clone() 
@@ -183,6 +656,37 @@ impl Config {
         Ok(path)
     }
 
+    /// 📂 Every root paths may live under: `root_dir` first, then `allowed_dirs`
+    pub fn allowed_roots(&self) -> Vec<PathBuf> {
+        std::iter::once(self.root_dir.clone())
+            .chain(self.allowed_dirs.iter().cloned())
+            .collect()
+    }
+
+    /// 🛡️ Whether a canonical path lies under any allowed root
+    ///
+    /// Roots are canonicalized here too, so a symlinked ROOT_DIR still matches.
+    pub fn is_path_allowed(&self, canonical_path: &Path) -> bool {
+        self.allowed_roots().iter()
+            .filter_map(|root| root.canonicalize().ok())
+            .any(|root| canonical_path.starts_with(root))
+    }
+
+    /// 🛡️ Resolve `path` through symlinks and `..`, refusing anything outside the allowed roots
+    ///
+    /// Paths that don't exist yet (write targets) are checked via their nearest
+    /// existing ancestor, and dangling links via the path they point at, so
+    /// writing through a symlink can't create files outside either. Returns the
+    /// canonical path.
+    pub fn validate_path(&self, path: &Path) -> EmpathicResult<PathBuf> {
+        let canonical = resolve_nonexistent(path, 0)
+            .ok_or_else(|| EmpathicError::InvalidPath { path: path.to_path_buf() })?;
+        if !self.is_path_allowed(&canonical) {
+            return Err(EmpathicError::FileAccessDenied { path: path.to_path_buf() });
+        }
+        Ok(canonical)
+    }
+
     /// Get LSP manager if available
     pub fn lsp_manager(&self) -> Option<&Arc<LspManager>> {
         self.lsp_manager.as_ref()
@@ -191,16 +695,193 @@ impl Config {
     /// 📊 Get configuration summary for logging
     pub fn summary(&self) -> String {
         format!(
-            "📁 Root: {}, 🔧 Paths: {}, 📝 Log: {}, ⏱️ Timeout: {}s, 🧠 LSP: {}",
+            "📁 Root: {}{}, 🔧 Paths: {}, 📝 Log: {}, ⏱️ Timeout: {}s, 🧠 LSP: {}{}",
             self.root_dir.display(),
+            if self.allowed_dirs.is_empty() { String::new() } else { format!(" (+{} allowed)", self.allowed_dirs.len()) },
             self.add_path.len(),
             self.log_level,
             self.request_timeout.as_secs(),
-            if self.lsp_manager.is_some() { "enabled" } else { "disabled" }
+            if self.lsp_manager.is_some() { "enabled" } else { "disabled" },
+            if self.tool_policy == ToolPolicy::default() { "" } else { ", 🔒 Tool policy: restricted" }
         )
     }
 }
 
+/// Whether `key` is `lsp.<known language>.<server key>`
+fn is_lsp_server_key(key: &str) -> bool {
+    let registry = ServerConfig::create_registry();
+    key.strip_prefix("lsp.")
+        .and_then(|rest| rest.split_once('.'))
+        .is_some_and(|(language, field)| registry.contains_key(language) && LSP_SERVER_KEYS.contains(&field))
+}
+
+/// 🛠️ Server overrides per language: LSP_<LANG>_PATH / _ARGS / _INIT_OPTIONS or `[lsp.<lang>]`
+///
+/// Env args are whitespace-separated; init options are JSON (a string in the
+/// file) merged into the server's defaults.
+fn lsp_server_overrides(file: Option<&ConfigFile>, lookup: &impl Fn(&str) -> Option<String>) -> EmpathicResult<HashMap<String, ServerOverride>> {
+    let mut overrides = HashMap::new();
+    for language in ServerConfig::create_registry().into_keys() {
+        let var = |field: &str| format!("LSP_{}_{}", language.to_uppercase(), field);
+        let key = |field: &str| format!("lsp.{language}.{field}");
+
+        let command = match (lookup(&var("PATH")), file) {
+            (Some(path), _) => Some(path),
+            (None, Some(file)) => file.get_str(&key("path"))?
+                .map(|path| file.resolve_path(&path).to_string_lossy().to_string()),
+            (None, None) => None,
+        };
+        let args = match (lookup(&var("ARGS")), file) {
+            (Some(args), _) => Some(args.split_whitespace().map(str::to_string).collect()),
+            (None, Some(file)) => file.get_str_list(&key("args"))?,
+            (None, None) => None,
+        };
+        let (field, init_options) = match (lookup(&var("INIT_OPTIONS")), file) {
+            (Some(json), _) => (var("INIT_OPTIONS"), Some(json)),
+            (None, Some(file)) => (key("init_options"), file.get_str(&key("init_options"))?),
+            (None, None) => (String::new(), None),
+        };
+        let init_options = match init_options {
+            Some(json) => match serde_json::from_str::<serde_json::Value>(&json) {
+                Ok(value) if value.is_object() => Some(value),
+                _ => return Err(EmpathicError::InvalidConfigValue {
+                    field,
+                    value: format!("{json} (expected a JSON object)"),
+                }),
+            },
+            None => None,
+        };
+
+        let server = ServerOverride { command, args, init_options };
+        if !server.is_empty() {
+            overrides.insert(language, server);
+        }
+    }
+    Ok(overrides)
+}
+
+/// 📊 Server limits: LSP_MAX_RSS_MB / LSP_MAX_CPU_PERCENT / LSP_RESOURCE_SUSTAIN /
+/// LSP_RESOURCE_ACTION (warn, restart or kill) / LSP_RESTART_DELAY or the matching `[lsp]` keys
+fn lsp_resource_config(file: Option<&ConfigFile>, lookup: &impl Fn(&str) -> Option<String>) -> EmpathicResult<ResourceConfig> {
+    let defaults = ResourceConfig::default();
+    let number = |var: &str, key: &str| -> EmpathicResult<Option<f64>> {
+        match (lookup(var), file) {
+            (Some(value), _) => match value.trim().parse::<f64>() {
+                Ok(number) if number >= 0.0 => Ok(Some(number)),
+                _ => Err(EmpathicError::InvalidConfigValue { field: var.to_string(), value }),
+            },
+            (None, Some(file)) => match file.get_float(key)? {
+                Some(number) if number >= 0.0 => Ok(Some(number)),
+                Some(number) => Err(EmpathicError::InvalidConfigValue { field: key.to_string(), value: number.to_string() }),
+                None => Ok(None),
+            },
+            (None, None) => Ok(None),
+        }
+    };
+    let (field, action) = match (lookup("LSP_RESOURCE_ACTION"), file) {
+        (Some(name), _) => ("LSP_RESOURCE_ACTION", Some(name)),
+        (None, Some(file)) => ("lsp.resource_action", file.get_str("lsp.resource_action")?),
+        (None, None) => ("", None),
+    };
+    let enforcement = match action {
+        Some(name) => Some(ResourceAction::from_name(&name).ok_or_else(|| EmpathicError::InvalidConfigValue {
+            field: field.to_string(),
+            value: format!("{name} (expected warn, restart or kill)"),
+        })?),
+        None => None,
+    };
+
+    Ok(ResourceConfig {
+        max_rss_mb: number("LSP_MAX_RSS_MB", "lsp.max_rss_mb")?.unwrap_or(defaults.max_rss_mb),
+        max_cpu_percent: number("LSP_MAX_CPU_PERCENT", "lsp.max_cpu_percent")?.unwrap_or(defaults.max_cpu_percent),
+        sustain_secs: number("LSP_RESOURCE_SUSTAIN", "lsp.resource_sustain")?.map_or(defaults.sustain_secs, |secs| secs as u64),
+        restart_delay_secs: number("LSP_RESTART_DELAY", "lsp.restart_delay")?.map_or(defaults.restart_delay_secs, |secs| secs as u64),
+        enforcement,
+        ..defaults
+    })
+}
+
+/// 🚩 Boolean from `var` (1/true/yes/on, 0/false/no/off) or the file's `key`; false when unset
+fn flag_value(file: Option<&ConfigFile>, lookup: &impl Fn(&str) -> Option<String>, var: &str, key: &str) -> EmpathicResult<bool> {
+    Ok(match (lookup(var), file) {
+        (Some(value), _) => match value.to_lowercase().as_str() {
+            "1" | "true" | "yes" | "on" => true,
+            "" | "0" | "false" | "no" | "off" => false,
+            _ => return Err(EmpathicError::InvalidConfigValue {
+                field: var.to_string(),
+                value,
+            }),
+        },
+        (None, Some(file)) => file.get_bool(key)?.unwrap_or(false),
+        (None, None) => false,
+    })
+}
+
+/// 🗄️ Response cache: LSP_CACHE_PERSIST / LSP_CACHE_DIR / LSP_CACHE_TTL /
+/// LSP_CACHE_MAX_ENTRIES / LSP_CACHE_MAX_BYTES or the `[lsp.cache]` table
+///
+/// Persistence is opt-in and defaults to `<root>/.empathic/lsp-cache`; a TTL
+/// replaces every per-method one.
+fn lsp_cache_config(file: Option<&ConfigFile>, lookup: &impl Fn(&str) -> Option<String>, root_dir: &Path) -> EmpathicResult<CacheConfig> {
+    let defaults = CacheConfig::default();
+    let number = |var: &str, key: &str| -> EmpathicResult<Option<u64>> {
+        match (lookup(var), file) {
+            (Some(value), _) => value.trim().parse::<u64>().map(Some)
+                .map_err(|_| EmpathicError::InvalidConfigValue { field: var.to_string(), value }),
+            (None, Some(file)) => match file.get_int(key)? {
+                Some(number) => u64::try_from(number).map(Some)
+                    .map_err(|_| EmpathicError::InvalidConfigValue { field: key.to_string(), value: number.to_string() }),
+                None => Ok(None),
+            },
+            (None, None) => Ok(None),
+        }
+    };
+    let dir = match (lookup("LSP_CACHE_DIR"), file) {
+        (Some(dir), _) => Some(PathBuf::from(dir)),
+        (None, Some(file)) => file.get_str("lsp.cache.dir")?.map(|dir| file.resolve_path(&dir)),
+        (None, None) => None,
+    };
+    let ttl = number("LSP_CACHE_TTL", "lsp.cache.ttl")?;
+
+    Ok(CacheConfig {
+        persist_dir: flag_value(file, lookup, "LSP_CACHE_PERSIST", "lsp.cache.persist")?
+            .then(|| dir.unwrap_or_else(|| root_dir.join(".empathic").join("lsp-cache"))),
+        diagnostics_ttl_secs: ttl.unwrap_or(defaults.diagnostics_ttl_secs),
+        completion_ttl_secs: ttl.unwrap_or(defaults.completion_ttl_secs),
+        symbols_ttl_secs: ttl.unwrap_or(defaults.symbols_ttl_secs),
+        hover_ttl_secs: ttl.unwrap_or(defaults.hover_ttl_secs),
+        max_entries: number("LSP_CACHE_MAX_ENTRIES", "lsp.cache.max_entries")?.map_or(defaults.max_entries, |n| (n as usize).max(1)),
+        max_bytes: number("LSP_CACHE_MAX_BYTES", "lsp.cache.max_bytes")?.map_or(defaults.max_bytes, |n| n as usize),
+    })
+}
+
+/// ⏱️ Request timeouts from `lsp.timeout` and `[lsp.timeouts]`, valued like LSP_TIMEOUT
+/// (seconds, or a string with an `ms`/`s`/`m` suffix)
+fn lsp_timeout_config(file: Option<&ConfigFile>) -> EmpathicResult<LspTimeoutConfig> {
+    let Some(file) = file else {
+        return Ok(LspTimeoutConfig::default());
+    };
+    let mut vars = Vec::new();
+    for key in file.keys() {
+        let var = match key.strip_prefix("lsp.timeouts.") {
+            Some(method) => format!("LSP_TIMEOUT_{method}"),
+            None if key == "lsp.timeout" => "LSP_TIMEOUT".to_string(),
+            None => continue,
+        };
+        let value = match file.get(key) {
+            Some(TomlValue::String(value)) => value.clone(),
+            Some(TomlValue::Integer(secs)) => secs.to_string(),
+            Some(other) => return Err(EmpathicError::InvalidConfigValue {
+                field: key.to_string(),
+                value: format!("expected seconds or a duration string, found {}", other.type_str()),
+            }),
+            None => continue,
+        };
+        vars.push((var, value));
+    }
+    Ok(LspTimeoutConfig::from_vars(vars))
+}
+
 // === 🎯 Compatibility Layer ===
 // For gradual migration - provides anyhow::Result version
 
@@ -214,6 +895,33 @@ impl Config {
     }
 }
 
+/// Maximum dangling-link hops followed, matching Linux's SYMLOOP_MAX
+const MAX_LINK_DEPTH: usize = 40;
+
+/// Canonicalize `path` even when its tail doesn't exist yet
+///
+/// Missing components are appended to the nearest existing ancestor; a
+/// dangling symlink is followed to the path it would create. `..` in the
+/// missing tail has no `file_name()`, so it is rejected rather than guessed.
+pub(crate) fn resolve_nonexistent(path: &Path, depth: usize) -> Option<PathBuf> {
+    let mut existing = path;
+    let mut missing = Vec::new();
+    let base = loop {
+        if let Ok(canonical) = existing.canonicalize() {
+            break canonical;
+        }
+        if let Ok(target) = std::fs::read_link(existing) {
+            if depth >= MAX_LINK_DEPTH {
+                return None;
+            }
+            break resolve_nonexistent(&existing.parent()?.join(target), depth + 1)?;
+        }
+        missing.push(existing.file_name()?);
+        existing = existing.parent()?;
+    };
+    Some(missing.iter().rev().fold(base, |acc, name| acc.join(name)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -303,4 +1011,342 @@ mod tests {
             assert!(matches!(e, EmpathicError::MissingEnvVar { .. }));
         }
     }
+
+    #[test]
+    fn test_file_values_with_env_precedence() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("workspace")).unwrap();
+        let path = dir.path().join(CONFIG_FILE_NAME);
+        std::fs::write(&path, "root_dir = \"workspace\"\nlog_level = \"info\"\nrequest_timeout = 30\nshutdown_timeout = 5\nmax_response_bytes = 4096\nmax_read_bytes = 1048576\n\n[watch]\nmax_paths = 4\n").unwrap();
+        let file = ConfigFile::load(&path).unwrap();
+
+        // 📄 File only
+        let config = Config::from_sources(Some(&file), |_| None).unwrap();
+        assert_eq!(config.root_dir, dir.path().join("workspace"));
+        assert_eq!(config.log_level, "info");
+        assert_eq!(config.request_timeout, Duration::from_secs(30));
+        assert_eq!(config.shutdown_timeout, Duration::from_secs(5));
+        assert_eq!(config.file_watcher.max_watchers(), 4);
+        assert_eq!(config.max_response_bytes, 4096);
+        assert_eq!(config.max_read_bytes, 1024 * 1024);
+
+        // 🌱 Env wins over the file
+        let config = Config::from_sources(Some(&file), |name| match name {
+            "LOGLEVEL" => Some("debug".to_string()),
+            "ROOT_DIR" => Some(dir.path().to_string_lossy().to_string()),
+            "SHUTDOWN_TIMEOUT" => Some("20".to_string()),
+            "MAX_RESPONSE_BYTES" => Some("0".to_string()),
+            "MAX_READ_BYTES" => Some("0".to_string()),
+            _ => None,
+        }).unwrap();
+        assert_eq!(config.max_response_bytes, 0);
+        assert_eq!(config.max_read_bytes, 0);
+        assert_eq!(config.log_level, "debug");
+        assert_eq!(config.shutdown_timeout, Duration::from_secs(20));
+        assert_eq!(config.root_dir, dir.path());
+        assert_eq!(config.request_timeout, Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_file_values_are_validated() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(CONFIG_FILE_NAME);
+
+        std::fs::write(&path, "log_level = \"loud\"\n").unwrap();
+        let file = ConfigFile::load(&path).unwrap();
+        assert!(Config::from_sources(Some(&file), |_| None).is_err());
+
+        std::fs::write(&path, "request_timeout = 900\n").unwrap();
+        let file = ConfigFile::load(&path).unwrap();
+        assert!(Config::from_sources(Some(&file), |_| None).is_err());
+
+        std::fs::write(&path, "shutdown_timeout = 0\n").unwrap();
+        let file = ConfigFile::load(&path).unwrap();
+        assert!(Config::from_sources(Some(&file), |_| None).is_err());
+
+        // No root_dir anywhere: the file's directory is the root
+        std::fs::write(&path, "").unwrap();
+        let file = ConfigFile::load(&path).unwrap();
+        assert_eq!(Config::from_sources(Some(&file), |_| None).unwrap().root_dir, dir.path());
+    }
+
+    #[test]
+    fn test_tool_policy_sources() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(CONFIG_FILE_NAME);
+        std::fs::write(&path, "[tools]\nread_only = true\ndisabled = [\"git\"]\n").unwrap();
+        let file = ConfigFile::load(&path).unwrap();
+
+        let config = Config::from_sources(Some(&file), |_| None).unwrap();
+        assert!(config.tool_policy.read_only);
+        assert!(!config.tool_policy.allows("write_file"));
+        assert!(config.tool_policy.allows("read_file"));
+
+        let config = Config::from_sources(Some(&file), |name| match name {
+            "READ_ONLY" => Some("false".to_string()),
+            "DISABLED_TOOLS" => Some("shell, bash_tool".to_string()),
+            _ => None,
+        }).unwrap();
+        assert!(config.tool_policy.allows("write_file"));
+        assert_eq!(config.tool_policy.disabled, vec!["shell", "bash_tool"]);
+
+        assert!(Config::from_sources(Some(&file), |name| (name == "READ_ONLY").then(|| "maybe".to_string())).is_err());
+    }
+
+    #[test]
+    fn test_soft_delete_sources() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(CONFIG_FILE_NAME);
+        std::fs::write(&path, "[delete]\nsoft = true\n").unwrap();
+        let file = ConfigFile::load(&path).unwrap();
+
+        assert!(Config::from_sources(Some(&file), |_| None).unwrap().soft_delete);
+        let env_off = |name: &str| (name == "SOFT_DELETE").then(|| "off".to_string());
+        assert!(!Config::from_sources(Some(&file), env_off).unwrap().soft_delete);
+        assert!(!Config::new(dir.path().to_path_buf()).soft_delete);
+    }
+
+    #[test]
+    fn test_env_policy() {
+        let policy = EnvPolicy {
+            allow: Some(vec!["CARGO_*".to_string(), "PATH".to_string(), "GITHUB_TOKEN".to_string()]),
+            deny: vec!["CARGO_REGISTRY_*".to_string()],
+            show_secrets: false,
+        };
+        assert!(policy.allows("PATH"));
+        assert!(policy.allows("CARGO_HOME"));
+        assert!(!policy.allows("CARGO_REGISTRY_TOKEN"), "deny wins over allow");
+        assert!(!policy.allows("HOME"));
+        assert!(EnvPolicy::default().allows("HOME"));
+
+        assert!(EnvPolicy::is_secret("GITHUB_TOKEN"));
+        assert!(EnvPolicy::is_secret("openai_api_key"));
+        assert!(EnvPolicy::is_secret("DB_PASSWORD"));
+        assert!(!EnvPolicy::is_secret("KEYBOARD_LAYOUT"));
+        assert!(!EnvPolicy::is_secret("TOKENIZERS_PARALLELISM"));
+    }
+
+    #[test]
+    fn test_env_policy_sources() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(CONFIG_FILE_NAME);
+        std::fs::write(&path, "[env]
+allow = [\"PATH\", \"RUST_*\"]
+show_secrets = true
+").unwrap();
+        let file = ConfigFile::load(&path).unwrap();
+
+        let config = Config::from_sources(Some(&file), |_| None).unwrap();
+        assert_eq!(config.env_policy.allow, Some(vec!["PATH".to_string(), "RUST_*".to_string()]));
+        assert!(config.env_policy.show_secrets);
+
+        let config = Config::from_sources(Some(&file), |name| match name {
+            "ENV_DENY" => Some("AWS_*, GITHUB_TOKEN".to_string()),
+            "ENV_SHOW_SECRETS" => Some("0".to_string()),
+            _ => None,
+        }).unwrap();
+        assert_eq!(config.env_policy.deny, vec!["AWS_*", "GITHUB_TOKEN"]);
+        assert!(!config.env_policy.show_secrets);
+    }
+
+    #[test]
+    fn test_lsp_server_sources() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(CONFIG_FILE_NAME);
+        std::fs::write(&path, concat!(
+            "[lsp]\n",
+            "prewarm = 2\n",
+            "change_debounce_ms = 0\n",
+            "max_rss_mb = 2048\n",
+            "resource_action = \"restart\"\n\n",
+            "[lsp.rust]\n",
+            "path = \"tools/rust-analyzer\"\n",
+            "args = [\"--log-file\", \"ra.log\"]\n",
+            "init_options = '{\"cargo\": {\"features\": [\"serde\"]}}'\n",
+        )).unwrap();
+        let file = ConfigFile::load(&path).unwrap();
+
+        let config = Config::from_sources(Some(&file), |_| None).unwrap();
+        let rust = &config.lsp_servers["rust"];
+        assert_eq!(rust.command.as_deref(), Some(dir.path().join("tools/rust-analyzer").to_str().unwrap()));
+        assert_eq!(rust.args.as_deref(), Some(&["--log-file".to_string(), "ra.log".to_string()][..]));
+        assert_eq!(rust.init_options.as_ref().unwrap()["cargo"]["features"][0], "serde");
+        assert!(!config.lsp_servers.contains_key("python"));
+        assert_eq!(config.lsp_prewarm, 2);
+        assert!(config.lsp_change_debounce.is_zero());
+        assert_eq!(config.lsp_resources.max_rss_mb, 2048.0);
+        assert_eq!(config.lsp_resources.enforcement, Some(ResourceAction::Restart));
+
+        let config = Config::from_sources(Some(&file), |name| match name {
+            "LSP_RUST_PATH" => Some("/opt/ra".to_string()),
+            "LSP_RUST_ARGS" => Some("-v  --no-log-buffering".to_string()),
+            "LSP_PREWARM" => Some("0".to_string()),
+            "LSP_CHANGE_DEBOUNCE_MS" => Some("120".to_string()),
+            "LSP_RESOURCE_ACTION" => Some("kill".to_string()),
+            "LSP_MAX_CPU_PERCENT" => Some("150.5".to_string()),
+            _ => None,
+        }).unwrap();
+        assert_eq!(config.lsp_resources.enforcement, Some(ResourceAction::Kill));
+        assert_eq!(config.lsp_resources.max_cpu_percent, 150.5);
+        assert_eq!(config.lsp_resources.max_rss_mb, 2048.0);
+        assert_eq!(config.lsp_prewarm, 0);
+        assert_eq!(config.lsp_change_debounce, Duration::from_millis(120));
+        assert_eq!(config.lsp_servers["rust"].command.as_deref(), Some("/opt/ra"));
+        assert_eq!(config.lsp_servers["rust"].args.as_ref().unwrap().len(), 2);
+
+        let bad_json = |name: &str| (name == "LSP_RUST_INIT_OPTIONS").then(|| "[1]".to_string());
+        assert!(Config::from_sources(Some(&file), bad_json).is_err());
+        assert!(Config::from_sources(None, |name| (name == "LSP_PREWARM").then(|| "all".to_string())).is_err());
+        assert!(Config::from_sources(None, |name| (name == "LSP_CHANGE_DEBOUNCE_MS").then(|| "-5".to_string())).is_err());
+        assert!(Config::from_sources(None, |name| (name == "LSP_RESOURCE_ACTION").then(|| "explode".to_string())).is_err());
+        assert!(Config::from_sources(None, |name| (name == "LSP_MAX_RSS_MB").then(|| "lots".to_string())).is_err());
+        assert!(is_lsp_server_key("lsp.python.args"));
+        assert!(!is_lsp_server_key("lsp.cobol.path"));
+    }
+
+    #[test]
+    fn test_lsp_runtime_sources() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(CONFIG_FILE_NAME);
+        std::fs::write(&path, concat!(
+            "log_format = \"json\"\n",
+            "[lsp]\n",
+            "restart_delay = 5\n",
+            "max_rss_mb = 1536.5\n",
+            "health_timeout = 3\n",
+            "state_file = \"state/lsp.json\"\n",
+            "timeout = \"20s\"\n",
+            "timeouts = { references = \"30s\", \"textDocument/hover\" = 2 }\n",
+            "cache.persist = true\n",
+            "cache.ttl = 120\n",
+            "cache.max_entries = 50\n",
+        )).unwrap();
+        let file = ConfigFile::load(&path).unwrap();
+
+        let config = Config::from_sources(Some(&file), |_| None).unwrap();
+        assert_eq!(config.log_format, LogFormat::Json);
+        assert_eq!(config.lsp_resources.restart_delay_secs, 5);
+        assert_eq!(config.lsp_resources.max_rss_mb, 1536.5);
+        assert_eq!(config.lsp_health_timeout, Duration::from_secs(3));
+        assert_eq!(config.lsp_state_file, dir.path().join("state/lsp.json"));
+        assert_eq!(config.lsp_timeouts.default_timeout, Duration::from_secs(20));
+        assert_eq!(config.lsp_timeouts.timeout_for("textDocument/references"), Duration::from_secs(30));
+        assert_eq!(config.lsp_timeouts.timeout_for("textDocument/hover"), Duration::from_secs(2));
+        assert_eq!(config.lsp_cache.persist_dir, Some(dir.path().join(".empathic/lsp-cache")));
+        assert_eq!(config.lsp_cache.hover_ttl_secs, 120);
+        assert_eq!(config.lsp_cache.max_entries, 50);
+
+        // 🌱 Env wins over the file
+        let config = Config::from_sources(Some(&file), |name| match name {
+            "LOG_FORMAT" => Some("text".to_string()),
+            "LSP_RESTART_DELAY" => Some("1".to_string()),
+            "LSP_HEALTH_TIMEOUT" => Some("9".to_string()),
+            "LSP_STATE_FILE" => Some("/var/lib/empathic/state.json".to_string()),
+            "LSP_CACHE_PERSIST" => Some("off".to_string()),
+            _ => None,
+        }).unwrap();
+        assert_eq!(config.log_format, LogFormat::Text);
+        assert_eq!(config.lsp_resources.restart_delay_secs, 1);
+        assert_eq!(config.lsp_health_timeout, Duration::from_secs(9));
+        assert_eq!(config.lsp_state_file, PathBuf::from("/var/lib/empathic/state.json"));
+        assert_eq!(config.lsp_cache.persist_dir, None);
+        let timeouts = config.lsp_timeouts.with_vars([("LSP_TIMEOUT_references".to_string(), "5s".to_string())]);
+        assert_eq!(timeouts.timeout_for("textDocument/references"), Duration::from_secs(5));
+
+        // 📁 Defaults hang off the root
+        let root = dir.path().to_string_lossy().to_string();
+        let config = Config::from_sources(None, |name| (name == "ROOT_DIR").then(|| root.clone())).unwrap();
+        assert_eq!(config.lsp_state_file, dir.path().join(".empathic/lsp_state.json"));
+        assert_eq!(config.lsp_health_timeout, DEFAULT_HEALTH_TIMEOUT);
+        assert_eq!(config.lsp_cache.persist_dir, None);
+
+        let with_root = |var: &'static str, value: &'static str| {
+            let root = root.clone();
+            move |name: &str| match name {
+                "ROOT_DIR" => Some(root.clone()),
+                _ => (name == var).then(|| value.to_string()),
+            }
+        };
+        assert!(Config::from_sources(None, with_root("LOG_FORMAT", "xml")).is_err());
+        assert!(Config::from_sources(None, with_root("LSP_HEALTH_TIMEOUT", "0")).is_err());
+        assert!(Config::from_sources(None, with_root("LSP_CACHE_MAX_BYTES", "big")).is_err());
+        assert!(Config::from_sources(None, with_root("LSP_RESTART_DELAY", "soon")).is_err());
+    }
+
+    #[test]
+    fn test_concurrency_sources() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(CONFIG_FILE_NAME);
+        std::fs::write(&path, "[concurrency]\nmax_tools = 3\nper_tool = [\"cargo=1\"]\n").unwrap();
+        let file = ConfigFile::load(&path).unwrap();
+
+        let config = Config::from_sources(Some(&file), |_| None).unwrap();
+        assert_eq!(config.tool_limiter.max_concurrent(), 3);
+        assert_eq!(config.tool_limiter.tool_limit("cargo"), Some(1));
+        assert_eq!(config.tool_limiter.tool_limit("gradle"), Some(1));
+
+        let config = Config::from_sources(Some(&file), |name| match name {
+            "MAX_CONCURRENT_TOOLS" => Some("16".to_string()),
+            "TOOL_CONCURRENCY" => Some("cargo=4,shell=2".to_string()),
+            _ => None,
+        }).unwrap();
+        assert_eq!(config.tool_limiter.max_concurrent(), 16);
+        assert_eq!(config.tool_limiter.tool_limit("cargo"), Some(4));
+        assert_eq!(config.tool_limiter.tool_limit("shell"), Some(2));
+
+        assert!(Config::from_sources(Some(&file), |name| (name == "MAX_CONCURRENT_TOOLS").then(|| "0".to_string())).is_err());
+        assert!(Config::from_sources(Some(&file), |name| (name == "TOOL_CONCURRENCY").then(|| "cargo".to_string())).is_err());
+    }
+
+    #[test]
+    fn test_allowed_dirs_sources() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("shared")).unwrap();
+        let path = dir.path().join(CONFIG_FILE_NAME);
+        std::fs::write(&path, "allowed_dirs = [\"shared\"]\n").unwrap();
+        let file = ConfigFile::load(&path).unwrap();
+
+        let config = Config::from_sources(Some(&file), |_| None).unwrap();
+        assert_eq!(config.allowed_dirs, vec![dir.path().join("shared")]);
+        assert_eq!(config.allowed_roots().len(), 2);
+        assert!(config.is_path_allowed(&dir.path().join("shared").canonicalize().unwrap()));
+
+        let env_dirs = format!("{}:", dir.path().display());
+        let config = Config::from_sources(Some(&file), |name| (name == "ALLOWED_DIRS").then(|| env_dirs.clone())).unwrap();
+        assert_eq!(config.allowed_dirs, vec![dir.path().to_path_buf()]);
+
+        assert!(Config::from_sources(None, |name| (name == "ALLOWED_DIRS").then(|| "/no/such/dir".to_string())).is_err());
+    }
+
+    #[test]
+    fn test_ignore_dirs_sources() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(CONFIG_FILE_NAME);
+        std::fs::write(&path, "[ignore]\nextra_dirs = [\"node_modules\", \".venv\"]\n").unwrap();
+        let file = ConfigFile::load(&path).unwrap();
+
+        let config = Config::from_sources(Some(&file), |_| None).unwrap();
+        assert_eq!(config.skip_dirs.entries(), vec![".git", ".hg", ".svn", "target", "node_modules", ".venv"]);
+
+        let config = Config::from_sources(Some(&file), |name| match name {
+            "IGNORE_DIRS" => Some("build".to_string()),
+            "EXTRA_IGNORE_DIRS" => Some("dist,*.egg-info".to_string()),
+            _ => None,
+        }).unwrap();
+        assert_eq!(config.skip_dirs.entries(), vec!["build", "dist", "*.egg-info"]);
+
+        assert!(Config::from_sources(None, |name| (name == "EXTRA_IGNORE_DIRS").then(|| "[oops".to_string())).is_err());
+    }
+
+    #[test]
+    fn test_validate_path_resolves_missing_tail() {
+        let root = tempfile::TempDir::new().unwrap();
+        let config = Config::new(root.path().to_path_buf());
+        let canonical_root = root.path().canonicalize().unwrap();
+
+        assert_eq!(config.validate_path(root.path()).unwrap(), canonical_root);
+        assert_eq!(config.validate_path(&root.path().join("new/dir/file.txt")).unwrap(), canonical_root.join("new/dir/file.txt"));
+        assert!(config.validate_path(&root.path().join("missing/../../outside")).is_err());
+        assert!(config.validate_path(&root.path().join("..")).is_err());
+    }
 }
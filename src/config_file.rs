@@ -0,0 +1,209 @@
+//! 📄 Config File - Reader for `empathic.toml`
+//!
+//! Parsed with the `toml` crate, so any valid TOML works: tables, inline
+//! tables, dotted keys, floats and `#` comments. Nested tables are flattened
+//! and their keys addressed as `table.key`.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::error::{EmpathicError, EmpathicResult};
+
+/// Default file name, looked up in ROOT_DIR
+pub const CONFIG_FILE_NAME: &str = "empathic.toml";
+
+/// 🧱 A parsed TOML value
+pub use toml::Value as TomlValue;
+
+/// 📄 Values loaded from one config file
+#[derive(Debug, Clone)]
+pub struct ConfigFile {
+    path: PathBuf,
+    values: HashMap<String, TomlValue>,
+}
+
+impl ConfigFile {
+    /// Read and parse a config file
+    pub fn load(path: &Path) -> EmpathicResult<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| EmpathicError::FileOperationFailed {
+                operation: "read config".to_string(),
+                path: path.to_path_buf(),
+                reason: e.to_string(),
+            })?;
+        Self::parse(path, &content)
+    }
+
+    /// Parse config file content; `path` is used for errors and relative paths
+    pub fn parse(path: &Path, content: &str) -> EmpathicResult<Self> {
+        let table: toml::Table = toml::from_str(content).map_err(|e| {
+            let line = e.span().map_or(1, |span| content[..span.start].matches('\n').count() + 1);
+            EmpathicError::ConfigValidation {
+                message: format!("{}:{}: {}", path.display(), line, e.message()),
+            }
+        })?;
+
+        let mut values = HashMap::new();
+        flatten("", table, &mut values);
+        Ok(Self { path: path.to_path_buf(), values })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Keys present in the file, sorted
+    pub fn keys(&self) -> Vec<&str> {
+        let mut keys: Vec<&str> = self.values.keys().map(String::as_str).collect();
+        keys.sort();
+        keys
+    }
+
+    pub fn get(&self, key: &str) -> Option<&TomlValue> {
+        self.values.get(key)
+    }
+
+    pub fn get_str(&self, key: &str) -> EmpathicResult<Option<String>> {
+        match self.get(key) {
+            None => Ok(None),
+            Some(TomlValue::String(value)) => Ok(Some(value.clone())),
+            Some(other) => Err(self.type_error(key, "string", other)),
+        }
+    }
+
+    pub fn get_int(&self, key: &str) -> EmpathicResult<Option<i64>> {
+        match self.get(key) {
+            None => Ok(None),
+            Some(TomlValue::Integer(value)) => Ok(Some(*value)),
+            Some(other) => Err(self.type_error(key, "integer", other)),
+        }
+    }
+
+    /// A float, or an integer widened to one
+    pub fn get_float(&self, key: &str) -> EmpathicResult<Option<f64>> {
+        match self.get(key) {
+            None => Ok(None),
+            Some(TomlValue::Float(value)) => Ok(Some(*value)),
+            Some(TomlValue::Integer(value)) => Ok(Some(*value as f64)),
+            Some(other) => Err(self.type_error(key, "number", other)),
+        }
+    }
+
+    pub fn get_bool(&self, key: &str) -> EmpathicResult<Option<bool>> {
+        match self.get(key) {
+            None => Ok(None),
+            Some(TomlValue::Boolean(value)) => Ok(Some(*value)),
+            Some(other) => Err(self.type_error(key, "boolean", other)),
+        }
+    }
+
+    pub fn get_str_list(&self, key: &str) -> EmpathicResult<Option<Vec<String>>> {
+        match self.get(key) {
+            None => Ok(None),
+            Some(TomlValue::Array(items)) => items.iter()
+                .map(|item| match item {
+                    TomlValue::String(value) => Ok(value.clone()),
+                    other => Err(self.type_error(key, "array of strings", other)),
+                })
+                .collect::<EmpathicResult<Vec<_>>>()
+                .map(Some),
+            Some(other) => Err(self.type_error(key, "array of strings", other)),
+        }
+    }
+
+    /// Resolve a path value relative to the directory holding the file
+    pub fn resolve_path(&self, value: &str) -> PathBuf {
+        let path = PathBuf::from(value);
+        if path.is_absolute() {
+            return path;
+        }
+        self.path.parent().unwrap_or(Path::new(".")).join(path)
+    }
+
+    fn type_error(&self, key: &str, expected: &str, found: &TomlValue) -> EmpathicError {
+        EmpathicError::InvalidConfigValue {
+            field: format!("{} ({})", key, self.path.display()),
+            value: format!("expected {}, found {}", expected, found.type_str()),
+        }
+    }
+}
+
+/// Store every non-table value under its dotted key
+fn flatten(prefix: &str, table: toml::Table, values: &mut HashMap<String, TomlValue>) {
+    for (key, value) in table {
+        let key = if prefix.is_empty() { key } else { format!("{}.{}", prefix, key) };
+        match value {
+            TomlValue::Table(table) => flatten(&key, table, values),
+            value => {
+                values.insert(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tables_and_types() {
+        let content = r#"
+# empathic settings
+root_dir = "workspace"   # relative to this file
+log_level = 'info'
+request_timeout = 1_20
+add_path = [
+    "/opt/bin",  # tools
+    "/usr/local/go/bin",
+]
+
+[watch]
+max_paths = 8
+enabled = true
+"#;
+        let file = ConfigFile::parse(Path::new("/etc/empathic/empathic.toml"), content).unwrap();
+        assert_eq!(file.get_str("root_dir").unwrap().as_deref(), Some("workspace"));
+        assert_eq!(file.get_str("log_level").unwrap().as_deref(), Some("info"));
+        assert_eq!(file.get_int("request_timeout").unwrap(), Some(120));
+        assert_eq!(file.get_str_list("add_path").unwrap().unwrap(), vec!["/opt/bin", "/usr/local/go/bin"]);
+        assert_eq!(file.get_int("watch.max_paths").unwrap(), Some(8));
+        assert_eq!(file.get_bool("watch.enabled").unwrap(), Some(true));
+        assert_eq!(file.resolve_path("workspace"), PathBuf::from("/etc/empathic/workspace"));
+    }
+
+    #[test]
+    fn test_parse_errors_name_the_line() {
+        let error = ConfigFile::parse(Path::new("empathic.toml"), "log_level = \"info\"\nbad line\n").unwrap_err();
+        assert!(error.to_string().contains("empathic.toml:2"), "{}", error);
+
+        let file = ConfigFile::parse(Path::new("empathic.toml"), "request_timeout = \"55\"").unwrap();
+        assert!(file.get_int("request_timeout").is_err());
+
+        assert!(ConfigFile::parse(Path::new("empathic.toml"), "a = [1, 2").is_err());
+        assert!(ConfigFile::parse(Path::new("empathic.toml"), "a = \"x\" y").is_err());
+    }
+
+    #[test]
+    fn test_floats_inline_tables_and_dotted_keys() {
+        let content = r#"
+lsp.max_rss_mb = 1536.5
+watch = { max_paths = 8, poll_ms = 250 }
+
+[lsp.rust]
+args = ["--log-file", "ra.log"]
+"#;
+        let file = ConfigFile::parse(Path::new("empathic.toml"), content).unwrap();
+        assert_eq!(file.get_float("lsp.max_rss_mb").unwrap(), Some(1536.5));
+        assert_eq!(file.get_float("watch.max_paths").unwrap(), Some(8.0));
+        assert_eq!(file.get_int("watch.poll_ms").unwrap(), Some(250));
+        assert_eq!(file.get_str_list("lsp.rust.args").unwrap().unwrap(), vec!["--log-file", "ra.log"]);
+        assert_eq!(file.keys(), vec!["lsp.max_rss_mb", "lsp.rust.args", "watch.max_paths", "watch.poll_ms"]);
+        assert!(file.get_int("lsp.max_rss_mb").is_err());
+    }
+
+    #[test]
+    fn test_hash_inside_string_is_kept() {
+        let file = ConfigFile::parse(Path::new("empathic.toml"), "name = \"a#b\" # comment").unwrap();
+        assert_eq!(file.get_str("name").unwrap().as_deref(), Some("a#b"));
+    }
+}
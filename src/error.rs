@@ -32,6 +32,9 @@ pub enum EmpathicError {
         reason: String,
     },
 
+    #[error("File too large: {path} is {size} bytes, over the {limit}-byte read limit (page through it with read_file_chunk)")]
+    FileTooLarge { path: PathBuf, size: u64, limit: u64 },
+
     // === ⚙️ Configuration Errors ===
     #[error("Configuration validation failed: {message}")]
     ConfigValidation { message: String },
@@ -61,6 +64,9 @@ pub enum EmpathicError {
     #[error("LSP request timeout: {timeout_secs}s")]
     LspTimeout { timeout_secs: u64 },
 
+    #[error("LSP request timeout: {method} exceeded {timeout_ms}ms")]
+    LspRequestTimeout { method: String, timeout_ms: u64 },
+
     #[error("No LSP server available for: {file_path}")]
     LspNoServerAvailable { file_path: PathBuf },
 
@@ -92,6 +98,14 @@ pub enum EmpathicError {
     #[error("Tool timeout: {tool_name} exceeded {timeout_secs}s")]
     ToolTimeout { tool_name: String, timeout_secs: u64 },
 
+    #[error("Command timed out after {timeout_secs}s and was killed: {command}\n--- stdout so far ---\n{stdout}\n--- stderr so far ---\n{stderr}")]
+    CommandTimeout {
+        command: String,
+        timeout_secs: u64,
+        stdout: String,
+        stderr: String,
+    },
+
     // === 📡 MCP Protocol Errors ===
     #[error("Invalid MCP request: {message}")]
     InvalidMcpRequest { message: String },
@@ -108,6 +122,22 @@ pub enum EmpathicError {
     #[error("Tool not found: {tool_name}")]
     ToolNotFound { tool_name: String },
 
+    #[error("Invalid arguments for tool '{tool_name}':\n• {}", problems.join("\n• "))]
+    ToolArgumentsInvalid { tool_name: String, problems: Vec<String> },
+
+    #[error("Tool '{tool_name}' is disabled by policy (ENABLED_TOOLS/DISABLED_TOOLS/READ_ONLY)")]
+    ToolDisabled { tool_name: String },
+
+    #[error("Prompt not found: {name}")]
+    PromptNotFound { name: String },
+
+    #[error("Invalid arguments for prompt '{prompt}': missing [{}], unknown [{}]", missing.join(", "), unknown.join(", "))]
+    PromptArgumentsInvalid {
+        prompt: String,
+        missing: Vec<String>,
+        unknown: Vec<String>,
+    },
+
     // === 🔍 Search & Replace Errors ===
     #[error("Search pattern not found: {pattern} in {file}")]
     SearchPatternNotFound { pattern: String, file: PathBuf },
@@ -121,13 +151,21 @@ pub enum EmpathicError {
     #[error("String not found in file: '{search_str}' does not appear in {path}")]
     StrReplaceNotFound { path: String, search_str: String },
 
-    #[error("String appears {count} times in file: '{search_str}' must appear exactly once in {path}")]
+    #[error("String appears {count} times in file: '{search_str}' must appear exactly once in {path} (or pick one with 'occurrence')")]
     StrReplaceMultipleMatches {
         path: String,
         search_str: String,
         count: usize,
     },
 
+    #[error("String appears {found} times in file, expected {expected}: '{search_str}' in {path}")]
+    StrReplaceCountMismatch {
+        path: String,
+        search_str: String,
+        expected: usize,
+        found: usize,
+    },
+
     // === 🧬 Unicode & Text Processing Errors ===
     #[error("Unicode processing error: {message}")]
     UnicodeError { message: String },
@@ -243,6 +281,9 @@ impl From<crate::lsp::types::LspError> for EmpathicError {
             }
             LspError::JsonRpcError { message } => EmpathicError::LspJsonRpcError { message },
             LspError::Timeout { timeout_secs } => EmpathicError::LspTimeout { timeout_secs },
+            LspError::RequestTimeout { method, timeout_ms } => {
+                EmpathicError::LspRequestTimeout { method, timeout_ms }
+            }
             LspError::NoServerAvailable { file_path } => {
                 EmpathicError::LspNoServerAvailable { file_path }
             }
@@ -268,6 +309,20 @@ impl EmpathicError {
         Self::FileNotFound { path: path.into() }
     }
 
+    /// Create a file operation error, keeping "not found" and "permission denied" distinct
+    pub fn file_io(operation: &str, path: impl Into<PathBuf>, error: std::io::Error) -> Self {
+        let path = path.into();
+        match error.kind() {
+            std::io::ErrorKind::NotFound => Self::FileNotFound { path },
+            std::io::ErrorKind::PermissionDenied => Self::FileAccessDenied { path },
+            _ => Self::FileOperationFailed {
+                operation: operation.to_string(),
+                path,
+                reason: error.to_string(),
+            },
+        }
+    }
+
     /// Create a tool execution error with context
     pub fn tool_failed(tool_name: impl Into<String>, message: impl Into<String>) -> Self {
         Self::ToolExecutionFailed {
@@ -356,7 +411,8 @@ impl EmpathicError {
             | EmpathicError::FileAccessDenied { .. }
             | EmpathicError::InvalidPath { .. }
             | EmpathicError::DirectoryCreationFailed { .. }
-            | EmpathicError::FileOperationFailed { .. } => "filesystem",
+            | EmpathicError::FileOperationFailed { .. }
+            | EmpathicError::FileTooLarge { .. } => "filesystem",
 
             EmpathicError::ConfigValidation { .. }
             | EmpathicError::MissingEnvVar { .. }
@@ -367,6 +423,7 @@ impl EmpathicError {
             | EmpathicError::CommandFailed { .. }
             | EmpathicError::CommandNotFound { .. }
             | EmpathicError::ToolTimeout { .. }
+            | EmpathicError::CommandTimeout { .. }
             | EmpathicError::InvalidArgument { .. } => "execution",
 
             EmpathicError::LspServerNotFound { .. }
@@ -374,6 +431,7 @@ impl EmpathicError {
             | EmpathicError::LspServerCrashed { .. }
             | EmpathicError::LspJsonRpcError { .. }
             | EmpathicError::LspTimeout { .. }
+            | EmpathicError::LspRequestTimeout { .. }
             | EmpathicError::LspNoServerAvailable { .. }
             | EmpathicError::LspInitializationFailed { .. }
             | EmpathicError::LspWorkspaceSyncFailed { .. } => "lsp",
@@ -382,13 +440,18 @@ impl EmpathicError {
             | EmpathicError::McpParameterMissing { .. }
             | EmpathicError::McpParameterInvalid { .. }
             | EmpathicError::JsonRpcProtocol { .. }
-            | EmpathicError::ToolNotFound { .. } => "protocol",
+            | EmpathicError::ToolNotFound { .. }
+            | EmpathicError::ToolArgumentsInvalid { .. }
+            | EmpathicError::ToolDisabled { .. }
+            | EmpathicError::PromptNotFound { .. }
+            | EmpathicError::PromptArgumentsInvalid { .. } => "protocol",
 
             EmpathicError::SearchPatternNotFound { .. }
             | EmpathicError::InvalidRegexPattern { .. }
             | EmpathicError::ReplaceOperationFailed { .. }
             | EmpathicError::StrReplaceNotFound { .. }
-            | EmpathicError::StrReplaceMultipleMatches { .. } => "search_replace",
+            | EmpathicError::StrReplaceMultipleMatches { .. }
+            | EmpathicError::StrReplaceCountMismatch { .. } => "search_replace",
 
             EmpathicError::UnicodeError { .. }
             | EmpathicError::TextEncodingError { .. }
@@ -408,6 +471,102 @@ impl EmpathicError {
     }
 }
 
+/// 🔢 Error taxonomy surfaced to MCP clients as JSON-RPC error codes
+///
+/// Protocol-level problems use the standard JSON-RPC codes; everything else
+/// gets a server-defined code from the -32000..-32099 range so clients can
+/// branch on the failure without parsing messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    /// Malformed request envelope (-32600)
+    InvalidRequest,
+    /// Unknown method or tool (-32601)
+    MethodNotFound,
+    /// Missing, mistyped or out-of-range arguments (-32602)
+    InvalidParams,
+    /// Any other failure while running a tool (-32000)
+    ServerError,
+    /// A tool, command or LSP request ran out of time (-32001)
+    Timeout,
+    /// Path outside the allowed roots or unreadable (-32002)
+    PathDenied,
+    /// File or directory doesn't exist (-32003)
+    NotFound,
+    /// Tool switched off by ENABLED_TOOLS/DISABLED_TOOLS/READ_ONLY (-32004)
+    ToolDisabled,
+    /// Operation unavailable on this platform or language server (-32005)
+    NotSupported,
+}
+
+impl ErrorCode {
+    /// Numeric JSON-RPC code
+    pub fn code(self) -> i32 {
+        match self {
+            ErrorCode::InvalidRequest => -32600,
+            ErrorCode::MethodNotFound => -32601,
+            ErrorCode::InvalidParams => -32602,
+            ErrorCode::ServerError => -32000,
+            ErrorCode::Timeout => -32001,
+            ErrorCode::PathDenied => -32002,
+            ErrorCode::NotFound => -32003,
+            ErrorCode::ToolDisabled => -32004,
+            ErrorCode::NotSupported => -32005,
+        }
+    }
+
+    /// Stable name sent as `data.kind`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::InvalidRequest => "invalid_request",
+            ErrorCode::MethodNotFound => "method_not_found",
+            ErrorCode::InvalidParams => "invalid_params",
+            ErrorCode::ServerError => "server_error",
+            ErrorCode::Timeout => "timeout",
+            ErrorCode::PathDenied => "path_denied",
+            ErrorCode::NotFound => "not_found",
+            ErrorCode::ToolDisabled => "tool_disabled",
+            ErrorCode::NotSupported => "not_supported",
+        }
+    }
+}
+
+impl EmpathicError {
+    /// Which JSON-RPC error class this error is reported as
+    pub fn error_code(&self) -> ErrorCode {
+        match self {
+            EmpathicError::InvalidMcpRequest { .. } | EmpathicError::JsonRpcProtocol { .. } => ErrorCode::InvalidRequest,
+
+            EmpathicError::ToolNotFound { .. } => ErrorCode::MethodNotFound,
+
+            EmpathicError::McpParameterMissing { .. }
+            | EmpathicError::McpParameterInvalid { .. }
+            | EmpathicError::MissingRequiredParameter { .. }
+            | EmpathicError::InvalidArgument { .. }
+            | EmpathicError::InvalidPath { .. }
+            | EmpathicError::ToolArgumentsInvalid { .. }
+            | EmpathicError::PromptNotFound { .. }
+            | EmpathicError::PromptArgumentsInvalid { .. }
+            | EmpathicError::InvalidRegexPattern { .. }
+            | EmpathicError::InvalidLineRange { .. } => ErrorCode::InvalidParams,
+
+            EmpathicError::ToolTimeout { .. }
+            | EmpathicError::CommandTimeout { .. }
+            | EmpathicError::LspTimeout { .. }
+            | EmpathicError::LspRequestTimeout { .. } => ErrorCode::Timeout,
+
+            EmpathicError::FileAccessDenied { .. } => ErrorCode::PathDenied,
+
+            EmpathicError::FileNotFound { .. } | EmpathicError::RootDirectoryNotFound { .. } => ErrorCode::NotFound,
+
+            EmpathicError::ToolDisabled { .. } => ErrorCode::ToolDisabled,
+
+            EmpathicError::NotSupported { .. } | EmpathicError::NotImplemented { .. } => ErrorCode::NotSupported,
+
+            _ => ErrorCode::ServerError,
+        }
+    }
+}
+
 // === 🔄 Compatibility Functions ===
 
 /// Convert any anyhow::Result to EmpathicResult for gradual migration
@@ -517,4 +676,15 @@ mod tests {
         let anyhow_result = to_anyhow(empathic_err);
         assert!(anyhow_result.is_err());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_error_codes() {
+        assert_eq!(EmpathicError::mcp_parameter("line", "must be >= 0").error_code(), ErrorCode::InvalidParams);
+        assert_eq!(EmpathicError::FileAccessDenied { path: "/etc".into() }.error_code().code(), -32002);
+        let missing = std::io::Error::from(std::io::ErrorKind::NotFound);
+        assert_eq!(EmpathicError::file_io("read", "/test", missing).error_code(), ErrorCode::NotFound);
+        assert_eq!(EmpathicError::file_not_found("/test").error_code().code(), -32003);
+        assert_eq!(EmpathicError::ToolDisabled { tool_name: "git".into() }.error_code().as_str(), "tool_disabled");
+        assert_eq!(EmpathicError::tool_failed("git", "boom").error_code().code(), -32000);
+    }
+}
@@ -1,28 +1,113 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use crate::error::{EmpathicResult, EmpathicError};
 
+/// Bytes inspected when sniffing for binary content
+const BINARY_SNIFF_BYTES: usize = 8 * 1024;
+
+/// Empathic-only ignore file: hides paths from listing, search and watching
+/// without touching git. Same syntax as `.gitignore`, and it wins over both
+/// `.ignore` and `.gitignore` where they disagree (so `!path` can re-include)
+pub const EMPATHIC_IGNORE: &str = ".empathicignore";
+
+/// Directory names skipped by default (`target` only inside Cargo projects)
+pub const DEFAULT_SKIP_DIRS: &[&str] = &[".git", ".hg", ".svn", "target"];
+
+/// 🙈 Directories recursive listings never descend into
+///
+/// By default VCS metadata, and `target` only next to a `Cargo.toml` so
+/// unrelated directories of that name still show up. Configured entries are
+/// directory names or globs (`*.egg-info`) and always apply; they either
+/// extend the defaults or replace them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkipDirs {
+    defaults: bool,
+    patterns: Vec<glob::Pattern>,
+}
+
+impl Default for SkipDirs {
+    fn default() -> Self {
+        Self { defaults: true, patterns: Vec::new() }
+    }
+}
+
+impl SkipDirs {
+    /// `replace` stands in for the defaults when given; `extra` adds to either
+    pub fn new(replace: Option<&[String]>, extra: &[String]) -> EmpathicResult<Self> {
+        let patterns = replace.unwrap_or_default().iter()
+            .chain(extra)
+            .map(|name| glob::Pattern::new(name.trim_end_matches('/')).map_err(|e| EmpathicError::InvalidConfigValue {
+                field: "IGNORE_DIRS".to_string(),
+                value: format!("{} ({})", name, e),
+            }))
+            .collect::<EmpathicResult<_>>()?;
+        Ok(Self { defaults: replace.is_none(), patterns })
+    }
+
+    /// Every entry, defaults first
+    pub fn entries(&self) -> Vec<String> {
+        let defaults = DEFAULT_SKIP_DIRS.iter().filter(|_| self.defaults).map(|name| name.to_string());
+        defaults.chain(self.patterns.iter().map(|pattern| pattern.as_str().to_string())).collect()
+    }
+
+    pub fn is_skipped(&self, path: &Path) -> bool {
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            return false;
+        };
+        let default = self.defaults && match name {
+            ".git" | ".hg" | ".svn" => true,
+            "target" => path.with_file_name("Cargo.toml").is_file(),
+            _ => false,
+        };
+        (default || self.patterns.iter().any(|pattern| pattern.matches(name))) && path.is_dir()
+    }
+}
+
 /// Unicode-aware file operations 🦀
 pub struct FileOps;
 
 impl FileOps {
-    /// Read entire file content
+    /// Read entire file content, decoding UTF-16/Latin-1 files to UTF-8
     pub async fn read_file(path: &Path) -> EmpathicResult<String> {
-        let content = tokio::fs::read_to_string(path).await
-            .map_err(|e| EmpathicError::FileOperationFailed {
-                operation: "read".to_string(),
+        Ok(Self::read_file_with_encoding(path, None).await?.0)
+    }
+
+    /// 🔤 Read and decode a file, returning the encoding that was used
+    ///
+    /// Without an explicit `encoding` the file is sniffed (see `TextEncoding::detect`).
+    pub async fn read_file_with_encoding(path: &Path, encoding: Option<TextEncoding>) -> EmpathicResult<(String, TextEncoding)> {
+        let bytes = tokio::fs::read(path).await
+            .map_err(|e| EmpathicError::file_io("read", path, e))?;
+
+        let encoding = encoding.unwrap_or_else(|| TextEncoding::detect(&bytes));
+        let content = encoding.decode(&bytes)
+            .map_err(|reason| EmpathicError::FileOperationFailed {
+                operation: "decode".to_string(),
                 path: path.to_path_buf(),
-                reason: e.to_string(),
+                reason,
             })?;
-        Ok(content)
+        Ok((content, encoding))
+    }
+
+    /// Encoding of an existing file (UTF-8 for missing or unreadable files)
+    pub async fn detect_encoding(path: &Path) -> TextEncoding {
+        match tokio::fs::read(path).await {
+            Ok(bytes) => TextEncoding::detect(&bytes),
+            Err(_) => TextEncoding::Utf8,
+        }
     }
     
     /// Read file content with line-based chunking
     pub async fn read_file_chunk(path: &Path, line_offset: usize, line_length: Option<usize>) -> EmpathicResult<String> {
         let content = Self::read_file(path).await?;
+        Ok(Self::chunk_lines(&content, line_offset, line_length))
+    }
+
+    /// Line-based chunk of already loaded content
+    pub fn chunk_lines(content: &str, line_offset: usize, line_length: Option<usize>) -> String {
         let lines: Vec<&str> = content.lines().collect();
         
         if line_offset >= lines.len() {
-            return Ok(String::new());
+            return String::new();
         }
         
         let end_line = match line_length {
@@ -31,11 +116,233 @@ impl FileOps {
         };
         
         let chunk_lines = &lines[line_offset..end_line];
-        Ok(chunk_lines.join("\n"))
+        chunk_lines.join("\n")
     }
     
-    /// Write entire file content
+    /// Prefix each line with its number, right-aligned, starting at `first_line`
+    pub fn number_lines(content: &str, first_line: usize) -> String {
+        let last_line = first_line + content.lines().count().saturating_sub(1);
+        let width = last_line.to_string().len();
+        content
+            .lines()
+            .enumerate()
+            .map(|(i, line)| format!("{:>width$} | {}", first_line + i, line))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Wrap content in a Markdown code fence tagged with `language`
+    ///
+    /// The fence is one backtick longer than any backtick run in the content
+    /// so embedded fences can't close it early.
+    pub fn fence(content: &str, language: &str) -> String {
+        let longest_run = content
+            .split(|c| c != '`')
+            .map(str::len)
+            .max()
+            .unwrap_or(0);
+        let fence = "`".repeat(longest_run.max(2) + 1);
+        let newline = if content.is_empty() || content.ends_with('\n') { "" } else { "\n" };
+        format!("{fence}{language}\n{content}{newline}{fence}")
+    }
+
+    /// Markdown fence language for a file, inferred from its extension or name
+    pub fn fence_language(path: &Path) -> &'static str {
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+        match name {
+            "Dockerfile" => return "dockerfile",
+            "Makefile" | "makefile" | "GNUmakefile" => return "makefile",
+            "CMakeLists.txt" => return "cmake",
+            _ => {}
+        }
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or_default().to_lowercase();
+        match extension.as_str() {
+            "rs" => "rust",
+            "py" | "pyi" => "python",
+            "js" | "mjs" | "cjs" => "javascript",
+            "jsx" => "jsx",
+            "ts" | "mts" | "cts" => "typescript",
+            "tsx" => "tsx",
+            "java" => "java",
+            "kt" | "kts" => "kotlin",
+            "gradle" => "groovy",
+            "go" => "go",
+            "c" | "h" => "c",
+            "cc" | "cpp" | "cxx" | "hpp" | "hh" | "hxx" => "cpp",
+            "cs" => "csharp",
+            "rb" => "ruby",
+            "php" => "php",
+            "swift" => "swift",
+            "scala" => "scala",
+            "sh" | "bash" | "zsh" => "bash",
+            "ps1" => "powershell",
+            "sql" => "sql",
+            "html" | "htm" => "html",
+            "css" => "css",
+            "scss" => "scss",
+            "json" => "json",
+            "toml" => "toml",
+            "yaml" | "yml" => "yaml",
+            "xml" => "xml",
+            "md" | "markdown" => "markdown",
+            "lua" => "lua",
+            "proto" => "protobuf",
+            _ => "",
+        }
+    }
+    
+    /// Read a 1-based inclusive line range, optionally capped at `max_bytes`
+    ///
+    /// The byte cap never splits a UTF-8 character; `truncated` reports
+    /// whether it cut the requested range short.
+    pub async fn read_file_range(path: &Path, start_line: usize, end_line: Option<usize>, max_bytes: Option<usize>, encoding: Option<TextEncoding>) -> EmpathicResult<FileRange> {
+        let (content, encoding) = Self::read_file_with_encoding(path, encoding).await?;
+        let lines: Vec<&str> = content.lines().collect();
+        let total_lines = lines.len();
+
+        let first = start_line.max(1);
+        let last = end_line.unwrap_or(total_lines).min(total_lines);
+        let mut selected = if first <= last {
+            lines[first - 1..last].join("\n")
+        } else {
+            String::new()
+        };
+
+        let mut truncated = false;
+        if let Some(max_bytes) = max_bytes
+            && selected.len() > max_bytes
+        {
+            let mut cut = max_bytes;
+            while !selected.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            selected.truncate(cut);
+            truncated = true;
+        }
+
+        let returned_lines = if selected.is_empty() { 0 } else { selected.matches('\n').count() + 1 };
+
+        Ok(FileRange {
+            bytes: selected.len(),
+            content: selected,
+            start_line: first,
+            end_line: first + returned_lines - 1,
+            total_lines,
+            truncated,
+            encoding,
+        })
+    }
+
+    /// 📦 Read up to `length` bytes at byte `offset` without loading the rest of the file
+    ///
+    /// Content is decoded as UTF-8. A character cut off at the end is left for
+    /// the next chunk (`next_offset` starts on it), continuation bytes at the
+    /// start are skipped, and any other invalid bytes become U+FFFD.
+    pub async fn read_file_bytes(path: &Path, offset: u64, length: usize) -> EmpathicResult<FileChunk> {
+        use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+        let mut file = tokio::fs::File::open(path).await
+            .map_err(|e| EmpathicError::file_io("read", path, e))?;
+        let total_size = file.metadata().await?.len();
+
+        let mut buffer = Vec::with_capacity(length);
+        if offset < total_size {
+            file.seek(std::io::SeekFrom::Start(offset)).await?;
+            file.take(length as u64).read_to_end(&mut buffer).await?;
+        }
+
+        let is_continuation = |byte: u8| byte & 0xC0 == 0x80;
+        let skipped = if offset > 0 {
+            buffer.iter().take(3).take_while(|byte| is_continuation(**byte)).count()
+        } else {
+            0
+        };
+        let mut end = buffer.len();
+        if offset + (end as u64) < total_size {
+            // Back up to the last character's lead byte and drop it if incomplete
+            let lead = (skipped..end).rev().take(4).find(|&i| !is_continuation(buffer[i]));
+            if let Some(lead) = lead.filter(|&lead| lead > skipped) {
+                let width = match buffer[lead] {
+                    0xC0..=0xDF => 2,
+                    0xE0..=0xEF => 3,
+                    0xF0..=0xF7 => 4,
+                    _ => 1,
+                };
+                if lead + width > end {
+                    end = lead;
+                }
+            }
+        }
+
+        let start = offset.min(total_size) + skipped as u64;
+        let next_offset = offset.min(total_size) + end as u64;
+        Ok(FileChunk {
+            content: String::from_utf8_lossy(&buffer[skipped..end]).into_owned(),
+            offset: start,
+            length: (next_offset - start) as usize,
+            total_size,
+            next_offset,
+            eof: next_offset >= total_size,
+        })
+    }
+
+    /// Sniff whether a file looks binary (NUL byte within the first 8KB)
+    ///
+    /// UTF-16 text is full of NULs, so a UTF-16 byte order mark wins.
+    pub async fn is_binary(path: &Path) -> EmpathicResult<bool> {
+        use tokio::io::AsyncReadExt;
+
+        let mut file = tokio::fs::File::open(path).await
+            .map_err(|e| EmpathicError::file_io("read", path, e))?;
+
+        let mut buffer = vec![0u8; BINARY_SNIFF_BYTES];
+        let mut filled = 0;
+        while filled < buffer.len() {
+            let read = file.read(&mut buffer[filled..]).await?;
+            if read == 0 {
+                break;
+            }
+            filled += read;
+        }
+
+        let sniffed = &buffer[..filled];
+        if matches!(TextEncoding::from_bom(sniffed), Some(TextEncoding::Utf16Le | TextEncoding::Utf16Be)) {
+            return Ok(false);
+        }
+        Ok(sniffed.contains(&0))
+    }
+    
+    /// Write entire file content atomically
+    ///
+    /// Content goes to a temp file in the same directory which is then renamed
+    /// over the target, so readers never see a half-written file. An existing
+    /// file's permissions carry over to the new one; symlinks are written
+    /// through, never replaced.
     pub async fn write_file(path: &Path, content: &str) -> EmpathicResult<()> {
+        Self::write_file_encoded(path, content, TextEncoding::Utf8).await
+    }
+
+    /// 🔤 Atomically write content re-encoded as `encoding`
+    ///
+    /// Fails without touching the file when the text can't be represented
+    /// (e.g. emoji in a Latin-1 file).
+    pub async fn write_file_encoded(path: &Path, content: &str, encoding: TextEncoding) -> EmpathicResult<()> {
+        // 🔗 Renaming onto a symlink would replace the link, so write its target instead
+        let target = crate::config::resolve_nonexistent(path, 0).unwrap_or_else(|| path.to_path_buf());
+        let path = target.as_path();
+        let bytes = encoding.encode(content)
+            .map_err(|reason| EmpathicError::FileOperationFailed {
+                operation: "encode".to_string(),
+                path: path.to_path_buf(),
+                reason,
+            })?;
+
+        let write_error = |e: std::io::Error| EmpathicError::FileOperationFailed {
+            operation: "write".to_string(),
+            path: path.to_path_buf(),
+            reason: e.to_string(),
+        };
+
         // Ensure parent directory exists
         if let Some(parent) = path.parent() {
             tokio::fs::create_dir_all(parent).await
@@ -45,18 +352,117 @@ impl FileOps {
                 })?;
         }
         
-        tokio::fs::write(path, content).await
+        let temp_path = Self::temp_path_for(path);
+        let result = async {
+            tokio::fs::write(&temp_path, &bytes).await?;
+            if let Ok(metadata) = tokio::fs::metadata(path).await {
+                tokio::fs::set_permissions(&temp_path, metadata.permissions()).await?;
+            }
+            tokio::fs::rename(&temp_path, path).await
+        }.await;
+
+        if let Err(e) = result {
+            let _ = tokio::fs::remove_file(&temp_path).await;
+            return Err(write_error(e));
+        }
+        Ok(())
+    }
+
+    /// ➕ Append content re-encoded as `encoding`, returning the new file size
+    ///
+    /// Existing bytes are never rewritten; a BOM is only written when the
+    /// file starts out empty.
+    pub async fn append_file(path: &Path, content: &str, encoding: TextEncoding) -> EmpathicResult<u64> {
+        use tokio::io::AsyncWriteExt;
+
+        let append_error = |e: std::io::Error| EmpathicError::FileOperationFailed {
+            operation: "append".to_string(),
+            path: path.to_path_buf(),
+            reason: e.to_string(),
+        };
+
+        let mut bytes = encoding.encode(content)
+            .map_err(|reason| EmpathicError::FileOperationFailed {
+                operation: "encode".to_string(),
+                path: path.to_path_buf(),
+                reason,
+            })?;
+
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await
+                .map_err(|e| EmpathicError::DirectoryCreationFailed {
+                    path: parent.to_path_buf(),
+                    reason: e.to_string(),
+                })?;
+        }
+
+        let existing_len = tokio::fs::metadata(path).await.map(|meta| meta.len()).unwrap_or(0);
+        if existing_len > 0 {
+            let bom_len = match encoding {
+                TextEncoding::Utf8Bom => 3,
+                TextEncoding::Utf16Le | TextEncoding::Utf16Be => 2,
+                TextEncoding::Utf8 | TextEncoding::Latin1 => 0,
+            };
+            bytes.drain(..bom_len);
+        }
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await
+            .map_err(append_error)?;
+        file.write_all(&bytes).await.map_err(append_error)?;
+        file.flush().await.map_err(append_error)?;
+
+        Ok(file.metadata().await.map_err(append_error)?.len())
+    }
+
+    /// 💾 Copy the current file to `<path>.bak`, returning the backup path
+    ///
+    /// Returns None when there is nothing to back up yet.
+    pub async fn backup_file(path: &Path) -> EmpathicResult<Option<std::path::PathBuf>> {
+        if !path.is_file() {
+            return Ok(None);
+        }
+
+        let mut backup_name = path.as_os_str().to_owned();
+        backup_name.push(".bak");
+        let backup_path = std::path::PathBuf::from(backup_name);
+
+        tokio::fs::copy(path, &backup_path).await
             .map_err(|e| EmpathicError::FileOperationFailed {
-                operation: "write".to_string(),
+                operation: "backup".to_string(),
                 path: path.to_path_buf(),
                 reason: e.to_string(),
             })?;
-        Ok(())
+        Ok(Some(backup_path))
+    }
+
+    /// Hidden sibling temp path used for atomic writes and set-aside move destinations
+    fn temp_path_for(path: &Path) -> std::path::PathBuf {
+        let file_name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.subsec_nanos())
+            .unwrap_or(0);
+        path.with_file_name(format!(".{}.{}.{}.tmp", file_name, std::process::id(), nanos))
     }
     
     /// Write file content with line-based range replacement
+    ///
+    /// The file keeps its dominant line ending (LF or CRLF), its trailing
+    /// newline and its encoding, whatever the replacement content uses.
     pub async fn write_file_range(path: &Path, content: &str, start: usize, end: Option<usize>) -> EmpathicResult<()> {
-        let existing_content = Self::read_file(path).await.unwrap_or_default();
+        let (existing_content, encoding) = Self::read_file_with_encoding(path, None).await
+            .unwrap_or_else(|_| (String::new(), TextEncoding::Utf8));
+        let final_content = Self::apply_line_range(&existing_content, content, start, end);
+        Self::write_file_encoded(path, &final_content, encoding).await
+    }
+
+    /// Compute the result of a line-range replacement without touching disk
+    pub fn apply_line_range(existing_content: &str, content: &str, start: usize, end: Option<usize>) -> String {
+        let layout = LineLayout::detect(existing_content);
         let mut lines: Vec<&str> = existing_content.lines().collect();
         
         // Extend lines if needed
@@ -81,16 +487,38 @@ impl FileOps {
             }
         }
         
-        let final_content = lines.join("\n");
-        Self::write_file(path, &final_content).await
+        layout.join(&lines)
+    }
+    
+    /// Insert `content` as whole lines before 0-indexed `line`
+    ///
+    /// Each line of `content` becomes its own line; `line: 0` prepends and any
+    /// `line` at or past the end appends. Line endings follow the existing
+    /// file. Returns the new content and the line the insertion landed on.
+    pub fn insert_lines(existing_content: &str, content: &str, line: usize) -> (String, usize) {
+        // An empty file gets a conventional trailing newline
+        let layout = LineLayout {
+            trailing_newline: existing_content.is_empty() || existing_content.ends_with('\n'),
+            ..LineLayout::detect(existing_content)
+        };
+        let mut lines: Vec<&str> = existing_content.lines().collect();
+        let position = line.min(lines.len());
+        lines.splice(position..position, content.lines());
+        (layout.join(&lines), position)
     }
     
     /// List directory contents with metadata and optional pattern matching
-    pub async fn list_files(path: &Path, recursive: bool, show_metadata: bool, pattern: Option<&str>) -> EmpathicResult<Vec<FileInfo>> {
+    ///
+    /// Recursive listings respect `.gitignore`, `.ignore` and `.empathicignore`
+    /// rules (root and nested) and skip
+    /// `skip_dirs` (VCS metadata and Cargo `target` by default) unless
+    /// `include_ignored` is set, which walks every file including hidden ones.
+    /// `max_depth` counts levels below `path` (1 = direct children).
+    pub async fn list_files(path: &Path, recursive: bool, show_metadata: bool, pattern: Option<&str>, include_ignored: bool, max_depth: Option<usize>, skip_dirs: &SkipDirs) -> EmpathicResult<Vec<FileInfo>> {
         let mut files = Vec::new();
         
         if recursive {
-            Self::list_files_recursive(path, &mut files, show_metadata, pattern).await?;
+            Self::list_files_recursive(path, &mut files, show_metadata, pattern, include_ignored, max_depth, skip_dirs.clone()).await?;
         } else {
             Self::list_files_single(path, &mut files, show_metadata, pattern).await?;
         }
@@ -111,7 +539,7 @@ impl FileOps {
             
             // Apply pattern filter if specified
             if let Some(pattern) = pattern
-                && !Self::matches_pattern(&file_info.name, pattern)?
+                && !Self::matches_pattern(&file_info.name, Path::new(&file_info.name), pattern)?
             {
                 continue;
             }
@@ -122,19 +550,29 @@ impl FileOps {
         Ok(())
     }
     
-    async fn list_files_recursive(path: &Path, files: &mut Vec<FileInfo>, show_metadata: bool, pattern: Option<&str>) -> EmpathicResult<()> {
+    async fn list_files_recursive(path: &Path, files: &mut Vec<FileInfo>, show_metadata: bool, pattern: Option<&str>, include_ignored: bool, max_depth: Option<usize>, skip_dirs: SkipDirs) -> EmpathicResult<()> {
         let path_owned = path.to_owned();
         let entries = tokio::task::spawn_blocking(move || {
             // Use ignore crate for .gitignore support 🎯
-            let walker = ignore::WalkBuilder::new(&path_owned)
-                .hidden(false)        // Show hidden files by default
-                .ignore(true)         // Respect .ignore files
-                .git_ignore(true)     // Respect .gitignore files 
-                .git_global(false)    // Don't use global git config
-                .git_exclude(false)   // Don't use .git/info/exclude
-                .require_git(false)   // Work in non-git directories
-                                .standard_filters(true) // Use standard filters for gitignore functionality
-                .build();
+            let walker = if include_ignored {
+                ignore::WalkBuilder::new(&path_owned)
+                    .standard_filters(false) // Escape hatch: walk everything
+                    .max_depth(max_depth)
+                    .build()
+            } else {
+                ignore::WalkBuilder::new(&path_owned)
+                    .hidden(false)        // Show hidden files by default
+                    .ignore(true)         // Respect .ignore files
+                    .git_ignore(true)     // Respect .gitignore files 
+                    .add_custom_ignore_filename(EMPATHIC_IGNORE) // Highest precedence
+                    .git_global(false)    // Don't use global git config
+                    .git_exclude(false)   // Don't use .git/info/exclude
+                    .require_git(false)   // Work in non-git directories
+                    .standard_filters(true) // Use standard filters for gitignore functionality
+                    .max_depth(max_depth)
+                    .filter_entry(move |entry| !skip_dirs.is_skipped(entry.path()))
+                    .build()
+            };
             
             let mut result = Vec::new();
             for entry in walker {
@@ -161,6 +599,7 @@ impl FileOps {
                 name: entry.file_name().to_string_lossy().to_string(),
                 path: entry.path().to_path_buf(),
                 is_dir: entry.file_type().is_some_and(|ft| ft.is_dir()),
+                is_symlink: entry.path_is_symlink(),
                 size: metadata.as_ref().map(|m| m.len()),
                 modified: metadata.as_ref().and_then(|m| m.modified().ok()),
                 permissions: if cfg!(unix) {
@@ -174,8 +613,9 @@ impl FileOps {
             };
             
             // Apply pattern filter if specified
+            let relative_path = entry.path().strip_prefix(path).unwrap_or(entry.path());
             if let Some(pattern) = pattern
-                && !Self::matches_pattern(&file_info.name, pattern)?
+                && !Self::matches_pattern(&file_info.name, relative_path, pattern)?
             {
                 continue;
             }
@@ -200,6 +640,7 @@ impl FileOps {
             name: entry.file_name().to_string_lossy().to_string(),
             path: entry.path(),
             is_dir: file_type.is_dir(),  // ✅ Always determine directory status correctly
+            is_symlink: file_type.is_symlink(),
             size: metadata.as_ref().map(|m| m.len()),
             modified: metadata.as_ref().and_then(|m| m.modified().ok()),
             permissions: if cfg!(unix) {
@@ -215,7 +656,8 @@ impl FileOps {
     
     /// Delete file or directory
     pub async fn delete_file(path: &Path, recursive: bool) -> EmpathicResult<()> {
-        if path.is_dir() {
+        // 🔗 A link to a directory is unlinked like a file, never emptied
+        if path.is_dir() && !path.is_symlink() {
             if recursive {
                 tokio::fs::remove_dir_all(path).await
                     .map_err(|e| EmpathicError::FileOperationFailed {
@@ -242,10 +684,259 @@ impl FileOps {
         Ok(())
     }
 
+    /// Copy a file, or a directory tree when `recursive`, returning bytes copied
+    pub async fn copy_file(from: &Path, to: &Path, recursive: bool) -> EmpathicResult<u64> {
+        if from.is_dir() {
+            if !recursive {
+                return Err(EmpathicError::FileOperationFailed {
+                    operation: "copy".to_string(),
+                    path: from.to_path_buf(),
+                    reason: "source is a directory (set recursive: true)".to_string(),
+                });
+            }
+            return Self::copy_dir_recursive(from, to).await;
+        }
+
+        if let Some(parent) = to.parent() {
+            tokio::fs::create_dir_all(parent).await
+                .map_err(|e| EmpathicError::DirectoryCreationFailed {
+                    path: parent.to_path_buf(),
+                    reason: e.to_string(),
+                })?;
+        }
+
+        tokio::fs::copy(from, to).await
+            .map_err(|e| EmpathicError::FileOperationFailed {
+                operation: "copy".to_string(),
+                path: from.to_path_buf(),
+                reason: e.to_string(),
+            })
+    }
+
+    /// Copy a directory tree; symlinks inside it are recreated, not followed
+    ///
+    /// A destination inside the source is refused, since the walk would
+    /// descend into the copy as it is being made.
+    async fn copy_dir_recursive(from: &Path, to: &Path) -> EmpathicResult<u64> {
+        let (from, to) = (from.to_path_buf(), to.to_path_buf());
+        let source = from.canonicalize()
+            .map_err(|e| EmpathicError::file_io("copy", &from, e))?;
+        if crate::config::resolve_nonexistent(&to, 0).is_some_and(|target| target.starts_with(&source)) {
+            return Err(EmpathicError::FileOperationFailed {
+                operation: "copy".to_string(),
+                path: to,
+                reason: format!("destination is inside the source directory {}", from.display()),
+            });
+        }
+
+        tokio::task::spawn_blocking(move || {
+            let mut bytes = 0;
+            for entry in walkdir::WalkDir::new(&from) {
+                let entry = entry.map_err(|e| EmpathicError::FileOperationFailed {
+                    operation: "directory walk".to_string(),
+                    path: from.clone(),
+                    reason: e.to_string(),
+                })?;
+                // Paths yielded by the walk are always under its root
+                let target = to.join(entry.path().strip_prefix(&from).unwrap_or(entry.path()));
+
+                if entry.file_type().is_dir() {
+                    std::fs::create_dir_all(&target)
+                        .map_err(|e| EmpathicError::DirectoryCreationFailed {
+                            path: target.clone(),
+                            reason: e.to_string(),
+                        })?;
+                } else if entry.file_type().is_symlink() {
+                    Self::copy_symlink(entry.path(), &target)
+                        .map_err(|e| EmpathicError::FileOperationFailed {
+                            operation: "copy symlink".to_string(),
+                            path: entry.path().to_path_buf(),
+                            reason: e.to_string(),
+                        })?;
+                } else {
+                    bytes += std::fs::copy(entry.path(), &target)
+                        .map_err(|e| EmpathicError::FileOperationFailed {
+                            operation: "copy".to_string(),
+                            path: entry.path().to_path_buf(),
+                            reason: e.to_string(),
+                        })?;
+                }
+            }
+            Ok(bytes)
+        }).await?
+    }
+
+    /// 🔗 Create a symlink at `to` pointing where the symlink `from` points
+    fn copy_symlink(from: &Path, to: &Path) -> std::io::Result<()> {
+        let target = std::fs::read_link(from)?;
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(&target, to)
+        }
+        #[cfg(windows)]
+        {
+            if from.is_dir() {
+                std::os::windows::fs::symlink_dir(&target, to)
+            } else {
+                std::os::windows::fs::symlink_file(&target, to)
+            }
+        }
+    }
     
-    /// Check if filename matches glob pattern
-    fn matches_pattern(filename: &str, pattern: &str) -> EmpathicResult<bool> {
-        use glob::Pattern;
+    /// 🚚 Move a file or directory tree, returning how it was moved
+    ///
+    /// A plain rename when source and destination share a filesystem;
+    /// otherwise (EXDEV) the tree is copied with permissions, timestamps and
+    /// symlinks preserved, then the source is removed. A failed copy removes
+    /// the partial destination and leaves the source untouched.
+    pub async fn move_file(from: &Path, to: &Path) -> EmpathicResult<MoveMethod> {
+        if let Some(parent) = to.parent() {
+            tokio::fs::create_dir_all(parent).await
+                .map_err(|e| EmpathicError::DirectoryCreationFailed {
+                    path: parent.to_path_buf(),
+                    reason: e.to_string(),
+                })?;
+        }
+
+        match tokio::fs::rename(from, to).await {
+            Ok(()) => Ok(MoveMethod::Rename),
+            Err(e) if Self::is_cross_device(&e) => {
+                log::info!("🚚 {} and {} are on different filesystems, copying instead", from.display(), to.display());
+                let bytes = Self::copy_then_delete(from, to).await?;
+                Ok(MoveMethod::CopyDelete { bytes })
+            }
+            Err(e) => Err(EmpathicError::FileOperationFailed {
+                operation: "move".to_string(),
+                path: from.to_path_buf(),
+                reason: e.to_string(),
+            }),
+        }
+    }
+
+    /// 🚚 Move `from` over an existing `to`
+    ///
+    /// The destination is renamed aside first and only deleted once the move
+    /// succeeded; if it fails, the destination is put back.
+    pub async fn move_replacing(from: &Path, to: &Path) -> EmpathicResult<MoveMethod> {
+        let aside = Self::temp_path_for(to);
+        tokio::fs::rename(to, &aside).await
+            .map_err(|e| EmpathicError::FileOperationFailed {
+                operation: "move (set destination aside)".to_string(),
+                path: to.to_path_buf(),
+                reason: e.to_string(),
+            })?;
+
+        match Self::move_file(from, to).await {
+            Ok(method) => {
+                let is_dir = tokio::fs::symlink_metadata(&aside).await.is_ok_and(|meta| meta.is_dir());
+                if let Err(e) = Self::delete_file(&aside, is_dir).await {
+                    log::warn!("⚠️ Replaced destination left at {}: {}", aside.display(), e);
+                }
+                Ok(method)
+            }
+            Err(e) => {
+                if let Err(restore) = tokio::fs::rename(&aside, to).await {
+                    log::error!("❌ Could not restore {} from {}: {}", to.display(), aside.display(), restore);
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Path from directory `from` to `to`, both absolute and normalized
+    ///
+    /// Climbs out of `from` with `..` past the common prefix. On Windows,
+    /// paths on different drives share no prefix and `to` is returned as is.
+    pub fn relative_path(from: &Path, to: &Path) -> PathBuf {
+        let from: Vec<_> = from.components().collect();
+        let to_components: Vec<_> = to.components().collect();
+        let common = from.iter().zip(&to_components).take_while(|(a, b)| a == b).count();
+        if common == 0 {
+            return to.to_path_buf();
+        }
+        let relative: PathBuf = std::iter::repeat_n(std::path::Component::ParentDir, from.len() - common)
+            .chain(to_components[common..].iter().copied())
+            .collect();
+        if relative.as_os_str().is_empty() { PathBuf::from(".") } else { relative }
+    }
+
+    /// Whether a rename failed only because it crosses filesystems
+    pub fn is_cross_device(error: &std::io::Error) -> bool {
+        error.kind() == std::io::ErrorKind::CrossesDevices
+    }
+
+    /// 📦 Cross-device move: preserve-copy `from` to `to`, then delete `from`
+    pub async fn copy_then_delete(from: &Path, to: &Path) -> EmpathicResult<u64> {
+        let (source, target) = (from.to_path_buf(), to.to_path_buf());
+        let copied = tokio::task::spawn_blocking(move || Self::copy_preserving(&source, &target)).await?;
+
+        let bytes = match copied {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                let partial = tokio::fs::symlink_metadata(to).await;
+                let _ = match partial {
+                    Ok(meta) if meta.is_dir() => tokio::fs::remove_dir_all(to).await,
+                    Ok(_) => tokio::fs::remove_file(to).await,
+                    Err(_) => Ok(()),
+                };
+                return Err(e);
+            }
+        };
+
+        let is_dir = tokio::fs::symlink_metadata(from).await.is_ok_and(|meta| meta.is_dir());
+        Self::delete_file(from, is_dir).await?;
+        Ok(bytes)
+    }
+
+    /// Copy one entry (recursing into directories) keeping mode, mtime and symlinks
+    fn copy_preserving(from: &Path, to: &Path) -> EmpathicResult<u64> {
+        let failed = |path: &Path, e: std::io::Error| EmpathicError::FileOperationFailed {
+            operation: "move (copy)".to_string(),
+            path: path.to_path_buf(),
+            reason: e.to_string(),
+        };
+        let metadata = std::fs::symlink_metadata(from).map_err(|e| failed(from, e))?;
+
+        let bytes = if metadata.file_type().is_symlink() {
+            Self::copy_symlink(from, to).map_err(|e| failed(to, e))?;
+            return Ok(0);
+        } else if metadata.is_dir() {
+            std::fs::create_dir_all(to).map_err(|e| failed(to, e))?;
+            let mut bytes = 0;
+            for entry in std::fs::read_dir(from).map_err(|e| failed(from, e))? {
+                let entry = entry.map_err(|e| failed(from, e))?;
+                bytes += Self::copy_preserving(&entry.path(), &to.join(entry.file_name()))?;
+            }
+            bytes
+        } else {
+            // std::fs::copy carries the permission bits over
+            std::fs::copy(from, to).map_err(|e| failed(from, e))?
+        };
+
+        // Directories get theirs last, after their contents stopped touching them
+        std::fs::set_permissions(to, metadata.permissions()).map_err(|e| failed(to, e))?;
+        if let Ok(modified) = metadata.modified() {
+            let times = std::fs::FileTimes::new().set_modified(modified);
+            let times = match metadata.accessed() {
+                Ok(accessed) => times.set_accessed(accessed),
+                Err(_) => times,
+            };
+            // Opening a directory for writing isn't portable; timestamps are best-effort there
+            if let Ok(file) = std::fs::File::options().write(!metadata.is_dir()).read(metadata.is_dir()).open(to) {
+                let _ = file.set_times(times);
+            }
+        }
+        Ok(bytes)
+    }
+
+    /// Check if a file matches a glob pattern
+    ///
+    /// Patterns without `/` match the file name only (`*.rs`, `test_?.txt`).
+    /// Patterns with `/` match the path relative to the listed directory, where
+    /// `*` stays within one component and `**` spans any number of directories
+    /// (`**/*.rs`, `src/*/mod.rs`).
+    fn matches_pattern(filename: &str, relative_path: &Path, pattern: &str) -> EmpathicResult<bool> {
+        use glob::{MatchOptions, Pattern};
         
         let glob_pattern = Pattern::new(pattern)
             .map_err(|e| EmpathicError::InvalidRegexPattern {
@@ -253,16 +944,598 @@ impl FileOps {
                 reason: format!("Invalid glob pattern: {}", e),
             })?;
         
-        Ok(glob_pattern.matches(filename))
+        if !pattern.contains('/') {
+            return Ok(glob_pattern.matches(filename));
+        }
+        
+        let options = MatchOptions {
+            require_literal_separator: true,
+            ..MatchOptions::default()
+        };
+        Ok(glob_pattern.matches_path_with(relative_path, options))
+    }
+}
+
+/// 🚚 How `FileOps::move_file` moved its source
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveMethod {
+    /// Same filesystem: an atomic rename
+    Rename,
+    /// Across filesystems: copied, then the source removed
+    CopyDelete { bytes: u64 },
+}
+
+/// ↩️ Line ending convention of a text file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+    Lf,
+    CrLf,
+}
+
+impl LineEnding {
+    /// Dominant ending in the content (LF when there are no line breaks or on a tie)
+    pub fn detect(content: &str) -> Self {
+        let crlf = content.matches("\r\n").count();
+        let lf = content.matches('\n').count() - crlf;
+        if crlf > lf { LineEnding::CrLf } else { LineEnding::Lf }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
     }
 }
 
+/// ↩️ How a file lays out its lines: the ending it uses and whether it ends with one
+///
+/// Edits are made on `\n`-separated text and written back through `join` or
+/// `restore`, so a CRLF file stays CRLF and the trailing newline (or its
+/// absence) survives whatever the edit's own content uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineLayout {
+    pub ending: LineEnding,
+    pub trailing_newline: bool,
+}
+
+impl LineLayout {
+    pub fn detect(content: &str) -> Self {
+        Self {
+            ending: LineEnding::detect(content),
+            trailing_newline: content.ends_with('\n'),
+        }
+    }
+
+    /// `text` with every CRLF turned into LF
+    pub fn normalize(text: &str) -> String {
+        text.replace("\r\n", "\n")
+    }
+
+    /// Join lines with the file's ending, adding its trailing newline back
+    pub fn join(&self, lines: &[&str]) -> String {
+        let mut joined = lines.join(self.ending.as_str());
+        if self.trailing_newline && !lines.is_empty() {
+            joined.push_str(self.ending.as_str());
+        }
+        joined
+    }
+
+    /// Lay edited, LF-normalized text out like the file it came from
+    pub fn restore(&self, text: &str) -> String {
+        self.join(&text.lines().collect::<Vec<_>>())
+    }
+}
+
+/// 🔤 Text encodings understood by the file tools
+///
+/// Conversion goes through `encoding_rs`; this type only maps its encodings to
+/// the names the tools expose. Detection is deliberately simple: a byte order
+/// mark decides, BOM-less text with NULs in every other byte is taken for
+/// UTF-16, valid UTF-8 stays UTF-8, and anything else falls back to Latin-1
+/// (which decodes any byte).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub enum TextEncoding {
+    #[serde(rename = "utf-8")]
+    Utf8,
+    /// UTF-8 with a leading byte order mark (kept on write)
+    #[serde(rename = "utf-8-bom")]
+    Utf8Bom,
+    #[serde(rename = "utf-16le")]
+    Utf16Le,
+    #[serde(rename = "utf-16be")]
+    Utf16Be,
+    #[serde(rename = "latin1")]
+    Latin1,
+}
+
+impl TextEncoding {
+    /// Parse a user-supplied encoding name (case-insensitive, any WHATWG label
+    /// of a supported encoding)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().replace('_', "-").as_str() {
+            "utf-8-bom" | "utf8-bom" | "utf-8-sig" => Some(Self::Utf8Bom),
+            // WHATWG folds ISO-8859-1 into windows-1252; the tools mean real Latin-1
+            label => match encoding_rs::Encoding::for_label(label.as_bytes())? {
+                encoding if encoding == encoding_rs::UTF_8 => Some(Self::Utf8),
+                encoding if encoding == encoding_rs::UTF_16LE => Some(Self::Utf16Le),
+                encoding if encoding == encoding_rs::UTF_16BE => Some(Self::Utf16Be),
+                encoding if encoding == encoding_rs::WINDOWS_1252 => Some(Self::Latin1),
+                _ => None,
+            },
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Utf8 => "utf-8",
+            Self::Utf8Bom => "utf-8-bom",
+            Self::Utf16Le => "utf-16le",
+            Self::Utf16Be => "utf-16be",
+            Self::Latin1 => "latin1",
+        }
+    }
+
+    /// Encoding announced by a byte order mark, if any
+    pub fn from_bom(bytes: &[u8]) -> Option<Self> {
+        let (encoding, _) = encoding_rs::Encoding::for_bom(bytes)?;
+        Some(match encoding {
+            encoding if encoding == encoding_rs::UTF_16LE => Self::Utf16Le,
+            encoding if encoding == encoding_rs::UTF_16BE => Self::Utf16Be,
+            _ => Self::Utf8Bom,
+        })
+    }
+
+    /// Best guess for raw file bytes
+    pub fn detect(bytes: &[u8]) -> Self {
+        if let Some(encoding) = Self::from_bom(bytes) {
+            return encoding;
+        }
+
+        // BOM-less UTF-16: mostly-ASCII text leaves the high byte of each unit
+        // NUL, which is still valid UTF-8, so check this first
+        if bytes.len() >= 2 && bytes.len().is_multiple_of(2) {
+            let units = bytes.len() / 2;
+            let even_nuls = bytes.iter().step_by(2).filter(|b| **b == 0).count();
+            let odd_nuls = bytes.iter().skip(1).step_by(2).filter(|b| **b == 0).count();
+            if odd_nuls * 2 > units && even_nuls == 0 {
+                return Self::Utf16Le;
+            }
+            if even_nuls * 2 > units && odd_nuls == 0 {
+                return Self::Utf16Be;
+            }
+        }
+
+        if encoding_rs::Encoding::utf8_valid_up_to(bytes) == bytes.len() {
+            return Self::Utf8;
+        }
+        Self::Latin1
+    }
+
+    /// Decode bytes to UTF-8 text (a matching BOM is stripped)
+    pub fn decode(&self, bytes: &[u8]) -> Result<String, String> {
+        let encoding = match self {
+            Self::Utf8 | Self::Utf8Bom => encoding_rs::UTF_8,
+            Self::Utf16Le => encoding_rs::UTF_16LE,
+            Self::Utf16Be => encoding_rs::UTF_16BE,
+            Self::Latin1 => return Ok(encoding_rs::mem::decode_latin1(bytes).into_owned()),
+        };
+        let bytes = match encoding_rs::Encoding::for_bom(bytes) {
+            Some((bom_encoding, bom_length)) if bom_encoding == encoding => &bytes[bom_length..],
+            _ => bytes,
+        };
+        encoding.decode_without_bom_handling_and_without_replacement(bytes)
+            .map(|text| text.into_owned())
+            .ok_or_else(|| match self {
+                Self::Utf8 | Self::Utf8Bom => "not valid UTF-8; pass an explicit encoding such as latin1".to_string(),
+                _ => format!("not valid {}", self.name()),
+            })
+    }
+
+    /// Encode UTF-8 text back to this encoding (BOM encodings write their BOM)
+    pub fn encode(&self, text: &str) -> Result<Vec<u8>, String> {
+        match self {
+            Self::Utf8 => Ok(text.as_bytes().to_vec()),
+            Self::Utf8Bom => {
+                let mut bytes = vec![0xEF, 0xBB, 0xBF];
+                bytes.extend_from_slice(text.as_bytes());
+                Ok(bytes)
+            }
+            // encoding_rs only decodes UTF-16 (WHATWG encoders never emit it)
+            Self::Utf16Le | Self::Utf16Be => {
+                let mut bytes = if *self == Self::Utf16Le { vec![0xFF, 0xFE] } else { vec![0xFE, 0xFF] };
+                for unit in text.encode_utf16() {
+                    let pair = if *self == Self::Utf16Le { unit.to_le_bytes() } else { unit.to_be_bytes() };
+                    bytes.extend_from_slice(&pair);
+                }
+                Ok(bytes)
+            }
+            Self::Latin1 => match text.chars().find(|c| u32::from(*c) > 0xFF) {
+                Some(c) => Err(format!("character '{}' cannot be encoded as latin1", c)),
+                None => Ok(encoding_rs::mem::encode_latin1_lossy(text).into_owned()),
+            },
+        }
+    }
+}
+
+/// 📄 Slice of a file returned by `FileOps::read_file_range`
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct FileRange {
+    #[serde(skip)]
+    pub content: String,
+    /// First line returned (1-based)
+    pub start_line: usize,
+    /// Last line returned, possibly partial when truncated (start_line - 1 if none)
+    pub end_line: usize,
+    pub total_lines: usize,
+    pub bytes: usize,
+    pub truncated: bool,
+    pub encoding: TextEncoding,
+}
+
+/// 📦 Byte range of a file returned by `FileOps::read_file_bytes`
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct FileChunk {
+    #[serde(skip)]
+    pub content: String,
+    /// Byte offset the content starts at
+    pub offset: u64,
+    /// Bytes of the file the content covers
+    pub length: usize,
+    pub total_size: u64,
+    /// Offset to pass for the next chunk (total_size once the end is reached)
+    pub next_offset: u64,
+    pub eof: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct FileInfo {
     pub name: String,
     pub path: std::path::PathBuf,
     pub is_dir: bool,
+    pub is_symlink: bool,
     pub size: Option<u64>,
     pub modified: Option<std::time::SystemTime>,
     pub permissions: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(relative_path: &str, pattern: &str) -> bool {
+        let path = Path::new(relative_path);
+        let filename = path.file_name().unwrap().to_string_lossy();
+        FileOps::matches_pattern(&filename, path, pattern).unwrap()
+    }
+
+    #[test]
+    fn test_simple_patterns_match_file_name() {
+        assert!(matches("src/deep/main.rs", "*.rs"));
+        assert!(matches("README.md", "README*"));
+        assert!(matches("src/test_a.txt", "test_?.txt"));
+        assert!(!matches("src/main.rs", "*.txt"));
+    }
+
+    #[test]
+    fn test_double_star_matches_any_depth() {
+        assert!(matches("main.rs", "**/*.rs"));
+        assert!(matches("src/lsp/manager/core.rs", "**/*.rs"));
+        assert!(matches("src/lsp/manager/mod.rs", "src/**/mod.rs"));
+        assert!(!matches("tests/mod.rs", "src/**/mod.rs"));
+    }
+
+    #[test]
+    fn test_single_star_stays_in_one_directory() {
+        assert!(matches("src/lsp/mod.rs", "src/*/mod.rs"));
+        assert!(!matches("src/lsp/manager/mod.rs", "src/*/mod.rs"));
+        assert!(!matches("src/mod.rs", "src/*/mod.rs"));
+    }
+
+    #[test]
+    fn test_character_classes() {
+        assert!(matches("src/a1.rs", "[ab][0-9].rs"));
+        assert!(!matches("src/c1.rs", "[ab][0-9].rs"));
+        assert!(matches("src/c1.rs", "[!ab]1.rs"));
+        assert!(matches("tests/lsp_hover_tests.rs", "tests/lsp_[a-h]*.rs"));
+    }
+
+    #[tokio::test]
+    async fn test_list_files_skip_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        for sub in ["target", "venv/lib", "pkg.egg-info", "src"] {
+            std::fs::create_dir_all(dir.path().join(sub)).unwrap();
+        }
+        for file in ["target/out.txt", "venv/lib/site.py", "pkg.egg-info/PKG-INFO", "src/main.py"] {
+            std::fs::write(dir.path().join(file), "x").unwrap();
+        }
+        let names = |files: Vec<FileInfo>| -> Vec<String> {
+            let mut names: Vec<String> = files.iter()
+                .filter(|f| !f.is_dir)
+                .map(|f| f.path.strip_prefix(dir.path()).unwrap().to_string_lossy().to_string())
+                .collect();
+            names.sort();
+            names
+        };
+
+        // 📦 No Cargo.toml, so `target` is an ordinary directory
+        let files = FileOps::list_files(dir.path(), true, false, None, false, None, &SkipDirs::default()).await.unwrap();
+        assert_eq!(names(files), vec!["pkg.egg-info/PKG-INFO", "src/main.py", "target/out.txt", "venv/lib/site.py"]);
+
+        let extra = SkipDirs::new(None, &["venv".to_string(), "*.egg-info/".to_string()]).unwrap();
+        let files = FileOps::list_files(dir.path(), true, false, None, false, None, &extra).await.unwrap();
+        assert_eq!(names(files), vec!["src/main.py", "target/out.txt"]);
+
+        std::fs::write(dir.path().join("Cargo.toml"), "").unwrap();
+        let files = FileOps::list_files(dir.path(), true, false, None, false, None, &SkipDirs::default()).await.unwrap();
+        assert!(!names(files).contains(&"target/out.txt".to_string()));
+
+        let replaced = SkipDirs::new(Some(&["src".to_string()]), &[]).unwrap();
+        let files = FileOps::list_files(dir.path(), true, false, None, false, None, &replaced).await.unwrap();
+        assert!(names(files).contains(&"target/out.txt".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_read_file_range() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("lines.txt");
+        std::fs::write(&file, "one\ntwo\nthree\nfour\n").unwrap();
+
+        let range = FileOps::read_file_range(&file, 2, Some(3), None, None).await.unwrap();
+        assert_eq!(range.content, "two\nthree");
+        assert_eq!((range.start_line, range.end_line, range.total_lines), (2, 3, 4));
+        assert!(!range.truncated);
+
+        let past_end = FileOps::read_file_range(&file, 3, Some(99), None, None).await.unwrap();
+        assert_eq!(past_end.content, "three\nfour");
+        assert_eq!(past_end.end_line, 4);
+
+        let beyond = FileOps::read_file_range(&file, 10, None, None, None).await.unwrap();
+        assert!(beyond.content.is_empty());
+        assert_eq!(beyond.end_line, 9);
+    }
+
+    #[tokio::test]
+    async fn test_read_file_bytes_pages_on_char_boundaries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("log.txt");
+        // "ab" then 'é' (2 bytes) then '🦀' (4 bytes): 8 bytes in total
+        std::fs::write(&path, "abé🦀").unwrap();
+
+        let chunk = FileOps::read_file_bytes(&path, 0, 3).await.unwrap();
+        assert_eq!((chunk.content.as_str(), chunk.next_offset, chunk.total_size, chunk.eof), ("ab", 2, 8, false));
+
+        let chunk = FileOps::read_file_bytes(&path, 2, 5).await.unwrap();
+        assert_eq!((chunk.content.as_str(), chunk.length, chunk.next_offset), ("é", 2, 4));
+
+        let chunk = FileOps::read_file_bytes(&path, 4, 100).await.unwrap();
+        assert_eq!((chunk.content.as_str(), chunk.eof), ("🦀", true));
+
+        // An offset inside a character starts at the next one
+        let chunk = FileOps::read_file_bytes(&path, 3, 100).await.unwrap();
+        assert_eq!((chunk.content.as_str(), chunk.offset), ("🦀", 4));
+
+        let chunk = FileOps::read_file_bytes(&path, 50, 10).await.unwrap();
+        assert_eq!((chunk.content.as_str(), chunk.offset, chunk.next_offset, chunk.eof), ("", 8, 8, true));
+    }
+
+    #[tokio::test]
+    async fn test_read_file_range_max_bytes_respects_char_boundaries() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("unicode.txt");
+        std::fs::write(&file, "héllo\nwörld\n").unwrap();
+
+        // "h" + 2-byte "é" would be split at 2 bytes
+        let range = FileOps::read_file_range(&file, 1, None, Some(2), None).await.unwrap();
+        assert_eq!(range.content, "h");
+        assert!(range.truncated);
+        assert_eq!(range.end_line, 1);
+
+        let spans_lines = FileOps::read_file_range(&file, 1, None, Some(9), None).await.unwrap();
+        assert_eq!(spans_lines.content, "héllo\nw");
+        assert_eq!(spans_lines.end_line, 2);
+    }
+
+    #[tokio::test]
+    async fn test_write_file_is_atomic_and_keeps_permissions() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("script.sh");
+        std::fs::write(&file, "old").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&file, std::fs::Permissions::from_mode(0o750)).unwrap();
+        }
+
+        FileOps::write_file(&file, "new").await.unwrap();
+
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "new");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&file).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o750);
+        }
+        // No temp files left behind
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_backup_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("config.toml");
+
+        assert!(FileOps::backup_file(&file).await.unwrap().is_none());
+
+        std::fs::write(&file, "original").unwrap();
+        let backup = FileOps::backup_file(&file).await.unwrap().unwrap();
+        assert_eq!(backup, dir.path().join("config.toml.bak"));
+        assert_eq!(std::fs::read_to_string(backup).unwrap(), "original");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_copy_then_delete_preserves_metadata() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempfile::tempdir().unwrap();
+        let from = dir.path().join("pkg");
+        std::fs::create_dir_all(from.join("bin")).unwrap();
+        std::fs::write(from.join("bin/run.sh"), "#!/bin/sh").unwrap();
+        std::fs::set_permissions(from.join("bin/run.sh"), std::fs::Permissions::from_mode(0o751)).unwrap();
+        std::os::unix::fs::symlink("bin/run.sh", from.join("run")).unwrap();
+        let mtime = std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_600_000_000);
+        std::fs::File::options().write(true).open(from.join("bin/run.sh")).unwrap()
+            .set_modified(mtime).unwrap();
+
+        let to = dir.path().join("moved/pkg");
+        std::fs::create_dir_all(to.parent().unwrap()).unwrap();
+        let bytes = FileOps::copy_then_delete(&from, &to).await.unwrap();
+
+        assert_eq!(bytes, 9);
+        assert!(!from.exists());
+        let script = std::fs::metadata(to.join("bin/run.sh")).unwrap();
+        assert_eq!(script.permissions().mode() & 0o777, 0o751);
+        assert_eq!(script.modified().unwrap(), mtime);
+        assert_eq!(std::fs::read_link(to.join("run")).unwrap(), Path::new("bin/run.sh"));
+    }
+
+    #[test]
+    fn test_cross_device_error_detection() {
+        #[cfg(target_os = "linux")]
+        assert!(FileOps::is_cross_device(&std::io::Error::from_raw_os_error(18)));
+        assert!(!FileOps::is_cross_device(&std::io::Error::from(std::io::ErrorKind::NotFound)));
+    }
+
+    #[test]
+    fn test_insert_lines_prepend_append_and_multiline() {
+        assert_eq!(FileOps::insert_lines("b\nc\n", "a", 0), ("a\nb\nc\n".to_string(), 0));
+        assert_eq!(FileOps::insert_lines("a\nd\n", "b\nc\n", 1), ("a\nb\nc\nd\n".to_string(), 1));
+        // Past the end appends, without padding blank lines
+        assert_eq!(FileOps::insert_lines("a\nb", "c", 10), ("a\nb\nc".to_string(), 2));
+        assert_eq!(FileOps::insert_lines("a\r\nb\r\n", "x\ny", 1), ("a\r\nx\r\ny\r\nb\r\n".to_string(), 1));
+        assert_eq!(FileOps::insert_lines("", "first", 3), ("first\n".to_string(), 0));
+    }
+
+    #[test]
+    fn test_fence_and_number_lines() {
+        assert_eq!(FileOps::fence_language(Path::new("src/main.rs")), "rust");
+        assert_eq!(FileOps::fence_language(Path::new("app.PY")), "python");
+        assert_eq!(FileOps::fence_language(Path::new("Makefile")), "makefile");
+        assert_eq!(FileOps::fence_language(Path::new("notes.unknown")), "");
+
+        assert_eq!(FileOps::fence("fn main() {}\n", "rust"), "```rust\nfn main() {}\n```");
+        assert_eq!(FileOps::fence("a ```b``` c", "markdown"), "````markdown\na ```b``` c\n````");
+
+        assert_eq!(FileOps::number_lines("a\nb", 9), " 9 | a\n10 | b");
+    }
+
+    #[test]
+    fn test_relative_path() {
+        assert_eq!(FileOps::relative_path(Path::new("/p/links"), Path::new("/p/src/lib.rs")), PathBuf::from("../src/lib.rs"));
+        assert_eq!(FileOps::relative_path(Path::new("/p"), Path::new("/p/src")), PathBuf::from("src"));
+        assert_eq!(FileOps::relative_path(Path::new("/p/a/b"), Path::new("/p")), PathBuf::from("../.."));
+        assert_eq!(FileOps::relative_path(Path::new("/p"), Path::new("/p")), PathBuf::from("."));
+    }
+
+    #[test]
+    fn test_line_ending_detection() {
+        assert_eq!(LineEnding::detect("a\r\nb\r\nc\n"), LineEnding::CrLf);
+        assert_eq!(LineEnding::detect("a\nb\r\nc\n"), LineEnding::Lf);
+        assert_eq!(LineEnding::detect("no breaks"), LineEnding::Lf);
+    }
+
+    #[test]
+    fn test_line_layout_restore() {
+        let crlf = LineLayout::detect("a\r\nb\r\n");
+        assert_eq!(crlf.restore(&LineLayout::normalize("a\r\nB\nc")), "a\r\nB\r\nc\r\n");
+        let unterminated = LineLayout::detect("a\nb");
+        assert_eq!(unterminated.restore("a\nB\n"), "a\nB");
+        // Blank lines the edit leaves at the end are kept
+        assert_eq!(crlf.restore("a\n\n"), "a\r\n\r\n");
+    }
+
+    #[tokio::test]
+    async fn test_write_file_range_preserves_crlf_and_trailing_newline() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("windows.txt");
+        std::fs::write(&file, "one\r\ntwo\r\nthree\r\n").unwrap();
+
+        FileOps::write_file_range(&file, "TWO\nTWO-B", 1, Some(2)).await.unwrap();
+
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "one\r\nTWO\r\nTWO-B\r\nthree\r\n");
+    }
+
+    #[tokio::test]
+    async fn test_write_file_range_keeps_missing_trailing_newline() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("unix.txt");
+        std::fs::write(&file, "one\ntwo").unwrap();
+
+        FileOps::write_file_range(&file, "uno", 0, Some(1)).await.unwrap();
+
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "uno\ntwo");
+    }
+
+    #[tokio::test]
+    async fn test_binary_sniff() {
+        let dir = tempfile::tempdir().unwrap();
+        let text = dir.path().join("text.rs");
+        let binary = dir.path().join("lib.so");
+        let late_nul = dir.path().join("late.bin");
+        std::fs::write(&text, "fn main() { println!(\"héllo\"); }").unwrap();
+        std::fs::write(&binary, [0x7f, b'E', b'L', b'F', 0, 1, 2]).unwrap();
+        // NUL bytes past the sniff window are not inspected
+        let mut late = vec![b'a'; BINARY_SNIFF_BYTES];
+        late.push(0);
+        std::fs::write(&late_nul, late).unwrap();
+
+        assert!(!FileOps::is_binary(&text).await.unwrap());
+        assert!(FileOps::is_binary(&binary).await.unwrap());
+        assert!(!FileOps::is_binary(&late_nul).await.unwrap());
+    }
+
+    #[test]
+    fn test_invalid_pattern_is_an_error() {
+        assert!(FileOps::matches_pattern("a.rs", Path::new("a.rs"), "[").is_err());
+    }
+
+    #[test]
+    fn test_encoding_detection() {
+        assert_eq!(TextEncoding::detect(b"plain ascii"), TextEncoding::Utf8);
+        assert_eq!(TextEncoding::detect(b"\xEF\xBB\xBFbom"), TextEncoding::Utf8Bom);
+        assert_eq!(TextEncoding::detect(b"\xFF\xFEh\0i\0"), TextEncoding::Utf16Le);
+        assert_eq!(TextEncoding::detect(b"\0h\0i\0!"), TextEncoding::Utf16Be);
+        assert_eq!(TextEncoding::detect(b"caf\xE9"), TextEncoding::Latin1);
+        assert_eq!(TextEncoding::from_name("ISO-8859-1"), Some(TextEncoding::Latin1));
+        assert_eq!(TextEncoding::from_name("ebcdic"), None);
+        assert_eq!(TextEncoding::from_name("UTF-16"), Some(TextEncoding::Utf16Le));
+        assert_eq!(TextEncoding::from_name("utf_8_sig"), Some(TextEncoding::Utf8Bom));
+        // A real label, but not one the tools can write back
+        assert_eq!(TextEncoding::from_name("shift_jis"), None);
+    }
+
+    #[test]
+    fn test_encoding_round_trip() {
+        for encoding in [TextEncoding::Utf8Bom, TextEncoding::Utf16Le, TextEncoding::Utf16Be, TextEncoding::Latin1] {
+            let bytes = encoding.encode("café\n").unwrap();
+            assert_eq!(TextEncoding::detect(&bytes), encoding);
+            assert_eq!(encoding.decode(&bytes).unwrap(), "café\n");
+        }
+        assert!(TextEncoding::Latin1.encode("🦀").is_err());
+        assert!(TextEncoding::Utf8.decode(b"caf\xE9").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_write_file_range_keeps_encoding() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("legacy.txt");
+        std::fs::write(&file, b"caf\xE9\nna\xEFve\n").unwrap();
+
+        FileOps::write_file_range(&file, "r\u{e9}sum\u{e9}", 1, Some(2)).await.unwrap();
+
+        assert_eq!(std::fs::read(&file).unwrap(), b"caf\xE9\nr\xE9sum\xE9\n");
+        let (content, encoding) = FileOps::read_file_with_encoding(&file, None).await.unwrap();
+        assert_eq!(content, "café\nrésumé\n");
+        assert_eq!(encoding, TextEncoding::Latin1);
+    }
+}
@@ -1,9 +1,15 @@
 pub mod config;
+pub mod config_file;
 pub mod error;
 pub mod fs;
+pub mod limiter;
+pub mod logging;
 pub mod lsp;
 pub mod mcp;
+pub mod prompts;
 pub mod tools;
+pub mod trash;
+pub mod watcher;
 
 pub use config::Config;
 pub use error::{EmpathicError, EmpathicResult};
@@ -0,0 +1,260 @@
+//! 🚦 Tool Limiter - Bounds how many tool calls execute at once
+//!
+//! Every `tools/call` takes a global permit and, for tools with their own
+//! limit, a per-tool permit before executing; excess calls wait in FIFO order
+//! (tokio semaphores are fair). Heavy build tools default to one or two
+//! concurrent runs so a burst of `cargo build` calls can't exhaust the host,
+//! while cheap tools only share the global limit.
+
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::error::{EmpathicError, EmpathicResult};
+
+/// Default cap on tool calls executing at once
+pub const DEFAULT_MAX_CONCURRENT_TOOLS: usize = 8;
+
+/// Default per-tool caps for tools that spawn heavy processes
+pub const DEFAULT_TOOL_LIMITS: &[(&str, usize)] = &[
+    ("cargo", 2),
+    ("gradle", 1),
+    ("make", 2),
+    ("npm", 2),
+];
+
+/// Tools that report on the limiter itself and must never queue behind it
+const EXEMPT_TOOLS: &[&str] = &["tool_queue"];
+
+/// A semaphore with counters for waiting and running calls
+#[derive(Debug)]
+struct Gate {
+    limit: usize,
+    semaphore: Arc<Semaphore>,
+    in_flight: Arc<AtomicUsize>,
+    queued: Arc<AtomicUsize>,
+}
+
+impl Gate {
+    fn new(limit: usize) -> Arc<Self> {
+        Arc::new(Self {
+            limit,
+            semaphore: Arc::new(Semaphore::new(limit)),
+            in_flight: Arc::new(AtomicUsize::new(0)),
+            queued: Arc::new(AtomicUsize::new(0)),
+        })
+    }
+
+    async fn enter(&self) -> (OwnedSemaphorePermit, Counted) {
+        let waiting = Counted::new(&self.queued);
+        let permit = Arc::clone(&self.semaphore)
+            .acquire_owned()
+            .await
+            .expect("tool limiter semaphores are never closed");
+        drop(waiting);
+        (permit, Counted::new(&self.in_flight))
+    }
+
+    fn stats(&self) -> GateStats {
+        GateStats {
+            limit: self.limit,
+            in_flight: self.in_flight.load(Ordering::Relaxed),
+            queued: self.queued.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Increments a counter while alive, so cancelled waits are uncounted too
+#[derive(Debug)]
+struct Counted(Arc<AtomicUsize>);
+
+impl Counted {
+    fn new(counter: &Arc<AtomicUsize>) -> Self {
+        counter.fetch_add(1, Ordering::Relaxed);
+        Self(Arc::clone(counter))
+    }
+}
+
+impl Drop for Counted {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// 🎫 Held while a tool executes; dropping it frees the slot
+#[derive(Debug)]
+pub struct ToolPermit {
+    _held: Vec<(OwnedSemaphorePermit, Counted)>,
+}
+
+/// 📊 Limit and current load of one gate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct GateStats {
+    pub limit: usize,
+    /// Calls holding a permit
+    pub in_flight: usize,
+    /// Calls waiting for a permit
+    pub queued: usize,
+}
+
+/// 📊 Snapshot of the limiter
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LimiterStats {
+    /// All tool calls, whatever their per-tool limit
+    pub global: GateStats,
+    /// Tools with their own limit
+    pub per_tool: BTreeMap<String, GateStats>,
+}
+
+/// 🚦 Global and per-tool concurrency limits shared by all tool calls
+#[derive(Debug)]
+pub struct ToolLimiter {
+    global: Arc<Gate>,
+    per_tool: HashMap<String, Arc<Gate>>,
+}
+
+impl ToolLimiter {
+    /// Create a limiter; a zero limit is raised to one
+    pub fn new(max_concurrent: usize, tool_limits: &[(String, usize)]) -> Self {
+        Self {
+            global: Gate::new(max_concurrent.max(1)),
+            per_tool: tool_limits.iter()
+                .map(|(name, limit)| (name.clone(), Gate::new((*limit).max(1))))
+                .collect(),
+        }
+    }
+
+    /// Default limits with `overrides` (from TOOL_CONCURRENCY) replacing per-tool entries
+    pub fn with_overrides(max_concurrent: usize, overrides: &[(String, usize)]) -> Self {
+        let mut limits: BTreeMap<String, usize> = DEFAULT_TOOL_LIMITS.iter()
+            .map(|(name, limit)| (name.to_string(), *limit))
+            .collect();
+        limits.extend(overrides.iter().cloned());
+        Self::new(max_concurrent, &limits.into_iter().collect::<Vec<_>>())
+    }
+
+    /// Parse `name=N` pairs such as `cargo=1,gradle=1`
+    pub fn parse_tool_limits(entries: &[String], field: &str) -> EmpathicResult<Vec<(String, usize)>> {
+        entries.iter()
+            .map(|entry| entry.trim())
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                entry.split_once('=')
+                    .and_then(|(name, limit)| Some((name.trim().to_string(), limit.trim().parse::<usize>().ok()?)))
+                    .filter(|(name, limit)| !name.is_empty() && *limit > 0)
+                    .ok_or_else(|| EmpathicError::InvalidConfigValue {
+                        field: field.to_string(),
+                        value: format!("{entry} (expected tool=N with N >= 1)"),
+                    })
+            })
+            .collect()
+    }
+
+    /// ⏳ Wait for a slot for `tool_name`
+    ///
+    /// The per-tool permit is taken first, so a queued `cargo` call doesn't
+    /// hold one of the global slots cheap tools could use meanwhile.
+    pub async fn acquire(&self, tool_name: &str) -> ToolPermit {
+        let mut held = Vec::with_capacity(2);
+        if EXEMPT_TOOLS.contains(&tool_name) {
+            return ToolPermit { _held: held };
+        }
+        if let Some(gate) = self.per_tool.get(tool_name) {
+            if gate.semaphore.available_permits() == 0 {
+                log::info!("🚦 {} queued: its limit of {} concurrent calls is reached", tool_name, gate.limit);
+            }
+            held.push(gate.enter().await);
+        }
+        if self.global.semaphore.available_permits() == 0 {
+            log::info!("🚦 {} queued: all {} tool slots busy", tool_name, self.global.limit);
+        }
+        held.push(self.global.enter().await);
+        ToolPermit { _held: held }
+    }
+
+    pub fn max_concurrent(&self) -> usize {
+        self.global.limit
+    }
+
+    /// Per-tool limit, if `tool_name` has one
+    pub fn tool_limit(&self, tool_name: &str) -> Option<usize> {
+        self.per_tool.get(tool_name).map(|gate| gate.limit)
+    }
+
+    /// 📊 Current in-flight and queued counts
+    ///
+    /// `global.queued` counts every waiting call, including those still
+    /// waiting on their per-tool limit.
+    pub fn stats(&self) -> LimiterStats {
+        let per_tool: BTreeMap<String, GateStats> = self.per_tool.iter()
+            .map(|(name, gate)| (name.clone(), gate.stats()))
+            .collect();
+        let mut global = self.global.stats();
+        global.queued += per_tool.values().map(|stats| stats.queued).sum::<usize>();
+        LimiterStats { global, per_tool }
+    }
+}
+
+impl Default for ToolLimiter {
+    fn default() -> Self {
+        Self::with_overrides(DEFAULT_MAX_CONCURRENT_TOOLS, &[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_per_tool_limit_queues_excess_calls() {
+        let limiter = Arc::new(ToolLimiter::new(4, &[("cargo".to_string(), 1)]));
+        let first = limiter.acquire("cargo").await;
+
+        let waiting = tokio::spawn({
+            let limiter = Arc::clone(&limiter);
+            async move { limiter.acquire("cargo").await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        // Cheap tools still run while cargo is queued
+        let other = limiter.acquire("read_file").await;
+        let stats = limiter.stats();
+        assert_eq!(stats.per_tool["cargo"], GateStats { limit: 1, in_flight: 1, queued: 1 });
+        assert_eq!(stats.global, GateStats { limit: 4, in_flight: 2, queued: 1 });
+
+        drop(first);
+        let second = tokio::time::timeout(Duration::from_secs(1), waiting).await.unwrap().unwrap();
+        assert_eq!(limiter.stats().per_tool["cargo"].queued, 0);
+        drop((second, other));
+        assert_eq!(limiter.stats().global.in_flight, 0);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_wait_leaves_queue() {
+        let limiter = ToolLimiter::new(1, &[]);
+        let _held = limiter.acquire("shell").await;
+        assert!(tokio::time::timeout(Duration::from_millis(20), limiter.acquire("shell")).await.is_err());
+        assert_eq!(limiter.stats().global, GateStats { limit: 1, in_flight: 1, queued: 0 });
+
+        // Status reporting never queues
+        let _status = limiter.acquire("tool_queue").await;
+    }
+
+    #[test]
+    fn test_parse_tool_limits() {
+        let parsed = ToolLimiter::parse_tool_limits(&["cargo=1".to_string(), " npm = 3 ".to_string()], "TOOL_CONCURRENCY").unwrap();
+        assert_eq!(parsed, vec![("cargo".to_string(), 1), ("npm".to_string(), 3)]);
+        for bad in ["cargo", "cargo=0", "=2", "cargo=many"] {
+            assert!(ToolLimiter::parse_tool_limits(&[bad.to_string()], "TOOL_CONCURRENCY").is_err(), "{bad}");
+        }
+
+        let limiter = ToolLimiter::with_overrides(8, &parsed);
+        assert_eq!(limiter.tool_limit("cargo"), Some(1));
+        assert_eq!(limiter.tool_limit("gradle"), Some(1));
+        assert_eq!(limiter.tool_limit("npm"), Some(3));
+        assert_eq!(limiter.tool_limit("read_file"), None);
+    }
+}
@@ -0,0 +1,281 @@
+//! 📝 Logging - Plain text or one JSON object per line
+//!
+//! `LOG_FORMAT=json` (or `log_format = "json"`) switches both the `log`
+//! records (formatted by env_logger in `main`) and the server's own stderr
+//! lines to JSON objects with `timestamp`, `level`, `target` and `message`;
+//! key-value fields given as `log::info!(path = %p; "...")` land under
+//! `fields`. Output still goes to stderr (and LOGFILE), never stdout.
+//!
+//! `ReloadableLogger` wraps env_logger so MCP `logging/setLevel` can replace
+//! the RUST_LOG filter at runtime; once a client has set a level, records at
+//! or above it are also sent to it as `notifications/message`.
+
+use serde_json::{json, Map, Value};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::mcp::protocol::JsonRpcNotification;
+
+static JSON_FORMAT: AtomicBool = AtomicBool::new(false);
+
+/// Level set through `logging/setLevel` as a `LevelFilter` index; `usize::MAX` = not set
+static CLIENT_LEVEL: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+/// Channel to the client for `notifications/message`, set with the level
+static CLIENT_SINK: OnceLock<Mutex<Option<UnboundedSender<JsonRpcNotification>>>> = OnceLock::new();
+
+/// Syslog severities accepted by `logging/setLevel`, most verbose first
+pub const MCP_LEVELS: &[&str] = &["debug", "info", "notice", "warning", "error", "critical", "alert", "emergency"];
+
+/// 🧾 Log line format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl LogFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "text" | "plain" => Some(Self::Text),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Select the format for lines written outside the `log` crate
+pub fn set_format(format: LogFormat) {
+    JSON_FORMAT.store(format == LogFormat::Json, Ordering::Relaxed);
+}
+
+pub fn is_json() -> bool {
+    JSON_FORMAT.load(Ordering::Relaxed)
+}
+
+/// 🧾 Render one log line as a JSON object (no trailing newline)
+pub fn json_line(level: &str, target: &str, message: &str, fields: Map<String, Value>) -> String {
+    let mut line = Map::new();
+    line.insert("timestamp".to_string(), Value::String(
+        chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+    ));
+    line.insert("level".to_string(), Value::String(level.to_uppercase()));
+    line.insert("target".to_string(), Value::String(target.to_string()));
+    line.insert("message".to_string(), Value::String(message.to_string()));
+    if !fields.is_empty() {
+        line.insert("fields".to_string(), Value::Object(fields));
+    }
+    Value::Object(line).to_string()
+}
+
+/// 🧾 Render a `log` record, including its key-value fields
+pub fn format_record(record: &log::Record) -> String {
+    json_line(
+        record.level().as_str(),
+        record.target(),
+        &record.args().to_string(),
+        record_fields(record),
+    )
+}
+
+/// Key-value fields attached to a record, keeping numbers and booleans typed
+pub fn record_fields(record: &log::Record) -> Map<String, Value> {
+    struct Collect(Map<String, Value>);
+
+    impl<'kvs> log::kv::VisitSource<'kvs> for Collect {
+        fn visit_pair(&mut self, key: log::kv::Key<'kvs>, value: log::kv::Value<'kvs>) -> Result<(), log::kv::Error> {
+            let value = if let Some(value) = value.to_bool() {
+                Value::Bool(value)
+            } else if let Some(value) = value.to_i64() {
+                Value::from(value)
+            } else if let Some(value) = value.to_u64() {
+                Value::from(value)
+            } else if let Some(value) = value.to_f64() {
+                Value::from(value)
+            } else {
+                Value::String(value.to_string())
+            };
+            self.0.insert(key.to_string(), value);
+            Ok(())
+        }
+    }
+
+    let mut fields = Collect(Map::new());
+    let _ = record.key_values().visit(&mut fields);
+    fields.0
+}
+
+/// `log` filter for an MCP level; the `log` crate has no notice/critical/alert/emergency
+pub fn level_filter_for(mcp_level: &str) -> Option<log::LevelFilter> {
+    Some(match mcp_level {
+        "debug" => log::LevelFilter::Debug,
+        "info" | "notice" => log::LevelFilter::Info,
+        "warning" => log::LevelFilter::Warn,
+        "error" | "critical" | "alert" | "emergency" => log::LevelFilter::Error,
+        _ => return None,
+    })
+}
+
+/// MCP level name for a record's level
+pub fn mcp_level(level: log::Level) -> &'static str {
+    match level {
+        log::Level::Error => "error",
+        log::Level::Warn => "warning",
+        log::Level::Info => "info",
+        log::Level::Debug | log::Level::Trace => "debug",
+    }
+}
+
+fn client_level() -> Option<log::LevelFilter> {
+    let index = CLIENT_LEVEL.load(Ordering::Relaxed);
+    log::LevelFilter::iter().nth(index)
+}
+
+/// 🎚️ Apply a client's `logging/setLevel`: replaces the RUST_LOG filter and
+/// starts forwarding records at `level` and above through `sink`
+pub fn set_client_level(level: log::LevelFilter, sink: Option<UnboundedSender<JsonRpcNotification>>) {
+    CLIENT_LEVEL.store(level as usize, Ordering::Relaxed);
+    if let Some(sink) = sink {
+        *CLIENT_SINK.get_or_init(|| Mutex::new(None)).lock().unwrap_or_else(|e| e.into_inner()) = Some(sink);
+    }
+    log::set_max_level(level);
+}
+
+/// 📨 `notifications/message` for a record, if a client asked for its level
+pub fn client_message(record: &log::Record) -> Option<JsonRpcNotification> {
+    let level = client_level()?;
+    if record.level() > level {
+        return None;
+    }
+    let fields = record_fields(record);
+    let data = if fields.is_empty() {
+        Value::String(record.args().to_string())
+    } else {
+        json!({"message": record.args().to_string(), "fields": fields})
+    };
+    Some(JsonRpcNotification::new("notifications/message", json!({
+        "level": mcp_level(record.level()),
+        "logger": record.target(),
+        "data": data,
+    })))
+}
+
+/// 🎚️ env_logger with a filter `logging/setLevel` can replace at runtime
+///
+/// The inner logger must accept every level; filtering happens here, against
+/// the RUST_LOG directives until a client sets a level. Logging starts before
+/// the config is loaded, so a JSON-formatting twin takes over once
+/// `set_format(LogFormat::Json)` is called.
+pub struct ReloadableLogger {
+    inner: env_logger::Logger,
+    json: Option<env_logger::Logger>,
+    filter: env_filter::Filter,
+}
+
+impl ReloadableLogger {
+    pub fn new(inner: env_logger::Logger, filter: env_filter::Filter) -> Self {
+        Self { inner, json: None, filter }
+    }
+
+    /// Write through `json` while the format is JSON (builder style)
+    pub fn with_json(mut self, json: env_logger::Logger) -> Self {
+        self.json = Some(json);
+        self
+    }
+
+    /// Install as the global logger
+    pub fn install(self) -> Result<(), log::SetLoggerError> {
+        let max_level = self.filter.filter();
+        log::set_boxed_logger(Box::new(self))?;
+        log::set_max_level(max_level);
+        Ok(())
+    }
+}
+
+impl log::Log for ReloadableLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        match client_level() {
+            Some(level) => metadata.level() <= level,
+            None => self.filter.enabled(metadata),
+        }
+    }
+
+    fn log(&self, record: &log::Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        match &self.json {
+            Some(json) if is_json() => json.log(record),
+            _ => self.inner.log(record),
+        }
+        if let Some(notification) = client_message(record)
+            && let Some(sink) = CLIENT_SINK.get()
+            && let Some(sender) = sink.lock().unwrap_or_else(|e| e.into_inner()).as_ref()
+        {
+            let _ = sender.send(notification);
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+        if let Some(json) = &self.json {
+            json.flush();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_record_with_fields() {
+        let fields: &[(&str, log::kv::Value)] = &[
+            ("path", log::kv::Value::from("src/lib.rs")),
+            ("bytes", log::kv::Value::from(42u64)),
+            ("cached", log::kv::Value::from(true)),
+        ];
+        let line = format_record(&log::Record::builder()
+            .level(log::Level::Warn)
+            .target("empathic::fs")
+            .args(format_args!("read \"{}\"", "x"))
+            .key_values(&fields)
+            .build());
+
+        let value: Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(value["level"], "WARN");
+        assert_eq!(value["target"], "empathic::fs");
+        assert_eq!(value["message"], "read \"x\"");
+        assert_eq!(value["fields"]["path"], "src/lib.rs");
+        assert_eq!(value["fields"]["bytes"], 42);
+        assert_eq!(value["fields"]["cached"], true);
+        assert!(value["timestamp"].as_str().unwrap().ends_with('Z'));
+        assert!(!line.contains('\n'));
+    }
+
+    #[test]
+    fn test_json_line_omits_empty_fields() {
+        let value: Value = serde_json::from_str(&json_line("info", "server", "multi\nline", Map::new())).unwrap();
+        assert_eq!(value["level"], "INFO");
+        assert_eq!(value["message"], "multi\nline");
+        assert!(value.get("fields").is_none());
+    }
+
+    #[test]
+    fn test_mcp_levels() {
+        assert_eq!(level_filter_for("notice"), Some(log::LevelFilter::Info));
+        assert_eq!(level_filter_for("critical"), Some(log::LevelFilter::Error));
+        assert_eq!(level_filter_for("verbose"), None);
+        assert!(MCP_LEVELS.iter().all(|level| level_filter_for(level).is_some()));
+        assert_eq!(mcp_level(log::Level::Warn), "warning");
+    }
+
+    #[test]
+    fn test_format_names() {
+        assert_eq!(LogFormat::from_name("JSON"), Some(LogFormat::Json));
+        assert_eq!(LogFormat::from_name("plain"), Some(LogFormat::Text));
+        assert_eq!(LogFormat::from_name("xml"), None);
+    }
+}
@@ -1,18 +1,30 @@
 //! ⚡ LSP Cache - Response caching for performance optimization
 //!
 //! Provides in-memory caching for LSP responses with smart invalidation based on
-//! file modifications and cache TTL policies.
-
-use crate::lsp::types::{CacheConfig, LspResult};
+//! file modifications and cache TTL policies. When `CacheConfig::persist_dir` is
+//! set, file-scoped entries are written to disk on shutdown and reloaded on
+//! startup, guarded by a hash of the file content they were computed from.
+//! The cache is bounded by entry count and total value size; beyond either
+//! bound expired entries go first, then the least recently used.
+//! Tools reach it through `LspManager::cached_request`, which keys entries
+//! by the version of the open document they were computed from.
+
+use crate::lsp::performance::LspMetrics;
+use crate::lsp::types::{CacheConfig, LspError, LspResult};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::hash::Hash;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 
+/// File name of the persisted cache inside `CacheConfig::persist_dir`
+const CACHE_FILE_NAME: &str = "cache.json";
+
 /// 🎯 Cache key for LSP operations
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum CacheKey {
     Diagnostics(PathBuf),
     Hover {
@@ -54,6 +66,17 @@ impl CacheKey {
             CacheKey::WorkspaceSymbols { .. } => None,
         }
     }
+
+    /// LSP method this key caches responses for
+    pub fn method(&self) -> &'static str {
+        match self {
+            CacheKey::Diagnostics(_) => "textDocument/publishDiagnostics",
+            CacheKey::Hover { .. } => "textDocument/hover",
+            CacheKey::Completion { .. } => "textDocument/completion",
+            CacheKey::DocumentSymbols(_) => "textDocument/documentSymbol",
+            CacheKey::WorkspaceSymbols { .. } => "workspace/symbol",
+        }
+    }
 }
 
 /// 🗃️ Cached entry with metadata
@@ -62,17 +85,42 @@ pub struct CacheEntry<T> {
     pub value: T,
     pub created_at: Instant,
     pub ttl: Duration,
+    /// Hash of the file content the value was computed from
+    pub content_hash: Option<u64>,
+    /// Version of the open document the value was computed from
+    pub document_version: Option<i32>,
+    /// Last time the entry was stored or served
+    pub last_used: Instant,
+    /// Serialized size of the value, counted against `CacheConfig::max_bytes`
+    pub size: usize,
 }
 
 impl<T> CacheEntry<T> {
     pub fn new(value: T, ttl: Duration) -> Self {
+        let now = Instant::now();
         Self {
             value,
-            created_at: Instant::now(),
+            created_at: now,
             ttl,
+            content_hash: None,
+            document_version: None,
+            last_used: now,
+            size: 0,
         }
     }
 
+    /// Attach the content hash of the file this entry belongs to
+    pub fn with_content_hash(mut self, content_hash: Option<u64>) -> Self {
+        self.content_hash = content_hash;
+        self
+    }
+
+    /// Attach the version of the open document this entry was computed from
+    pub fn with_document_version(mut self, document_version: Option<i32>) -> Self {
+        self.document_version = document_version;
+        self
+    }
+
     pub fn is_expired(&self) -> bool {
         self.created_at.elapsed() > self.ttl
     }
@@ -82,6 +130,14 @@ impl<T> CacheEntry<T> {
     }
 }
 
+impl CacheEntry<serde_json::Value> {
+    /// Create an entry sized by its serialized JSON
+    pub fn sized(value: serde_json::Value, ttl: Duration) -> Self {
+        let size = serde_json::to_vec(&value).map_or(0, |bytes| bytes.len());
+        Self { size, ..Self::new(value, ttl) }
+    }
+}
+
 /// 💾 LSP response cache
 #[derive(Debug)]
 pub struct LspCache {
@@ -91,6 +147,12 @@ pub struct LspCache {
     config: CacheConfig,
     /// File modification times for invalidation
     file_mtimes: RwLock<HashMap<PathBuf, std::time::SystemTime>>,
+    /// Metrics receiving hit/miss counts
+    metrics: Option<Arc<LspMetrics>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    /// Entries dropped to stay within the size bounds
+    evictions: AtomicU64,
 }
 
 impl LspCache {
@@ -100,32 +162,92 @@ impl LspCache {
     }
 
     /// Create a new cache with custom configuration
+    ///
+    /// With a `persist_dir`, entries saved by a previous process are loaded
+    /// immediately; those whose file content changed since are dropped.
     pub fn with_config(config: CacheConfig) -> Self {
+        let (mut storage, file_mtimes) = match config.persist_dir.as_deref() {
+            Some(dir) => load_persisted(&dir.join(CACHE_FILE_NAME), &config),
+            None => (HashMap::new(), HashMap::new()),
+        };
+        let evicted = evict_to_bounds(&mut storage, &config);
+
         Self {
-            storage: RwLock::new(HashMap::new()),
+            storage: RwLock::new(storage),
             config,
-            file_mtimes: RwLock::new(HashMap::new()),
+            file_mtimes: RwLock::new(file_mtimes),
+            metrics: None,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(evicted as u64),
         }
     }
 
+    /// 📊 Record hits and misses into shared LSP metrics (builder style)
+    pub fn with_metrics(mut self, metrics: Arc<LspMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// 💾 File the cache is persisted to, if persistence is enabled
+    pub fn persist_file(&self) -> Option<PathBuf> {
+        self.config.persist_dir.as_ref().map(|dir| dir.join(CACHE_FILE_NAME))
+    }
+
     /// 📥 Get a cached value
     pub async fn get<T>(&self, key: &CacheKey) -> Option<T>
     where
         T: for<'de> Deserialize<'de>,
     {
+        self.get_versioned(key, None).await
+    }
+
+    /// 📥 Get a cached value computed from `document_version` of its file
+    ///
+    /// An entry stored for another version is a miss. Unversioned entries
+    /// (and lookups) match any version: entries reloaded from disk are already
+    /// guarded by the content hash.
+    pub async fn get_versioned<T>(&self, key: &CacheKey, document_version: Option<i32>) -> Option<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let version_matches = |entry: &CacheEntry<serde_json::Value>| {
+            document_version.is_none() || entry.document_version.is_none() || entry.document_version == document_version
+        };
+
         // Check if the cache entry is still valid
-        if let Some(entry) = self.get_entry(key).await
-            && !entry.is_expired() && !self.is_file_modified(key).await
-            && let Ok(value) = serde_json::from_value(entry.value)
-        {
-            return Some(value);
+        let value = match self.get_entry(key).await {
+            Some(entry) if !entry.is_expired() && version_matches(&entry) && !self.is_file_modified(key).await => {
+                serde_json::from_value(entry.value).ok()
+            }
+            _ => None,
+        };
+
+        if value.is_some() {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            if let Some(entry) = self.storage.write().await.get_mut(key) {
+                entry.last_used = Instant::now();
+            }
+        } else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+        }
+        if let Some(metrics) = &self.metrics {
+            metrics.record_cache_hit(value.is_some());
         }
 
-        None
+        value
     }
 
     /// 📤 Store a value in the cache
     pub async fn set<T>(&self, key: CacheKey, value: T) -> LspResult<()>
+    where
+        T: Serialize,
+    {
+        self.set_versioned(key, value, None).await
+    }
+
+    /// 📤 Store a value computed from `document_version` of its file
+    pub async fn set_versioned<T>(&self, key: CacheKey, value: T, document_version: Option<i32>) -> LspResult<()>
     where
         T: Serialize,
     {
@@ -134,7 +256,10 @@ impl LspCache {
         })?;
 
         let ttl = key.ttl(&self.config);
-        let entry = CacheEntry::new(json_value, ttl);
+        let content_hash = key.file_path().and_then(content_hash);
+        let entry = CacheEntry::sized(json_value, ttl)
+            .with_content_hash(content_hash)
+            .with_document_version(document_version);
 
         // Update file modification time if applicable
         if let Some(file_path) = key.file_path() {
@@ -144,6 +269,8 @@ impl LspCache {
         // Store in cache
         let mut storage = self.storage.write().await;
         storage.insert(key, entry);
+        let evicted = evict_to_bounds(&mut storage, &self.config);
+        self.evictions.fetch_add(evicted as u64, Ordering::Relaxed);
 
         Ok(())
     }
@@ -197,18 +324,54 @@ impl LspCache {
         storage.retain(|_key, entry| !entry.is_expired());
     }
 
+    /// 💾 Write file-scoped entries to disk, returning how many were saved
+    ///
+    /// A no-op when persistence is disabled. Workspace symbol results are
+    /// skipped because no single file hash can tell whether they are stale.
+    pub async fn save_to_disk(&self) -> LspResult<usize> {
+        let Some(path) = self.persist_file() else {
+            return Ok(0);
+        };
+
+        let entries: Vec<PersistedEntry> = {
+            let storage = self.storage.read().await;
+            storage.iter()
+                .filter_map(|(key, entry)| {
+                    key.file_path()?;
+                    Some(PersistedEntry {
+                        key: key.clone(),
+                        method: key.method().to_string(),
+                        content_hash: entry.content_hash?,
+                        value: entry.value.clone(),
+                    })
+                })
+                .collect()
+        };
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let persisted = PersistedCache { saved_at: chrono::Utc::now(), entries };
+        std::fs::write(&path, serde_json::to_string(&persisted)?)?;
+
+        log::info!("💾 Persisted {} LSP cache entries to {}", persisted.entries.len(), path.display());
+        Ok(persisted.entries.len())
+    }
+
     /// 📊 Get cache statistics
     pub async fn stats(&self) -> CacheStats {
         let storage = self.storage.read().await;
         let total_entries = storage.len();
 
         let mut expired_count = 0;
+        let mut total_bytes = 0;
         let mut by_type = HashMap::new();
 
         for (key, entry) in storage.iter() {
             if entry.is_expired() {
                 expired_count += 1;
             }
+            total_bytes += entry.size;
 
             let key_type = match key {
                 CacheKey::Diagnostics(_) => "diagnostics",
@@ -221,10 +384,19 @@ impl LspCache {
             *by_type.entry(key_type.to_string()).or_insert(0) += 1;
         }
 
+        let hits = self.hits.load(Ordering::Relaxed);
+        let misses = self.misses.load(Ordering::Relaxed);
         CacheStats {
             total_entries,
             expired_entries: expired_count,
             entries_by_type: by_type,
+            total_bytes,
+            max_entries: self.config.max_entries,
+            max_bytes: self.config.max_bytes,
+            hits,
+            misses,
+            hit_rate: if hits + misses == 0 { 0.0 } else { hits as f64 / (hits + misses) as f64 },
+            evictions: self.evictions.load(Ordering::Relaxed),
         }
     }
 
@@ -264,6 +436,128 @@ impl LspCache {
     }
 }
 
+/// 💾 On-disk cache entry, keyed by file path + method and guarded by content hash
+#[derive(Debug, Serialize, Deserialize)]
+struct PersistedEntry {
+    key: CacheKey,
+    method: String,
+    content_hash: u64,
+    value: serde_json::Value,
+}
+
+/// 💾 On-disk cache file layout
+#[derive(Debug, Serialize, Deserialize)]
+struct PersistedCache {
+    saved_at: chrono::DateTime<chrono::Utc>,
+    entries: Vec<PersistedEntry>,
+}
+
+type LoadedCache = (
+    HashMap<CacheKey, CacheEntry<serde_json::Value>>,
+    HashMap<PathBuf, std::time::SystemTime>,
+);
+
+/// Load persisted entries whose files still hash to the saved content
+///
+/// Reloaded entries get a fresh TTL: the content hash already proves the
+/// file is unchanged, so their age in the previous process doesn't matter.
+fn load_persisted(path: &Path, config: &CacheConfig) -> LoadedCache {
+    let mut storage = HashMap::new();
+    let mut file_mtimes = HashMap::new();
+
+    let persisted: PersistedCache = match std::fs::read_to_string(path)
+        .map_err(LspError::from)
+        .and_then(|content| serde_json::from_str(&content).map_err(LspError::from))
+    {
+        Ok(persisted) => persisted,
+        Err(e) => {
+            if path.exists() {
+                log::warn!("💾 Ignoring unreadable LSP cache {}: {}", path.display(), e);
+            }
+            return (storage, file_mtimes);
+        }
+    };
+
+    let mut current_hashes: HashMap<PathBuf, Option<u64>> = HashMap::new();
+    let mut stale = 0;
+
+    for entry in persisted.entries {
+        let Some(file_path) = entry.key.file_path() else {
+            continue;
+        };
+        let current = *current_hashes
+            .entry(file_path.to_path_buf())
+            .or_insert_with(|| content_hash(file_path));
+        if current != Some(entry.content_hash) {
+            stale += 1;
+            continue;
+        }
+
+        if let Ok(mtime) = std::fs::metadata(file_path).and_then(|m| m.modified()) {
+            file_mtimes.insert(file_path.to_path_buf(), mtime);
+        }
+        let ttl = entry.key.ttl(config);
+        storage.insert(
+            entry.key,
+            CacheEntry::sized(entry.value, ttl).with_content_hash(Some(entry.content_hash)),
+        );
+    }
+
+    log::info!(
+        "💾 Loaded {} LSP cache entries from {} (saved {}, {} stale dropped)",
+        storage.len(), path.display(), persisted.saved_at.to_rfc3339(), stale
+    );
+    (storage, file_mtimes)
+}
+
+/// 🧹 Drop entries until `storage` fits the configured bounds, returning how many
+///
+/// Expired entries are dropped first, then the least recently used. An entry
+/// larger than `max_bytes` on its own is never kept.
+fn evict_to_bounds(storage: &mut HashMap<CacheKey, CacheEntry<serde_json::Value>>, config: &CacheConfig) -> usize {
+    let mut total_bytes: usize = storage.values().map(|entry| entry.size).sum();
+    let over = |len: usize, bytes: usize| len > config.max_entries || bytes > config.max_bytes;
+    if !over(storage.len(), total_bytes) {
+        return 0;
+    }
+
+    let before = storage.len();
+    storage.retain(|_, entry| {
+        let expired = entry.is_expired();
+        if expired {
+            total_bytes -= entry.size;
+        }
+        !expired
+    });
+
+    while over(storage.len(), total_bytes) {
+        let Some(oldest) = storage.iter()
+            .min_by_key(|(_, entry)| entry.last_used)
+            .map(|(key, _)| key.clone())
+        else {
+            break;
+        };
+        if let Some(entry) = storage.remove(&oldest) {
+            total_bytes -= entry.size;
+        }
+    }
+
+    let evicted = before - storage.len();
+    log::debug!("🧹 Evicted {} LSP cache entries to stay within bounds", evicted);
+    evicted
+}
+
+/// 🔑 Stable FNV-1a hash of a file's content (None if unreadable)
+fn content_hash(path: &Path) -> Option<u64> {
+    const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
+
+    let bytes = std::fs::read(path).ok()?;
+    Some(bytes.iter().fold(FNV_OFFSET, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+    }))
+}
+
 impl Default for LspCache {
     fn default() -> Self {
         Self::new()
@@ -276,5 +570,154 @@ pub struct CacheStats {
     pub total_entries: usize,
     pub expired_entries: usize,
     pub entries_by_type: HashMap<String, usize>,
+    /// Summed serialized size of all values
+    pub total_bytes: usize,
+    pub max_entries: usize,
+    pub max_bytes: usize,
+    pub hits: u64,
+    pub misses: u64,
+    /// hits / (hits + misses), 0 before any lookup
+    pub hit_rate: f64,
+    pub evictions: u64,
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn persistent_config(dir: &Path) -> CacheConfig {
+        CacheConfig {
+            persist_dir: Some(dir.join("lsp-cache")),
+            ..CacheConfig::default()
+        }
+    }
+
+    fn hover_key(file_path: &Path) -> CacheKey {
+        CacheKey::Hover { file_path: file_path.to_path_buf(), line: 0, character: 3 }
+    }
+
+    #[tokio::test]
+    async fn test_persisted_entries_survive_restart() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("main.rs");
+        std::fs::write(&file, "fn main() {}\n").unwrap();
+
+        let cache = LspCache::with_config(persistent_config(dir.path()));
+        cache.set(hover_key(&file), "fn main()").await.unwrap();
+        cache.set(CacheKey::WorkspaceSymbols {
+            query: "main".to_string(),
+            project_path: dir.path().to_path_buf(),
+        }, "ignored").await.unwrap();
+        assert_eq!(cache.save_to_disk().await.unwrap(), 1);
+
+        let reloaded = LspCache::with_config(persistent_config(dir.path()));
+        let value: Option<String> = reloaded.get(&hover_key(&file)).await;
+        assert_eq!(value.as_deref(), Some("fn main()"));
+        assert_eq!(reloaded.stats().await.total_entries, 1);
+    }
+
+    #[tokio::test]
+    async fn test_persisted_entries_dropped_when_content_changes() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("main.rs");
+        std::fs::write(&file, "fn main() {}\n").unwrap();
+
+        let cache = LspCache::with_config(persistent_config(dir.path()));
+        cache.set(hover_key(&file), "fn main()").await.unwrap();
+        cache.save_to_disk().await.unwrap();
+
+        std::fs::write(&file, "fn main() { println!(); }\n").unwrap();
+
+        let reloaded = LspCache::with_config(persistent_config(dir.path()));
+        let value: Option<String> = reloaded.get(&hover_key(&file)).await;
+        assert!(value.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_memory_only_cache_writes_nothing() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("main.rs");
+        std::fs::write(&file, "fn main() {}\n").unwrap();
+
+        let cache = LspCache::new();
+        cache.set(hover_key(&file), "fn main()").await.unwrap();
+
+        assert!(cache.persist_file().is_none());
+        assert_eq!(cache.save_to_disk().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_evicts_least_recently_used_beyond_max_entries() {
+        let dir = tempdir().unwrap();
+        let files: Vec<PathBuf> = (0..3).map(|i| dir.path().join(format!("f{i}.rs"))).collect();
+        let cache = LspCache::with_config(CacheConfig { max_entries: 2, ..CacheConfig::default() });
+
+        cache.set(hover_key(&files[0]), "zero").await.unwrap();
+        cache.set(hover_key(&files[1]), "one").await.unwrap();
+        // Reading f0 makes f1 the least recently used
+        assert!(cache.get::<String>(&hover_key(&files[0])).await.is_some());
+        cache.set(hover_key(&files[2]), "two").await.unwrap();
+
+        assert!(cache.get::<String>(&hover_key(&files[0])).await.is_some());
+        assert!(cache.get::<String>(&hover_key(&files[1])).await.is_none());
+        assert!(cache.get::<String>(&hover_key(&files[2])).await.is_some());
+
+        let stats = cache.stats().await;
+        assert_eq!((stats.total_entries, stats.evictions), (2, 1));
+        assert_eq!((stats.hits, stats.misses), (3, 1));
+        assert_eq!(stats.hit_rate, 0.75);
+    }
+
+    #[tokio::test]
+    async fn test_max_bytes_bounds_total_size() {
+        let dir = tempdir().unwrap();
+        let cache = LspCache::with_config(CacheConfig { max_bytes: 100, ..CacheConfig::default() });
+
+        // Each value serializes to 42 bytes (40 characters plus quotes)
+        for i in 0..3 {
+            cache.set(hover_key(&dir.path().join(format!("f{i}.rs"))), "x".repeat(40)).await.unwrap();
+        }
+        let stats = cache.stats().await;
+        assert_eq!((stats.total_entries, stats.total_bytes), (2, 84));
+
+        // Too big to ever fit
+        cache.set(hover_key(&dir.path().join("big.rs")), "x".repeat(200)).await.unwrap();
+        assert!(cache.get::<String>(&hover_key(&dir.path().join("big.rs"))).await.is_none());
+        assert!(cache.stats().await.total_bytes <= 100);
+    }
+
+    #[tokio::test]
+    async fn test_versioned_entries_miss_other_versions() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("main.rs");
+        let cache = LspCache::new();
+
+        cache.set_versioned(hover_key(&file), "fn main()", Some(2)).await.unwrap();
+        assert_eq!(cache.get_versioned::<String>(&hover_key(&file), Some(2)).await.as_deref(), Some("fn main()"));
+        assert!(cache.get_versioned::<String>(&hover_key(&file), Some(3)).await.is_none());
+        assert!(cache.get::<String>(&hover_key(&file)).await.is_some());
+
+        cache.set(hover_key(&file), "unversioned").await.unwrap();
+        assert!(cache.get_versioned::<String>(&hover_key(&file), Some(3)).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_get_records_hits_and_misses() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("main.rs");
+        std::fs::write(&file, "fn main() {}\n").unwrap();
+
+        let metrics = Arc::new(LspMetrics::default());
+        let cache = LspCache::new().with_metrics(metrics.clone());
+
+        assert!(cache.get::<String>(&hover_key(&file)).await.is_none());
+        cache.set(hover_key(&file), "fn main()").await.unwrap();
+        assert!(cache.get::<String>(&hover_key(&file)).await.is_some());
+
+        assert_eq!(metrics.cache_hits.load(Ordering::Relaxed), 1);
+        assert_eq!(metrics.cache_misses.load(Ordering::Relaxed), 1);
+        assert!(metrics.summary().contains("50% cache hit"));
+    }
+}
@@ -3,7 +3,10 @@
 //! Handles JSON-RPC 2.0 communication with LSP servers over stdin/stdout.
 //! Manages request correlation, async responses, and LSP initialization.
 
-use crate::lsp::types::{LspError, LspResult};
+use crate::lsp::performance::LspMetrics;
+use crate::lsp::positions::PositionConverter;
+use crate::lsp::readiness::ReadinessState;
+use crate::lsp::types::{LspError, LspResult, LspTimeoutConfig, PositionEncoding};
 use lsp_types::*;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
@@ -14,7 +17,7 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
-use tokio::sync::{broadcast, mpsc, oneshot, RwLock};
+use tokio::sync::{broadcast, mpsc, oneshot, watch, RwLock};
 use tokio::time::timeout;
 use url::Url;
 
@@ -57,6 +60,25 @@ pub struct JsonRpcError {
     pub data: Option<Value>,
 }
 
+/// 📥 State the reader task needs to route incoming messages
+struct IncomingContext {
+    pending_requests: Arc<RwLock<HashMap<u64, oneshot::Sender<JsonRpcResponse>>>>,
+    notification_tx: broadcast::Sender<JsonRpcNotification>,
+    settings: Arc<RwLock<Value>>,
+    /// Outgoing queue, for answering server-initiated requests
+    reply_tx: mpsc::UnboundedSender<String>,
+    readiness: Arc<watch::Sender<ReadinessState>>,
+    positions: Arc<PositionConverter>,
+}
+
+/// Look up a dotted `workspace/configuration` section, e.g. `rust-analyzer.cargo`
+fn settings_section(settings: &Value, section: &str) -> Value {
+    section.split('.')
+        .try_fold(settings, |value, key| value.get(key))
+        .cloned()
+        .unwrap_or(Value::Null)
+}
+
 /// 🔧 LSP client for JSON-RPC communication
 pub struct LspClient {
     /// Project path this client serves
@@ -69,10 +91,20 @@ pub struct LspClient {
     message_sender: mpsc::UnboundedSender<String>,
     /// Server capabilities after initialization
     capabilities: Arc<RwLock<Option<ServerCapabilities>>>,
-    /// Default request timeout
-    timeout_duration: Duration,
+    /// Server name and version from the initialize result
+    server_info: Arc<RwLock<Option<ServerInfo>>>,
+    /// Request timeouts, resolved per LSP method
+    timeouts: Arc<LspTimeoutConfig>,
     /// Notification broadcaster for LSP notifications
     notification_tx: broadcast::Sender<JsonRpcNotification>,
+    /// Settings served for `workspace/configuration` requests, keyed by section
+    settings: Arc<RwLock<Value>>,
+    /// Metrics receiving per-method request timings, attached by the manager
+    metrics: Arc<std::sync::OnceLock<Arc<LspMetrics>>>,
+    /// Loading/indexing state, updated from progress notifications
+    readiness: Arc<watch::Sender<ReadinessState>>,
+    /// Rewrites positions between tool columns and the negotiated encoding
+    positions: Arc<PositionConverter>,
 }
 
 impl std::fmt::Debug for LspClient {
@@ -80,7 +112,7 @@ impl std::fmt::Debug for LspClient {
         f.debug_struct("LspClient")
             .field("project_path", &self.project_path)
             .field("next_id", &self.next_id.load(Ordering::SeqCst))
-            .field("timeout_duration", &self.timeout_duration)
+            .field("default_timeout", &self.timeouts.default_timeout)
             .field("notification_subscribers", &self.notification_tx.receiver_count())
             .finish_non_exhaustive()
     }
@@ -94,8 +126,13 @@ impl Clone for LspClient {
             pending_requests: self.pending_requests.clone(),
             message_sender: self.message_sender.clone(),
             capabilities: self.capabilities.clone(),
-            timeout_duration: self.timeout_duration,
+            server_info: self.server_info.clone(),
+            timeouts: self.timeouts.clone(),
             notification_tx: self.notification_tx.clone(),
+            settings: self.settings.clone(),
+            metrics: self.metrics.clone(),
+            readiness: self.readiness.clone(),
+            positions: self.positions.clone(),
         }
     }
 }
@@ -114,43 +151,50 @@ impl LspClient {
         // Create notification broadcast channel with capacity for 100 notifications
         let (notification_tx, _) = broadcast::channel(100);
 
-        // 📊 Read LSP_TIMEOUT from environment (default: 60s)
-        let timeout_duration = std::env::var("LSP_TIMEOUT")
-            .ok()
-            .and_then(|s| s.parse::<u64>().ok())
-            .map(Duration::from_secs)
-            .unwrap_or_else(|| Duration::from_secs(60));
-
-        log::debug!("⏱️ LSP client timeout set to {}s", timeout_duration.as_secs());
-
+        let settings = Arc::new(RwLock::new(json!({})));
+        let readiness = Arc::new(watch::Sender::new(ReadinessState::default()));
+        let positions = Arc::new(PositionConverter::default());
         let client = Self {
             project_path,
             next_id: AtomicU64::new(1),
             pending_requests: pending_requests.clone(),
-            message_sender: message_tx,
+            message_sender: message_tx.clone(),
             capabilities: Arc::new(RwLock::new(None)),
-            timeout_duration,
+            server_info: Arc::new(RwLock::new(None)),
+            timeouts: Arc::new(LspTimeoutConfig::default()),
             notification_tx: notification_tx.clone(),
+            settings: settings.clone(),
+            metrics: Arc::new(std::sync::OnceLock::new()),
+            readiness: readiness.clone(),
+            positions: positions.clone(),
         };
 
         // Spawn communication tasks
         tokio::spawn({
             let pending_requests = pending_requests.clone();
+            let incoming = IncomingContext { pending_requests, notification_tx, settings, reply_tx: message_tx, readiness, positions };
             async move {
-                Self::run_communication(stdin, stdout, message_rx, pending_requests, notification_tx).await
+                Self::run_communication(stdin, stdout, message_rx, incoming).await
             }
         });
 
         Ok(client)
     }
 
+    /// ⏱️ Use `timeouts` for requests instead of the 60s default (builder style)
+    pub fn with_timeouts(mut self, timeouts: Arc<LspTimeoutConfig>) -> Self {
+        log::debug!("⏱️ LSP client timeout set to {}s ({} per-method overrides)",
+            timeouts.default_timeout.as_secs(), timeouts.timeouts.len());
+        self.timeouts = timeouts;
+        self
+    }
+
     /// 🚀 Main communication loop with proper LSP Content-Length protocol
     async fn run_communication(
         mut stdin: tokio::process::ChildStdin,
         stdout: tokio::process::ChildStdout,
         mut message_rx: mpsc::UnboundedReceiver<String>,
-        pending_requests: Arc<RwLock<HashMap<u64, oneshot::Sender<JsonRpcResponse>>>>,
-        notification_tx: broadcast::Sender<JsonRpcNotification>,
+        incoming: IncomingContext,
     ) {
         let mut reader = BufReader::new(stdout);
 
@@ -185,7 +229,7 @@ impl LspClient {
                 read_result = Self::read_lsp_message(&mut reader) => {
                     match read_result {
                         Ok(Some(content)) => {
-                            if let Err(e) = Self::handle_incoming_message(&content, &pending_requests, &notification_tx).await {
+                            if let Err(e) = Self::handle_incoming_message(&content, &incoming).await {
                                 log::error!("Failed to handle incoming LSP message: {e}");
                             }
                         }
@@ -249,11 +293,7 @@ impl LspClient {
     }
 
     /// Handle an incoming message from LSP server
-    async fn handle_incoming_message(
-        content: &str,
-        pending_requests: &Arc<RwLock<HashMap<u64, oneshot::Sender<JsonRpcResponse>>>>,
-        notification_tx: &broadcast::Sender<JsonRpcNotification>,
-    ) -> LspResult<()> {
+    async fn handle_incoming_message(content: &str, incoming: &IncomingContext) -> LspResult<()> {
         let content = content.trim();
         if content.is_empty() {
             return Ok(());
@@ -269,19 +309,51 @@ impl LspClient {
         match message {
             JsonRpcMessage::Response(response) => {
                 // Find and notify the pending request
-                let mut pending = pending_requests.write().await;
+                let mut pending = incoming.pending_requests.write().await;
                 if let Some(sender) = pending.remove(&response.id) {
                     let _ = sender.send(response); // Ignore if receiver is dropped
                 }
             }
-            JsonRpcMessage::Notification(notification) => {
+            JsonRpcMessage::Notification(mut notification) => {
                 // Broadcast notification to all subscribers
                 log::debug!("📨 LSP notification: {}", notification.method);
-                let _ = notification_tx.send(notification); // Ignore if no subscribers
+                if let Some(params) = notification.params.as_mut() {
+                    incoming.positions.from_server(&notification.method, params, None).await;
+                }
+                incoming.readiness.send_if_modified(|state| state.apply(&notification));
+                let _ = incoming.notification_tx.send(notification); // Ignore if no subscribers
+            }
+            JsonRpcMessage::Request(request) if request.method == "workspace/configuration" => {
+                // ⚙️ Servers pull settings after didChangeConfiguration; answer from the store
+                let settings = incoming.settings.read().await;
+                let items = request.params.as_ref()
+                    .and_then(|params| params.get("items"))
+                    .and_then(Value::as_array)
+                    .cloned()
+                    .unwrap_or_default();
+                let result: Vec<Value> = items.iter()
+                    .map(|item| match item.get("section").and_then(Value::as_str) {
+                        Some(section) => settings_section(&settings, section),
+                        None => settings.clone(),
+                    })
+                    .collect();
+                let response = json!({"jsonrpc": "2.0", "id": request.id, "result": result});
+                let _ = incoming.reply_tx.send(response.to_string());
+            }
+            JsonRpcMessage::Request(request) if request.method == "window/workDoneProgress/create" => {
+                // ⏳ Accept progress tokens so loading and indexing get reported
+                let response = json!({"jsonrpc": "2.0", "id": request.id, "result": null});
+                let _ = incoming.reply_tx.send(response.to_string());
             }
-            JsonRpcMessage::Request(_request) => {
-                // LSP servers shouldn't send requests to clients in our use case
-                log::warn!("Unexpected request from LSP server");
+            JsonRpcMessage::Request(request) => {
+                // Requests must be answered; anything else is unsupported here
+                log::warn!("Unexpected request from LSP server: {}", request.method);
+                let response = json!({
+                    "jsonrpc": "2.0",
+                    "id": request.id,
+                    "error": {"code": -32601, "message": format!("Method not found: {}", request.method)}
+                });
+                let _ = incoming.reply_tx.send(response.to_string());
             }
         }
 
@@ -293,6 +365,66 @@ impl LspClient {
     where
         T: for<'de> Deserialize<'de>,
     {
+        let metrics = self.metrics.get();
+        if let Some(metrics) = metrics {
+            metrics.request_started();
+        }
+        // 🔤 Tool columns go out in the server's encoding and come back as tool columns
+        let mut params = params;
+        if let Some(params) = params.as_mut() {
+            self.positions.to_server(params).await;
+        }
+        let document_uri = params.as_ref()
+            .and_then(|params| params["textDocument"]["uri"].as_str())
+            .map(str::to_string);
+        let start = std::time::Instant::now();
+        let response = self.round_trip(method, params, self.timeouts.timeout_for(method)).await;
+        if let Some(metrics) = metrics {
+            metrics.request_finished();
+            let success = response.as_ref().is_ok_and(|response| response.error.is_none());
+            metrics.record_method(method, start.elapsed(), success);
+        }
+        let response = response?;
+
+        // Handle response or error
+        if let Some(error) = response.error {
+            return Err(LspError::JsonRpcError {
+                message: format!("LSP error {}: {}", error.code, error.message),
+            });
+        }
+
+        // result field is REQUIRED in successful responses, but can be null
+        // response.result is Option<Value>, where:
+        // - None means field is missing (protocol violation)
+        // - Some(Value::Null) means result is null (valid, means "no info")
+        // - Some(Value::...) means result has data
+        let mut result = response.result.unwrap_or(Value::Null);
+        self.positions.from_server(method, &mut result, document_uri.as_deref()).await;
+
+        serde_json::from_value(result).map_err(|e| LspError::InvalidResponse {
+            message: format!("Failed to deserialize response: {e}"),
+        })
+    }
+
+    /// 📊 Record this client's requests (and its clones') into `metrics`
+    ///
+    /// Only the first attachment takes effect.
+    pub fn attach_metrics(&self, metrics: Arc<LspMetrics>) {
+        let _ = self.metrics.set(metrics);
+    }
+
+    /// 🏓 Time a round-trip of a request no server implements
+    ///
+    /// Any reply, MethodNotFound included, shows the server's main loop is
+    /// still serving requests; a live but hung server times out instead.
+    pub async fn ping(&self, probe_timeout: Duration) -> LspResult<Duration> {
+        let start = std::time::Instant::now();
+        self.round_trip("empathic/ping", None, probe_timeout).await?;
+        Ok(start.elapsed())
+    }
+
+    /// 📤 Send a request and wait up to `request_timeout` for its raw response
+    async fn round_trip(&self, method: &str, params: Option<Value>, request_timeout: Duration) -> LspResult<JsonRpcResponse> {
         let id = self.next_id.fetch_add(1, Ordering::SeqCst);
         let (response_tx, response_rx) = oneshot::channel();
 
@@ -317,37 +449,26 @@ impl LspClient {
             }
         })?;
 
-        // Wait for response with timeout
-        let response = timeout(self.timeout_duration, response_rx)
-            .await
-            .map_err(|_| LspError::Timeout {
-                timeout_secs: self.timeout_duration.as_secs(),
-            })?
+        // Wait for response with the method's timeout
+        match timeout(request_timeout, response_rx).await {
+            Ok(response) => response,
+            Err(_) => {
+                // Drop the waiter so a late response isn't routed to nobody
+                self.pending_requests.write().await.remove(&id);
+                return Err(LspError::RequestTimeout {
+                    method: method.to_string(),
+                    timeout_ms: request_timeout.as_millis() as u64,
+                });
+            }
+        }
             .map_err(|_| LspError::JsonRpcError {
                 message: "Response channel closed".to_string(),
-            })?;
-
-        // Handle response or error
-        if let Some(error) = response.error {
-            return Err(LspError::JsonRpcError {
-                message: format!("LSP error {}: {}", error.code, error.message),
-            });
-        }
-
-        // result field is REQUIRED in successful responses, but can be null
-        // response.result is Option<Value>, where:
-        // - None means field is missing (protocol violation)
-        // - Some(Value::Null) means result is null (valid, means "no info")
-        // - Some(Value::...) means result has data
-        let result = response.result.unwrap_or(Value::Null);
-
-        serde_json::from_value(result).map_err(|e| LspError::InvalidResponse {
-            message: format!("Failed to deserialize response: {e}"),
-        })
+            })
     }
 
     /// 📢 Send a JSON-RPC notification (no response expected)
     pub async fn send_notification(&self, method: &str, params: Option<Value>) -> LspResult<()> {
+        self.positions.track_notification(method, params.as_ref());
         let notification = JsonRpcNotification {
             jsonrpc: "2.0".to_string(),
             method: method.to_string(),
@@ -366,9 +487,17 @@ impl LspClient {
 
     /// 🚀 Initialize the LSP server
     pub async fn initialize(&self) -> LspResult<InitializeResult> {
+        self.initialize_with(None).await
+    }
+
+    /// 🚀 Initialize, passing the server its initial settings as `initializationOptions`
+    pub async fn initialize_with(&self, initialization_options: Option<Value>) -> LspResult<InitializeResult> {
         let client_capabilities = ClientCapabilities {
             workspace: Some(WorkspaceClientCapabilities {
                 configuration: Some(true),
+                execute_command: Some(DynamicRegistrationClientCapabilities {
+                    dynamic_registration: Some(false),
+                }),
                 did_change_watched_files: Some(DidChangeWatchedFilesClientCapabilities {
                     dynamic_registration: Some(false),
                     relative_pattern_support: Some(true),
@@ -398,13 +527,84 @@ impl LspClient {
                 references: Some(ReferenceClientCapabilities {
                     dynamic_registration: Some(false),
                 }),
+                document_highlight: Some(DocumentHighlightClientCapabilities {
+                    dynamic_registration: Some(false),
+                }),
+                folding_range: Some(FoldingRangeClientCapabilities {
+                    dynamic_registration: Some(false),
+                    line_folding_only: Some(false),
+                    ..Default::default()
+                }),
+                selection_range: Some(SelectionRangeClientCapabilities {
+                    dynamic_registration: Some(false),
+                }),
+                code_lens: Some(CodeLensClientCapabilities {
+                    dynamic_registration: Some(false),
+                }),
+                type_hierarchy: Some(TypeHierarchyClientCapabilities {
+                    dynamic_registration: Some(false),
+                }),
+                diagnostic: Some(DiagnosticClientCapabilities {
+                    dynamic_registration: Some(false),
+                    related_document_support: Some(false),
+                }),
+                rename: Some(RenameClientCapabilities {
+                    dynamic_registration: Some(false),
+                    prepare_support: Some(true),
+                    ..Default::default()
+                }),
                 document_symbol: Some(DocumentSymbolClientCapabilities {
                     dynamic_registration: Some(false),
                     hierarchical_document_symbol_support: Some(true),
                     ..Default::default()
                 }),
+                semantic_tokens: Some(SemanticTokensClientCapabilities {
+                    dynamic_registration: Some(false),
+                    requests: SemanticTokensClientCapabilitiesRequests {
+                        range: Some(true),
+                        full: Some(SemanticTokensFullOptions::Bool(true)),
+                    },
+                    token_types: vec![
+                        SemanticTokenType::NAMESPACE, SemanticTokenType::TYPE, SemanticTokenType::CLASS,
+                        SemanticTokenType::ENUM, SemanticTokenType::INTERFACE, SemanticTokenType::STRUCT,
+                        SemanticTokenType::TYPE_PARAMETER, SemanticTokenType::PARAMETER, SemanticTokenType::VARIABLE,
+                        SemanticTokenType::PROPERTY, SemanticTokenType::ENUM_MEMBER, SemanticTokenType::EVENT,
+                        SemanticTokenType::FUNCTION, SemanticTokenType::METHOD, SemanticTokenType::MACRO,
+                        SemanticTokenType::KEYWORD, SemanticTokenType::MODIFIER, SemanticTokenType::COMMENT,
+                        SemanticTokenType::STRING, SemanticTokenType::NUMBER, SemanticTokenType::REGEXP,
+                        SemanticTokenType::OPERATOR, SemanticTokenType::DECORATOR,
+                    ],
+                    token_modifiers: vec![
+                        SemanticTokenModifier::DECLARATION, SemanticTokenModifier::DEFINITION,
+                        SemanticTokenModifier::READONLY, SemanticTokenModifier::STATIC,
+                        SemanticTokenModifier::DEPRECATED, SemanticTokenModifier::ABSTRACT,
+                        SemanticTokenModifier::ASYNC, SemanticTokenModifier::MODIFICATION,
+                        SemanticTokenModifier::DOCUMENTATION, SemanticTokenModifier::DEFAULT_LIBRARY,
+                    ],
+                    formats: vec![TokenFormat::RELATIVE],
+                    overlapping_token_support: Some(false),
+                    multiline_token_support: Some(false),
+                    ..Default::default()
+                }),
                 ..Default::default()
             }),
+            window: Some(WindowClientCapabilities {
+                work_done_progress: Some(true),
+                ..Default::default()
+            }),
+            // 🔤 UTF-8 spares converting columns; UTF-16 is the LSP default fallback
+            general: Some(GeneralClientCapabilities {
+                position_encodings: Some(vec![PositionEncodingKind::UTF8, PositionEncodingKind::UTF16]),
+                ..Default::default()
+            }),
+            // rust-analyzer: send experimental/serverStatus with a quiescent flag, and
+            // emit Run/Debug/references code lenses, which it gates on these commands
+            experimental: Some(json!({
+                "serverStatusNotification": true,
+                "commands": {
+                    "commands": ["rust-analyzer.runSingle", "rust-analyzer.debugSingle", "rust-analyzer.showReferences"]
+                }
+            })),
             ..Default::default()
         };
 
@@ -412,7 +612,7 @@ impl LspClient {
         let root_uri = Uri::from_str(root_url.as_str()).unwrap();
         let params = InitializeParams {
             process_id: Some(std::process::id()),
-            initialization_options: None,
+            initialization_options,
             capabilities: client_capabilities,
             trace: Some(TraceValue::Off),
             workspace_folders: Some(vec![WorkspaceFolder {
@@ -440,6 +640,8 @@ impl LspClient {
             let mut capabilities = self.capabilities.write().await;
             *capabilities = Some(result.capabilities.clone());
         }
+        self.positions.set_encoding(PositionEncoding::from_kind(result.capabilities.position_encoding.as_ref()));
+        *self.server_info.write().await = result.server_info.clone();
 
         // Send initialized notification
         self.send_notification("initialized", Some(json!({})))
@@ -448,6 +650,27 @@ impl LspClient {
         Ok(result)
     }
 
+    /// ⚙️ Store the settings served for `section` without notifying the server
+    pub async fn configure(&self, section: &str, settings: Value) {
+        let mut stored = self.settings.write().await;
+        if let Value::Object(sections) = &mut *stored {
+            sections.insert(section.to_string(), settings);
+        }
+    }
+
+    /// ⚙️ Replace `section`'s settings and send `workspace/didChangeConfiguration`
+    ///
+    /// rust-analyzer ignores the notification's payload and re-reads its
+    /// section through `workspace/configuration`, which is answered from the
+    /// same store; the payload is still filled in for push-style servers.
+    pub async fn did_change_configuration(&self, section: &str, settings: Value) -> LspResult<()> {
+        self.configure(section, settings.clone()).await;
+        self.send_notification(
+            "workspace/didChangeConfiguration",
+            Some(json!({"settings": {section: settings}})),
+        ).await
+    }
+
     /// Get server capabilities
     pub async fn capabilities(&self) -> Option<ServerCapabilities> {
         self.capabilities.read().await.clone()
@@ -480,6 +703,66 @@ impl LspClient {
         self.send_request("textDocument/references", Some(serde_json::to_value(params)?)).await
     }
 
+    /// 🎯 Send document highlight request
+    pub async fn document_highlight(&self, params: DocumentHighlightParams) -> LspResult<Option<Vec<DocumentHighlight>>> {
+        self.send_request("textDocument/documentHighlight", Some(serde_json::to_value(params)?)).await
+    }
+
+    /// 🎯 Send folding range request
+    pub async fn folding_range(&self, params: FoldingRangeParams) -> LspResult<Option<Vec<FoldingRange>>> {
+        self.send_request("textDocument/foldingRange", Some(serde_json::to_value(params)?)).await
+    }
+
+    /// 🎨 Send semantic tokens request for a whole document
+    pub async fn semantic_tokens_full(&self, params: SemanticTokensParams) -> LspResult<Option<SemanticTokensResult>> {
+        self.send_request("textDocument/semanticTokens/full", Some(serde_json::to_value(params)?)).await
+    }
+
+    /// 🎨 Send semantic tokens request for a range
+    pub async fn semantic_tokens_range(&self, params: SemanticTokensRangeParams) -> LspResult<Option<SemanticTokensRangeResult>> {
+        self.send_request("textDocument/semanticTokens/range", Some(serde_json::to_value(params)?)).await
+    }
+
+    /// ✏️ Send prepare rename request
+    pub async fn prepare_rename(&self, params: TextDocumentPositionParams) -> LspResult<Option<PrepareRenameResponse>> {
+        self.send_request("textDocument/prepareRename", Some(serde_json::to_value(params)?)).await
+    }
+
+    /// 🔎 Send code lens request
+    pub async fn code_lens(&self, params: CodeLensParams) -> LspResult<Option<Vec<CodeLens>>> {
+        self.send_request("textDocument/codeLens", Some(serde_json::to_value(params)?)).await
+    }
+
+    /// 🔎 Fill in the command of a lazily returned code lens
+    pub async fn code_lens_resolve(&self, lens: CodeLens) -> LspResult<CodeLens> {
+        self.send_request("codeLens/resolve", Some(serde_json::to_value(lens)?)).await
+    }
+
+    /// 🎯 Send selection range request
+    pub async fn selection_range(&self, params: SelectionRangeParams) -> LspResult<Option<Vec<SelectionRange>>> {
+        self.send_request("textDocument/selectionRange", Some(serde_json::to_value(params)?)).await
+    }
+
+    /// 🌳 Send prepare type hierarchy request
+    pub async fn prepare_type_hierarchy(&self, params: TypeHierarchyPrepareParams) -> LspResult<Option<Vec<TypeHierarchyItem>>> {
+        self.send_request("textDocument/prepareTypeHierarchy", Some(serde_json::to_value(params)?)).await
+    }
+
+    /// 🌳 Send type hierarchy supertypes request
+    pub async fn type_hierarchy_supertypes(&self, params: TypeHierarchySupertypesParams) -> LspResult<Option<Vec<TypeHierarchyItem>>> {
+        self.send_request("typeHierarchy/supertypes", Some(serde_json::to_value(params)?)).await
+    }
+
+    /// 🌳 Send type hierarchy subtypes request
+    pub async fn type_hierarchy_subtypes(&self, params: TypeHierarchySubtypesParams) -> LspResult<Option<Vec<TypeHierarchyItem>>> {
+        self.send_request("typeHierarchy/subtypes", Some(serde_json::to_value(params)?)).await
+    }
+
+    /// 🩺 Pull diagnostics for one document (`textDocument/diagnostic`)
+    pub async fn document_diagnostic(&self, params: DocumentDiagnosticParams) -> LspResult<DocumentDiagnosticReportResult> {
+        self.send_request("textDocument/diagnostic", Some(serde_json::to_value(params)?)).await
+    }
+
     /// 🎯 Send document symbols request
     pub async fn document_symbols(&self, params: DocumentSymbolParams) -> LspResult<Option<DocumentSymbolResponse>> {
         self.send_request("textDocument/documentSymbol", Some(serde_json::to_value(params)?)).await
@@ -490,6 +773,87 @@ impl LspClient {
         self.send_request("workspace/symbol", Some(serde_json::to_value(params)?)).await
     }
 
+    /// 🌊 workspace/symbol with partial results handed to `on_partial` as they arrive
+    ///
+    /// A `partialResultToken` is attached and `$/progress` batches for it are
+    /// passed on while the request runs. The returned list consolidates every
+    /// batch and the final response; a server that ignores the token simply
+    /// answers with everything at the end, as with `workspace_symbols`.
+    pub async fn workspace_symbols_streaming<F>(&self, mut params: WorkspaceSymbolParams, mut on_partial: F) -> LspResult<Option<Vec<SymbolInformation>>>
+    where
+        F: FnMut(&[SymbolInformation]),
+    {
+        let token = format!("empathic-partial-{}", self.next_id.load(Ordering::SeqCst));
+        params.partial_result_params.partial_result_token = Some(NumberOrString::String(token.clone()));
+
+        // Subscribe before sending so no early batch is missed
+        let mut notifications = self.subscribe_notifications();
+        let mut symbols: Vec<SymbolInformation> = Vec::new();
+        let mut take_batch = |notification: JsonRpcNotification, symbols: &mut Vec<SymbolInformation>| {
+            let Some(params) = notification.params.filter(|_| notification.method == "$/progress") else {
+                return;
+            };
+            if params.get("token").and_then(Value::as_str) != Some(token.as_str()) {
+                return;
+            }
+            if let Some(Ok(batch)) = params.get("value").cloned().map(serde_json::from_value::<Vec<SymbolInformation>>) {
+                on_partial(&batch);
+                symbols.extend(batch);
+            }
+        };
+
+        let request = self.send_request::<Option<Vec<SymbolInformation>>>("workspace/symbol", Some(serde_json::to_value(params)?));
+        tokio::pin!(request);
+        let response = loop {
+            tokio::select! {
+                response = &mut request => break response?,
+                notification = notifications.recv() => match notification {
+                    Ok(notification) => take_batch(notification, &mut symbols),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        log::warn!("⚠️ Missed {skipped} LSP notifications while streaming workspace symbols");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => {
+                        break request.await?;
+                    }
+                },
+            }
+        };
+        // Batches are routed before the response that follows them
+        while let Ok(notification) = notifications.try_recv() {
+            take_batch(notification, &mut symbols);
+        }
+
+        if response.is_none() && symbols.is_empty() {
+            return Ok(None);
+        }
+        symbols.extend(response.unwrap_or_default());
+        Ok(Some(symbols))
+    }
+
+    /// Server name and version, if the server sent them
+    pub async fn server_info(&self) -> Option<ServerInfo> {
+        self.server_info.read().await.clone()
+    }
+
+    /// 🔤 Unit of `character` in this server's positions (UTF-16 until initialized)
+    pub async fn position_encoding(&self) -> PositionEncoding {
+        self.capabilities.read().await.as_ref()
+            .map_or_else(PositionEncoding::default, |caps| PositionEncoding::from_kind(caps.position_encoding.as_ref()))
+    }
+
+    /// ⚙️ Send workspace/executeCommand request
+    pub async fn execute_command(&self, params: ExecuteCommandParams) -> LspResult<Option<Value>> {
+        self.send_request("workspace/executeCommand", Some(serde_json::to_value(params)?)).await
+    }
+
+    /// ⚙️ Commands advertised by the server's executeCommandProvider
+    pub async fn supported_commands(&self) -> Vec<String> {
+        self.capabilities().await
+            .and_then(|caps| caps.execute_command_provider)
+            .map(|provider| provider.commands)
+            .unwrap_or_default()
+    }
+
     /// 🔍 Get server capabilities after initialization
     pub async fn get_capabilities(&self) -> Option<ServerCapabilities> {
         let caps = self.capabilities.read().await;
@@ -514,6 +878,32 @@ impl LspClient {
         Ok(())
     }
 
+    /// ⏳ Whether the server has finished loading and indexing the workspace
+    pub fn is_ready(&self) -> bool {
+        self.readiness.borrow().is_ready()
+    }
+
+    /// ⏳ Loading state as seen from progress notifications so far
+    pub fn readiness(&self) -> ReadinessState {
+        self.readiness.borrow().clone()
+    }
+
+    /// ⏳ Wait until the server reports indexing complete (see `readiness`)
+    ///
+    /// Returns how long the wait took; already-ready servers return at once.
+    pub async fn wait_until_ready(&self, ready_timeout: Duration) -> LspResult<Duration> {
+        let start = std::time::Instant::now();
+        let mut rx = self.readiness.subscribe();
+        match timeout(ready_timeout, rx.wait_for(ReadinessState::is_ready)).await {
+            Ok(Ok(_)) => Ok(start.elapsed()),
+            Ok(Err(_)) => Err(LspError::ServerCrashed { project_path: self.project_path.clone() }),
+            Err(_) => Err(LspError::RequestTimeout {
+                method: "wait_until_ready".to_string(),
+                timeout_ms: ready_timeout.as_millis() as u64,
+            }),
+        }
+    }
+
     /// 📡 Subscribe to LSP notifications
     /// Returns a receiver that will get all notifications from the LSP server
     pub fn subscribe_notifications(&self) -> broadcast::Receiver<JsonRpcNotification> {
@@ -5,10 +5,13 @@
 //! resources by cleaning up servers that are no longer being used.
 
 use std::collections::HashMap;
+use std::future::Future;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Weak};
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
 
 /// Key identifying a unique LSP server instance: (project_path, language)
 pub type ServerKey = (PathBuf, String);
@@ -24,6 +27,9 @@ pub struct IdleMonitorConfig {
     
     /// Enable/disable idle monitoring
     pub enabled: bool,
+    
+    /// Run the cleanup loop in a background task (opt-in)
+    pub background: bool,
 }
 
 impl Default for IdleMonitorConfig {
@@ -32,6 +38,7 @@ impl Default for IdleMonitorConfig {
             idle_timeout: Duration::from_secs(10 * 60), // 10 minutes
             check_interval: Duration::from_secs(60),     // 1 minute
             enabled: true,
+            background: false,
         }
     }
 }
@@ -56,10 +63,16 @@ impl IdleMonitorConfig {
             .and_then(|s| s.parse::<bool>().ok())
             .unwrap_or(true);
         
+        let background = std::env::var("LSP_IDLE_BACKGROUND")
+            .ok()
+            .and_then(|s| s.parse::<bool>().ok())
+            .unwrap_or(false);
+        
         Self {
             idle_timeout,
             check_interval,
             enabled,
+            background,
         }
     }
 }
@@ -67,7 +80,8 @@ impl IdleMonitorConfig {
 /// ⏱️ Monitors LSP server idle time
 ///
 /// This component tracks the last time each LSP server was used.
-/// External tasks can query for idle servers and handle shutdowns.
+/// Idle servers are shut down by a background task (see `spawn_background`)
+/// or by whoever calls the owner's cleanup method.
 #[derive(Debug)]
 pub struct IdleMonitor {
     /// Last request time per server
@@ -75,6 +89,9 @@ pub struct IdleMonitor {
     
     /// Configuration
     config: IdleMonitorConfig,
+    
+    /// Whether a background cleanup task is currently running
+    running: Arc<AtomicBool>,
 }
 
 impl IdleMonitor {
@@ -88,9 +105,52 @@ impl IdleMonitor {
         Self {
             last_used: Arc::new(RwLock::new(HashMap::new())),
             config,
+            running: Arc::new(AtomicBool::new(false)),
         }
     }
     
+    /// Current configuration
+    pub fn config(&self) -> &IdleMonitorConfig {
+        &self.config
+    }
+    
+    /// Whether a background cleanup task is running
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+    
+    /// 🔁 Spawn a task calling `cleanup` every `check_interval`
+    ///
+    /// The task only holds a weak reference to `owner` and upgrades it per
+    /// tick, so it never keeps the owner alive; it exits once the owner is
+    /// dropped or the returned handle is stopped.
+    pub fn spawn_background<T, F, Fut>(&self, owner: Weak<T>, cleanup: F) -> IdleMonitorHandle
+    where
+        T: Send + Sync + 'static,
+        F: Fn(Arc<T>) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send,
+    {
+        let interval = self.config.check_interval;
+        let running = Arc::clone(&self.running);
+        running.store(true, Ordering::SeqCst);
+        
+        let task_running = Arc::clone(&running);
+        let task = tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                let Some(owner) = owner.upgrade() else {
+                    log::debug!("⏱️ Idle monitor owner dropped, stopping background task");
+                    break;
+                };
+                cleanup(owner).await;
+            }
+            task_running.store(false, Ordering::SeqCst);
+        });
+        
+        log::info!("⏱️ Idle monitor running every {:?}", interval);
+        IdleMonitorHandle { task, running }
+    }
+    
     /// Create from environment variables
     pub fn from_env() -> Self {
         Self::with_config(IdleMonitorConfig::from_env())
@@ -168,6 +228,7 @@ impl IdleMonitor {
             total_tracked,
             idle_servers,
             oldest_idle_time,
+            monitor_running: self.is_running(),
             config: self.config.clone(),
         }
     }
@@ -179,6 +240,27 @@ impl Default for IdleMonitor {
     }
 }
 
+/// 🛑 Handle to a background idle cleanup task; dropping it stops the task
+#[derive(Debug)]
+pub struct IdleMonitorHandle {
+    task: JoinHandle<()>,
+    running: Arc<AtomicBool>,
+}
+
+impl IdleMonitorHandle {
+    /// Stop the background task
+    pub fn stop(self) {
+        // Drop does the work
+    }
+}
+
+impl Drop for IdleMonitorHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+        self.running.store(false, Ordering::SeqCst);
+    }
+}
+
 /// Statistics about idle monitoring
 #[derive(Debug, Clone)]
 pub struct IdleMonitorStats {
@@ -191,6 +273,9 @@ pub struct IdleMonitorStats {
     /// Time since last use for the oldest idle server
     pub oldest_idle_time: Option<Duration>,
     
+    /// Whether the background cleanup task is running
+    pub monitor_running: bool,
+    
     /// Current configuration
     pub config: IdleMonitorConfig,
 }
@@ -203,11 +288,12 @@ impl IdleMonitorStats {
             .unwrap_or_else(|| "N/A".to_string());
         
         format!(
-            "📊 Tracking {} servers, {} idle (oldest: {}), timeout: {:.1}m",
+            "📊 Tracking {} servers, {} idle (oldest: {}), timeout: {:.1}m, monitor: {}",
             self.total_tracked,
             self.idle_servers,
             oldest,
-            self.config.idle_timeout.as_secs_f64() / 60.0
+            self.config.idle_timeout.as_secs_f64() / 60.0,
+            if self.monitor_running { "running" } else { "stopped" }
         )
     }
 }
@@ -254,6 +340,7 @@ mod tests {
             idle_timeout: Duration::from_millis(100),
             check_interval: Duration::from_millis(50),
             enabled: true,
+            background: false,
         };
         
         let monitor = IdleMonitor::with_config(config);
@@ -302,4 +389,39 @@ mod tests {
         
         assert!(summary.contains("Tracking 1 servers"));
     }
+    
+    #[tokio::test]
+    async fn test_background_task_ticks_and_stops() {
+        use std::sync::atomic::AtomicUsize;
+        
+        let monitor = IdleMonitor::with_config(IdleMonitorConfig {
+            check_interval: Duration::from_millis(10),
+            ..IdleMonitorConfig::default()
+        });
+        let ticks = Arc::new(AtomicUsize::new(0));
+        let handle = monitor.spawn_background(Arc::downgrade(&ticks), |ticks| async move {
+            ticks.fetch_add(1, Ordering::SeqCst);
+        });
+        assert!(monitor.get_stats().await.monitor_running);
+        
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert!(ticks.load(Ordering::SeqCst) >= 2);
+        
+        handle.stop();
+        assert!(!monitor.is_running());
+    }
+    
+    #[tokio::test]
+    async fn test_background_task_does_not_keep_owner_alive() {
+        let monitor = IdleMonitor::with_config(IdleMonitorConfig {
+            check_interval: Duration::from_millis(10),
+            ..IdleMonitorConfig::default()
+        });
+        let owner = Arc::new(());
+        let _handle = monitor.spawn_background(Arc::downgrade(&owner), |_| async {});
+        
+        drop(owner);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!monitor.is_running());
+    }
 }
@@ -3,18 +3,21 @@
 //! Core orchestration and high-level management of LSP servers.
 //! Coordinates between lifecycle management, document tracking, and performance optimization.
 
-use super::{lifecycle::ProcessLifecycle, tracker::DocumentTracker};
-use crate::lsp::cache::LspCache;
+use super::{lifecycle::ProcessLifecycle, tracker::{ChangeDebouncer, DEFAULT_CHANGE_DEBOUNCE, DocumentStateSnapshot, DocumentTracker}};
+use crate::lsp::cache::{CacheKey, LspCache};
 use crate::lsp::client::LspClient;
 use crate::lsp::idle_monitor::IdleMonitor;
-use crate::lsp::performance::{LspMetrics, ConnectionPool, PerformanceTester};
-use crate::lsp::resource::ResourceConfig;
-use crate::lsp::types::{LspError, LspProcess, LspResult, HealthCheckResult};
-use crate::lsp::ProjectDetector;
-use std::collections::HashMap;
+use crate::lsp::performance::{LspMetrics, MetricsSnapshot, ConnectionPool, PerformanceTester};
+use crate::lsp::resource::{EnforcementEvent, ResourceAction, ResourceConfig};
+use crate::lsp::types::{CacheConfig, ColdStartReport, LspError, LspProcess, LspResult, LspTimeoutConfig, HealthCheckResult};
+use crate::lsp::{ProjectDetector, ServerConfig, ServerOverride};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tokio::process::Child;
 use tokio::sync::RwLock;
 
@@ -24,11 +27,17 @@ pub struct LspManagerCore {
     /// Active LSP server processes per project path
     processes: RwLock<HashMap<PathBuf, LspProcess>>,
     /// LSP clients for communication per project path
+    ///
+    /// Keyed by the project root `ProjectDetector` resolves a file to (the
+    /// nearest `Cargo.toml` directory), so every file in a project shares one
+    /// client and one rust-analyzer process.
     clients: RwLock<HashMap<PathBuf, LspClient>>,
     /// Child process handles for cleanup
     children: RwLock<HashMap<PathBuf, Child>>,
     /// Document tracking per project path with performance metrics
     documents: RwLock<HashMap<PathBuf, DocumentTracker>>,
+    /// Coalesces bursts of document updates into single didChange notifications
+    change_debouncer: Arc<ChangeDebouncer>,
     /// Project detector for routing files to projects
     detector: ProjectDetector,
     /// Response cache for performance optimization
@@ -45,6 +54,44 @@ pub struct LspManagerCore {
     lifecycle: ProcessLifecycle,
     /// Idle timeout monitor (v2.1.0)
     idle_monitor: Arc<IdleMonitor>,
+    /// File the open-document set is persisted to across restarts
+    state_file: PathBuf,
+    /// How long a health-check ping may take before the server counts as hung
+    health_timeout: Duration,
+    /// Servers that crashed and exhausted their automatic restarts
+    given_up: RwLock<HashSet<PathBuf>>,
+    /// Per-project locks serializing crash recovery so a crash triggers only one respawn
+    recovery_locks: std::sync::Mutex<HashMap<PathBuf, Arc<tokio::sync::Mutex<()>>>>,
+    /// Serializes first spawns so concurrent requests for a new project share one server
+    spawn_lock: tokio::sync::Mutex<()>,
+    /// Runtime rust-analyzer setting overrides per project, reapplied on every (re)spawn
+    server_settings: RwLock<HashMap<PathBuf, serde_json::Value>>,
+}
+
+/// 💾 Default document state file, `<root>/.empathic/lsp_state.json`
+pub fn default_state_file(root_dir: &Path) -> PathBuf {
+    root_dir.join(".empathic").join("lsp_state.json")
+}
+
+/// 🏓 Default responsiveness probe timeout for health checks
+pub const DEFAULT_HEALTH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 🔗 `file://` URI of a document, as sent to the server
+fn document_uri(file_path: &Path) -> LspResult<lsp_types::Uri> {
+    use std::str::FromStr;
+    let file_url = url::Url::from_file_path(file_path).map_err(|_| LspError::InvalidRequest {
+        message: format!("Invalid file path: {}", file_path.display()),
+    })?;
+    Ok(lsp_types::Uri::from_str(file_url.as_str()).unwrap())
+}
+
+/// 📝 How `open_virtual_document` changed a document, so it can be put back
+#[derive(Debug)]
+pub enum VirtualDocument {
+    /// Opened just for the request; closed afterwards
+    Opened,
+    /// Was already open with this content; restored afterwards
+    Replaced(String),
 }
 
 impl LspManagerCore {
@@ -61,8 +108,15 @@ impl LspManagerCore {
             clients: RwLock::new(HashMap::new()),
             children: RwLock::new(HashMap::new()),
             documents: RwLock::new(HashMap::new()),
+            change_debouncer: Arc::new(ChangeDebouncer::new(DEFAULT_CHANGE_DEBOUNCE)),
+            state_file: default_state_file(&root_dir),
+            health_timeout: DEFAULT_HEALTH_TIMEOUT,
+            given_up: RwLock::new(HashSet::new()),
+            recovery_locks: std::sync::Mutex::new(HashMap::new()),
+            spawn_lock: tokio::sync::Mutex::new(()),
+            server_settings: RwLock::new(HashMap::new()),
+            cache: LspCache::with_config(CacheConfig::default()).with_metrics(metrics.clone()),
             detector: ProjectDetector::new(root_dir),
-            cache: LspCache::new(),
             metrics,
             connection_pool,
             performance_tester,
@@ -84,8 +138,15 @@ impl LspManagerCore {
             clients: RwLock::new(HashMap::new()),
             children: RwLock::new(HashMap::new()),
             documents: RwLock::new(HashMap::new()),
+            change_debouncer: Arc::new(ChangeDebouncer::new(DEFAULT_CHANGE_DEBOUNCE)),
+            state_file: default_state_file(&root_dir),
+            health_timeout: DEFAULT_HEALTH_TIMEOUT,
+            given_up: RwLock::new(HashSet::new()),
+            recovery_locks: std::sync::Mutex::new(HashMap::new()),
+            spawn_lock: tokio::sync::Mutex::new(()),
+            server_settings: RwLock::new(HashMap::new()),
+            cache: LspCache::with_config(CacheConfig::default()).with_metrics(metrics.clone()),
             detector: ProjectDetector::new(root_dir),
-            cache: LspCache::new(),
             metrics,
             connection_pool,
             performance_tester,
@@ -94,11 +155,60 @@ impl LspManagerCore {
         }
     }
 
+    /// 🛠️ Apply user overrides to rust-analyzer and search `extra_path` (ADD_PATH) before PATH
+    pub fn set_server_override(&mut self, overrides: &ServerOverride, extra_path: Vec<PathBuf>) {
+        let server = ServerConfig::rust_analyzer().with_override(overrides);
+        if !overrides.is_empty() {
+            log::info!("🛠️ rust-analyzer configured: command '{}', args {:?}", server.server_command, server.args);
+        }
+        self.lifecycle.set_server(server, extra_path);
+    }
+
+    /// ⏱️ Coalesce didChange notifications over `window` (zero sends every change)
+    pub fn set_change_debounce(&mut self, window: Duration) {
+        self.change_debouncer = Arc::new(ChangeDebouncer::new(window));
+    }
+
+    /// 💾 Persist the open-document set to `path` instead of the default
+    pub fn set_state_file(&mut self, path: PathBuf) {
+        self.state_file = path;
+    }
+
+    /// 🏓 Flag servers that don't answer a health-check ping within `timeout`
+    pub fn set_health_timeout(&mut self, timeout: Duration) {
+        self.health_timeout = timeout;
+    }
+
+    /// 🗄️ Replace the response cache with one built from `config` (loading its persisted entries)
+    pub fn set_cache_config(&mut self, config: CacheConfig) {
+        self.cache = LspCache::with_config(config).with_metrics(self.metrics.clone());
+    }
+
+    /// ⏱️ Per-method request timeouts for clients spawned from now on
+    pub fn set_request_timeouts(&mut self, timeouts: LspTimeoutConfig) {
+        self.lifecycle.set_request_timeouts(timeouts);
+    }
+
     /// 📊 Get performance metrics summary
     pub fn performance_summary(&self) -> String {
         self.metrics.summary()
     }
 
+    /// 📊 Structured metrics: totals, per-method latencies, queue depth
+    pub fn metrics_snapshot(&self) -> MetricsSnapshot {
+        self.metrics.snapshot()
+    }
+
+    /// 🔄 Zero the performance counters
+    pub fn reset_metrics(&self) {
+        self.metrics.reset();
+    }
+
+    /// 🔗 (created, reused) LSP client counts
+    pub fn client_counts(&self) -> (u64, u64) {
+        self.metrics.client_counts()
+    }
+
     /// 🧪 Run performance benchmark for LSP operation
     pub async fn benchmark_operation<F, T>(&self, method: &str, operation: F) -> LspResult<T>
     where
@@ -163,6 +273,7 @@ impl LspManagerCore {
             {
                 let processes = self.processes.read().await;
                 if let Some(process) = processes.get(&project.root_path) {
+                    self.metrics.record_client(true);
                     return Ok(process.clone());
                 }
             }
@@ -183,20 +294,49 @@ impl LspManagerCore {
     /// Internal method for server spawning (without benchmarking to avoid recursion)
     /// Internal method for server spawning (without benchmarking to avoid recursion)
     async fn get_or_spawn_server_internal(&self, project_path: &Path) -> LspResult<LspClient> {
-        // Check if client already exists
-        {
-            let clients = self.clients.read().await;
-            if let Some(client) = clients.get(project_path) {
-                // Mark as used before returning existing client
-                self.mark_server_used(project_path).await;
-                return Ok(client.clone());
-            }
+        if self.given_up.read().await.contains(project_path) {
+            return Err(LspError::ServerCrashed { project_path: project_path.to_path_buf() });
+        }
+
+        // 💀 A server that died since last use is respawned with backoff
+        if self.has_exited(project_path).await {
+            return self.recover_server(project_path).await;
+        }
+
+        if let Some(client) = self.existing_client(project_path).await {
+            return Ok(client);
+        }
+
+        // Concurrent first requests for a project wait here, then find the winner's client
+        let _spawn_guard = self.spawn_lock.lock().await;
+        if let Some(client) = self.existing_client(project_path).await {
+            return Ok(client);
         }
         
         // Spawn new rust-analyzer process using lifecycle manager
-        let (process, client, child) = self.lifecycle.spawn_rust_analyzer(project_path).await?;
+        let settings = self.server_settings(project_path).await;
+        let (process, client, child) = self.lifecycle.spawn_rust_analyzer(project_path, settings).await?;
+        self.register_server(project_path, process, client.clone(), child).await;
         
-        // Store everything
+        Ok(client)
+    }
+
+    /// 🔗 Client of an already running server for `project_path`, never spawning one
+    pub async fn running_client(&self, project_path: &Path) -> Option<LspClient> {
+        self.existing_client(project_path).await
+    }
+
+    /// 🔗 Running client for a project, marked as used and counted as a reuse
+    async fn existing_client(&self, project_path: &Path) -> Option<LspClient> {
+        let client = self.clients.read().await.get(project_path).cloned()?;
+        self.mark_server_used(project_path).await;
+        self.metrics.record_client(true);
+        Some(client)
+    }
+
+    /// 📋 Store a freshly spawned server and start tracking its documents
+    async fn register_server(&self, project_path: &Path, process: LspProcess, client: LspClient, child: Child) {
+        client.attach_metrics(self.metrics.clone());
         {
             let mut processes = self.processes.write().await;
             let mut clients = self.clients.write().await;
@@ -204,15 +344,206 @@ impl LspManagerCore {
             let mut documents = self.documents.write().await;
 
             processes.insert(project_path.to_path_buf(), process);
-            clients.insert(project_path.to_path_buf(), client.clone());
+            clients.insert(project_path.to_path_buf(), client);
             children.insert(project_path.to_path_buf(), child);
             documents.insert(project_path.to_path_buf(), DocumentTracker::new(self.metrics.clone()));
         }
+        self.metrics.record_client(false);
         
         // Mark newly spawned server as used
         self.mark_server_used(project_path).await;
-        
-        Ok(client)
+    }
+
+    // === 🔄 Crash Recovery ===
+
+    /// Whether the server process for a project has exited
+    async fn has_exited(&self, project_path: &Path) -> bool {
+        let mut children = self.children.write().await;
+        children
+            .get_mut(project_path)
+            .is_some_and(|child| matches!(child.try_wait(), Ok(Some(_))))
+    }
+
+    /// 🔄 Respawn every server whose process has exited
+    ///
+    /// Returns the projects that were brought back.
+    pub async fn recover_crashed_servers(&self) -> Vec<PathBuf> {
+        let exited = {
+            let mut children = self.children.write().await;
+            ProcessLifecycle::detect_exited(&mut children)
+        };
+
+        let mut recovered = Vec::new();
+        for (project_path, exit_code) in exited {
+            log::warn!("💀 LSP server for {} exited (code: {:?})", project_path.display(), exit_code);
+            if self.recover_server(&project_path).await.is_ok() {
+                recovered.push(project_path);
+            }
+        }
+        recovered
+    }
+
+    /// 🔄 Replace a crashed server: backoff, respawn, then re-open tracked documents
+    ///
+    /// Gives up after `max_restart_attempts`; the project is then reported unhealthy
+    /// until `restart_server` is called explicitly.
+    async fn recover_server(&self, project_path: &Path) -> LspResult<LspClient> {
+        // Only this project waits out the backoff; other projects recover independently
+        let lock = self.recovery_lock(project_path);
+        let guard = lock.lock().await;
+
+        // Another caller may have recovered it while we waited for the lock
+        if !self.has_exited(project_path).await
+            && let Some(client) = self.clients.read().await.get(project_path)
+        {
+            return Ok(client.clone());
+        }
+
+        let documents = self.forget_server(project_path).await;
+
+        loop {
+            if !self.lifecycle.can_restart(project_path).await {
+                log::error!("🚑 Giving up on LSP server for {} after repeated crashes", project_path.display());
+                self.given_up.write().await.insert(project_path.to_path_buf());
+                return Err(LspError::ServerCrashed { project_path: project_path.to_path_buf() });
+            }
+
+            let delay = self.lifecycle.restart_delay(project_path).await;
+            log::warn!("🔄 Restarting crashed LSP server for {} in {:?}", project_path.display(), delay);
+            tokio::time::sleep(delay).await;
+            self.lifecycle.record_restart(project_path, "process exited".to_string()).await;
+
+            let settings = self.server_settings(project_path).await;
+            match self.lifecycle.spawn_rust_analyzer(project_path, settings).await {
+                Ok((process, client, child)) => {
+                    self.register_server(project_path, process, client.clone(), child).await;
+                    drop(guard); // re-opening may itself detect a new crash
+                    self.reopen_documents(&documents).await;
+                    return Ok(client);
+                }
+                Err(e) => {
+                    log::error!("❌ Restart of {} failed: {}", project_path.display(), e);
+                    self.lifecycle.record_failed_restart().await;
+                }
+            }
+        }
+    }
+
+    /// Recovery lock of one project
+    fn recovery_lock(&self, project_path: &Path) -> Arc<tokio::sync::Mutex<()>> {
+        let mut locks = self.recovery_locks.lock().unwrap_or_else(|e| e.into_inner());
+        Arc::clone(locks.entry(project_path.to_path_buf()).or_default())
+    }
+
+    /// Drop all bookkeeping for a dead server, returning the documents it had open
+    async fn forget_server(&self, project_path: &Path) -> Vec<super::tracker::TrackedDocument> {
+        let mut processes = self.processes.write().await;
+        let mut clients = self.clients.write().await;
+        let mut children = self.children.write().await;
+        let mut documents = self.documents.write().await;
+
+        processes.remove(project_path);
+        clients.remove(project_path);
+        children.remove(project_path);
+        documents.remove(project_path)
+            .map(|tracker| tracker.snapshot())
+            .unwrap_or_default()
+    }
+
+    /// Re-send didOpen for documents that were open before a restart
+    async fn reopen_documents(&self, documents: &[super::tracker::TrackedDocument]) {
+        for document in documents {
+            // Boxed: opening goes back through get_client, which can itself trigger recovery
+            if let Err(e) = Box::pin(self.open_document_with_version(&document.path, document.version)).await {
+                log::warn!("📄 Failed to re-open {} after restart: {}", document.path.display(), e);
+            }
+        }
+    }
+
+    /// 🔄 Deliberately restart a project's server, keeping its open documents
+    ///
+    /// Also clears any "given up" state and restart history from earlier crashes.
+    pub async fn restart_server(&self, project_path: &Path) -> LspResult<LspProcess> {
+        self.given_up.write().await.remove(project_path);
+        self.lifecycle.reset_restarts(project_path).await;
+
+        let documents = self.documents.read().await
+            .get(project_path)
+            .map(|tracker| tracker.snapshot())
+            .unwrap_or_default();
+
+        self.shutdown_server(project_path).await?;
+        self.get_or_spawn_server_internal(project_path).await?;
+        self.reopen_documents(&documents).await;
+
+        self.processes.read().await
+            .get(project_path)
+            .cloned()
+            .ok_or_else(|| LspError::ServerCrashed { project_path: project_path.to_path_buf() })
+    }
+
+    /// 🥶 Measure a full cold start: spawn → initialize → first diagnostics
+    ///
+    /// Any running server for the project is shut down first so the numbers
+    /// reflect a genuinely fresh start. The new server stays running afterwards.
+    pub async fn measure_cold_start(
+        &self,
+        project_path: &Path,
+        ready_file: &Path,
+        ready_timeout: Duration,
+    ) -> LspResult<ColdStartReport> {
+        let is_running = self.processes.read().await.contains_key(project_path);
+        if is_running {
+            log::info!("🥶 Shutting down {} for cold-start measurement", project_path.display());
+            self.shutdown_server(project_path).await?;
+        }
+
+        let total_start = Instant::now();
+
+        let settings = self.server_settings(project_path).await;
+        let (process, client, child, timings) = self
+            .benchmark_operation("cold_start", self.lifecycle.spawn_rust_analyzer_timed(project_path, settings))
+            .await?;
+        let server_name = process.server_name.clone();
+        let process_id = process.process_id;
+        self.register_server(project_path, process, client.clone(), child).await;
+
+        // ⏳ Time-to-ready: subscribe before didOpen so the first diagnostics can't be missed
+        let ready_start = Instant::now();
+        let mut notifications = client.subscribe_notifications();
+        self.open_document_with_version(ready_file, 1).await?;
+
+        let reached_ready = tokio::time::timeout(ready_timeout, async {
+            loop {
+                match notifications.recv().await {
+                    Ok(notification) if notification.method == "textDocument/publishDiagnostics" => return true,
+                    Ok(_) | Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => return false,
+                }
+            }
+        })
+        .await
+        .unwrap_or(false);
+        let ready = ready_start.elapsed();
+
+        let to_ms = |d: Duration| d.as_secs_f64() * 1000.0;
+        let report = ColdStartReport {
+            project_path: project_path.to_path_buf(),
+            server_name,
+            process_id,
+            spawn_ms: to_ms(timings.spawn),
+            initialize_ms: to_ms(timings.initialize),
+            ready_ms: to_ms(ready),
+            total_ms: to_ms(total_start.elapsed()),
+            reached_ready,
+        };
+
+        log::info!(
+            "🥶 Cold start for {}: spawn {:.0}ms, initialize {:.0}ms, ready {:.0}ms (total {:.0}ms)",
+            project_path.display(), report.spawn_ms, report.initialize_ms, report.ready_ms, report.total_ms
+        );
+
+        Ok(report)
     }
 
     /// 🎯 Get LSP client for the given file path
@@ -232,6 +563,20 @@ impl LspManagerCore {
             })
     }
 
+    /// ⏳ Wait until the server for `path`'s project has finished indexing
+    ///
+    /// Spawns the server if needed. Returns how long the wait took.
+    pub async fn wait_until_ready(&self, path: &Path, ready_timeout: Duration) -> LspResult<Duration> {
+        let client = self.get_client(path).await?;
+        if !client.is_ready() {
+            log::info!("⏳ Waiting up to {}ms for rust-analyzer to finish indexing ({})",
+                ready_timeout.as_millis(), client.readiness().active_work().join(", "));
+        }
+        let waited = client.wait_until_ready(ready_timeout).await?;
+        log::debug!("✅ LSP ready after {}ms", waited.as_millis());
+        Ok(waited)
+    }
+
     /// 📄 Ensure a document is open in the LSP server (sends didOpen if needed)
     ///
     /// This method handles document synchronization with the LSP server:
@@ -242,6 +587,72 @@ impl LspManagerCore {
     ///
     /// This should be called before making any LSP requests that require document context.
     pub async fn ensure_document_open(&self, file_path: &Path) -> LspResult<()> {
+        // ⏱️ Requests must see the latest content, not what a pending debounce holds
+        if self.change_debouncer.flush(&document_uri(file_path)?).await? {
+            log::debug!("⏱️ Flushed pending change before request: {}", file_path.display());
+        }
+        if !self.open_document_with_version(file_path, 1).await? {
+            log::debug!("📄 Document already open: {}", file_path.display());
+            return Ok(());
+        }
+
+        // 🔥 HEURISTIC FIX: Give rust-analyzer a moment to start processing the file
+        // This is a simple delay to reduce the likelihood of race conditions.
+        // Most files index in <1 second, but we add a 2-second buffer to be safe.
+        // First hover/diagnostics request may still be slow, but much better than 55s timeout.
+        log::debug!("⏳ Waiting 2s for rust-analyzer to start indexing...");
+        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+        log::debug!("✅ Document opened, indexing should be underway");
+
+        Ok(())
+    }
+
+    /// 📝 Replace a document's content in the server (textDocument/didChange)
+    ///
+    /// Opens the document first if needed. Changes are coalesced over the
+    /// debounce window; `ensure_document_open` flushes before any request.
+    pub async fn update_document(&self, file_path: &Path, content: &str) -> LspResult<()> {
+        let project = self.require_project(file_path).await?;
+        self.open_document_with_version(file_path, 1).await?;
+        let client = self.get_client(file_path).await?;
+        let file_uri = document_uri(file_path)?;
+
+        let version = {
+            let mut documents = self.documents.write().await;
+            let Some(tracker) = documents.get_mut(&project.root_path) else {
+                return Ok(());
+            };
+            if tracker.get_content(&file_uri).is_some_and(|current| current == content) {
+                return Ok(());
+            }
+            tracker.update_content(&file_uri, content.to_string());
+            tracker.document_version(&file_uri).unwrap_or(1)
+        };
+        self.cache.invalidate_file(file_path).await;
+
+        log::debug!("📝 Queued change v{} for {} ({} chars)", version, file_path.display(), content.len());
+        self.change_debouncer.queue(file_uri, version, content.to_string(), client).await
+    }
+
+    /// ⏱️ Send every debounced change now, before a request that reads the whole workspace
+    pub async fn flush_pending_changes(&self) -> LspResult<usize> {
+        self.change_debouncer.flush_all().await
+    }
+
+    /// ⏱️ Documents with a change waiting for the debounce window to close
+    pub async fn pending_change_count(&self) -> usize {
+        self.change_debouncer.pending_count().await
+    }
+
+    /// 📄 Send didOpen with the given version unless already open
+    ///
+    /// Returns `true` when the document was newly opened.
+    async fn open_document_with_version(&self, file_path: &Path, version: i32) -> LspResult<bool> {
+        self.open_document_with_text(file_path, version, None).await
+    }
+
+    /// 📄 Send didOpen with `text`, or the file's content when `None`, unless already open
+    async fn open_document_with_text(&self, file_path: &Path, version: i32, text: Option<&str>) -> LspResult<bool> {
         use lsp_types::*;
         use std::str::FromStr;
         use url::Url;
@@ -260,17 +671,19 @@ impl LspManagerCore {
             if let Some(tracker) = documents.get(&project.root_path)
                 && tracker.is_open(&file_uri)
             {
-                log::debug!("📄 Document already open: {}", file_path.display());
-                return Ok(());
+                return Ok(false);
             }
         }
 
-        // Read file content
-        let content = tokio::fs::read_to_string(file_path).await.map_err(|e| {
-            LspError::InvalidRequest {
-                message: format!("Failed to read file {}: {}", file_path.display(), e),
-            }
-        })?;
+        // Read file content unless the caller supplied it
+        let content = match text {
+            Some(text) => text.to_string(),
+            None => tokio::fs::read_to_string(file_path).await.map_err(|e| {
+                LspError::InvalidRequest {
+                    message: format!("Failed to read file {}: {}", file_path.display(), e),
+                }
+            })?,
+        };
 
         // Get client (this ensures server is spawned)
         let client = self.get_client(file_path).await?;
@@ -287,7 +700,7 @@ impl LspManagerCore {
             text_document: TextDocumentItem {
                 uri: file_uri.clone(),
                 language_id: language_id.to_string(),
-                version: 1,
+                version,
                 text: content.clone(),
             },
         };
@@ -298,23 +711,211 @@ impl LspManagerCore {
         {
             let mut documents = self.documents.write().await;
             if let Some(tracker) = documents.get_mut(&project.root_path) {
-                tracker.add_document(file_uri.clone(), content);
+                tracker.add_document_with_version(file_uri.clone(), content, version);
             }
         }
 
         log::info!("📄 Opened document in LSP: {}", file_path.display());
-        
-        // 🔥 HEURISTIC FIX: Give rust-analyzer a moment to start processing the file
-        // This is a simple delay to reduce the likelihood of race conditions.
-        // Most files index in <1 second, but we add a 2-second buffer to be safe.
-        // First hover/diagnostics request may still be slow, but much better than 55s timeout.
-        log::debug!("⏳ Waiting 2s for rust-analyzer to start indexing...");
-        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
-        log::debug!("✅ Document opened, indexing should be underway");
+        Ok(true)
+    }
 
+    /// 📄 Send didClose and stop tracking a document (no-op when it isn't open)
+    pub async fn close_document(&self, file_path: &Path) -> LspResult<()> {
+        let project = self.require_project(file_path).await?;
+        let file_uri = document_uri(file_path)?;
+        {
+            let documents = self.documents.read().await;
+            if !documents.get(&project.root_path).is_some_and(|tracker| tracker.is_open(&file_uri)) {
+                return Ok(());
+            }
+        }
+
+        // A change still waiting in the debouncer must not arrive after the close
+        self.change_debouncer.flush(&file_uri).await?;
+        let client = self.get_client(file_path).await?;
+        let params = lsp_types::DidCloseTextDocumentParams {
+            text_document: lsp_types::TextDocumentIdentifier { uri: file_uri.clone() },
+        };
+        client.send_notification("textDocument/didClose", Some(serde_json::to_value(params)?)).await?;
+
+        if let Some(tracker) = self.documents.write().await.get_mut(&project.root_path) {
+            tracker.remove_document(&file_uri);
+        }
+        self.cache.invalidate_file(file_path).await;
+        log::debug!("📄 Closed document: {}", file_path.display());
         Ok(())
     }
 
+    /// 📄 Send didOpen unless already open, without waiting for indexing
+    pub async fn open_document(&self, file_path: &Path) -> LspResult<bool> {
+        self.open_document_with_version(file_path, 1).await
+    }
+
+    /// 📄 Whether the server for `file_path` has it open (false outside any project)
+    async fn is_document_open(&self, file_path: &Path) -> bool {
+        let Ok(Some(project)) = self.detector.find_project_for_file(file_path) else {
+            return false;
+        };
+        let Ok(file_uri) = document_uri(file_path) else {
+            return false;
+        };
+        self.documents.read().await
+            .get(&project.root_path)
+            .is_some_and(|tracker| tracker.is_open(&file_uri))
+    }
+
+    /// 💾 Tell the server a file was rewritten on disk (didChange + didSave)
+    ///
+    /// Only documents the server has open are synced - closed ones are read
+    /// from disk on their next didOpen, so no server is spawned for them.
+    /// Returns `true` when the server was notified.
+    pub async fn sync_saved_document(&self, file_path: &Path, content: &str) -> LspResult<bool> {
+        self.cache.invalidate_file(file_path).await;
+        if !self.is_document_open(file_path).await {
+            return Ok(false);
+        }
+
+        self.update_document(file_path, content).await?;
+        let file_uri = document_uri(file_path)?;
+        // The save must follow the change it describes
+        self.change_debouncer.flush(&file_uri).await?;
+        let client = self.get_client(file_path).await?;
+        let params = lsp_types::DidSaveTextDocumentParams {
+            text_document: lsp_types::TextDocumentIdentifier { uri: file_uri },
+            text: None,
+        };
+        client.send_notification("textDocument/didSave", Some(serde_json::to_value(params)?)).await?;
+        log::debug!("💾 Synced saved document: {}", file_path.display());
+        Ok(true)
+    }
+
+    /// 🚚 Follow a move on disk: didClose documents open under `from`, didOpen them under `to`
+    ///
+    /// `from` may be a file or a directory. Returns the paths reopened.
+    pub async fn rename_documents(&self, from: &Path, to: &Path) -> LspResult<Vec<PathBuf>> {
+        let moved: Vec<(PathBuf, PathBuf)> = {
+            let documents = self.documents.read().await;
+            documents
+                .iter()
+                .flat_map(|(root, tracker)| {
+                    tracker.snapshot().into_iter()
+                        .filter(|document| document.path.starts_with(from))
+                        .map(|document| (root.clone(), document.path))
+                })
+                .collect()
+        };
+
+        let mut reopened = Vec::new();
+        for (root, old_path) in moved {
+            // The old path is gone, so close through the server that had it open
+            let file_uri = document_uri(&old_path)?;
+            self.change_debouncer.flush(&file_uri).await?;
+            if let Some(client) = self.existing_client(&root).await {
+                let params = lsp_types::DidCloseTextDocumentParams {
+                    text_document: lsp_types::TextDocumentIdentifier { uri: file_uri.clone() },
+                };
+                client.send_notification("textDocument/didClose", Some(serde_json::to_value(params)?)).await?;
+            }
+            if let Some(tracker) = self.documents.write().await.get_mut(&root) {
+                tracker.remove_document(&file_uri);
+            }
+            self.cache.invalidate_file(&old_path).await;
+
+            let relative = old_path.strip_prefix(from).unwrap_or(Path::new(""));
+            let new_path = if relative.as_os_str().is_empty() { to.to_path_buf() } else { to.join(relative) };
+            match self.open_document(&new_path).await {
+                Ok(_) => reopened.push(new_path),
+                Err(e) => log::warn!("⚠️ Could not reopen moved document {}: {}", new_path.display(), e),
+            }
+        }
+        Ok(reopened)
+    }
+
+    /// 📝 Make the server see `content` for `file_path` instead of what's on disk
+    ///
+    /// Open documents get a didChange, others a didOpen with `content`. Pass
+    /// the result to `close_virtual_document` once the request is done.
+    pub async fn open_virtual_document(&self, file_path: &Path, content: &str) -> LspResult<VirtualDocument> {
+        let project = self.require_project(file_path).await?;
+        let file_uri = document_uri(file_path)?;
+        let previous = self.documents.read().await
+            .get(&project.root_path)
+            .and_then(|tracker| tracker.get_content(&file_uri).cloned());
+
+        // Responses cached for the saved content don't describe this buffer
+        self.cache.invalidate_file(file_path).await;
+        match previous {
+            Some(previous) => {
+                self.update_document(file_path, content).await?;
+                Ok(VirtualDocument::Replaced(previous))
+            }
+            None => {
+                self.open_document_with_text(file_path, 1, Some(content)).await?;
+                Ok(VirtualDocument::Opened)
+            }
+        }
+    }
+
+    /// 📝 Undo `open_virtual_document`: restore the previous content or close the document
+    pub async fn close_virtual_document(&self, file_path: &Path, document: VirtualDocument) -> LspResult<()> {
+        match document {
+            VirtualDocument::Replaced(previous) => {
+                self.update_document(file_path, &previous).await?;
+                self.cache.invalidate_file(file_path).await;
+                Ok(())
+            }
+            VirtualDocument::Opened => self.close_document(file_path).await,
+        }
+    }
+
+    // === 💾 Document State Persistence ===
+
+    /// Path of the file the open-document set is persisted to
+    pub fn state_file(&self) -> &Path {
+        &self.state_file
+    }
+
+    /// 💾 Snapshot every open document (paths and versions only)
+    pub async fn snapshot_documents(&self) -> DocumentStateSnapshot {
+        let documents = self.documents.read().await;
+        let mut tracked: Vec<_> = documents.values().flat_map(|tracker| tracker.snapshot()).collect();
+        tracked.sort_by(|a, b| a.path.cmp(&b.path));
+        DocumentStateSnapshot::new(tracked)
+    }
+
+    /// 💾 Persist the current open-document set to the state file
+    pub async fn save_document_state(&self) -> LspResult<DocumentStateSnapshot> {
+        let snapshot = self.snapshot_documents().await;
+        snapshot.save(&self.state_file)?;
+        Ok(snapshot)
+    }
+
+    /// ♻️ Re-open every document recorded in the state file
+    ///
+    /// Documents that no longer exist or fail to open are skipped.
+    /// Returns the paths that were re-opened.
+    pub async fn restore_document_state(&self) -> LspResult<Vec<PathBuf>> {
+        let Some(snapshot) = DocumentStateSnapshot::load(&self.state_file)? else {
+            return Ok(Vec::new());
+        };
+
+        let mut reopened = Vec::new();
+        for document in snapshot.documents {
+            if !document.path.exists() {
+                log::debug!("♻️ Skipping vanished document: {}", document.path.display());
+                continue;
+            }
+
+            match self.open_document_with_version(&document.path, document.version).await {
+                Ok(_) => reopened.push(document.path),
+                Err(e) => log::warn!("♻️ Failed to restore document {}: {}", document.path.display(), e),
+            }
+        }
+
+        log::info!("♻️ Restored {} open documents from {}", reopened.len(), self.state_file.display());
+        Ok(reopened)
+    }
+
     /// 🛑 Gracefully shutdown a specific LSP server
     pub async fn shutdown_server(&self, project_path: &Path) -> LspResult<()> {
         // Close all documents for this server first
@@ -336,7 +937,25 @@ impl LspManagerCore {
     }
 
     /// 🛑 Shutdown all LSP servers
+    ///
+    /// The open-document set is persisted first so the next start can restore it;
+    /// with nothing open, any stale state file is removed instead. The response
+    /// cache is written to disk too when persistence is enabled.
     pub async fn shutdown_all(&self) -> LspResult<()> {
+        if let Err(e) = self.cache.save_to_disk().await {
+            log::warn!("💾 Failed to persist LSP cache: {}", e);
+        }
+
+        let snapshot = self.snapshot_documents().await;
+        let persisted = if snapshot.is_empty() {
+            DocumentStateSnapshot::clear(&self.state_file)
+        } else {
+            snapshot.save(&self.state_file)
+        };
+        if let Err(e) = persisted {
+            log::warn!("💾 Failed to persist document state: {}", e);
+        }
+
         // Clear all documents
         {
             let mut documents = self.documents.write().await;
@@ -355,6 +974,72 @@ impl LspManagerCore {
         Ok(())
     }
 
+    /// ⚙️ Effective rust-analyzer settings for a project: defaults plus overrides
+    pub async fn server_settings(&self, project_path: &Path) -> serde_json::Value {
+        let overrides = self.server_settings.read().await;
+        self.lifecycle.server_config().settings_with(overrides.get(project_path))
+    }
+
+    /// ⚙️ Merge `settings` into a project's rust-analyzer configuration
+    ///
+    /// The overrides are kept so restarts reapply them, and a running server
+    /// gets `workspace/didChangeConfiguration`. rust-analyzer applies most
+    /// settings live (`checkOnSave`, `check.*`, `diagnostics.*`,
+    /// `completion.*`, `inlayHints.*`); `cargo.*` and `procMacro.*` changes
+    /// trigger a workspace reload that can take as long as a cold start, and
+    /// startup-only options such as `server.extraEnv` or `cachePriming.*` need
+    /// `shutdown_server` before they apply. Returns the effective settings.
+    pub async fn update_server_config(&self, project_path: &Path, settings: serde_json::Value) -> LspResult<serde_json::Value> {
+        if !settings.is_object() {
+            return Err(LspError::InvalidRequest {
+                message: "server settings must be a JSON object".to_string(),
+            });
+        }
+
+        {
+            let mut overrides = self.server_settings.write().await;
+            let entry = overrides.entry(project_path.to_path_buf())
+                .or_insert_with(|| serde_json::json!({}));
+            crate::lsp::server_config::merge_settings(entry, &settings);
+        }
+        let effective = self.server_settings(project_path).await;
+
+        let client = self.clients.read().await.get(project_path).cloned();
+        if let Some(client) = client {
+            let section = self.lifecycle.server_config().settings_section.clone().unwrap_or_default();
+            client.did_change_configuration(&section, effective.clone()).await?;
+            // Diagnostics and analysis results may change with the new settings
+            self.cache.invalidate_project(project_path).await;
+            log::info!("⚙️ Sent didChangeConfiguration to {}", project_path.display());
+        }
+
+        Ok(effective)
+    }
+
+    /// 💀 Kill every LSP server without the shutdown handshake
+    ///
+    /// Last resort when `shutdown_all` overruns its deadline; dropping the
+    /// timed-out future releases the locks it held. Returns how many servers
+    /// were killed.
+    pub async fn kill_all(&self) -> usize {
+        self.documents.write().await.clear();
+
+        let mut processes = self.processes.write().await;
+        let mut clients = self.clients.write().await;
+        let mut children = self.children.write().await;
+
+        for (project_path, child) in children.iter_mut() {
+            if let Err(e) = child.start_kill() {
+                log::warn!("💀 Failed to kill LSP server for {}: {}", project_path.display(), e);
+            }
+        }
+        let killed = children.len();
+        children.clear();
+        clients.clear();
+        processes.clear();
+        killed
+    }
+
     /// 📊 Get status of all running LSP servers
     pub async fn get_server_status(&self) -> Vec<LspProcess> {
         let processes = self.processes.read().await;
@@ -362,9 +1047,54 @@ impl LspManagerCore {
     }
 
     /// 🏥 Health check for LSP servers
+    ///
+    /// A server is healthy when its process is alive and it answers a
+    /// responsiveness probe within the health timeout (LSP_HEALTH_TIMEOUT, 5s by default).
     pub async fn health_check(&self) -> LspResult<Vec<(PathBuf, bool)>> {
-        let children = self.children.read().await;
-        self.lifecycle.health_check(&children).await
+        let probes = self.probe_servers().await;
+        let mut children = self.children.write().await;
+        let liveness = self.lifecycle.health_check(&mut children).await?;
+        Ok(liveness
+            .into_iter()
+            .map(|(project, alive)| {
+                let responsive = !matches!(probes.get(&project), Some(None));
+                (project, alive && responsive)
+            })
+            .collect())
+    }
+
+    /// 🏓 Ping every running server concurrently
+    ///
+    /// Maps each project to its round-trip latency, or `None` when the server
+    /// did not answer in time (hung or stuck in a long operation).
+    pub async fn probe_servers(&self) -> HashMap<PathBuf, Option<Duration>> {
+        let clients: Vec<(PathBuf, LspClient)> = self.clients.read().await
+            .iter()
+            .map(|(project, client)| (project.clone(), client.clone()))
+            .collect();
+        let probe_timeout = self.health_timeout;
+
+        let probes: Vec<_> = clients
+            .into_iter()
+            .map(|(project, client)| tokio::spawn(async move {
+                let latency = match client.ping(probe_timeout).await {
+                    Ok(latency) => Some(latency),
+                    Err(e) => {
+                        log::warn!("🏓 LSP server for {} is unresponsive: {}", project.display(), e);
+                        None
+                    }
+                };
+                (project, latency)
+            }))
+            .collect();
+
+        let mut results = HashMap::new();
+        for probe in probes {
+            if let Ok((project, latency)) = probe.await {
+                results.insert(project, latency);
+            }
+        }
+        results
     }
 
     /// Get the project detector
@@ -386,6 +1116,42 @@ impl LspManagerCore {
         &self.cache
     }
 
+    /// ⚡ Answer `request` from the response cache when it was computed from the current document
+    ///
+    /// Entries are keyed by `key` plus the version of the open document, so a
+    /// didChange makes them miss even before invalidation drops them. Only
+    /// `Some` responses are stored: an empty answer may just mean the server
+    /// is still indexing.
+    pub async fn cached_request<T, F>(&self, key: CacheKey, request: F) -> LspResult<Option<T>>
+    where
+        T: Serialize + DeserializeOwned,
+        F: Future<Output = LspResult<Option<T>>>,
+    {
+        let version = match key.file_path() {
+            Some(file_path) => self.document_version(file_path).await,
+            None => None,
+        };
+        if let Some(value) = self.cache.get_versioned(&key, version).await {
+            log::debug!("⚡ Cache hit for {}", key.method());
+            return Ok(Some(value));
+        }
+
+        let response = request.await?;
+        if let Some(value) = &response {
+            self.cache.set_versioned(key, value, version).await?;
+        }
+        Ok(response)
+    }
+
+    /// 📄 Version of `file_path` last sent to its server (None when not open)
+    async fn document_version(&self, file_path: &Path) -> Option<i32> {
+        let project = self.detector.find_project_for_file(file_path).ok()??;
+        let file_uri = document_uri(file_path).ok()?;
+        self.documents.read().await
+            .get(&project.root_path)?
+            .document_version(&file_uri)
+    }
+
     /// 🗑️ Invalidate cache for a specific file
     pub async fn invalidate_file_cache(&self, file_path: &Path) {
         self.cache.invalidate_file(file_path).await;
@@ -423,10 +1189,76 @@ impl LspManagerCore {
         self.lifecycle.check_process_limits(pid).await
     }
 
+    /// 🚨 Act on servers that stayed over their resource limits
+    ///
+    /// Only processes this manager spawned are considered (`ps` also lists
+    /// unrelated rust-analyzer instances). Returns the actions taken; empty
+    /// when no enforcement action is configured.
+    pub async fn enforce_resource_limits(&self) -> Vec<EnforcementEvent> {
+        let Some(action) = self.lifecycle.enforcement_action() else {
+            return Vec::new();
+        };
+        let violations = self.lifecycle.sustained_violations().await;
+        if violations.is_empty() {
+            return Vec::new();
+        }
+
+        let owners: HashMap<u32, PathBuf> = self.processes.read().await
+            .iter()
+            .map(|(project_path, process)| (process.process_id, project_path.clone()))
+            .collect();
+
+        let mut events = Vec::new();
+        for (pid, reason) in violations {
+            let Some(project_path) = owners.get(&pid) else {
+                continue;
+            };
+            let outcome = match action {
+                ResourceAction::Warn => Ok(()),
+                ResourceAction::Restart => self.restart_server(project_path).await.map(|_| ()),
+                ResourceAction::Kill => self.shutdown_server(project_path).await,
+            };
+            match &outcome {
+                Ok(()) => log::warn!("🚨 LSP server {} for {} over limits ({}): {}", pid, project_path.display(), reason, action.name()),
+                Err(e) => log::error!("🚨 Failed to {} LSP server {} for {}: {}", action.name(), pid, project_path.display(), e),
+            }
+
+            let event = EnforcementEvent {
+                pid,
+                project_path: project_path.clone(),
+                action,
+                reason,
+                succeeded: outcome.is_ok(),
+                timestamp: Instant::now(),
+            };
+            self.lifecycle.record_enforcement(event.clone()).await;
+            events.push(event);
+        }
+        events
+    }
+
+    /// Recent resource enforcement actions, oldest first
+    pub async fn enforcement_events(&self) -> Vec<EnforcementEvent> {
+        self.lifecycle.enforcement_events().await
+    }
+
+    /// How often the background resource monitor runs
+    pub fn monitor_interval(&self) -> Duration {
+        self.lifecycle.monitor_interval()
+    }
+
     /// Perform comprehensive health check with resource monitoring
     pub async fn comprehensive_health_check(&self) -> LspResult<HealthCheckResult> {
-        let children = self.children.read().await;
-        self.lifecycle.comprehensive_health_check(&children).await
+        let probes = self.probe_servers().await;
+        let given_up = self.given_up.read().await.clone();
+        let mut result = {
+            let mut children = self.children.write().await;
+            self.lifecycle.comprehensive_health_check(&mut children, &given_up, &probes).await?
+        };
+        result.server_binaries = self.processes.read().await.iter()
+            .map(|(project, process)| (project.clone(), process.binary_path.clone()))
+            .collect();
+        Ok(result)
     }
 
     // === 📂 Document Operations Helpers ===
@@ -452,16 +1284,15 @@ impl LspManagerCore {
 
     /// Get the idle monitor instance for external monitoring tasks
     ///
-    /// The idle monitor tracks server usage but doesn't shutdown servers itself.
-    /// External tasks should periodically call `shutdown_idle_servers()` to clean up.
+    /// The idle monitor tracks server usage but doesn't shutdown servers itself;
+    /// `LspManager::start_idle_monitor` runs `shutdown_idle_servers()` periodically.
     pub fn get_idle_monitor(&self) -> Arc<IdleMonitor> {
         Arc::clone(&self.idle_monitor)
     }
 
-    /// Check if idle monitoring is enabled in configuration
+    /// Check if idle monitoring is enabled in configuration (LSP_ENABLE_IDLE_MONITOR)
     pub fn is_idle_monitoring_enabled(&self) -> bool {
-        // Check the idle monitor's config (we can access this through stats)
-        true // IdleMonitor::from_env() sets this based on LSP_ENABLE_IDLE_MONITOR
+        self.idle_monitor.config().enabled
     }
 
     /// Mark a server as recently used (updates idle timer)
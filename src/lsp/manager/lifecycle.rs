@@ -4,46 +4,101 @@
 //! Includes resource management, health monitoring, and automatic restart capabilities.
 
 use crate::lsp::client::LspClient;
-use crate::lsp::resource::{ResourceMonitor, ResourceConfig, ResourceStats};
-use crate::lsp::types::{LspError, LspProcess, LspResult, HealthCheckResult};
-use std::collections::HashMap;
+use crate::lsp::server_config::ServerConfig;
+use crate::lsp::resource::{EnforcementEvent, ResourceAction, ResourceMonitor, ResourceConfig, ResourceStats};
+use crate::lsp::types::{LspError, LspProcess, LspResult, LspTimeoutConfig, HealthCheckResult};
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use tokio::process::{Child, Command};
 use std::process::Stdio;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// ⏱️ Phase durations of a server startup
+#[derive(Debug, Clone, Copy)]
+pub struct StartupTimings {
+    /// Locating the binary, spawning the process and wiring up the client
+    pub spawn: Duration,
+    /// `initialize` request/response plus the `initialized` notification
+    pub initialize: Duration,
+}
 
 /// ⚡ LSP Process Lifecycle Manager
 #[derive(Debug)]
 pub struct ProcessLifecycle {
     /// Resource monitoring and automatic restart
     resource_monitor: ResourceMonitor,
+    /// rust-analyzer command, args and initialization options, overrides applied
+    server: ServerConfig,
+    /// Directories searched before PATH and prepended to the server's PATH (ADD_PATH)
+    extra_path: Vec<PathBuf>,
+    /// Request timeouts handed to every spawned client
+    request_timeouts: Arc<LspTimeoutConfig>,
 }
 
 impl ProcessLifecycle {
     pub fn new() -> Self {
-        Self {
-            resource_monitor: ResourceMonitor::with_defaults(),
-        }
+        Self::with_resource_config(ResourceConfig::default())
     }
 
     pub fn with_resource_config(resource_config: ResourceConfig) -> Self {
         Self {
             resource_monitor: ResourceMonitor::new(resource_config),
+            server: ServerConfig::rust_analyzer(),
+            extra_path: Vec::new(),
+            request_timeouts: Arc::new(LspTimeoutConfig::default()),
         }
     }
 
+    /// 🛠️ Spawn `server` instead of the default rust-analyzer, finding it via `extra_path` first
+    pub fn set_server(&mut self, server: ServerConfig, extra_path: Vec<PathBuf>) {
+        self.server = server;
+        self.extra_path = extra_path;
+    }
+
+    /// ⏱️ Per-method request timeouts for clients spawned from now on
+    pub fn set_request_timeouts(&mut self, timeouts: LspTimeoutConfig) {
+        self.request_timeouts = Arc::new(timeouts);
+    }
+
+    /// The rust-analyzer configuration servers are spawned with
+    pub fn server_config(&self) -> &ServerConfig {
+        &self.server
+    }
+
     /// 🦀 Spawn a new rust-analyzer process for the given project
-    pub async fn spawn_rust_analyzer(&self, project_path: &Path) -> LspResult<(LspProcess, LspClient, Child)> {
+    ///
+    /// `settings` is the `rust-analyzer` settings section, sent as
+    /// `initializationOptions` and served for `workspace/configuration`.
+    pub async fn spawn_rust_analyzer(&self, project_path: &Path, settings: serde_json::Value) -> LspResult<(LspProcess, LspClient, Child)> {
+        let (process, client, child, _timings) = self.spawn_rust_analyzer_timed(project_path, settings).await?;
+        Ok((process, client, child))
+    }
+
+    /// 🦀 Spawn rust-analyzer, recording how long the spawn and initialize phases took
+    pub async fn spawn_rust_analyzer_timed(&self, project_path: &Path, settings: serde_json::Value) -> LspResult<(LspProcess, LspClient, Child, StartupTimings)> {
+        let spawn_start = Instant::now();
+
         // Find rust-analyzer binary
-        let rust_analyzer_path = self.find_rust_analyzer().await?;
+        let rust_analyzer_path = self.find_rust_analyzer()?;
+        log::info!("🦀 Using {} for {}", rust_analyzer_path.display(), project_path.display());
 
         // Spawn the process
         let mut command = Command::new(&rust_analyzer_path);
         command
+            .args(&self.server.args)
             .current_dir(project_path)
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .kill_on_drop(true);
+        // Project-local toolchains (cargo, rustc, proc-macro servers) must be found too
+        if !self.extra_path.is_empty() {
+            let current = std::env::var_os("PATH").unwrap_or_default();
+            let path = std::env::join_paths(self.extra_path.iter().cloned().chain(std::env::split_paths(&current)))
+                .map_err(|e| LspError::SpawnError { message: format!("Invalid ADD_PATH entry: {e}") })?;
+            command.env("PATH", path);
+        }
 
         let mut child = command.spawn().map_err(|e| LspError::SpawnError {
             message: format!("Failed to spawn rust-analyzer: {e}"),
@@ -63,18 +118,26 @@ impl ProcessLifecycle {
             message: "Failed to get stdout handle".to_string(),
         })?;
 
-        let client = LspClient::new(stdin, stdout, project_path.to_path_buf()).await?;
+        let client = LspClient::new(stdin, stdout, project_path.to_path_buf()).await?
+            .with_timeouts(self.request_timeouts.clone());
+        let spawn = spawn_start.elapsed();
+        if let Some(section) = &self.server.settings_section {
+            client.configure(section, settings.clone()).await;
+        }
 
         // Initialize the LSP server
-        let init_result = client.initialize().await?;
+        let initialize_start = Instant::now();
+        let init_result = client.initialize_with(Some(settings)).await?;
         log::info!("🚀 LSP server initialized with capabilities: {:?}", init_result.capabilities);
 
         // Send initialized notification
         client.send_notification("initialized", None).await?;
+        let initialize = initialize_start.elapsed();
 
         let lsp_process = LspProcess {
             project_path: project_path.to_path_buf(),
             server_name: "rust-analyzer".to_string(),
+            binary_path: rust_analyzer_path,
             process_id,
             capabilities: Some(init_result.capabilities),
             initialized: true,
@@ -86,41 +149,18 @@ impl ProcessLifecycle {
             process_id
         );
 
-        Ok((lsp_process, client, child))
+        Ok((lsp_process, client, child, StartupTimings { spawn, initialize }))
     }
 
-    /// 🔍 Find rust-analyzer binary in PATH
-    async fn find_rust_analyzer(&self) -> LspResult<PathBuf> {
-        // Try common names for rust-analyzer
-        let candidates = ["rust-analyzer", "rust-analyzer.exe"];
-
-        for candidate in &candidates {
-            // Try 'which' first (Unix-like systems)
-            if let Ok(output) = Command::new("which").arg(candidate).output().await
-                && output.status.success()
-            {
-                let path_output = String::from_utf8_lossy(&output.stdout);
-                let path_str = path_output.trim();
-                if !path_str.is_empty() {
-                    return Ok(PathBuf::from(path_str));
-                }
-            }
-
-            // Try 'where' for Windows
-            if let Ok(output) = Command::new("where").arg(candidate).output().await
-                && output.status.success()
-            {
-                let path_output = String::from_utf8_lossy(&output.stdout);
-                let path_str = path_output.lines().next().unwrap_or("").trim();
-                if !path_str.is_empty() {
-                    return Ok(PathBuf::from(path_str));
-                }
-            }
-        }
-
-        Err(LspError::NoServerAvailable {
-            file_path: PathBuf::from("rust-analyzer not found in PATH"),
-        })
+    /// 🔍 Find the rust-analyzer binary: configured path, else ADD_PATH, else PATH
+    fn find_rust_analyzer(&self) -> LspResult<PathBuf> {
+        self.server.resolve_binary(&self.extra_path, std::env::var_os("PATH").as_deref())
+            .ok_or_else(|| LspError::NoServerAvailable {
+                file_path: PathBuf::from(format!(
+                    "{} not found (searched ADD_PATH and PATH; set LSP_RUST_PATH to its location)",
+                    self.server.server_command
+                )),
+            })
     }
 
     /// 🛑 Shutdown an LSP server for a specific project
@@ -203,18 +243,60 @@ impl ProcessLifecycle {
     }
 
     /// 🏥 Perform health check on all running processes
-    pub async fn health_check(&self, children: &HashMap<PathBuf, Child>) -> LspResult<Vec<(PathBuf, bool)>> {
+    pub async fn health_check(&self, children: &mut HashMap<PathBuf, Child>) -> LspResult<Vec<(PathBuf, bool)>> {
         let mut results = Vec::new();
 
-        for (project_path, child) in children.iter() {
-            // Simple health check - see if the process is still running
-            let is_healthy = child.id().is_some();
+        for (project_path, child) in children.iter_mut() {
+            // Healthy while the process has not exited
+            let is_healthy = matches!(child.try_wait(), Ok(None));
             results.push((project_path.clone(), is_healthy));
         }
 
         Ok(results)
     }
 
+    /// 💀 Find server processes that have exited, with their exit codes
+    pub fn detect_exited(children: &mut HashMap<PathBuf, Child>) -> Vec<(PathBuf, Option<i32>)> {
+        children
+            .iter_mut()
+            .filter_map(|(project_path, child)| match child.try_wait() {
+                Ok(Some(status)) => Some((project_path.clone(), status.code())),
+                Ok(None) => None,
+                Err(e) => {
+                    log::warn!("❌ Failed to poll LSP process for {}: {}", project_path.display(), e);
+                    None
+                }
+            })
+            .collect()
+    }
+
+    // === 🔄 Crash Recovery ===
+
+    /// Whether another automatic restart is allowed for the project
+    pub async fn can_restart(&self, project_path: &Path) -> bool {
+        self.resource_monitor.can_restart(&project_path.to_path_buf()).await
+    }
+
+    /// Backoff to wait before the next restart attempt
+    pub async fn restart_delay(&self, project_path: &Path) -> Duration {
+        self.resource_monitor.restart_delay(project_path).await
+    }
+
+    /// Record a restart attempt
+    pub async fn record_restart(&self, project_path: &Path, reason: String) {
+        self.resource_monitor.record_restart(project_path.to_path_buf(), reason).await;
+    }
+
+    /// Record a restart attempt that failed to spawn a working server
+    pub async fn record_failed_restart(&self) {
+        self.resource_monitor.record_failed_restart().await;
+    }
+
+    /// Forget restart history (after a deliberate manual restart)
+    pub async fn reset_restarts(&self, project_path: &Path) {
+        self.resource_monitor.reset_restarts(project_path).await;
+    }
+
     // === 📊 Resource Management Methods ===
 
     /// Start resource monitoring for all LSP processes
@@ -247,8 +329,48 @@ impl ProcessLifecycle {
         self.resource_monitor.check_process_limits(pid).await
     }
 
+    // === 🚨 Resource Enforcement ===
+
+    /// Configured action for sustained limit violations (None: monitor only)
+    pub fn enforcement_action(&self) -> Option<ResourceAction> {
+        self.resource_monitor.config().enforcement
+    }
+
+    /// Seconds between enforcement checks
+    pub fn monitor_interval(&self) -> Duration {
+        Duration::from_secs(self.resource_monitor.config().monitor_interval_secs.max(1))
+    }
+
+    /// Re-read process usage and return processes over limits for the sustain window
+    pub async fn sustained_violations(&self) -> Vec<(u32, String)> {
+        if let Err(e) = self.resource_monitor.refresh().await {
+            log::warn!("📊 Failed to update memory usage: {}", e);
+            return Vec::new();
+        }
+        self.resource_monitor.sustained_violations().await
+    }
+
+    /// Record an action taken against a server
+    pub async fn record_enforcement(&self, event: EnforcementEvent) {
+        self.resource_monitor.record_enforcement(event).await;
+    }
+
+    /// Recent enforcement actions, oldest first
+    pub async fn enforcement_events(&self) -> Vec<EnforcementEvent> {
+        self.resource_monitor.enforcement_events().await
+    }
+
     /// Comprehensive health check including resource monitoring
-    pub async fn comprehensive_health_check(&self, children: &HashMap<PathBuf, Child>) -> LspResult<HealthCheckResult> {
+    ///
+    /// Servers in `given_up` exhausted their automatic restarts and are reported unhealthy.
+    /// `probes` holds responsiveness round-trips: `None` marks a live but hung server
+    /// (unhealthy), a missing entry means the server was not probed.
+    pub async fn comprehensive_health_check(
+        &self,
+        children: &mut HashMap<PathBuf, Child>,
+        given_up: &HashSet<PathBuf>,
+        probes: &HashMap<PathBuf, Option<Duration>>,
+    ) -> LspResult<HealthCheckResult> {
         // Basic health check
         let process_health = self.health_check(children).await?;
         
@@ -258,14 +380,28 @@ impl ProcessLifecycle {
         // Separate healthy and unhealthy processes
         let mut healthy_processes = Vec::new();
         let mut unhealthy_processes = Vec::new();
+        let mut unresponsive_processes = Vec::new();
+        let mut latencies = HashMap::new();
         
-        for (project_path, is_healthy) in process_health {
-            if is_healthy {
+        for (project_path, is_alive) in process_health {
+            match probes.get(&project_path) {
+                Some(Some(latency)) => {
+                    latencies.insert(project_path.clone(), *latency);
+                }
+                Some(None) if is_alive => {
+                    unresponsive_processes.push(project_path.clone());
+                    unhealthy_processes.push(project_path);
+                    continue;
+                }
+                _ => {}
+            }
+            if is_alive {
                 healthy_processes.push(project_path);
             } else {
                 unhealthy_processes.push(project_path);
             }
         }
+        unhealthy_processes.extend(given_up.iter().filter(|p| !children.contains_key(*p)).cloned());
         
         // Create performance summary
         let performance_summary = format!(
@@ -281,6 +417,10 @@ impl ProcessLifecycle {
             resource_stats,
             performance_summary,
             over_limit_count: 0, // TODO: Get actual over-limit count from resource monitor
+            restart_counts: self.resource_monitor.restart_counts().await,
+            unresponsive_processes,
+            latencies,
+            server_binaries: HashMap::new(), // Filled in by the manager, which knows the processes
             timestamp: std::time::Instant::now(),
         })
     }
@@ -291,3 +431,48 @@ impl Default for ProcessLifecycle {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spawn(program: &str, args: &[&str]) -> Child {
+        Command::new(program).args(args).kill_on_drop(true).spawn().unwrap()
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_detects_exited_processes() {
+        let mut children = HashMap::new();
+        let mut crashed = spawn("sh", &["-c", "exit 3"]);
+        crashed.wait().await.unwrap();
+        children.insert(PathBuf::from("/crashed"), crashed);
+        children.insert(PathBuf::from("/running"), spawn("sleep", &["30"]));
+        children.insert(PathBuf::from("/hung"), spawn("sleep", &["30"]));
+
+        let exited = ProcessLifecycle::detect_exited(&mut children);
+        assert_eq!(exited, vec![(PathBuf::from("/crashed"), Some(3))]);
+
+        let lifecycle = ProcessLifecycle::new();
+        let mut health = lifecycle.health_check(&mut children).await.unwrap();
+        health.sort();
+        assert_eq!(health, vec![
+            (PathBuf::from("/crashed"), false),
+            (PathBuf::from("/hung"), true),
+            (PathBuf::from("/running"), true),
+        ]);
+
+        let given_up = HashSet::from([PathBuf::from("/flapping")]);
+        let probes = HashMap::from([
+            (PathBuf::from("/running"), Some(Duration::from_millis(3))),
+            (PathBuf::from("/hung"), None),
+        ]);
+        let result = lifecycle.comprehensive_health_check(&mut children, &given_up, &probes).await.unwrap();
+        assert!(result.unhealthy_processes.contains(&PathBuf::from("/crashed")));
+        assert!(result.unhealthy_processes.contains(&PathBuf::from("/flapping")));
+        assert_eq!(result.healthy_processes, vec![PathBuf::from("/running")]);
+        assert_eq!(result.unresponsive_processes, vec![PathBuf::from("/hung")]);
+        assert!(result.unhealthy_processes.contains(&PathBuf::from("/hung")));
+        assert_eq!(result.latencies[&PathBuf::from("/running")], Duration::from_millis(3));
+    }
+}
@@ -10,11 +10,19 @@ pub mod lifecycle;
 pub mod tracker;
 
 use self::core::LspManagerCore;
-use crate::lsp::resource::ResourceConfig;
-use crate::lsp::types::{LspProcess, LspResult, HealthCheckResult};
-use crate::lsp::ProjectDetector;
+pub use self::core::VirtualDocument;
+use crate::lsp::idle_monitor::IdleMonitorHandle;
+use crate::lsp::resource::{EnforcementEvent, ResourceConfig};
+use crate::lsp::types::{CacheConfig, ColdStartReport, LspProcess, LspResult, LspTimeoutConfig, HealthCheckResult};
+use crate::lsp::{ProjectDetector, ServerOverride};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// How long pre-warming waits for each server to finish indexing
+pub const PREWARM_READY_TIMEOUT: Duration = Duration::from_secs(300);
 
 /// 🚀 High-level LSP Manager facade
 /// 
@@ -41,6 +49,42 @@ impl LspManager {
         }
     }
 
+    /// 🛠️ Spawn rust-analyzer with `overrides` (binary, args, init options), finding it via `extra_path` first
+    pub fn with_server_override(mut self, overrides: &ServerOverride, extra_path: Vec<PathBuf>) -> Self {
+        self.core.set_server_override(overrides, extra_path);
+        self
+    }
+
+    /// ⏱️ Coalesce rapid document updates over `window` (zero sends every change)
+    pub fn with_change_debounce(mut self, window: std::time::Duration) -> Self {
+        self.core.set_change_debounce(window);
+        self
+    }
+
+    /// 💾 Persist the open-document set to `path` instead of `<root>/.empathic/lsp_state.json`
+    pub fn with_state_file(mut self, path: PathBuf) -> Self {
+        self.core.set_state_file(path);
+        self
+    }
+
+    /// 🏓 Count servers that don't answer a health-check ping within `timeout` as hung
+    pub fn with_health_timeout(mut self, timeout: Duration) -> Self {
+        self.core.set_health_timeout(timeout);
+        self
+    }
+
+    /// 🗄️ Cache responses per `config` (TTLs, size bounds, persistence)
+    pub fn with_cache_config(mut self, config: CacheConfig) -> Self {
+        self.core.set_cache_config(config);
+        self
+    }
+
+    /// ⏱️ Time out requests per `timeouts` instead of the 60s default
+    pub fn with_request_timeouts(mut self, timeouts: LspTimeoutConfig) -> Self {
+        self.core.set_request_timeouts(timeouts);
+        self
+    }
+
     // === 🎯 Core Server Management ===
 
     /// Get or spawn an LSP server for the given file
@@ -53,6 +97,16 @@ impl LspManager {
         self.core.get_client(file_path).await
     }
 
+    /// Block until the project's server reports indexing complete, up to `ready_timeout`
+    pub async fn wait_until_ready(&self, path: &Path, ready_timeout: std::time::Duration) -> LspResult<std::time::Duration> {
+        self.core.wait_until_ready(path, ready_timeout).await
+    }
+
+    /// Client of an already running server for a project root (no spawning)
+    pub async fn running_client(&self, project_path: &Path) -> Option<crate::lsp::client::LspClient> {
+        self.core.running_client(project_path).await
+    }
+
     /// Gracefully shutdown a specific LSP server
     pub async fn shutdown_server(&self, project_path: &Path) -> LspResult<()> {
         self.core.shutdown_server(project_path).await
@@ -63,6 +117,23 @@ impl LspManager {
         self.core.shutdown_all().await
     }
 
+    /// Merge rust-analyzer settings for a project and push them to its server
+    ///
+    /// See `LspManagerCore::update_server_config` for which settings apply live.
+    pub async fn update_server_config(&self, project_path: &Path, settings: serde_json::Value) -> LspResult<serde_json::Value> {
+        self.core.update_server_config(project_path, settings).await
+    }
+
+    /// Effective rust-analyzer settings for a project
+    pub async fn server_settings(&self, project_path: &Path) -> serde_json::Value {
+        self.core.server_settings(project_path).await
+    }
+
+    /// Kill all LSP servers without waiting for them to shut down
+    pub async fn kill_all(&self) -> usize {
+        self.core.kill_all().await
+    }
+
     /// Get status of all running LSP servers
     pub async fn get_server_status(&self) -> Vec<LspProcess> {
         self.core.get_server_status().await
@@ -77,34 +148,73 @@ impl LspManager {
 
     /// Open a document in the LSP server (textDocument/didOpen)
     pub async fn open_document(&self, file_path: &Path) -> LspResult<()> {
-        // For now, delegate to core - full document operations integration pending
         log::debug!("📂 Opening document: {}", file_path.display());
-        
-        // Ensure we have a running server first
-        let _server = self.get_or_spawn_server(file_path).await?;
-        
-        // TODO: Integrate with tracker::DocumentOperations properly
-        // This is a simplified implementation for now
-        Ok(())
+        self.core.open_document(file_path).await.map(|_| ())
     }
 
     /// Update document content in the LSP server (textDocument/didChange)
+    ///
+    /// Rapid updates are debounced (`with_change_debounce`) into one didChange
+    /// with the latest content; requests flush pending changes first.
     pub async fn update_document(&self, file_path: &Path, new_content: &str) -> LspResult<()> {
-        log::debug!("📝 Updating document: {} ({} chars)", file_path.display(), new_content.len());
-        
-        // Ensure we have a running server first
-        let _server = self.get_or_spawn_server(file_path).await?;
-        
-        // TODO: Integrate with tracker::DocumentOperations properly
-        Ok(())
+        self.core.update_document(file_path, new_content).await
+    }
+
+    /// Send all debounced changes now (before workspace-wide requests)
+    pub async fn flush_pending_changes(&self) -> LspResult<usize> {
+        self.core.flush_pending_changes().await
+    }
+
+    /// Documents whose latest change hasn't been sent yet
+    pub async fn pending_change_count(&self) -> usize {
+        self.core.pending_change_count().await
     }
 
     /// Close a document in the LSP server (textDocument/didClose)
     pub async fn close_document(&self, file_path: &Path) -> LspResult<()> {
-        log::debug!("📄 Closing document: {}", file_path.display());
-        
-        // TODO: Integrate with tracker::DocumentOperations properly
-        Ok(())
+        self.core.close_document(file_path).await
+    }
+
+    /// Push content written to disk to the server (didChange + didSave) if it has the file open
+    pub async fn sync_saved_document(&self, file_path: &Path, content: &str) -> LspResult<bool> {
+        self.core.sync_saved_document(file_path, content).await
+    }
+
+    /// Move open documents from `from` to `to` (didClose + didOpen) after a rename on disk
+    pub async fn rename_documents(&self, from: &Path, to: &Path) -> LspResult<Vec<PathBuf>> {
+        self.core.rename_documents(from, to).await
+    }
+
+    /// Serve requests for a file from `content` instead of the file on disk
+    pub async fn open_virtual_document(&self, file_path: &Path, content: &str) -> LspResult<VirtualDocument> {
+        self.core.open_virtual_document(file_path, content).await
+    }
+
+    /// Restore or close a document opened with `open_virtual_document`
+    pub async fn close_virtual_document(&self, file_path: &Path, document: VirtualDocument) -> LspResult<()> {
+        self.core.close_virtual_document(file_path, document).await
+    }
+
+    // === 💾 Document State Persistence ===
+
+    /// Path of the file the open-document set is persisted to
+    pub fn state_file(&self) -> &Path {
+        self.core.state_file()
+    }
+
+    /// Snapshot every open document (paths and versions only)
+    pub async fn snapshot_documents(&self) -> tracker::DocumentStateSnapshot {
+        self.core.snapshot_documents().await
+    }
+
+    /// Persist the current open-document set to the state file
+    pub async fn save_document_state(&self) -> LspResult<tracker::DocumentStateSnapshot> {
+        self.core.save_document_state().await
+    }
+
+    /// Re-open every document recorded in the state file
+    pub async fn restore_document_state(&self) -> LspResult<Vec<PathBuf>> {
+        self.core.restore_document_state().await
     }
 
     // === 🏥 Health & Monitoring ===
@@ -114,6 +224,11 @@ impl LspManager {
         self.core.health_check().await
     }
 
+    /// Round-trip latency per running server (`None` = unresponsive)
+    pub async fn probe_servers(&self) -> HashMap<PathBuf, Option<Duration>> {
+        self.core.probe_servers().await
+    }
+
     /// Perform comprehensive health check with resource monitoring
     pub async fn comprehensive_health_check(&self) -> LspResult<HealthCheckResult> {
         self.core.comprehensive_health_check().await
@@ -126,6 +241,21 @@ impl LspManager {
         self.core.performance_summary()
     }
 
+    /// Structured metrics: totals, per-method latency percentiles, queue depth
+    pub fn metrics_snapshot(&self) -> crate::lsp::performance::MetricsSnapshot {
+        self.core.metrics_snapshot()
+    }
+
+    /// Zero the performance counters for a fresh measurement window
+    pub fn reset_metrics(&self) {
+        self.core.reset_metrics();
+    }
+
+    /// 🔗 (created, reused) LSP client counts; files in one project share a client
+    pub fn client_counts(&self) -> (u64, u64) {
+        self.core.client_counts()
+    }
+
     /// Run performance benchmark for LSP operation
     pub async fn benchmark_operation<F, T>(&self, method: &str, operation: F) -> LspResult<T>
     where
@@ -134,6 +264,16 @@ impl LspManager {
         self.core.benchmark_operation(method, operation).await
     }
 
+    /// Measure a fresh spawn + initialize + first-diagnostics cycle for a project
+    pub async fn measure_cold_start(
+        &self,
+        project_path: &Path,
+        ready_file: &Path,
+        ready_timeout: std::time::Duration,
+    ) -> LspResult<ColdStartReport> {
+        self.core.measure_cold_start(project_path, ready_file, ready_timeout).await
+    }
+
     /// Get document statistics
     pub async fn get_document_stats(&self) -> HashMap<PathBuf, usize> {
         self.core.get_document_stats().await
@@ -166,6 +306,95 @@ impl LspManager {
         self.core.check_process_limits(pid).await
     }
 
+    /// 🚨 Warn about, restart or kill servers over their limits (per ResourceConfig::enforcement)
+    pub async fn enforce_resource_limits(&self) -> Vec<EnforcementEvent> {
+        self.core.enforce_resource_limits().await
+    }
+
+    /// Recent resource enforcement actions, oldest first
+    pub async fn enforcement_events(&self) -> Vec<EnforcementEvent> {
+        self.core.enforcement_events().await
+    }
+
+    /// 🚨 Start the periodic resource monitor
+    ///
+    /// Every `monitor_interval_secs` it respawns servers whose process exited
+    /// and, when an action is configured, enforces resource limits. Like the
+    /// idle monitor, it holds only a weak reference to the manager. Abort the
+    /// handle to stop it.
+    pub fn start_resource_monitor(self: &Arc<Self>) -> JoinHandle<()> {
+        let interval = self.core.monitor_interval();
+        let manager = Arc::downgrade(self);
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                let Some(manager) = manager.upgrade() else {
+                    break;
+                };
+                let recovered = manager.recover_crashed_servers().await;
+                if !recovered.is_empty() {
+                    log::info!("🔄 Recovered {} crashed LSP server(s)", recovered.len());
+                }
+                manager.enforce_resource_limits().await;
+            }
+        })
+    }
+
+    /// 🔥 Rust project roots pre-warming would start servers for, shallowest first
+    pub fn prewarm_targets(&self, max_servers: usize) -> LspResult<Vec<PathBuf>> {
+        let mut roots: Vec<PathBuf> = self.detector().find_rust_projects()?
+            .into_iter()
+            .map(|project| project.root_path)
+            .collect();
+        // Stable: paths stay sorted within a depth
+        roots.sort_by_key(|root| root.components().count());
+        roots.truncate(max_servers);
+        Ok(roots)
+    }
+
+    /// 🔥 Start servers for up to `max_servers` detected Rust projects in the background
+    ///
+    /// Servers are spawned one after another and then index concurrently;
+    /// each is logged once it reports ready, and a summary when all are done.
+    /// Returns None when `max_servers` is 0. Abort the handle to stop waiting.
+    pub fn start_prewarm(self: &Arc<Self>, max_servers: usize) -> Option<JoinHandle<()>> {
+        if max_servers == 0 {
+            return None;
+        }
+        let manager = Arc::clone(self);
+        Some(tokio::spawn(async move {
+            let roots = match manager.prewarm_targets(max_servers) {
+                Ok(roots) => roots,
+                Err(e) => {
+                    log::warn!("🔥 LSP pre-warming skipped: {}", e);
+                    return;
+                }
+            };
+            let started = std::time::Instant::now();
+            log::info!("🔥 Pre-warming LSP servers for {} project(s)", roots.len());
+
+            let mut spawned = Vec::new();
+            for root in roots {
+                match manager.get_client(&root).await {
+                    Ok(_) => spawned.push(root),
+                    Err(e) => log::warn!("🔥 Failed to pre-warm LSP server for {}: {}", root.display(), e),
+                }
+            }
+            let mut ready = 0;
+            for root in &spawned {
+                match manager.wait_until_ready(root, PREWARM_READY_TIMEOUT).await {
+                    Ok(_) => {
+                        ready += 1;
+                        log::info!("🔥 LSP server for {} ready after {}ms", root.display(), started.elapsed().as_millis());
+                    }
+                    Err(e) => log::warn!("🔥 LSP server for {} not ready: {}", root.display(), e),
+                }
+            }
+            log::info!("🔥 LSP pre-warming complete: {}/{} server(s) ready in {}ms",
+                ready, spawned.len(), started.elapsed().as_millis());
+        }))
+    }
+
     /// Gracefully shutdown all LSP servers (alias for compatibility)
     pub async fn graceful_shutdown_all(&self) -> LspResult<()> {
         self.shutdown_all().await
@@ -178,6 +407,15 @@ impl LspManager {
         self.core.cache()
     }
 
+    /// Serve a request from the response cache, keyed by `key` and the document version
+    pub async fn cached_request<T, F>(&self, key: crate::lsp::cache::CacheKey, request: F) -> LspResult<Option<T>>
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned,
+        F: std::future::Future<Output = LspResult<Option<T>>>,
+    {
+        self.core.cached_request(key, request).await
+    }
+
     /// Invalidate cache for a specific file
     pub async fn invalidate_file_cache(&self, file_path: &Path) {
         self.core.invalidate_file_cache(file_path).await;
@@ -195,18 +433,18 @@ impl LspManager {
         self.core.detector()
     }
 
-    /// 🔄 Restart a crashed LSP server (convenience method)
+    /// 🔄 Restart an LSP server, re-opening its tracked documents
     pub async fn restart_server(&self, project_path: &Path) -> LspResult<LspProcess> {
-        log::warn!("🔄 Restarting crashed LSP server for project: {}", project_path.display());
-        
-        // Shutdown the old server
-        self.shutdown_server(project_path).await?;
-        
-        // Create a dummy file path within the project to trigger spawn
-        let dummy_file = project_path.join("src").join("lib.rs");
-        
-        // Spawn new server
-        self.get_or_spawn_server(&dummy_file).await
+        log::warn!("🔄 Restarting LSP server for project: {}", project_path.display());
+        self.core.restart_server(project_path).await
+    }
+
+    /// 🔄 Respawn servers whose process exited (with backoff); returns recovered projects
+    ///
+    /// Runs from the resource monitor; crashes are also detected lazily whenever a
+    /// crashed server's client is requested.
+    pub async fn recover_crashed_servers(&self) -> Vec<PathBuf> {
+        self.core.recover_crashed_servers().await
     }
 
     // === ⏱️ Idle Monitoring (v2.1.0) ===
@@ -219,22 +457,29 @@ impl LspManager {
     /// Manually trigger shutdown of idle servers
     ///
     /// This checks for idle servers and shuts them down immediately.
-    /// For automatic monitoring, call this method periodically from your own task.
-    ///
-    /// Example:
-    /// ```ignore
-    /// let manager = Arc::new(LspManager::new(root));
-    /// let manager_clone = Arc::clone(&manager);
-    /// tokio::spawn(async move {
-    ///     loop {
-    ///         tokio::time::sleep(Duration::from_secs(60)).await;
-    ///         let _ = manager_clone.shutdown_idle_servers().await;
-    ///     }
-    /// });
-    /// ```
+    /// For automatic monitoring, see `start_idle_monitor`.
     pub async fn shutdown_idle_servers(&self) -> LspResult<Vec<PathBuf>> {
         self.core.shutdown_idle_servers().await
     }
+
+    /// ⏱️ Start the background idle cleanup task when configured
+    ///
+    /// Returns None unless idle monitoring is enabled and LSP_IDLE_BACKGROUND is
+    /// set. The task holds only a weak reference to the manager; stop it by
+    /// dropping or calling `stop()` on the handle.
+    pub fn start_idle_monitor(self: &Arc<Self>) -> Option<IdleMonitorHandle> {
+        let monitor = self.core.get_idle_monitor();
+        if !monitor.config().enabled || !monitor.config().background {
+            return None;
+        }
+        Some(monitor.spawn_background(Arc::downgrade(self), |manager: Arc<Self>| async move {
+            match manager.shutdown_idle_servers().await {
+                Ok(paths) if !paths.is_empty() => log::info!("⏰ Shut down {} idle LSP server(s)", paths.len()),
+                Ok(_) => {}
+                Err(e) => log::warn!("⏰ Idle cleanup failed: {}", e),
+            }
+        }))
+    }
 }
 
 impl Drop for LspManager {
@@ -2,18 +2,25 @@
 //!
 //! Manages document state synchronization between empathic and LSP servers.
 //! Tracks open documents, content changes, and handles didOpen/didClose/didChange events.
+//! Bursts of edits to one document are coalesced by `ChangeDebouncer` into a
+//! single `didChange` carrying the latest content.
 
 use crate::lsp::performance::LspMetrics;
 use crate::lsp::types::{LspError, LspResult};
 use crate::lsp::client::LspClient;
 use crate::lsp::ProjectDetector;
 use lsp_types::*;
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 use url::Url;
 
+/// ⏱️ Default window for coalescing didChange notifications
+pub const DEFAULT_CHANGE_DEBOUNCE: Duration = Duration::from_millis(50);
+
 /// Type alias for async function returning LSP client
 type GetClientFn<'a> = &'a dyn Fn(&Path) -> std::pin::Pin<Box<dyn std::future::Future<Output = LspResult<LspClient>> + Send + '_>>;
 
@@ -27,6 +34,8 @@ pub struct DocumentTracker {
     open_documents: HashSet<Uri>,
     /// Last known content of each document (for change detection)
     document_content: HashMap<Uri, String>,
+    /// Version last sent to the LSP server for each document
+    document_versions: HashMap<Uri, i32>,
     /// Performance metrics for this tracker
     #[allow(dead_code)]
     metrics: Arc<LspMetrics>,
@@ -37,6 +46,7 @@ impl DocumentTracker {
         Self {
             open_documents: HashSet::new(),
             document_content: HashMap::new(),
+            document_versions: HashMap::new(),
             metrics,
         }
     }
@@ -46,7 +56,12 @@ impl DocumentTracker {
     }
 
     pub fn add_document(&mut self, uri: Uri, content: String) {
+        self.add_document_with_version(uri, content, 1);
+    }
+
+    pub fn add_document_with_version(&mut self, uri: Uri, content: String, version: i32) {
         self.open_documents.insert(uri.clone());
+        self.document_versions.insert(uri.clone(), version);
         self.document_content.insert(uri, content);
         
         log::debug!("📂 Opened document, total open: {}", self.open_documents.len());
@@ -55,6 +70,7 @@ impl DocumentTracker {
     pub fn remove_document(&mut self, uri: &Uri) {
         self.open_documents.remove(uri);
         self.document_content.remove(uri);
+        self.document_versions.remove(uri);
         
         log::debug!("📂 Closed document, total open: {}", self.open_documents.len());
     }
@@ -62,18 +78,203 @@ impl DocumentTracker {
     pub fn update_content(&mut self, uri: &Uri, content: String) {
         if self.open_documents.contains(uri) {
             self.document_content.insert(uri.clone(), content);
+            *self.document_versions.entry(uri.clone()).or_insert(1) += 1;
             log::debug!("📝 Updated document content: {}", uri.as_str());
         }
     }
 
-    #[allow(dead_code)]
     pub fn get_content(&self, uri: &Uri) -> Option<&String> {
         self.document_content.get(uri)
     }
 
+    pub fn document_version(&self, uri: &Uri) -> Option<i32> {
+        self.document_versions.get(uri).copied()
+    }
+
     pub fn open_document_count(&self) -> usize {
         self.open_documents.len()
     }
+
+    /// 💾 Snapshot open documents as file paths and versions (content is never persisted)
+    pub fn snapshot(&self) -> Vec<TrackedDocument> {
+        let mut documents: Vec<TrackedDocument> = self
+            .open_documents
+            .iter()
+            .filter_map(|uri| {
+                let path = Url::parse(uri.as_str()).ok()?.to_file_path().ok()?;
+                Some(TrackedDocument {
+                    path,
+                    version: self.document_version(uri).unwrap_or(1),
+                })
+            })
+            .collect();
+        documents.sort_by(|a, b| a.path.cmp(&b.path));
+        documents
+    }
+}
+
+/// 💾 A persisted open document - path and version only
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrackedDocument {
+    pub path: PathBuf,
+    pub version: i32,
+}
+
+/// 💾 Snapshot of every document open across all LSP servers
+///
+/// Written on graceful shutdown and read back on startup so a restarted
+/// server gets the same open-document set without the agent re-warming it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DocumentStateSnapshot {
+    /// RFC 3339 timestamp of when the snapshot was taken
+    pub saved_at: String,
+    pub documents: Vec<TrackedDocument>,
+}
+
+impl DocumentStateSnapshot {
+    pub fn new(documents: Vec<TrackedDocument>) -> Self {
+        Self {
+            saved_at: chrono::Utc::now().to_rfc3339(),
+            documents,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    /// Write the snapshot to disk, creating parent directories as needed
+    pub fn save(&self, state_file: &Path) -> LspResult<()> {
+        if let Some(parent) = state_file.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(state_file, serde_json::to_string_pretty(self)?)?;
+        log::debug!("💾 Saved {} open documents to {}", self.documents.len(), state_file.display());
+        Ok(())
+    }
+
+    /// Read a snapshot from disk - `None` when no state file exists
+    pub fn load(state_file: &Path) -> LspResult<Option<Self>> {
+        if !state_file.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(state_file)?;
+        Ok(Some(serde_json::from_str(&content)?))
+    }
+
+    /// Remove the state file if present
+    pub fn clear(state_file: &Path) -> LspResult<()> {
+        if state_file.exists() {
+            std::fs::remove_file(state_file)?;
+        }
+        Ok(())
+    }
+}
+
+/// 📝 Latest unsent content of a document
+struct PendingChange {
+    version: i32,
+    content: String,
+    client: LspClient,
+}
+
+/// ⏱️ Coalesces rapid document updates into one didChange per window
+///
+/// The first change to a document schedules a flush after the window; later
+/// changes within it only replace the pending content, so the server sees a
+/// single full-content change at the newest version. `flush` sends a pending
+/// change immediately and must run before requests that need current content;
+/// `flush_all` does the same for workspace-wide requests.
+pub struct ChangeDebouncer {
+    window: Duration,
+    /// Held across the send, so a flush never returns while another is mid-send
+    pending: tokio::sync::Mutex<HashMap<Uri, PendingChange>>,
+}
+
+impl std::fmt::Debug for ChangeDebouncer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChangeDebouncer")
+            .field("window", &self.window)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ChangeDebouncer {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            pending: tokio::sync::Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Queue `content` as `uri`'s newest version, sending it once the window closes
+    pub async fn queue(self: &Arc<Self>, uri: Uri, version: i32, content: String, client: LspClient) -> LspResult<()> {
+        if self.window.is_zero() {
+            return send_did_change(&client, uri, version, content).await;
+        }
+
+        let first = {
+            let mut pending = self.pending.lock().await;
+            pending.insert(uri.clone(), PendingChange { version, content, client }).is_none()
+        };
+        if first {
+            let debouncer = Arc::clone(self);
+            tokio::spawn(async move {
+                tokio::time::sleep(debouncer.window).await;
+                if let Err(e) = debouncer.flush(&uri).await {
+                    log::warn!("⏱️ Debounced didChange for {} failed: {}", uri.as_str(), e);
+                }
+            });
+        }
+        Ok(())
+    }
+
+    /// Send `uri`'s pending change now; returns whether there was one
+    pub async fn flush(&self, uri: &Uri) -> LspResult<bool> {
+        let mut pending = self.pending.lock().await;
+        let Some(change) = pending.remove(uri) else {
+            return Ok(false);
+        };
+        send_did_change(&change.client, uri.clone(), change.version, change.content).await?;
+        Ok(true)
+    }
+
+    /// Send every pending change now; returns how many were sent
+    pub async fn flush_all(&self) -> LspResult<usize> {
+        let mut pending = self.pending.lock().await;
+        let changes: Vec<_> = pending.drain().collect();
+        let count = changes.len();
+        for (uri, change) in changes {
+            send_did_change(&change.client, uri, change.version, change.content).await?;
+        }
+        Ok(count)
+    }
+
+    /// Drop `uri`'s pending change (the document was closed)
+    pub async fn discard(&self, uri: &Uri) {
+        self.pending.lock().await.remove(uri);
+    }
+
+    pub async fn pending_count(&self) -> usize {
+        self.pending.lock().await.len()
+    }
+}
+
+/// Full-content `textDocument/didChange`
+async fn send_did_change(client: &LspClient, uri: Uri, version: i32, content: String) -> LspResult<()> {
+    let params = DidChangeTextDocumentParams {
+        text_document: VersionedTextDocumentIdentifier { uri, version },
+        content_changes: vec![TextDocumentContentChangeEvent {
+            range: None,
+            range_length: None,
+            text: content,
+        }],
+    };
+    client.send_notification("textDocument/didChange", Some(serde_json::to_value(params)?)).await
 }
 
 /// 📄 Document Operations Handler
@@ -239,3 +440,116 @@ impl<'a> DocumentOperations<'a> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn uri_for(path: &Path) -> Uri {
+        Uri::from_str(Url::from_file_path(path).unwrap().as_str()).unwrap()
+    }
+
+    #[test]
+    fn test_snapshot_tracks_paths_and_versions() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let main_rs = temp_dir.path().join("main.rs");
+        let lib_rs = temp_dir.path().join("lib.rs");
+
+        let mut tracker = DocumentTracker::new(Arc::new(LspMetrics::default()));
+        tracker.add_document(uri_for(&main_rs), "fn main() {}".to_string());
+        tracker.add_document_with_version(uri_for(&lib_rs), "pub fn f() {}".to_string(), 4);
+        tracker.update_content(&uri_for(&main_rs), "fn main() { }".to_string());
+
+        let snapshot = tracker.snapshot();
+        assert_eq!(snapshot, vec![
+            TrackedDocument { path: lib_rs, version: 4 },
+            TrackedDocument { path: main_rs, version: 2 },
+        ]);
+    }
+
+    /// Client whose "server" is `cat`, so every notification sent comes back
+    async fn echo_client() -> (tokio::process::Child, LspClient) {
+        let mut child = tokio::process::Command::new("cat")
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .unwrap();
+        let stdin = child.stdin.take().unwrap();
+        let stdout = child.stdout.take().unwrap();
+        let client = LspClient::new(stdin, stdout, PathBuf::from("/tmp")).await.unwrap();
+        (child, client)
+    }
+
+    #[tokio::test]
+    async fn test_debouncer_coalesces_burst_and_flushes() {
+        let (_child, client) = echo_client().await;
+        let mut echoed = client.subscribe_notifications();
+        let uri = uri_for(Path::new("/tmp/lib.rs"));
+        let debouncer = Arc::new(ChangeDebouncer::new(Duration::from_millis(200)));
+
+        for version in 2..=6 {
+            debouncer.queue(uri.clone(), version, format!("v{version}"), client.clone()).await.unwrap();
+        }
+        assert_eq!(debouncer.pending_count().await, 1);
+
+        // A request-time flush sends only the newest content
+        assert!(debouncer.flush(&uri).await.unwrap());
+        assert!(!debouncer.flush(&uri).await.unwrap());
+        let notification = tokio::time::timeout(Duration::from_secs(5), echoed.recv()).await.unwrap().unwrap();
+        assert_eq!(notification.method, "textDocument/didChange");
+        let params = notification.params.unwrap();
+        assert_eq!(params["textDocument"]["version"], 6);
+        assert_eq!(params["contentChanges"][0]["text"], "v6");
+
+        // Without a flush, the window closing sends the change
+        debouncer.queue(uri.clone(), 7, "v7".to_string(), client.clone()).await.unwrap();
+        let notification = tokio::time::timeout(Duration::from_secs(5), echoed.recv()).await.unwrap().unwrap();
+        assert_eq!(notification.params.unwrap()["textDocument"]["version"], 7);
+        assert_eq!(debouncer.pending_count().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_debouncer_flush_all_sends_every_document() {
+        let (_child, client) = echo_client().await;
+        let mut echoed = client.subscribe_notifications();
+        let debouncer = Arc::new(ChangeDebouncer::new(Duration::from_secs(60)));
+        for name in ["a.rs", "b.rs"] {
+            let uri = uri_for(&Path::new("/tmp").join(name));
+            debouncer.queue(uri, 2, name.to_string(), client.clone()).await.unwrap();
+        }
+
+        assert_eq!(debouncer.flush_all().await.unwrap(), 2);
+        assert_eq!(debouncer.pending_count().await, 0);
+        let mut texts = Vec::new();
+        for _ in 0..2 {
+            let notification = tokio::time::timeout(Duration::from_secs(5), echoed.recv()).await.unwrap().unwrap();
+            texts.push(notification.params.unwrap()["contentChanges"][0]["text"].as_str().unwrap().to_string());
+        }
+        texts.sort();
+        assert_eq!(texts, vec!["a.rs", "b.rs"]);
+    }
+
+    #[test]
+    fn test_snapshot_file_roundtrip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let state_file = temp_dir.path().join(".empathic").join("lsp_state.json");
+
+        assert!(DocumentStateSnapshot::load(&state_file).unwrap().is_none());
+
+        let snapshot = DocumentStateSnapshot::new(vec![TrackedDocument {
+            path: temp_dir.path().join("main.rs"),
+            version: 3,
+        }]);
+        snapshot.save(&state_file).unwrap();
+
+        let content = std::fs::read_to_string(&state_file).unwrap();
+        assert!(!content.contains("fn main"), "content must never be persisted");
+
+        let loaded = DocumentStateSnapshot::load(&state_file).unwrap().unwrap();
+        assert_eq!(loaded.documents, snapshot.documents);
+
+        DocumentStateSnapshot::clear(&state_file).unwrap();
+        assert!(!state_file.exists());
+    }
+}
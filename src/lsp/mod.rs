@@ -15,23 +15,28 @@
 //! - **performance**: Request queuing, metrics, and optimization
 //! - **resource**: Memory monitoring and process management
 //! - **idle_monitor**: Automatic idle timeout and cleanup
+//! - **readiness**: Indexing-complete tracking from progress notifications
+//! - **positions**: Tool columns converted to and from the negotiated position encoding
 
 pub mod cache;
 pub mod client;
 pub mod idle_monitor;
 pub mod manager;
 pub mod performance;
+pub mod positions;
 pub mod project_detector;
+pub mod readiness;
 pub mod resource;
 pub mod server_config;
 pub mod types;
 
 pub use cache::LspCache;
 pub use client::LspClient;
-pub use idle_monitor::{IdleMonitor, IdleMonitorConfig, IdleMonitorStats, ServerKey};
+pub use idle_monitor::{IdleMonitor, IdleMonitorConfig, IdleMonitorHandle, IdleMonitorStats, ServerKey};
 pub use manager::LspManager;
 pub use performance::{LspMetrics, RequestQueue, ConnectionPool, PerformanceTester, RequestPriority};
 pub use project_detector::{Project, ProjectDetector, RustProject};
-pub use resource::{ResourceMonitor, ResourceConfig, MemoryUsage, ResourceStats};
-pub use server_config::ServerConfig;
-pub use types::{LspError, LspResult, HealthCheckResult};
+pub use readiness::ReadinessState;
+pub use resource::{ResourceMonitor, ResourceConfig, ResourceAction, EnforcementEvent, MemoryUsage, ResourceStats};
+pub use server_config::{ServerConfig, ServerOverride};
+pub use types::{ColdStartReport, LspError, LspResult, LspTimeoutConfig, HealthCheckResult, PositionEncoding};
@@ -3,13 +3,17 @@
 //! Provides request queuing, response time metrics, and connection optimizations
 //! to ensure optimal LSP server communication performance.
 
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use tokio::sync::{oneshot, RwLock, Semaphore};
+use serde::Serialize;
 use serde_json::Value;
 
+/// Latency samples kept per method for percentiles
+const LATENCY_WINDOW: usize = 1024;
+
 /// 📊 Performance metrics for LSP operations
 #[derive(Debug)]
 pub struct LspMetrics {
@@ -29,6 +33,64 @@ pub struct LspMetrics {
     pub cache_hits: AtomicU64,
     /// Cache misses
     pub cache_misses: AtomicU64,
+    /// LSP clients spawned (including crash recovery)
+    pub clients_created: AtomicU64,
+    /// Requests served by an already-running project client
+    pub clients_reused: AtomicU64,
+    /// Per LSP method counts and recent latencies
+    methods: Mutex<HashMap<String, MethodRecord>>,
+}
+
+/// Counts and a sliding latency window for one LSP method
+#[derive(Debug, Default)]
+struct MethodRecord {
+    count: u64,
+    errors: u64,
+    total_ms: f64,
+    max_ms: f64,
+    recent_ms: VecDeque<f64>,
+}
+
+/// 📊 Request counts and latency percentiles for one LSP method
+///
+/// Percentiles cover the most recent 1024 requests of the method.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MethodMetrics {
+    pub count: u64,
+    pub errors: u64,
+    pub avg_ms: f64,
+    pub p50_ms: f64,
+    pub p90_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+}
+
+/// 📊 Point-in-time copy of all LSP metrics
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetricsSnapshot {
+    pub total_requests: u64,
+    pub successful_requests: u64,
+    pub failed_requests: u64,
+    pub avg_response_time_ms: u64,
+    /// Requests currently awaiting a server response
+    pub queue_depth: usize,
+    pub peak_queue_depth: usize,
+    pub cache_hits: u64,
+    pub cache_misses: u64,
+    /// hits / (hits + misses), 0 before any lookup
+    pub cache_hit_rate: f64,
+    pub clients_created: u64,
+    pub clients_reused: u64,
+    pub methods: BTreeMap<String, MethodMetrics>,
+}
+
+/// Nearest-rank percentile of an ascending slice
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
 }
 
 impl Default for LspMetrics {
@@ -42,6 +104,9 @@ impl Default for LspMetrics {
             failed_requests: AtomicU64::new(0),
             cache_hits: AtomicU64::new(0),
             cache_misses: AtomicU64::new(0),
+            clients_created: AtomicU64::new(0),
+            clients_reused: AtomicU64::new(0),
+            methods: Mutex::new(HashMap::new()),
         }
     }
 }
@@ -70,6 +135,36 @@ impl LspMetrics {
         self.avg_response_time_ms.store(new_avg, Ordering::Relaxed);
     }
     
+    /// 📈 Record a completed request to the server, by LSP method
+    pub fn record_method(&self, method: &str, duration: Duration, success: bool) {
+        self.record_request(duration, success);
+
+        let ms = duration.as_secs_f64() * 1000.0;
+        let mut methods = self.methods.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let record = methods.entry(method.to_string()).or_default();
+        record.count += 1;
+        if !success {
+            record.errors += 1;
+        }
+        record.total_ms += ms;
+        record.max_ms = record.max_ms.max(ms);
+        if record.recent_ms.len() == LATENCY_WINDOW {
+            record.recent_ms.pop_front();
+        }
+        record.recent_ms.push_back(ms);
+    }
+
+    /// ⏳ A request was sent and awaits its response
+    pub fn request_started(&self) {
+        let depth = self.current_queue_depth.fetch_add(1, Ordering::Relaxed) + 1;
+        self.peak_queue_depth.fetch_max(depth, Ordering::Relaxed);
+    }
+
+    /// ✅ A request got its response (or gave up)
+    pub fn request_finished(&self) {
+        let _ = self.current_queue_depth.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |depth| depth.checked_sub(1));
+    }
+
     /// 📊 Update queue depth metrics
     pub fn update_queue_depth(&self, depth: usize) {
         self.current_queue_depth.store(depth, Ordering::Relaxed);
@@ -90,6 +185,76 @@ impl LspMetrics {
         }
     }
     
+    /// 🔗 Record whether a client lookup spawned a server or reused one
+    pub fn record_client(&self, reused: bool) {
+        if reused {
+            self.clients_reused.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.clients_created.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+    
+    /// 🔗 (created, reused) client counts
+    pub fn client_counts(&self) -> (u64, u64) {
+        (
+            self.clients_created.load(Ordering::Relaxed),
+            self.clients_reused.load(Ordering::Relaxed),
+        )
+    }
+    
+    /// 📊 Structured copy of every counter and per-method latency
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        let cache_hits = self.cache_hits.load(Ordering::Relaxed);
+        let cache_misses = self.cache_misses.load(Ordering::Relaxed);
+        let (clients_created, clients_reused) = self.client_counts();
+
+        let methods = self.methods.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+            .map(|(method, record)| {
+                let mut sorted: Vec<f64> = record.recent_ms.iter().copied().collect();
+                sorted.sort_by(f64::total_cmp);
+                (method.clone(), MethodMetrics {
+                    count: record.count,
+                    errors: record.errors,
+                    avg_ms: if record.count == 0 { 0.0 } else { record.total_ms / record.count as f64 },
+                    p50_ms: percentile(&sorted, 50.0),
+                    p90_ms: percentile(&sorted, 90.0),
+                    p99_ms: percentile(&sorted, 99.0),
+                    max_ms: record.max_ms,
+                })
+            })
+            .collect();
+
+        MetricsSnapshot {
+            total_requests: self.total_requests.load(Ordering::Relaxed),
+            successful_requests: self.successful_requests.load(Ordering::Relaxed),
+            failed_requests: self.failed_requests.load(Ordering::Relaxed),
+            avg_response_time_ms: self.avg_response_time_ms.load(Ordering::Relaxed),
+            queue_depth: self.current_queue_depth.load(Ordering::Relaxed),
+            peak_queue_depth: self.peak_queue_depth.load(Ordering::Relaxed),
+            cache_hits,
+            cache_misses,
+            cache_hit_rate: if cache_hits + cache_misses == 0 { 0.0 } else { cache_hits as f64 / (cache_hits + cache_misses) as f64 },
+            clients_created,
+            clients_reused,
+            methods,
+        }
+    }
+
+    /// 🔄 Zero the counters for a fresh measurement window
+    ///
+    /// The live queue depth is kept, and becomes the new peak.
+    pub fn reset(&self) {
+        for counter in [
+            &self.total_requests, &self.avg_response_time_ms, &self.successful_requests, &self.failed_requests,
+            &self.cache_hits, &self.cache_misses, &self.clients_created, &self.clients_reused,
+        ] {
+            counter.store(0, Ordering::Relaxed);
+        }
+        self.peak_queue_depth.store(self.current_queue_depth.load(Ordering::Relaxed), Ordering::Relaxed);
+        self.methods.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clear();
+    }
+
     /// 📋 Get performance summary
     pub fn summary(&self) -> String {
         let total = self.total_requests.load(Ordering::Relaxed);
@@ -99,12 +264,14 @@ impl LspMetrics {
         let hits = self.cache_hits.load(Ordering::Relaxed);
         let misses = self.cache_misses.load(Ordering::Relaxed);
         
-        let success_rate = if total > 0 { (success * 100) / total } else { 0 };
-        let cache_rate = if hits + misses > 0 { (hits * 100) / (hits + misses) } else { 0 };
+        let success_rate = (success * 100).checked_div(total).unwrap_or(0);
+        let cache_rate = (hits * 100).checked_div(hits + misses).unwrap_or(0);
+        
+        let (created, reused) = self.client_counts();
         
         format!(
-            "🏎️ LSP Performance: {} requests, {}% success, {}ms avg, peak queue: {}, {}% cache hit",
-            total, success_rate, avg_ms, peak_queue, cache_rate
+            "🏎️ LSP Performance: {} requests, {}% success, {}ms avg, peak queue: {}, {}% cache hit, clients: {} created / {} reused",
+            total, success_rate, avg_ms, peak_queue, cache_rate, created, reused
         )
     }
 }
@@ -391,6 +558,44 @@ mod tests {
         assert_eq!(metrics.avg_response_time_ms.load(Ordering::Relaxed), 200);
     }
     
+    #[test]
+    fn test_client_counts_in_summary() {
+        let metrics = LspMetrics::default();
+        metrics.record_client(false);
+        metrics.record_client(true);
+        metrics.record_client(true);
+        
+        assert_eq!(metrics.client_counts(), (1, 2));
+        assert!(metrics.summary().contains("clients: 1 created / 2 reused"));
+    }
+    
+    #[test]
+    fn test_method_percentiles_and_reset() {
+        let metrics = LspMetrics::default();
+        for ms in 1..=100 {
+            metrics.record_method("textDocument/hover", Duration::from_millis(ms), ms != 100);
+        }
+        metrics.record_method("textDocument/definition", Duration::from_millis(7), true);
+        metrics.request_started();
+        metrics.request_started();
+        metrics.request_finished();
+
+        let snapshot = metrics.snapshot();
+        let hover = &snapshot.methods["textDocument/hover"];
+        assert_eq!((hover.count, hover.errors), (100, 1));
+        assert_eq!((hover.p50_ms, hover.p90_ms, hover.p99_ms, hover.max_ms), (50.0, 90.0, 99.0, 100.0));
+        assert_eq!(hover.avg_ms, 50.5);
+        assert_eq!(snapshot.methods["textDocument/definition"].p99_ms, 7.0);
+        assert_eq!(snapshot.total_requests, 101);
+        assert_eq!((snapshot.queue_depth, snapshot.peak_queue_depth), (1, 2));
+
+        metrics.reset();
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.total_requests, 0);
+        assert!(snapshot.methods.is_empty());
+        assert_eq!((snapshot.queue_depth, snapshot.peak_queue_depth), (1, 1));
+    }
+    
     #[test]
     fn test_request_priority() {
         assert_eq!(RequestPriority::for_method("textDocument/publishDiagnostics"), RequestPriority::Critical);
@@ -0,0 +1,326 @@
+//! 🔤 Position Conversion - Tool columns to and from the server's position encoding
+//!
+//! Every `character` a tool accepts or reports counts UTF-16 code units
+//! (`PositionEncoding::TOOL`, the LSP default), whatever the server
+//! negotiated. The client rewrites positions in request params into the
+//! server's encoding and positions in responses and notifications back,
+//! resolving each against the document it belongs to: the text last sent
+//! with didOpen/didChange, or the file on disk.
+
+use crate::lsp::types::PositionEncoding;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::sync::RwLock;
+
+/// Methods whose result carries relative semantic token data
+const SEMANTIC_TOKEN_METHODS: &[&str] = &["textDocument/semanticTokens/full", "textDocument/semanticTokens/range"];
+
+/// 🔤 Converts positions between `PositionEncoding::TOOL` and the negotiated encoding
+#[derive(Debug, Default)]
+pub struct PositionConverter {
+    /// Encoding the server picked at initialize (UTF-16 until then)
+    encoding: RwLock<PositionEncoding>,
+    /// Text of open documents by URI, as last sent to the server
+    documents: RwLock<HashMap<String, String>>,
+}
+
+/// Which way a conversion goes
+#[derive(Debug, Clone, Copy)]
+enum Direction {
+    ToServer,
+    FromServer,
+}
+
+impl PositionConverter {
+    pub fn encoding(&self) -> PositionEncoding {
+        *self.encoding.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn set_encoding(&self, encoding: PositionEncoding) {
+        *self.encoding.write().unwrap_or_else(|e| e.into_inner()) = encoding;
+    }
+
+    /// 📄 Follow didOpen/didChange/didClose so positions resolve against unsaved text
+    pub fn track_notification(&self, method: &str, params: Option<&Value>) {
+        let Some(params) = params else {
+            return;
+        };
+        let Some(uri) = params["textDocument"]["uri"].as_str() else {
+            return;
+        };
+        let mut documents = self.documents.write().unwrap_or_else(|e| e.into_inner());
+        match method {
+            "textDocument/didOpen" => {
+                if let Some(text) = params["textDocument"]["text"].as_str() {
+                    documents.insert(uri.to_string(), text.to_string());
+                }
+            }
+            "textDocument/didChange" => {
+                // Full-content changes replace the text; anything else falls back to disk
+                match params["contentChanges"].as_array().and_then(|changes| changes.last()) {
+                    Some(change) if change.get("range").is_none() => {
+                        if let Some(text) = change["text"].as_str() {
+                            documents.insert(uri.to_string(), text.to_string());
+                        }
+                    }
+                    _ => {
+                        documents.remove(uri);
+                    }
+                }
+            }
+            "textDocument/didClose" => {
+                documents.remove(uri);
+            }
+            _ => {}
+        }
+    }
+
+    /// ➡️ Rewrite positions in request params from tool columns to the server's encoding
+    pub async fn to_server(&self, params: &mut Value) {
+        self.convert(params, None, Direction::ToServer).await;
+    }
+
+    /// ⬅️ Rewrite positions in a response or notification to tool columns
+    ///
+    /// `uri` is the document positions without their own URI belong to (the
+    /// request's `textDocument`). Semantic token data is converted for the
+    /// methods that return it.
+    pub async fn from_server(&self, method: &str, value: &mut Value, uri: Option<&str>) {
+        let encoding = self.encoding();
+        if encoding == PositionEncoding::TOOL {
+            return;
+        }
+        if SEMANTIC_TOKEN_METHODS.contains(&method)
+            && let Some(uri) = uri
+            && let Some(Value::Array(data)) = value.get_mut("data")
+        {
+            let texts = self.load_texts(HashSet::from([uri.to_string()])).await;
+            if let Some(text) = texts.get(uri) {
+                convert_semantic_tokens(data, &text.split('\n').collect::<Vec<_>>(), encoding);
+            }
+            return;
+        }
+        self.convert(value, uri, Direction::FromServer).await;
+    }
+
+    async fn convert(&self, value: &mut Value, uri: Option<&str>, direction: Direction) {
+        let encoding = self.encoding();
+        if encoding == PositionEncoding::TOOL {
+            return;
+        }
+        let mut uris = HashSet::new();
+        collect_uris(value, uri, &mut uris);
+        if uris.is_empty() {
+            return;
+        }
+        let texts = self.load_texts(uris).await;
+        let lines: HashMap<&str, Vec<&str>> = texts.iter()
+            .map(|(uri, text)| (uri.as_str(), text.split('\n').collect()))
+            .collect();
+        let (from, to) = match direction {
+            Direction::ToServer => (PositionEncoding::TOOL, encoding),
+            Direction::FromServer => (encoding, PositionEncoding::TOOL),
+        };
+        convert_positions(value, uri, &lines, from, to);
+    }
+
+    /// Text of each URI: tracked open documents first, then the file on disk
+    async fn load_texts(&self, uris: HashSet<String>) -> HashMap<String, String> {
+        let mut texts = HashMap::new();
+        let mut missing = Vec::new();
+        {
+            let documents = self.documents.read().unwrap_or_else(|e| e.into_inner());
+            for uri in uris {
+                match documents.get(&uri) {
+                    Some(text) => {
+                        texts.insert(uri, text.clone());
+                    }
+                    None => missing.push(uri),
+                }
+            }
+        }
+        for uri in missing {
+            let Some(path) = url::Url::parse(&uri).ok().and_then(|url| url.to_file_path().ok()) else {
+                continue;
+            };
+            if let Ok(text) = tokio::fs::read_to_string(&path).await {
+                texts.insert(uri, text);
+            }
+        }
+        texts
+    }
+}
+
+/// A `{line, character}` object
+fn is_position(map: &serde_json::Map<String, Value>) -> bool {
+    map.len() == 2 && map.get("line").is_some_and(Value::is_u64) && map.get("character").is_some_and(Value::is_u64)
+}
+
+/// Document an object's positions belong to: its own `uri`/`targetUri`/`textDocument.uri`, else `inherited`
+fn object_uri<'a>(map: &'a serde_json::Map<String, Value>, inherited: Option<&'a str>) -> Option<&'a str> {
+    map.get("uri").and_then(Value::as_str)
+        .or_else(|| map.get("targetUri").and_then(Value::as_str))
+        .or_else(|| map.get("textDocument").and_then(|document| document.get("uri")).and_then(Value::as_str))
+        .or(inherited)
+}
+
+/// URI a child value's positions belong to
+///
+/// `originSelectionRange` of a LocationLink is in the requesting document,
+/// and `WorkspaceEdit.changes` is keyed by URI.
+fn child_uri<'a>(key: &'a str, own: Option<&'a str>, inherited: Option<&'a str>) -> Option<&'a str> {
+    if key == "originSelectionRange" {
+        inherited
+    } else if key.starts_with("file://") {
+        Some(key)
+    } else {
+        own
+    }
+}
+
+fn collect_uris(value: &Value, uri: Option<&str>, uris: &mut HashSet<String>) {
+    match value {
+        Value::Object(map) if is_position(map) => {
+            if let Some(uri) = uri {
+                uris.insert(uri.to_string());
+            }
+        }
+        Value::Object(map) => {
+            let own = object_uri(map, uri);
+            for (key, child) in map {
+                collect_uris(child, child_uri(key, own, uri), uris);
+            }
+        }
+        Value::Array(items) => items.iter().for_each(|item| collect_uris(item, uri, uris)),
+        _ => {}
+    }
+}
+
+fn convert_positions(value: &mut Value, uri: Option<&str>, lines: &HashMap<&str, Vec<&str>>, from: PositionEncoding, to: PositionEncoding) {
+    match value {
+        Value::Object(map) if is_position(map) => {
+            let Some(line_text) = uri
+                .and_then(|uri| lines.get(uri))
+                .and_then(|document| map["line"].as_u64().and_then(|line| document.get(line as usize)))
+            else {
+                return;
+            };
+            let character = map["character"].as_u64().unwrap_or(0) as u32;
+            map.insert("character".to_string(), Value::from(convert_column(line_text, character, from, to)));
+        }
+        Value::Object(map) => {
+            let own = object_uri(map, uri).map(str::to_string);
+            for (key, child) in map.iter_mut() {
+                convert_positions(child, child_uri(key, own.as_deref(), uri), lines, from, to);
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| convert_positions(item, uri, lines, from, to)),
+        _ => {}
+    }
+}
+
+/// 🔤 Column `character` on `line` counted in `from` units, recounted in `to` units
+///
+/// Columns past the end of the line keep their overshoot.
+pub fn convert_column(line: &str, character: u32, from: PositionEncoding, to: PositionEncoding) -> u32 {
+    let (mut from_units, mut to_units) = (0, 0);
+    for ch in line.chars() {
+        if from_units >= character {
+            return to_units;
+        }
+        from_units += from.units(ch);
+        to_units += to.units(ch);
+    }
+    to_units + character.saturating_sub(from_units)
+}
+
+/// 🎨 Recount relative semantic token starts and lengths from `encoding` into tool columns
+///
+/// `data` is the flat `[deltaLine, deltaStart, length, type, modifiers]*` array.
+fn convert_semantic_tokens(data: &mut [Value], lines: &[&str], encoding: PositionEncoding) {
+    let (mut line, mut start, mut previous_start) = (0usize, 0u32, 0u32);
+    for token in data.chunks_exact_mut(5) {
+        let field = |value: &Value| value.as_u64().unwrap_or(0) as u32;
+        let (delta_line, delta_start, length) = (field(&token[0]), field(&token[1]), field(&token[2]));
+        if delta_line > 0 {
+            line += delta_line as usize;
+            start = delta_start;
+            previous_start = 0;
+        } else {
+            start += delta_start;
+        }
+
+        let text = lines.get(line).copied().unwrap_or("");
+        let tool_start = convert_column(text, start, encoding, PositionEncoding::TOOL);
+        let tool_end = convert_column(text, start + length, encoding, PositionEncoding::TOOL);
+        token[1] = Value::from(tool_start - previous_start);
+        token[2] = Value::from(tool_end - tool_start);
+        previous_start = tool_start;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    // "🦀" is 4 bytes / 2 UTF-16 units
+    const LINE: &str = "let s = \"🦀\"; let crab = s;";
+
+    fn converter(text: &str) -> PositionConverter {
+        let converter = PositionConverter::default();
+        converter.set_encoding(PositionEncoding::Utf8);
+        converter.track_notification("textDocument/didOpen", Some(&json!({
+            "textDocument": {"uri": "file:///src/main.rs", "languageId": "rust", "version": 1, "text": text}
+        })));
+        converter
+    }
+
+    #[tokio::test]
+    async fn test_request_and_response_positions_round_trip() {
+        let crab_utf16 = LINE[..LINE.find("crab").unwrap()].encode_utf16().count() as u64;
+        let crab_utf8 = LINE.find("crab").unwrap() as u64;
+        let converter = converter(LINE);
+
+        let mut params = json!({"textDocument": {"uri": "file:///src/main.rs"}, "position": {"line": 0, "character": crab_utf16}});
+        converter.to_server(&mut params).await;
+        assert_eq!(params["position"]["character"], crab_utf8);
+
+        let mut result = json!({"range": {"start": {"line": 0, "character": crab_utf8}, "end": {"line": 0, "character": crab_utf8 + 4}}});
+        converter.from_server("textDocument/hover", &mut result, Some("file:///src/main.rs")).await;
+        assert_eq!(result["range"]["start"]["character"], crab_utf16);
+        assert_eq!(result["range"]["end"]["character"], crab_utf16 + 4);
+    }
+
+    #[tokio::test]
+    async fn test_utf16_server_is_left_alone() {
+        let converter = converter(LINE);
+        converter.set_encoding(PositionEncoding::Utf16);
+        let mut params = json!({"textDocument": {"uri": "file:///src/main.rs"}, "position": {"line": 0, "character": 20}});
+        converter.to_server(&mut params).await;
+        assert_eq!(params["position"]["character"], 20);
+    }
+
+    #[tokio::test]
+    async fn test_semantic_tokens_are_recounted() {
+        let converter = converter(LINE);
+        let crab_utf8 = LINE.find("crab").unwrap() as u64;
+        let s_utf8 = LINE.rfind('s').unwrap() as u64;
+        // `let` at 0 (3 long), then `crab`, then the trailing `s`
+        let mut result = json!({"data": [0, 0, 3, 0, 0, 0, crab_utf8, 4, 1, 0, 0, s_utf8 - crab_utf8, 1, 1, 0]});
+        converter.from_server("textDocument/semanticTokens/full", &mut result, Some("file:///src/main.rs")).await;
+
+        let crab_utf16 = LINE[..LINE.find("crab").unwrap()].encode_utf16().count() as u64;
+        let s_utf16 = LINE[..LINE.rfind('s').unwrap()].encode_utf16().count() as u64;
+        assert_eq!(result["data"], json!([0, 0, 3, 0, 0, 0, crab_utf16, 4, 1, 0, 0, s_utf16 - crab_utf16, 1, 1, 0]));
+    }
+
+    #[test]
+    fn test_convert_column() {
+        let crab_utf8 = LINE.find("crab").unwrap() as u32;
+        assert_eq!(convert_column(LINE, crab_utf8, PositionEncoding::Utf8, PositionEncoding::Utf16), crab_utf8 - 2);
+        assert_eq!(convert_column(LINE, crab_utf8 - 2, PositionEncoding::Utf16, PositionEncoding::Utf8), crab_utf8);
+        // Past the end keeps the overshoot
+        assert_eq!(convert_column("ab", 5, PositionEncoding::Utf16, PositionEncoding::Utf8), 5);
+    }
+}
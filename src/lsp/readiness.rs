@@ -0,0 +1,130 @@
+//! ⏳ Server Readiness - Is rust-analyzer done loading the workspace?
+//!
+//! A cold rust-analyzer answers requests before `cargo metadata` and
+//! indexing finish, returning empty completions and diagnostics. Readiness
+//! is read from the notifications the server sends while loading:
+//! `experimental/serverStatus` (`quiescent: true` once idle) when the server
+//! supports it, otherwise `$/progress` work-done reports — ready once an
+//! indexing report has ended and no other work is in progress.
+
+use serde_json::Value;
+use std::collections::HashMap;
+
+use super::client::JsonRpcNotification;
+
+/// ⏳ Loading state gathered from progress and status notifications
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReadinessState {
+    /// Work in progress, by token: its title
+    active: HashMap<String, String>,
+    /// An indexing report has finished
+    indexed: bool,
+    /// Last `experimental/serverStatus` verdict
+    quiescent: Option<bool>,
+}
+
+impl ReadinessState {
+    /// Whether the server has finished loading and indexing
+    pub fn is_ready(&self) -> bool {
+        self.quiescent.unwrap_or(self.indexed && self.active.is_empty())
+    }
+
+    /// Titles of work still in progress, sorted
+    pub fn active_work(&self) -> Vec<String> {
+        let mut titles: Vec<String> = self.active.values().cloned().collect();
+        titles.sort();
+        titles
+    }
+
+    /// Update from a notification; returns whether anything changed
+    pub fn apply(&mut self, notification: &JsonRpcNotification) -> bool {
+        let Some(params) = &notification.params else {
+            return false;
+        };
+        let before = self.clone();
+        match notification.method.as_str() {
+            "experimental/serverStatus" => {
+                if let Some(quiescent) = params.get("quiescent").and_then(Value::as_bool) {
+                    self.quiescent = Some(quiescent);
+                }
+            }
+            "$/progress" => self.apply_progress(params),
+            _ => return false,
+        }
+        *self != before
+    }
+
+    fn apply_progress(&mut self, params: &Value) {
+        let token = match params.get("token") {
+            Some(Value::String(token)) => token.clone(),
+            Some(Value::Number(token)) => token.to_string(),
+            _ => return,
+        };
+        let value = params.get("value").unwrap_or(&Value::Null);
+        match value.get("kind").and_then(Value::as_str) {
+            Some("begin") => {
+                let title = value.get("title").and_then(Value::as_str).unwrap_or(&token).to_string();
+                self.active.insert(token, title);
+            }
+            Some("end") => {
+                let title = self.active.remove(&token).unwrap_or_default();
+                if is_indexing(&token, &title) {
+                    self.indexed = true;
+                }
+            }
+            // Reports and partial results (arrays, no kind) don't change readiness
+            _ => {}
+        }
+    }
+}
+
+/// rust-analyzer reports indexing as "Indexing" (token `rustAnalyzer/Indexing`
+/// or, in newer versions, `rustAnalyzer/cachePriming`)
+fn is_indexing(token: &str, title: &str) -> bool {
+    title.contains("Indexing") || token.contains("Indexing") || token.contains("cachePriming")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn notification(method: &str, params: Value) -> JsonRpcNotification {
+        JsonRpcNotification { jsonrpc: "2.0".to_string(), method: method.to_string(), params: Some(params) }
+    }
+
+    fn progress(token: &str, value: Value) -> JsonRpcNotification {
+        notification("$/progress", json!({"token": token, "value": value}))
+    }
+
+    #[test]
+    fn test_ready_after_indexing_ends_and_work_drains() {
+        let mut state = ReadinessState::default();
+        assert!(!state.is_ready());
+
+        assert!(state.apply(&progress("rustAnalyzer/Fetching", json!({"kind": "begin", "title": "Fetching"}))));
+        assert!(state.apply(&progress("rustAnalyzer/Indexing", json!({"kind": "begin", "title": "Indexing"}))));
+        assert!(!state.apply(&progress("rustAnalyzer/Indexing", json!({"kind": "report", "percentage": 50}))));
+        assert_eq!(state.active_work(), vec!["Fetching", "Indexing"]);
+
+        state.apply(&progress("rustAnalyzer/Indexing", json!({"kind": "end"})));
+        assert!(!state.is_ready(), "fetching is still running");
+        state.apply(&progress("rustAnalyzer/Fetching", json!({"kind": "end"})));
+        assert!(state.is_ready());
+
+        // Partial-result batches share $/progress but carry no kind
+        assert!(!state.apply(&progress("empathic-partial-3", json!([]))));
+    }
+
+    #[test]
+    fn test_server_status_overrides_progress() {
+        let mut state = ReadinessState::default();
+        state.apply(&progress("rustAnalyzer/Indexing", json!({"kind": "begin", "title": "Indexing"})));
+        state.apply(&notification("experimental/serverStatus", json!({"health": "ok", "quiescent": true})));
+        assert!(state.is_ready());
+
+        state.apply(&notification("experimental/serverStatus", json!({"health": "ok", "quiescent": false})));
+        assert!(!state.is_ready());
+        assert!(!state.apply(&notification("textDocument/publishDiagnostics", json!({}))));
+    }
+}
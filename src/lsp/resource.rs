@@ -4,7 +4,7 @@
 //! shutdown management for LSP server processes to ensure system stability.
 
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -22,6 +22,9 @@ pub struct MemoryUsage {
     pub vms_bytes: u64,
     /// Memory usage as percentage of system total
     pub memory_percent: f64,
+    /// CPU usage percentage since the previous reading, 100 per busy core
+    /// (Linux: `/proc/<pid>/stat` deltas; macOS: `ps`; 0 where unavailable)
+    pub cpu_percent: f64,
     /// Timestamp of measurement
     pub timestamp: Instant,
 }
@@ -34,10 +37,17 @@ impl MemoryUsage {
             rss_bytes,
             vms_bytes,
             memory_percent,
+            cpu_percent: 0.0,
             timestamp: Instant::now(),
         }
     }
     
+    /// Attach a CPU usage reading
+    pub fn with_cpu(mut self, cpu_percent: f64) -> Self {
+        self.cpu_percent = cpu_percent;
+        self
+    }
+    
     /// Get RSS in megabytes for human-readable display
     pub fn rss_mb(&self) -> f64 {
         self.rss_bytes as f64 / (1024.0 * 1024.0)
@@ -52,6 +62,103 @@ impl MemoryUsage {
     pub fn exceeds_limits(&self, max_rss_mb: f64, max_memory_percent: f64) -> bool {
         self.rss_mb() > max_rss_mb || self.memory_percent > max_memory_percent
     }
+    
+    /// Describe which configured limit is exceeded, if any
+    pub fn limit_violation(&self, config: &ResourceConfig) -> Option<String> {
+        if self.rss_mb() > config.max_rss_mb {
+            Some(format!("RSS {:.0}MB > {:.0}MB", self.rss_mb(), config.max_rss_mb))
+        } else if self.memory_percent > config.max_memory_percent {
+            Some(format!("memory {:.1}% > {:.1}%", self.memory_percent, config.max_memory_percent))
+        } else if self.cpu_percent > config.max_cpu_percent {
+            Some(format!("CPU {:.0}% > {:.0}%", self.cpu_percent, config.max_cpu_percent))
+        } else {
+            None
+        }
+    }
+}
+
+/// 🚨 What to do with a server that stays over its limits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceAction {
+    /// Log the violation only
+    Warn,
+    /// Restart the server, re-opening its tracked documents
+    Restart,
+    /// Shut the server down; it respawns on the next request
+    Kill,
+}
+
+impl ResourceAction {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "warn" => Some(Self::Warn),
+            "restart" => Some(Self::Restart),
+            "kill" => Some(Self::Kill),
+            _ => None,
+        }
+    }
+    
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Warn => "warn",
+            Self::Restart => "restart",
+            Self::Kill => "kill",
+        }
+    }
+}
+
+/// 🚨 One enforcement action taken against a server
+#[derive(Debug, Clone)]
+pub struct EnforcementEvent {
+    pub pid: u32,
+    pub project_path: PathBuf,
+    pub action: ResourceAction,
+    /// Which limit was exceeded, e.g. "RSS 2048MB > 1024MB"
+    pub reason: String,
+    /// Whether the restart/kill itself succeeded
+    pub succeeded: bool,
+    pub timestamp: Instant,
+}
+
+/// Enforcement events kept for summaries
+const MAX_ENFORCEMENT_EVENTS: usize = 20;
+
+/// Clock ticks per second (USER_HZ) that `/proc/<pid>/stat` times are counted in
+const CLOCK_TICKS_PER_SEC: f64 = 100.0;
+
+/// ⏱️ CPU time a process had used by a given moment
+#[derive(Debug, Clone, Copy)]
+struct CpuSample {
+    ticks: u64,
+    at: Instant,
+}
+
+/// utime + stime, in clock ticks, from a `/proc/<pid>/stat` line
+fn parse_proc_stat_ticks(stat: &str) -> Option<u64> {
+    // The command name may contain spaces and parentheses; fields resume after the last ')'
+    let fields: Vec<&str> = stat.get(stat.rfind(')')? + 1..)?.split_whitespace().collect();
+    let field = |number: usize| fields.get(number - 3)?.parse::<u64>().ok();
+    Some(field(14)? + field(15)?)
+}
+
+/// CPU percentage used between two samples of one process
+fn cpu_percent_between(previous: CpuSample, current: CpuSample) -> f64 {
+    let elapsed = current.at.duration_since(previous.at).as_secs_f64();
+    if elapsed <= 0.0 {
+        return 0.0;
+    }
+    current.ticks.saturating_sub(previous.ticks) as f64 / CLOCK_TICKS_PER_SEC / elapsed * 100.0
+}
+
+/// CPU time a process has used so far, where the platform exposes it
+#[cfg(target_os = "linux")]
+fn read_cpu_ticks(pid: u32) -> Option<u64> {
+    parse_proc_stat_ticks(&std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_cpu_ticks(_pid: u32) -> Option<u64> {
+    None
 }
 
 /// ⚙️ Resource monitoring configuration
@@ -67,6 +174,14 @@ pub struct ResourceConfig {
     pub restart_grace_secs: u64,
     /// Maximum restart attempts before giving up (default: 3)
     pub max_restart_attempts: u32,
+    /// Maximum CPU percentage per process (default: 400%, i.e. four cores)
+    pub max_cpu_percent: f64,
+    /// How long a process must stay over a limit before enforcement (default: 120s)
+    pub sustain_secs: u64,
+    /// Action for sustained violations; None only monitors (default)
+    pub enforcement: Option<ResourceAction>,
+    /// First backoff delay before restarting a crashed server, doubling per attempt (default: 2s)
+    pub restart_delay_secs: u64,
 }
 
 impl Default for ResourceConfig {
@@ -77,6 +192,10 @@ impl Default for ResourceConfig {
             monitor_interval_secs: 30,  // 30 seconds
             restart_grace_secs: 60,     // 1 minute
             max_restart_attempts: 3,    // 3 attempts
+            max_cpu_percent: 400.0,     // 4 cores
+            sustain_secs: 120,          // 2 minutes
+            enforcement: None,          // monitor only
+            restart_delay_secs: 2,      // 2 seconds
         }
     }
 }
@@ -92,6 +211,8 @@ pub struct ResourceStats {
     pub total_restarts: u64,
     /// Failed restart attempts
     pub failed_restarts: u64,
+    /// Restart/kill/warn actions taken for sustained limit violations
+    pub enforcement_actions: u64,
     /// Average memory usage across all processes (MB)
     pub avg_memory_mb: f64,
     /// Peak memory usage observed (MB)
@@ -107,6 +228,7 @@ impl Default for ResourceStats {
             over_limit_processes: 0,
             total_restarts: 0,
             failed_restarts: 0,
+            enforcement_actions: 0,
             avg_memory_mb: 0.0,
             peak_memory_mb: 0.0,
             last_update: Instant::now(),
@@ -133,10 +255,18 @@ pub struct ResourceMonitor {
     memory_usage: Arc<RwLock<HashMap<u32, MemoryUsage>>>,
     /// Restart tracking per project path
     restart_info: Arc<RwLock<HashMap<PathBuf, RestartInfo>>>,
+    /// First backoff delay before restarting a crashed server
+    restart_base_delay: Duration,
     /// Resource monitoring statistics
     stats: Arc<RwLock<ResourceStats>>,
     /// Whether monitoring is active
     monitoring_active: Arc<RwLock<bool>>,
+    /// When each process was first seen over its limits
+    over_limit_since: Arc<RwLock<HashMap<u32, Instant>>>,
+    /// Most recent enforcement actions, oldest first
+    enforcement_log: Arc<RwLock<Vec<EnforcementEvent>>>,
+    /// CPU time of each process at the previous reading
+    cpu_samples: Arc<RwLock<HashMap<u32, CpuSample>>>,
 }
 
 impl std::fmt::Debug for ResourceMonitor {
@@ -151,14 +281,23 @@ impl ResourceMonitor {
     /// Create new resource monitor with configuration
     pub fn new(config: ResourceConfig) -> Self {
         Self {
+            restart_base_delay: Duration::from_secs(config.restart_delay_secs),
             config,
             memory_usage: Arc::new(RwLock::new(HashMap::new())),
             restart_info: Arc::new(RwLock::new(HashMap::new())),
             stats: Arc::new(RwLock::new(ResourceStats::default())),
             monitoring_active: Arc::new(RwLock::new(false)),
+            over_limit_since: Arc::new(RwLock::new(HashMap::new())),
+            enforcement_log: Arc::new(RwLock::new(Vec::new())),
+            cpu_samples: Arc::new(RwLock::new(HashMap::new())),
         }
     }
     
+    /// Monitoring configuration
+    pub fn config(&self) -> &ResourceConfig {
+        &self.config
+    }
+    
     /// Create monitor with default configuration
     pub fn with_defaults() -> Self {
         Self::new(ResourceConfig::default())
@@ -175,6 +314,7 @@ impl ResourceMonitor {
         let config = self.config.clone();
         let memory_usage = self.memory_usage.clone();
         let stats = self.stats.clone();
+        let cpu_samples = self.cpu_samples.clone();
         let monitoring_active = self.monitoring_active.clone();
         
         tokio::spawn(async move {
@@ -183,7 +323,7 @@ impl ResourceMonitor {
             while *monitoring_active.read().await {
                 interval.tick().await;
                 
-                if let Err(e) = Self::update_memory_usage(&memory_usage, &stats).await {
+                if let Err(e) = Self::update_memory_usage(&memory_usage, &stats, &cpu_samples).await {
                     log::warn!("📊 Failed to update memory usage: {}", e);
                 }
             }
@@ -234,6 +374,48 @@ impl ResourceMonitor {
             .collect()
     }
     
+    /// Take a fresh memory/CPU reading now instead of waiting for the monitor loop
+    pub async fn refresh(&self) -> Result<(), String> {
+        Self::update_memory_usage(&self.memory_usage, &self.stats, &self.cpu_samples).await
+    }
+    
+    /// 🚨 Processes that have stayed over a limit for at least `sustain_secs`
+    ///
+    /// Updates the over-limit timers from the latest reading: processes back
+    /// under their limits (or gone) start over on their next violation.
+    pub async fn sustained_violations(&self) -> Vec<(u32, String)> {
+        let usage = self.memory_usage.read().await;
+        let mut since = self.over_limit_since.write().await;
+        let now = Instant::now();
+        let sustain = Duration::from_secs(self.config.sustain_secs);
+        
+        since.retain(|pid, _| usage.get(pid).is_some_and(|mem| mem.limit_violation(&self.config).is_some()));
+        usage.iter()
+            .filter_map(|(pid, mem)| {
+                let reason = mem.limit_violation(&self.config)?;
+                let first_seen = *since.entry(*pid).or_insert(now);
+                (now.duration_since(first_seen) >= sustain).then_some((*pid, reason))
+            })
+            .collect()
+    }
+    
+    /// Record an enforcement action; the process's sustain window starts over
+    pub async fn record_enforcement(&self, event: EnforcementEvent) {
+        self.over_limit_since.write().await.remove(&event.pid);
+        self.stats.write().await.enforcement_actions += 1;
+        
+        let mut log = self.enforcement_log.write().await;
+        log.push(event);
+        if log.len() > MAX_ENFORCEMENT_EVENTS {
+            log.remove(0);
+        }
+    }
+    
+    /// Recent enforcement actions, oldest first
+    pub async fn enforcement_events(&self) -> Vec<EnforcementEvent> {
+        self.enforcement_log.read().await.clone()
+    }
+    
     /// Record a process restart
     pub async fn record_restart(&self, project_path: PathBuf, reason: String) {
         let mut restarts = self.restart_info.write().await;
@@ -264,6 +446,36 @@ impl ResourceMonitor {
         }
     }
     
+    /// Exponential backoff before the next restart: base × 2^attempts, capped at the grace period
+    pub async fn restart_delay(&self, project_path: &Path) -> Duration {
+        let attempts = self.restart_info.read().await
+            .get(project_path)
+            .map(|info| info.attempts)
+            .unwrap_or(0);
+        let cap = Duration::from_secs(self.config.restart_grace_secs).max(self.restart_base_delay);
+        self.restart_base_delay
+            .saturating_mul(2u32.saturating_pow(attempts))
+            .min(cap)
+    }
+
+    /// Record a restart attempt that failed to bring the server back
+    pub async fn record_failed_restart(&self) {
+        self.stats.write().await.failed_restarts += 1;
+    }
+
+    /// Restart attempts per project path
+    pub async fn restart_counts(&self) -> HashMap<PathBuf, u32> {
+        self.restart_info.read().await
+            .iter()
+            .map(|(path, info)| (path.clone(), info.attempts))
+            .collect()
+    }
+
+    /// Forget restart history for a project (after a manual restart)
+    pub async fn reset_restarts(&self, project_path: &Path) {
+        self.restart_info.write().await.remove(project_path);
+    }
+
     /// Get current resource monitoring statistics
     pub async fn get_stats(&self) -> ResourceStats {
         let stats = self.stats.read().await;
@@ -286,21 +498,37 @@ impl ResourceMonitor {
         let total_memory: f64 = usage.values().map(|u| u.rss_mb()).sum();
         let over_limit = self.get_over_limit_processes().await;
         
-        format!(
+        let mut summary = format!(
             "📊 Resource Monitor: {} processes, {:.1}MB total, {} over limits, {} restarts",
             usage.len(),
             total_memory,
             over_limit.len(),
             stats.total_restarts
-        )
+        );
+        
+        // 🚨 Servers recycled for runaway usage
+        let events = self.enforcement_log.read().await;
+        if let Some(last) = events.last() {
+            summary.push_str(&format!(
+                ", {} enforced (last: {} {} for {}: {}{})",
+                stats.enforcement_actions,
+                last.action.name(),
+                last.pid,
+                last.project_path.display(),
+                last.reason,
+                if last.succeeded { "" } else { ", failed" }
+            ));
+        }
+        summary
     }
     
     /// Internal method to update memory usage for all processes
     async fn update_memory_usage(
         memory_usage: &Arc<RwLock<HashMap<u32, MemoryUsage>>>,
         stats: &Arc<RwLock<ResourceStats>>,
+        cpu_samples: &RwLock<HashMap<u32, CpuSample>>,
     ) -> Result<(), String> {
-        let current_usage = Self::get_system_memory_usage().await?;
+        let current_usage = Self::sample_cpu(Self::get_system_memory_usage().await?, cpu_samples).await;
         
         // Update memory usage map
         {
@@ -340,6 +568,31 @@ impl ResourceMonitor {
         Ok(())
     }
     
+    /// 🧮 Attach CPU usage since each process's previous reading
+    ///
+    /// A process's first reading has no baseline and keeps what the platform
+    /// reported (0 on Linux); samples of processes that are gone are dropped.
+    async fn sample_cpu(usage: Vec<MemoryUsage>, cpu_samples: &RwLock<HashMap<u32, CpuSample>>) -> Vec<MemoryUsage> {
+        let mut samples = cpu_samples.write().await;
+        let mut current = HashMap::new();
+        let usage = usage.into_iter()
+            .map(|mem| {
+                let Some(ticks) = read_cpu_ticks(mem.pid) else {
+                    return mem;
+                };
+                let sample = CpuSample { ticks, at: Instant::now() };
+                let previous = samples.get(&mem.pid).copied();
+                current.insert(mem.pid, sample);
+                match previous {
+                    Some(previous) => mem.with_cpu(cpu_percent_between(previous, sample)),
+                    None => mem,
+                }
+            })
+            .collect();
+        *samples = current;
+        usage
+    }
+    
     /// Get memory usage for all rust-analyzer processes
     async fn get_system_memory_usage() -> Result<Vec<MemoryUsage>, String> {
         // Try different approaches based on OS
@@ -362,7 +615,7 @@ impl ResourceMonitor {
     #[cfg(target_os = "macos")]
     async fn get_memory_usage_macos() -> Result<Vec<MemoryUsage>, String> {
         let output = Command::new("ps")
-            .args(["-ax", "-o", "pid,rss,vsz,%mem,comm"])
+            .args(["-ax", "-o", "pid,rss,vsz,%mem,%cpu,comm"])
             .output()
             .map_err(|e| format!("Failed to run ps command: {}", e))?;
         
@@ -375,19 +628,20 @@ impl ResourceMonitor {
         
         for line in stdout.lines().skip(1) { // Skip header
             let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.len() >= 5 && parts[4].contains("rust-analyzer")
-                && let (Ok(pid), Ok(rss_kb), Ok(vsz_kb), Ok(mem_percent)) = (
+            if parts.len() >= 6 && parts[5].contains("rust-analyzer")
+                && let (Ok(pid), Ok(rss_kb), Ok(vsz_kb), Ok(mem_percent), Ok(cpu_percent)) = (
                     parts[0].parse::<u32>(),
                     parts[1].parse::<u64>(),
                     parts[2].parse::<u64>(),
                     parts[3].parse::<f64>(),
+                    parts[4].parse::<f64>(),
                 ) {
                 processes.push(MemoryUsage::new(
                     pid,
                     rss_kb * 1024, // Convert KB to bytes
                     vsz_kb * 1024, // Convert KB to bytes
                     mem_percent,
-                ));
+                ).with_cpu(cpu_percent));
             }
         }
         
@@ -408,22 +662,22 @@ impl ResourceMonitor {
         let stdout = String::from_utf8_lossy(&output.stdout);
         let mut processes = Vec::new();
         
+        // %cpu from ps is a lifetime average; `sample_cpu` measures recent use instead
         for line in stdout.lines().skip(1) { // Skip header
             let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.len() >= 5 && parts[4].contains("rust-analyzer") {
-                if let (Ok(pid), Ok(rss_kb), Ok(vsz_kb), Ok(mem_percent)) = (
+            if parts.len() >= 5 && parts[4].contains("rust-analyzer")
+                && let (Ok(pid), Ok(rss_kb), Ok(vsz_kb), Ok(mem_percent)) = (
                     parts[0].parse::<u32>(),
                     parts[1].parse::<u64>(),
                     parts[2].parse::<u64>(),
                     parts[3].parse::<f64>(),
                 ) {
-                    processes.push(MemoryUsage::new(
-                        pid,
-                        rss_kb * 1024, // Convert KB to bytes
-                        vsz_kb * 1024, // Convert KB to bytes
-                        mem_percent,
-                    ));
-                }
+                processes.push(MemoryUsage::new(
+                    pid,
+                    rss_kb * 1024, // Convert KB to bytes
+                    vsz_kb * 1024, // Convert KB to bytes
+                    mem_percent,
+                ));
             }
         }
         
@@ -537,6 +791,29 @@ mod tests {
         assert_eq!(stats.total_restarts, 3);
     }
     
+    #[tokio::test]
+    async fn test_restart_backoff_is_exponential_and_capped() {
+        let monitor = ResourceMonitor::new(ResourceConfig {
+            restart_grace_secs: 10,
+            max_restart_attempts: 5,
+            restart_delay_secs: 2,
+            ..ResourceConfig::default()
+        });
+        let project_path = PathBuf::from("/test/project");
+
+        let mut delays = Vec::new();
+        for _ in 0..4 {
+            delays.push(monitor.restart_delay(&project_path).await.as_secs());
+            monitor.record_restart(project_path.clone(), "Crash detected".to_string()).await;
+        }
+        assert_eq!(delays, vec![2, 4, 8, 10]);
+        assert_eq!(monitor.restart_counts().await.get(&project_path), Some(&4));
+
+        monitor.reset_restarts(&project_path).await;
+        assert_eq!(monitor.restart_delay(&project_path).await, Duration::from_secs(2));
+        assert!(monitor.restart_counts().await.is_empty());
+    }
+    
     #[tokio::test]
     async fn test_process_removal() {
         let monitor = ResourceMonitor::with_defaults();
@@ -552,4 +829,76 @@ mod tests {
         monitor.remove_process(1234).await;
         assert!(monitor.get_memory_usage(1234).await.is_none());
     }
-}
\ No newline at end of file
+    
+    #[tokio::test]
+    async fn test_sustained_violations_and_enforcement_summary() {
+        let monitor = ResourceMonitor::new(ResourceConfig {
+            max_rss_mb: 100.0,
+            sustain_secs: 0,
+            enforcement: Some(ResourceAction::Restart),
+            ..ResourceConfig::default()
+        });
+        {
+            let mut usage = monitor.memory_usage.write().await;
+            usage.insert(1, MemoryUsage::new(1, 1024 * 1024 * 500, 0, 1.0));
+            usage.insert(2, MemoryUsage::new(2, 1024 * 1024 * 50, 0, 1.0));
+            usage.insert(3, MemoryUsage::new(3, 1024 * 1024 * 50, 0, 1.0).with_cpu(900.0));
+        }
+        
+        let mut violations = monitor.sustained_violations().await;
+        violations.sort();
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0], (1, "RSS 500MB > 100MB".to_string()));
+        assert!(violations[1].1.starts_with("CPU"));
+        
+        monitor.record_enforcement(EnforcementEvent {
+            pid: 1,
+            project_path: PathBuf::from("/test/project"),
+            action: ResourceAction::Restart,
+            reason: violations[0].1.clone(),
+            succeeded: true,
+            timestamp: Instant::now(),
+        }).await;
+        assert_eq!(monitor.get_stats().await.enforcement_actions, 1);
+        assert!(monitor.get_summary().await.contains("1 enforced (last: restart 1 for /test/project: RSS 500MB > 100MB)"));
+    }
+    
+    #[tokio::test]
+    async fn test_violation_must_be_sustained() {
+        let monitor = ResourceMonitor::new(ResourceConfig {
+            max_rss_mb: 100.0,
+            sustain_secs: 60,
+            ..ResourceConfig::default()
+        });
+        monitor.memory_usage.write().await.insert(1, MemoryUsage::new(1, 1024 * 1024 * 500, 0, 1.0));
+        
+        assert!(monitor.sustained_violations().await.is_empty());
+        assert!(monitor.over_limit_since.read().await.contains_key(&1));
+        
+        // Back under the limit resets the window
+        monitor.memory_usage.write().await.insert(1, MemoryUsage::new(1, 1024 * 1024 * 10, 0, 1.0));
+        assert!(monitor.sustained_violations().await.is_empty());
+        assert!(monitor.over_limit_since.read().await.is_empty());
+    }
+    
+    #[test]
+    fn test_resource_action_names() {
+        assert_eq!(ResourceAction::from_name("Restart"), Some(ResourceAction::Restart));
+        assert_eq!(ResourceAction::from_name("kill").map(|a| a.name()), Some("kill"));
+        assert_eq!(ResourceAction::from_name("reboot"), None);
+    }
+    
+    #[test]
+    fn test_cpu_from_proc_stat_deltas() {
+        // utime (field 14) = 250, stime (field 15) = 50; the name holds spaces and a ')'
+        let stat = "4242 (rust analyzer) x) S 1 4242 4242 0 -1 4194304 100 0 0 0 250 50 0 0 20 0 12 0 100 0 0";
+        assert_eq!(parse_proc_stat_ticks(stat), Some(300));
+        assert_eq!(parse_proc_stat_ticks("4242 (truncated"), None);
+        
+        let start = Instant::now();
+        let previous = CpuSample { ticks: 300, at: start };
+        let current = CpuSample { ticks: 500, at: start + Duration::from_secs(1) };
+        assert_eq!(cpu_percent_between(previous, current), 200.0);
+        assert_eq!(cpu_percent_between(current, current), 0.0);
+    }
+}
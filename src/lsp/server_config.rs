@@ -5,6 +5,8 @@
 
 use serde_json::{json, Value};
 use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
 
 /// 🔧 Language Server Configuration
 ///
@@ -28,9 +30,62 @@ pub struct ServerConfig {
     
     /// LSP initialization options (language-specific settings)
     pub init_options: Option<Value>,
+    
+    /// Section the server pulls its settings from via `workspace/configuration`
+    ///
+    /// When set, `init_options` are the initial settings of that section and
+    /// runtime overrides are merged on top of them.
+    pub settings_section: Option<String>,
+}
+
+/// 🛠️ User overrides for one language's server (LSP_<LANG>_* or `[lsp.<lang>]`)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ServerOverride {
+    /// Binary name or path replacing `server_command`
+    pub command: Option<String>,
+    /// Arguments replacing the defaults
+    pub args: Option<Vec<String>>,
+    /// Merged into the default `init_options` (see `merge_settings`)
+    pub init_options: Option<Value>,
+}
+
+impl ServerOverride {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
 }
 
 impl ServerConfig {
+    /// 🛠️ This configuration with `overrides` applied
+    pub fn with_override(mut self, overrides: &ServerOverride) -> Self {
+        if let Some(command) = &overrides.command {
+            self.server_command = command.clone();
+        }
+        if let Some(args) = &overrides.args {
+            self.args = args.clone();
+        }
+        if let Some(init_options) = &overrides.init_options {
+            self.init_options = Some(self.settings_with(Some(init_options)));
+        }
+        self
+    }
+
+    /// 🔍 Locate the server binary
+    ///
+    /// A command containing a path separator is used as given; a bare name is
+    /// searched in `extra_path` (ADD_PATH) first, then in `path_var` (PATH).
+    pub fn resolve_binary(&self, extra_path: &[PathBuf], path_var: Option<&OsStr>) -> Option<PathBuf> {
+        let command = Path::new(&self.server_command);
+        if command.components().count() > 1 {
+            return command.is_file().then(|| command.to_path_buf());
+        }
+        let names = [self.server_command.clone(), format!("{}.exe", self.server_command)];
+        extra_path.iter().cloned()
+            .chain(path_var.map(|path| std::env::split_paths(path).collect::<Vec<_>>()).unwrap_or_default())
+            .flat_map(|dir| names.iter().map(move |name| dir.join(name)))
+            .find(|candidate| candidate.is_file())
+    }
+
     /// 🦀 rust-analyzer configuration
     pub fn rust_analyzer() -> Self {
         Self {
@@ -39,7 +94,13 @@ impl ServerConfig {
             args: vec![],
             project_markers: vec!["Cargo.toml".to_string()],
             file_extensions: vec![".rs".to_string()],
-            init_options: None,
+            // Defaults spelled out so overrides have something visible to change
+            init_options: Some(json!({
+                "checkOnSave": true,
+                "procMacro": { "enable": true },
+                "cargo": { "features": [] }
+            })),
+            settings_section: Some("rust-analyzer".to_string()),
         }
     }
 
@@ -66,6 +127,7 @@ impl ServerConfig {
                     }
                 }
             })),
+            settings_section: None,
         }
     }
 
@@ -90,9 +152,19 @@ impl ServerConfig {
                     }
                 }
             })),
+            settings_section: None,
         }
     }
 
+    /// ⚙️ Initial settings with `overrides` merged in (see `merge_settings`)
+    pub fn settings_with(&self, overrides: Option<&Value>) -> Value {
+        let mut settings = self.init_options.clone().unwrap_or_else(|| json!({}));
+        if let Some(overrides) = overrides {
+            merge_settings(&mut settings, overrides);
+        }
+        settings
+    }
+
     /// 📚 Create registry with all built-in server configurations
     pub fn create_registry() -> HashMap<String, ServerConfig> {
         let mut registry = HashMap::new();
@@ -130,10 +202,84 @@ impl ServerConfig {
     }
 }
 
+/// 🔀 Deep-merge `update` into `settings`
+///
+/// Objects merge key by key, `null` removes a key, anything else replaces.
+pub fn merge_settings(settings: &mut Value, update: &Value) {
+    match (settings, update) {
+        (Value::Object(settings), Value::Object(update)) => {
+            for (key, value) in update {
+                if value.is_null() {
+                    settings.remove(key);
+                } else {
+                    merge_settings(settings.entry(key.clone()).or_insert(Value::Null), value);
+                }
+            }
+        }
+        (settings, update) => *settings = update.clone(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_settings_overrides_merge_deeply() {
+        let config = ServerConfig::rust_analyzer();
+        let settings = config.settings_with(Some(&json!({
+            "checkOnSave": false,
+            "cargo": { "features": ["serde"], "target": "wasm32-unknown-unknown" },
+            "procMacro": null
+        })));
+        assert_eq!(settings, json!({
+            "checkOnSave": false,
+            "cargo": { "features": ["serde"], "target": "wasm32-unknown-unknown" }
+        }));
+        assert_eq!(config.settings_with(None), config.init_options.unwrap());
+    }
+
+    #[test]
+    fn test_override_replaces_command_and_merges_options() {
+        let config = ServerConfig::rust_analyzer().with_override(&ServerOverride {
+            command: Some("/opt/ra/bin/rust-analyzer".to_string()),
+            args: Some(vec!["--log-file".to_string(), "/tmp/ra.log".to_string()]),
+            init_options: Some(json!({"cargo": {"target": "wasm32-unknown-unknown"}})),
+        });
+        assert_eq!(config.server_command, "/opt/ra/bin/rust-analyzer");
+        assert_eq!(config.args, vec!["--log-file", "/tmp/ra.log"]);
+        assert_eq!(config.settings_with(None), json!({
+            "checkOnSave": true,
+            "procMacro": { "enable": true },
+            "cargo": { "features": [], "target": "wasm32-unknown-unknown" }
+        }));
+        assert!(ServerOverride::default().is_empty());
+    }
+
+    #[test]
+    fn test_resolve_binary_prefers_extra_path() {
+        let extra = tempfile::tempdir().unwrap();
+        let system = tempfile::tempdir().unwrap();
+        for dir in [extra.path(), system.path()] {
+            std::fs::write(dir.join("rust-analyzer"), "").unwrap();
+        }
+        let config = ServerConfig::rust_analyzer();
+        let path_var = std::env::join_paths([system.path()]).unwrap();
+
+        assert_eq!(config.resolve_binary(&[extra.path().to_path_buf()], Some(&path_var)), Some(extra.path().join("rust-analyzer")));
+        assert_eq!(config.resolve_binary(&[], Some(&path_var)), Some(system.path().join("rust-analyzer")));
+        assert_eq!(config.resolve_binary(&[], None), None);
+
+        // Explicit paths are not searched for
+        let explicit = config.clone().with_override(&ServerOverride {
+            command: Some(system.path().join("rust-analyzer").to_string_lossy().to_string()),
+            ..Default::default()
+        });
+        assert_eq!(explicit.resolve_binary(&[], None), Some(system.path().join("rust-analyzer")));
+        let missing = config.with_override(&ServerOverride { command: Some("/nope/rust-analyzer".to_string()), ..Default::default() });
+        assert_eq!(missing.resolve_binary(&[extra.path().to_path_buf()], Some(&path_var)), None);
+    }
+
     #[test]
     fn test_rust_analyzer_config() {
         let config = ServerConfig::rust_analyzer();
@@ -2,8 +2,9 @@
 
 use lsp_types::*;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 /// 🎯 LSP operation result type
 pub type LspResult<T> = Result<T, LspError>;
@@ -26,6 +27,9 @@ pub enum LspError {
     #[error("LSP request timeout after {timeout_secs}s")]
     Timeout { timeout_secs: u64 },
 
+    #[error("LSP request '{method}' timed out after {timeout_ms}ms")]
+    RequestTimeout { method: String, timeout_ms: u64 },
+
     #[error("No LSP server available for file: {file_path}")]
     NoServerAvailable { file_path: PathBuf },
 
@@ -54,11 +58,20 @@ pub enum LspError {
     },
 }
 
+impl LspError {
+    /// Whether the request timed out and may succeed if retried
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, LspError::Timeout { .. } | LspError::RequestTimeout { .. })
+    }
+}
+
 /// 🏗️ LSP server process information
 #[derive(Debug, Clone)]
 pub struct LspProcess {
     pub project_path: PathBuf,
     pub server_name: String,
+    /// Resolved server binary the process was started from
+    pub binary_path: PathBuf,
     pub process_id: u32,
     pub capabilities: Option<ServerCapabilities>,
     pub initialized: bool,
@@ -80,6 +93,49 @@ impl FilePosition {
     }
 }
 
+/// 🔤 Unit of a position's `character`, negotiated through `positionEncoding`
+///
+/// LSP 3.17 servers pick one of the encodings the client offers; older
+/// servers, and servers that don't choose, count UTF-16 code units.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PositionEncoding {
+    Utf8,
+    #[default]
+    Utf16,
+    Utf32,
+}
+
+impl PositionEncoding {
+    /// Unit of every `character` tools accept and report, whatever the server negotiated
+    pub const TOOL: Self = Self::Utf16;
+
+    /// Encoding from `ServerCapabilities.positionEncoding`
+    pub fn from_kind(kind: Option<&PositionEncodingKind>) -> Self {
+        match kind.map(PositionEncodingKind::as_str) {
+            Some("utf-8") => Self::Utf8,
+            Some("utf-32") => Self::Utf32,
+            _ => Self::Utf16,
+        }
+    }
+
+    /// Code units `ch` takes up
+    pub fn units(self, ch: char) -> u32 {
+        match self {
+            Self::Utf8 => ch.len_utf8() as u32,
+            Self::Utf16 => ch.len_utf16() as u32,
+            Self::Utf32 => 1,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Utf8 => "utf-8",
+            Self::Utf16 => "utf-16",
+            Self::Utf32 => "utf-32",
+        }
+    }
+}
+
 /// 🎯 Diagnostic result with file context
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DiagnosticResult {
@@ -94,6 +150,12 @@ pub struct CacheConfig {
     pub completion_ttl_secs: u64,
     pub symbols_ttl_secs: u64,
     pub hover_ttl_secs: u64,
+    /// Directory the cache is persisted to across restarts (None = memory only)
+    pub persist_dir: Option<PathBuf>,
+    /// Entry cap; least recently used entries are evicted beyond it
+    pub max_entries: usize,
+    /// Cap on the summed serialized size of cached values
+    pub max_bytes: usize,
 }
 
 impl Default for CacheConfig {
@@ -103,10 +165,123 @@ impl Default for CacheConfig {
             completion_ttl_secs: 30,    // 30 seconds
             symbols_ttl_secs: 600,      // 10 minutes
             hover_ttl_secs: 60,         // 1 minute
+            persist_dir: None,
+            max_entries: 10_000,
+            max_bytes: 64 * 1024 * 1024, // 64 MiB
+        }
+    }
+}
+
+/// ⏱️ Per-method LSP request timeouts
+///
+/// `LSP_TIMEOUT` (or `lsp.timeout`) sets the default (seconds). Individual
+/// methods are tuned with `LSP_TIMEOUT_<method>` (or `[lsp.timeouts]` keys),
+/// where `<method>` is the full LSP method with `/` replaced by `_`
+/// (`LSP_TIMEOUT_textDocument_references`) or just its last segment
+/// (`LSP_TIMEOUT_references`). Values accept `ms`, `s` or `m` suffixes; bare
+/// numbers are seconds.
+#[derive(Debug, Clone)]
+pub struct LspTimeoutConfig {
+    pub default_timeout: Duration,
+    pub timeouts: HashMap<String, Duration>,
+}
+
+impl Default for LspTimeoutConfig {
+    fn default() -> Self {
+        Self {
+            default_timeout: Duration::from_secs(60),
+            timeouts: HashMap::new(),
         }
     }
 }
 
+impl LspTimeoutConfig {
+    /// Build timeouts from key/value pairs (environment-style)
+    pub fn from_vars(vars: impl IntoIterator<Item = (String, String)>) -> Self {
+        Self::default().with_vars(vars)
+    }
+
+    /// Apply `LSP_TIMEOUT` / `LSP_TIMEOUT_<method>` pairs on top of these timeouts
+    pub fn with_vars(self, vars: impl IntoIterator<Item = (String, String)>) -> Self {
+        let mut config = self;
+
+        for (key, value) in vars {
+            if key == "LSP_TIMEOUT" {
+                match parse_duration(&value) {
+                    Some(duration) => config.default_timeout = duration,
+                    None => log::warn!("⏱️ Ignoring invalid LSP_TIMEOUT value: {}", value),
+                }
+            } else if let Some(method) = key.strip_prefix("LSP_TIMEOUT_") {
+                match parse_duration(&value) {
+                    Some(duration) => {
+                        config.timeouts.insert(method.to_string(), duration);
+                    }
+                    None => log::warn!("⏱️ Ignoring invalid {} value: {}", key, value),
+                }
+            }
+        }
+
+        config
+    }
+
+    /// Override the timeout for a method (builder style)
+    pub fn with_timeout(mut self, method: impl Into<String>, timeout: Duration) -> Self {
+        self.timeouts.insert(method.into(), timeout);
+        self
+    }
+
+    /// Resolve the timeout for an LSP method, most specific key first
+    pub fn timeout_for(&self, method: &str) -> Duration {
+        let env_key = method.replace('/', "_");
+        let last_segment = method.rsplit('/').next().unwrap_or(method);
+
+        [method, env_key.as_str(), last_segment]
+            .iter()
+            .find_map(|key| self.timeouts.get(*key))
+            .copied()
+            .unwrap_or(self.default_timeout)
+    }
+}
+
+/// Parse "30s", "500ms", "2m" or a bare number of seconds
+fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Some(ms) = value.strip_suffix("ms") {
+        ms.trim().parse().ok().map(Duration::from_millis)
+    } else if let Some(secs) = value.strip_suffix('s') {
+        secs.trim().parse().ok().map(Duration::from_secs)
+    } else if let Some(mins) = value.strip_suffix('m') {
+        mins.trim().parse::<u64>().ok().map(|m| Duration::from_secs(m * 60))
+    } else {
+        value.parse().ok().map(Duration::from_secs)
+    }
+}
+
+/// 🥶 Cold-start latency breakdown for a language server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColdStartReport {
+    pub project_path: PathBuf,
+    pub server_name: String,
+    pub process_id: u32,
+    /// Binary lookup + process spawn + client setup
+    pub spawn_ms: f64,
+    /// `initialize` handshake
+    pub initialize_ms: f64,
+    /// didOpen until the first diagnostics were published
+    pub ready_ms: f64,
+    /// Wall-clock time for the whole cycle
+    pub total_ms: f64,
+    /// False when no diagnostics arrived before the ready timeout
+    pub reached_ready: bool,
+}
+
+impl ColdStartReport {
+    /// Sum of the individual phases (slightly below `total_ms` due to bookkeeping)
+    pub fn phases_ms(&self) -> f64 {
+        self.spawn_ms + self.initialize_ms + self.ready_ms
+    }
+}
+
 /// 🏥 Comprehensive health check result
 #[derive(Debug, Clone)]
 pub struct HealthCheckResult {
@@ -120,6 +295,14 @@ pub struct HealthCheckResult {
     pub performance_summary: String,
     /// Number of processes over resource limits
     pub over_limit_count: usize,
+    /// Automatic restart attempts per project (high counts mean a flapping server)
+    pub restart_counts: HashMap<PathBuf, u32>,
+    /// Running servers that did not answer the responsiveness probe in time (hung)
+    pub unresponsive_processes: Vec<PathBuf>,
+    /// Round-trip latency of the responsiveness probe per responsive server
+    pub latencies: HashMap<PathBuf, Duration>,
+    /// Binary each running server was started from, so misconfiguration is visible
+    pub server_binaries: HashMap<PathBuf, PathBuf>,
     /// Timestamp of health check
     pub timestamp: Instant,
 }
@@ -148,12 +331,56 @@ impl HealthCheckResult {
     /// Get human-readable summary
     pub fn summary(&self) -> String {
         format!(
-            "🏥 Health: {:.1}% ({}/{} healthy), {} over limits, {} restarts",
+            "🏥 Health: {:.1}% ({}/{} healthy), {} unresponsive, {} over limits, {} restarts",
             self.health_percentage(),
             self.healthy_processes.len(),
             self.total_processes(),
+            self.unresponsive_processes.len(),
             self.over_limit_count,
             self.resource_stats.total_restarts
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_timeout_defaults() {
+        let config = LspTimeoutConfig::from_vars(Vec::new());
+        assert_eq!(config.timeout_for("textDocument/hover"), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_timeout_per_method_from_env() {
+        let config = LspTimeoutConfig::from_vars(vars(&[
+            ("LSP_TIMEOUT", "20"),
+            ("LSP_TIMEOUT_references", "30s"),
+            ("LSP_TIMEOUT_workspace_symbol", "2m"),
+            ("LSP_TIMEOUT_hover", "500ms"),
+            ("LSP_TIMEOUT_completion", "soon"),
+            ("PATH", "/usr/bin"),
+        ]));
+
+        assert_eq!(config.timeout_for("textDocument/references"), Duration::from_secs(30));
+        assert_eq!(config.timeout_for("workspace/symbol"), Duration::from_secs(120));
+        assert_eq!(config.timeout_for("textDocument/hover"), Duration::from_millis(500));
+        // Invalid values fall back to the default
+        assert_eq!(config.timeout_for("textDocument/completion"), Duration::from_secs(20));
+    }
+
+    #[test]
+    fn test_full_method_key_wins_over_segment() {
+        let config = LspTimeoutConfig::default()
+            .with_timeout("symbol", Duration::from_secs(5))
+            .with_timeout("workspace/symbol", Duration::from_secs(45));
+
+        assert_eq!(config.timeout_for("workspace/symbol"), Duration::from_secs(45));
+        assert_eq!(config.timeout_for("textDocument/documentSymbol"), Duration::from_secs(60));
+    }
+}
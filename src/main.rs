@@ -5,6 +5,7 @@ use std::sync::{Arc, Mutex};
 /// 📝 TeeWriter - writes to both stderr and a file simultaneously
 /// CRITICAL: stdout is reserved exclusively for JSON-RPC protocol messages
 /// All logging MUST go to stderr to avoid breaking MCP protocol
+#[derive(Clone)]
 struct TeeWriter {
     file: Arc<Mutex<std::fs::File>>,
 }
@@ -41,19 +42,31 @@ impl Write for TeeWriter {
 }
 
 /// 🔧 Initialize logging with optional file output
-fn init_logging() -> EmpathicResult<()> {
+///
+/// Returns the file writer, if any, so shutdown can flush it.
+fn init_logging() -> EmpathicResult<Option<TeeWriter>> {
     use env_logger::Builder;
     use std::env;
     use std::path::PathBuf;
 
     let mut builder = Builder::new();
+    let mut log_writer = None;
     
-    // Parse RUST_LOG or default to info
+    // 🧾 The format comes from the config, loaded after logging starts, so a
+    // JSON twin (one object per line, same destinations) is built up front
+    let mut json_builder = Builder::new();
+    json_builder.format(|buf, record| writeln!(buf, "{}", empathic::logging::format_record(record)));
+    
+    // Parse RUST_LOG or default to info; applied by the reloadable wrapper so
+    // logging/setLevel can replace it, while env_logger itself passes everything
+    let mut filter = env_filter::Builder::new();
     if let Ok(rust_log) = env::var("RUST_LOG") {
-        builder.parse_filters(&rust_log);
+        filter.parse(&rust_log);
     } else {
-        builder.filter_level(log::LevelFilter::Info);
+        filter.filter_level(log::LevelFilter::Info);
     }
+    builder.filter_level(log::LevelFilter::Trace);
+    json_builder.filter_level(log::LevelFilter::Trace);
     
     // Check if LOGFILE is set and construct path with ROOT_DIR
     if let Ok(logfile_name) = env::var("LOGFILE") {
@@ -80,24 +93,31 @@ fn init_logging() -> EmpathicResult<()> {
         let tee_writer = TeeWriter::new(file);
         
         // Configure builder to use tee writer
-        builder.target(env_logger::Target::Pipe(Box::new(tee_writer)));
+        builder.target(env_logger::Target::Pipe(Box::new(tee_writer.clone())));
+        json_builder.target(env_logger::Target::Pipe(Box::new(tee_writer.clone())));
+        log_writer = Some(tee_writer);
         
         // Print to stderr before logger is initialized
         eprintln!("📝 Logging to file: {}", logfile_path.display());
     }
     
-    builder.init();
-    Ok(())
+    empathic::logging::ReloadableLogger::new(builder.build(), filter.build())
+        .with_json(json_builder.build())
+        .install()
+        .map_err(|e| empathic::EmpathicError::tool_failed("logging", e.to_string()))?;
+    Ok(log_writer)
 }
 
 #[tokio::main]
 async fn main() -> EmpathicResult<()> {
     // Initialize logging early with optional file output
-    init_logging()?;
+    let log_writer = init_logging()?;
     
     // Create config with improved error handling
-    let config = match Config::from_env() {
+    let config = match Config::load() {
         Ok(config) => {
+            // 🧾 LOG_FORMAT or log_format
+            empathic::logging::set_format(config.log_format);
             log::info!("🚀 Configuration loaded: {}", config.summary());
             config
         },
@@ -110,6 +130,7 @@ async fn main() -> EmpathicResult<()> {
                 eprintln!("   export ROOT_DIR=/path/to/your/workspace");
                 eprintln!("   export LOGLEVEL=info  # Optional: trace,debug,info,warn,error");
                 eprintln!("   export ADD_PATH=/extra/bins  # Optional: additional PATH entries");
+                eprintln!("   or put settings in $ROOT_DIR/empathic.toml (EMPATHIC_CONFIG=/path/to/file to override)");
             }
             
             std::process::exit(1);
@@ -125,10 +146,21 @@ async fn main() -> EmpathicResult<()> {
     
     // Create and run server
     let mut server = McpServer::new(config);
-    if let Err(e) = server.run().await {
+    let result = server.run().await;
+    drop(server);
+    
+    // 📝 Make sure the last log lines reach LOGFILE before exiting
+    log::logger().flush();
+    if let Some(mut log_writer) = log_writer {
+        let _ = log_writer.flush();
+    }
+    
+    if let Err(e) = result {
         eprintln!("❌ Server error: {}", e);
         std::process::exit(1);
     }
     
-    Ok(())
+    // 🛑 Exit explicitly: after a signal, tokio's stdin reader is still blocked
+    // on a read and would keep the runtime from shutting down
+    std::process::exit(0)
 }
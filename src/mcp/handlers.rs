@@ -1,8 +1,11 @@
 use std::collections::HashMap;
+use serde_json::Value;
+use tokio::sync::mpsc::UnboundedSender;
 
 use crate::config::Config;
 use crate::tools::Tool;
 use crate::mcp::protocol::*;
+use crate::mcp::progress::ProgressReporter;
 use crate::{json_rpc_response, json_rpc_error};
 use crate::error::EmpathicError;
 
@@ -61,11 +64,18 @@ fn format_detailed_error(error: &EmpathicError, tool_name: &str) -> String {
 pub struct RequestHandler<'a> {
     config: &'a Config,
     tools: &'a HashMap<String, Box<dyn Tool>>,
+    notifications: Option<UnboundedSender<JsonRpcNotification>>,
 }
 
 impl<'a> RequestHandler<'a> {
     pub fn new(config: &'a Config, tools: &'a HashMap<String, Box<dyn Tool>>) -> Self {
-        Self { config, tools }
+        Self { config, tools, notifications: None }
+    }
+
+    /// 📡 Deliver progress notifications from tool calls through `sender`
+    pub fn with_notifications(mut self, sender: UnboundedSender<JsonRpcNotification>) -> Self {
+        self.notifications = Some(sender);
+        self
     }
 
     pub async fn handle_request(&self, request: JsonRpcRequest) -> Option<JsonRpcResponse> {
@@ -94,10 +104,26 @@ impl<'a> RequestHandler<'a> {
                 log::info!("📝 Handling prompts/list request");
                 self.handle_prompts_list(request).await
             },
+            "prompts/get" => {
+                log::info!("📝 Handling prompts/get request");
+                self.handle_prompts_get(request).await
+            },
+            "logging/setLevel" => {
+                log::info!("🎚️ Handling logging/setLevel request");
+                self.handle_logging_set_level(request).await
+            },
             "resources/list" => {
                 log::info!("📂 Handling resources/list request");
                 self.handle_resources_list(request).await
             },
+            "resources/subscribe" => {
+                log::info!("👀 Handling resources/subscribe request");
+                self.handle_resources_subscribe(request, true).await
+            },
+            "resources/unsubscribe" => {
+                log::info!("🙈 Handling resources/unsubscribe request");
+                self.handle_resources_subscribe(request, false).await
+            },
             _ => {
                 log::error!("❌ Unknown method: {}", request.method);
                 JsonRpcResponse {
@@ -116,6 +142,35 @@ impl<'a> RequestHandler<'a> {
         Some(response)
     }
     
+    /// 📦 Handle a JSON-RPC batch: one response per request, in request order
+    ///
+    /// Members run concurrently. A member that isn't a valid request gets its
+    /// own -32600 error (echoing its id when it has one) without failing the
+    /// rest, and notifications produce no entry. Returns None when nothing
+    /// needs a reply; an empty batch is itself an invalid request.
+    pub async fn handle_batch(&self, items: Vec<Value>) -> Option<Vec<JsonRpcResponse>> {
+        if items.is_empty() {
+            return Some(vec![json_rpc_error!(None, -32600, "Invalid Request: empty batch")]);
+        }
+        
+        let replies = items.into_iter().map(|item| async move {
+            let id = item.get("id").cloned();
+            match serde_json::from_value::<JsonRpcRequest>(item) {
+                Ok(request) => {
+                    let is_notification = request.id.is_none();
+                    self.handle_request(request).await.filter(|_| !is_notification)
+                }
+                Err(e) => {
+                    log::error!("❌ Invalid request in batch: {}", e);
+                    Some(json_rpc_error!(id, -32600, format!("Invalid Request: {}", e)))
+                }
+            }
+        });
+        let responses: Vec<_> = futures::future::join_all(replies).await.into_iter().flatten().collect();
+        
+        (!responses.is_empty()).then_some(responses)
+    }
+    
     async fn handle_initialize(&self, request: JsonRpcRequest) -> JsonRpcResponse {
         log::info!("🚀 MCP server initialized");
         
@@ -125,6 +180,14 @@ impl<'a> RequestHandler<'a> {
                 tools: Some(ToolsCapability {
                     list_changed: false,
                 }),
+                resources: Some(ResourcesCapability {
+                    subscribe: true,
+                    list_changed: false,
+                }),
+                prompts: Some(PromptsCapability {
+                    list_changed: false,
+                }),
+                logging: Some(LoggingCapability {}),
             },
             server_info: ServerInfo {
                 name: env!("CARGO_PKG_NAME").to_string(),
@@ -139,6 +202,7 @@ impl<'a> RequestHandler<'a> {
     
     async fn handle_tools_list(&self, request: JsonRpcRequest) -> JsonRpcResponse {
         let tools: Vec<ToolInfo> = self.tools.values()
+            .filter(|tool| self.config.tool_policy.allows(tool.name()))
             .map(|tool| ToolInfo {
                 name: tool.name().to_string(),
                 description: tool.description().to_string(),
@@ -170,14 +234,29 @@ impl<'a> RequestHandler<'a> {
             }
         };
         
+        if !self.config.tool_policy.allows(tool_name) {
+            let error = EmpathicError::ToolDisabled { tool_name: tool_name.to_string() };
+            log::warn!("🔒 {}", error);
+            return json_rpc_error!(request.id, error: &error, &error);
+        }
+        
         let tool = match self.tools.get(tool_name) {
             Some(tool) => tool,
             None => {
-                return json_rpc_error!(request.id, -32601, &format!("Tool '{tool_name}' not found"));
+                let error = EmpathicError::ToolNotFound { tool_name: tool_name.to_string() };
+                return json_rpc_error!(request.id, error: &error, format!("Tool '{tool_name}' not found"));
             }
         };
         
-        let arguments = params.get("arguments").cloned().unwrap_or_default();
+        let arguments = params.get("arguments").cloned().unwrap_or_else(|| serde_json::json!({}));
+        
+        // 🧾 Name the exact missing or mistyped fields before the tool ever runs
+        let problems = crate::tools::arg_validation::validate_arguments(&tool.schema(), &arguments);
+        if !problems.is_empty() {
+            let error = EmpathicError::ToolArgumentsInvalid { tool_name: tool_name.to_string(), problems };
+            log::warn!("🧾 Rejected {} call: {}", tool_name, error);
+            return json_rpc_error!(request.id, error: &error, format_detailed_error(&error, tool_name));
+        }
         
         // 🚀 PROACTIVE LSP SPAWNING (v2.2.5)
         // When ANY tool is called with a `project` parameter, spawn LSP server
@@ -206,16 +285,39 @@ impl<'a> RequestHandler<'a> {
         let timeout_duration = self.config.request_timeout;
         log::debug!("⏱️ Executing {} with {}s timeout", tool_name, timeout_duration.as_secs());
         
-        match tokio::time::timeout(timeout_duration, tool.execute(arguments, self.config)).await {
-            Ok(Ok(result)) => {
+        // 📡 Progress goes to the client's progressToken, falling back to the request id
+        // 🚦 Waiting for a concurrency slot counts against the request timeout
+        let limiter = &self.config.tool_limiter;
+        let execution = async {
+            let _permit = limiter.acquire(tool_name).await;
+            tool.execute(arguments, self.config).await
+        };
+        let execution = async {
+            let token = params.get("_meta")
+                .and_then(|meta| meta.get("progressToken"))
+                .cloned()
+                .or_else(|| request.id.clone());
+            match (&self.notifications, token) {
+                (Some(sender), Some(token)) => ProgressReporter::new(token, sender.clone()).scope(execution).await,
+                _ => execution.await,
+            }
+        };
+        
+        match tokio::time::timeout(timeout_duration, execution).await {
+            Ok(Ok(mut result)) => {
                 log::debug!("✅ Tool {} completed successfully", tool_name);
+                // 📏 One global cap instead of every tool inventing its own truncation
+                let hint = crate::tools::narrowing_hint(tool_name);
+                if let Some(omitted) = crate::tools::truncate_response(&mut result, self.config.max_response_bytes, hint) {
+                    log::warn!("📏 Truncated {} output: {} bytes omitted", tool_name, omitted);
+                }
                 json_rpc_response!(request.id, result)
             },
             Ok(Err(e)) => {
                 // 🔍 Generate comprehensive error message with context
                 let detailed_error = format_detailed_error(&e, tool_name);
                 log::error!("❌ Tool {} failed: {}", tool_name, detailed_error);
-                json_rpc_error!(request.id, -32000, &detailed_error)
+                json_rpc_error!(request.id, error: &e, detailed_error)
             },
             Err(_) => {
                 let timeout_msg = format!(
@@ -224,16 +326,93 @@ impl<'a> RequestHandler<'a> {
                     timeout_duration.as_secs()
                 );
                 log::error!("{}", timeout_msg);
-                json_rpc_error!(request.id, -32001, &timeout_msg)
+                let error = EmpathicError::ToolTimeout { tool_name: tool_name.to_string(), timeout_secs: timeout_duration.as_secs() };
+                json_rpc_error!(request.id, error: &error, timeout_msg)
             }
         }
     }
     
+    /// 🎚️ Replace the log filter and stream records at `level` and above as `notifications/message`
+    async fn handle_logging_set_level(&self, request: JsonRpcRequest) -> JsonRpcResponse {
+        let level = request.params.as_ref()
+            .and_then(|params| params.get("level"))
+            .and_then(|level| level.as_str());
+        let Some((name, filter)) = level.and_then(|name| crate::logging::level_filter_for(name).map(|filter| (name, filter))) else {
+            return json_rpc_error!(
+                request.id,
+                -32602,
+                format!("Invalid params: 'level' must be one of {}", crate::logging::MCP_LEVELS.join(", "))
+            );
+        };
+
+        crate::logging::set_client_level(filter, self.notifications.clone());
+        log::info!("🎚️ Log level set to {} by client", name);
+        json_rpc_response!(request.id, serde_json::json!({}))
+    }
+
     async fn handle_prompts_list(&self, request: JsonRpcRequest) -> JsonRpcResponse {
-        json_rpc_response!(request.id, serde_json::json!({ "prompts": [] }))
+        json_rpc_response!(request.id, serde_json::json!({ "prompts": crate::prompts::get_prompts_schema() }))
+    }
+    
+    async fn handle_prompts_get(&self, request: JsonRpcRequest) -> JsonRpcResponse {
+        let params = request.params.clone().unwrap_or_default();
+        let Some(name) = params.get("name").and_then(|name| name.as_str()) else {
+            return json_rpc_error!(request.id, -32602, "Invalid params: missing prompt 'name'");
+        };
+        let arguments = params.get("arguments")
+            .and_then(|arguments| arguments.as_object())
+            .cloned()
+            .unwrap_or_default();
+        
+        match crate::prompts::render_prompt(name, &arguments, self.config).await {
+            Ok(result) => json_rpc_response!(request.id, result),
+            Err(error) => {
+                let mut json_error = JsonRpcError::from_error(&error, &error);
+                // 🏷️ Missing/unknown argument lists go in `data` so clients can highlight them
+                if let (EmpathicError::PromptArgumentsInvalid { missing, unknown, .. }, Some(data)) = (&error, json_error.data.as_mut()) {
+                    data["missing"] = serde_json::json!(missing);
+                    data["unknown"] = serde_json::json!(unknown);
+                }
+                JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id: request.id,
+                    result: None,
+                    error: Some(json_error),
+                }
+            }
+        }
     }
     
     async fn handle_resources_list(&self, request: JsonRpcRequest) -> JsonRpcResponse {
         json_rpc_response!(request.id, serde_json::json!({ "resources": [] }))
     }
-}
\ No newline at end of file
+    
+    /// 👀 Subscribe to (or unsubscribe from) change notifications for a file URI
+    ///
+    /// Subscriptions live in the shared `FileWatcher` - this server speaks to a
+    /// single client over stdio, so per-connection tracking is per-server. The
+    /// watch loop then sends `notifications/resources/updated` for the URI.
+    async fn handle_resources_subscribe(&self, request: JsonRpcRequest, subscribe: bool) -> JsonRpcResponse {
+        let uri = request.params.as_ref()
+            .and_then(|params| params.get("uri"))
+            .and_then(|uri| uri.as_str());
+        let Some(path) = uri.and_then(|uri| url::Url::parse(uri).ok()).and_then(|uri| uri.to_file_path().ok()) else {
+            return json_rpc_error!(request.id, -32602, format!("Invalid params: 'uri' must be a file:// URI, got {:?}", uri));
+        };
+        
+        let watcher = &self.config.file_watcher;
+        if subscribe {
+            let result = crate::tools::watch::resolve_paths(&[path.to_string_lossy().to_string()], None, self.config)
+                .and_then(|paths| watcher.watch(&paths));
+            if let Err(e) = result {
+                return json_rpc_error!(request.id, -32602, format!("Cannot subscribe to {}: {}", path.display(), e));
+            }
+        } else {
+            // The file may be gone already, so fall back to the literal path
+            let path = path.canonicalize().unwrap_or(path);
+            watcher.unwatch(Some(&[path]));
+        }
+        
+        json_rpc_response!(request.id, serde_json::json!({}))
+    }
+}
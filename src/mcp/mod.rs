@@ -8,6 +8,7 @@
 
 pub mod protocol;
 pub mod handlers;
+pub mod progress;
 pub mod server;
 
 // Re-export main types for convenience
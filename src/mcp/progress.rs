@@ -0,0 +1,82 @@
+//! 📡 Progress Notifications - Live output for long-running tool calls
+//!
+//! The request handler runs each `tools/call` inside a `ProgressReporter`
+//! scope bound to the call's progress token (or its request id). Code deep in
+//! a tool can then emit `notifications/progress` messages without threading a
+//! channel through every signature; outside a scope reporting is a no-op.
+
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::mcp::protocol::JsonRpcNotification;
+
+tokio::task_local! {
+    static CURRENT: ProgressReporter;
+}
+
+/// 📡 Sends progress notifications for one tool call
+#[derive(Clone, Debug)]
+pub struct ProgressReporter {
+    token: Value,
+    sender: UnboundedSender<JsonRpcNotification>,
+    progress: Arc<AtomicU64>,
+}
+
+impl ProgressReporter {
+    pub fn new(token: Value, sender: UnboundedSender<JsonRpcNotification>) -> Self {
+        Self {
+            token,
+            sender,
+            progress: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Reporter of the tool call currently executing, if any
+    pub fn current() -> Option<Self> {
+        CURRENT.try_with(|reporter| reporter.clone()).ok()
+    }
+
+    /// Run `future` with this reporter available via `current()`
+    pub async fn scope<F: std::future::Future>(self, future: F) -> F::Output {
+        CURRENT.scope(self, future).await
+    }
+
+    /// Send one progress message; the counter increases with every call
+    pub fn report(&self, message: &str) {
+        let progress = self.progress.fetch_add(1, Ordering::Relaxed) + 1;
+        let notification = JsonRpcNotification::new("notifications/progress", json!({
+            "progressToken": self.token,
+            "progress": progress,
+            "message": message,
+        }));
+        // The writer only goes away at shutdown; dropping progress then is fine
+        let _ = self.sender.send(notification);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_reporter_is_scoped_to_the_call() {
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        assert!(ProgressReporter::current().is_none());
+
+        ProgressReporter::new(json!("tok-1"), sender).scope(async {
+            let reporter = ProgressReporter::current().expect("reporter in scope");
+            reporter.report("Compiling empathic");
+            reporter.report("Finished");
+        }).await;
+
+        let first = receiver.recv().await.unwrap();
+        assert_eq!(first.method, "notifications/progress");
+        assert_eq!(first.params.as_ref().unwrap()["progressToken"], "tok-1");
+        assert_eq!(first.params.as_ref().unwrap()["message"], "Compiling empathic");
+        let second = receiver.recv().await.unwrap();
+        assert_eq!(second.params.unwrap()["progress"], 2);
+        assert!(ProgressReporter::current().is_none());
+    }
+}
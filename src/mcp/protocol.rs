@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::error::{EmpathicError, EmpathicResult};
+
 /// 🚀 JSON-RPC 2.0 response macros - eliminates boilerplate
 #[macro_export]
 macro_rules! json_rpc_response {
@@ -16,6 +18,14 @@ macro_rules! json_rpc_response {
 
 #[macro_export]
 macro_rules! json_rpc_error {
+    ($id:expr, error: $error:expr, $message:expr) => {
+        $crate::mcp::protocol::JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            id: $id,
+            result: None,
+            error: Some($crate::mcp::protocol::JsonRpcError::from_error($error, $message)),
+        }
+    };
     ($id:expr, $code:expr, $message:expr) => {
         $crate::mcp::protocol::JsonRpcResponse {
             jsonrpc: "2.0".to_string(),
@@ -51,6 +61,25 @@ pub struct JsonRpcResponse {
     pub error: Option<JsonRpcError>,
 }
 
+/// 🔔 JSON-RPC 2.0 Notification (server → client, no id)
+#[derive(Debug, Serialize)]
+pub struct JsonRpcNotification {
+    pub jsonrpc: String,
+    pub method: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<Value>,
+}
+
+impl JsonRpcNotification {
+    pub fn new(method: impl Into<String>, params: Value) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            method: method.into(),
+            params: Some(params),
+        }
+    }
+}
+
 /// ❌ JSON-RPC 2.0 Error Structure
 #[derive(Debug, Serialize)]
 pub struct JsonRpcError {
@@ -60,6 +89,18 @@ pub struct JsonRpcError {
     pub data: Option<Value>,
 }
 
+impl JsonRpcError {
+    /// 🔢 Code from the error's `ErrorCode`, with `{kind, category}` in `data`
+    pub fn from_error(error: &EmpathicError, message: impl ToString) -> Self {
+        let code = error.error_code();
+        Self {
+            code: code.code(),
+            message: message.to_string(),
+            data: Some(serde_json::json!({ "kind": code.as_str(), "category": error.category() })),
+        }
+    }
+}
+
 /// 🎯 MCP Initialize Response
 #[derive(Debug, Serialize)]
 pub struct InitializeResult {
@@ -74,8 +115,15 @@ pub struct InitializeResult {
 #[derive(Debug, Serialize)]
 pub struct Capabilities {
     pub tools: Option<ToolsCapability>,
+    pub resources: Option<ResourcesCapability>,
+    pub prompts: Option<PromptsCapability>,
+    pub logging: Option<LoggingCapability>,
 }
 
+/// 🎚️ Logging Capability - the server accepts `logging/setLevel`
+#[derive(Debug, Serialize)]
+pub struct LoggingCapability {}
+
 /// 🛠️ Tools Capability Configuration
 #[derive(Debug, Serialize)]
 pub struct ToolsCapability {
@@ -83,6 +131,21 @@ pub struct ToolsCapability {
     pub list_changed: bool,
 }
 
+/// 📂 Resources Capability Configuration
+#[derive(Debug, Serialize)]
+pub struct ResourcesCapability {
+    pub subscribe: bool,
+    #[serde(rename = "listChanged")]
+    pub list_changed: bool,
+}
+
+/// 📝 Prompts Capability Configuration
+#[derive(Debug, Serialize)]
+pub struct PromptsCapability {
+    #[serde(rename = "listChanged")]
+    pub list_changed: bool,
+}
+
 /// 📋 Server Information
 #[derive(Debug, Serialize)]
 pub struct ServerInfo {
@@ -104,3 +167,104 @@ pub struct ToolInfo {
     #[serde(rename = "inputSchema")]
     pub input_schema: Value,
 }
+
+/// 📄 Opaque pagination cursor returned as `next_cursor` by paged tools
+///
+/// Clients must treat it as an opaque string; it is URL-safe base64 of a
+/// versioned offset so the encoding can change without breaking callers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Cursor {
+    pub offset: usize,
+}
+
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+impl Cursor {
+    pub fn encode(&self) -> String {
+        let plain = format!("v1:{}", self.offset);
+        let mut encoded = String::new();
+        for chunk in plain.as_bytes().chunks(3) {
+            let bits = chunk.iter().enumerate()
+                .fold(0u32, |bits, (i, &byte)| bits | (byte as u32) << (16 - 8 * i));
+            for i in 0..=chunk.len() {
+                encoded.push(BASE64_ALPHABET[(bits >> (18 - 6 * i) & 0x3f) as usize] as char);
+            }
+        }
+        encoded
+    }
+
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let mut bytes = Vec::new();
+        for chunk in cursor.as_bytes().chunks(4) {
+            if chunk.len() < 2 {
+                return None;
+            }
+            let mut bits = 0u32;
+            for (i, &symbol) in chunk.iter().enumerate() {
+                let value = BASE64_ALPHABET.iter().position(|&c| c == symbol)? as u32;
+                bits |= value << (18 - 6 * i);
+            }
+            for i in 0..chunk.len() - 1 {
+                bytes.push((bits >> (16 - 8 * i)) as u8);
+            }
+        }
+        let offset = String::from_utf8(bytes).ok()?
+            .strip_prefix("v1:")?
+            .parse()
+            .ok()?;
+        Some(Self { offset })
+    }
+}
+
+/// 📄 Cut one page out of `items`, returning it with the cursor of the next page
+///
+/// Without `limit` everything from the cursor on is returned.
+pub fn paginate<T>(items: Vec<T>, cursor: Option<&str>, limit: Option<usize>) -> EmpathicResult<(Vec<T>, Option<String>)> {
+    let start = match cursor {
+        Some(cursor) => Cursor::decode(cursor)
+            .ok_or_else(|| EmpathicError::mcp_parameter("cursor", "not a cursor returned by this tool"))?
+            .offset,
+        None => 0,
+    };
+    if limit == Some(0) {
+        return Err(EmpathicError::mcp_parameter("limit", "must be at least 1"));
+    }
+
+    let total = items.len();
+    let end = limit.map_or(total, |limit| start.saturating_add(limit).min(total));
+    let page = items.into_iter().skip(start).take(end.saturating_sub(start)).collect();
+    let next_cursor = (end < total).then(|| Cursor { offset: end }.encode());
+    Ok((page, next_cursor))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_round_trip() {
+        for offset in [0, 7, 100, 123456789] {
+            let encoded = Cursor { offset }.encode();
+            assert!(encoded.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+            assert_eq!(Cursor::decode(&encoded), Some(Cursor { offset }));
+        }
+        assert_eq!(Cursor::decode("not a cursor!"), None);
+        assert_eq!(Cursor::decode("MTA"), None); // base64 of "10" without the version prefix
+    }
+
+    #[test]
+    fn test_paginate() {
+        let items: Vec<u32> = (0..5).collect();
+        let (page, next) = paginate(items.clone(), None, Some(2)).unwrap();
+        assert_eq!(page, vec![0, 1]);
+        let (page, next) = paginate(items.clone(), next.as_deref(), Some(2)).unwrap();
+        assert_eq!(page, vec![2, 3]);
+        let (page, next) = paginate(items.clone(), next.as_deref(), Some(2)).unwrap();
+        assert_eq!(page, vec![4]);
+        assert!(next.is_none());
+
+        assert_eq!(paginate(items.clone(), None, None).unwrap(), (items.clone(), None));
+        assert!(paginate(items.clone(), Some("bogus"), None).is_err());
+        assert!(paginate(items, None, Some(0)).is_err());
+    }
+}
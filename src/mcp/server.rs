@@ -1,14 +1,17 @@
 use std::sync::Arc;
 use std::collections::HashMap;
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader as TokioBufReader};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader as TokioBufReader, Stdout};
+use tokio::sync::Mutex;
+use serde_json::Value;
 
 use crate::error::EmpathicResult;
 
 use crate::config::Config;
-use crate::tools::{Tool, get_all_tools};
+use crate::tools::{Tool, get_enabled_tools};
 use crate::lsp::LspManager;
-use crate::mcp::protocol::JsonRpcRequest;
+use crate::mcp::protocol::{JsonRpcNotification, JsonRpcRequest};
 use crate::mcp::handlers::RequestHandler;
+use crate::watcher::FileWatcher;
 
 /// 🔥 Log level hierarchy: ERROR > WARN > INFO > DEBUG
 fn should_log(config_level: &str, message_level: &str) -> bool {
@@ -27,14 +30,130 @@ fn should_log(config_level: &str, message_level: &str) -> bool {
 #[inline(always)]
 fn log(config: &Config, level: &str, msg: &str) {
     if should_log(&config.log_level, level) {
-        eprintln!("{level}: {msg}");
+        if crate::logging::is_json() {
+            eprintln!("{}", crate::logging::json_line(level, module_path!(), msg, Default::default()));
+        } else {
+            eprintln!("{level}: {msg}");
+        }
+    }
+}
+
+/// 📤 Write one JSON-RPC message line; responses and notifications share stdout
+async fn write_message(stdout: &Mutex<Stdout>, message: &str) -> std::io::Result<()> {
+    let mut stdout = stdout.lock().await;
+    stdout.write_all(message.as_bytes()).await?;
+    stdout.write_all(b"\n").await?;
+    stdout.flush().await
+}
+
+/// 📨 Handle one input line (a request or a batch) and serialize its reply, if any
+async fn reply_to(handler: &RequestHandler<'_>, config: &Config, line: &str) -> Option<serde_json::Result<String>> {
+    // 📦 A JSON array is a batch; anything else a single request
+    match serde_json::from_str::<Value>(line) {
+        Ok(Value::Array(items)) => {
+            log(config, "INFO", &format!("📦 Parsed JSON-RPC batch of {} messages", items.len()));
+            handler.handle_batch(items).await
+                .map(|responses| serde_json::to_string(&responses))
+        },
+        Ok(value) => match serde_json::from_value::<JsonRpcRequest>(value) {
+            Ok(request) => {
+                log(config, "INFO", &format!("✅ Parsed JSON-RPC request: {}", request.method));
+                handler.handle_request(request).await
+                    .map(|response| serde_json::to_string(&response))
+            },
+            Err(e) => {
+                log(config, "ERROR", &format!("❌ Failed to parse JSON-RPC request: {e}"));
+                None
+            }
+        },
+        Err(e) => {
+            log(config, "ERROR", &format!("❌ Failed to parse JSON-RPC request: {e}"));
+            None
+        }
+    }
+}
+
+/// 👀 Notify the client about changes under watched paths
+///
+/// Wakes on OS watcher events and lets each burst settle for the poll
+/// interval before reporting it. Changed files also drop their cached LSP
+/// responses.
+async fn run_watch_loop(watcher: Arc<FileWatcher>, lsp_manager: Arc<LspManager>, stdout: Arc<Mutex<Stdout>>) {
+    loop {
+        watcher.changed().await;
+        tokio::time::sleep(watcher.poll_interval()).await;
+
+        // Re-checking flagged directories walks them, so keep it off the runtime
+        let poll_watcher = Arc::clone(&watcher);
+        let events = match tokio::task::spawn_blocking(move || poll_watcher.poll()).await {
+            Ok(events) => events,
+            Err(e) => {
+                log::warn!("👀 File watch poll failed: {}", e);
+                continue;
+            }
+        };
+
+        for event in events {
+            lsp_manager.invalidate_file_cache(&event.path).await;
+
+            let uri = url::Url::from_file_path(&event.path)
+                .map(|uri| uri.to_string())
+                .unwrap_or_else(|_| event.path.to_string_lossy().to_string());
+            let notification = JsonRpcNotification::new(
+                "notifications/resources/updated",
+                serde_json::json!({
+                    "uri": uri,
+                    "kind": event.kind,
+                    "watch_root": event.watch_root,
+                }),
+            );
+
+            match serde_json::to_string(&notification) {
+                Ok(message) => {
+                    if let Err(e) = write_message(&stdout, &message).await {
+                        log::warn!("👀 Failed to send change notification: {}", e);
+                        return;
+                    }
+                }
+                Err(e) => log::warn!("👀 Failed to serialize change notification: {}", e),
+            }
+        }
+    }
+}
+
+/// 🛑 Resolve when the process is asked to stop (SIGTERM/SIGINT, Ctrl-C off unix)
+async fn shutdown_signal() -> &'static str {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        match (signal(SignalKind::terminate()), signal(SignalKind::interrupt())) {
+            (Ok(mut terminate), Ok(mut interrupt)) => tokio::select! {
+                _ = terminate.recv() => "SIGTERM",
+                _ = interrupt.recv() => "SIGINT",
+            },
+            (Err(e), _) | (_, Err(e)) => {
+                log::warn!("🛑 Failed to install signal handlers: {}", e);
+                std::future::pending().await
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        match tokio::signal::ctrl_c().await {
+            Ok(()) => "Ctrl-C",
+            Err(e) => {
+                log::warn!("🛑 Failed to install Ctrl-C handler: {}", e);
+                std::future::pending().await
+            }
+        }
     }
 }
 
 /// 🚀 MCP JSON-RPC 2.0 Server 
 pub struct McpServer {
     config: Config,
-    tools: HashMap<String, Box<dyn Tool>>,
+    /// Shared with the tasks serving each request
+    tools: Arc<HashMap<String, Box<dyn Tool>>>,
     /// 🧠 LSP manager for semantic code analysis
     lsp_manager: Arc<LspManager>,
 }
@@ -44,14 +163,22 @@ impl McpServer {
         log(&config, "INFO", "🚀 Creating new MCP server instance");
         
         // Create LSP manager for semantic analysis and file synchronization
-        let lsp_manager = Arc::new(LspManager::new(config.root_dir.clone()));
+        let lsp_manager = Arc::new(
+            LspManager::with_resource_config(config.root_dir.clone(), config.lsp_resources.clone())
+                .with_server_override(&config.lsp_servers.get("rust").cloned().unwrap_or_default(), config.add_path.clone())
+                .with_change_debounce(config.lsp_change_debounce)
+                .with_state_file(config.lsp_state_file.clone())
+                .with_health_timeout(config.lsp_health_timeout)
+                .with_request_timeouts(config.lsp_timeouts.clone())
+                .with_cache_config(config.lsp_cache.clone())
+        );
         
         // Set LSP manager in config so tools can access it
         config.set_lsp_manager(lsp_manager.clone());
         
-        let tools = get_all_tools().into_iter()
+        let tools = Arc::new(get_enabled_tools(&config).into_iter()
             .map(|tool| (tool.name().to_string(), tool))
-            .collect::<HashMap<_, _>>();
+            .collect::<HashMap<_, _>>());
         
         log(&config, "INFO", &format!("🔧 Registered {} tools", tools.len()));
         log(&config, "INFO", "🧠 LSP manager initialized for file synchronization");
@@ -67,20 +194,77 @@ impl McpServer {
         log(&self.config, "INFO", "🚀 MCP server initialized");
         
         let stdin = tokio::io::stdin();
-        let mut stdout = tokio::io::stdout();
+        let stdout = Arc::new(Mutex::new(tokio::io::stdout()));
         let mut reader = TokioBufReader::new(stdin);
         let mut line = String::new();
         let mut request_count = 0;
         
-        let handler = RequestHandler::new(&self.config, &self.tools);
+        // 📡 Progress notifications from running tools share stdout with responses
+        let (notification_tx, mut notification_rx) = tokio::sync::mpsc::unbounded_channel::<JsonRpcNotification>();
+        let notification_stdout = Arc::clone(&stdout);
+        let notification_writer = tokio::spawn(async move {
+            while let Some(notification) = notification_rx.recv().await {
+                match serde_json::to_string(&notification) {
+                    Ok(message) => {
+                        if let Err(e) = write_message(&notification_stdout, &message).await {
+                            log::warn!("📡 Failed to send notification: {}", e);
+                            return;
+                        }
+                    }
+                    Err(e) => log::warn!("📡 Failed to serialize notification: {}", e),
+                }
+            }
+        });
+        
+        // ⚡ Each message is served on its own task, so a slow tool call doesn't
+        // hold up the ones behind it; the tool limiter bounds how many execute
+        let shared_config = Arc::new(self.config.clone());
+        let mut in_flight = tokio::task::JoinSet::new();
+        let mut signalled = false;
+
+        // ♻️ Re-open documents left open by the previous session (in background)
+        if self.lsp_manager.state_file().exists() {
+            let lsp_manager = Arc::clone(&self.lsp_manager);
+            tokio::spawn(async move {
+                if let Err(e) = lsp_manager.restore_document_state().await {
+                    log::warn!("♻️ Failed to restore LSP document state: {}", e);
+                }
+            });
+        }
+        
+        // ⏱️ Automatic idle server cleanup (opt-in via LSP_IDLE_BACKGROUND)
+        let idle_monitor = self.lsp_manager.start_idle_monitor();
+        // 🚨 Crash recovery, plus runaway server recycling (opt-in via LSP_RESOURCE_ACTION)
+        let resource_monitor = self.lsp_manager.start_resource_monitor();
+        // 🔥 Servers for detected projects start indexing before the first request (opt-in via LSP_PREWARM)
+        let prewarm = self.lsp_manager.start_prewarm(self.config.lsp_prewarm);
+        
+        // 👀 Change notifications for paths registered via watch_paths
+        let watch_loop = tokio::spawn(run_watch_loop(
+            Arc::clone(&self.config.file_watcher),
+            Arc::clone(&self.lsp_manager),
+            Arc::clone(&stdout),
+        ));
+        
+        let shutdown = shutdown_signal();
+        tokio::pin!(shutdown);
         
         loop {
             log(&self.config, "DEBUG", &format!("📋 Loop iteration {request_count}, clearing line buffer"));
             line.clear();
             
-            match reader.read_line(&mut line).await {
+            let read = tokio::select! {
+                read = reader.read_line(&mut line) => read,
+                signal = &mut shutdown => {
+                    log(&self.config, "INFO", &format!("🛑 Received {signal}, shutting down"));
+                    signalled = true;
+                    break;
+                }
+            };
+            
+            match read {
                 Ok(0) => {
-                    log(&self.config, "INFO", "🚀 MCP server initialized");
+                    log(&self.config, "INFO", "📭 stdin closed, shutting down");
                     break;
                 },
                 Ok(bytes_read) => {
@@ -92,41 +276,28 @@ impl McpServer {
                         continue;
                     }
                     
-                    match serde_json::from_str::<JsonRpcRequest>(&line) {
-                        Ok(request) => {
-                            log(&self.config, "INFO", &format!("✅ Parsed JSON-RPC request: {}", request.method));
-                            
-                            if let Some(response) = handler.handle_request(request).await {
-                                match serde_json::to_string(&response) {
-                                    Ok(response_json) => {
-                                        log(&self.config, "DEBUG", "📤 Sending JSON-RPC response");
-                                        
-                                        if let Err(e) = stdout.write_all(response_json.as_bytes()).await {
-                                            log(&self.config, "ERROR", &format!("❌ Failed to write response: {e}"));
-                                            return Err(e.into());
-                                        }
-                                        
-                                        if let Err(e) = stdout.write_all(b"\n").await {
-                                            log(&self.config, "ERROR", &format!("❌ Failed to write newline: {e}"));
-                                            return Err(e.into());
-                                        }
-                                        
-                                        if let Err(e) = stdout.flush().await {
-                                            log(&self.config, "ERROR", &format!("❌ Failed to flush stdout: {e}"));
-                                            return Err(e.into());
-                                        }
-                                    },
-                                    Err(e) => {
-                                        log(&self.config, "ERROR", &format!("❌ Failed to serialize response: {e}"));
-                                        return Err(e.into());
-                                    }
+                    let config = Arc::clone(&shared_config);
+                    let tools = Arc::clone(&self.tools);
+                    let notifications = notification_tx.clone();
+                    let stdout = Arc::clone(&stdout);
+                    let message = line.clone();
+                    in_flight.spawn(async move {
+                        let handler = RequestHandler::new(&config, &tools).with_notifications(notifications);
+                        match reply_to(&handler, &config, &message).await {
+                            Some(Ok(response_json)) => {
+                                log(&config, "DEBUG", "📤 Sending JSON-RPC response");
+                                if let Err(e) = write_message(&stdout, &response_json).await {
+                                    log(&config, "ERROR", &format!("❌ Failed to write response: {e}"));
                                 }
-                            }
-                        },
-                        Err(e) => {
-                            log(&self.config, "ERROR", &format!("❌ Failed to parse JSON-RPC request: {e}"));
+                            },
+                            Some(Err(e)) => {
+                                log(&config, "ERROR", &format!("❌ Failed to serialize response: {e}"));
+                            },
+                            None => {}
                         }
-                    }
+                    });
+                    // Reap finished requests so the set only holds running ones
+                    while in_flight.try_join_next().is_some() {}
                 },
                 Err(e) => {
                     log(&self.config, "ERROR", &format!("❌ Failed to read from stdin: {e}"));
@@ -135,9 +306,37 @@ impl McpServer {
             }
         }
         
+        // 📭 Requests already read still get their replies; a signal cancels them
+        if signalled {
+            in_flight.abort_all();
+        }
+        while in_flight.join_next().await.is_some() {}
+        
+        watch_loop.abort();
+        notification_writer.abort();
+        if let Some(idle_monitor) = idle_monitor {
+            idle_monitor.stop();
+        }
+        if let Some(prewarm) = prewarm {
+            prewarm.abort();
+        }
+        resource_monitor.abort();
+        
+        // 🛑 Stuck servers get SHUTDOWN_TIMEOUT to answer, then are killed
         log(&self.config, "INFO", "🧠 Shutting down LSP servers before exit");
-        if let Err(e) = self.lsp_manager.shutdown_all().await {
-            log(&self.config, "ERROR", &format!("❌ Error shutting down LSP servers: {}", e));
+        match tokio::time::timeout(self.config.shutdown_timeout, self.lsp_manager.graceful_shutdown_all()).await {
+            Ok(Ok(())) => {},
+            Ok(Err(e)) => {
+                log(&self.config, "ERROR", &format!("❌ Error shutting down LSP servers: {}", e));
+            },
+            Err(_) => {
+                let killed = self.lsp_manager.kill_all().await;
+                log(&self.config, "WARN", &format!(
+                    "💀 LSP shutdown exceeded {}s, killed {} server(s)",
+                    self.config.shutdown_timeout.as_secs(),
+                    killed,
+                ));
+            },
         }
         
         log(&self.config, "INFO", "✅ MCP server shutdown complete");
@@ -0,0 +1,158 @@
+//! 🪝 Context Hooks - Project context inlined into prompts at render time
+//!
+//! After arguments are filled in, a template may still contain hooks:
+//!
+//! - `{{file:PATH}}` - the file's content (read_file, fenced)
+//! - `{{diagnostics:PATH}}` - rust-analyzer diagnostics for a file
+//! - `{{diagnostics}}` - diagnostics across the project
+//! - `{{git_diff}}` / `{{git_diff:PATH}}` - uncommitted changes
+//!
+//! Hooks may use arguments (`{{file:{{path}}}}`). Each is resolved by calling
+//! the matching tool, so path checks and the tool policy apply; output is
+//! capped per hook and in total. A hook that fails is replaced by a short
+//! note instead of failing the prompt.
+
+use regex::Regex;
+use serde_json::{json, Value};
+use std::sync::OnceLock;
+
+use crate::config::Config;
+use crate::tools::{get_enabled_tools, truncate_response};
+
+/// Largest inlined result of a single hook
+pub const HOOK_MAX_BYTES: usize = 16 * 1024;
+
+/// Largest total of inlined context when `max_response_bytes` is unlimited
+pub const CONTEXT_MAX_BYTES: usize = 64 * 1024;
+
+/// 🪝 A parsed context hook
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContextHook {
+    File(String),
+    Diagnostics(Option<String>),
+    GitDiff(Option<String>),
+}
+
+impl ContextHook {
+    /// Parse the inside of `{{...}}`; `None` for anything that isn't a hook
+    pub fn parse(spec: &str) -> Option<Self> {
+        let (kind, argument) = match spec.split_once(':') {
+            Some((kind, argument)) => (kind.trim(), Some(argument.trim().to_string()).filter(|a| !a.is_empty())),
+            None => (spec.trim(), None),
+        };
+        match kind {
+            "file" => argument.map(Self::File),
+            "diagnostics" => Some(Self::Diagnostics(argument)),
+            "git_diff" => Some(Self::GitDiff(argument)),
+            _ => None,
+        }
+    }
+
+    /// Tool and arguments that produce the hook's content
+    fn tool_call(&self) -> (&'static str, Value) {
+        match self {
+            Self::File(path) => ("read_file", json!({"path": path, "fenced": true})),
+            Self::Diagnostics(Some(path)) => ("lsp_diagnostics", json!({"file_path": path, "project": ""})),
+            Self::Diagnostics(None) => ("lsp_project_diagnostics", json!({"project": ""})),
+            Self::GitDiff(Some(path)) => ("git_diff", json!({"paths": [path]})),
+            Self::GitDiff(None) => ("git_diff", json!({})),
+        }
+    }
+}
+
+fn hook_pattern() -> &'static Regex {
+    static HOOK: OnceLock<Regex> = OnceLock::new();
+    HOOK.get_or_init(|| Regex::new(r"\{\{((?:file|diagnostics|git_diff)(?::[^{}]*)?)\}\}").unwrap())
+}
+
+/// Whether `text` contains any context hook
+pub fn has_hooks(text: &str) -> bool {
+    hook_pattern().is_match(text)
+}
+
+/// 🪝 Replace every hook in `text` with its resolved content
+pub async fn resolve_hooks(text: &str, config: &Config) -> String {
+    let mut budget = match config.max_response_bytes {
+        0 => CONTEXT_MAX_BYTES,
+        limit => limit.min(CONTEXT_MAX_BYTES),
+    };
+    let mut rendered = String::with_capacity(text.len());
+    let mut last = 0;
+    for captures in hook_pattern().captures_iter(text) {
+        let whole = captures.get(0).unwrap();
+        rendered.push_str(&text[last..whole.start()]);
+        last = whole.end();
+
+        let Some(hook) = ContextHook::parse(&captures[1]) else {
+            rendered.push_str(whole.as_str());
+            continue;
+        };
+        let content = resolve_hook(&hook, HOOK_MAX_BYTES.min(budget), config).await;
+        budget = budget.saturating_sub(content.len());
+        rendered.push_str(&content);
+    }
+    rendered.push_str(&text[last..]);
+    rendered
+}
+
+/// Content for one hook, at most about `max_bytes` (a truncation note may follow)
+async fn resolve_hook(hook: &ContextHook, max_bytes: usize, config: &Config) -> String {
+    let (tool_name, arguments) = hook.tool_call();
+    if max_bytes == 0 {
+        return format!("[{} omitted: prompt context limit reached]", tool_name);
+    }
+    let Some(tool) = get_enabled_tools(config).into_iter().find(|tool| tool.name() == tool_name) else {
+        return format!("[{} unavailable: tool is disabled]", tool_name);
+    };
+
+    match tool.execute(arguments, config).await {
+        Ok(mut result) => {
+            truncate_response(&mut result, max_bytes, Some("the prompt context cap applies"));
+            let text: Vec<&str> = result["content"].as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|item| item["text"].as_str())
+                .collect();
+            text.join("\n")
+        }
+        Err(error) => {
+            log::debug!("🪝 Prompt context hook {:?} failed: {}", hook, error);
+            format!("[{} unavailable: {}]", tool_name, error)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hooks() {
+        assert_eq!(ContextHook::parse("file:src/lib.rs"), Some(ContextHook::File("src/lib.rs".to_string())));
+        assert_eq!(ContextHook::parse("file:"), None);
+        assert_eq!(ContextHook::parse("diagnostics"), Some(ContextHook::Diagnostics(None)));
+        assert_eq!(ContextHook::parse("git_diff: src "), Some(ContextHook::GitDiff(Some("src".to_string()))));
+        assert_eq!(ContextHook::parse("path"), None);
+        assert!(has_hooks("see {{file:a.rs}}"));
+        assert!(!has_hooks("plain {{path}} template"));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_file_hook_with_cap() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("small.rs"), "fn main() {}\n").unwrap();
+        std::fs::write(dir.path().join("big.txt"), "x".repeat(HOOK_MAX_BYTES * 2)).unwrap();
+        let config = Config::new(dir.path().to_path_buf());
+
+        let text = resolve_hooks("Code:\n{{file:small.rs}}\nEnd", &config).await;
+        assert!(text.contains("```rust\nfn main() {}\n```"), "{}", text);
+        assert!(text.starts_with("Code:\n") && text.ends_with("\nEnd"));
+
+        let text = resolve_hooks("{{file:big.txt}}", &config).await;
+        assert!(text.len() < HOOK_MAX_BYTES + 200);
+        assert!(text.contains("output truncated"));
+
+        let text = resolve_hooks("{{file:missing.rs}}", &config).await;
+        assert!(text.starts_with("[read_file unavailable:"), "{}", text);
+    }
+}
@@ -0,0 +1,11 @@
+//! 📝 Prompts - MCP prompt templates served via prompts/list and prompts/get
+//!
+//! Every prompt declares its arguments (name, description, required, default)
+//! so clients can render a form, and `get_prompt` rejects calls with missing
+//! or unknown arguments before filling the template. `render_prompt` then
+//! inlines project context (files, diagnostics, diffs) the template asks for.
+
+pub mod context;
+pub mod templates;
+
+pub use templates::{get_prompt, get_prompts_schema, render_prompt};
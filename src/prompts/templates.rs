@@ -0,0 +1,199 @@
+//! 📝 Prompt Templates - Built-in prompts and argument validation
+//!
+//! Templates use `{{argument}}` placeholders. Optional arguments fall back to
+//! their declared default (or an empty string) when the client omits them.
+//! Context hooks such as `{{file:{{path}}}}` are left for `render_prompt`.
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+use crate::config::Config;
+use crate::error::{EmpathicError, EmpathicResult};
+use super::context;
+
+/// 🏷️ One declared prompt argument
+#[derive(Debug, Clone, Serialize)]
+pub struct PromptArgument {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub required: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<&'static str>,
+}
+
+/// 📝 A prompt template with its argument schema
+#[derive(Debug, Clone, Serialize)]
+pub struct PromptTemplate {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub arguments: &'static [PromptArgument],
+    #[serde(skip)]
+    pub template: &'static str,
+}
+
+const fn required(name: &'static str, description: &'static str) -> PromptArgument {
+    PromptArgument { name, description, required: true, default: None }
+}
+
+const fn optional(name: &'static str, description: &'static str, default: Option<&'static str>) -> PromptArgument {
+    PromptArgument { name, description, required: false, default }
+}
+
+/// 📚 Built-in prompts
+pub static PROMPTS: &[PromptTemplate] = &[
+    PromptTemplate {
+        name: "code_review",
+        description: "Review a file for bugs, readability and error handling",
+        arguments: &[
+            required("path", "File to review, relative to the project"),
+            optional("focus", "What to concentrate on", Some("correctness, readability and error handling")),
+        ],
+        template: "Review `{{path}}`. Read it with read_file first, then focus on {{focus}}. \
+                   List concrete issues with line numbers and suggested fixes, most severe first.",
+    },
+    PromptTemplate {
+        name: "explain_error",
+        description: "Explain a compiler or runtime error and propose a fix",
+        arguments: &[
+            required("error", "The error message, as printed"),
+            optional("context", "What you were doing when it happened", None),
+        ],
+        template: "Explain this error and how to fix it:\n\n```\n{{error}}\n```\n\n{{context}}\n\n\
+                   Use lsp_diagnostics and read_file to look at the code involved before answering.",
+    },
+    PromptTemplate {
+        name: "commit_message",
+        description: "Write a commit message for the current changes",
+        arguments: &[
+            optional("style", "Message convention", Some("a short imperative subject line and a wrapped body")),
+        ],
+        template: "Run git_diff to see the uncommitted changes, then write a commit message using {{style}}. \
+                   Describe what changed and why; don't list files.",
+    },
+    PromptTemplate {
+        name: "fix_diagnostics",
+        description: "Fix the errors and warnings rust-analyzer reports for a file (inlines diagnostics and content)",
+        arguments: &[
+            required("path", "Rust file to fix, relative to the root"),
+        ],
+        template: "Fix the problems rust-analyzer reports in `{{path}}`, errors first.\n\n\
+                   Diagnostics:\n{{diagnostics:{{path}}}}\n\n\
+                   Current content:\n{{file:{{path}}}}\n\n\
+                   Make the smallest edits that fix each one, then check with lsp_diagnostics again.",
+    },
+];
+
+/// 📋 Prompt list for `prompts/list`
+pub fn get_prompts_schema() -> Value {
+    json!(PROMPTS)
+}
+
+/// 🎯 Validate `arguments` and render the named prompt for `prompts/get`
+pub fn get_prompt(name: &str, arguments: &Map<String, Value>) -> EmpathicResult<Value> {
+    let prompt = PROMPTS.iter()
+        .find(|prompt| prompt.name == name)
+        .ok_or_else(|| EmpathicError::PromptNotFound { name: name.to_string() })?;
+
+    let missing: Vec<String> = prompt.arguments.iter()
+        .filter(|arg| arg.required && !arguments.contains_key(arg.name))
+        .map(|arg| arg.name.to_string())
+        .collect();
+    let unknown: Vec<String> = arguments.keys()
+        .filter(|key| !prompt.arguments.iter().any(|arg| arg.name == key.as_str()))
+        .cloned()
+        .collect();
+    if !missing.is_empty() || !unknown.is_empty() {
+        return Err(EmpathicError::PromptArgumentsInvalid {
+            prompt: name.to_string(),
+            missing,
+            unknown,
+        });
+    }
+
+    let mut text = prompt.template.to_string();
+    for arg in prompt.arguments {
+        let value = match arguments.get(arg.name) {
+            Some(Value::String(value)) => value.clone(),
+            Some(other) => return Err(EmpathicError::mcp_parameter(arg.name, format!("expected a string, got {}", other))),
+            None => arg.default.unwrap_or_default().to_string(),
+        };
+        text = text.replace(&format!("{{{{{}}}}}", arg.name), &value);
+    }
+
+    Ok(json!({
+        "description": prompt.description,
+        "messages": [{
+            "role": "user",
+            "content": {"type": "text", "text": text.trim()}
+        }]
+    }))
+}
+
+/// 🪝 `get_prompt`, then resolve context hooks by calling the matching tools
+pub async fn render_prompt(name: &str, arguments: &Map<String, Value>, config: &Config) -> EmpathicResult<Value> {
+    let mut result = get_prompt(name, arguments)?;
+    let text = &mut result["messages"][0]["content"]["text"];
+    if let Some(template) = text.as_str().filter(|template| context::has_hooks(template)) {
+        *text = Value::String(context::resolve_hooks(template, config).await);
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(value: Value) -> Map<String, Value> {
+        value.as_object().cloned().unwrap()
+    }
+
+    #[test]
+    fn test_prompts_declare_arguments() {
+        let schema = get_prompts_schema();
+        let review = schema.as_array().unwrap().iter().find(|p| p["name"] == "code_review").unwrap();
+        assert_eq!(review["arguments"][0]["name"], "path");
+        assert_eq!(review["arguments"][0]["required"], true);
+        assert!(review["arguments"][1]["default"].is_string());
+        assert!(review.get("template").is_none());
+    }
+
+    #[test]
+    fn test_get_prompt_fills_defaults() {
+        let result = get_prompt("code_review", &args(json!({"path": "src/lib.rs"}))).unwrap();
+        let text = result["messages"][0]["content"]["text"].as_str().unwrap();
+        assert!(text.contains("`src/lib.rs`"));
+        assert!(text.contains("correctness, readability"));
+        assert!(!text.contains("{{"));
+    }
+
+    #[test]
+    fn test_get_prompt_reports_missing_and_unknown() {
+        match get_prompt("code_review", &args(json!({"paht": "x"}))) {
+            Err(EmpathicError::PromptArgumentsInvalid { missing, unknown, .. }) => {
+                assert_eq!(missing, vec!["path"]);
+                assert_eq!(unknown, vec!["paht"]);
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(matches!(get_prompt("nope", &Map::new()), Err(EmpathicError::PromptNotFound { .. })));
+        assert!(get_prompt("explain_error", &args(json!({"error": 42}))).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_render_prompt_inlines_context() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("lib.rs"), "pub fn broken() -> u32 { \"no\" }\n").unwrap();
+        let config = Config::new(dir.path().to_path_buf());
+
+        let result = render_prompt("fix_diagnostics", &args(json!({"path": "lib.rs"})), &config).await.unwrap();
+        let text = result["messages"][0]["content"]["text"].as_str().unwrap();
+        assert!(text.contains("```rust\npub fn broken()"));
+        // No LSP manager here: the hook degrades to a note instead of failing
+        assert!(text.contains("[lsp_diagnostics unavailable:"));
+        assert!(!text.contains("{{"));
+
+        // Plain templates render exactly as get_prompt does
+        let plain = args(json!({"path": "lib.rs"}));
+        assert_eq!(render_prompt("code_review", &plain, &config).await.unwrap(), get_prompt("code_review", &plain).unwrap());
+    }
+}
@@ -0,0 +1,198 @@
+//! 🧾 Argument Validation - Check tool arguments against the tool's JSON schema
+//!
+//! Runs before a tool deserializes its arguments so a malformed call is told
+//! exactly which field is missing or mistyped and what was expected, instead
+//! of a bare serde message. Supports the schema subset tools use: `type`
+//! (single or list), `enum`, `minimum`/`maximum`, `required`, `properties`,
+//! `items` and `oneOf`/`anyOf`. Unknown fields are left to the tool.
+
+use serde_json::Value;
+
+/// 🧾 Problems with `args` under `schema`, one readable line each (empty when valid)
+pub fn validate_arguments(schema: &Value, args: &Value) -> Vec<String> {
+    let mut problems = Vec::new();
+    check(schema, args, "", &mut problems);
+    problems
+}
+
+fn check(schema: &Value, value: &Value, path: &str, problems: &mut Vec<String>) {
+    if let Some(types) = schema.get("type") {
+        let allowed: Vec<&str> = match types {
+            Value::String(name) => vec![name.as_str()],
+            Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !allowed.is_empty() && !allowed.iter().any(|name| has_type(value, name)) {
+            problems.push(format!("{} must be {}, got {}", describe(path), allowed.join(" or "), show(value)));
+            return;
+        }
+    }
+
+    if let Some(options) = schema.get("enum").and_then(Value::as_array)
+        && !options.contains(value)
+    {
+        let options: Vec<String> = options.iter().map(Value::to_string).collect();
+        problems.push(format!("{} must be one of {}, got {}", describe(path), options.join(", "), show(value)));
+    }
+
+    if let Some(number) = value.as_f64() {
+        if let Some(minimum) = schema.get("minimum").and_then(Value::as_f64)
+            && number < minimum
+        {
+            problems.push(format!("{} must be >= {}, got {}", describe(path), minimum, value));
+        }
+        if let Some(maximum) = schema.get("maximum").and_then(Value::as_f64)
+            && number > maximum
+        {
+            problems.push(format!("{} must be <= {}, got {}", describe(path), maximum, value));
+        }
+    }
+
+    if let Some(object) = value.as_object() {
+        let required: Vec<&str> = schema.get("required").and_then(Value::as_array)
+            .map(|names| names.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+        let properties = schema.get("properties").and_then(Value::as_object);
+
+        for name in &required {
+            if !object.contains_key(*name) {
+                let expected = properties
+                    .and_then(|properties| properties.get(*name))
+                    .and_then(|property| property.get("type"))
+                    .map(|kind| format!(" ({})", kind.as_str().map_or_else(|| kind.to_string(), str::to_string)))
+                    .unwrap_or_default();
+                problems.push(format!("missing required field '{}'{}", join(path, name), expected));
+            }
+        }
+        for (name, field) in object {
+            let Some(property) = properties.and_then(|properties| properties.get(name)) else {
+                continue;
+            };
+            // Optional fields may be sent as null, which serde reads as absent
+            if field.is_null() && !required.contains(&name.as_str()) {
+                continue;
+            }
+            check(property, field, &join(path, name), problems);
+        }
+    }
+
+    if let (Some(items), Some(elements)) = (schema.get("items"), value.as_array()) {
+        for (index, element) in elements.iter().enumerate() {
+            check(items, element, &format!("{path}[{index}]"), problems);
+        }
+    }
+
+    for keyword in ["oneOf", "anyOf"] {
+        let Some(branches) = schema.get(keyword).and_then(Value::as_array) else {
+            continue;
+        };
+        // Report the branch that came closest (right type first, then fewest
+        // problems), so the advice is actionable
+        let closest = branches.iter()
+            .map(|branch| {
+                let mut branch_problems = Vec::new();
+                check(branch, value, path, &mut branch_problems);
+                let type_matches = branch.get("type").and_then(Value::as_str)
+                    .is_none_or(|name| has_type(value, name));
+                (!type_matches, branch_problems)
+            })
+            .min_by_key(|(wrong_type, branch_problems)| (*wrong_type, branch_problems.len()));
+        if let Some((_, closest)) = closest
+            && !closest.is_empty()
+        {
+            problems.extend(closest);
+        }
+    }
+}
+
+fn has_type(value: &Value, name: &str) -> bool {
+    match name {
+        "string" => value.is_string(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "boolean" => value.is_boolean(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+fn join(path: &str, name: &str) -> String {
+    if path.is_empty() { name.to_string() } else { format!("{path}.{name}") }
+}
+
+fn describe(path: &str) -> String {
+    if path.is_empty() { "arguments".to_string() } else { format!("field '{path}'") }
+}
+
+/// JSON type and a short preview of a value
+fn show(value: &Value) -> String {
+    let kind = match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(number) if number.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    };
+    let mut preview = value.to_string();
+    if preview.chars().count() > 40 {
+        preview = format!("{}…", preview.chars().take(40).collect::<String>());
+    }
+    match value {
+        Value::Array(_) | Value::Object(_) => kind.to_string(),
+        _ => format!("{kind} {preview}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::SchemaBuilder;
+    use serde_json::json;
+
+    #[test]
+    fn test_reports_missing_and_mistyped_fields() {
+        let schema = SchemaBuilder::new()
+            .required_string("path", "File path")
+            .optional_integer("limit", "Max results", Some(1))
+            .optional_bool("recursive", "Recurse", Some(false))
+            .build();
+
+        assert!(validate_arguments(&schema, &json!({"path": "a.rs", "limit": 3})).is_empty());
+        assert!(validate_arguments(&schema, &json!({"path": "a.rs", "limit": null})).is_empty());
+        assert_eq!(validate_arguments(&schema, &json!({"limit": "5", "recursive": "yes"})), vec![
+            "missing required field 'path' (string)".to_string(),
+            "field 'limit' must be integer, got string \"5\"".to_string(),
+            "field 'recursive' must be boolean, got string \"yes\"".to_string(),
+        ]);
+        assert_eq!(validate_arguments(&schema, &json!({"path": "a.rs", "limit": 0})),
+            vec!["field 'limit' must be >= 1, got 0".to_string()]);
+        assert_eq!(validate_arguments(&schema, &json!(["a.rs"])), vec!["arguments must be object, got array".to_string()]);
+    }
+
+    #[test]
+    fn test_nested_items_and_alternatives() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "occurrence": {"oneOf": [{"type": "integer", "minimum": 1}, {"type": "string", "enum": ["all"]}]},
+                "operations": {
+                    "type": "array",
+                    "items": {"type": "object", "properties": {"search": {"type": "string"}}, "required": ["search"]}
+                }
+            },
+            "anyOf": [{"required": ["path", "search"]}, {"required": ["path", "operations"]}]
+        });
+
+        assert!(validate_arguments(&schema, &json!({"path": "a", "operations": [{"search": "x"}], "occurrence": "all"})).is_empty());
+        assert_eq!(validate_arguments(&schema, &json!({"path": "a", "operations": [{}]})),
+            vec!["missing required field 'operations[0].search' (string)".to_string()]);
+        assert_eq!(validate_arguments(&schema, &json!({"path": "a", "search": "x", "occurrence": "first"})),
+            vec!["field 'occurrence' must be one of \"all\", got string \"first\"".to_string()]);
+        assert_eq!(validate_arguments(&schema, &json!({"search": "x"})),
+            vec!["missing required field 'path'".to_string()]);
+    }
+}
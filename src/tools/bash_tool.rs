@@ -4,15 +4,14 @@
 //! with `command` and `description` parameters for better context and logging.
 
 use async_trait::async_trait;
-use crate::error::EmpathicError;
 use serde::{Deserialize, Serialize};
-use std::env;
-use std::process::Stdio;
+use std::collections::HashMap;
 use tokio::process::Command;
 
 use crate::tools::{ToolBuilder, SchemaBuilder};
 use crate::config::Config;
 use crate::error::EmpathicResult;
+use super::executor_utils::{command_env, resolve_timeout, resolve_working_dir, run_with_timeout};
 
 /// 🐚 Bash Tool - Expected interface for Claude Desktop
 pub struct BashTool;
@@ -23,6 +22,13 @@ pub struct BashArgs {
     command: String,
     /// Description/reasoning for why this command is being run (for context and logging)
     description: String,
+    /// Kill the command (and its children) after this many seconds
+    timeout_secs: Option<u64>,
+    #[serde(default)]
+    stream: bool,
+    cwd: Option<String>,
+    #[serde(default)]
+    env: HashMap<String, String>,
 }
 
 #[derive(Serialize)]
@@ -54,60 +60,44 @@ impl ToolBuilder for BashTool {
         SchemaBuilder::new()
             .required_string("command", "Bash command to execute")
             .required_string("description", "Why I'm running this command (for context and debugging)")
+            .optional_integer("timeout_secs", "Kill the command and its child processes after this many seconds (default: 120, max: 1800)", Some(1))
+            .optional_bool("stream", "Send stdout/stderr lines as notifications/progress while the command runs (full output is still returned)", Some(false))
+            .optional_string("cwd", "Subdirectory to run in, relative to the project (must stay inside the root)")
+            .optional_string_map("env", "Extra environment variables, e.g. {\"RUST_LOG\": \"debug\"}")
             .build()
     }
     
     async fn run(args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
-        // Always use ROOT_DIR as working directory (simpler interface, no project parameter)
-        let working_dir = &config.root_dir;
+        let timeout_secs = resolve_timeout(args.timeout_secs)?;
+        
+        // ROOT_DIR by default (simpler interface, no project parameter); `cwd` narrows it
+        let working_dir = resolve_working_dir(config, None, args.cwd.as_deref())?;
         
         // Log the description for better debugging context
         log::info!("🐚 bash_tool: {} - Running: {}", args.description, args.command);
         
-        // Prepare environment with additional paths
-        let mut env_vars = std::collections::HashMap::new();
-        let path_enhanced = if !config.add_path.is_empty() {
-            let current_path = env::var("PATH").unwrap_or_default();
-            let additional_paths: Vec<String> = config.add_path
-                .iter()
-                .map(|p| p.to_string_lossy().to_string())
-                .collect();
-            let new_path = format!("{}:{}", additional_paths.join(":"), current_path);
-            env_vars.insert("PATH".to_string(), new_path);
-            true
-        } else {
-            false
-        };
+        let (env_vars, path_enhanced) = command_env(config, &args.env)?;
         
         // Use bash for command execution
         let mut cmd = Command::new("bash");
         cmd.arg("-c")
            .arg(&args.command)
-           .current_dir(working_dir)
-           .stdout(Stdio::piped())
-           .stderr(Stdio::piped());
+           .current_dir(&working_dir);
         
         for (key, value) in env_vars {
             cmd.env(key, value);
         }
         
-        let output = cmd.output().await
-            .map_err(|e| EmpathicError::ToolExecutionFailed {
-                tool_name: "bash_tool".to_string(),
-                message: format!("Failed to execute command '{}' ({}): {}", args.command, args.description, e),
-            })?;
-        
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let stderr = String::from_utf8_lossy(&output.stderr);
+        let output = run_with_timeout(cmd, "bash_tool", &args.command, timeout_secs, args.stream).await?;
         
         Ok(BashOutput {
             command: args.command,
             description: args.description,
             working_dir: working_dir.to_string_lossy().to_string(),
             exit_code: output.status.code().unwrap_or(-1),
-            stdout: stdout.trim_end().to_string(),
-            stderr: stderr.trim_end().to_string(),
             success: output.status.success(),
+            stdout: output.stdout,
+            stderr: output.stderr,
             path_enhanced,
         })
     }
@@ -0,0 +1,324 @@
+//! 🎯 Build Target Discovery - What `make` and `gradle` can run
+//!
+//! Makefiles are parsed directly: rule lines give the targets, `.PHONY`
+//! marks the phony ones and a trailing `## text` is taken as a description.
+//! Gradle only knows its tasks after configuring the build, so `gradle tasks
+//! --all` is run and its grouped listing parsed. Both results are cached,
+//! keyed by the build files' modification times.
+
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::SystemTime;
+
+/// Makefile names in GNU make's lookup order
+pub const MAKEFILE_NAMES: &[&str] = &["GNUmakefile", "makefile", "Makefile"];
+
+/// Files whose changes can alter the gradle task list
+pub const GRADLE_BUILD_FILES: &[&str] = &[
+    "build.gradle",
+    "build.gradle.kts",
+    "settings.gradle",
+    "settings.gradle.kts",
+];
+
+/// 🔨 One Makefile target
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct MakeTarget {
+    pub name: String,
+    pub phony: bool,
+    /// 1-based line of the first rule for this target
+    pub line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// 🔨 Targets of one Makefile
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct MakeTargets {
+    pub makefile: String,
+    /// Target run by a bare `make`
+    pub default_target: Option<String>,
+    pub targets: Vec<MakeTarget>,
+}
+
+/// 🐘 One gradle task
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct GradleTask {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// 🐘 A titled group from `gradle tasks`, e.g. "Build tasks"
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct GradleTaskGroup {
+    pub group: String,
+    pub tasks: Vec<GradleTask>,
+}
+
+/// 🐘 Gradle tasks of one project
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct GradleTasks {
+    pub groups: Vec<GradleTaskGroup>,
+    /// True when served from the mtime cache instead of running gradle
+    pub cached: bool,
+}
+
+/// Find the Makefile make would pick in `dir`
+pub fn find_makefile(dir: &Path) -> Option<PathBuf> {
+    MAKEFILE_NAMES.iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.is_file())
+}
+
+/// 🔍 Parse targets out of Makefile source
+pub fn parse_makefile(content: &str) -> (Option<String>, Vec<MakeTarget>) {
+    let mut targets: Vec<MakeTarget> = Vec::new();
+    let mut phony = HashSet::new();
+    let mut first_target = None;
+    let mut default_goal = None;
+    let mut in_define = false;
+
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if in_define {
+            in_define = trimmed != "endef";
+            continue;
+        }
+        if trimmed.starts_with("define ") || trimmed == "define" {
+            in_define = true;
+            continue;
+        }
+        // Recipes, comments and blank lines never declare targets
+        if line.starts_with('\t') || trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        if let Some(goal) = trimmed.strip_prefix(".DEFAULT_GOAL")
+            .and_then(|rest| rest.trim_start().trim_start_matches([':', '?', '+']).strip_prefix('='))
+        {
+            default_goal = Some(goal.trim().to_string());
+            continue;
+        }
+
+        let Some((names, rest)) = split_rule(line) else {
+            continue;
+        };
+        if names.starts_with(".PHONY") {
+            phony.extend(strip_comment(rest).split_whitespace().map(str::to_string));
+            continue;
+        }
+
+        let description = rest.split_once("##")
+            .map(|(_, text)| text.trim().to_string())
+            .filter(|text| !text.is_empty());
+        for name in names.split_whitespace() {
+            // Special targets, pattern rules and computed names aren't runnable by name
+            if name.starts_with('.') || name.contains('%') || name.contains('$') {
+                continue;
+            }
+            if first_target.is_none() {
+                first_target = Some(name.to_string());
+            }
+            match targets.iter_mut().find(|target| target.name == name) {
+                Some(existing) => {
+                    if existing.description.is_none() {
+                        existing.description = description.clone();
+                    }
+                }
+                None => targets.push(MakeTarget {
+                    name: name.to_string(),
+                    phony: false,
+                    line: index + 1,
+                    description: description.clone(),
+                }),
+            }
+        }
+    }
+
+    // .PHONY may come before or after the rules it names
+    for target in &mut targets {
+        target.phony = phony.contains(&target.name);
+    }
+    (default_goal.or(first_target), targets)
+}
+
+/// Split `targets: prerequisites` at the rule colon, skipping assignments
+fn split_rule(line: &str) -> Option<(&str, &str)> {
+    let colon = line.find(':')?;
+    let (names, rest) = (&line[..colon], &line[colon + 1..]);
+    if names.contains('=') || names.trim().is_empty() {
+        return None;
+    }
+    // `::` is a double-colon rule; `:=` and `::=` are assignments
+    let rest = rest.strip_prefix(':').unwrap_or(rest);
+    if rest.starts_with('=') {
+        return None;
+    }
+    Some((names.trim(), rest))
+}
+
+fn strip_comment(text: &str) -> &str {
+    text.split('#').next().unwrap_or_default()
+}
+
+/// 🔍 Parse the grouped listing printed by `gradle tasks --all --console=plain`
+pub fn parse_gradle_tasks(output: &str) -> Vec<GradleTaskGroup> {
+    let lines: Vec<&str> = output.lines().collect();
+    let mut groups: Vec<GradleTaskGroup> = Vec::new();
+    let mut current: Option<GradleTaskGroup> = None;
+    let mut index = 0;
+
+    while index < lines.len() {
+        let line = lines[index].trim_end();
+        let underline = lines.get(index + 1).map(|next| next.trim_end()).unwrap_or_default();
+        if is_rule(underline) && !line.is_empty() && !is_rule(line) {
+            groups.extend(current.take());
+            // The banner and the rules section share the heading format but hold no tasks
+            if !line.starts_with("Tasks runnable from") && line != "Rules" {
+                current = Some(GradleTaskGroup { group: line.to_string(), tasks: Vec::new() });
+            }
+            index += 2;
+            continue;
+        }
+        if line.is_empty() {
+            groups.extend(current.take());
+        } else if let Some(group) = current.as_mut() {
+            let (name, description) = match line.split_once(" - ") {
+                Some((name, description)) => (name, Some(description.trim().to_string())),
+                None => (line, None),
+            };
+            group.tasks.push(GradleTask { name: name.trim().to_string(), description });
+        }
+        index += 1;
+    }
+
+    groups.extend(current);
+    groups.retain(|group| !group.tasks.is_empty());
+    groups
+}
+
+fn is_rule(line: &str) -> bool {
+    line.len() >= 3 && line.chars().all(|c| c == '-')
+}
+
+/// Modification times of whichever `files` exist in `dir`
+pub fn mtimes(dir: &Path, files: &[&str]) -> Vec<Option<SystemTime>> {
+    files.iter()
+        .map(|file| std::fs::metadata(dir.join(file)).and_then(|meta| meta.modified()).ok())
+        .collect()
+}
+
+type CacheEntry<T> = (Vec<Option<SystemTime>>, T);
+
+/// 🗄️ Parsed results keyed by path, valid while the build files' mtimes match
+pub struct MtimeCache<T> {
+    entries: OnceLock<Mutex<HashMap<PathBuf, CacheEntry<T>>>>,
+}
+
+impl<T: Clone> MtimeCache<T> {
+    pub const fn new() -> Self {
+        Self { entries: OnceLock::new() }
+    }
+
+    pub fn get(&self, key: &Path, stamp: &[Option<SystemTime>]) -> Option<T> {
+        let entries = self.entries.get_or_init(Default::default).lock().ok()?;
+        entries.get(key)
+            .filter(|(cached_stamp, _)| cached_stamp == stamp)
+            .map(|(_, value)| value.clone())
+    }
+
+    pub fn insert(&self, key: PathBuf, stamp: Vec<Option<SystemTime>>, value: T) {
+        if let Ok(mut entries) = self.entries.get_or_init(Default::default).lock() {
+            entries.insert(key, (stamp, value));
+        }
+    }
+}
+
+impl<T: Clone> Default for MtimeCache<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_makefile() {
+        let makefile = "\
+CC := gcc
+VERSION = 1.0
+.PHONY: build test
+
+build: deps ## Compile everything
+\t$(CC) -o app main.c
+
+test: build
+\t./app --test
+
+%.o: %.c
+\t$(CC) -c $<
+
+define HELP
+fake: target
+endef
+
+clean::
+\trm -f app
+.PHONY: clean
+";
+        let (default_target, targets) = parse_makefile(makefile);
+        assert_eq!(default_target.as_deref(), Some("build"));
+        let names: Vec<&str> = targets.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["build", "test", "clean"]);
+        assert!(targets.iter().all(|t| t.phony));
+        assert_eq!(targets[0].description.as_deref(), Some("Compile everything"));
+        assert_eq!(targets[0].line, 5);
+    }
+
+    #[test]
+    fn test_default_goal_override() {
+        let (default_target, _) = parse_makefile(".DEFAULT_GOAL := test\nbuild:\n\ttrue\ntest:\n\ttrue\n");
+        assert_eq!(default_target.as_deref(), Some("test"));
+    }
+
+    #[test]
+    fn test_parse_gradle_tasks() {
+        let output = "\
+> Task :tasks
+
+------------------------------------------------------------
+Tasks runnable from root project 'demo'
+------------------------------------------------------------
+
+Application tasks
+-----------------
+run - Runs this project as a JVM application
+
+Build tasks
+-----------
+assemble - Assembles the outputs of this project.
+build - Assembles and tests this project.
+
+Other tasks
+-----------
+compileJava
+
+Rules
+-----
+Pattern: clean<TaskName>: Cleans the output files of a task.
+
+BUILD SUCCESSFUL in 1s
+";
+        let groups = parse_gradle_tasks(output);
+        let names: Vec<&str> = groups.iter().map(|g| g.group.as_str()).collect();
+        assert_eq!(names, vec!["Application tasks", "Build tasks", "Other tasks"]);
+        assert_eq!(groups[1].tasks[1].name, "build");
+        assert_eq!(groups[1].tasks[1].description.as_deref(), Some("Assembles and tests this project."));
+        assert_eq!(groups[2].tasks[0].description, None);
+    }
+}
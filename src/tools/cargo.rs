@@ -1,12 +1,15 @@
 //! 🦀 Cargo Tool - Clean ToolBuilder implementation
 
 use async_trait::async_trait;
-use crate::error::EmpathicResult;
-use serde::Deserialize;
+use crate::error::{EmpathicResult, EmpathicError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 use crate::tools::{ToolBuilder, SchemaBuilder};
 use crate::config::Config;
-use super::executor_utils::{execute_command, CommandOutput};
+use super::executor_utils::{execute_command_with, CommandOptions, CommandOutput};
+use super::cargo_output::{CargoClippyReport, CargoFmtReport, CargoTestReport};
+use super::cargo_toolchain::CargoToolchainReport;
 
 /// 🦀 Cargo Tool using modern ToolBuilder pattern
 pub struct CargoTool;
@@ -14,10 +17,32 @@ pub struct CargoTool;
 #[derive(Deserialize)]
 pub struct CargoArgs {
     args: Vec<String>,
+    /// Parse `cargo test` results / `cargo clippy` lints into JSON
+    #[serde(default)]
+    structured: bool,
+    /// For structured clippy: pass `--fix` to apply machine-applicable suggestions;
+    /// for structured fmt: write the formatting instead of `--check`
+    #[serde(default, alias = "apply")]
+    apply_fixable: bool,
+    /// Send output lines as progress notifications while cargo runs
+    #[serde(default)]
+    stream: bool,
+    cwd: Option<String>,
+    #[serde(default)]
+    env: HashMap<String, String>,
     project: Option<String>,
 }
 
-pub type CargoOutput = CommandOutput;
+/// 🦀 Raw command output, or a parsed report in structured mode
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum CargoOutput {
+    Raw(CommandOutput),
+    Test(Box<CargoTestReport>),
+    Clippy(Box<CargoClippyReport>),
+    Fmt(Box<CargoFmtReport>),
+    Toolchain(Box<CargoToolchainReport>),
+}
 
 #[async_trait]
 impl ToolBuilder for CargoTool {
@@ -34,15 +59,123 @@ impl ToolBuilder for CargoTool {
     
     fn schema() -> serde_json::Value {
         SchemaBuilder::new()
-            .required_array("args", "Cargo command arguments (e.g., ['build'], ['test', '--release'])")
+            .required_array("args", "Cargo command arguments (e.g., ['build'], ['test', '--release']); ['toolchain'] reports rustc/cargo versions, the active and pinned toolchain and the manifest's edition")
+            .optional_bool("structured", "For 'test': per-test pass/fail/ignored results, failure output and compile errors; for 'clippy': lints with spans and suggested replacements; for 'fmt': runs 'cargo fmt --check' and returns status formatted/would_change/error with the diff, writing nothing (raw output under 'raw')", Some(false))
+            .optional_bool("apply_fixable", "With structured clippy: run 'cargo clippy --fix' to apply machine-applicable suggestions (add --allow-dirty to args for uncommitted trees); with structured fmt: actually format the files (alias: 'apply')", Some(false))
+            .optional_bool("stream", "Send stdout/stderr lines as notifications/progress while the command runs (full output is still returned)", Some(false))
+            .optional_string("cwd", "Subdirectory to run in, relative to the project (must stay inside the root)")
+            .optional_string_map("env", "Extra environment variables, e.g. {\"RUST_LOG\": \"debug\"}")
             .optional_string("project", "Project name for execution directory")
             .build()
     }
     
     async fn run(args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
-        execute_command("cargo", args.args, args.project.as_deref(), config).await
+        let subcommand = args.args.first().map(String::as_str);
+        if args.apply_fixable && !(args.structured && matches!(subcommand, Some("clippy" | "fmt"))) {
+            return Err(EmpathicError::InvalidArgument {
+                arg: "apply_fixable".to_string(),
+                reason: "requires structured=true and a 'clippy' or 'fmt' command".to_string(),
+            });
+        }
+
+        let options = CommandOptions { cwd: args.cwd, env: args.env, stream: args.stream };
+        // 🧰 Not a cargo subcommand: gathered from rustc, rustup and the project files
+        if subcommand == Some("toolchain") {
+            let report = CargoToolchainReport::collect(args.project.as_deref(), config, &options).await?;
+            return Ok(CargoOutput::Toolchain(Box::new(report)));
+        }
+        if !args.structured {
+            let output = execute_command_with("cargo", args.args, args.project.as_deref(), config, &options).await?;
+            return Ok(CargoOutput::Raw(output));
+        }
+
+        match subcommand {
+            Some("test") => {
+                let cargo_args = with_json_messages(args.args);
+                let output = execute_command_with("cargo", cargo_args, args.project.as_deref(), config, &options).await?;
+                Ok(CargoOutput::Test(Box::new(CargoTestReport::from_output(output))))
+            }
+            Some("clippy") => {
+                let mut cargo_args = args.args;
+                if args.apply_fixable && !cargo_args.iter().any(|arg| arg == "--fix") {
+                    cargo_args.insert(1, "--fix".to_string());
+                }
+                let fixed = cargo_args.iter().any(|arg| arg == "--fix");
+                let cargo_args = with_json_messages(cargo_args);
+                let output = execute_command_with("cargo", cargo_args, args.project.as_deref(), config, &options).await?;
+                Ok(CargoOutput::Clippy(Box::new(CargoClippyReport::from_output(output, fixed))))
+            }
+            Some("fmt") => {
+                let cargo_args = if args.apply_fixable { args.args } else { with_fmt_check(args.args) };
+                let output = execute_command_with("cargo", cargo_args, args.project.as_deref(), config, &options).await?;
+                Ok(CargoOutput::Fmt(Box::new(CargoFmtReport::from_output(output, args.apply_fixable))))
+            }
+            _ => Err(EmpathicError::InvalidArgument {
+                arg: "structured".to_string(),
+                reason: "structured output is supported for 'cargo test', 'cargo clippy' and 'cargo fmt'".to_string(),
+            }),
+        }
+    }
+}
+
+/// Add `--message-format=json` before any `--` so it reaches cargo, not the test binary
+pub(crate) fn with_json_messages(mut args: Vec<String>) -> Vec<String> {
+    if args.iter().any(|arg| arg.starts_with("--message-format")) {
+        return args;
+    }
+    let position = args.iter().position(|arg| arg == "--").unwrap_or(args.len());
+    args.insert(position, "--message-format=json".to_string());
+    args
+}
+
+/// Add `--check` for cargo and `--color never` for rustfmt, keeping the diff plain text
+fn with_fmt_check(mut args: Vec<String>) -> Vec<String> {
+    let separator = match args.iter().position(|arg| arg == "--") {
+        Some(position) => position,
+        None => {
+            args.push("--".to_string());
+            args.len() - 1
+        }
+    };
+    if !args[..separator].iter().any(|arg| arg == "--check") {
+        args.insert(separator, "--check".to_string());
     }
+    if !args.iter().any(|arg| arg.starts_with("--color")) {
+        args.push("--color=never".to_string());
+    }
+    args
 }
 
 // 🔧 Implement Tool trait using the builder pattern
 crate::impl_tool_for_builder!(CargoTool);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_json_message_flag_goes_before_test_args() {
+        assert_eq!(with_json_messages(strings(&["test"])), strings(&["test", "--message-format=json"]));
+        assert_eq!(
+            with_json_messages(strings(&["test", "parser", "--", "--nocapture"])),
+            strings(&["test", "parser", "--message-format=json", "--", "--nocapture"])
+        );
+        let explicit = strings(&["test", "--message-format=json-diagnostic-short"]);
+        assert_eq!(with_json_messages(explicit.clone()), explicit);
+    }
+
+    #[test]
+    fn test_fmt_check_flags() {
+        assert_eq!(with_fmt_check(strings(&["fmt"])), strings(&["fmt", "--check", "--", "--color=never"]));
+        assert_eq!(
+            with_fmt_check(strings(&["fmt", "--all", "--", "--edition", "2021"])),
+            strings(&["fmt", "--all", "--check", "--", "--edition", "2021", "--color=never"])
+        );
+        let explicit = strings(&["fmt", "--check", "--", "--color=always"]);
+        assert_eq!(with_fmt_check(explicit.clone()), explicit);
+    }
+}
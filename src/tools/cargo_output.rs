@@ -0,0 +1,500 @@
+//! 🦀 Cargo Output Parsing - Structured results from cargo's JSON messages
+//!
+//! `cargo test --message-format=json` interleaves compiler messages (JSON
+//! lines) with libtest's report on stdout. Test results are read from the
+//! human `test name ... ok` lines, or from libtest JSON events when the run
+//! used `-Z unstable-options --format json`. `cargo clippy` output is the
+//! same compiler messages, with lint names and suggested replacements.
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::executor_utils::CommandOutput;
+
+/// 🩺 A rustc diagnostic taken from a `compiler-message`
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct CompilerDiagnostic {
+    pub level: String,
+    /// Error code or lint name, e.g. E0308
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_column: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rendered: Option<String>,
+    /// Replacements proposed by rustc/clippy
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub suggestions: Vec<Suggestion>,
+}
+
+impl CompilerDiagnostic {
+    /// Has a suggestion `cargo clippy --fix` would apply
+    pub fn is_fixable(&self) -> bool {
+        self.suggestions.iter().any(|s| s.applicability.as_deref() == Some("MachineApplicable"))
+    }
+}
+
+/// 🔧 A `suggested_replacement` for a source span
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Suggestion {
+    pub message: String,
+    pub file: String,
+    pub line: u64,
+    pub column: u64,
+    pub end_line: u64,
+    pub end_column: u64,
+    pub replacement: String,
+    /// MachineApplicable, MaybeIncorrect, HasPlaceholders or Unspecified
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub applicability: Option<String>,
+}
+
+/// 🧪 Outcome of a single test
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TestStatus {
+    Ok,
+    Failed,
+    Ignored,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct TestCase {
+    pub name: String,
+    pub status: TestStatus,
+    /// Captured output (failures only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+}
+
+#[derive(Serialize, Debug, Default, Clone, PartialEq)]
+pub struct TestSummary {
+    pub passed: usize,
+    pub failed: usize,
+    pub ignored: usize,
+    pub total: usize,
+}
+
+/// 🧪 Parsed `cargo test` run
+#[derive(Serialize)]
+pub struct CargoTestReport {
+    pub success: bool,
+    /// Compilation failed, so no (or not all) tests ran
+    pub build_failed: bool,
+    pub compile_errors: Vec<CompilerDiagnostic>,
+    pub warnings: usize,
+    pub summary: TestSummary,
+    pub tests: Vec<TestCase>,
+    pub raw: CommandOutput,
+}
+
+impl CargoTestReport {
+    pub fn from_output(raw: CommandOutput) -> Self {
+        let messages = parse_compiler_messages(&raw.stdout);
+        let tests = parse_test_results(&raw.stdout);
+
+        let compile_errors: Vec<CompilerDiagnostic> = messages.diagnostics.iter()
+            .filter(|d| d.level == "error")
+            .cloned()
+            .collect();
+        let warnings = messages.diagnostics.iter().filter(|d| d.level == "warning").count();
+
+        let summary = TestSummary {
+            passed: tests.iter().filter(|t| t.status == TestStatus::Ok).count(),
+            failed: tests.iter().filter(|t| t.status == TestStatus::Failed).count(),
+            ignored: tests.iter().filter(|t| t.status == TestStatus::Ignored).count(),
+            total: tests.len(),
+        };
+
+        Self {
+            success: raw.success,
+            build_failed: messages.build_succeeded == Some(false) || !compile_errors.is_empty(),
+            compile_errors,
+            warnings,
+            summary,
+            tests,
+            raw,
+        }
+    }
+}
+
+/// 📎 Parsed `cargo clippy` run
+#[derive(Serialize)]
+pub struct CargoClippyReport {
+    pub success: bool,
+    /// `--fix` was passed, so machine-applicable suggestions were applied
+    pub fixed: bool,
+    pub summary: LintSummary,
+    pub lints: Vec<CompilerDiagnostic>,
+    pub raw: CommandOutput,
+}
+
+#[derive(Serialize, Debug, Default, Clone, PartialEq)]
+pub struct LintSummary {
+    pub errors: usize,
+    pub warnings: usize,
+    /// Lints with a machine-applicable fix
+    pub fixable: usize,
+}
+
+impl CargoClippyReport {
+    pub fn from_output(raw: CommandOutput, fixed: bool) -> Self {
+        let lints = parse_compiler_messages(&raw.stdout).diagnostics;
+        let summary = LintSummary {
+            errors: lints.iter().filter(|d| d.level == "error").count(),
+            warnings: lints.iter().filter(|d| d.level == "warning").count(),
+            fixable: lints.iter().filter(|d| d.is_fixable()).count(),
+        };
+
+        Self {
+            success: raw.success,
+            fixed,
+            summary,
+            lints,
+            raw,
+        }
+    }
+}
+
+/// 🎨 Outcome of a `cargo fmt` run
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FmtStatus {
+    /// `--check` found nothing to change
+    Formatted,
+    /// `--check` found files rustfmt would rewrite
+    WouldChange,
+    /// Formatting was written to disk
+    Applied,
+    /// rustfmt or cargo failed (parse error, bad arguments, ...)
+    Error,
+}
+
+/// 🎨 Parsed `cargo fmt` run
+#[derive(Serialize)]
+pub struct CargoFmtReport {
+    pub status: FmtStatus,
+    /// Whether the code is (now) formatted
+    pub formatted: bool,
+    /// Files listed in the diff
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<String>,
+    /// The changes rustfmt would make
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<String>,
+    pub raw: CommandOutput,
+}
+
+impl CargoFmtReport {
+    /// Classify by exit code: `--check` exits 1 both for a diff and for a
+    /// parse error, so a diff without errors on stderr is what "would change" means
+    pub fn from_output(raw: CommandOutput, applied: bool) -> Self {
+        let files = parse_fmt_diff_files(&raw.stdout);
+        let errored = raw.stderr.lines().any(|line| line.starts_with("error"));
+        let status = match raw.exit_code {
+            0 if applied => FmtStatus::Applied,
+            0 => FmtStatus::Formatted,
+            1 if !applied && !files.is_empty() && !errored => FmtStatus::WouldChange,
+            _ => FmtStatus::Error,
+        };
+        let diff = (!raw.stdout.trim().is_empty()).then(|| raw.stdout.clone());
+
+        Self {
+            status,
+            formatted: matches!(status, FmtStatus::Formatted | FmtStatus::Applied),
+            files,
+            diff,
+            raw,
+        }
+    }
+}
+
+/// Files from rustfmt's `Diff in <file>:<line>:` headers, in order of appearance
+fn parse_fmt_diff_files(stdout: &str) -> Vec<String> {
+    let mut files: Vec<String> = Vec::new();
+    for line in stdout.lines() {
+        let Some(rest) = line.strip_prefix("Diff in ") else {
+            continue;
+        };
+        // Older rustfmt prints "Diff in <file> at line <n>:"
+        let file = match rest.split_once(" at line ") {
+            Some((file, _)) => file,
+            None => rest.trim_end_matches(':').rsplit_once(':').map_or(rest, |(file, _)| file),
+        };
+        if !files.iter().any(|known| known == file) {
+            files.push(file.to_string());
+        }
+    }
+    files
+}
+
+/// Compiler diagnostics plus cargo's final build verdict
+#[derive(Debug, Default)]
+pub struct CompilerMessages {
+    pub diagnostics: Vec<CompilerDiagnostic>,
+    pub build_succeeded: Option<bool>,
+}
+
+/// Collect `compiler-message` and `build-finished` records from cargo JSON output
+///
+/// Span-less summaries ("aborting due to...", "N warnings emitted") are
+/// dropped, as are repeats from targets sharing the same source.
+pub fn parse_compiler_messages(stdout: &str) -> CompilerMessages {
+    let mut messages = CompilerMessages::default();
+
+    for value in json_lines(stdout) {
+        match value["reason"].as_str() {
+            Some("compiler-message") => {
+                if let Some(diagnostic) = parse_diagnostic(&value["message"])
+                    && (diagnostic.file.is_some() || diagnostic.code.is_some())
+                    && !messages.diagnostics.contains(&diagnostic)
+                {
+                    messages.diagnostics.push(diagnostic);
+                }
+            }
+            Some("build-finished") => messages.build_succeeded = value["success"].as_bool(),
+            _ => {}
+        }
+    }
+    messages
+}
+
+fn parse_diagnostic(message: &Value) -> Option<CompilerDiagnostic> {
+    let primary = message["spans"].as_array()
+        .and_then(|spans| spans.iter().find(|span| span["is_primary"].as_bool() == Some(true)));
+
+    Some(CompilerDiagnostic {
+        level: message["level"].as_str()?.to_string(),
+        code: message["code"]["code"].as_str().map(str::to_string),
+        message: message["message"].as_str()?.to_string(),
+        file: primary.and_then(|span| span["file_name"].as_str()).map(str::to_string),
+        line: primary.and_then(|span| span["line_start"].as_u64()),
+        column: primary.and_then(|span| span["column_start"].as_u64()),
+        end_line: primary.and_then(|span| span["line_end"].as_u64()),
+        end_column: primary.and_then(|span| span["column_end"].as_u64()),
+        rendered: message["rendered"].as_str().map(str::to_string),
+        suggestions: parse_suggestions(message),
+    })
+}
+
+/// Suggested replacements on the message's own spans and its `help` children
+fn parse_suggestions(message: &Value) -> Vec<Suggestion> {
+    let own = std::iter::once(message);
+    let children = message["children"].as_array().into_iter().flatten();
+
+    own.chain(children)
+        .flat_map(|entry| {
+            let text = entry["message"].as_str().unwrap_or_default();
+            entry["spans"].as_array().into_iter().flatten()
+                .filter_map(move |span| Some(Suggestion {
+                    message: text.to_string(),
+                    file: span["file_name"].as_str()?.to_string(),
+                    line: span["line_start"].as_u64()?,
+                    column: span["column_start"].as_u64()?,
+                    end_line: span["line_end"].as_u64()?,
+                    end_column: span["column_end"].as_u64()?,
+                    replacement: span["suggested_replacement"].as_str()?.to_string(),
+                    applicability: span["suggestion_applicability"].as_str().map(str::to_string),
+                }))
+        })
+        .collect()
+}
+
+/// Parse libtest results, preferring JSON events when present
+pub fn parse_test_results(stdout: &str) -> Vec<TestCase> {
+    let json_tests: Vec<TestCase> = json_lines(stdout)
+        .filter(|value| value["type"] == "test")
+        .filter_map(|value| {
+            let status = match value["event"].as_str()? {
+                "ok" => TestStatus::Ok,
+                "failed" | "timeout" => TestStatus::Failed,
+                "ignored" => TestStatus::Ignored,
+                _ => return None, // "started"
+            };
+            Some(TestCase {
+                name: value["name"].as_str()?.to_string(),
+                status,
+                output: value["stdout"].as_str().filter(|s| !s.is_empty()).map(str::to_string),
+            })
+        })
+        .collect();
+
+    if !json_tests.is_empty() {
+        return json_tests;
+    }
+    parse_human_test_results(stdout)
+}
+
+/// `test name ... ok` lines plus the `---- name stdout ----` failure sections
+fn parse_human_test_results(stdout: &str) -> Vec<TestCase> {
+    let mut tests: Vec<TestCase> = Vec::new();
+    let mut failure_output: Vec<(String, String)> = Vec::new();
+    let mut current_failure: Option<(String, Vec<&str>)> = None;
+
+    for line in stdout.lines() {
+        if let Some(header) = line.strip_prefix("---- ").and_then(|rest| rest.strip_suffix(" ----")) {
+            if let Some((name, lines)) = current_failure.take() {
+                failure_output.push((name, lines.join("\n")));
+            }
+            let name = header.strip_suffix(" stdout").unwrap_or(header);
+            current_failure = Some((name.to_string(), Vec::new()));
+            continue;
+        }
+
+        if let Some((name, lines)) = current_failure.as_mut() {
+            // Failure sections end at the next libtest heading
+            if line == "failures:" || line == "successes:" || line.starts_with("test result:") {
+                failure_output.push((std::mem::take(name), lines.join("\n")));
+                current_failure = None;
+            } else {
+                lines.push(line);
+            }
+            continue;
+        }
+
+        if let Some(rest) = line.strip_prefix("test ")
+            && let Some((name, result)) = rest.rsplit_once(" ... ")
+        {
+            let status = match result {
+                "ok" => TestStatus::Ok,
+                "FAILED" => TestStatus::Failed,
+                result if result.starts_with("ignored") => TestStatus::Ignored,
+                _ => continue, // benchmarks and anything unrecognised
+            };
+            tests.push(TestCase { name: name.to_string(), status, output: None });
+        }
+    }
+
+    if let Some((name, lines)) = current_failure {
+        failure_output.push((name, lines.join("\n")));
+    }
+
+    for (name, output) in failure_output {
+        if let Some(test) = tests.iter_mut().find(|t| t.name == name && t.status == TestStatus::Failed && t.output.is_none()) {
+            test.output = Some(output.trim().to_string());
+        }
+    }
+    tests
+}
+
+fn json_lines(stdout: &str) -> impl Iterator<Item = Value> + '_ {
+    stdout.lines()
+        .filter(|line| line.starts_with('{'))
+        .filter_map(|line| serde_json::from_str(line).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HUMAN_RUN: &str = "\
+running 3 tests
+test tests::adds ... ok
+test tests::breaks ... FAILED
+test tests::slow ... ignored, takes minutes
+
+failures:
+
+---- tests::breaks stdout ----
+thread 'tests::breaks' panicked at src/lib.rs:9:9:
+assertion failed: false
+
+
+failures:
+    tests::breaks
+
+test result: FAILED. 1 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out";
+
+    #[test]
+    fn test_parse_human_results_with_failure_output() {
+        let tests = parse_test_results(HUMAN_RUN);
+        assert_eq!(tests.len(), 3);
+        assert_eq!(tests[0], TestCase { name: "tests::adds".to_string(), status: TestStatus::Ok, output: None });
+        assert_eq!(tests[1].status, TestStatus::Failed);
+        assert_eq!(tests[1].output.as_deref(), Some("thread 'tests::breaks' panicked at src/lib.rs:9:9:\nassertion failed: false"));
+        assert_eq!(tests[2].status, TestStatus::Ignored);
+    }
+
+    #[test]
+    fn test_parse_libtest_json_events() {
+        let stdout = r#"{ "type": "suite", "event": "started", "test_count": 2 }
+{ "type": "test", "event": "started", "name": "a" }
+{ "type": "test", "name": "a", "event": "ok" }
+{ "type": "test", "name": "b", "event": "failed", "stdout": "boom\n" }"#;
+
+        let tests = parse_test_results(stdout);
+        assert_eq!(tests.len(), 2);
+        assert_eq!(tests[1].status, TestStatus::Failed);
+        assert_eq!(tests[1].output.as_deref(), Some("boom\n"));
+    }
+
+    #[test]
+    fn test_parse_compile_error() {
+        let stdout = r#"{"reason":"compiler-message","message":{"level":"error","code":{"code":"E0308"},"message":"mismatched types","spans":[{"file_name":"src/lib.rs","line_start":3,"column_start":5,"is_primary":true}],"rendered":"error[E0308]: mismatched types"}}
+{"reason":"compiler-message","message":{"level":"warning","code":null,"message":"unused variable","spans":[{"file_name":"src/lib.rs","line_start":2,"column_start":9,"is_primary":true}],"rendered":null}}
+{"reason":"compiler-message","message":{"level":"error","code":null,"message":"aborting due to 1 previous error","spans":[],"rendered":null}}
+{"reason":"build-finished","success":false}"#;
+
+        let messages = parse_compiler_messages(stdout);
+        assert_eq!(messages.build_succeeded, Some(false));
+        assert_eq!(messages.diagnostics.len(), 2);
+        let error = &messages.diagnostics[0];
+        assert_eq!(error.code.as_deref(), Some("E0308"));
+        assert_eq!((error.file.as_deref(), error.line, error.column), (Some("src/lib.rs"), Some(3), Some(5)));
+        assert!(parse_test_results(stdout).is_empty());
+    }
+
+    #[test]
+    fn test_clippy_lint_with_suggestion() {
+        let stdout = r#"{"reason":"compiler-message","message":{"level":"warning","code":{"code":"clippy::needless_return"},"message":"unneeded `return` statement","spans":[{"file_name":"src/lib.rs","line_start":2,"line_end":2,"column_start":5,"column_end":14,"is_primary":true,"suggested_replacement":null}],"children":[{"level":"help","message":"remove `return`","spans":[{"file_name":"src/lib.rs","line_start":2,"line_end":2,"column_start":5,"column_end":14,"is_primary":true,"suggested_replacement":"1","suggestion_applicability":"MachineApplicable"}],"children":[]}],"rendered":"warning: unneeded `return` statement"}}
+{"reason":"compiler-message","message":{"level":"warning","code":null,"message":"1 warning emitted","spans":[],"children":[],"rendered":"warning: 1 warning emitted"}}"#;
+
+        let lints = parse_compiler_messages(stdout).diagnostics;
+        assert_eq!(lints.len(), 1);
+        assert_eq!(lints[0].code.as_deref(), Some("clippy::needless_return"));
+        assert_eq!((lints[0].end_line, lints[0].end_column), (Some(2), Some(14)));
+        assert_eq!(lints[0].suggestions.len(), 1);
+        assert_eq!(lints[0].suggestions[0].replacement, "1");
+        assert_eq!(lints[0].suggestions[0].message, "remove `return`");
+        assert!(lints[0].is_fixable());
+    }
+
+    fn fmt_output(exit_code: i32, stdout: &str, stderr: &str) -> CommandOutput {
+        CommandOutput {
+            command: "cargo".to_string(),
+            args: vec!["fmt".to_string(), "--check".to_string()],
+            working_dir: "/work".to_string(),
+            exit_code,
+            stdout: stdout.to_string(),
+            stderr: stderr.to_string(),
+            success: exit_code == 0,
+            path_enhanced: false,
+        }
+    }
+
+    #[test]
+    fn test_fmt_exit_code_classification() {
+        let clean = CargoFmtReport::from_output(fmt_output(0, "", ""), false);
+        assert_eq!((clean.status, clean.formatted, clean.diff), (FmtStatus::Formatted, true, None));
+
+        let diff = "Diff in /work/src/main.rs:1:\n-fn main(){}\n+fn main() {}\nDiff in /work/src/lib.rs at line 3:\n-x\n+y\nDiff in /work/src/main.rs:9:\n";
+        let changed = CargoFmtReport::from_output(fmt_output(1, diff, ""), false);
+        assert_eq!((changed.status, changed.formatted), (FmtStatus::WouldChange, false));
+        assert_eq!(changed.files, vec!["/work/src/main.rs", "/work/src/lib.rs"]);
+        assert_eq!(changed.diff.as_deref(), Some(diff));
+
+        let parse_error = CargoFmtReport::from_output(fmt_output(1, "", "error: expected expression, found `;`"), false);
+        assert_eq!(parse_error.status, FmtStatus::Error);
+        assert_eq!(CargoFmtReport::from_output(fmt_output(2, "", "error: unexpected argument"), false).status, FmtStatus::Error);
+        assert_eq!(CargoFmtReport::from_output(fmt_output(0, "", ""), true).status, FmtStatus::Applied);
+    }
+}
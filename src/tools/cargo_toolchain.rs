@@ -0,0 +1,251 @@
+//! 🧰 Cargo Toolchain Info - Which Rust the project builds with
+//!
+//! Reports the `rustc`/`cargo` versions cargo will use from the project
+//! directory (so rustup overrides apply), the toolchain rustup resolves, any
+//! pin from `rust-toolchain.toml`/`rust-toolchain`, and the edition and
+//! `rust-version` declared in `Cargo.toml`. Manifests are scanned line by
+//! line for the few keys needed rather than parsed as full TOML.
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::error::EmpathicResult;
+use super::executor_utils::{execute_command_with, resolve_working_dir, CommandOptions};
+
+/// Toolchain files in rustup's lookup order
+pub const TOOLCHAIN_FILES: &[&str] = &["rust-toolchain.toml", "rust-toolchain"];
+
+/// Edition cargo assumes when the manifest declares none
+const DEFAULT_EDITION: &str = "2015";
+
+/// 🧰 Toolchain and manifest facts for one project directory
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct CargoToolchainReport {
+    /// `rustc --version`, e.g. "rustc 1.89.0 (29483883e 2025-08-04)"
+    pub rustc_version: Option<String>,
+    /// `cargo --version`
+    pub cargo_version: Option<String>,
+    /// Toolchain rustup resolves here, e.g. "stable-x86_64-unknown-linux-gnu"
+    pub active_toolchain: Option<String>,
+    /// Why rustup picked it, e.g. "default" or "overridden by '.../rust-toolchain.toml'"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active_toolchain_source: Option<String>,
+    /// Whether a toolchain file pins the toolchain
+    pub pinned: bool,
+    /// The pinning toolchain file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub toolchain_file: Option<String>,
+    /// Channel from the toolchain file, e.g. "1.80.0" or "nightly-2025-01-01"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pinned_channel: Option<String>,
+    /// Nearest `Cargo.toml`
+    pub manifest: Option<String>,
+    /// Edition from the manifest, following `edition.workspace = true`
+    pub edition: Option<String>,
+    /// Minimum supported Rust version (`rust-version`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rust_version: Option<String>,
+}
+
+impl CargoToolchainReport {
+    /// Collect the report for the project (or `cwd` inside it)
+    pub async fn collect(project: Option<&str>, config: &Config, options: &CommandOptions) -> EmpathicResult<Self> {
+        let working_dir = resolve_working_dir(config, project, options.cwd.as_deref())?;
+        let options = CommandOptions { stream: false, ..options.clone() };
+        let version = |command: &'static str| {
+            let options = options.clone();
+            async move {
+                let output = execute_command_with(command, vec!["--version".to_string()], project, config, &options).await.ok()?;
+                output.success.then(|| output.stdout.trim().to_string())
+            }
+        };
+        let rustc_version = version("rustc").await;
+        let cargo_version = version("cargo").await;
+
+        // rustup is optional; a plain rustc install simply has no active toolchain
+        let rustup_args = vec!["show".to_string(), "active-toolchain".to_string()];
+        let (active_toolchain, active_toolchain_source) = match execute_command_with("rustup", rustup_args, project, config, &options).await {
+            Ok(output) if output.success => parse_active_toolchain(&output.stdout),
+            _ => (None, None),
+        };
+
+        // Toolchain files and manifests are only looked up inside the allowed root
+        let working_dir = working_dir.canonicalize().unwrap_or(working_dir);
+        let boundary = config.allowed_roots().iter()
+            .filter_map(|root| root.canonicalize().ok())
+            .find(|root| working_dir.starts_with(root))
+            .unwrap_or_else(|| working_dir.clone());
+        let toolchain_file = find_upwards(&working_dir, &boundary, TOOLCHAIN_FILES);
+        let pinned_channel = match &toolchain_file {
+            Some(path) => tokio::fs::read_to_string(path).await.ok().and_then(|content| parse_toolchain_channel(&content)),
+            None => None,
+        };
+
+        let manifest = find_upwards(&working_dir, &boundary, &["Cargo.toml"]);
+        let (edition, rust_version) = match &manifest {
+            Some(path) => manifest_facts(path, &boundary).await,
+            None => (None, None),
+        };
+
+        Ok(Self {
+            rustc_version,
+            cargo_version,
+            active_toolchain,
+            active_toolchain_source,
+            pinned: toolchain_file.is_some(),
+            toolchain_file: toolchain_file.map(|path| path.to_string_lossy().to_string()),
+            pinned_channel,
+            manifest: manifest.map(|path| path.to_string_lossy().to_string()),
+            edition,
+            rust_version,
+        })
+    }
+}
+
+/// Edition and rust-version for a manifest, resolving workspace inheritance
+async fn manifest_facts(manifest: &Path, boundary: &Path) -> (Option<String>, Option<String>) {
+    let Ok(content) = tokio::fs::read_to_string(manifest).await else {
+        return (None, None);
+    };
+    let mut edition = manifest_value(&content, "package", "edition");
+    let mut rust_version = manifest_value(&content, "package", "rust-version");
+
+    let inherited = |value: &Option<ManifestValue>| matches!(value, Some(ManifestValue::Workspace));
+    if inherited(&edition) || inherited(&rust_version) {
+        let mut dir = manifest.parent().and_then(Path::parent);
+        while let Some(current) = dir.filter(|dir| dir.starts_with(boundary)) {
+            if let Ok(root) = tokio::fs::read_to_string(current.join("Cargo.toml")).await
+                && root.lines().any(|line| line.trim() == "[workspace.package]")
+            {
+                if inherited(&edition) {
+                    edition = manifest_value(&root, "workspace.package", "edition");
+                }
+                if inherited(&rust_version) {
+                    rust_version = manifest_value(&root, "workspace.package", "rust-version");
+                }
+                break;
+            }
+            dir = current.parent();
+        }
+    }
+
+    let literal = |value: Option<ManifestValue>| match value {
+        Some(ManifestValue::Literal(value)) => Some(value),
+        _ => None,
+    };
+    let declares_package = content.lines().any(|line| line.trim() == "[package]");
+    let edition = literal(edition).or_else(|| declares_package.then(|| DEFAULT_EDITION.to_string()));
+    (edition, literal(rust_version))
+}
+
+/// A manifest key's value: a string literal or `workspace = true` inheritance
+#[derive(Debug, Clone, PartialEq)]
+enum ManifestValue {
+    Literal(String),
+    Workspace,
+}
+
+/// Value of `key` in `[table]`, including the `key.workspace = true` and
+/// `key = { workspace = true }` forms
+fn manifest_value(content: &str, table: &str, key: &str) -> Option<ManifestValue> {
+    let header = format!("[{}]", table);
+    let mut in_table = false;
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_table = line == header;
+            continue;
+        }
+        if !in_table {
+            continue;
+        }
+        let Some((name, value)) = line.split_once('=') else {
+            continue;
+        };
+        let (name, value) = (name.trim(), value.trim());
+        if name == format!("{}.workspace", key) || (name == key && value.starts_with('{') && value.contains("workspace")) {
+            return Some(ManifestValue::Workspace);
+        }
+        if name == key {
+            return string_literal(value).map(ManifestValue::Literal);
+        }
+    }
+    None
+}
+
+/// Channel from `rust-toolchain.toml`, or the bare channel in a legacy `rust-toolchain` file
+fn parse_toolchain_channel(content: &str) -> Option<String> {
+    if content.lines().any(|line| line.trim() == "[toolchain]") {
+        return match manifest_value(content, "toolchain", "channel") {
+            Some(ManifestValue::Literal(channel)) => Some(channel),
+            _ => None,
+        };
+    }
+    content
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+}
+
+/// `rustup show active-toolchain` prints "<toolchain> (<reason>)"
+fn parse_active_toolchain(stdout: &str) -> (Option<String>, Option<String>) {
+    let Some(line) = stdout.lines().map(str::trim).find(|line| !line.is_empty()) else {
+        return (None, None);
+    };
+    match line.split_once(" (") {
+        Some((toolchain, reason)) => (Some(toolchain.to_string()), Some(reason.trim_end_matches(')').to_string())),
+        None => (Some(line.to_string()), None),
+    }
+}
+
+/// Contents of a double- or single-quoted TOML string, ignoring a trailing comment
+fn string_literal(value: &str) -> Option<String> {
+    let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let rest = &value[1..];
+    rest.find(quote).map(|end| rest[..end].to_string())
+}
+
+/// First of `names` found in `start` or its ancestors, stopping at `boundary`
+fn find_upwards(start: &Path, boundary: &Path, names: &[&str]) -> Option<PathBuf> {
+    let mut dir = Some(start);
+    while let Some(current) = dir.filter(|dir| dir.starts_with(boundary)) {
+        if let Some(found) = names.iter().map(|name| current.join(name)).find(|path| path.is_file()) {
+            return Some(found);
+        }
+        dir = current.parent();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_values() {
+        let manifest = "[package]\nname = \"demo\"\nedition = \"2021\" # stable\nrust-version.workspace = true\n\n[dependencies]\nedition = \"nope\"\n";
+        assert_eq!(manifest_value(manifest, "package", "edition"), Some(ManifestValue::Literal("2021".to_string())));
+        assert_eq!(manifest_value(manifest, "package", "rust-version"), Some(ManifestValue::Workspace));
+        assert_eq!(manifest_value("[package]\nedition = { workspace = true }\n", "package", "edition"), Some(ManifestValue::Workspace));
+        assert_eq!(manifest_value(manifest, "workspace.package", "edition"), None);
+    }
+
+    #[test]
+    fn test_toolchain_channel() {
+        assert_eq!(parse_toolchain_channel("[toolchain]\nchannel = \"1.80.0\"\ncomponents = [\"clippy\"]\n").as_deref(), Some("1.80.0"));
+        assert_eq!(parse_toolchain_channel("# pinned\nnightly-2025-01-01\n").as_deref(), Some("nightly-2025-01-01"));
+        assert_eq!(parse_toolchain_channel("[toolchain]\npath = \"/opt/rust\"\n"), None);
+    }
+
+    #[test]
+    fn test_active_toolchain() {
+        assert_eq!(
+            parse_active_toolchain("stable-x86_64-unknown-linux-gnu (default)\n"),
+            (Some("stable-x86_64-unknown-linux-gnu".to_string()), Some("default".to_string()))
+        );
+        assert_eq!(parse_active_toolchain("1.80.0-aarch64-apple-darwin\n"), (Some("1.80.0-aarch64-apple-darwin".to_string()), None));
+        assert_eq!(parse_active_toolchain(""), (None, None));
+    }
+}
@@ -0,0 +1,100 @@
+//! 📋 Copy File Tool - Modern ToolBuilder implementation
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::tools::{ToolBuilder, SchemaBuilder};
+use crate::config::Config;
+use crate::fs::FileOps;
+use crate::error::{EmpathicResult, EmpathicError};
+
+/// 📋 Copy File Tool using modern ToolBuilder pattern
+pub struct CopyFileTool;
+
+#[derive(Deserialize)]
+pub struct CopyFileArgs {
+    from: String,
+    to: String,
+    #[serde(default)]
+    recursive: bool,
+    #[serde(default)]
+    overwrite: bool,
+    project: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct CopyFileOutput {
+    success: bool,
+    from: String,
+    to: String,
+    bytes_copied: u64,
+    was_directory: bool,
+    overwritten: bool,
+}
+
+#[async_trait]
+impl ToolBuilder for CopyFileTool {
+    type Args = CopyFileArgs;
+    type Output = CopyFileOutput;
+
+    fn name() -> &'static str {
+        "copy_file"
+    }
+
+    fn description() -> &'static str {
+        "📋 Copy file or directory (binary-safe, preserves permissions) with optional overwrite"
+    }
+
+    fn schema() -> serde_json::Value {
+        SchemaBuilder::new()
+            .required_string("from", "Source file or directory path")
+            .required_string("to", "Destination path (parent directories are created)")
+            .optional_bool("recursive", "Copy directories recursively", Some(false))
+            .optional_bool("overwrite", "Replace the destination if it already exists", Some(false))
+            .optional_string("project", "Project name for path resolution")
+            .build()
+    }
+
+    async fn run(args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
+        let working_dir = config.safe_project_path(args.project.as_deref())?;
+        let from_path = working_dir.join(&args.from);
+        let to_path = working_dir.join(&args.to);
+        config.validate_path(&from_path)?;
+        config.validate_path(&to_path)?;
+
+        let metadata = tokio::fs::metadata(&from_path).await
+            .map_err(|_| EmpathicError::FileNotFound { path: from_path.clone() })?;
+
+        let overwritten = tokio::fs::symlink_metadata(&to_path).await.is_ok();
+        if overwritten && !args.overwrite {
+            return Err(EmpathicError::FileOperationFailed {
+                operation: "copy".to_string(),
+                path: to_path,
+                reason: "destination already exists (set overwrite: true)".to_string(),
+            });
+        }
+
+        let bytes_copied = FileOps::copy_file(&from_path, &to_path, args.recursive).await?;
+
+        // 🗑️ Overwritten files must not serve stale LSP responses
+        if overwritten && let Some(lsp_manager) = config.lsp_manager() {
+            if metadata.is_dir() {
+                lsp_manager.invalidate_project_cache(&to_path).await;
+            } else {
+                lsp_manager.invalidate_file_cache(&to_path).await;
+            }
+        }
+
+        Ok(CopyFileOutput {
+            success: true,
+            from: from_path.to_string_lossy().to_string(),
+            to: to_path.to_string_lossy().to_string(),
+            bytes_copied,
+            was_directory: metadata.is_dir(),
+            overwritten,
+        })
+    }
+}
+
+// 🔧 Implement Tool trait using the builder pattern
+crate::impl_tool_for_builder!(CopyFileTool);
@@ -1,4 +1,7 @@
 //! 🗑️ Delete File Tool - Modern ToolBuilder implementation
+//!
+//! `soft: true` (or SOFT_DELETE) moves the target into the trash instead of
+//! unlinking it; `restore_file` brings it back.
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -7,6 +10,7 @@ use crate::tools::{ToolBuilder, SchemaBuilder};
 use crate::config::Config;
 use crate::fs::FileOps;
 use crate::error::{EmpathicResult, EmpathicError};
+use crate::trash::Trash;
 
 /// 🗑️ Delete File Tool using modern ToolBuilder pattern
 pub struct DeleteFileTool;
@@ -16,6 +20,8 @@ pub struct DeleteFileArgs {
     path: Option<String>,
     #[serde(default)]
     recursive: bool,
+    /// Move to the trash instead of unlinking (default: SOFT_DELETE)
+    soft: Option<bool>,
     project: Option<String>,
 }
 
@@ -26,6 +32,10 @@ pub struct DeleteFileOutput {
     was_directory: bool,
     recursive: bool,
     lsp_closed: bool,
+    soft: bool,
+    /// Trash entry name to pass to restore_file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    trash_name: Option<String>,
 }
 
 #[async_trait]
@@ -38,13 +48,14 @@ impl ToolBuilder for DeleteFileTool {
     }
     
     fn description() -> &'static str {
-        "🗑️ Delete file or directory with optional recursive deletion"
+        "🗑️ Delete file or directory with optional recursive deletion; soft mode moves it to a recoverable trash"
     }
     
     fn schema() -> serde_json::Value {
         SchemaBuilder::new()
             .required_string("path", "Path to the file or directory to delete")
             .optional_bool("recursive", "Delete directories recursively", Some(false))
+            .optional_bool("soft", "Move to .empathic/trash instead of deleting, restorable with restore_file (default: server's SOFT_DELETE setting)", None)
             .optional_string("project", "Project name for path resolution")
             .build()
     }
@@ -57,6 +68,14 @@ impl ToolBuilder for DeleteFileTool {
                 .ok_or_else(|| EmpathicError::MissingRequiredParameter { parameter: "path".to_string() })?
         );
         
+        // 🔗 Removing a link never touches its target, so only the link's location must be allowed
+        let is_symlink = tokio::fs::symlink_metadata(&file_path).await
+            .is_ok_and(|meta| meta.file_type().is_symlink());
+        match file_path.parent() {
+            Some(parent) if is_symlink => config.validate_path(parent)?,
+            _ => config.validate_path(&file_path)?,
+        };
+        
         // Check if path exists and get its type
         let metadata = tokio::fs::metadata(&file_path).await
             .map_err(|_| EmpathicError::FileNotFound { path: file_path.clone() })?;
@@ -65,12 +84,39 @@ impl ToolBuilder for DeleteFileTool {
         // 🚀 No LSP sync needed - rust-analyzer detects file deletions automatically
         let lsp_closed = false;
         
-        FileOps::delete_file(&file_path, args.recursive).await
-            .map_err(|e| EmpathicError::FileOperationFailed {
-                operation: "delete".to_string(),
-                path: file_path.clone(),
-                reason: e.to_string(),
-            })?;
+        // 🗑️ Anything already in the trash is deleted for real
+        let trash = Trash::new(&config.root_dir);
+        let soft = args.soft.unwrap_or(config.soft_delete) && !trash.contains(&file_path);
+        let trash_name = if soft {
+            // Same rule as a hard delete: non-empty directories need recursive
+            let non_empty = is_dir && !is_symlink && std::fs::read_dir(&file_path)
+                .is_ok_and(|mut entries| entries.next().is_some());
+            if non_empty && !args.recursive {
+                return Err(EmpathicError::FileOperationFailed {
+                    operation: "delete".to_string(),
+                    path: file_path,
+                    reason: "directory not empty (set recursive: true)".to_string(),
+                });
+            }
+            Some(trash.put(&file_path).await?.name)
+        } else {
+            FileOps::delete_file(&file_path, args.recursive).await
+                .map_err(|e| EmpathicError::FileOperationFailed {
+                    operation: "delete".to_string(),
+                    path: file_path.clone(),
+                    reason: e.to_string(),
+                })?;
+            None
+        };
+        
+        // 🗑️ A deleted file must not keep serving cached LSP responses
+        if let Some(lsp_manager) = config.lsp_manager() {
+            if is_dir {
+                lsp_manager.invalidate_project_cache(&file_path).await;
+            } else {
+                lsp_manager.invalidate_file_cache(&file_path).await;
+            }
+        }
         
         Ok(DeleteFileOutput {
             success: true,
@@ -78,6 +124,8 @@ impl ToolBuilder for DeleteFileTool {
             was_directory: is_dir,
             recursive: args.recursive,
             lsp_closed,
+            soft,
+            trash_name,
         })
     }
 }
@@ -0,0 +1,103 @@
+//! 🔀 Diff Utilities - Unified diffs for dry-run previews
+//!
+//! Line diff from `similar` rendered as `@@`-style hunks, used by the editing
+//! tools to show a proposed change without writing it. Lines are compared with
+//! their terminators, so line-ending and final-newline changes show up too.
+
+use similar::{ChangeTag, TextDiff};
+use std::time::Duration;
+
+/// Unchanged lines shown around each change
+const CONTEXT_LINES: usize = 2;
+
+/// Past this the diff settles for a coarser result (keeps huge rewrites cheap)
+const DIFF_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// 🔀 Render a unified diff between two texts (empty string when identical)
+pub fn unified_diff(old: &str, new: &str, path: &str) -> String {
+    let diff = TextDiff::configure().timeout(DIFF_TIMEOUT).diff_lines(old, new);
+    let hunks = diff.grouped_ops(CONTEXT_LINES);
+    if hunks.is_empty() {
+        return String::new();
+    }
+
+    let mut output = format!("--- a/{}\n+++ b/{}\n", path, path);
+    for hunk in &hunks {
+        let (Some(first), Some(last)) = (hunk.first(), hunk.last()) else { continue };
+        let old_range = first.old_range().start..last.old_range().end;
+        let new_range = first.new_range().start..last.new_range().end;
+
+        // Empty sides point at the line before, per unified diff convention
+        let old_header = if old_range.is_empty() { old_range.start } else { old_range.start + 1 };
+        let new_header = if new_range.is_empty() { new_range.start } else { new_range.start + 1 };
+        output.push_str(&format!("@@ -{},{} +{},{} @@\n", old_header, old_range.len(), new_header, new_range.len()));
+
+        for change in hunk.iter().flat_map(|op| diff.iter_changes(op)) {
+            output.push(match change.tag() {
+                ChangeTag::Equal => ' ',
+                ChangeTag::Delete => '-',
+                ChangeTag::Insert => '+',
+            });
+            let line = change.value();
+            output.push_str(line);
+            if !line.ends_with('\n') {
+                output.push_str("\n\\ No newline at end of file\n");
+            }
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identical_texts_have_empty_diff() {
+        assert_eq!(unified_diff("a\nb\n", "a\nb\n", "f.txt"), "");
+    }
+
+    #[test]
+    fn test_single_change_with_context() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n";
+        let new = "1\n2\n3\nfour\n5\n6\n7\n";
+
+        assert_eq!(unified_diff(old, new, "f.txt"), "\
+--- a/f.txt
++++ b/f.txt
+@@ -2,5 +2,5 @@
+ 2
+ 3
+-4
++four
+ 5
+ 6
+");
+    }
+
+    #[test]
+    fn test_distant_changes_form_separate_hunks() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\ni\n";
+        let new = "A\nb\nc\nd\ne\nf\ng\nh\nI\n";
+        let diff = unified_diff(old, new, "f.txt");
+
+        assert_eq!(diff.matches("@@ -").count(), 2);
+        assert!(diff.contains("@@ -1,3 +1,3 @@"));
+        assert!(diff.contains("@@ -7,3 +7,3 @@"));
+    }
+
+    #[test]
+    fn test_pure_insertion_into_empty_file() {
+        assert_eq!(unified_diff("", "new\n", "f.txt"), "--- a/f.txt\n+++ b/f.txt\n@@ -0,0 +1,1 @@\n+new\n");
+    }
+
+    #[test]
+    fn test_line_ending_and_final_newline_changes() {
+        assert_eq!(unified_diff("a\nb\n", "a\r\nb\n", "f.txt"), "--- a/f.txt\n+++ b/f.txt\n@@ -1,2 +1,2 @@\n-a\n+a\r\n b\n");
+        assert_eq!(
+            unified_diff("a\nb\n", "a\nb", "f.txt"),
+            "--- a/f.txt\n+++ b/f.txt\n@@ -1,2 +1,2 @@\n a\n-b\n+b\n\\ No newline at end of file\n",
+        );
+    }
+}
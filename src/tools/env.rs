@@ -6,15 +6,20 @@ use std::collections::HashMap;
 use std::env;
 
 use crate::tools::{ToolBuilder, SchemaBuilder};
-use crate::config::Config;
-use crate::error::EmpathicResult;
+use crate::config::{Config, EnvPolicy};
+use crate::error::{EmpathicError, EmpathicResult};
+
+/// Stands in for the value of a secret-looking variable
+const MASK: &str = "********";
 
 /// 🌍 Environment Variables Tool using modern ToolBuilder pattern
 pub struct EnvTool;
 
 #[derive(Deserialize)]
 pub struct EnvArgs {
-    // No arguments needed for env tool
+    /// Reveal secret-looking values; only honoured when the server enables it
+    #[serde(default)]
+    show_secrets: bool,
 }
 
 #[derive(Serialize)]
@@ -27,6 +32,10 @@ pub struct EnvOutput {
     path_enhanced: bool,
     /// Whether ROOT_DIR was injected
     root_dir_injected: bool,
+    /// Variables withheld by the server's env allowlist/denylist
+    hidden: usize,
+    /// Secret-looking variables whose values were masked
+    masked: Vec<String>,
 }
 
 #[async_trait]
@@ -39,21 +48,31 @@ impl ToolBuilder for EnvTool {
     }
     
     fn description() -> &'static str {
-        "🌍 Get full environment variables"
+        "🌍 Get environment variables permitted by the server's env policy, with secret-looking values masked"
     }
     
     fn schema() -> serde_json::Value {
         SchemaBuilder::new()
+            .optional_bool("show_secrets", "Reveal values of *_KEY, *_TOKEN, *_SECRET and *_PASSWORD variables (requires ENV_SHOW_SECRETS on the server)", Some(false))
             .build()
     }
     
-    async fn run(_args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
-        // Get all environment variables
-        let mut env_vars: HashMap<String, String> = env::vars().collect();
-        let _original_count = env_vars.len();
-        
+    async fn run(args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
+        let policy = &config.env_policy;
+        if args.show_secrets && !policy.show_secrets {
+            return Err(EmpathicError::InvalidArgument {
+                arg: "show_secrets".to_string(),
+                reason: "revealing secrets is disabled on this server (set ENV_SHOW_SECRETS or [env] show_secrets)".to_string(),
+            });
+        }
+
+        // Get the environment variables the policy permits
+        let (mut env_vars, withheld): (HashMap<String, String>, HashMap<String, String>) =
+            env::vars().partition(|(name, _)| policy.allows(name));
+        let hidden = withheld.len();
+
         // Add configured paths to PATH
-        let path_enhanced = if !config.add_path.is_empty() {
+        let path_enhanced = if !config.add_path.is_empty() && env_vars.contains_key("PATH") {
             let current_path = env::var("PATH").unwrap_or_default();
             let additional_paths: Vec<String> = config.add_path
                 .iter()
@@ -70,11 +89,25 @@ impl ToolBuilder for EnvTool {
         // Add ROOT_DIR to the environment variables for clarity
         let root_dir_injected = !env_vars.contains_key("ROOT_DIR");
         env_vars.insert("ROOT_DIR".to_string(), config.root_dir.to_string_lossy().to_string());
-        
+
+        // Mask credential-looking values unless explicitly revealed
+        let mut masked = Vec::new();
+        if !args.show_secrets {
+            for (name, value) in env_vars.iter_mut() {
+                if EnvPolicy::is_secret(name) {
+                    *value = MASK.to_string();
+                    masked.push(name.clone());
+                }
+            }
+            masked.sort();
+        }
+
         Ok(EnvOutput {
             count: env_vars.len(),
             path_enhanced,
             root_dir_injected,
+            hidden,
+            masked,
             env_vars,
         })
     }
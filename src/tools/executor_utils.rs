@@ -1,12 +1,18 @@
 //! 🔧 Executor Utilities - Shared command execution logic
 
 use serde::Serialize;
+use std::collections::HashMap;
 use std::env;
-use std::process::Stdio;
+use std::path::PathBuf;
+use std::process::{ExitStatus, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt};
 use tokio::process::Command;
 
 use crate::config::Config;
 use crate::error::{EmpathicResult, EmpathicError};
+use crate::mcp::progress::ProgressReporter;
 
 #[derive(Serialize)]
 pub struct CommandOutput {
@@ -30,10 +36,49 @@ pub async fn execute_command(
     project: Option<&str>, 
     config: &Config
 ) -> EmpathicResult<CommandOutput> {
-    let working_dir = config.project_path(project);
+    execute_command_with(command, args, project, config, &CommandOptions::default()).await
+}
+
+/// ⚙️ Per-invocation overrides for command tools
+#[derive(Debug, Default, Clone)]
+pub struct CommandOptions {
+    /// Subdirectory (relative to the project) to run in
+    pub cwd: Option<String>,
+    /// Extra environment variables; these win over the enhanced PATH
+    pub env: HashMap<String, String>,
+    /// Send output lines as progress notifications while the command runs
+    pub stream: bool,
+}
+
+/// 📂 Resolve the working directory for a command, keeping `cwd` inside the allowed roots
+pub fn resolve_working_dir(config: &Config, project: Option<&str>, cwd: Option<&str>) -> EmpathicResult<PathBuf> {
+    let base = config.project_path(project);
+    let Some(cwd) = cwd else {
+        return Ok(base);
+    };
     
-    // Prepare environment with additional paths
-    let mut env_vars = std::collections::HashMap::new();
+    let path = base.join(cwd);
+    let canonical = path.canonicalize()
+        .map_err(|_| EmpathicError::FileNotFound { path: path.clone() })?;
+    // Canonical paths resolve both `..` and symlinks before the containment check
+    if !config.is_path_allowed(&canonical) {
+        return Err(EmpathicError::InvalidPath { path });
+    }
+    if !canonical.is_dir() {
+        return Err(EmpathicError::InvalidArgument {
+            arg: "cwd".to_string(),
+            reason: format!("{} is not a directory", path.display()),
+        });
+    }
+    
+    Ok(canonical)
+}
+
+/// 🌱 Build the child environment: ADD_PATH entries first, then user overrides
+///
+/// Returns the variables to set and whether PATH was enhanced.
+pub fn command_env(config: &Config, overrides: &HashMap<String, String>) -> EmpathicResult<(HashMap<String, String>, bool)> {
+    let mut env_vars = HashMap::new();
     let path_enhanced = if !config.add_path.is_empty() {
         let current_path = env::var("PATH").unwrap_or_default();
         let additional_paths: Vec<String> = config.add_path
@@ -47,24 +92,61 @@ pub async fn execute_command(
         false
     };
     
+    for (key, value) in overrides {
+        if key.is_empty() || key.contains('=') || key.contains('\0') || value.contains('\0') {
+            return Err(EmpathicError::InvalidArgument {
+                arg: "env".to_string(),
+                reason: format!("invalid environment variable '{}'", key),
+            });
+        }
+        env_vars.insert(key.clone(), value.clone());
+    }
+    
+    Ok((env_vars, path_enhanced))
+}
+
+/// 📡 `execute_command` with `cwd`/`env` overrides and optional streaming
+///
+/// With `stream` set, each stdout/stderr line is reported to the current
+/// tool call's `ProgressReporter` as it arrives; the returned output is
+/// still the complete buffered result.
+pub async fn execute_command_with(
+    command: &str, 
+    args: Vec<String>, 
+    project: Option<&str>, 
+    config: &Config,
+    options: &CommandOptions,
+) -> EmpathicResult<CommandOutput> {
+    let working_dir = resolve_working_dir(config, project, options.cwd.as_deref())?;
+    let (env_vars, path_enhanced) = command_env(config, &options.env)?;
+    
     let mut cmd = Command::new(command);
     cmd.args(&args)
        .current_dir(&working_dir)
+       .stdin(Stdio::null())
        .stdout(Stdio::piped())
-       .stderr(Stdio::piped());
+       .stderr(Stdio::piped())
+       .kill_on_drop(true);
     
     for (key, value) in env_vars {
         cmd.env(key, value);
     }
     
-    let output = cmd.output().await
+    let mut child = cmd.spawn()
         .map_err(|_e| EmpathicError::CommandNotFound { command: command.to_string() })?;
     
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let stderr = String::from_utf8_lossy(&output.stderr);
+    let reporter = if options.stream { ProgressReporter::current() } else { None };
+    let stdout_buffer = Arc::new(Mutex::new(Vec::new()));
+    let stderr_buffer = Arc::new(Mutex::new(Vec::new()));
+    let stdout_reader = tokio::spawn(drain(child.stdout.take(), Arc::clone(&stdout_buffer), reporter.clone()));
+    let stderr_reader = tokio::spawn(drain(child.stderr.take(), Arc::clone(&stderr_buffer), reporter));
     
-    let exit_code = output.status.code().unwrap_or(-1);
-    let success = output.status.success();
+    let status = child.wait().await?;
+    let _ = stdout_reader.await;
+    let _ = stderr_reader.await;
+    
+    let exit_code = status.code().unwrap_or(-1);
+    let success = status.success();
     
     // ✅ ALWAYS return the output - don't error on non-zero exit codes!
     // Commands like `git status`, `cargo test`, `make` often return non-zero legitimately
@@ -73,9 +155,143 @@ pub async fn execute_command(
         args,
         working_dir: working_dir.to_string_lossy().to_string(),
         exit_code,
-        stdout: stdout.trim_end().to_string(),
-        stderr: stderr.trim_end().to_string(),
+        stdout: take_lossy(&stdout_buffer),
+        stderr: take_lossy(&stderr_buffer),
         success,
         path_enhanced,
     })
 }
+
+/// Timeout for shell commands when none is given
+pub const DEFAULT_TIMEOUT_SECS: u64 = 120;
+
+/// Upper bound accepted for `timeout_secs`
+pub const MAX_TIMEOUT_SECS: u64 = 1800;
+
+/// How long to keep draining pipes after killing a timed-out process group
+const KILL_GRACE: Duration = Duration::from_millis(500);
+
+/// Validate a requested `timeout_secs` (None → default)
+pub fn resolve_timeout(requested: Option<u64>) -> EmpathicResult<u64> {
+    match requested {
+        None => Ok(DEFAULT_TIMEOUT_SECS),
+        Some(0) => Err(EmpathicError::mcp_parameter("timeout_secs", "must be at least 1")),
+        Some(secs) if secs > MAX_TIMEOUT_SECS => Err(EmpathicError::mcp_parameter(
+            "timeout_secs",
+            format!("{} exceeds the maximum of {}s", secs, MAX_TIMEOUT_SECS),
+        )),
+        Some(secs) => Ok(secs),
+    }
+}
+
+/// Captured output of a command that finished in time
+pub struct ProcessOutput {
+    pub status: ExitStatus,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// ⏱️ Run a command, killing its whole process group if it outlives `timeout_secs`
+///
+/// The child gets its own process group and a closed stdin, so neither
+/// background jobs nor prompts can outlive or block the server. On timeout
+/// the error carries whatever output was produced so far. With `stream` set,
+/// lines are also reported as progress notifications while the command runs.
+pub async fn run_with_timeout(mut cmd: Command, tool_name: &str, command: &str, timeout_secs: u64, stream: bool) -> EmpathicResult<ProcessOutput> {
+    cmd.stdin(Stdio::null())
+       .stdout(Stdio::piped())
+       .stderr(Stdio::piped())
+       .kill_on_drop(true);
+    #[cfg(unix)]
+    cmd.process_group(0);
+
+    let mut child = cmd.spawn()
+        .map_err(|e| EmpathicError::ToolExecutionFailed {
+            tool_name: tool_name.to_string(),
+            message: format!("Failed to execute command '{}': {}", command, e),
+        })?;
+
+    // The reporter is task-local, so grab it before handing pipes to spawned readers
+    let reporter = if stream { ProgressReporter::current() } else { None };
+    let stdout_buffer = Arc::new(Mutex::new(Vec::new()));
+    let stderr_buffer = Arc::new(Mutex::new(Vec::new()));
+    let stdout_reader = tokio::spawn(drain(child.stdout.take(), Arc::clone(&stdout_buffer), reporter.clone()));
+    let stderr_reader = tokio::spawn(drain(child.stderr.take(), Arc::clone(&stderr_buffer), reporter));
+
+    // Readers count too: a backgrounded grandchild holding the pipes open is still "running"
+    let finished = tokio::time::timeout(Duration::from_secs(timeout_secs), async {
+        let status = child.wait().await;
+        let _ = stdout_reader.await;
+        let _ = stderr_reader.await;
+        status
+    }).await;
+
+    match finished {
+        Ok(status) => Ok(ProcessOutput {
+            status: status?,
+            stdout: take_lossy(&stdout_buffer),
+            stderr: take_lossy(&stderr_buffer),
+        }),
+        Err(_) => {
+            kill_process_group(&mut child).await;
+            tokio::time::sleep(KILL_GRACE).await;
+            Err(EmpathicError::CommandTimeout {
+                command: command.to_string(),
+                timeout_secs,
+                stdout: take_lossy(&stdout_buffer),
+                stderr: take_lossy(&stderr_buffer),
+            })
+        }
+    }
+}
+
+/// Copy a pipe into a shared buffer until EOF, reporting complete lines if asked
+async fn drain<R: AsyncRead + Unpin>(pipe: Option<R>, buffer: Arc<Mutex<Vec<u8>>>, reporter: Option<ProgressReporter>) {
+    let Some(mut pipe) = pipe else {
+        return;
+    };
+    let mut chunk = [0u8; 8192];
+    let mut pending = Vec::new();
+    while let Ok(read) = pipe.read(&mut chunk).await {
+        if read == 0 {
+            break;
+        }
+        buffer.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).extend_from_slice(&chunk[..read]);
+        if let Some(reporter) = &reporter {
+            pending.extend_from_slice(&chunk[..read]);
+            while let Some(newline) = pending.iter().position(|&b| b == b'\n') {
+                let line: Vec<u8> = pending.drain(..=newline).collect();
+                report_line(reporter, &line);
+            }
+        }
+    }
+    if let Some(reporter) = &reporter && !pending.is_empty() {
+        report_line(reporter, &pending);
+    }
+}
+
+fn report_line(reporter: &ProgressReporter, line: &[u8]) {
+    let line = String::from_utf8_lossy(line);
+    reporter.report(line.trim_end_matches(['\r', '\n']));
+}
+
+fn take_lossy(buffer: &Mutex<Vec<u8>>) -> String {
+    let bytes = std::mem::take(&mut *buffer.lock().unwrap_or_else(|poisoned| poisoned.into_inner()));
+    String::from_utf8_lossy(&bytes).trim_end().to_string()
+}
+
+/// SIGKILL the child's process group (the child itself elsewhere)
+async fn kill_process_group(child: &mut tokio::process::Child) {
+    #[cfg(unix)]
+    if let Some(pid) = child.id() {
+        // The group id equals the child's pid thanks to process_group(0)
+        let _ = Command::new("kill")
+            .args(["-KILL", "--", &format!("-{}", pid)])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .await;
+    }
+    let _ = child.kill().await;
+}
@@ -0,0 +1,129 @@
+//! ℹ️ File Info Tool - Modern ToolBuilder implementation
+//!
+//! Stat a single path without listing its parent: size, timestamps, type,
+//! permission bits and symlink target.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::tools::{ToolBuilder, SchemaBuilder, default_fs_path};
+use crate::config::Config;
+use crate::error::{EmpathicResult, EmpathicError};
+
+/// ℹ️ File Info Tool using modern ToolBuilder pattern
+pub struct FileInfoTool;
+
+#[derive(Deserialize)]
+pub struct FileInfoArgs {
+    path: Option<String>,
+    project: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct FileInfoOutput {
+    path: String,
+    exists: bool,
+    is_file: bool,
+    is_dir: bool,
+    is_symlink: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    symlink_target: Option<String>,
+    /// Symlink whose target is missing
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    broken_symlink: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size: Option<u64>,
+    /// Unix timestamps (seconds)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    modified: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    created: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    accessed: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    permissions: Option<String>,
+    readonly: bool,
+}
+
+#[async_trait]
+impl ToolBuilder for FileInfoTool {
+    type Args = FileInfoArgs;
+    type Output = FileInfoOutput;
+
+    fn name() -> &'static str {
+        "file_info"
+    }
+
+    fn description() -> &'static str {
+        "ℹ️ Get file or directory metadata: size, timestamps, type, permissions, symlink target"
+    }
+
+    fn schema() -> serde_json::Value {
+        SchemaBuilder::new()
+            .optional_string("path", "Path to inspect (default: project root \".\" when project is set)")
+            .optional_string("project", "Project name for path resolution")
+            .build()
+    }
+
+    async fn run(args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
+        let path = default_fs_path(args.path, args.project.as_deref());
+        let working_dir = config.safe_project_path(args.project.as_deref())?;
+        let file_path = working_dir.join(&path);
+        config.validate_path(&file_path)?;
+
+        // 🔗 lstat first so symlinks are reported as links, not their targets
+        let link_metadata = tokio::fs::symlink_metadata(&file_path).await
+            .map_err(|_| EmpathicError::FileNotFound { path: file_path.clone() })?;
+        let is_symlink = link_metadata.file_type().is_symlink();
+
+        let symlink_target = if is_symlink {
+            tokio::fs::read_link(&file_path).await.ok()
+                .map(|target| target.to_string_lossy().to_string())
+        } else {
+            None
+        };
+
+        // Follow the link for size/type; a dangling link falls back to the link itself
+        let target_metadata = tokio::fs::metadata(&file_path).await.ok();
+        let broken_symlink = is_symlink && target_metadata.is_none();
+        let metadata = target_metadata.unwrap_or(link_metadata);
+
+        Ok(FileInfoOutput {
+            path: file_path.to_string_lossy().to_string(),
+            exists: !broken_symlink,
+            is_file: !broken_symlink && metadata.is_file(),
+            is_dir: !broken_symlink && metadata.is_dir(),
+            is_symlink,
+            symlink_target,
+            broken_symlink,
+            size: (!broken_symlink).then_some(metadata.len()),
+            modified: metadata.modified().ok().and_then(unix_secs),
+            created: metadata.created().ok().and_then(unix_secs),
+            accessed: metadata.accessed().ok().and_then(unix_secs),
+            permissions: permission_bits(&metadata),
+            readonly: metadata.permissions().readonly(),
+        })
+    }
+}
+
+fn unix_secs(time: std::time::SystemTime) -> Option<u64> {
+    time.duration_since(std::time::UNIX_EPOCH).ok().map(|d| d.as_secs())
+}
+
+/// Octal permission bits (unix only)
+fn permission_bits(metadata: &std::fs::Metadata) -> Option<String> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        Some(format!("{:o}", metadata.permissions().mode() & 0o7777))
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = metadata;
+        None
+    }
+}
+
+// 🔧 Implement Tool trait using the builder pattern
+crate::impl_tool_for_builder!(FileInfoTool);
@@ -1,12 +1,22 @@
 //! 🐙 Git Tool - Clean ToolBuilder implementation
+//!
+//! `structured: true` with `args: ["status", ...]` runs
+//! `git status --porcelain=v2 --branch -z` and parses it: branch, upstream,
+//! ahead/behind and each file's staged/unstaged state, renames included.
+//!
+//! `dry_run: true` previews `clean`, `push`, `rebase` and other destructive
+//! commands instead of running them; `clean` and those without a native dry
+//! run need `confirm: true` (see `git_preview`).
 
 use async_trait::async_trait;
-use crate::error::EmpathicResult;
-use serde::Deserialize;
+use crate::error::{EmpathicError, EmpathicResult};
+use serde::{Deserialize, Serialize};
 
 use crate::tools::{ToolBuilder, SchemaBuilder};
 use crate::config::Config;
 use super::executor_utils::{execute_command, CommandOutput};
+use super::git_history::run_git;
+use super::git_preview::{classify_in_repo, preview, GitPreview};
 
 /// 🐙 Git Tool using modern ToolBuilder pattern
 pub struct GitTool;
@@ -14,10 +24,112 @@ pub struct GitTool;
 #[derive(Deserialize)]
 pub struct GitArgs {
     args: Vec<String>,
+    /// Parse `git status` into JSON
+    #[serde(default)]
+    structured: bool,
+    /// Report what a mutating command would do without running it
+    #[serde(default)]
+    dry_run: bool,
+    /// Run a destructive command that has no native dry run
+    #[serde(default)]
+    confirm: bool,
     project: Option<String>,
 }
 
-pub type GitOutput = CommandOutput;
+/// 🐙 Raw command output, or parsed status in structured mode
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum GitOutput {
+    Raw(CommandOutput),
+    Status(Box<GitStatus>),
+    Preview(Box<GitPreview>),
+}
+
+/// 📋 Parsed `git status --porcelain=v2 --branch`
+#[derive(Serialize, Debug, Default, PartialEq)]
+pub struct GitStatus {
+    /// Current branch; None when HEAD is detached
+    pub branch: Option<String>,
+    pub detached: bool,
+    /// HEAD commit; None before the first commit
+    pub commit: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upstream: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ahead: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub behind: Option<u64>,
+    /// Nothing staged, unstaged, untracked or conflicted
+    pub clean: bool,
+    pub files: Vec<FileStatus>,
+    pub summary: StatusSummary,
+}
+
+/// 📄 One changed, untracked or conflicted path
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct FileStatus {
+    pub path: String,
+    /// Source path of a rename or copy
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub orig_path: Option<String>,
+    pub state: FileState,
+    /// Change in the index (what a commit would include)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub staged: Option<ChangeKind>,
+    /// Change in the working tree not yet staged
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unstaged: Option<ChangeKind>,
+    /// Rename/copy similarity percentage
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub similarity: Option<u8>,
+    /// Porcelain XY code of a merge conflict, e.g. UU (both modified)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conflict: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FileState {
+    Changed,
+    Renamed,
+    Copied,
+    Conflicted,
+    Untracked,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Modified,
+    TypeChanged,
+    Added,
+    Deleted,
+    Renamed,
+    Copied,
+}
+
+impl ChangeKind {
+    /// A porcelain status letter; `.` (unchanged) and unknown letters are None
+    fn from_code(code: char) -> Option<Self> {
+        match code {
+            'M' => Some(Self::Modified),
+            'T' => Some(Self::TypeChanged),
+            'A' => Some(Self::Added),
+            'D' => Some(Self::Deleted),
+            'R' => Some(Self::Renamed),
+            'C' => Some(Self::Copied),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Default, PartialEq)]
+pub struct StatusSummary {
+    pub staged: usize,
+    pub unstaged: usize,
+    pub untracked: usize,
+    pub conflicted: usize,
+}
 
 #[async_trait]
 impl ToolBuilder for GitTool {
@@ -35,14 +147,216 @@ impl ToolBuilder for GitTool {
     fn schema() -> serde_json::Value {
         SchemaBuilder::new()
             .required_array("args", "Git command arguments (e.g., ['status'], ['commit', '-m', 'message'])")
+            .optional_bool("structured", "For 'status': branch, upstream, ahead/behind and per-file staged/unstaged/untracked state with renames, as JSON (extra args such as paths are passed on)", Some(false))
+            .optional_bool("dry_run", "Preview instead of running: clean lists paths it would remove, push the ref updates, rebase the commits to replay, and reset --hard/checkout --/restore/stash drop/branch -D the uncommitted changes at stake", Some(false))
+            .optional_bool("confirm", "Required to run commands that discard work (clean, reset --hard, checkout -f/--/<path>, restore, stash drop/clear, branch -D)", Some(false))
             .optional_string("project", "Project name for execution directory")
             .build()
     }
     
     async fn run(args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
-        execute_command("git", args.args, args.project.as_deref(), config).await
+        let mutation = classify_in_repo(&args.args, args.project.as_deref(), config).await?;
+        if args.dry_run {
+            let Some(mutation) = mutation else {
+                return Err(EmpathicError::InvalidArgument {
+                    arg: "dry_run".to_string(),
+                    reason: "dry_run applies to clean, push, rebase and destructive reset/checkout/restore/stash/branch commands".to_string(),
+                });
+            };
+            let preview = preview(mutation, &args.args, args.project.as_deref(), config).await?;
+            return Ok(GitOutput::Preview(Box::new(preview)));
+        }
+        if let Some(effect) = mutation.and_then(|mutation| mutation.confirm_reason())
+            && !args.confirm
+        {
+            return Err(EmpathicError::InvalidArgument {
+                arg: "confirm".to_string(),
+                reason: format!("'git {}' {}; preview it with dry_run: true, then pass confirm: true to run it", args.args.join(" "), effect),
+            });
+        }
+
+        if !args.structured {
+            let output = execute_command("git", args.args, args.project.as_deref(), config).await?;
+            return Ok(GitOutput::Raw(output));
+        }
+        if args.args.first().map(String::as_str) != Some("status") {
+            return Err(EmpathicError::InvalidArgument {
+                arg: "structured".to_string(),
+                reason: "structured output is supported for 'git status'".to_string(),
+            });
+        }
+
+        let mut git_args: Vec<String> = ["status", "--porcelain=v2", "--branch", "-z"].map(String::from).into();
+        git_args.extend(args.args.into_iter().skip(1));
+        let output = run_git("git", git_args, args.project.as_deref(), config).await?;
+        Ok(GitOutput::Status(Box::new(parse_porcelain_v2(&output.stdout))))
+    }
+}
+
+/// 🔍 Parse NUL-separated `git status --porcelain=v2 --branch -z` output
+///
+/// Records are `# branch.*` headers, `1` (changed), `2` (renamed/copied,
+/// followed by the original path as its own record), `u` (unmerged), `?`
+/// (untracked) and `!` (ignored, skipped).
+pub fn parse_porcelain_v2(stdout: &str) -> GitStatus {
+    let mut status = GitStatus::default();
+    let mut records = stdout.split('\0').filter(|record| !record.is_empty());
+
+    while let Some(record) = records.next() {
+        let (kind, rest) = record.split_once(' ').unwrap_or((record, ""));
+        match kind {
+            "#" => parse_header(rest, &mut status),
+            "1" => {
+                // XY sub mH mI mW hH hI path
+                let fields: Vec<&str> = rest.splitn(8, ' ').collect();
+                if let [xy, .., path] = fields[..] && fields.len() == 8 {
+                    status.files.push(changed_file(xy, path, None, None));
+                }
+            }
+            "2" => {
+                // XY sub mH mI mW hH hI Xscore path, then origPath
+                let fields: Vec<&str> = rest.splitn(9, ' ').collect();
+                if let [xy, .., score, path] = fields[..] && fields.len() == 9 {
+                    let similarity = score.get(1..).and_then(|score| score.parse().ok());
+                    let orig_path = records.next().map(str::to_string);
+                    status.files.push(changed_file(xy, path, orig_path, similarity));
+                }
+            }
+            "u" => {
+                // XY sub m1 m2 m3 mW h1 h2 h3 path
+                let fields: Vec<&str> = rest.splitn(10, ' ').collect();
+                if let [xy, .., path] = fields[..] && fields.len() == 10 {
+                    status.files.push(FileStatus {
+                        path: path.to_string(),
+                        orig_path: None,
+                        state: FileState::Conflicted,
+                        staged: None,
+                        unstaged: None,
+                        similarity: None,
+                        conflict: Some(xy.to_string()),
+                    });
+                }
+            }
+            "?" => status.files.push(FileStatus {
+                path: rest.to_string(),
+                orig_path: None,
+                state: FileState::Untracked,
+                staged: None,
+                unstaged: None,
+                similarity: None,
+                conflict: None,
+            }),
+            _ => {}
+        }
+    }
+
+    status.summary = StatusSummary {
+        staged: status.files.iter().filter(|file| file.staged.is_some()).count(),
+        unstaged: status.files.iter().filter(|file| file.unstaged.is_some()).count(),
+        untracked: status.files.iter().filter(|file| file.state == FileState::Untracked).count(),
+        conflicted: status.files.iter().filter(|file| file.state == FileState::Conflicted).count(),
+    };
+    status.clean = status.files.is_empty();
+    status
+}
+
+fn parse_header(header: &str, status: &mut GitStatus) {
+    let (key, value) = header.split_once(' ').unwrap_or((header, ""));
+    match key {
+        "branch.oid" => status.commit = (value != "(initial)").then(|| value.to_string()),
+        "branch.head" => {
+            status.detached = value == "(detached)";
+            status.branch = (!status.detached).then(|| value.to_string());
+        }
+        "branch.upstream" => status.upstream = Some(value.to_string()),
+        "branch.ab" => {
+            // "+<ahead> -<behind>"
+            for count in value.split_whitespace() {
+                if let Some(ahead) = count.strip_prefix('+') {
+                    status.ahead = ahead.parse().ok();
+                } else if let Some(behind) = count.strip_prefix('-') {
+                    status.behind = behind.parse().ok();
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn changed_file(xy: &str, path: &str, orig_path: Option<String>, similarity: Option<u8>) -> FileStatus {
+    let mut codes = xy.chars();
+    let staged = codes.next().and_then(ChangeKind::from_code);
+    let unstaged = codes.next().and_then(ChangeKind::from_code);
+    let state = match (orig_path.is_some(), staged) {
+        (true, Some(ChangeKind::Copied)) => FileState::Copied,
+        (true, _) => FileState::Renamed,
+        (false, _) => FileState::Changed,
+    };
+    FileStatus {
+        path: path.to_string(),
+        orig_path,
+        state,
+        staged,
+        unstaged,
+        similarity,
+        conflict: None,
     }
 }
 
 // 🔧 Implement Tool trait using the builder pattern
 crate::impl_tool_for_builder!(GitTool);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_porcelain_v2() {
+        let stdout = [
+            "# branch.oid 1f2e3d4c5b6a",
+            "# branch.head main",
+            "# branch.upstream origin/main",
+            "# branch.ab +2 -1",
+            "1 M. N... 100644 100644 100644 aaa bbb src/lib.rs",
+            "1 .D N... 100644 100644 000000 aaa aaa old notes.txt",
+            "2 R. N... 100644 100644 100644 aaa bbb R92 src/new name.rs",
+            "src/old.rs",
+            "u UU N... 100644 100644 100644 100644 aaa bbb ccc Cargo.toml",
+            "? scratch.rs",
+            "! target/",
+            "",
+        ].join("\0");
+
+        let status = parse_porcelain_v2(&stdout);
+        assert_eq!(status.branch.as_deref(), Some("main"));
+        assert_eq!(status.commit.as_deref(), Some("1f2e3d4c5b6a"));
+        assert_eq!(status.upstream.as_deref(), Some("origin/main"));
+        assert_eq!((status.ahead, status.behind), (Some(2), Some(1)));
+        assert!(!status.clean);
+
+        let paths: Vec<&str> = status.files.iter().map(|file| file.path.as_str()).collect();
+        assert_eq!(paths, vec!["src/lib.rs", "old notes.txt", "src/new name.rs", "Cargo.toml", "scratch.rs"]);
+        assert_eq!((status.files[0].staged, status.files[0].unstaged), (Some(ChangeKind::Modified), None));
+        assert_eq!((status.files[1].staged, status.files[1].unstaged), (None, Some(ChangeKind::Deleted)));
+
+        let renamed = &status.files[2];
+        assert_eq!(renamed.state, FileState::Renamed);
+        assert_eq!(renamed.orig_path.as_deref(), Some("src/old.rs"));
+        assert_eq!(renamed.similarity, Some(92));
+        assert_eq!(status.files[3].conflict.as_deref(), Some("UU"));
+
+        assert_eq!(status.summary, StatusSummary { staged: 2, unstaged: 1, untracked: 1, conflicted: 1 });
+    }
+
+    #[test]
+    fn test_parse_fresh_repo_and_detached_head() {
+        let status = parse_porcelain_v2("# branch.oid (initial)\0# branch.head master\0");
+        assert_eq!((status.commit, status.branch.as_deref()), (None, Some("master")));
+        assert!(status.clean);
+
+        let status = parse_porcelain_v2("# branch.oid abc\0# branch.head (detached)\0");
+        assert!(status.detached);
+        assert_eq!(status.branch, None);
+        assert_eq!((status.ahead, status.upstream), (None, None));
+    }
+}
@@ -0,0 +1,541 @@
+//! 📜 Git History Tools - Structured diff, log and blame
+//!
+//! Thin wrappers over `git diff`, `git log` and `git blame` that parse the
+//! output into JSON, so agents can read hunks, commits and line attribution
+//! without scraping text. Anything more exotic stays with the raw `git` tool.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::tools::{ToolBuilder, SchemaBuilder};
+use crate::config::Config;
+use crate::error::{EmpathicResult, EmpathicError};
+use super::executor_utils::{execute_command, CommandOutput};
+
+/// Commits returned by git_log when max_count is not given
+const DEFAULT_LOG_COUNT: usize = 20;
+
+/// Field and record separators for `git log --format` (never appear in messages)
+const FIELD_SEP: char = '\u{1f}';
+const RECORD_SEP: char = '\u{1e}';
+
+/// 🔀 Git Diff Tool using modern ToolBuilder pattern
+pub struct GitDiffTool;
+
+/// 📜 Git Log Tool using modern ToolBuilder pattern
+pub struct GitLogTool;
+
+/// 🔎 Git Blame Tool using modern ToolBuilder pattern
+pub struct GitBlameTool;
+
+#[derive(Deserialize)]
+pub struct GitDiffArgs {
+    #[serde(default)]
+    staged: bool,
+    from: Option<String>,
+    to: Option<String>,
+    paths: Option<Vec<String>>,
+    context: Option<usize>,
+    project: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct GitLogArgs {
+    max_count: Option<usize>,
+    #[serde(rename = "ref")]
+    git_ref: Option<String>,
+    path: Option<String>,
+    project: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct GitBlameArgs {
+    path: String,
+    start_line: Option<usize>,
+    end_line: Option<usize>,
+    #[serde(rename = "ref")]
+    git_ref: Option<String>,
+    project: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FileChangeStatus {
+    Added,
+    Deleted,
+    Modified,
+    Renamed,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct DiffHunk {
+    pub old_start: usize,
+    pub old_lines: usize,
+    pub new_start: usize,
+    pub new_lines: usize,
+    /// Text after the closing `@@` (usually the enclosing function)
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub section: String,
+    /// Hunk body lines, each prefixed with ' ', '+' or '-'
+    pub lines: Vec<String>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct FileDiff {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_path: Option<String>,
+    pub status: FileChangeStatus,
+    pub binary: bool,
+    pub additions: usize,
+    pub deletions: usize,
+    pub hunks: Vec<DiffHunk>,
+}
+
+#[derive(Serialize)]
+pub struct GitDiffOutput {
+    files: Vec<FileDiff>,
+    files_changed: usize,
+    additions: usize,
+    deletions: usize,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct CommitInfo {
+    pub hash: String,
+    pub author: String,
+    pub email: String,
+    /// Author date, ISO 8601
+    pub date: String,
+    pub subject: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub body: String,
+}
+
+#[derive(Serialize)]
+pub struct GitLogOutput {
+    commits: Vec<CommitInfo>,
+    count: usize,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct BlameLine {
+    pub line: usize,
+    pub hash: String,
+    pub author: String,
+    pub email: String,
+    /// Author date, RFC 3339 (UTC)
+    pub date: String,
+    pub summary: String,
+    pub content: String,
+}
+
+#[derive(Serialize)]
+pub struct GitBlameOutput {
+    path: String,
+    lines: Vec<BlameLine>,
+}
+
+/// Run git and turn a non-zero exit into an error carrying stderr
+pub(super) async fn run_git(tool_name: &str, args: Vec<String>, project: Option<&str>, config: &Config) -> EmpathicResult<CommandOutput> {
+    let output = execute_command("git", args, project, config).await?;
+    if !output.success {
+        return Err(EmpathicError::ToolExecutionFailed {
+            tool_name: tool_name.to_string(),
+            message: output.stderr,
+        });
+    }
+    Ok(output)
+}
+
+#[async_trait]
+impl ToolBuilder for GitDiffTool {
+    type Args = GitDiffArgs;
+    type Output = GitDiffOutput;
+
+    fn name() -> &'static str {
+        "git_diff"
+    }
+
+    fn description() -> &'static str {
+        "🔀 Structured git diff (unstaged, staged or between refs) with per-file hunks"
+    }
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "staged": {"type": "boolean", "description": "Diff the index against HEAD (--cached)", "default": false},
+                "from": {"type": "string", "description": "Base ref/commit (default: working tree vs index)"},
+                "to": {"type": "string", "description": "Target ref/commit (requires from; default: working tree)"},
+                "paths": {"type": "array", "items": {"type": "string"}, "description": "Limit the diff to these paths"},
+                "context": {"type": "integer", "minimum": 0, "description": "Context lines around changes (default: git's 3)"},
+                "project": {"type": "string", "description": "Project name for execution directory"}
+            },
+            "required": [],
+            "additionalProperties": false
+        })
+    }
+
+    async fn run(args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
+        if args.to.is_some() && args.from.is_none() {
+            return Err(EmpathicError::mcp_parameter("to", "requires 'from'"));
+        }
+
+        let mut git_args = vec!["diff".to_string(), "--no-color".to_string(), "--no-ext-diff".to_string()];
+        if let Some(context) = args.context {
+            git_args.push(format!("-U{}", context));
+        }
+        if args.staged {
+            git_args.push("--cached".to_string());
+        }
+        git_args.extend(args.from);
+        git_args.extend(args.to);
+        git_args.push("--".to_string());
+        git_args.extend(args.paths.unwrap_or_default());
+
+        let output = run_git(Self::name(), git_args, args.project.as_deref(), config).await?;
+        let files = parse_diff(&output.stdout);
+
+        Ok(GitDiffOutput {
+            files_changed: files.len(),
+            additions: files.iter().map(|f| f.additions).sum(),
+            deletions: files.iter().map(|f| f.deletions).sum(),
+            files,
+        })
+    }
+}
+
+#[async_trait]
+impl ToolBuilder for GitLogTool {
+    type Args = GitLogArgs;
+    type Output = GitLogOutput;
+
+    fn name() -> &'static str {
+        "git_log"
+    }
+
+    fn description() -> &'static str {
+        "📜 Structured git log: commits with hash, author, date and message"
+    }
+
+    fn schema() -> Value {
+        SchemaBuilder::new()
+            .optional_integer("max_count", "Maximum commits to return (default: 20)", Some(1))
+            .optional_string("ref", "Revision or range to list (default: HEAD)")
+            .optional_string("path", "Only commits touching this path")
+            .optional_string("project", "Project name for execution directory")
+            .build()
+    }
+
+    async fn run(args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
+        let max_count = args.max_count.unwrap_or(DEFAULT_LOG_COUNT);
+        let mut git_args = vec![
+            "log".to_string(),
+            format!("--max-count={}", max_count),
+            format!("--format=%H{0}%an{0}%ae{0}%aI{0}%s{0}%b{1}", FIELD_SEP, RECORD_SEP),
+        ];
+        git_args.extend(args.git_ref);
+        git_args.push("--".to_string());
+        git_args.extend(args.path);
+
+        let output = run_git(Self::name(), git_args, args.project.as_deref(), config).await?;
+        let commits = parse_log(&output.stdout);
+
+        Ok(GitLogOutput {
+            count: commits.len(),
+            commits,
+        })
+    }
+}
+
+#[async_trait]
+impl ToolBuilder for GitBlameTool {
+    type Args = GitBlameArgs;
+    type Output = GitBlameOutput;
+
+    fn name() -> &'static str {
+        "git_blame"
+    }
+
+    fn description() -> &'static str {
+        "🔎 Structured git blame: last commit, author and date for each line of a file"
+    }
+
+    fn schema() -> Value {
+        SchemaBuilder::new()
+            .required_string("path", "File to blame")
+            .optional_integer("start_line", "First line (1-based, inclusive)", Some(1))
+            .optional_integer("end_line", "Last line (1-based, inclusive)", Some(1))
+            .optional_string("ref", "Blame the file as of this revision (default: working tree)")
+            .optional_string("project", "Project name for execution directory")
+            .build()
+    }
+
+    async fn run(args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
+        let mut git_args = vec!["blame".to_string(), "--line-porcelain".to_string()];
+        match (args.start_line, args.end_line) {
+            (Some(start), Some(end)) if end < start => {
+                return Err(EmpathicError::mcp_parameter("end_line", format!("{} is before start_line {}", end, start)));
+            }
+            (Some(start), Some(end)) => git_args.push(format!("-L{},{}", start, end)),
+            (Some(start), None) => git_args.push(format!("-L{},", start)),
+            (None, Some(end)) => git_args.push(format!("-L1,{}", end)),
+            (None, None) => {}
+        }
+        git_args.extend(args.git_ref);
+        git_args.push("--".to_string());
+        git_args.push(args.path.clone());
+
+        let output = run_git(Self::name(), git_args, args.project.as_deref(), config).await?;
+
+        Ok(GitBlameOutput {
+            path: args.path,
+            lines: parse_blame(&output.stdout),
+        })
+    }
+}
+
+/// Parse `git diff` output into per-file changes
+pub fn parse_diff(stdout: &str) -> Vec<FileDiff> {
+    let mut files: Vec<FileDiff> = Vec::new();
+
+    for line in stdout.lines() {
+        if let Some(rest) = line.strip_prefix("diff --git ") {
+            // Refined by ---/+++ and rename headers below
+            let path = rest.split_once(" b/").map(|(_, b)| b).unwrap_or(rest).to_string();
+            files.push(FileDiff {
+                path,
+                old_path: None,
+                status: FileChangeStatus::Modified,
+                binary: false,
+                additions: 0,
+                deletions: 0,
+                hunks: Vec::new(),
+            });
+            continue;
+        }
+        let Some(file) = files.last_mut() else {
+            continue;
+        };
+
+        if let Some(hunk) = file.hunks.last_mut()
+            && let Some(kind) = line.chars().next()
+            && matches!(kind, ' ' | '+' | '-' | '\\')
+        {
+            match kind {
+                '+' => file.additions += 1,
+                '-' => file.deletions += 1,
+                _ => {}
+            }
+            hunk.lines.push(line.to_string());
+        } else if let Some(header) = line.strip_prefix("@@ ") {
+            if let Some(hunk) = parse_hunk_header(header) {
+                file.hunks.push(hunk);
+            }
+        } else if line.starts_with("new file mode") {
+            file.status = FileChangeStatus::Added;
+        } else if line.starts_with("deleted file mode") {
+            file.status = FileChangeStatus::Deleted;
+        } else if let Some(from) = line.strip_prefix("rename from ") {
+            file.status = FileChangeStatus::Renamed;
+            file.old_path = Some(from.to_string());
+        } else if let Some(to) = line.strip_prefix("rename to ") {
+            file.path = to.to_string();
+        } else if line.starts_with("Binary files ") {
+            file.binary = true;
+        } else if let Some(old) = line.strip_prefix("--- a/") {
+            if file.status == FileChangeStatus::Deleted {
+                file.path = old.to_string();
+            }
+        } else if let Some(new) = line.strip_prefix("+++ b/") {
+            file.path = new.to_string();
+        }
+    }
+    files
+}
+
+/// `-12,3 +12,4 @@ fn name()` → hunk with empty body
+fn parse_hunk_header(header: &str) -> Option<DiffHunk> {
+    let (ranges, section) = header.split_once(" @@").unwrap_or((header, ""));
+    let (old, new) = ranges.split_once(' ')?;
+
+    let parse_range = |range: &str| -> Option<(usize, usize)> {
+        match range.split_once(',') {
+            Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+            None => Some((range.parse().ok()?, 1)),
+        }
+    };
+    let (old_start, old_lines) = parse_range(old.strip_prefix('-')?)?;
+    let (new_start, new_lines) = parse_range(new.strip_prefix('+')?)?;
+
+    Some(DiffHunk {
+        old_start,
+        old_lines,
+        new_start,
+        new_lines,
+        section: section.trim().to_string(),
+        lines: Vec::new(),
+    })
+}
+
+/// Parse records produced by the git_log `--format` string
+pub fn parse_log(stdout: &str) -> Vec<CommitInfo> {
+    stdout.split(RECORD_SEP)
+        .filter_map(|record| {
+            let mut fields = record.trim_start_matches('\n').splitn(6, FIELD_SEP);
+            Some(CommitInfo {
+                hash: fields.next().filter(|h| !h.is_empty())?.to_string(),
+                author: fields.next()?.to_string(),
+                email: fields.next()?.to_string(),
+                date: fields.next()?.to_string(),
+                subject: fields.next()?.to_string(),
+                body: fields.next().unwrap_or_default().trim_end().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Parse `git blame --line-porcelain` output
+pub fn parse_blame(stdout: &str) -> Vec<BlameLine> {
+    let mut lines = Vec::new();
+    let mut current: Option<BlameLine> = None;
+
+    for line in stdout.lines() {
+        if let Some(content) = line.strip_prefix('\t') {
+            if let Some(mut entry) = current.take() {
+                entry.content = content.to_string();
+                lines.push(entry);
+            }
+            continue;
+        }
+
+        match current.as_mut() {
+            None => {
+                // Header: <hash> <original line> <final line> [<group size>]
+                let mut parts = line.split(' ');
+                let (Some(hash), Some(_), Some(final_line)) = (parts.next(), parts.next(), parts.next()) else {
+                    continue;
+                };
+                current = Some(BlameLine {
+                    line: final_line.parse().unwrap_or(0),
+                    hash: hash.to_string(),
+                    author: String::new(),
+                    email: String::new(),
+                    date: String::new(),
+                    summary: String::new(),
+                    content: String::new(),
+                });
+            }
+            Some(entry) => {
+                let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+                match key {
+                    "author" => entry.author = value.to_string(),
+                    "author-mail" => entry.email = value.trim_matches(|c| c == '<' || c == '>').to_string(),
+                    "author-time" => {
+                        entry.date = value.parse::<i64>().ok()
+                            .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+                            .map(|date| date.to_rfc3339())
+                            .unwrap_or_default();
+                    }
+                    "summary" => entry.summary = value.to_string(),
+                    _ => {}
+                }
+            }
+        }
+    }
+    lines
+}
+
+// 🔧 Implement Tool trait using the builder pattern
+crate::impl_tool_for_builder!(GitDiffTool);
+crate::impl_tool_for_builder!(GitLogTool);
+crate::impl_tool_for_builder!(GitBlameTool);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_diff_with_new_modified_and_renamed_files() {
+        let stdout = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,3 @@ mod parser;
+ fn a() {}
+-fn b() {}
++fn b() { todo!() }
+ fn c() {}
+diff --git a/new.txt b/new.txt
+new file mode 100644
+index 0000000..3333333
+--- /dev/null
++++ b/new.txt
+@@ -0,0 +1 @@
++hello
+diff --git a/old name.rs b/new name.rs
+similarity index 100%
+rename from old name.rs
+rename to new name.rs
+";
+        let files = parse_diff(stdout);
+        assert_eq!(files.len(), 3);
+
+        assert_eq!(files[0].path, "src/lib.rs");
+        assert_eq!((files[0].additions, files[0].deletions), (1, 1));
+        assert_eq!(files[0].hunks[0].section, "mod parser;");
+        assert_eq!(files[0].hunks[0].lines.len(), 4);
+
+        assert_eq!(files[1].status, FileChangeStatus::Added);
+        assert_eq!((files[1].hunks[0].new_start, files[1].hunks[0].new_lines), (1, 1));
+
+        assert_eq!(files[2].status, FileChangeStatus::Renamed);
+        assert_eq!(files[2].path, "new name.rs");
+        assert_eq!(files[2].old_path.as_deref(), Some("old name.rs"));
+    }
+
+    #[test]
+    fn test_parse_log_records() {
+        let stdout = format!(
+            "abc{0}Ann{0}ann@x.io{0}2024-01-02T03:04:05+00:00{0}Fix parser{0}Longer\n\nbody\n{1}\ndef{0}Bob{0}bob@x.io{0}2024-01-01T00:00:00+00:00{0}Init{0}{1}\n",
+            FIELD_SEP, RECORD_SEP
+        );
+        let commits = parse_log(&stdout);
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].subject, "Fix parser");
+        assert_eq!(commits[0].body, "Longer\n\nbody");
+        assert_eq!(commits[1].hash, "def");
+        assert!(commits[1].body.is_empty());
+    }
+
+    #[test]
+    fn test_parse_blame_line_porcelain() {
+        let stdout = "\
+abc123 1 1 2
+author Ann
+author-mail <ann@x.io>
+author-time 1700000000
+author-tz +0000
+summary Add main
+filename src/main.rs
+\tfn main() {
+abc123 2 2
+author Ann
+author-mail <ann@x.io>
+author-time 1700000000
+author-tz +0000
+summary Add main
+filename src/main.rs
+\t}
+";
+        let lines = parse_blame(stdout);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].email, "ann@x.io");
+        assert_eq!(lines[0].content, "fn main() {");
+        assert_eq!(lines[1].line, 2);
+        assert!(lines[0].date.starts_with("2023-11-14T22:13:20"));
+    }
+}
@@ -0,0 +1,296 @@
+//! 🛡️ Git Preview - Dry runs and confirmation for destructive git commands
+//!
+//! `clean`, `push` and `rebase` can be previewed: clean and push through
+//! git's own `--dry-run`, rebase by listing the commits it would replay.
+//! `clean` deletes untracked files for good, so it also needs `confirm: true`.
+//! Commands that throw away work without a native dry run (`reset --hard`,
+//! forced or path `checkout`, worktree `restore`, `stash drop/clear`,
+//! `branch -D`) only run with `confirm: true`; their preview lists the
+//! uncommitted changes at stake.
+
+use serde::Serialize;
+
+use crate::config::Config;
+use crate::error::{EmpathicError, EmpathicResult};
+use super::executor_utils::execute_command;
+
+/// Effect of a `checkout` that restores paths instead of switching branches
+const CHECKOUT_PATHS: &str = "overwrites working tree changes";
+
+/// What a git command can do to the repository
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GitMutation {
+    /// `git clean`: `--dry-run` lists what would be removed; needs `confirm: true`
+    Clean,
+    /// `git push`: `--dry-run --porcelain` reports each ref update
+    Push,
+    /// `git rebase`: previewed as the commits it would replay
+    Rebase,
+    /// Discards work with no native dry run; needs `confirm: true`
+    Destructive(&'static str),
+}
+
+impl GitMutation {
+    /// What running it throws away, when it needs `confirm: true`
+    pub fn confirm_reason(&self) -> Option<&'static str> {
+        match self {
+            GitMutation::Clean => Some("permanently deletes untracked files"),
+            GitMutation::Destructive(effect) => Some(effect),
+            GitMutation::Push | GitMutation::Rebase => None,
+        }
+    }
+}
+
+/// 🔍 One ref update from `git push --porcelain`
+#[derive(Serialize, Debug, PartialEq)]
+pub struct PushRef {
+    /// ' ' fast-forward, '+' forced, '-' deleted, '*' new, '!' rejected, '=' up to date
+    pub flag: String,
+    pub from: String,
+    pub to: String,
+    pub summary: String,
+}
+
+/// 🛡️ What a command would do, without doing it
+#[derive(Serialize, Debug)]
+pub struct GitPreview {
+    pub dry_run: bool,
+    /// The command that was previewed, e.g. "git clean -fd"
+    pub command: String,
+    /// Running it needs `confirm: true`
+    pub requires_confirm: bool,
+    pub summary: String,
+    /// Paths `clean` would delete
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub would_remove: Vec<String>,
+    /// Ref updates `push` would make
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub refs: Vec<PushRef>,
+    /// Commits `rebase` would replay, oldest first
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub commits: Vec<String>,
+    /// Uncommitted changes (`git status --short`) a destructive command may discard
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub uncommitted: Vec<String>,
+    /// Native dry-run output when it couldn't be parsed further
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+}
+
+/// Index of the subcommand, skipping global options (`-C dir`, `-c k=v`)
+fn subcommand_index(args: &[String]) -> Option<usize> {
+    let mut index = 0;
+    while index < args.len() {
+        match args[index].as_str() {
+            "-C" | "-c" | "--git-dir" | "--work-tree" => index += 2,
+            arg if arg.starts_with('-') => index += 1,
+            _ => return Some(index),
+        }
+    }
+    None
+}
+
+/// 🛡️ Classify a git command; `None` for anything that doesn't need guarding
+pub fn classify(args: &[String]) -> Option<GitMutation> {
+    let index = subcommand_index(args)?;
+    let rest = &args[index + 1..];
+    let has = |flags: &[&str]| rest.iter().any(|arg| flags.contains(&arg.as_str()));
+    // Combined short flags such as `-fd` or `-Df`
+    let has_short = |flag: char| rest.iter().any(|arg| arg.starts_with('-') && !arg.starts_with("--") && arg.contains(flag));
+
+    match args[index].as_str() {
+        // `clean -n` is already a dry run
+        "clean" if !has(&["--dry-run"]) && !has_short('n') => Some(GitMutation::Clean),
+        "push" => Some(GitMutation::Push),
+        "rebase" if !has(&["--continue", "--abort", "--skip", "--quit", "--edit-todo", "--show-current-patch"]) => Some(GitMutation::Rebase),
+        "reset" if has(&["--hard", "--merge", "--keep"]) => Some(GitMutation::Destructive("discards uncommitted changes")),
+        "checkout" if has(&["-f", "--force", "--", "."]) => Some(GitMutation::Destructive(CHECKOUT_PATHS)),
+        // `checkout <tree-ish> <path>...`; a single name is resolved by `classify_in_repo`
+        "checkout" if checkout_operands(rest).is_some_and(|operands| operands.len() > 1) => Some(GitMutation::Destructive(CHECKOUT_PATHS)),
+        "restore" if !has(&["-S", "--staged"]) || has(&["-W", "--worktree"]) => Some(GitMutation::Destructive("overwrites working tree changes")),
+        "stash" if rest.first().is_some_and(|action| action == "drop" || action == "clear") => Some(GitMutation::Destructive("deletes stashed changes")),
+        "branch" if has_short('D') || ((has_short('d') || has(&["--delete"])) && (has_short('f') || has(&["--force"]))) => Some(GitMutation::Destructive("deletes unmerged branches")),
+        _ => None,
+    }
+}
+
+/// Positional operands of `checkout`; `None` when it creates a branch (`-b`, `-B`, `--orphan`)
+fn checkout_operands(rest: &[String]) -> Option<Vec<&String>> {
+    if rest.iter().any(|arg| matches!(arg.as_str(), "-b" | "-B" | "--orphan")) {
+        return None;
+    }
+    Some(rest.iter().filter(|arg| !arg.starts_with('-')).collect())
+}
+
+/// 🛡️ `classify`, also catching `checkout <name>` when `name` is a path rather than a commit
+///
+/// Git itself prefers the commit when a name could be both.
+pub async fn classify_in_repo(args: &[String], project: Option<&str>, config: &Config) -> EmpathicResult<Option<GitMutation>> {
+    if let Some(mutation) = classify(args) {
+        return Ok(Some(mutation));
+    }
+    let Some(index) = subcommand_index(args).filter(|&index| args[index] == "checkout") else {
+        return Ok(None);
+    };
+    let operands = checkout_operands(&args[index + 1..]);
+    let Some([name]) = operands.as_deref() else {
+        return Ok(None);
+    };
+    let verify = vec!["rev-parse".to_string(), "--verify".to_string(), "--quiet".to_string(), format!("{}^{{commit}}", name)];
+    let output = execute_command("git", verify, project, config).await?;
+    Ok((!output.success).then_some(GitMutation::Destructive(CHECKOUT_PATHS)))
+}
+
+/// `args` with `flags` inserted right after the subcommand
+fn with_flags(args: &[String], flags: &[&str]) -> Vec<String> {
+    let mut args = args.to_vec();
+    if let Some(index) = subcommand_index(&args) {
+        for (offset, flag) in flags.iter().enumerate() {
+            args.insert(index + 1 + offset, flag.to_string());
+        }
+    }
+    args
+}
+
+/// Paths from `git clean --dry-run` ("Would remove target/")
+pub fn parse_clean_dry_run(stdout: &str) -> Vec<String> {
+    stdout.lines()
+        .filter_map(|line| line.strip_prefix("Would remove "))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Ref lines from `git push --porcelain` ("<flag>\t<from>:<to>\t<summary>")
+pub fn parse_push_porcelain(stdout: &str) -> Vec<PushRef> {
+    stdout.lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            let flag = fields.next()?;
+            let (from, to) = fields.next()?.split_once(':')?;
+            if flag.chars().count() != 1 {
+                return None;
+            }
+            Some(PushRef {
+                flag: flag.to_string(),
+                from: from.to_string(),
+                to: to.to_string(),
+                summary: fields.next().unwrap_or_default().trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// 🔍 Preview `mutation` of `args` without changing the repository
+pub async fn preview(mutation: GitMutation, args: &[String], project: Option<&str>, config: &Config) -> EmpathicResult<GitPreview> {
+    let mut preview = GitPreview {
+        dry_run: true,
+        command: format!("git {}", args.join(" ")),
+        requires_confirm: mutation.confirm_reason().is_some(),
+        summary: String::new(),
+        would_remove: Vec::new(),
+        refs: Vec::new(),
+        commits: Vec::new(),
+        uncommitted: Vec::new(),
+        output: None,
+    };
+
+    match mutation {
+        GitMutation::Clean => {
+            let output = execute_command("git", with_flags(args, &["--dry-run"]), project, config).await?;
+            if !output.success {
+                return Err(EmpathicError::tool_failed("git", output.stderr));
+            }
+            preview.would_remove = parse_clean_dry_run(&output.stdout);
+            preview.summary = format!("would remove {} path(s); pass confirm: true to run it", preview.would_remove.len());
+        }
+        GitMutation::Push => {
+            // Exits non-zero when a ref would be rejected; the porcelain lines still say which
+            let output = execute_command("git", with_flags(args, &["--dry-run", "--porcelain"]), project, config).await?;
+            preview.refs = parse_push_porcelain(&output.stdout);
+            if preview.refs.is_empty() {
+                preview.output = Some(format!("{}{}", output.stdout, output.stderr).trim().to_string());
+            }
+            let rejected = preview.refs.iter().filter(|r| r.flag == "!").count();
+            preview.summary = format!("would update {} ref(s), {} rejected", preview.refs.len() - rejected, rejected);
+        }
+        GitMutation::Rebase => {
+            let index = subcommand_index(args).unwrap_or(0);
+            // Positional arguments, minus the values of `--onto X` / `-s X` / `-X X`
+            let rest = &args[index + 1..];
+            let takes_value = |i: usize| i > 0 && matches!(rest[i - 1].as_str(), "--onto" | "-s" | "--strategy" | "-X" | "--strategy-option");
+            let positional: Vec<&String> = rest.iter().enumerate()
+                .filter(|(i, arg)| !arg.starts_with('-') && !takes_value(*i))
+                .map(|(_, arg)| arg)
+                .collect();
+            let upstream = positional.first().map_or("@{upstream}", |arg| arg.as_str());
+            let branch = positional.get(1).map_or("HEAD", |arg| arg.as_str());
+            let range = format!("{}..{}", upstream, branch);
+            let log_args = ["log", "--reverse", "--oneline", "--no-decorate", range.as_str()].map(String::from).to_vec();
+            let output = execute_command("git", log_args, project, config).await?;
+            if !output.success {
+                return Err(EmpathicError::tool_failed("git", output.stderr));
+            }
+            preview.commits = output.stdout.lines().map(str::to_string).collect();
+            preview.summary = format!("would replay {} commit(s) onto {}", preview.commits.len(), upstream);
+        }
+        GitMutation::Destructive(effect) => {
+            let output = execute_command("git", vec!["status".to_string(), "--short".to_string()], project, config).await?;
+            preview.uncommitted = output.stdout.lines().map(str::to_string).collect();
+            preview.summary = format!("{}; no native dry run, pass confirm: true to run it", effect);
+        }
+    }
+    Ok(preview)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(classify(&args(&["clean", "-fd"])), Some(GitMutation::Clean));
+        assert_eq!(classify(&args(&["clean", "-fdn"])), None);
+        assert!(GitMutation::Clean.confirm_reason().is_some());
+        assert_eq!(GitMutation::Push.confirm_reason(), None);
+        assert_eq!(classify(&args(&["-C", "sub", "push", "origin", "main"])), Some(GitMutation::Push));
+        assert_eq!(classify(&args(&["rebase", "main"])), Some(GitMutation::Rebase));
+        assert_eq!(classify(&args(&["rebase", "--continue"])), None);
+        assert!(matches!(classify(&args(&["reset", "--hard", "HEAD~1"])), Some(GitMutation::Destructive(_))));
+        assert_eq!(classify(&args(&["reset", "HEAD~1"])), None);
+        assert!(matches!(classify(&args(&["checkout", "--", "src/lib.rs"])), Some(GitMutation::Destructive(_))));
+        assert_eq!(classify(&args(&["checkout", "-b", "feature"])), None);
+        assert_eq!(classify(&args(&["checkout", "-b", "feature", "origin/main"])), None);
+        assert!(matches!(classify(&args(&["checkout", "HEAD~1", "src/lib.rs"])), Some(GitMutation::Destructive(_))));
+        // A single name needs the repository to tell a branch from a path
+        assert_eq!(classify(&args(&["checkout", "src/lib.rs"])), None);
+        assert_eq!(classify(&args(&["restore", "--staged", "a.rs"])), None);
+        assert!(matches!(classify(&args(&["stash", "drop"])), Some(GitMutation::Destructive(_))));
+        assert!(matches!(classify(&args(&["branch", "-df", "old"])), Some(GitMutation::Destructive(_))));
+        assert_eq!(classify(&args(&["branch", "-d", "old"])), None);
+        assert_eq!(classify(&args(&["status"])), None);
+    }
+
+    #[test]
+    fn test_parse_dry_runs() {
+        assert_eq!(parse_clean_dry_run("Would remove build/\nWould remove notes.txt\n"), vec!["build/", "notes.txt"]);
+
+        let refs = parse_push_porcelain("To /tmp/remote.git\n*\trefs/heads/feature:refs/heads/feature\t[new branch]\n!\trefs/heads/main:refs/heads/main\t[rejected] (non-fast-forward)\nDone\n");
+        assert_eq!(refs.len(), 2);
+        assert_eq!(refs[0], PushRef {
+            flag: "*".to_string(),
+            from: "refs/heads/feature".to_string(),
+            to: "refs/heads/feature".to_string(),
+            summary: "[new branch]".to_string(),
+        });
+        assert_eq!(refs[1].flag, "!");
+    }
+
+    #[test]
+    fn test_with_flags_after_subcommand() {
+        assert_eq!(with_flags(&args(&["-C", "sub", "clean", "-fd"]), &["--dry-run"]), args(&["-C", "sub", "clean", "--dry-run", "-fd"]));
+    }
+}
@@ -1,13 +1,18 @@
 //! 🐘 Gradle Tool - Clean ToolBuilder implementation
 
 use async_trait::async_trait;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
-use crate::error::EmpathicResult;
+use crate::error::{EmpathicError, EmpathicResult};
 
 use crate::tools::{ToolBuilder, SchemaBuilder};
 use crate::config::Config;
-use super::executor_utils::{execute_command, CommandOutput};
+use super::build_targets::{mtimes, parse_gradle_tasks, GradleTasks, MtimeCache, GRADLE_BUILD_FILES};
+use super::executor_utils::{execute_command_with, resolve_working_dir, CommandOptions, CommandOutput};
+
+/// 🗄️ Parsed `gradle tasks` listings; running gradle to get them takes seconds
+static GRADLE_TASKS: MtimeCache<GradleTasks> = MtimeCache::new();
 
 /// 🐘 Gradle Tool using modern ToolBuilder pattern
 pub struct GradleTool;
@@ -16,10 +21,24 @@ pub struct GradleTool;
 pub struct GradleArgs {
     #[serde(default)]
     args: Vec<String>,
+    /// List available tasks instead of running gradle with `args`
+    #[serde(default)]
+    list_tasks: bool,
+    #[serde(default)]
+    stream: bool,
+    cwd: Option<String>,
+    #[serde(default)]
+    env: HashMap<String, String>,
     project: Option<String>,
 }
 
-pub type GradleOutput = CommandOutput;
+/// 🐘 Command output, or the grouped task list in `list_tasks` mode
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum GradleOutput {
+    Run(CommandOutput),
+    Tasks(GradleTasks),
+}
 
 #[async_trait]
 impl ToolBuilder for GradleTool {
@@ -37,12 +56,44 @@ impl ToolBuilder for GradleTool {
     fn schema() -> serde_json::Value {
         SchemaBuilder::new()
             .optional_array("args", "Gradle task arguments (e.g., ['build'], ['clean', 'test'])")
+            .optional_bool("list_tasks", "List tasks by group (from 'gradle tasks --all', cached until build files change) instead of running gradle", Some(false))
+            .optional_bool("stream", "Send stdout/stderr lines as notifications/progress while the command runs (full output is still returned)", Some(false))
+            .optional_string("cwd", "Subdirectory to run in, relative to the project (must stay inside the root)")
+            .optional_string_map("env", "Extra environment variables, e.g. {\"RUST_LOG\": \"debug\"}")
             .optional_string("project", "Project name for execution directory")
             .build()
     }
     
     async fn run(args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
-        execute_command("gradle", args.args, args.project.as_deref(), config).await
+        let list_tasks = args.list_tasks;
+        let working_dir = resolve_working_dir(config, args.project.as_deref(), args.cwd.as_deref())?;
+        let options = CommandOptions { cwd: args.cwd, env: args.env, stream: args.stream };
+        
+        if !list_tasks {
+            let output = execute_command_with("gradle", args.args, args.project.as_deref(), config, &options).await?;
+            return Ok(GradleOutput::Run(output));
+        }
+        
+        let stamp = mtimes(&working_dir, GRADLE_BUILD_FILES);
+        if stamp.iter().all(Option::is_none) {
+            return Err(EmpathicError::FileNotFound { path: working_dir.join("build.gradle") });
+        }
+        if let Some(tasks) = GRADLE_TASKS.get(&working_dir, &stamp) {
+            return Ok(GradleOutput::Tasks(GradleTasks { cached: true, ..tasks }));
+        }
+        
+        let task_args = vec!["tasks".to_string(), "--all".to_string(), "--console=plain".to_string()];
+        let output = execute_command_with("gradle", task_args, args.project.as_deref(), config, &options).await?;
+        if !output.success {
+            return Err(EmpathicError::ToolExecutionFailed {
+                tool_name: "gradle".to_string(),
+                message: format!("'gradle tasks --all' failed: {}", output.stderr),
+            });
+        }
+        
+        let tasks = GradleTasks { groups: parse_gradle_tasks(&output.stdout), cached: false };
+        GRADLE_TASKS.insert(working_dir, stamp, tasks.clone());
+        Ok(GradleOutput::Tasks(tasks))
     }
 }
 
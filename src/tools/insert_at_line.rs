@@ -0,0 +1,95 @@
+//! ➕ Insert At Line Tool - Add lines without replacing any
+//!
+//! `content` is split into lines and inserted before the 0-indexed `line`:
+//! `line: 0` prepends, and a `line` at or past the end of the file appends.
+//! The file keeps its line endings, trailing newline and encoding.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::tools::{ToolBuilder, SchemaBuilder, sync_lsp_document};
+use crate::tools::diff_utils::unified_diff;
+use crate::config::Config;
+use crate::fs::{FileOps, TextEncoding};
+use crate::error::EmpathicResult;
+
+/// ➕ Insert At Line Tool using modern ToolBuilder pattern
+pub struct InsertAtLineTool;
+
+#[derive(Deserialize)]
+pub struct InsertAtLineArgs {
+    path: String,
+    /// 0-indexed line to insert before
+    line: usize,
+    content: String,
+    #[serde(default)]
+    dry_run: bool,
+    project: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct InsertAtLineOutput {
+    success: bool,
+    path: String,
+    /// Where the first inserted line ended up (clamped to the end of the file)
+    line: usize,
+    lines_inserted: usize,
+    total_lines: usize,
+    dry_run: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    diff: Option<String>,
+    encoding: TextEncoding,
+}
+
+#[async_trait]
+impl ToolBuilder for InsertAtLineTool {
+    type Args = InsertAtLineArgs;
+    type Output = InsertAtLineOutput;
+
+    fn name() -> &'static str {
+        "insert_at_line"
+    }
+
+    fn description() -> &'static str {
+        "➕ Insert lines into a file before a 0-indexed line (0 prepends, past the end appends), keeping its line endings"
+    }
+
+    fn schema() -> serde_json::Value {
+        SchemaBuilder::new()
+            .required_string("path", "Path to the file to edit")
+            .required_integer("line", "0-indexed line to insert before; 0 prepends, the line count or more appends", Some(0))
+            .required_string("content", "Text to insert; each line becomes its own line in the file")
+            .optional_bool("dry_run", "Return a unified diff of the change without writing", Some(false))
+            .optional_string("project", "Project name for path resolution")
+            .build()
+    }
+
+    async fn run(args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
+        let file_path = config.validate_path(&config.project_path(args.project.as_deref()).join(&args.path))?;
+
+        let (original_content, encoding) = FileOps::read_file_with_encoding(&file_path, None).await?;
+        let (new_content, line) = FileOps::insert_lines(&original_content, &args.content, args.line);
+
+        let diff = if args.dry_run {
+            Some(unified_diff(&original_content, &new_content, &args.path))
+        } else {
+            FileOps::write_file_encoded(&file_path, &new_content, encoding).await?;
+            sync_lsp_document(config, &file_path, &new_content).await;
+            None
+        };
+
+        Ok(InsertAtLineOutput {
+            success: true,
+            path: file_path.to_string_lossy().to_string(),
+            line,
+            lines_inserted: args.content.lines().count(),
+            total_lines: new_content.lines().count(),
+            dry_run: args.dry_run,
+            diff,
+            encoding,
+        })
+    }
+}
+
+// 🔧 Implement Tool trait using the builder pattern
+crate::impl_tool_for_builder!(InsertAtLineTool);
@@ -1,4 +1,10 @@
 //! 📁 List Files Tool - Modern ToolBuilder implementation
+//!
+//! Recursive listings can be cut off at `max_depth` and rendered either as
+//! the flat `files` array or, with `format: "tree"`, as an indented tree
+//! that shows a project's layout in one call. With `fuzzy: true` the
+//! pattern is a fuzzy query instead (`usrsvc` finds `user_service.rs`) and
+//! files come back ranked by their match score.
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -7,7 +13,8 @@ use serde_json::Value;
 use crate::tools::{ToolBuilder, SchemaBuilder};
 use crate::config::Config;
 use crate::fs::FileOps;
-use crate::error::EmpathicResult;
+use crate::error::{EmpathicError, EmpathicResult};
+use crate::mcp::protocol::paginate;
 
 /// 📁 List Files Tool using modern ToolBuilder pattern
 pub struct ListFilesTool;
@@ -21,6 +28,19 @@ pub struct ListFilesArgs {
     #[serde(default)]
     show_metadata: bool,
     pattern: Option<String>,
+    /// Treat `pattern` as a fuzzy file name query and rank by score
+    #[serde(default)]
+    fuzzy: bool,
+    #[serde(default)]
+    include_ignored: bool,
+    /// Levels below `path` to descend (implies recursive)
+    max_depth: Option<usize>,
+    /// "flat" (default) or "tree" (implies recursive)
+    format: Option<String>,
+    /// Page size; omit to list everything
+    limit: Option<usize>,
+    /// `next_cursor` from the previous page
+    cursor: Option<String>,
     project: Option<String>,
 }
 
@@ -28,30 +48,117 @@ pub struct ListFilesArgs {
 pub struct FileEntry {
     name: String,
     path: String,
+    /// Path relative to the listed directory
+    relative_path: String,
     is_dir: bool,
+    /// "file", "dir" or "symlink"
+    #[serde(rename = "type")]
+    file_type: &'static str,
     #[serde(skip_serializing_if = "Option::is_none")]
     size: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     modified: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     permissions: Option<String>,
+    /// Fuzzy match score, 1.0 for an exact name (fuzzy mode only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    score: Option<f64>,
 }
 
 #[derive(Serialize)]
 pub struct ListFilesOutput {
-    files: Vec<FileEntry>,
+    /// Omitted in tree format
+    #[serde(skip_serializing_if = "Option::is_none")]
+    files: Option<Vec<FileEntry>>,
+    /// Indented listing, directories suffixed with '/' and files with their size
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tree: Option<String>,
     path: String,
     recursive: bool,
     show_metadata: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_depth: Option<usize>,
     count: usize,
+    /// Entries across all pages
+    total: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_cursor: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pattern: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    fuzzy: bool,
 }
 
 fn default_path() -> String {
     ".".to_string()
 }
 
+/// 🌳 Render entries (sorted by path) as an indented tree
+fn render_tree(entries: &[FileEntry]) -> String {
+    let mut tree = String::new();
+    for entry in entries {
+        let depth = std::path::Path::new(&entry.relative_path).components().count().saturating_sub(1);
+        tree.push_str(&"  ".repeat(depth));
+        tree.push_str(&entry.name);
+        match (entry.file_type, entry.size) {
+            ("dir", _) => tree.push('/'),
+            ("symlink", _) => tree.push_str(" @"),
+            (_, Some(size)) => tree.push_str(&format!(" ({} bytes)", size)),
+            _ => {}
+        }
+        tree.push('\n');
+    }
+    tree
+}
+
+/// 🔎 Subsequence score of `query` in `name`, case-insensitive: 0.0 when a
+/// query character is missing, 1.0 for an exact match. Consecutive characters,
+/// word starts (after `_`, `-`, `.`, `/` or a camelCase hump) and short names
+/// score higher.
+pub fn fuzzy_score(query: &str, name: &str) -> f64 {
+    let query: Vec<char> = query.chars().filter(|c| !c.is_whitespace()).flat_map(char::to_lowercase).collect();
+    let chars: Vec<char> = name.chars().collect();
+    let lower: Vec<char> = chars.iter().map(|c| c.to_lowercase().next().unwrap_or(*c)).collect();
+    if query.is_empty() {
+        return 0.0;
+    }
+    if lower == query {
+        return 1.0;
+    }
+
+    let mut points = 0.0;
+    let mut matched = 0;
+    let mut previous: Option<usize> = None;
+    for (index, c) in lower.iter().enumerate() {
+        if matched == query.len() {
+            break;
+        }
+        if *c != query[matched] {
+            continue;
+        }
+        points += 1.0;
+        let word_start = index == 0
+            || matches!(chars[index - 1], '_' | '-' | '.' | '/' | ' ')
+            || (chars[index].is_uppercase() && chars[index - 1].is_lowercase());
+        if word_start {
+            points += 1.0;
+        }
+        if previous.is_some_and(|p| p + 1 == index) {
+            points += 1.0;
+        }
+        previous = Some(index);
+        matched += 1;
+    }
+    if matched < query.len() {
+        return 0.0;
+    }
+
+    let quality = points / (3.0 * query.len() as f64);
+    let coverage = query.len() as f64 / chars.len() as f64;
+    let score = (0.7 * quality + 0.3 * coverage).min(0.99);
+    (score * 1000.0).round() / 1000.0
+}
+
 #[async_trait]
 impl ToolBuilder for ListFilesTool {
     type Args = ListFilesArgs;
@@ -62,7 +169,7 @@ impl ToolBuilder for ListFilesTool {
     }
     
     fn description() -> &'static str {
-        "📁 List directory contents with optional metadata and recursion"
+        "📁 List directory contents with optional metadata, recursion depth limit and tree output"
     }
     
     fn schema() -> Value {
@@ -70,29 +177,94 @@ impl ToolBuilder for ListFilesTool {
             .optional_string("path", "Directory path to list (default: '.')")
             .optional_bool("recursive", "List files recursively, will use .gitignore rules", Some(false))
             .optional_bool("show_metadata", "Show file metadata (size, permissions, dates)", Some(false))
-            .optional_string("pattern", "Glob pattern to search files by name (implies recursive=true, will use .gitignore)")
+            .optional_string("pattern", "Glob pattern: file name (*.rs) or relative path with ** and ? (src/**/mod.rs) (implies recursive=true, will use .gitignore)")
+            .optional_bool("fuzzy", "Treat pattern as a fuzzy file name query (usrsvc matches user_service.rs) and return files ranked by score; a query with '/' is matched against the relative path", Some(false))
+            .optional_bool("include_ignored", "Also list files excluded by .gitignore/.ignore/.empathicignore, VCS directories, Cargo target/ and hidden files", Some(false))
+            .optional_integer("max_depth", "Levels below path to descend, 1 = direct children (implies recursive=true)", Some(1))
+            .optional_string("format", "Output format: 'flat' (files array, default) or 'tree' (indented text with sizes; implies recursive=true)")
+            .optional_integer("limit", "Maximum entries per page; a next_cursor is returned when more remain", Some(1))
+            .optional_string("cursor", "next_cursor from a previous call to fetch the following page")
             .optional_string("project", "Project name for path resolution")
             .build()
     }
     
     async fn run(args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
-        // If pattern is specified, force recursive to true
-        let recursive = if args.pattern.is_some() { true } else { args.recursive };
+        let tree = match args.format.as_deref() {
+            None | Some("flat") => false,
+            Some("tree") => true,
+            Some(other) => return Err(EmpathicError::InvalidArgument {
+                arg: "format".to_string(),
+                reason: format!("unknown format '{}' (expected flat or tree)", other),
+            }),
+        };
+        
+        let fuzzy_query = match (args.fuzzy, args.pattern.as_deref()) {
+            (false, _) => None,
+            (true, Some(query)) if !query.trim().is_empty() => Some(query),
+            (true, _) => return Err(EmpathicError::InvalidArgument {
+                arg: "pattern".to_string(),
+                reason: "fuzzy mode needs a non-empty pattern to match".to_string(),
+            }),
+        };
+        
+        // Patterns, depth limits and trees all need the recursive walk
+        let recursive = args.recursive || args.pattern.is_some() || args.max_depth.is_some() || tree;
         
         let working_dir = config.project_path(args.project.as_deref());
         let list_path = working_dir.join(&args.path);
+        config.validate_path(&list_path)?;
+        
+        // 📏 Sizes come with every recursive entry; other metadata only on request
+        let glob = if fuzzy_query.is_some() { None } else { args.pattern.as_deref() };
+        let mut files = FileOps::list_files(&list_path, recursive, args.show_metadata || recursive, glob, args.include_ignored, args.max_depth, &config.skip_dirs).await?;
+        // The walk yields the listed directory itself first
+        files.retain(|file| file.path != list_path);
+        // Directory order isn't stable across calls; pages need a fixed order
+        files.sort_by(|a, b| a.path.cmp(&b.path));
         
-        let files = FileOps::list_files(&list_path, recursive, args.show_metadata, args.pattern.as_deref()).await?;
+        // 🔎 Fuzzy mode keeps matching files only, best score first (path order breaks ties)
+        let mut files: Vec<(Option<f64>, _)> = match fuzzy_query {
+            None => files.into_iter().map(|file| (None, file)).collect(),
+            Some(query) => files.into_iter()
+                .filter(|file| !file.is_dir)
+                .filter_map(|file| {
+                    let candidate = if query.contains('/') {
+                        file.path.strip_prefix(&list_path).unwrap_or(&file.path).to_string_lossy().to_string()
+                    } else {
+                        file.name.clone()
+                    };
+                    let score = fuzzy_score(query, &candidate);
+                    (score > 0.0).then_some((Some(score), file))
+                })
+                .collect(),
+        };
+        files.sort_by(|a, b| b.0.unwrap_or(0.0).total_cmp(&a.0.unwrap_or(0.0)));
+        
+        let total = files.len();
+        let (files, next_cursor) = paginate(files, args.cursor.as_deref(), args.limit)?;
         
         let file_entries: Vec<FileEntry> = files.into_iter()
-            .map(|file| {
+            .map(|(score, file)| {
+                let file_type = if file.is_symlink {
+                    "symlink"
+                } else if file.is_dir {
+                    "dir"
+                } else {
+                    "file"
+                };
                 let mut entry = FileEntry {
                     name: file.name,
+                    relative_path: file.path.strip_prefix(&list_path)
+                        .unwrap_or(&file.path)
+                        .to_string_lossy()
+                        .to_string(),
                     path: file.path.to_string_lossy().to_string(),
                     is_dir: file.is_dir,
-                    size: None,
+                    file_type,
+                    size: (recursive && !file.is_dir).then_some(file.size).flatten(),
                     modified: None,
                     permissions: None,
+                    score,
                 };
                 
                 if args.show_metadata {
@@ -113,12 +285,36 @@ impl ToolBuilder for ListFilesTool {
             path: list_path.to_string_lossy().to_string(),
             recursive,
             show_metadata: args.show_metadata,
+            max_depth: args.max_depth,
             count: file_entries.len(),
+            total,
+            next_cursor,
             pattern: args.pattern,
-            files: file_entries,
+            fuzzy: args.fuzzy,
+            tree: tree.then(|| render_tree(&file_entries)),
+            files: (!tree).then_some(file_entries),
         })
     }
 }
 
 // 🔧 Implement Tool trait using the builder pattern
 crate::impl_tool_for_builder!(ListFilesTool);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert_eq!(fuzzy_score("main.rs", "main.rs"), 1.0);
+        assert_eq!(fuzzy_score("MAIN.RS", "main.rs"), 1.0);
+        assert_eq!(fuzzy_score("xyz", "user_service.rs"), 0.0);
+        assert_eq!(fuzzy_score("", "user_service.rs"), 0.0);
+
+        // Word starts and consecutive runs beat scattered characters
+        let service = fuzzy_score("usrsvc", "user_service.rs");
+        assert!(service > 0.0 && service < 1.0);
+        assert!(fuzzy_score("us", "UserService.rs") > fuzzy_score("us", "houses.rs"));
+        assert!(fuzzy_score("main", "main.rs") > fuzzy_score("main", "domain_main_handler.rs"));
+    }
+}
@@ -4,13 +4,20 @@
 
 use crate::config::Config;
 use crate::error::{EmpathicResult, EmpathicError};
-use crate::lsp::manager::LspManager;
+use crate::lsp::manager::{LspManager, VirtualDocument};
 use crate::tools::{Tool, format_json_response};
 use async_trait::async_trait;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use serde_json::{json, Value};
 use std::path::PathBuf;
+use std::sync::Arc;
+
+/// ⏳ Argument every LSP tool accepts: wait for indexing to finish first
+pub const WAIT_READY_ARG: &str = "wait_ready_ms";
+
+/// 📝 Argument every LSP tool accepts: analyze this text instead of the saved file
+pub const CONTENT_ARG: &str = "content";
 
 /// 🎯 Common input for all LSP tools
 pub trait LspInput: DeserializeOwned + Send {
@@ -75,6 +82,15 @@ impl<T: BaseLspTool + 'static> Tool for T {
             "project": {
                 "type": "string", 
                 "description": "Project name for path resolution"
+            },
+            WAIT_READY_ARG: {
+                "type": "integer",
+                "minimum": 0,
+                "description": "Wait up to this many ms for rust-analyzer to finish indexing before the request, failing on timeout (avoids empty results from a cold server)"
+            },
+            CONTENT_ARG: {
+                "type": "string",
+                "description": "Unsaved buffer text to analyze in place of the file on disk (the file must exist; it is left untouched and the server goes back to the saved content afterwards)"
             }
         });
 
@@ -97,9 +113,21 @@ impl<T: BaseLspTool + 'static> Tool for T {
         })
     }
 
-    async fn execute(&self, args: Value, config: &Config) -> EmpathicResult<Value> {
+    async fn execute(&self, mut args: Value, config: &Config) -> EmpathicResult<Value> {
+        // ⏳ Common option, taken out before the tool's own input is parsed
+        let wait_ready_ms = args.as_object_mut()
+            .and_then(|args| args.remove(WAIT_READY_ARG))
+            .and_then(|value| value.as_u64());
+        let content = args.as_object_mut()
+            .and_then(|args| args.remove(CONTENT_ARG))
+            .and_then(|value| value.as_str().map(str::to_string));
+
         // 📥 Parse input
-        let input: T::Input = serde_json::from_value(args)?;
+        let input: T::Input = serde_json::from_value(args)
+            .map_err(|e| EmpathicError::ToolArgumentsInvalid {
+                tool_name: T::name().to_string(),
+                problems: vec![e.to_string()],
+            })?;
 
         // 📋 Store needed values before moving input
         let file_path_str = input.file_path().to_string();
@@ -112,15 +140,69 @@ impl<T: BaseLspTool + 'static> Tool for T {
             config,
         )?;
 
+        if let Some(wait_ready_ms) = wait_ready_ms {
+            get_lsp_manager(config)?
+                .wait_until_ready(&file_path, std::time::Duration::from_millis(wait_ready_ms))
+                .await?;
+        }
+
+        // 📝 The tool's own ensure_document_open then finds the buffer already open
+        let virtual_document = match content {
+            Some(content) => {
+                let manager = get_lsp_manager(config)?;
+                let document = manager.open_virtual_document(&file_path, &content).await?;
+                Some(VirtualDocumentGuard { manager: manager.clone(), file_path: file_path.clone(), document: Some(document) })
+            }
+            None => None,
+        };
+
         // 🚀 Execute LSP operation
         // Note: LSP server is spawned proactively in mcp/handlers.rs for ALL tools
-        let mut output = self.execute_lsp(input, file_path.clone(), config).await?;
+        let result = self.execute_lsp(input, file_path.clone(), config).await
+            .and_then(|mut output| {
+                // 📤 Set common output fields
+                output.set_file_path(file_path.to_string_lossy().to_string());
+                output.set_project(project_str);
+
+                format_json_response(&output)
+            });
+
+        if let Some(guard) = virtual_document {
+            guard.close().await;
+        }
+        result
+    }
+}
 
-        // 📤 Set common output fields
-        output.set_file_path(file_path.to_string_lossy().to_string());
-        output.set_project(project_str);
+/// 📝 Puts a virtual document back when the request ends, even if it is cancelled
+struct VirtualDocumentGuard {
+    manager: Arc<LspManager>,
+    file_path: PathBuf,
+    document: Option<VirtualDocument>,
+}
 
-        format_json_response(&output)
+impl VirtualDocumentGuard {
+    async fn close(mut self) {
+        if let Some(document) = self.document.take()
+            && let Err(e) = self.manager.close_virtual_document(&self.file_path, document).await {
+            log::warn!("📝 Failed to restore {} after a virtual-content request: {}", self.file_path.display(), e);
+        }
+    }
+}
+
+impl Drop for VirtualDocumentGuard {
+    fn drop(&mut self) {
+        // ⏱️ Timed out mid-request: restore in the background so later calls see the saved file
+        if let Some(document) = self.document.take()
+            && let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            let manager = self.manager.clone();
+            let file_path = self.file_path.clone();
+            runtime.spawn(async move {
+                if let Err(e) = manager.close_virtual_document(&file_path, document).await {
+                    log::warn!("📝 Failed to restore {} after a cancelled request: {}", file_path.display(), e);
+                }
+            });
+        }
     }
 }
 
@@ -132,12 +214,9 @@ pub fn validate_lsp_file_path(
 ) -> EmpathicResult<PathBuf> {
     // 🎯 Resolve file path relative to project directory
     let working_dir = config.project_path(Some(project));
-    let file_path = working_dir.join(file_path);
 
-    // 🛡️ Security: Validate file is within project directory
-    if !file_path.starts_with(&working_dir) {
-        return Err(EmpathicError::FileAccessDenied { path: file_path.clone() });
-    }
+    // 🛡️ Security: the canonical path (`..` and symlinks resolved) must stay in the allowed roots
+    let file_path = config.validate_path(&working_dir.join(file_path))?;
 
     // 📁 Validate file exists
     if !file_path.exists() {
@@ -162,6 +241,21 @@ pub fn get_lsp_manager(config: &Config) -> EmpathicResult<&std::sync::Arc<LspMan
         .ok_or_else(|| EmpathicError::tool_failed("lsp_manager", "LSP manager not available"))
 }
 
+/// 🧩 Fail with a clear "not supported" error when the server lacks a capability
+pub async fn require_server_capability(
+    client: &crate::lsp::client::LspClient,
+    method: &str,
+    supported: impl Fn(&lsp_types::ServerCapabilities) -> bool,
+) -> EmpathicResult<()> {
+    match client.capabilities().await {
+        Some(capabilities) if !supported(&capabilities) => Err(EmpathicError::NotSupported {
+            operation: method.to_string(),
+            platform: "the running language server".to_string(),
+        }),
+        _ => Ok(()),
+    }
+}
+
 /// 🎯 Position helper for tools that need line/character
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
 pub struct Position {
@@ -284,6 +378,8 @@ mod tests {
         // Should have base properties
         assert!(schema["properties"]["file_path"].is_object());
         assert!(schema["properties"]["project"].is_object());
+        assert!(schema["properties"][WAIT_READY_ARG].is_object());
+        assert_eq!(schema["properties"][CONTENT_ARG]["type"], "string");
         
         // Should have additional properties
         assert!(schema["properties"]["test_param"].is_object());
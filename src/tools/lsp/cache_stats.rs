@@ -0,0 +1,43 @@
+//! 🗄️ Cache Stats Tool - Size, bounds and hit rate of the LSP response cache
+
+use super::base::get_lsp_manager;
+use crate::config::Config;
+use crate::error::EmpathicResult;
+use crate::lsp::cache::CacheStats;
+use crate::tools::{SchemaBuilder, ToolBuilder};
+use async_trait::async_trait;
+use serde::Deserialize;
+
+/// 🗄️ Cache Stats Tool implementation
+pub struct CacheStatsTool;
+
+#[derive(Deserialize)]
+pub struct CacheStatsArgs {
+    // No arguments needed
+}
+
+#[async_trait]
+impl ToolBuilder for CacheStatsTool {
+    type Args = CacheStatsArgs;
+    type Output = CacheStats;
+
+    fn name() -> &'static str {
+        "cache_stats"
+    }
+
+    fn description() -> &'static str {
+        "🗄️ Show LSP response cache entries, bytes, bounds, evictions and hit rate"
+    }
+
+    fn schema() -> serde_json::Value {
+        SchemaBuilder::new()
+            .build()
+    }
+
+    async fn run(_args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
+        Ok(get_lsp_manager(config)?.cache().stats().await)
+    }
+}
+
+// 🔧 Implement Tool trait using the builder pattern
+crate::impl_tool_for_builder!(CacheStatsTool);
@@ -0,0 +1,246 @@
+//! 🧩 LSP Capabilities Tool - What the running language server supports
+//!
+//! Reports the `ServerCapabilities` from the server's `InitializeResult` as a
+//! flat feature → supported map plus the details callers usually need (code
+//! action kinds, rename preparation, semantic token modes, commands). The
+//! capabilities are stored on the client at initialization, so this never
+//! sends a request to an already running server.
+
+use super::base::get_lsp_manager;
+use crate::config::Config;
+use crate::error::{EmpathicError, EmpathicResult};
+use crate::lsp::PositionEncoding;
+use crate::tools::{SchemaBuilder, ToolBuilder};
+use async_trait::async_trait;
+use lsp_types::ServerCapabilities;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// 🧩 LSP Capabilities Tool implementation
+pub struct LspCapabilitiesTool;
+
+/// Readable feature names and the `ServerCapabilities` fields behind them
+const FEATURES: &[(&str, &str)] = &[
+    ("hover", "hoverProvider"),
+    ("completion", "completionProvider"),
+    ("signature_help", "signatureHelpProvider"),
+    ("goto_declaration", "declarationProvider"),
+    ("goto_definition", "definitionProvider"),
+    ("goto_type_definition", "typeDefinitionProvider"),
+    ("goto_implementation", "implementationProvider"),
+    ("references", "referencesProvider"),
+    ("document_highlight", "documentHighlightProvider"),
+    ("document_symbols", "documentSymbolProvider"),
+    ("workspace_symbols", "workspaceSymbolProvider"),
+    ("code_actions", "codeActionProvider"),
+    ("code_lens", "codeLensProvider"),
+    ("document_links", "documentLinkProvider"),
+    ("colors", "colorProvider"),
+    ("formatting", "documentFormattingProvider"),
+    ("range_formatting", "documentRangeFormattingProvider"),
+    ("on_type_formatting", "documentOnTypeFormattingProvider"),
+    ("rename", "renameProvider"),
+    ("folding_range", "foldingRangeProvider"),
+    ("selection_range", "selectionRangeProvider"),
+    ("execute_command", "executeCommandProvider"),
+    ("call_hierarchy", "callHierarchyProvider"),
+    ("linked_editing_range", "linkedEditingRangeProvider"),
+    ("semantic_tokens", "semanticTokensProvider"),
+    ("moniker", "monikerProvider"),
+    ("inlay_hints", "inlayHintProvider"),
+    ("inline_values", "inlineValueProvider"),
+    ("pull_diagnostics", "diagnosticProvider"),
+];
+
+#[derive(Deserialize)]
+pub struct CapabilitiesArgs {
+    project: String,
+    /// File whose server to inspect (default: the project's server)
+    file_path: Option<String>,
+    /// Include the full ServerCapabilities object
+    #[serde(default)]
+    include_raw: bool,
+}
+
+/// 🧩 Server identity and what it advertised
+#[derive(Serialize, Debug)]
+pub struct CapabilitiesOutput {
+    pub project: String,
+    /// Server name from the initialize result, e.g. "rust-analyzer"
+    pub server_name: Option<String>,
+    pub server_version: Option<String>,
+    /// Every known feature, true when the server advertises it
+    pub features: BTreeMap<&'static str, bool>,
+    pub details: CapabilityDetails,
+    /// Server-specific `experimental` capabilities, as sent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub experimental: Option<Value>,
+    /// Full ServerCapabilities (include_raw only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw: Option<Value>,
+}
+
+/// Options of the features most often needed before making a request
+#[derive(Serialize, Debug, Default, PartialEq)]
+pub struct CapabilityDetails {
+    /// "none", "full" or "incremental"
+    pub text_document_sync: Option<String>,
+    /// Unit of `character` in positions: "utf-8", "utf-16" (the default) or "utf-32"
+    pub position_encoding: String,
+    /// `textDocument/prepareRename` is supported
+    pub rename_prepare: bool,
+    pub code_action_kinds: Vec<String>,
+    /// `codeAction/resolve` is supported
+    pub code_action_resolve: bool,
+    pub code_lens_resolve: bool,
+    pub completion_trigger_characters: Vec<String>,
+    pub completion_resolve: bool,
+    pub signature_help_trigger_characters: Vec<String>,
+    pub semantic_tokens_full: bool,
+    pub semantic_tokens_delta: bool,
+    pub semantic_tokens_range: bool,
+    pub inlay_hint_resolve: bool,
+    pub commands: Vec<String>,
+}
+
+/// Feature map from the capabilities' JSON form: a field that is present and
+/// not `false` means supported, whichever of its shapes the server used
+fn feature_map(raw: &Value) -> BTreeMap<&'static str, bool> {
+    FEATURES.iter()
+        .map(|(feature, field)| (*feature, !matches!(raw.get(field), None | Some(Value::Null) | Some(Value::Bool(false)))))
+        .collect()
+}
+
+fn capability_details(caps: &ServerCapabilities, raw: &Value) -> CapabilityDetails {
+    let strings = |value: &Value| -> Vec<String> {
+        value.as_array().into_iter().flatten().filter_map(Value::as_str).map(str::to_string).collect()
+    };
+    let flag = |value: &Value| value.as_bool().unwrap_or(false);
+
+    let sync_kind = match &raw["textDocumentSync"] {
+        Value::Number(kind) => kind.as_u64(),
+        options => options["change"].as_u64(),
+    };
+    let semantic_full = &raw["semanticTokensProvider"]["full"];
+
+    CapabilityDetails {
+        text_document_sync: sync_kind.map(|kind| match kind {
+            0 => "none",
+            1 => "full",
+            _ => "incremental",
+        }.to_string()),
+        position_encoding: PositionEncoding::from_kind(caps.position_encoding.as_ref()).as_str().to_string(),
+        rename_prepare: flag(&raw["renameProvider"]["prepareProvider"]),
+        code_action_kinds: strings(&raw["codeActionProvider"]["codeActionKinds"]),
+        code_action_resolve: flag(&raw["codeActionProvider"]["resolveProvider"]),
+        code_lens_resolve: flag(&raw["codeLensProvider"]["resolveProvider"]),
+        completion_trigger_characters: strings(&raw["completionProvider"]["triggerCharacters"]),
+        completion_resolve: flag(&raw["completionProvider"]["resolveProvider"]),
+        signature_help_trigger_characters: strings(&raw["signatureHelpProvider"]["triggerCharacters"]),
+        semantic_tokens_full: flag(semantic_full) || semantic_full.is_object(),
+        semantic_tokens_delta: flag(&semantic_full["delta"]),
+        semantic_tokens_range: flag(&raw["semanticTokensProvider"]["range"]) || raw["semanticTokensProvider"]["range"].is_object(),
+        inlay_hint_resolve: flag(&raw["inlayHintProvider"]["resolveProvider"]),
+        commands: caps.execute_command_provider.as_ref().map(|provider| provider.commands.clone()).unwrap_or_default(),
+    }
+}
+
+#[async_trait]
+impl ToolBuilder for LspCapabilitiesTool {
+    type Args = CapabilitiesArgs;
+    type Output = CapabilitiesOutput;
+
+    fn name() -> &'static str {
+        "lsp_capabilities"
+    }
+
+    fn description() -> &'static str {
+        "🧩 Show which LSP features the project's language server supports (rename, code actions, inlay hints, semantic tokens, ...) from its initialize result"
+    }
+
+    fn schema() -> serde_json::Value {
+        SchemaBuilder::new()
+            .required_string("project", "Project name for path resolution")
+            .optional_string("file_path", "File whose language server to inspect (default: the project's server)")
+            .optional_bool("include_raw", "Also return the full ServerCapabilities object", Some(false))
+            .build()
+    }
+
+    async fn run(args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
+        let path = match &args.file_path {
+            Some(file_path) => {
+                let path = crate::tools::resolve_file_path(file_path, Some(&args.project), config)?;
+                crate::tools::validate_file_exists(&path)?;
+                path
+            }
+            None => {
+                let path = config.project_path(Some(&args.project));
+                if !path.exists() {
+                    return Err(EmpathicError::FileNotFound { path });
+                }
+                path
+            }
+        };
+
+        let lsp_manager = get_lsp_manager(config)?;
+        let client = lsp_manager.get_client(&path).await
+            .map_err(|e| EmpathicError::tool_failed(
+                "lsp_capabilities",
+                format!("Failed to get LSP client for {}: {}", path.display(), e)
+            ))?;
+        let capabilities = client.capabilities().await
+            .ok_or_else(|| EmpathicError::tool_failed(
+                "lsp_capabilities",
+                format!("Language server for {} has not finished initializing", path.display())
+            ))?;
+        let server_info = client.server_info().await;
+
+        let raw = serde_json::to_value(&capabilities)?;
+        Ok(CapabilitiesOutput {
+            project: args.project,
+            server_name: server_info.as_ref().map(|info| info.name.clone()),
+            server_version: server_info.and_then(|info| info.version),
+            features: feature_map(&raw),
+            details: capability_details(&capabilities, &raw),
+            experimental: capabilities.experimental.clone(),
+            raw: args.include_raw.then_some(raw),
+        })
+    }
+}
+
+crate::impl_tool_for_builder!(LspCapabilitiesTool);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_features_and_details() {
+        let raw = json!({
+            "textDocumentSync": {"openClose": true, "change": 2},
+            "hoverProvider": true,
+            "renameProvider": {"prepareProvider": true},
+            "codeActionProvider": {"codeActionKinds": ["quickfix", "refactor"], "resolveProvider": true},
+            "documentFormattingProvider": false,
+            "semanticTokensProvider": {"legend": {"tokenTypes": [], "tokenModifiers": []}, "full": {"delta": true}, "range": true},
+            "executeCommandProvider": {"commands": ["rust-analyzer.runSingle"]}
+        });
+        let caps: ServerCapabilities = serde_json::from_value(raw.clone()).unwrap();
+        let raw = serde_json::to_value(&caps).unwrap();
+
+        let features = feature_map(&raw);
+        assert_eq!(features.len(), FEATURES.len());
+        assert!(features["hover"] && features["rename"] && features["code_actions"] && features["semantic_tokens"]);
+        assert!(!features["formatting"] && !features["inlay_hints"]);
+
+        let details = capability_details(&caps, &raw);
+        assert_eq!(details.text_document_sync.as_deref(), Some("incremental"));
+        assert_eq!(details.position_encoding, "utf-16");
+        assert!(details.rename_prepare && details.code_action_resolve);
+        assert_eq!(details.code_action_kinds, vec!["quickfix", "refactor"]);
+        assert!(details.semantic_tokens_full && details.semantic_tokens_delta && details.semantic_tokens_range);
+        assert_eq!(details.commands, vec!["rust-analyzer.runSingle"]);
+    }
+}
@@ -0,0 +1,238 @@
+//! 🔎 LSP Code Lens Tool - Actionable annotations above items
+//!
+//! rust-analyzer annotates tests and mains with "▶︎ Run | Debug" lenses and
+//! items with reference/implementation counts. Lenses may come back without a
+//! command, to be filled in lazily through `codeLens/resolve`; those are
+//! resolved here when the server supports it.
+
+use super::base::{BaseLspTool, LspInput, LspOutput, RangeInfo, get_lsp_manager, require_server_capability};
+use crate::config::Config;
+use crate::error::{EmpathicError, EmpathicResult};
+use async_trait::async_trait;
+use lsp_types::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use url::Url;
+
+/// 🔎 LSP Code Lens Tool implementation
+pub struct LspCodeLensTool;
+
+/// Input parameters for lsp_code_lens tool
+#[derive(Debug, Deserialize)]
+pub struct CodeLensInput {
+    file_path: String,
+    project: String,
+    /// Resolve lenses returned without a command (default: true)
+    #[serde(default = "default_resolve")]
+    resolve: bool,
+}
+
+fn default_resolve() -> bool {
+    true
+}
+
+impl LspInput for CodeLensInput {
+    fn file_path(&self) -> &str {
+        &self.file_path
+    }
+
+    fn project(&self) -> &str {
+        &self.project
+    }
+}
+
+/// Output format for code lens results
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CodeLensOutput {
+    pub file_path: String,
+    pub project: String,
+    pub lenses: Vec<CodeLensInfo>,
+    pub total_lenses: usize,
+    /// Lenses still without a command (resolve unsupported, disabled or failed)
+    pub unresolved: usize,
+}
+
+impl LspOutput for CodeLensOutput {
+    fn set_file_path(&mut self, path: String) {
+        self.file_path = path;
+    }
+
+    fn set_project(&mut self, project: String) {
+        self.project = project;
+    }
+}
+
+/// Single code lens
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CodeLensInfo {
+    pub range: RangeInfo,
+    /// "run", "debug", "references", "implementations" or "other"
+    pub kind: String,
+    /// Text shown to the user, e.g. "▶︎ Run Test" or "3 references"
+    pub title: Option<String>,
+    /// Command identifier, e.g. "rust-analyzer.runSingle"
+    pub command: Option<String>,
+    /// Command arguments (for run lenses, the runnable with its cargo args)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<Vec<Value>>,
+    pub resolved: bool,
+}
+
+impl CodeLensInfo {
+    fn from_lsp_code_lens(lens: &CodeLens) -> Self {
+        let title = lens.command.as_ref().map(|command| command.title.clone());
+        let command = lens.command.as_ref().map(|command| command.command.clone());
+        Self {
+            range: RangeInfo::from_lsp_range(&lens.range),
+            kind: lens_kind(command.as_deref(), title.as_deref()).to_string(),
+            arguments: lens.command.as_ref().and_then(|command| command.arguments.clone()),
+            resolved: lens.command.is_some(),
+            title,
+            command,
+        }
+    }
+}
+
+/// Classify a lens by its command id, falling back to its title
+fn lens_kind(command: Option<&str>, title: Option<&str>) -> &'static str {
+    let command = command.unwrap_or_default().to_lowercase();
+    let title = title.unwrap_or_default().to_lowercase();
+    if command.contains("debug") || title.contains("debug") {
+        "debug"
+    } else if command.contains("run") || title.contains("run") {
+        "run"
+    } else if title.contains("implementation") {
+        "implementations"
+    } else if command.contains("reference") || title.contains("reference") {
+        "references"
+    } else {
+        "other"
+    }
+}
+
+#[async_trait]
+impl BaseLspTool for LspCodeLensTool {
+    type Input = CodeLensInput;
+    type Output = CodeLensOutput;
+
+    fn name() -> &'static str {
+        "lsp_code_lens"
+    }
+
+    fn description() -> &'static str {
+        "🔎 Get code lenses (Run/Debug on tests and mains, reference and implementation counts) for a Rust file"
+    }
+
+    fn additional_schema() -> serde_json::Value {
+        json!({
+            "resolve": {
+                "type": "boolean",
+                "default": true,
+                "description": "Resolve lenses the server returns without a command via codeLens/resolve"
+            }
+        })
+    }
+
+    async fn execute_lsp(
+        &self,
+        input: Self::Input,
+        file_path: PathBuf,
+        config: &Config,
+    ) -> EmpathicResult<Self::Output> {
+        let lsp_manager = get_lsp_manager(config)?;
+
+        // Ensure document is open/synced
+        lsp_manager.ensure_document_open(&file_path).await
+            .map_err(|e| EmpathicError::tool_failed(
+                "lsp_code_lens",
+                format!("Failed to sync document {}: {}", file_path.display(), e)
+            ))?;
+
+        // Get LSP client
+        let client = lsp_manager.get_client(&file_path).await
+            .map_err(|e| EmpathicError::tool_failed(
+                "lsp_code_lens",
+                format!("Failed to get LSP client for {}: {}", file_path.display(), e)
+            ))?;
+
+        require_server_capability(&client, "textDocument/codeLens", |caps| caps.code_lens_provider.is_some()).await?;
+        let resolve_supported = client.capabilities().await
+            .and_then(|caps| caps.code_lens_provider)
+            .and_then(|options| options.resolve_provider)
+            .unwrap_or(false);
+
+        log::info!("🔎 Getting code lenses for {}", file_path.display());
+
+        let uri = Url::from_file_path(&file_path)
+            .map_err(|_| EmpathicError::InvalidPath { path: file_path.clone() })?;
+
+        let params = CodeLensParams {
+            text_document: TextDocumentIdentifier {
+                uri: uri.to_string().parse().unwrap()
+            },
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+
+        let code_lenses = client.code_lens(params).await
+            .map_err(|e| EmpathicError::tool_failed(
+                "lsp_code_lens",
+                format!("Code lens request failed for {}: {}", file_path.display(), e)
+            ))?
+            .unwrap_or_default();
+
+        let mut lenses = Vec::with_capacity(code_lenses.len());
+        for lens in code_lenses {
+            let lens = if lens.command.is_none() && input.resolve && resolve_supported {
+                // A failed resolve leaves the lens unresolved rather than failing the call
+                match client.code_lens_resolve(lens.clone()).await {
+                    Ok(resolved) => resolved,
+                    Err(e) => {
+                        log::debug!("codeLens/resolve failed at line {}: {}", lens.range.start.line, e);
+                        lens
+                    }
+                }
+            } else {
+                lens
+            };
+            lenses.push(CodeLensInfo::from_lsp_code_lens(&lens));
+        }
+        lenses.sort_by_key(|lens| (lens.range.start_line, lens.range.start_character));
+
+        Ok(CodeLensOutput {
+            file_path: String::new(), // Set by base trait
+            project: String::new(),   // Set by base trait
+            total_lenses: lenses.len(),
+            unresolved: lenses.iter().filter(|lens| !lens.resolved).count(),
+            lenses,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lens_kind() {
+        assert_eq!(lens_kind(Some("rust-analyzer.runSingle"), Some("▶\u{fe0e} Run Test")), "run");
+        assert_eq!(lens_kind(Some("rust-analyzer.debugSingle"), Some("Debug")), "debug");
+        assert_eq!(lens_kind(Some("rust-analyzer.showReferences"), Some("3 references")), "references");
+        assert_eq!(lens_kind(Some("rust-analyzer.showReferences"), Some("2 implementations")), "implementations");
+        assert_eq!(lens_kind(None, Some("1 reference")), "references");
+        assert_eq!(lens_kind(None, None), "other");
+    }
+
+    #[test]
+    fn test_unresolved_lens() {
+        let lens = CodeLens {
+            range: Range::new(lsp_types::Position::new(4, 0), lsp_types::Position::new(4, 8)),
+            command: None,
+            data: Some(json!({"impls": 1})),
+        };
+        let info = CodeLensInfo::from_lsp_code_lens(&lens);
+        assert!(!info.resolved);
+        assert_eq!((info.range.start_line, info.kind.as_str(), info.title), (4, "other", None));
+    }
+}
@@ -0,0 +1,91 @@
+//! 🥶 LSP Cold Start Tool - Measure language server startup latency
+//!
+//! Restarts the server for a project from scratch and reports how long each
+//! phase took: spawn, initialize handshake and time until first diagnostics
+
+use super::base::get_lsp_manager;
+use crate::config::Config;
+use crate::error::{EmpathicError, EmpathicResult};
+use crate::lsp::types::ColdStartReport;
+use crate::tools::{SchemaBuilder, ToolBuilder};
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::time::Duration;
+
+/// 🥶 LSP Cold Start Tool implementation
+pub struct LspColdStartTool;
+
+/// Files tried (in order) when no file_path is given
+const DEFAULT_READY_FILES: &[&str] = &["src/main.rs", "src/lib.rs"];
+
+#[derive(Deserialize)]
+pub struct ColdStartArgs {
+    project: String,
+    language: Option<String>,
+    file_path: Option<String>,
+    ready_timeout_secs: Option<u64>,
+}
+
+#[async_trait]
+impl ToolBuilder for LspColdStartTool {
+    type Args = ColdStartArgs;
+    type Output = ColdStartReport;
+
+    fn name() -> &'static str {
+        "lsp_cold_start"
+    }
+
+    fn description() -> &'static str {
+        "🥶 Measure language server cold-start latency (spawn, initialize, time-to-first-diagnostics). Restarts the project's server"
+    }
+
+    fn schema() -> serde_json::Value {
+        SchemaBuilder::new()
+            .required_string("project", "Project name for path resolution")
+            .optional_string("language", "Language server to measure (default: rust)")
+            .optional_string("file_path", "File opened to wait for first diagnostics (default: src/main.rs or src/lib.rs)")
+            .optional_integer("ready_timeout_secs", "Max seconds to wait for first diagnostics (default: 30)", Some(1))
+            .build()
+    }
+
+    async fn run(args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
+        let language = args.language.as_deref().unwrap_or("rust");
+        if language != "rust" {
+            return Err(EmpathicError::NotSupported {
+                operation: format!("cold start measurement for '{}'", language),
+                platform: "empathic (only rust-analyzer is managed)".to_string(),
+            });
+        }
+
+        let working_dir = config.project_path(Some(&args.project));
+        if !working_dir.exists() {
+            return Err(EmpathicError::FileNotFound { path: working_dir });
+        }
+
+        let ready_file = match args.file_path {
+            Some(file_path) => crate::tools::resolve_file_path(&file_path, Some(&args.project), config)?,
+            None => DEFAULT_READY_FILES.iter()
+                .map(|candidate| working_dir.join(candidate))
+                .find(|candidate| candidate.exists())
+                .ok_or_else(|| EmpathicError::tool_failed(
+                    "lsp_cold_start",
+                    format!("No src/main.rs or src/lib.rs in {}, pass file_path", working_dir.display())
+                ))?,
+        };
+        crate::tools::validate_file_exists(&ready_file)?;
+
+        let lsp_manager = get_lsp_manager(config)?;
+        let project = lsp_manager.detector().find_project_for_file(&ready_file)?
+            .ok_or_else(|| EmpathicError::LspNoServerAvailable { file_path: ready_file.clone() })?;
+
+        let ready_timeout = Duration::from_secs(args.ready_timeout_secs.unwrap_or(30));
+
+        lsp_manager.measure_cold_start(&project.root_path, &ready_file, ready_timeout).await
+            .map_err(|e| EmpathicError::tool_failed(
+                "lsp_cold_start",
+                format!("Cold start measurement failed for {}: {}", project.root_path.display(), e)
+            ))
+    }
+}
+
+crate::impl_tool_for_builder!(LspColdStartTool);
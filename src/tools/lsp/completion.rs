@@ -3,8 +3,10 @@
 //! Provides context-aware completion suggestions using rust-analyzer
 
 use super::base::{BaseLspTool, LspInput, LspOutput, get_lsp_manager};
+use super::offsets;
 use crate::config::Config;
 use crate::error::{EmpathicResult, EmpathicError};
+use crate::lsp::PositionEncoding;
 use async_trait::async_trait;
 use lsp_types::*;
 use serde::{Deserialize, Serialize};
@@ -122,7 +124,7 @@ impl BaseLspTool for LspCompletionTool {
             "character": {
                 "type": "integer",
                 "minimum": 0,
-                "description": "Character position (0-indexed)"
+                "description": "Character position (0-indexed, UTF-16 code units)"
             }
         })
     }
@@ -168,10 +170,11 @@ impl BaseLspTool for LspCompletionTool {
             .unwrap_or_default();
 
         // Extract current word being typed
+        let encoding = PositionEncoding::TOOL;
         let current_word = if let Some(line) = lines.get(input.line as usize) {
             let chars: Vec<char> = line.chars().collect();
-            let mut start = input.character as usize;
-            let mut end = input.character as usize;
+            let mut start = offsets::char_column(line, input.character, encoding);
+            let mut end = start;
 
             // Find word boundaries
             while start > 0 && chars.get(start - 1).map(|c| c.is_alphanumeric() || *c == '_').unwrap_or(false) {
@@ -51,7 +51,7 @@ impl LspOutput for DiagnosticsOutput {
 
 /// Simplified diagnostic information for MCP output
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct DiagnosticInfo {
+pub(crate) struct DiagnosticInfo {
     message: String,
     severity: String,
     line: u32,
@@ -64,7 +64,7 @@ struct DiagnosticInfo {
 
 impl DiagnosticInfo {
     /// Convert from LSP Diagnostic to our format
-    fn from_lsp_diagnostic(diagnostic: &lsp_types::Diagnostic) -> Self {
+    pub(crate) fn from_lsp_diagnostic(diagnostic: &lsp_types::Diagnostic) -> Self {
         use lsp_types::NumberOrString;
         
         Self {
@@ -96,7 +96,7 @@ impl DiagnosticInfo {
 
 /// Diagnostic summary statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct DiagnosticSummary {
+pub(crate) struct DiagnosticSummary {
     total: usize,
     errors: usize,
     warnings: usize,
@@ -105,7 +105,7 @@ struct DiagnosticSummary {
 }
 
 impl DiagnosticSummary {
-    fn from_diagnostics(diagnostics: &[DiagnosticInfo]) -> Self {
+    pub(crate) fn from_diagnostics(diagnostics: &[DiagnosticInfo]) -> Self {
         let mut errors = 0;
         let mut warnings = 0;
         let mut information = 0;
@@ -0,0 +1,240 @@
+//! 🔦 LSP Document Highlight Tool - Highlight symbol occurrences within a file
+//!
+//! Lighter than find-references: only the current file is searched, so the
+//! workspace does not need to be fully indexed
+
+use super::base::{BaseLspTool, LspInput, LspOutput, RangeInfo, get_lsp_manager};
+use crate::config::Config;
+use crate::error::{EmpathicError, EmpathicResult};
+use async_trait::async_trait;
+use lsp_types::*;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::PathBuf;
+use url::Url;
+
+/// 🔦 LSP Document Highlight Tool implementation
+pub struct LspDocumentHighlightTool;
+
+/// Input parameters for lsp_document_highlight tool
+#[derive(Debug, Deserialize)]
+pub struct DocumentHighlightInput {
+    file_path: String,
+    project: String,
+    line: u32,
+    character: u32,
+}
+
+impl LspInput for DocumentHighlightInput {
+    fn file_path(&self) -> &str {
+        &self.file_path
+    }
+
+    fn project(&self) -> &str {
+        &self.project
+    }
+}
+
+/// Output format for document highlight results
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DocumentHighlightOutput {
+    pub file_path: String,
+    pub project: String,
+    pub position: super::base::Position,
+    pub highlights: Vec<HighlightInfo>,
+    pub summary: HighlightSummary,
+}
+
+impl LspOutput for DocumentHighlightOutput {
+    fn set_file_path(&mut self, path: String) {
+        self.file_path = path;
+    }
+
+    fn set_project(&mut self, project: String) {
+        self.project = project;
+    }
+}
+
+/// Single highlighted occurrence
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HighlightInfo {
+    pub range: RangeInfo,
+    pub kind: String,
+}
+
+/// Summary of highlight counts per kind
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct HighlightSummary {
+    pub total: usize,
+    pub read: usize,
+    pub write: usize,
+    pub text: usize,
+}
+
+impl HighlightInfo {
+    fn from_lsp_highlight(highlight: &DocumentHighlight) -> Self {
+        Self {
+            range: RangeInfo::from_lsp_range(&highlight.range),
+            kind: highlight_kind_name(highlight.kind).to_string(),
+        }
+    }
+}
+
+/// 🏷️ Map DocumentHighlightKind to a readable name (spec default is Text)
+fn highlight_kind_name(kind: Option<DocumentHighlightKind>) -> &'static str {
+    match kind {
+        Some(DocumentHighlightKind::READ) => "read",
+        Some(DocumentHighlightKind::WRITE) => "write",
+        _ => "text",
+    }
+}
+
+impl HighlightSummary {
+    fn from_highlights(highlights: &[HighlightInfo]) -> Self {
+        let mut summary = Self {
+            total: highlights.len(),
+            ..Default::default()
+        };
+        for highlight in highlights {
+            match highlight.kind.as_str() {
+                "read" => summary.read += 1,
+                "write" => summary.write += 1,
+                _ => summary.text += 1,
+            }
+        }
+        summary
+    }
+}
+
+#[async_trait]
+impl BaseLspTool for LspDocumentHighlightTool {
+    type Input = DocumentHighlightInput;
+    type Output = DocumentHighlightOutput;
+
+    fn name() -> &'static str {
+        "lsp_document_highlight"
+    }
+
+    fn description() -> &'static str {
+        "🔦 Highlight all occurrences of a symbol within a single Rust file (read/write/text kinds)"
+    }
+
+    fn additional_schema() -> serde_json::Value {
+        json!({
+            "line": {
+                "type": "integer",
+                "minimum": 0,
+                "description": "Line number (0-indexed)"
+            },
+            "character": {
+                "type": "integer",
+                "minimum": 0,
+                "description": "Character position (0-indexed, UTF-16 code units)"
+            }
+        })
+    }
+
+    fn additional_required() -> Vec<&'static str> {
+        vec!["line", "character"]
+    }
+
+    async fn execute_lsp(
+        &self,
+        input: Self::Input,
+        file_path: PathBuf,
+        config: &Config,
+    ) -> EmpathicResult<Self::Output> {
+        let lsp_manager = get_lsp_manager(config)?;
+
+        // Ensure document is open/synced
+        lsp_manager.ensure_document_open(&file_path).await
+            .map_err(|e| EmpathicError::tool_failed(
+                "lsp_document_highlight",
+                format!("Failed to sync document {}: {}", file_path.display(), e)
+            ))?;
+
+        // Get LSP client
+        let client = lsp_manager.get_client(&file_path).await
+            .map_err(|e| EmpathicError::tool_failed(
+                "lsp_document_highlight",
+                format!("Failed to get LSP client for {}: {}", file_path.display(), e)
+            ))?;
+
+        log::info!("🔦 Highlighting symbol at {}:{}:{}",
+            file_path.display(), input.line, input.character);
+
+        let uri = Url::from_file_path(&file_path)
+            .map_err(|_| EmpathicError::InvalidPath { path: file_path.clone() })?;
+
+        let params = DocumentHighlightParams {
+            text_document_position_params: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier {
+                    uri: uri.to_string().parse().unwrap()
+                },
+                position: lsp_types::Position {
+                    line: input.line,
+                    character: input.character,
+                },
+            },
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+
+        let highlight_result = client.document_highlight(params).await
+            .map_err(|e| EmpathicError::tool_failed(
+                "lsp_document_highlight",
+                format!("Document highlight request failed for {}:{}:{}: {}",
+                    file_path.display(), input.line, input.character, e)
+            ))?;
+
+        // A position that isn't on a symbol yields null - report an empty result
+        let highlights: Vec<HighlightInfo> = highlight_result
+            .unwrap_or_default()
+            .iter()
+            .map(HighlightInfo::from_lsp_highlight)
+            .collect();
+
+        let summary = HighlightSummary::from_highlights(&highlights);
+
+        Ok(DocumentHighlightOutput {
+            file_path: String::new(), // Set by base trait
+            project: String::new(),   // Set by base trait
+            position: super::base::Position::new(input.line, input.character),
+            highlights,
+            summary,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlight_kind_names() {
+        assert_eq!(highlight_kind_name(Some(DocumentHighlightKind::READ)), "read");
+        assert_eq!(highlight_kind_name(Some(DocumentHighlightKind::WRITE)), "write");
+        assert_eq!(highlight_kind_name(Some(DocumentHighlightKind::TEXT)), "text");
+        assert_eq!(highlight_kind_name(None), "text");
+    }
+
+    #[test]
+    fn test_summary_counts() {
+        let range = lsp_types::Range::default();
+        let highlights: Vec<HighlightInfo> = [
+            Some(DocumentHighlightKind::WRITE),
+            Some(DocumentHighlightKind::READ),
+            Some(DocumentHighlightKind::READ),
+            None,
+        ]
+        .into_iter()
+        .map(|kind| HighlightInfo::from_lsp_highlight(&DocumentHighlight { range, kind }))
+        .collect();
+
+        let summary = HighlightSummary::from_highlights(&highlights);
+        assert_eq!(summary.total, 4);
+        assert_eq!(summary.read, 2);
+        assert_eq!(summary.write, 1);
+        assert_eq!(summary.text, 1);
+    }
+}
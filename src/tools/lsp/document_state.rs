@@ -0,0 +1,80 @@
+//! 💾 LSP Document State Tool - Persist and restore the open-document set
+//!
+//! Snapshots which documents are open in the LSP servers (paths and versions
+//! only) so a restarted server can be re-warmed without the agent re-opening files
+
+use super::base::get_lsp_manager;
+use crate::config::Config;
+use crate::error::{EmpathicError, EmpathicResult};
+use crate::lsp::manager::tracker::TrackedDocument;
+use crate::tools::{SchemaBuilder, ToolBuilder};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+/// 💾 LSP Document State Tool implementation
+pub struct LspDocumentStateTool;
+
+#[derive(Deserialize)]
+pub struct DocumentStateArgs {
+    /// One of "save", "restore" or "show"
+    action: String,
+}
+
+#[derive(Serialize)]
+pub struct DocumentStateOutput {
+    action: String,
+    state_file: String,
+    /// Documents saved, restored or currently open, depending on the action
+    documents: Vec<TrackedDocument>,
+    count: usize,
+}
+
+#[async_trait]
+impl ToolBuilder for LspDocumentStateTool {
+    type Args = DocumentStateArgs;
+    type Output = DocumentStateOutput;
+
+    fn name() -> &'static str {
+        "lsp_document_state"
+    }
+
+    fn description() -> &'static str {
+        "💾 Save, restore or show the set of documents open in LSP servers (survives server restarts)"
+    }
+
+    fn schema() -> serde_json::Value {
+        SchemaBuilder::new()
+            .required_string("action", "Action: 'save' (snapshot open documents), 'restore' (re-open saved documents) or 'show' (list open documents)")
+            .build()
+    }
+
+    async fn run(args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
+        let lsp_manager = get_lsp_manager(config)?;
+
+        let documents = match args.action.as_str() {
+            "save" => lsp_manager.save_document_state().await?.documents,
+            "restore" => {
+                let reopened = lsp_manager.restore_document_state().await?;
+                let snapshot = lsp_manager.snapshot_documents().await;
+                snapshot
+                    .documents
+                    .into_iter()
+                    .filter(|doc| reopened.contains(&doc.path))
+                    .collect()
+            }
+            "show" => lsp_manager.snapshot_documents().await.documents,
+            other => {
+                return Err(EmpathicError::mcp_parameter("action", other));
+            }
+        };
+
+        Ok(DocumentStateOutput {
+            action: args.action,
+            state_file: lsp_manager.state_file().to_string_lossy().to_string(),
+            count: documents.len(),
+            documents,
+        })
+    }
+}
+
+crate::impl_tool_for_builder!(LspDocumentStateTool);
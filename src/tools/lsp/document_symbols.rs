@@ -3,7 +3,10 @@
 //! Provides a hierarchical view of symbols in a Rust file (functions, structs, enums, etc.)
 
 use super::base::{BaseLspTool, LspInput, LspOutput};
+use super::offsets::{LineIndex, RangeOffsets};
 use crate::error::EmpathicResult;
+use crate::lsp::cache::CacheKey;
+use crate::lsp::PositionEncoding;
 use async_trait::async_trait;
 use lsp_types::*;
 use serde::{Deserialize, Serialize};
@@ -58,6 +61,9 @@ struct SymbolInfo {
     character: u32,
     end_line: u32,
     end_character: u32,
+    /// Byte offsets and char columns of the range, for editing tools
+    #[serde(flatten)]
+    offsets: Option<RangeOffsets>,
     children: Vec<SymbolInfo>,
 }
 
@@ -76,9 +82,9 @@ struct SymbolsSummary {
 
 impl SymbolInfo {
     /// Convert from LSP DocumentSymbol to our format
-    fn from_document_symbol(symbol: &DocumentSymbol) -> Self {
+    fn from_document_symbol(symbol: &DocumentSymbol, index: Option<&LineIndex>) -> Self {
         let children = symbol.children.as_ref()
-            .map(|children| children.iter().map(Self::from_document_symbol).collect())
+            .map(|children| children.iter().map(|child| Self::from_document_symbol(child, index)).collect())
             .unwrap_or_default();
 
         Self {
@@ -89,11 +95,12 @@ impl SymbolInfo {
             character: symbol.range.start.character,
             end_line: symbol.range.end.line,
             end_character: symbol.range.end.character,
+            offsets: index.and_then(|index| index.range_offsets(symbol.range)),
             children,
         }
     }
 
-    fn from_symbol_information(symbol: &SymbolInformation) -> Self {
+    fn from_symbol_information(symbol: &SymbolInformation, index: Option<&LineIndex>) -> Self {
         Self {
             name: symbol.name.clone(),
             kind: format!("{:?}", symbol.kind),
@@ -102,6 +109,7 @@ impl SymbolInfo {
             character: symbol.location.range.start.character,
             end_line: symbol.location.range.end.line,
             end_character: symbol.location.range.end.character,
+            offsets: index.and_then(|index| index.range_offsets(symbol.location.range)),
             children: Vec::new(),
         }
     }
@@ -191,19 +199,23 @@ impl BaseLspTool for LspDocumentSymbolsTool {
             partial_result_params: PartialResultParams::default(),
         };
 
-        // Call LSP server
-        let response = client.document_symbols(params).await?;
+        // Call LSP server (or answer from the cache for this document version)
+        let key = CacheKey::DocumentSymbols(file_path.clone());
+        let response = lsp_manager.cached_request(key, client.document_symbols(params)).await?;
 
         // Convert response to our format
+        let encoding = PositionEncoding::TOOL;
+        let index = tokio::fs::read_to_string(&file_path).await.ok()
+            .map(|text| LineIndex::with_encoding(text, encoding));
         let symbols: Vec<SymbolInfo> = match response {
             Some(DocumentSymbolResponse::Flat(symbol_info)) => {
                 symbol_info.iter()
-                    .map(SymbolInfo::from_symbol_information)
+                    .map(|symbol| SymbolInfo::from_symbol_information(symbol, index.as_ref()))
                     .collect()
             }
             Some(DocumentSymbolResponse::Nested(document_symbols)) => {
                 document_symbols.iter()
-                    .map(SymbolInfo::from_document_symbol)
+                    .map(|symbol| SymbolInfo::from_document_symbol(symbol, index.as_ref()))
                     .collect()
             }
             None => Vec::new(),
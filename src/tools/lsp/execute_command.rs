@@ -0,0 +1,130 @@
+//! ⚙️ LSP Execute Command Tool - Run server-specific commands
+//!
+//! Lists the commands a language server advertises via `executeCommandProvider`
+//! and runs a named one through `workspace/executeCommand`, unlocking
+//! server-specific functionality without a dedicated tool per command
+
+use super::base::get_lsp_manager;
+use crate::config::Config;
+use crate::error::{EmpathicError, EmpathicResult};
+use crate::tools::ToolBuilder;
+use async_trait::async_trait;
+use lsp_types::ExecuteCommandParams;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+/// ⚙️ LSP Execute Command Tool implementation
+pub struct LspExecuteCommandTool;
+
+#[derive(Deserialize)]
+pub struct ExecuteCommandArgs {
+    project: String,
+    /// Command to run - when omitted only the advertised commands are listed
+    command: Option<String>,
+    #[serde(default)]
+    arguments: Vec<Value>,
+}
+
+#[derive(Serialize)]
+pub struct ExecuteCommandOutput {
+    project: String,
+    available_commands: Vec<String>,
+    command: Option<String>,
+    executed: bool,
+    result: Option<Value>,
+}
+
+#[async_trait]
+impl ToolBuilder for LspExecuteCommandTool {
+    type Args = ExecuteCommandArgs;
+    type Output = ExecuteCommandOutput;
+
+    fn name() -> &'static str {
+        "lsp_execute_command"
+    }
+
+    fn description() -> &'static str {
+        "⚙️ List or run language server commands (workspace/executeCommand), e.g. rust-analyzer.reloadWorkspace"
+    }
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "project": {
+                    "type": "string",
+                    "description": "Project name for path resolution"
+                },
+                "command": {
+                    "type": "string",
+                    "description": "Command to execute; omit to list the commands the server supports"
+                },
+                "arguments": {
+                    "type": "array",
+                    "description": "Arguments passed to the command as-is"
+                }
+            },
+            "required": ["project"],
+            "additionalProperties": false
+        })
+    }
+
+    async fn run(args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
+        let working_dir = config.project_path(Some(&args.project));
+        if !working_dir.exists() {
+            return Err(EmpathicError::FileNotFound { path: working_dir });
+        }
+
+        let lsp_manager = get_lsp_manager(config)?;
+        let client = lsp_manager.get_client(&working_dir).await
+            .map_err(|e| EmpathicError::tool_failed(
+                "lsp_execute_command",
+                format!("Failed to get LSP client for {}: {}", working_dir.display(), e)
+            ))?;
+
+        let available_commands = client.supported_commands().await;
+
+        let Some(command) = args.command else {
+            return Ok(ExecuteCommandOutput {
+                project: args.project,
+                available_commands,
+                command: None,
+                executed: false,
+                result: None,
+            });
+        };
+
+        // 🛡️ Only run commands the server actually advertises
+        if !available_commands.contains(&command) {
+            return Err(EmpathicError::tool_failed(
+                "lsp_execute_command",
+                format!("Command '{}' is not supported by the language server. Available: [{}]",
+                    command, available_commands.join(", "))
+            ));
+        }
+
+        log::info!("⚙️ Executing LSP command '{}' in {}", command, working_dir.display());
+
+        let params = ExecuteCommandParams {
+            command: command.clone(),
+            arguments: args.arguments,
+            work_done_progress_params: Default::default(),
+        };
+
+        let result = client.execute_command(params).await
+            .map_err(|e| EmpathicError::tool_failed(
+                "lsp_execute_command",
+                format!("Command '{}' failed: {}", command, e)
+            ))?;
+
+        Ok(ExecuteCommandOutput {
+            project: args.project,
+            available_commands,
+            command: Some(command),
+            executed: true,
+            result,
+        })
+    }
+}
+
+crate::impl_tool_for_builder!(LspExecuteCommandTool);
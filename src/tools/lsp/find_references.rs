@@ -3,8 +3,10 @@
 //! Provides comprehensive reference finding using rust-analyzer
 
 use super::base::{BaseLspTool, LspInput, LspOutput, get_lsp_manager};
+use super::offsets::{self, OffsetResolver, RangeOffsets};
 use crate::config::Config;
 use crate::error::{EmpathicError, EmpathicResult};
+use crate::lsp::PositionEncoding;
 use async_trait::async_trait;
 use lsp_types::*;
 use serde::{Deserialize, Serialize};
@@ -81,6 +83,9 @@ pub struct ReferenceLocation {
     pub end_character: u32,
     pub context: String,
     pub reference_kind: String,
+    /// Byte offsets and char columns of the range, for editing tools
+    #[serde(flatten)]
+    pub offsets: Option<RangeOffsets>,
 }
 
 /// Summary of reference search results
@@ -108,6 +113,7 @@ impl ReferenceLocation {
             end_character: location.range.end.character,
             context: context.to_string(),
             reference_kind: reference_kind.to_string(),
+            offsets: None,
         }
     }
 }
@@ -135,7 +141,7 @@ impl BaseLspTool for LspFindReferencesTool {
             "character": {
                 "type": "integer",
                 "minimum": 0,
-                "description": "Character position (0-indexed)"
+                "description": "Character position (0-indexed, UTF-16 code units)"
             },
             "include_declaration": {
                 "type": "boolean",
@@ -210,10 +216,11 @@ impl BaseLspTool for LspFindReferencesTool {
         let lines: Vec<&str> = file_contents.lines().collect();
 
         // Get symbol info from current position
+        let encoding = PositionEncoding::TOOL;
         let symbol_info = if let Some(line) = lines.get(input.line as usize) {
             let chars: Vec<char> = line.chars().collect();
-            let mut start = input.character as usize;
-            let mut end = input.character as usize;
+            let mut start = offsets::char_column(line, input.character, encoding);
+            let mut end = start;
 
             // Find word boundaries
             while start > 0 && chars.get(start - 1).map(|c| c.is_alphanumeric() || *c == '_').unwrap_or(false) {
@@ -241,6 +248,7 @@ impl BaseLspTool for LspFindReferencesTool {
         let references: Vec<ReferenceLocation> = if let Some(locations) = references_result {
             let mut refs = Vec::new();
             let mut unique_files = std::collections::HashSet::new();
+            let mut resolver = OffsetResolver::new(PositionEncoding::TOOL);
 
             for location in locations {
                 // Extract context from the reference location
@@ -268,11 +276,13 @@ impl BaseLspTool for LspFindReferencesTool {
                     "reference"
                 };
 
+                let mut reference = ReferenceLocation::from_lsp_location(&location, reference_kind, &context);
                 if let Some(path) = ref_path {
+                    reference.offsets = resolver.offsets(&path, location.range).await;
                     unique_files.insert(path);
                 }
 
-                refs.push(ReferenceLocation::from_lsp_location(&location, reference_kind, &context));
+                refs.push(reference);
             }
 
             refs
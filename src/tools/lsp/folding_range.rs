@@ -0,0 +1,155 @@
+//! 📐 LSP Folding Range Tool - Collapsible regions of a file
+//!
+//! Exposes the block structure of a file (functions, impls, comments, imports)
+//! as reported by the language server
+
+use super::base::{BaseLspTool, LspInput, LspOutput, get_lsp_manager, require_server_capability};
+use crate::config::Config;
+use crate::error::{EmpathicError, EmpathicResult};
+use async_trait::async_trait;
+use lsp_types::*;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use url::Url;
+
+/// 📐 LSP Folding Range Tool implementation
+pub struct LspFoldingRangeTool;
+
+/// Input parameters for lsp_folding_range tool
+#[derive(Debug, Deserialize)]
+pub struct FoldingRangeInput {
+    file_path: String,
+    project: String,
+}
+
+impl LspInput for FoldingRangeInput {
+    fn file_path(&self) -> &str {
+        &self.file_path
+    }
+
+    fn project(&self) -> &str {
+        &self.project
+    }
+}
+
+/// Output format for folding range results
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FoldingRangeOutput {
+    pub file_path: String,
+    pub project: String,
+    pub ranges: Vec<FoldingRangeInfo>,
+    pub total_ranges: usize,
+}
+
+impl LspOutput for FoldingRangeOutput {
+    fn set_file_path(&mut self, path: String) {
+        self.file_path = path;
+    }
+
+    fn set_project(&mut self, project: String) {
+        self.project = project;
+    }
+}
+
+/// Single collapsible region
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FoldingRangeInfo {
+    pub start_line: u32,
+    pub start_character: Option<u32>,
+    pub end_line: u32,
+    pub end_character: Option<u32>,
+    /// "comment", "imports", "region" or null for code blocks
+    pub kind: Option<String>,
+    pub collapsed_text: Option<String>,
+}
+
+impl FoldingRangeInfo {
+    fn from_lsp_folding_range(range: &FoldingRange) -> Self {
+        Self {
+            start_line: range.start_line,
+            start_character: range.start_character,
+            end_line: range.end_line,
+            end_character: range.end_character,
+            kind: range.kind.as_ref().map(|kind| match kind {
+                FoldingRangeKind::Comment => "comment".to_string(),
+                FoldingRangeKind::Imports => "imports".to_string(),
+                FoldingRangeKind::Region => "region".to_string(),
+            }),
+            collapsed_text: range.collapsed_text.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl BaseLspTool for LspFoldingRangeTool {
+    type Input = FoldingRangeInput;
+    type Output = FoldingRangeOutput;
+
+    fn name() -> &'static str {
+        "lsp_folding_range"
+    }
+
+    fn description() -> &'static str {
+        "📐 Get collapsible regions (functions, blocks, comments, imports) of a Rust file"
+    }
+
+    async fn execute_lsp(
+        &self,
+        _input: Self::Input,
+        file_path: PathBuf,
+        config: &Config,
+    ) -> EmpathicResult<Self::Output> {
+        let lsp_manager = get_lsp_manager(config)?;
+
+        // Ensure document is open/synced
+        lsp_manager.ensure_document_open(&file_path).await
+            .map_err(|e| EmpathicError::tool_failed(
+                "lsp_folding_range",
+                format!("Failed to sync document {}: {}", file_path.display(), e)
+            ))?;
+
+        // Get LSP client
+        let client = lsp_manager.get_client(&file_path).await
+            .map_err(|e| EmpathicError::tool_failed(
+                "lsp_folding_range",
+                format!("Failed to get LSP client for {}: {}", file_path.display(), e)
+            ))?;
+
+        require_server_capability(&client, "textDocument/foldingRange", |caps| {
+            !matches!(caps.folding_range_provider, None | Some(FoldingRangeProviderCapability::Simple(false)))
+        }).await?;
+
+        log::info!("📐 Getting folding ranges for {}", file_path.display());
+
+        let uri = Url::from_file_path(&file_path)
+            .map_err(|_| EmpathicError::InvalidPath { path: file_path.clone() })?;
+
+        let params = FoldingRangeParams {
+            text_document: TextDocumentIdentifier {
+                uri: uri.to_string().parse().unwrap()
+            },
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+
+        let folding_result = client.folding_range(params).await
+            .map_err(|e| EmpathicError::tool_failed(
+                "lsp_folding_range",
+                format!("Folding range request failed for {}: {}", file_path.display(), e)
+            ))?;
+
+        let mut ranges: Vec<FoldingRangeInfo> = folding_result
+            .unwrap_or_default()
+            .iter()
+            .map(FoldingRangeInfo::from_lsp_folding_range)
+            .collect();
+        ranges.sort_by_key(|range| (range.start_line, range.end_line));
+
+        Ok(FoldingRangeOutput {
+            file_path: String::new(), // Set by base trait
+            project: String::new(),   // Set by base trait
+            total_ranges: ranges.len(),
+            ranges,
+        })
+    }
+}
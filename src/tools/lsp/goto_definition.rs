@@ -1,17 +1,30 @@
 //! 🧭 LSP Go To Definition Tool - Navigate to symbol definitions
 //!
-//! Provides navigation to symbol definitions using rust-analyzer
+//! Provides navigation to symbol definitions using rust-analyzer. With
+//! `resolve_reexports`, a definition that lands on a `pub use` is followed
+//! with further goto requests until the original item is reached.
 
 use super::base::{BaseLspTool, LspInput, LspOutput, get_lsp_manager};
+use super::offsets::{self, OffsetResolver, RangeOffsets};
 use crate::config::Config;
 use crate::error::{EmpathicError, EmpathicResult};
+use crate::lsp::PositionEncoding;
 use async_trait::async_trait;
 use lsp_types::*;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::path::PathBuf;
+use std::sync::LazyLock;
 use url::Url;
 
+/// Most re-export hops followed before giving up
+const MAX_REEXPORT_HOPS: usize = 5;
+
+/// `use` items, optionally public, e.g. `pub(crate) use inner::Thing;`
+static REEXPORT: LazyLock<regex::Regex> = LazyLock::new(|| {
+    regex::Regex::new(r"^\s*(?:pub(?:\s*\([^)]*\))?\s+)?use\s").expect("valid re-export regex")
+});
+
 /// 🧭 LSP Go To Definition Tool implementation
 pub struct LspGotoDefinitionTool;
 
@@ -22,6 +35,9 @@ pub struct GotoDefinitionInput {
     project: String,
     line: u32,
     character: u32,
+    /// Follow `pub use` re-exports to the original definition
+    #[serde(default)]
+    resolve_reexports: bool,
 }
 
 impl LspInput for GotoDefinitionInput {
@@ -42,6 +58,9 @@ pub struct GotoDefinitionOutput {
     position: PositionInfo,
     definitions: Vec<DefinitionLocation>,
     symbol_info: Option<SymbolInfo>,
+    /// Re-export sites passed through, in order, when resolve_reexports is set
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    reexport_chain: Vec<DefinitionLocation>,
 }
 
 impl LspOutput for GotoDefinitionOutput {
@@ -62,7 +81,7 @@ struct PositionInfo {
 }
 
 /// Definition location information
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 struct DefinitionLocation {
     file_path: String,
     line: u32,
@@ -70,6 +89,9 @@ struct DefinitionLocation {
     end_line: u32,
     end_character: u32,
     context: Option<String>,
+    /// Byte offsets and char columns of the range, for editing tools
+    #[serde(flatten)]
+    offsets: Option<RangeOffsets>,
 }
 
 /// Symbol information at current position
@@ -98,10 +120,86 @@ impl DefinitionLocation {
             end_line: location.range.end.line,
             end_character: location.range.end.character,
             context: file_path_context.map(String::from),
+            offsets: None,
         })
     }
 }
 
+/// 📍 Flatten any goto response shape into locations
+fn response_locations(response: GotoDefinitionResponse) -> Vec<Location> {
+    match response {
+        GotoDefinitionResponse::Scalar(location) => vec![location],
+        GotoDefinitionResponse::Array(locations) => locations,
+        // Location links provide more detail but we can extract basic Location from them
+        GotoDefinitionResponse::Link(location_links) => location_links.into_iter()
+            .map(|link| Location { uri: link.target_uri, range: link.target_selection_range })
+            .collect(),
+    }
+}
+
+/// Whether a source line is a `use` item, i.e. a definition that re-exports another
+fn is_reexport(line: &str) -> bool {
+    REEXPORT.is_match(line)
+}
+
+/// 🔁 Follow definitions sitting on `use` items until a real definition
+///
+/// Returns the re-export sites passed through and the final definitions.
+/// Stops after `MAX_REEXPORT_HOPS`, on a location seen before, or when a
+/// hop yields nothing (the last re-export site is then the answer).
+async fn follow_reexports(
+    client: &crate::lsp::client::LspClient,
+    mut definitions: Vec<DefinitionLocation>,
+) -> (Vec<DefinitionLocation>, Vec<DefinitionLocation>) {
+    let mut chain: Vec<DefinitionLocation> = Vec::new();
+
+    while chain.len() < MAX_REEXPORT_HOPS {
+        let Some(first) = definitions.first().cloned() else {
+            break;
+        };
+        let line = tokio::fs::read_to_string(&first.file_path).await.ok()
+            .and_then(|text| text.lines().nth(first.line as usize).map(str::to_string));
+        if !line.as_deref().is_some_and(is_reexport) || chain.contains(&first) {
+            break;
+        }
+        let Ok(uri) = Url::from_file_path(&first.file_path) else {
+            break;
+        };
+
+        log::debug!("🔁 Following re-export at {}:{}:{}", first.file_path, first.line, first.character);
+        // The target may lie outside the project (a dependency); the server
+        // resolves it from disk, so no didOpen is sent
+        let params = GotoDefinitionParams {
+            text_document_position_params: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier {
+                    uri: uri.to_string().parse().unwrap()
+                },
+                position: Position { line: first.line, character: first.character },
+            },
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+        let next: Vec<DefinitionLocation> = match client.goto_definition(params).await {
+            Ok(Some(response)) => response_locations(response).iter()
+                .filter_map(|location| DefinitionLocation::from_lsp_location(location, None).ok())
+                .collect(),
+            Ok(None) => Vec::new(),
+            Err(e) => {
+                log::warn!("⚠️ Re-export follow-up failed at {}:{}: {}", first.file_path, first.line, e);
+                Vec::new()
+            }
+        };
+        // Pointing back at itself means the server can't see further
+        if next.is_empty() || next.first() == Some(&first) {
+            break;
+        }
+        chain.push(first);
+        definitions = next;
+    }
+
+    (chain, definitions)
+}
+
 #[async_trait]
 impl BaseLspTool for LspGotoDefinitionTool {
     type Input = GotoDefinitionInput;
@@ -125,7 +223,12 @@ impl BaseLspTool for LspGotoDefinitionTool {
             "character": {
                 "type": "integer",
                 "minimum": 0,
-                "description": "Character position (0-indexed)"
+                "description": "Character position (0-indexed, UTF-16 code units)"
+            },
+            "resolve_reexports": {
+                "type": "boolean",
+                "default": false,
+                "description": "When the definition is a `pub use` re-export, keep following it to the original item (up to 5 hops) and return the chain"
             }
         })
     }
@@ -187,30 +290,24 @@ impl BaseLspTool for LspGotoDefinitionTool {
 
         // Convert LSP response to our format
         let mut definitions = Vec::new();
-        
         if let Some(response) = definition_result {
-            match response {
-                GotoDefinitionResponse::Scalar(location) => {
-                    definitions.push(DefinitionLocation::from_lsp_location(&location, None)?);
-                }
-                GotoDefinitionResponse::Array(locations) => {
-                    for location in locations {
-                        definitions.push(DefinitionLocation::from_lsp_location(&location, None)?);
-                    }
-                }
-                GotoDefinitionResponse::Link(location_links) => {
-                    // Location links provide more detail but we can extract basic Location from them
-                    for link in location_links {
-                        let location = Location {
-                            uri: link.target_uri.clone(),
-                            range: link.target_selection_range,
-                        };
-                        definitions.push(DefinitionLocation::from_lsp_location(&location, None)?);
-                    }
-                }
+            for location in response_locations(response) {
+                definitions.push(DefinitionLocation::from_lsp_location(&location, None)?);
             }
         }
 
+        let (mut reexport_chain, mut definitions) = if input.resolve_reexports {
+            follow_reexports(&client, definitions).await
+        } else {
+            (Vec::new(), definitions)
+        };
+
+        let mut resolver = OffsetResolver::new(PositionEncoding::TOOL);
+        for location in definitions.iter_mut().chain(reexport_chain.iter_mut()) {
+            let range = offsets::range(location.line, location.character, location.end_line, location.end_character);
+            location.offsets = resolver.offsets(std::path::Path::new(&location.file_path), range).await;
+        }
+
         // Create symbol info (optional, could extract from hover if needed)
         let symbol_info = if definitions.is_empty() {
             None
@@ -231,6 +328,40 @@ impl BaseLspTool for LspGotoDefinitionTool {
             },
             definitions,
             symbol_info,
+            reexport_chain,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_reexport() {
+        assert!(is_reexport("pub use inner::Thing;"));
+        assert!(is_reexport("    pub(crate) use crate::config::{Config, ToolPolicy};"));
+        assert!(is_reexport("pub(in crate::tools) use base::*;"));
+        assert!(is_reexport("use std::path::Path as P;"));
+        assert!(!is_reexport("pub fn user() {}"));
+        assert!(!is_reexport("pub struct UseCase;"));
+        assert!(!is_reexport("// pub use old::Thing;"));
+    }
+
+    #[test]
+    fn test_response_locations_shapes() {
+        let uri: lsp_types::Uri = "file:///tmp/lib.rs".parse().unwrap();
+        let range = Range::new(Position::new(3, 4), Position::new(3, 9));
+        let location = Location { uri: uri.clone(), range };
+
+        assert_eq!(response_locations(GotoDefinitionResponse::Scalar(location.clone())), vec![location.clone()]);
+        let link = LocationLink {
+            origin_selection_range: None,
+            target_uri: uri,
+            target_range: Range::new(Position::new(1, 0), Position::new(5, 1)),
+            target_selection_range: range,
+        };
+        // Links resolve to the identifier, not the whole item
+        assert_eq!(response_locations(GotoDefinitionResponse::Link(vec![link])), vec![location]);
+    }
+}
@@ -1,9 +1,13 @@
 //! 🔍 LSP Hover Tool - Get type information and documentation at cursor position
 //!
-//! Retrieves hover information from rust-analyzer for Rust files at specific positions
+//! Retrieves hover information from rust-analyzer for Rust files at specific positions.
+//! All three hover shapes (`MarkedString`, `MarkedString[]`, `MarkupContent`)
+//! are normalized to one markdown string, which is always returned raw;
+//! `format: "plaintext"` additionally strips fences and markdown syntax.
 
 use super::base::{BaseLspTool, LspInput, LspOutput, get_lsp_manager};
 use crate::config::Config;
+use crate::lsp::cache::CacheKey;
 use crate::error::{EmpathicError, EmpathicResult};
 use async_trait::async_trait;
 use lsp_types::*;
@@ -22,6 +26,17 @@ pub struct HoverInput {
     project: String,
     line: u32,
     character: u32,
+    #[serde(default)]
+    format: HoverFormat,
+}
+
+/// 🧾 Text format for hover contents
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HoverFormat {
+    #[default]
+    Markdown,
+    Plaintext,
 }
 
 impl LspInput for HoverInput {
@@ -63,8 +78,12 @@ pub struct PositionInfo {
 /// Hover information content
 #[derive(Debug, Serialize, Deserialize)]
 pub struct HoverInfo {
+    /// Format of `contents` and `documentation`
+    pub format: HoverFormat,
     pub contents: Vec<String>,
     pub documentation: Option<String>,
+    /// The server's response as markdown, whatever `format` was requested
+    pub markdown: String,
     pub range: Option<RangeInfo>,
 }
 
@@ -90,9 +109,10 @@ impl RangeInfo {
 
 impl HoverInfo {
     /// Convert from LSP Hover type to our internal format
-    fn from_lsp_hover(hover: &Hover) -> Self {
+    pub(super) fn from_lsp_hover(hover: &Hover, format: HoverFormat) -> Self {
         let mut contents = Vec::new();
         let mut documentation = None;
+        let mut plain = false;
 
         match &hover.contents {
             HoverContents::Scalar(marked_string) => {
@@ -106,17 +126,21 @@ impl HoverInfo {
             HoverContents::Markup(markup) => {
                 match markup.kind {
                     MarkupKind::PlainText => {
+                        plain = true;
                         contents.push(markup.value.clone());
                     }
                     MarkupKind::Markdown => {
                         // Split markdown content into code blocks and documentation
                         let lines: Vec<&str> = markup.value.lines().collect();
                         let mut current_content = String::new();
+                        let mut in_fence = false;
 
                         for line in lines {
-                            if line.starts_with("```") || current_content.is_empty() {
+                            let fence = line.starts_with("```");
+                            if fence || in_fence || current_content.is_empty() {
                                 current_content.push_str(line);
                                 current_content.push('\n');
+                                in_fence ^= fence;
                             } else if !line.trim().is_empty() {
                                 // This might be documentation
                                 if documentation.is_none() {
@@ -136,9 +160,22 @@ impl HoverInfo {
             }
         }
 
+        let markdown = match &hover.contents {
+            HoverContents::Markup(markup) => markup.value.clone(),
+            _ => contents.join("\n\n"),
+        };
+        
+        // Plaintext markup is already plain; stripping could eat real characters
+        if format == HoverFormat::Plaintext && !plain {
+            contents = contents.iter().map(|content| markdown_to_plaintext(content)).collect();
+            documentation = documentation.map(|doc| markdown_to_plaintext(&doc));
+        }
+
         Self {
+            format,
             contents,
             documentation,
+            markdown,
             range: hover.range.as_ref().map(RangeInfo::from_lsp_range),
         }
     }
@@ -153,6 +190,104 @@ impl HoverInfo {
     }
 }
 
+/// 📄 Reduce markdown to readable text
+///
+/// Code fences are dropped but their contents kept verbatim; headings, rules,
+/// quotes, emphasis, inline code ticks, link targets and escapes are removed.
+pub fn markdown_to_plaintext(markdown: &str) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut in_fence = false;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            lines.push(line.to_string());
+            continue;
+        }
+
+        let compact: String = trimmed.chars().filter(|c| !c.is_whitespace()).collect();
+        if compact.len() >= 3 && (compact.chars().all(|c| c == '-') || compact.chars().all(|c| c == '*') || compact.chars().all(|c| c == '_')) {
+            lines.push(String::new());
+            continue;
+        }
+
+        let mut text = trimmed;
+        let heading = text.trim_start_matches('#');
+        if heading.len() < text.len() && (heading.is_empty() || heading.starts_with(' ')) {
+            text = heading.trim_start();
+        }
+        while let Some(quoted) = text.strip_prefix('>') {
+            text = quoted.trim_start();
+        }
+        lines.push(strip_inline_markdown(text).trim_end().to_string());
+    }
+
+    // Collapse the blank runs left behind by fences and rules
+    let mut plain = String::new();
+    let mut blank = false;
+    for line in lines {
+        if line.is_empty() {
+            blank = true;
+            continue;
+        }
+        if !plain.is_empty() {
+            plain.push_str(if blank { "\n\n" } else { "\n" });
+        }
+        plain.push_str(&line);
+        blank = false;
+    }
+    plain
+}
+
+/// Drop inline markdown from one line of prose
+fn strip_inline_markdown(line: &str) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let mut text = String::with_capacity(line.len());
+    let mut index = 0;
+
+    while index < chars.len() {
+        match chars[index] {
+            '\\' if chars.get(index + 1).is_some_and(|c| c.is_ascii_punctuation()) => {
+                text.push(chars[index + 1]);
+                index += 2;
+                continue;
+            }
+            '`' => {}
+            // Emphasis markers hug a word; a lone `*` (as in `a * b`) is kept
+            '*' | '_' if {
+                let before = index.checked_sub(1).and_then(|i| chars.get(i));
+                let after = chars.get(index + 1);
+                let word = |c: Option<&char>| c.is_some_and(|c| c.is_alphanumeric());
+                // `snake_case` keeps its underscores
+                !(chars[index] == '_' && word(before) && word(after))
+                    && (after.is_some_and(|c| !c.is_whitespace()) || before.is_some_and(|c| !c.is_whitespace()))
+            } => {}
+            '[' => {
+                if let Some(close) = chars[index + 1..].iter().position(|&c| c == ']').map(|i| index + 1 + i) {
+                    let label: String = chars[index + 1..close].iter().collect();
+                    text.push_str(&strip_inline_markdown(&label));
+                    index = close + 1;
+                    // `[label](target)` drops the target too
+                    if chars.get(index) == Some(&'(')
+                        && let Some(end) = chars[index..].iter().position(|&c| c == ')')
+                    {
+                        index += end + 1;
+                    }
+                    continue;
+                }
+                text.push('[');
+            }
+            c => text.push(c),
+        }
+        index += 1;
+    }
+    text
+}
+
 #[async_trait]
 impl BaseLspTool for LspHoverTool {
     type Input = HoverInput;
@@ -176,7 +311,13 @@ impl BaseLspTool for LspHoverTool {
             "character": {
                 "type": "integer", 
                 "minimum": 0,
-                "description": "Character position (0-indexed)"
+                "description": "Character position (0-indexed, UTF-16 code units)"
+            },
+            "format": {
+                "type": "string",
+                "enum": ["markdown", "plaintext"],
+                "default": "markdown",
+                "description": "Format of contents/documentation; the raw markdown is always returned as `markdown`"
             }
         })
     }
@@ -226,8 +367,9 @@ impl BaseLspTool for LspHoverTool {
             work_done_progress_params: Default::default(),
         };
 
-        // Send hover request to rust-analyzer
-        let hover_result = client.hover(params).await
+        // Send hover request to rust-analyzer (or answer from the cache for this document version)
+        let key = CacheKey::Hover { file_path: file_path.clone(), line: input.line, character: input.character };
+        let hover_result = lsp_manager.cached_request(key, client.hover(params)).await
             .map_err(|e| EmpathicError::tool_failed(
                 "lsp_hover",
                 format!("Hover request failed for {}:{}:{}: {}", 
@@ -235,7 +377,7 @@ impl BaseLspTool for LspHoverTool {
             ))?;
 
         // Convert LSP response to our format
-        let hover_info = hover_result.map(|h| HoverInfo::from_lsp_hover(&h));
+        let hover_info = hover_result.map(|h| HoverInfo::from_lsp_hover(&h, input.format));
 
         Ok(HoverOutput {
             file_path: String::new(), // Set by base trait
@@ -248,3 +390,50 @@ impl BaseLspTool for LspHoverTool {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn markup(kind: MarkupKind, value: &str) -> Hover {
+        Hover {
+            contents: HoverContents::Markup(MarkupContent { kind, value: value.to_string() }),
+            range: None,
+        }
+    }
+
+    #[test]
+    fn test_markdown_to_plaintext() {
+        let markdown = "```rust\nmy_crate::Person\n```\n\n```rust\npub struct Person\n```\n\n---\n\n# Example\n\nA **person** with a `name`, see [`Vec`](https://doc.rust-lang.org) or [Self::new].\nUse a * b and snake_case\\_too.";
+        assert_eq!(
+            markdown_to_plaintext(markdown),
+            "my_crate::Person\n\npub struct Person\n\nExample\n\nA person with a name, see Vec or Self::new.\nUse a * b and snake_case_too."
+        );
+    }
+
+    #[test]
+    fn test_hover_variants_share_one_shape() {
+        let scalar = Hover {
+            contents: HoverContents::Scalar(MarkedString::LanguageString(LanguageString {
+                language: "rust".to_string(),
+                value: "fn main()".to_string(),
+            })),
+            range: None,
+        };
+        let info = HoverInfo::from_lsp_hover(&scalar, HoverFormat::Plaintext);
+        assert_eq!(info.contents, vec!["fn main()"]);
+        assert_eq!(info.markdown, "```rust\nfn main()\n```");
+
+        let info = HoverInfo::from_lsp_hover(&markup(MarkupKind::Markdown, "```rust\nfn main()\n```\nRuns **first**."), HoverFormat::Plaintext);
+        assert_eq!(info.contents, vec!["fn main()"]);
+        assert_eq!(info.documentation.as_deref(), Some("Runs first."));
+        assert!(info.markdown.contains("**first**"));
+
+        // Plaintext from the server is left alone
+        let info = HoverInfo::from_lsp_hover(&markup(MarkupKind::PlainText, "a *b* c"), HoverFormat::Plaintext);
+        assert_eq!(info.contents, vec!["a *b* c"]);
+
+        let info = HoverInfo::from_lsp_hover(&markup(MarkupKind::Markdown, "`x`"), HoverFormat::Markdown);
+        assert_eq!(info.contents, vec!["`x`\n"]);
+    }
+}
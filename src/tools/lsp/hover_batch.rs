@@ -0,0 +1,216 @@
+//! 🔍 LSP Hover Batch Tool - Hover information for many positions in one call
+//!
+//! Sends one `textDocument/hover` per position through the file's client,
+//! at most `max_concurrency` at a time, and returns the results in request
+//! order. A position whose request fails gets an `error` instead of failing
+//! the whole batch.
+
+use super::base::{BaseLspTool, LspInput, LspOutput, Position, get_lsp_manager};
+use super::hover::{HoverFormat, HoverInfo};
+use crate::config::Config;
+use crate::error::{EmpathicError, EmpathicResult};
+use crate::lsp::cache::CacheKey;
+use async_trait::async_trait;
+use lsp_types::{HoverParams, TextDocumentIdentifier, TextDocumentPositionParams};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use url::Url;
+
+/// 🔍 LSP Hover Batch Tool implementation
+pub struct LspHoverBatchTool;
+
+/// Requests in flight when max_concurrency is not given
+const DEFAULT_CONCURRENCY: usize = 8;
+
+/// Upper bound on max_concurrency, so one batch can't flood the server
+const MAX_CONCURRENCY: usize = 32;
+
+/// Most positions accepted in one call
+const MAX_POSITIONS: usize = 500;
+
+/// Input parameters for lsp_hover_batch tool
+#[derive(Debug, Deserialize)]
+pub struct HoverBatchInput {
+    file_path: String,
+    project: String,
+    positions: Vec<Position>,
+    #[serde(default)]
+    format: HoverFormat,
+    max_concurrency: Option<usize>,
+}
+
+impl LspInput for HoverBatchInput {
+    fn file_path(&self) -> &str {
+        &self.file_path
+    }
+
+    fn project(&self) -> &str {
+        &self.project
+    }
+}
+
+/// Output format for batched hovers
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HoverBatchOutput {
+    pub file_path: String,
+    pub project: String,
+    /// One entry per requested position, in request order
+    pub results: Vec<HoverBatchResult>,
+    /// Positions with hover information
+    pub found: usize,
+    pub failed: usize,
+}
+
+impl LspOutput for HoverBatchOutput {
+    fn set_file_path(&mut self, path: String) {
+        self.file_path = path;
+    }
+
+    fn set_project(&mut self, project: String) {
+        self.project = project;
+    }
+}
+
+/// Hover for a single requested position
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HoverBatchResult {
+    pub position: Position,
+    pub hover_info: Option<HoverInfo>,
+    /// Why the request failed; hover_info is then None
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[async_trait]
+impl BaseLspTool for LspHoverBatchTool {
+    type Input = HoverBatchInput;
+    type Output = HoverBatchOutput;
+
+    fn name() -> &'static str {
+        "lsp_hover_batch"
+    }
+
+    fn description() -> &'static str {
+        "🔍 Get hover information for many positions in a Rust file in one call, requested concurrently, results aligned with the positions"
+    }
+
+    fn additional_schema() -> serde_json::Value {
+        json!({
+            "positions": {
+                "type": "array",
+                "minItems": 1,
+                "maxItems": MAX_POSITIONS,
+                "description": "Positions to hover (0-indexed line/character)",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "line": { "type": "integer", "minimum": 0 },
+                        "character": { "type": "integer", "minimum": 0 }
+                    },
+                    "required": ["line", "character"]
+                }
+            },
+            "format": {
+                "type": "string",
+                "enum": ["markdown", "plaintext"],
+                "default": "markdown",
+                "description": "Format of contents/documentation; the raw markdown is always returned as `markdown`"
+            },
+            "max_concurrency": {
+                "type": "integer",
+                "minimum": 1,
+                "maximum": MAX_CONCURRENCY,
+                "default": DEFAULT_CONCURRENCY,
+                "description": "Hover requests in flight at once"
+            }
+        })
+    }
+
+    fn additional_required() -> Vec<&'static str> {
+        vec!["positions"]
+    }
+
+    async fn execute_lsp(
+        &self,
+        input: Self::Input,
+        file_path: PathBuf,
+        config: &Config,
+    ) -> EmpathicResult<Self::Output> {
+        if input.positions.is_empty() {
+            return Err(EmpathicError::mcp_parameter("positions", "[]"));
+        }
+        if input.positions.len() > MAX_POSITIONS {
+            return Err(EmpathicError::InvalidArgument {
+                arg: "positions".to_string(),
+                reason: format!("{} positions, at most {} per call", input.positions.len(), MAX_POSITIONS),
+            });
+        }
+        let concurrency = input.max_concurrency.unwrap_or(DEFAULT_CONCURRENCY).clamp(1, MAX_CONCURRENCY);
+
+        let lsp_manager = get_lsp_manager(config)?;
+
+        // Ensure document is open/synced once for the whole batch
+        lsp_manager.ensure_document_open(&file_path).await
+            .map_err(|e| EmpathicError::tool_failed(
+                "lsp_hover_batch",
+                format!("Failed to sync document {}: {}", file_path.display(), e)
+            ))?;
+
+        let client = lsp_manager.get_client(&file_path).await
+            .map_err(|e| EmpathicError::tool_failed(
+                "lsp_hover_batch",
+                format!("Failed to get LSP client for {}: {}", file_path.display(), e)
+            ))?;
+
+        log::info!("🔍 Hover batch of {} positions in {} ({} at a time)",
+            input.positions.len(), file_path.display(), concurrency);
+
+        let uri = Url::from_file_path(&file_path)
+            .map_err(|_| EmpathicError::InvalidPath { path: file_path.clone() })?;
+        let text_document = TextDocumentIdentifier { uri: uri.to_string().parse().unwrap() };
+
+        // 🚦 Every request is spawned up front; the semaphore keeps at most
+        // `concurrency` of them at the server, and handles keep request order
+        let permits = Arc::new(Semaphore::new(concurrency));
+        let handles: Vec<_> = input.positions.iter()
+            .map(|position| {
+                let client = client.clone();
+                let lsp_manager = Arc::clone(lsp_manager);
+                let key = CacheKey::Hover { file_path: file_path.clone(), line: position.line, character: position.character };
+                let permits = Arc::clone(&permits);
+                let params = HoverParams {
+                    text_document_position_params: TextDocumentPositionParams {
+                        text_document: text_document.clone(),
+                        position: lsp_types::Position::new(position.line, position.character),
+                    },
+                    work_done_progress_params: Default::default(),
+                };
+                tokio::spawn(async move {
+                    let _permit = permits.acquire_owned().await;
+                    lsp_manager.cached_request(key, client.hover(params)).await
+                })
+            })
+            .collect();
+
+        let mut results = Vec::with_capacity(handles.len());
+        for (position, handle) in input.positions.into_iter().zip(handles) {
+            let (hover_info, error) = match handle.await {
+                Ok(Ok(hover)) => (hover.map(|hover| HoverInfo::from_lsp_hover(&hover, input.format)), None),
+                Ok(Err(e)) => (None, Some(e.to_string())),
+                Err(e) => (None, Some(format!("hover task failed: {}", e))),
+            };
+            results.push(HoverBatchResult { position, hover_info, error });
+        }
+
+        Ok(HoverBatchOutput {
+            file_path: String::new(), // Set by base trait
+            project: String::new(),   // Set by base trait
+            found: results.iter().filter(|result| result.hover_info.is_some()).count(),
+            failed: results.iter().filter(|result| result.error.is_some()).count(),
+            results,
+        })
+    }
+}
@@ -0,0 +1,74 @@
+//! 📊 LSP Metrics Tool - Request counts, latency percentiles and load
+//!
+//! Surfaces `LspMetrics` to MCP clients so slow LSP calls can be diagnosed
+//! without reading server logs. `reset` starts a fresh measurement window.
+
+use super::base::get_lsp_manager;
+use crate::config::Config;
+use crate::error::EmpathicResult;
+use crate::lsp::performance::MetricsSnapshot;
+use crate::tools::{SchemaBuilder, ToolBuilder};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+/// 📊 LSP Metrics Tool implementation
+pub struct LspMetricsTool;
+
+#[derive(Deserialize)]
+pub struct LspMetricsArgs {
+    #[serde(default)]
+    reset: bool,
+}
+
+#[derive(Serialize)]
+pub struct LspMetricsOutput {
+    #[serde(flatten)]
+    metrics: MetricsSnapshot,
+    /// Projects with a running language server
+    active_servers: Vec<String>,
+    /// Whether the counters were zeroed after this snapshot
+    reset: bool,
+}
+
+#[async_trait]
+impl ToolBuilder for LspMetricsTool {
+    type Args = LspMetricsArgs;
+    type Output = LspMetricsOutput;
+
+    fn name() -> &'static str {
+        "lsp_metrics"
+    }
+
+    fn description() -> &'static str {
+        "📊 Show LSP performance metrics: per-method counts and latency percentiles, cache hit rate, active servers, queue depth"
+    }
+
+    fn schema() -> serde_json::Value {
+        SchemaBuilder::new()
+            .optional_bool("reset", "Zero the counters after reporting them, starting a fresh measurement window", Some(false))
+            .build()
+    }
+
+    async fn run(args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
+        let lsp_manager = get_lsp_manager(config)?;
+        let metrics = lsp_manager.metrics_snapshot();
+        if args.reset {
+            lsp_manager.reset_metrics();
+        }
+
+        let mut active_servers: Vec<String> = lsp_manager.get_server_status().await
+            .iter()
+            .map(|process| process.project_path.to_string_lossy().to_string())
+            .collect();
+        active_servers.sort();
+
+        Ok(LspMetricsOutput {
+            metrics,
+            active_servers,
+            reset: args.reset,
+        })
+    }
+}
+
+// 🔧 Implement Tool trait using the builder pattern
+crate::impl_tool_for_builder!(LspMetricsTool);
@@ -3,18 +3,49 @@
 //! Provides semantic code analysis capabilities through external LSP servers
 
 pub mod base;
+pub mod cache_stats;
+pub mod capabilities;
+pub mod code_lens;
+pub mod cold_start;
 pub mod completion;
 pub mod diagnostics;
+pub mod document_highlight;
+pub mod document_state;
 pub mod document_symbols;
+pub mod execute_command;
 pub mod find_references;
+pub mod folding_range;
 pub mod goto_definition;
 pub mod hover;
+pub mod hover_batch;
+pub mod metrics;
+pub mod offsets;
+pub mod prepare_rename;
+pub mod project_diagnostics;
+pub mod selection_range;
+pub mod semantic_tokens;
+pub mod type_hierarchy;
 pub mod workspace_symbols;
 
+pub use cache_stats::CacheStatsTool;
+pub use capabilities::LspCapabilitiesTool;
+pub use code_lens::LspCodeLensTool;
+pub use cold_start::LspColdStartTool;
 pub use completion::LspCompletionTool;
 pub use diagnostics::LspDiagnosticsTool;
+pub use document_highlight::LspDocumentHighlightTool;
+pub use document_state::LspDocumentStateTool;
 pub use document_symbols::LspDocumentSymbolsTool;
+pub use execute_command::LspExecuteCommandTool;
 pub use find_references::LspFindReferencesTool;
+pub use folding_range::LspFoldingRangeTool;
 pub use goto_definition::LspGotoDefinitionTool;
 pub use hover::LspHoverTool;
+pub use hover_batch::LspHoverBatchTool;
+pub use metrics::LspMetricsTool;
+pub use prepare_rename::LspPrepareRenameTool;
+pub use project_diagnostics::LspProjectDiagnosticsTool;
+pub use selection_range::LspSelectionRangeTool;
+pub use semantic_tokens::LspSemanticTokensTool;
+pub use type_hierarchy::LspTypeHierarchyTool;
 pub use workspace_symbols::LspWorkspaceSymbolsTool;
@@ -0,0 +1,168 @@
+//! 📏 Position Offsets - UTF-8 byte offsets for LSP positions
+//!
+//! LSP `character` counts code units of the negotiated position encoding
+//! (UTF-8 with rust-analyzer, UTF-16 by default), so on lines with non-ASCII
+//! text it can differ from both the byte offsets and the char columns that
+//! editing tools work in. Results that carry ranges add `start_byte` /
+//! `end_byte` (from the start of the file) and `start_char` / `end_char`
+//! (Unicode scalar columns on their lines), resolved against the file on disk.
+
+use crate::lsp::PositionEncoding;
+use lsp_types::{Position, Range};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// 📏 A range as byte offsets into the file and char columns on its lines
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RangeOffsets {
+    /// UTF-8 byte offset of the start, from the beginning of the file
+    pub start_byte: usize,
+    pub end_byte: usize,
+    /// Start column in chars (Unicode scalar values), 0-indexed
+    pub start_char: u32,
+    pub end_char: u32,
+}
+
+/// 📐 Line starts of a file's text, for resolving LSP positions
+pub struct LineIndex {
+    text: String,
+    line_starts: Vec<usize>,
+    encoding: PositionEncoding,
+}
+
+impl LineIndex {
+    /// Index for UTF-16 positions, the LSP default
+    pub fn new(text: String) -> Self {
+        Self::with_encoding(text, PositionEncoding::Utf16)
+    }
+
+    pub fn with_encoding(text: String, encoding: PositionEncoding) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(index, _)| index + 1))
+            .collect();
+        Self { text, line_starts, encoding }
+    }
+
+    /// Byte offset and char column of a position
+    ///
+    /// A character past the end of its line means the end of the line, as
+    /// LSP specifies; a line past the end of the file resolves to nothing.
+    pub fn resolve(&self, position: Position) -> Option<(usize, u32)> {
+        let line_start = *self.line_starts.get(position.line as usize)?;
+        let line_end = self.line_starts.get(position.line as usize + 1)
+            .map_or(self.text.len(), |next| next - 1);
+        let line = self.text[line_start..line_end].trim_end_matches('\r');
+
+        let (mut units, mut column) = (0u32, 0u32);
+        for (byte, ch) in line.char_indices() {
+            if units >= position.character {
+                return Some((line_start + byte, column));
+            }
+            units += self.encoding.units(ch);
+            column += 1;
+        }
+        Some((line_start + line.len(), column))
+    }
+
+    pub fn range_offsets(&self, range: Range) -> Option<RangeOffsets> {
+        let (start_byte, start_char) = self.resolve(range.start)?;
+        let (end_byte, end_char) = self.resolve(range.end)?;
+        Some(RangeOffsets { start_byte, end_byte, start_char, end_char })
+    }
+}
+
+/// 📂 Resolves ranges in many files, reading each file once
+#[derive(Default)]
+pub struct OffsetResolver {
+    files: HashMap<PathBuf, Option<LineIndex>>,
+    encoding: PositionEncoding,
+}
+
+impl OffsetResolver {
+    /// Resolver for positions in the server's negotiated `encoding`
+    pub fn new(encoding: PositionEncoding) -> Self {
+        Self { files: HashMap::new(), encoding }
+    }
+
+    /// Offsets of `range` in `path`, or None when the file can't be read
+    pub async fn offsets(&mut self, path: &Path, range: Range) -> Option<RangeOffsets> {
+        if !self.files.contains_key(path) {
+            let encoding = self.encoding;
+            let index = tokio::fs::read_to_string(path).await.ok().map(|text| LineIndex::with_encoding(text, encoding));
+            self.files.insert(path.to_path_buf(), index);
+        }
+        self.files.get(path)?.as_ref()?.range_offsets(range)
+    }
+}
+
+/// Char index into `line` of the encoded column `character` (the line's
+/// length when past its end), for slicing words out of a line's chars
+pub fn char_column(line: &str, character: u32, encoding: PositionEncoding) -> usize {
+    let mut units = 0;
+    for (column, ch) in line.chars().enumerate() {
+        if units >= character {
+            return column;
+        }
+        units += encoding.units(ch);
+    }
+    line.chars().count()
+}
+
+/// Range from the `line`/`character`/`end_line`/`end_character` fields tools report
+pub fn range(line: u32, character: u32, end_line: u32, end_character: u32) -> Range {
+    Range::new(Position::new(line, character), Position::new(end_line, end_character))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_utf16_positions() {
+        // "é" is 2 bytes / 1 unit, "🦀" is 4 bytes / 2 units
+        let index = LineIndex::new("fn main() {}\nlet café = \"🦀\"; x\r\nend".to_string());
+        assert_eq!(index.resolve(Position::new(0, 3)), Some((3, 3)));
+        let second = 13;
+        assert_eq!(index.resolve(Position::new(1, 8)), Some((second + 9, 8)));
+        // x sits after the crab: 17 units, 16 chars, 20 bytes in
+        assert_eq!(index.resolve(Position::new(1, 17)), Some((second + 20, 16)));
+        // Past the end of a CRLF line: before the \r
+        assert_eq!(index.resolve(Position::new(1, 99)), Some((second + 21, 17)));
+        assert_eq!(index.resolve(Position::new(2, 3)), Some((second + 23 + 3, 3)));
+        assert_eq!(index.resolve(Position::new(3, 0)), None);
+
+        let offsets = index.range_offsets(range(1, 4, 1, 8)).unwrap();
+        assert_eq!(offsets, RangeOffsets { start_byte: second + 4, end_byte: second + 9, start_char: 4, end_char: 8 });
+        assert_eq!(&index.text[offsets.start_byte..offsets.end_byte], "café");
+    }
+
+    #[test]
+    fn test_resolve_utf8_and_utf32_positions() {
+        // "🦀" is 4 bytes / 2 UTF-16 units / 1 char
+        let text = "let s = \"🦀\"; let crab = s;".to_string();
+        let crab_bytes = text.find("crab").unwrap();
+        let utf8 = LineIndex::with_encoding(text.clone(), PositionEncoding::Utf8);
+        assert_eq!(utf8.resolve(Position::new(0, crab_bytes as u32)), Some((crab_bytes, 17)));
+        let utf32 = LineIndex::with_encoding(text.clone(), PositionEncoding::Utf32);
+        assert_eq!(utf32.resolve(Position::new(0, 17)), Some((crab_bytes, 17)));
+        // The same column read as UTF-16 lands two chars further
+        assert_eq!(LineIndex::new(text.clone()).resolve(Position::new(0, crab_bytes as u32)).map(|(_, c)| c), Some(19));
+
+        assert_eq!(char_column(&text, crab_bytes as u32, PositionEncoding::Utf8), 17);
+        assert_eq!(char_column(&text, 18, PositionEncoding::Utf16), 17);
+        assert_eq!(char_column(&text, 999, PositionEncoding::Utf8), text.chars().count());
+    }
+
+    #[tokio::test]
+    async fn test_resolver_reads_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("lib.rs");
+        std::fs::write(&file, "// ü\npub fn ünïcode() {}\n").unwrap();
+
+        let mut resolver = OffsetResolver::default();
+        let offsets = resolver.offsets(&file, range(1, 7, 1, 14)).await.unwrap();
+        assert_eq!((offsets.start_byte, offsets.end_byte), (13, 22));
+        assert!(resolver.offsets(&dir.path().join("missing.rs"), range(0, 0, 0, 1)).await.is_none());
+    }
+}
@@ -0,0 +1,252 @@
+//! ✏️ LSP Prepare Rename Tool - Check a position is renameable before renaming
+//!
+//! Asks `textDocument/prepareRename` whether the symbol under the cursor can be
+//! renamed and returns the exact identifier span to replace. Keywords, literals
+//! and symbols from outside the workspace come back as "not renameable" with
+//! the server's reason instead of failing a later rename.
+
+use super::base::{BaseLspTool, LspInput, LspOutput, RangeInfo, get_lsp_manager, require_server_capability};
+use crate::config::Config;
+use crate::error::{EmpathicError, EmpathicResult};
+use crate::lsp::types::{LspError, PositionEncoding};
+use async_trait::async_trait;
+use lsp_types::*;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::PathBuf;
+use url::Url;
+
+/// ✏️ LSP Prepare Rename Tool implementation
+pub struct LspPrepareRenameTool;
+
+/// Input parameters for lsp_prepare_rename tool
+#[derive(Debug, Deserialize)]
+pub struct PrepareRenameInput {
+    file_path: String,
+    project: String,
+    line: u32,
+    character: u32,
+}
+
+impl LspInput for PrepareRenameInput {
+    fn file_path(&self) -> &str {
+        &self.file_path
+    }
+
+    fn project(&self) -> &str {
+        &self.project
+    }
+}
+
+/// Output format for prepare rename results
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PrepareRenameOutput {
+    pub file_path: String,
+    pub project: String,
+    pub position: super::base::Position,
+    pub renameable: bool,
+    /// Span of the identifier a rename would replace
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<RangeInfo>,
+    /// Current text of the identifier (server placeholder when provided)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub placeholder: Option<String>,
+    /// Why the position can't be renamed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl LspOutput for PrepareRenameOutput {
+    fn set_file_path(&mut self, path: String) {
+        self.file_path = path;
+    }
+
+    fn set_project(&mut self, project: String) {
+        self.project = project;
+    }
+}
+
+/// 🔤 Span of the identifier touching `character` (in `encoding` units) on `line`
+///
+/// Used for the `defaultBehavior` response, where the server defers to the
+/// client's own notion of a word.
+fn identifier_range(line_text: &str, line: u32, character: u32, encoding: PositionEncoding) -> Option<(Range, String)> {
+    let is_ident = |ch: char| ch.is_alphanumeric() || ch == '_';
+    let mut column = 0;
+    let chars: Vec<(u32, char)> = line_text.chars()
+        .map(|ch| {
+            let start = column;
+            column += encoding.units(ch);
+            (start, ch)
+        })
+        .collect();
+
+    // The cursor may sit right after the identifier's last character
+    let at = chars.iter().position(|(start, ch)| *start == character && is_ident(*ch))
+        .or_else(|| chars.iter().position(|(start, ch)| start + encoding.units(*ch) == character && is_ident(*ch)))?;
+    let first = chars[..at].iter().rposition(|(_, ch)| !is_ident(*ch)).map_or(0, |i| i + 1);
+    let last = chars[at..].iter().position(|(_, ch)| !is_ident(*ch)).map_or(chars.len(), |i| at + i);
+
+    let word: String = chars[first..last].iter().map(|(_, ch)| ch).collect();
+    let end = chars.get(last).map_or(column, |(start, _)| *start);
+    Some((Range::new(lsp_types::Position::new(line, chars[first].0), lsp_types::Position::new(line, end)), word))
+}
+
+/// 🧩 Normalize the three response shapes into (range, placeholder)
+fn resolve_response(response: PrepareRenameResponse, text: &str, line: u32, character: u32, encoding: PositionEncoding) -> Option<(Range, Option<String>)> {
+    match response {
+        PrepareRenameResponse::Range(range) => Some((range, None)),
+        PrepareRenameResponse::RangeWithPlaceholder { range, placeholder } => Some((range, Some(placeholder))),
+        PrepareRenameResponse::DefaultBehavior { default_behavior: false } => None,
+        PrepareRenameResponse::DefaultBehavior { default_behavior: true } => {
+            let line_text = text.lines().nth(line as usize)?;
+            identifier_range(line_text, line, character, encoding).map(|(range, word)| (range, Some(word)))
+        }
+    }
+}
+
+#[async_trait]
+impl BaseLspTool for LspPrepareRenameTool {
+    type Input = PrepareRenameInput;
+    type Output = PrepareRenameOutput;
+
+    fn name() -> &'static str {
+        "lsp_prepare_rename"
+    }
+
+    fn description() -> &'static str {
+        "✏️ Check whether the symbol at a position can be renamed and get the exact identifier range"
+    }
+
+    fn additional_schema() -> serde_json::Value {
+        json!({
+            "line": {
+                "type": "integer",
+                "minimum": 0,
+                "description": "Line number (0-indexed)"
+            },
+            "character": {
+                "type": "integer",
+                "minimum": 0,
+                "description": "Character position (0-indexed, UTF-16 code units)"
+            }
+        })
+    }
+
+    fn additional_required() -> Vec<&'static str> {
+        vec!["line", "character"]
+    }
+
+    async fn execute_lsp(
+        &self,
+        input: Self::Input,
+        file_path: PathBuf,
+        config: &Config,
+    ) -> EmpathicResult<Self::Output> {
+        let lsp_manager = get_lsp_manager(config)?;
+
+        // Ensure document is open/synced
+        lsp_manager.ensure_document_open(&file_path).await
+            .map_err(|e| EmpathicError::tool_failed(
+                "lsp_prepare_rename",
+                format!("Failed to sync document {}: {}", file_path.display(), e)
+            ))?;
+
+        // Get LSP client
+        let client = lsp_manager.get_client(&file_path).await
+            .map_err(|e| EmpathicError::tool_failed(
+                "lsp_prepare_rename",
+                format!("Failed to get LSP client for {}: {}", file_path.display(), e)
+            ))?;
+
+        require_server_capability(&client, "textDocument/prepareRename", |caps| {
+            matches!(&caps.rename_provider, Some(OneOf::Right(RenameOptions { prepare_provider: Some(true), .. })))
+        }).await?;
+
+        log::info!("✏️ Preparing rename at {}:{}:{}",
+            file_path.display(), input.line, input.character);
+
+        let uri = Url::from_file_path(&file_path)
+            .map_err(|_| EmpathicError::InvalidPath { path: file_path.clone() })?;
+
+        let params = TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier {
+                uri: uri.to_string().parse().unwrap()
+            },
+            position: lsp_types::Position {
+                line: input.line,
+                character: input.character,
+            },
+        };
+
+        let (resolved, reason) = match client.prepare_rename(params).await {
+            Ok(Some(response)) => {
+                let text = tokio::fs::read_to_string(&file_path).await.unwrap_or_default();
+                let resolved = resolve_response(response, &text, input.line, input.character, PositionEncoding::TOOL);
+                let reason = resolved.is_none().then(|| "no identifier at this position".to_string());
+                (resolved, reason)
+            }
+            Ok(None) => (None, Some("the server reports nothing renameable here".to_string())),
+            // 🚫 Servers reject keywords, literals and foreign symbols with an error response
+            Err(LspError::JsonRpcError { message }) => (None, Some(message)),
+            Err(e) => return Err(EmpathicError::tool_failed(
+                "lsp_prepare_rename",
+                format!("Prepare rename request failed for {}:{}:{}: {}",
+                    file_path.display(), input.line, input.character, e)
+            )),
+        };
+
+        Ok(PrepareRenameOutput {
+            file_path: String::new(), // Set by base trait
+            project: String::new(),   // Set by base trait
+            position: super::base::Position::new(input.line, input.character),
+            renameable: resolved.is_some(),
+            range: resolved.as_ref().map(|(range, _)| RangeInfo::from_lsp_range(range)),
+            placeholder: resolved.and_then(|(_, placeholder)| placeholder),
+            reason,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(range: Range) -> (u32, u32, u32) {
+        (range.start.line, range.start.character, range.end.character)
+    }
+
+    #[test]
+    fn test_identifier_range() {
+        let found = |text: &str, character: u32| identifier_range(text, 4, character, PositionEncoding::Utf16)
+            .map(|(range, word)| (span(range), word));
+        assert_eq!(found("let total_sum = 1;", 6), Some(((4, 4, 13), "total_sum".to_string())));
+        // Just past the end still counts
+        assert_eq!(found("let total_sum = 1;", 13), Some(((4, 4, 13), "total_sum".to_string())));
+        assert_eq!(found("let total_sum = 1;", 14), None);
+        // 'é' is one UTF-16 unit, '🦀' two
+        assert_eq!(found("🦀 café", 3), Some(((4, 3, 7), "café".to_string())));
+        // In UTF-8 '🦀' is four units and 'é' two
+        let utf8 = identifier_range("🦀 café", 4, 5, PositionEncoding::Utf8).map(|(range, word)| (span(range), word));
+        assert_eq!(utf8, Some(((4, 5, 10), "café".to_string())));
+    }
+
+    #[test]
+    fn test_resolve_all_response_shapes() {
+        let range = Range::new(lsp_types::Position::new(0, 4), lsp_types::Position::new(0, 9));
+        let text = "let value = 1;";
+
+        assert_eq!(resolve_response(PrepareRenameResponse::Range(range), text, 0, 5, PositionEncoding::Utf16), Some((range, None)));
+        assert_eq!(
+            resolve_response(PrepareRenameResponse::RangeWithPlaceholder { range, placeholder: "value".into() }, text, 0, 5, PositionEncoding::Utf16),
+            Some((range, Some("value".to_string())))
+        );
+        assert_eq!(
+            resolve_response(PrepareRenameResponse::DefaultBehavior { default_behavior: true }, text, 0, 5, PositionEncoding::Utf16),
+            Some((range, Some("value".to_string())))
+        );
+        assert_eq!(resolve_response(PrepareRenameResponse::DefaultBehavior { default_behavior: false }, text, 0, 5, PositionEncoding::Utf16), None);
+        // Default behavior between operators finds no identifier
+        assert_eq!(resolve_response(PrepareRenameResponse::DefaultBehavior { default_behavior: true }, text, 0, 10, PositionEncoding::Utf16), None);
+    }
+}
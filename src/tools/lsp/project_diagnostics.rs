@@ -0,0 +1,307 @@
+//! 🩺 LSP Project Diagnostics Tool - Every problem in a project, grouped by file
+//!
+//! The IDE "Problems" panel: opens the project's Rust sources (skipping
+//! ignored and `target/` files, up to a cap) and gathers their diagnostics.
+//! Servers offering pull diagnostics are asked file by file; otherwise
+//! `publishDiagnostics` notifications are collected until every file has
+//! reported or the wait runs out.
+
+use super::base::get_lsp_manager;
+use super::diagnostics::{DiagnosticInfo, DiagnosticSummary};
+use crate::config::Config;
+use crate::error::{EmpathicError, EmpathicResult};
+use crate::fs::{FileOps, SkipDirs};
+use crate::lsp::client::LspClient;
+use crate::lsp::LspManager;
+use crate::tools::{SchemaBuilder, ToolBuilder};
+use async_trait::async_trait;
+use lsp_types::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Default cap on files opened in one call
+const DEFAULT_MAX_FILES: usize = 200;
+
+/// Default time to wait for pushed diagnostics
+const DEFAULT_WAIT_MS: u64 = 5000;
+
+/// 🩺 LSP Project Diagnostics Tool implementation
+pub struct LspProjectDiagnosticsTool;
+
+#[derive(Deserialize)]
+pub struct ProjectDiagnosticsArgs {
+    project: String,
+    /// Subdirectory of the project to check
+    path: Option<String>,
+    max_files: Option<usize>,
+    wait_ms: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FileDiagnostics {
+    /// Path relative to the project
+    file_path: String,
+    summary: DiagnosticSummary,
+    diagnostics: Vec<DiagnosticInfo>,
+}
+
+#[derive(Serialize)]
+pub struct ProjectDiagnosticsOutput {
+    project: String,
+    /// "pull" (textDocument/diagnostic) or "push" (publishDiagnostics)
+    method: &'static str,
+    /// Files with at least one diagnostic, most errors first
+    files: Vec<FileDiagnostics>,
+    summary: DiagnosticSummary,
+    files_checked: usize,
+    total_files: usize,
+    /// Whether max_files left some sources unchecked
+    truncated: bool,
+}
+
+#[async_trait]
+impl ToolBuilder for LspProjectDiagnosticsTool {
+    type Args = ProjectDiagnosticsArgs;
+    type Output = ProjectDiagnosticsOutput;
+
+    fn name() -> &'static str {
+        "lsp_project_diagnostics"
+    }
+
+    fn description() -> &'static str {
+        "🩺 Get diagnostics for a whole Rust project (or a subdirectory), grouped by file with counts by severity"
+    }
+
+    fn schema() -> serde_json::Value {
+        SchemaBuilder::new()
+            .required_string("project", "Project name (directory containing Cargo.toml)")
+            .optional_string("path", "Only check sources under this subdirectory of the project")
+            .optional_integer("max_files", "Maximum source files to open (default: 200)", Some(1))
+            .optional_integer("wait_ms", "How long to wait for pushed diagnostics (default: 5000)", Some(0))
+            .build()
+    }
+
+    async fn run(args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
+        let project_dir = config.safe_project_path(Some(&args.project))?;
+        if !project_dir.join("Cargo.toml").is_file() {
+            return Err(EmpathicError::LspInitializationFailed {
+                reason: format!("Not a Rust project - Cargo.toml not found in: {}", project_dir.display()),
+            });
+        }
+        let scan_dir = match &args.path {
+            Some(path) => config.validate_path(&project_dir.join(path))?,
+            None => project_dir.clone(),
+        };
+        crate::tools::validate_dir_exists(&scan_dir)?;
+
+        let mut files = source_files(&scan_dir, &config.skip_dirs).await?;
+        let total_files = files.len();
+        files.truncate(args.max_files.unwrap_or(DEFAULT_MAX_FILES));
+        let wait = Duration::from_millis(args.wait_ms.unwrap_or(DEFAULT_WAIT_MS));
+
+        let lsp_manager = get_lsp_manager(config)?;
+        let client = lsp_manager.get_client(&project_dir).await?;
+        // ⏱️ Diagnostics of one file depend on edits to others, so send them all first
+        lsp_manager.flush_pending_changes().await?;
+        let supports_pull = client.capabilities().await
+            .is_some_and(|capabilities| capabilities.diagnostic_provider.is_some());
+
+        log::info!("🩺 Collecting {} diagnostics for {} files in {}",
+            if supports_pull { "pulled" } else { "pushed" }, files.len(), scan_dir.display());
+        let (method, collected) = if supports_pull {
+            ("pull", pull_diagnostics(lsp_manager, &client, &files).await)
+        } else {
+            ("push", push_diagnostics(lsp_manager, &client, &files, &scan_dir, wait).await)
+        };
+
+        let (grouped, summary) = group_by_file(&project_dir, collected);
+        Ok(ProjectDiagnosticsOutput {
+            project: args.project,
+            method,
+            files: grouped,
+            summary,
+            files_checked: files.len(),
+            total_files,
+            truncated: files.len() < total_files,
+        })
+    }
+}
+
+/// 📂 Rust sources under `dir`, honoring ignore rules and skipping `target/`
+async fn source_files(dir: &Path, skip_dirs: &SkipDirs) -> EmpathicResult<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = FileOps::list_files(dir, true, false, Some("*.rs"), false, None, skip_dirs).await?
+        .into_iter()
+        .filter(|file| !file.is_dir)
+        .map(|file| file.path)
+        .filter(|path| !path.strip_prefix(dir).unwrap_or(path).components().any(|c| c.as_os_str() == "target"))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// 📥 Ask for each file's diagnostics (servers with a diagnosticProvider)
+async fn pull_diagnostics(lsp_manager: &LspManager, client: &LspClient, files: &[PathBuf]) -> HashMap<PathBuf, Vec<Diagnostic>> {
+    let mut collected = HashMap::new();
+    for file in files {
+        if let Err(e) = lsp_manager.ensure_document_open(file).await {
+            log::warn!("⚠️ Skipping {}: {}", file.display(), e);
+            continue;
+        }
+        let Ok(uri) = url::Url::from_file_path(file) else {
+            continue;
+        };
+        let params = DocumentDiagnosticParams {
+            text_document: TextDocumentIdentifier { uri: uri.to_string().parse().unwrap() },
+            identifier: None,
+            previous_result_id: None,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+        let items = match client.document_diagnostic(params).await {
+            Ok(DocumentDiagnosticReportResult::Report(DocumentDiagnosticReport::Full(report))) => {
+                report.full_document_diagnostic_report.items
+            }
+            Ok(_) => Vec::new(),
+            Err(e) => {
+                log::warn!("⚠️ Pulling diagnostics for {} failed: {}", file.display(), e);
+                continue;
+            }
+        };
+        collected.insert(file.clone(), items);
+    }
+    collected
+}
+
+/// 📡 Open every file and collect publishDiagnostics for anything under `scan_dir`
+///
+/// A publish replaces earlier ones for the same file. Waiting ends once every
+/// opened file has reported, or after `wait`.
+async fn push_diagnostics(
+    lsp_manager: &LspManager,
+    client: &LspClient,
+    files: &[PathBuf],
+    scan_dir: &Path,
+    wait: Duration,
+) -> HashMap<PathBuf, Vec<Diagnostic>> {
+    // Subscribe first so publishes triggered by didOpen aren't missed
+    let mut notifications = client.subscribe_notifications();
+    let mut pending: HashSet<PathBuf> = HashSet::new();
+    for file in files {
+        match lsp_manager.ensure_document_open(file).await {
+            Ok(()) => {
+                pending.insert(file.clone());
+            }
+            Err(e) => log::warn!("⚠️ Skipping {}: {}", file.display(), e),
+        }
+    }
+
+    let mut collected = HashMap::new();
+    let deadline = tokio::time::Instant::now() + wait;
+    while !pending.is_empty() {
+        let notification = match tokio::time::timeout_at(deadline, notifications.recv()).await {
+            Ok(Ok(notification)) => notification,
+            Ok(Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped))) => {
+                log::warn!("⚠️ Missed {skipped} LSP notifications while collecting diagnostics");
+                continue;
+            }
+            Ok(Err(tokio::sync::broadcast::error::RecvError::Closed)) | Err(_) => break,
+        };
+        if notification.method != "textDocument/publishDiagnostics" {
+            continue;
+        }
+        let Some(params) = notification.params
+            .and_then(|params| serde_json::from_value::<PublishDiagnosticsParams>(params).ok())
+        else {
+            continue;
+        };
+        let Some(path) = url::Url::parse(params.uri.as_str()).ok().and_then(|url| url.to_file_path().ok()) else {
+            continue;
+        };
+        if path.starts_with(scan_dir) {
+            pending.remove(&path);
+            collected.insert(path, params.diagnostics);
+        }
+    }
+    if !pending.is_empty() {
+        log::debug!("📊 {} files sent no diagnostics (likely clean)", pending.len());
+    }
+    collected
+}
+
+/// 🗂️ Files with diagnostics, most errors then warnings first, plus project totals
+fn group_by_file(project_dir: &Path, collected: HashMap<PathBuf, Vec<Diagnostic>>) -> (Vec<FileDiagnostics>, DiagnosticSummary) {
+    let count = |diagnostics: &[Diagnostic], severity: DiagnosticSeverity| {
+        diagnostics.iter().filter(|diagnostic| diagnostic.severity == Some(severity)).count()
+    };
+    let mut files: Vec<(PathBuf, Vec<Diagnostic>)> = collected.into_iter()
+        .filter(|(_, diagnostics)| !diagnostics.is_empty())
+        .collect();
+    files.sort_by(|(a_path, a), (b_path, b)| {
+        let key = |diagnostics: &[Diagnostic]| (count(diagnostics, DiagnosticSeverity::ERROR), count(diagnostics, DiagnosticSeverity::WARNING));
+        key(b).cmp(&key(a)).then_with(|| a_path.cmp(b_path))
+    });
+
+    let mut all = Vec::new();
+    let grouped = files.into_iter()
+        .map(|(path, diagnostics)| {
+            let diagnostics: Vec<DiagnosticInfo> = diagnostics.iter().map(DiagnosticInfo::from_lsp_diagnostic).collect();
+            all.extend(diagnostics.iter().cloned());
+            FileDiagnostics {
+                file_path: path.strip_prefix(project_dir).unwrap_or(&path).to_string_lossy().to_string(),
+                summary: DiagnosticSummary::from_diagnostics(&diagnostics),
+                diagnostics,
+            }
+        })
+        .collect();
+    (grouped, DiagnosticSummary::from_diagnostics(&all))
+}
+
+// 🔧 Implement Tool trait using the builder pattern
+crate::impl_tool_for_builder!(LspProjectDiagnosticsTool);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diagnostic(severity: DiagnosticSeverity) -> Diagnostic {
+        Diagnostic {
+            severity: Some(severity),
+            message: "problem".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_group_by_file_orders_by_errors() {
+        let project = Path::new("/work/app");
+        let collected = HashMap::from([
+            (project.join("src/clean.rs"), vec![]),
+            (project.join("src/warned.rs"), vec![diagnostic(DiagnosticSeverity::WARNING); 3]),
+            (project.join("src/broken.rs"), vec![diagnostic(DiagnosticSeverity::ERROR), diagnostic(DiagnosticSeverity::HINT)]),
+        ]);
+
+        let (files, summary) = group_by_file(project, collected);
+        let paths: Vec<&str> = files.iter().map(|file| file.file_path.as_str()).collect();
+        assert_eq!(paths, vec!["src/broken.rs", "src/warned.rs"]);
+        assert_eq!(files[0].diagnostics.len(), 2);
+
+        let summary = serde_json::to_value(summary).unwrap();
+        assert_eq!(summary["total"], 5);
+        assert_eq!(summary["errors"], 1);
+        assert_eq!(summary["warnings"], 3);
+        assert_eq!(summary["hints"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_source_files_skip_target_and_other_languages() {
+        let dir = tempfile::tempdir().unwrap();
+        for (path, content) in [("src/main.rs", "fn main() {}"), ("target/debug/build/out.rs", ""), ("README.md", "")] {
+            let path = dir.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+        assert_eq!(source_files(dir.path(), &SkipDirs::default()).await.unwrap(), vec![dir.path().join("src/main.rs")]);
+    }
+}
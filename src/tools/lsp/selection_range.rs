@@ -0,0 +1,199 @@
+//! 🎯 LSP Selection Range Tool - Expand-selection hierarchy for positions
+//!
+//! For each position returns the chain of enclosing ranges, innermost first
+//! (identifier → expression → statement → block → item ...)
+
+use super::base::{BaseLspTool, LspInput, LspOutput, Position, RangeInfo, get_lsp_manager, require_server_capability};
+use crate::config::Config;
+use crate::error::{EmpathicError, EmpathicResult};
+use async_trait::async_trait;
+use lsp_types::{SelectionRange, SelectionRangeParams, SelectionRangeProviderCapability, TextDocumentIdentifier};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::PathBuf;
+use url::Url;
+
+/// 🎯 LSP Selection Range Tool implementation
+pub struct LspSelectionRangeTool;
+
+/// Input parameters for lsp_selection_range tool
+#[derive(Debug, Deserialize)]
+pub struct SelectionRangeInput {
+    file_path: String,
+    project: String,
+    positions: Vec<Position>,
+}
+
+impl LspInput for SelectionRangeInput {
+    fn file_path(&self) -> &str {
+        &self.file_path
+    }
+
+    fn project(&self) -> &str {
+        &self.project
+    }
+}
+
+/// Output format for selection range results
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SelectionRangeOutput {
+    pub file_path: String,
+    pub project: String,
+    pub selections: Vec<SelectionHierarchy>,
+}
+
+impl LspOutput for SelectionRangeOutput {
+    fn set_file_path(&mut self, path: String) {
+        self.file_path = path;
+    }
+
+    fn set_project(&mut self, project: String) {
+        self.project = project;
+    }
+}
+
+/// Enclosing ranges for a single requested position, innermost first
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SelectionHierarchy {
+    pub position: Position,
+    pub ranges: Vec<RangeInfo>,
+}
+
+impl SelectionHierarchy {
+    fn from_lsp_selection_range(position: Position, selection: &SelectionRange) -> Self {
+        let mut ranges = vec![RangeInfo::from_lsp_range(&selection.range)];
+        let mut parent = selection.parent.as_deref();
+        while let Some(current) = parent {
+            ranges.push(RangeInfo::from_lsp_range(&current.range));
+            parent = current.parent.as_deref();
+        }
+
+        Self { position, ranges }
+    }
+}
+
+#[async_trait]
+impl BaseLspTool for LspSelectionRangeTool {
+    type Input = SelectionRangeInput;
+    type Output = SelectionRangeOutput;
+
+    fn name() -> &'static str {
+        "lsp_selection_range"
+    }
+
+    fn description() -> &'static str {
+        "🎯 Get the expand-selection hierarchy (expression → statement → block) for positions in a Rust file"
+    }
+
+    fn additional_schema() -> serde_json::Value {
+        json!({
+            "positions": {
+                "type": "array",
+                "minItems": 1,
+                "description": "Positions to expand from (0-indexed line/character)",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "line": { "type": "integer", "minimum": 0 },
+                        "character": { "type": "integer", "minimum": 0 }
+                    },
+                    "required": ["line", "character"]
+                }
+            }
+        })
+    }
+
+    fn additional_required() -> Vec<&'static str> {
+        vec!["positions"]
+    }
+
+    async fn execute_lsp(
+        &self,
+        input: Self::Input,
+        file_path: PathBuf,
+        config: &Config,
+    ) -> EmpathicResult<Self::Output> {
+        if input.positions.is_empty() {
+            return Err(EmpathicError::mcp_parameter("positions", "[]"));
+        }
+
+        let lsp_manager = get_lsp_manager(config)?;
+
+        // Ensure document is open/synced
+        lsp_manager.ensure_document_open(&file_path).await
+            .map_err(|e| EmpathicError::tool_failed(
+                "lsp_selection_range",
+                format!("Failed to sync document {}: {}", file_path.display(), e)
+            ))?;
+
+        // Get LSP client
+        let client = lsp_manager.get_client(&file_path).await
+            .map_err(|e| EmpathicError::tool_failed(
+                "lsp_selection_range",
+                format!("Failed to get LSP client for {}: {}", file_path.display(), e)
+            ))?;
+
+        require_server_capability(&client, "textDocument/selectionRange", |caps| {
+            !matches!(caps.selection_range_provider, None | Some(SelectionRangeProviderCapability::Simple(false)))
+        }).await?;
+
+        log::info!("🎯 Getting selection ranges for {} positions in {}",
+            input.positions.len(), file_path.display());
+
+        let uri = Url::from_file_path(&file_path)
+            .map_err(|_| EmpathicError::InvalidPath { path: file_path.clone() })?;
+
+        let params = SelectionRangeParams {
+            text_document: TextDocumentIdentifier {
+                uri: uri.to_string().parse().unwrap()
+            },
+            positions: input.positions.iter()
+                .map(|p| lsp_types::Position { line: p.line, character: p.character })
+                .collect(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+
+        let selection_result = client.selection_range(params).await
+            .map_err(|e| EmpathicError::tool_failed(
+                "lsp_selection_range",
+                format!("Selection range request failed for {}: {}", file_path.display(), e)
+            ))?;
+
+        // The server answers positionally - one SelectionRange per requested position
+        let selections = input.positions.into_iter()
+            .zip(selection_result.unwrap_or_default().iter())
+            .map(|(position, selection)| SelectionHierarchy::from_lsp_selection_range(position, selection))
+            .collect();
+
+        Ok(SelectionRangeOutput {
+            file_path: String::new(), // Set by base trait
+            project: String::new(),   // Set by base trait
+            selections,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(start: u32, end: u32) -> lsp_types::Range {
+        lsp_types::Range::new(lsp_types::Position::new(0, start), lsp_types::Position::new(0, end))
+    }
+
+    #[test]
+    fn test_hierarchy_flattens_parents_innermost_first() {
+        let selection = SelectionRange {
+            range: range(4, 5),
+            parent: Some(Box::new(SelectionRange {
+                range: range(0, 10),
+                parent: Some(Box::new(SelectionRange { range: range(0, 20), parent: None })),
+            })),
+        };
+
+        let hierarchy = SelectionHierarchy::from_lsp_selection_range(Position::new(0, 4), &selection);
+        let ends: Vec<u32> = hierarchy.ranges.iter().map(|r| r.end_character).collect();
+        assert_eq!(ends, vec![5, 10, 20]);
+    }
+}
@@ -0,0 +1,337 @@
+//! 🎨 LSP Semantic Tokens Tool - Server-side token classification
+//!
+//! Decodes the delta-encoded `textDocument/semanticTokens` array into explicit
+//! tokens using the legend the server advertised at initialization, so callers
+//! get precise types (macro vs function, mutable vs readonly) instead of
+//! guessing from syntax.
+
+use super::base::{BaseLspTool, LspInput, LspOutput, get_lsp_manager, require_server_capability};
+use crate::config::Config;
+use crate::error::{EmpathicError, EmpathicResult};
+use crate::lsp::PositionEncoding;
+use async_trait::async_trait;
+use lsp_types::*;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::PathBuf;
+use url::Url;
+
+/// 🎨 LSP Semantic Tokens Tool implementation
+pub struct LspSemanticTokensTool;
+
+/// Input parameters for lsp_semantic_tokens tool
+#[derive(Debug, Deserialize)]
+pub struct SemanticTokensInput {
+    file_path: String,
+    project: String,
+    /// First line of the range (0-based); whole file when omitted
+    start_line: Option<u32>,
+    /// Last line of the range (0-based, inclusive)
+    end_line: Option<u32>,
+}
+
+impl LspInput for SemanticTokensInput {
+    fn file_path(&self) -> &str {
+        &self.file_path
+    }
+
+    fn project(&self) -> &str {
+        &self.project
+    }
+}
+
+/// Output format for semantic token results
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SemanticTokensOutput {
+    pub file_path: String,
+    pub project: String,
+    /// Whether `textDocument/semanticTokens/range` was used
+    pub range: bool,
+    pub tokens: Vec<SemanticTokenInfo>,
+    pub total_tokens: usize,
+}
+
+impl LspOutput for SemanticTokensOutput {
+    fn set_file_path(&mut self, path: String) {
+        self.file_path = path;
+    }
+
+    fn set_project(&mut self, project: String) {
+        self.project = project;
+    }
+}
+
+/// Single classified token (0-based line, start and length in the server's position encoding)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SemanticTokenInfo {
+    pub line: u32,
+    pub start: u32,
+    pub length: u32,
+    /// Token type from the legend, e.g. "function", "macro", "variable"
+    #[serde(rename = "type")]
+    pub token_type: String,
+    /// Modifier names from the legend, e.g. ["declaration", "mutable"]
+    pub modifiers: Vec<String>,
+    /// Source text covered by the token
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+/// 🔓 Decode LSP's relative token encoding into absolute positions
+///
+/// Each token's line is relative to the previous token, and its start is
+/// relative to the previous start when both are on the same line. Indexes
+/// missing from the legend decode as "unknown".
+pub fn decode_semantic_tokens(data: &[SemanticToken], legend: &SemanticTokensLegend) -> Vec<SemanticTokenInfo> {
+    let mut line = 0;
+    let mut start = 0;
+    data.iter()
+        .map(|token| {
+            if token.delta_line > 0 {
+                line += token.delta_line;
+                start = token.delta_start;
+            } else {
+                start += token.delta_start;
+            }
+            SemanticTokenInfo {
+                line,
+                start,
+                length: token.length,
+                token_type: legend.token_types.get(token.token_type as usize)
+                    .map(|token_type| token_type.as_str().to_string())
+                    .unwrap_or_else(|| "unknown".to_string()),
+                modifiers: legend.token_modifiers.iter()
+                    .enumerate()
+                    .filter(|(bit, _)| *bit < 32 && token.token_modifiers_bitset & (1 << bit) != 0)
+                    .map(|(_, modifier)| modifier.as_str().to_string())
+                    .collect(),
+                text: None,
+            }
+        })
+        .collect()
+}
+
+/// Slice `length` code units starting at column `start` out of `line`
+fn unit_slice(line: &str, start: u32, length: u32, encoding: PositionEncoding) -> Option<String> {
+    let mut column = 0;
+    let mut begin = None;
+    for (offset, ch) in line.char_indices() {
+        if column == start {
+            begin = Some(offset);
+        }
+        if column == start + length {
+            return begin.map(|begin| line[begin..offset].to_string());
+        }
+        column += encoding.units(ch);
+    }
+    match (begin, column == start + length) {
+        (Some(begin), true) => Some(line[begin..].to_string()),
+        _ => None,
+    }
+}
+
+/// The legend, plus whether range and full requests are supported
+fn token_support(capabilities: &ServerCapabilities) -> Option<(&SemanticTokensLegend, bool, bool)> {
+    let options = match capabilities.semantic_tokens_provider.as_ref()? {
+        SemanticTokensServerCapabilities::SemanticTokensOptions(options) => options,
+        SemanticTokensServerCapabilities::SemanticTokensRegistrationOptions(registration) => {
+            &registration.semantic_tokens_options
+        }
+    };
+    let full = !matches!(options.full, None | Some(SemanticTokensFullOptions::Bool(false)));
+    Some((&options.legend, options.range == Some(true), full))
+}
+
+#[async_trait]
+impl BaseLspTool for LspSemanticTokensTool {
+    type Input = SemanticTokensInput;
+    type Output = SemanticTokensOutput;
+
+    fn name() -> &'static str {
+        "lsp_semantic_tokens"
+    }
+
+    fn description() -> &'static str {
+        "🎨 Get semantic token classification (type, modifiers, text) for a Rust file or a line range"
+    }
+
+    fn additional_schema() -> serde_json::Value {
+        json!({
+            "start_line": {
+                "type": "integer",
+                "minimum": 0,
+                "description": "First line of the range (0-based); omit both lines for the whole file"
+            },
+            "end_line": {
+                "type": "integer",
+                "minimum": 0,
+                "description": "Last line of the range (0-based, inclusive)"
+            }
+        })
+    }
+
+    async fn execute_lsp(
+        &self,
+        input: Self::Input,
+        file_path: PathBuf,
+        config: &Config,
+    ) -> EmpathicResult<Self::Output> {
+        let range = match (input.start_line, input.end_line) {
+            (None, None) => None,
+            (start, end) => {
+                let start = start.unwrap_or(0);
+                let end = end.unwrap_or(u32::MAX - 1);
+                if end < start {
+                    return Err(EmpathicError::InvalidArgument {
+                        arg: "end_line".to_string(),
+                        reason: format!("end_line {end} is before start_line {start}"),
+                    });
+                }
+                Some(Range::new(lsp_types::Position::new(start, 0), lsp_types::Position::new(end + 1, 0)))
+            }
+        };
+
+        let lsp_manager = get_lsp_manager(config)?;
+
+        // Ensure document is open/synced
+        lsp_manager.ensure_document_open(&file_path).await
+            .map_err(|e| EmpathicError::tool_failed(
+                "lsp_semantic_tokens",
+                format!("Failed to sync document {}: {}", file_path.display(), e)
+            ))?;
+
+        // Get LSP client
+        let client = lsp_manager.get_client(&file_path).await
+            .map_err(|e| EmpathicError::tool_failed(
+                "lsp_semantic_tokens",
+                format!("Failed to get LSP client for {}: {}", file_path.display(), e)
+            ))?;
+
+        let method = if range.is_some() { "textDocument/semanticTokens/range" } else { "textDocument/semanticTokens/full" };
+        require_server_capability(&client, method, |caps| match token_support(caps) {
+            Some((_, range_supported, full_supported)) => if range.is_some() { range_supported } else { full_supported },
+            None => false,
+        }).await?;
+
+        // The legend is required to name anything
+        let capabilities = client.capabilities().await
+            .ok_or_else(|| EmpathicError::tool_failed("lsp_semantic_tokens", "Language server is not initialized"))?;
+        let legend = token_support(&capabilities)
+            .map(|(legend, _, _)| legend.clone())
+            .ok_or_else(|| EmpathicError::NotSupported {
+                operation: method.to_string(),
+                platform: "the running language server".to_string(),
+            })?;
+
+        log::info!("🎨 Getting semantic tokens for {}", file_path.display());
+
+        let uri = Url::from_file_path(&file_path)
+            .map_err(|_| EmpathicError::InvalidPath { path: file_path.clone() })?;
+        let text_document = TextDocumentIdentifier {
+            uri: uri.to_string().parse().unwrap()
+        };
+
+        let data = match range {
+            Some(range) => {
+                let params = SemanticTokensRangeParams {
+                    text_document,
+                    range,
+                    work_done_progress_params: Default::default(),
+                    partial_result_params: Default::default(),
+                };
+                match client.semantic_tokens_range(params).await {
+                    Ok(Some(SemanticTokensRangeResult::Tokens(tokens))) => tokens.data,
+                    Ok(Some(SemanticTokensRangeResult::Partial(partial))) => partial.data,
+                    Ok(None) => Vec::new(),
+                    Err(e) => return Err(EmpathicError::tool_failed(
+                        "lsp_semantic_tokens",
+                        format!("Semantic tokens request failed for {}: {}", file_path.display(), e)
+                    )),
+                }
+            }
+            None => {
+                let params = SemanticTokensParams {
+                    text_document,
+                    work_done_progress_params: Default::default(),
+                    partial_result_params: Default::default(),
+                };
+                match client.semantic_tokens_full(params).await {
+                    Ok(Some(SemanticTokensResult::Tokens(tokens))) => tokens.data,
+                    Ok(Some(SemanticTokensResult::Partial(partial))) => partial.data,
+                    Ok(None) => Vec::new(),
+                    Err(e) => return Err(EmpathicError::tool_failed(
+                        "lsp_semantic_tokens",
+                        format!("Semantic tokens request failed for {}: {}", file_path.display(), e)
+                    )),
+                }
+            }
+        };
+
+        let mut tokens = decode_semantic_tokens(&data, &legend);
+
+        // Attach source text so callers don't need a second read
+        let encoding = PositionEncoding::TOOL;
+        if let Ok(content) = tokio::fs::read_to_string(&file_path).await {
+            let lines: Vec<&str> = content.lines().collect();
+            for token in &mut tokens {
+                token.text = lines.get(token.line as usize)
+                    .and_then(|line| unit_slice(line, token.start, token.length, encoding));
+            }
+        }
+
+        Ok(SemanticTokensOutput {
+            file_path: String::new(), // Set by base trait
+            project: String::new(),   // Set by base trait
+            range: range.is_some(),
+            total_tokens: tokens.len(),
+            tokens,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(delta_line: u32, delta_start: u32, length: u32, token_type: u32, token_modifiers_bitset: u32) -> SemanticToken {
+        SemanticToken { delta_line, delta_start, length, token_type, token_modifiers_bitset }
+    }
+
+    #[test]
+    fn test_decode_relative_positions_and_legend() {
+        let legend = SemanticTokensLegend {
+            token_types: vec![SemanticTokenType::KEYWORD, SemanticTokenType::FUNCTION, SemanticTokenType::MACRO],
+            token_modifiers: vec![SemanticTokenModifier::DECLARATION, SemanticTokenModifier::new("mutable")],
+        };
+        // fn main() {
+        //     println!("x");
+        let tokens = decode_semantic_tokens(&[
+            token(0, 0, 2, 0, 0),
+            token(0, 3, 4, 1, 0b01),
+            token(1, 4, 7, 2, 0b10),
+            token(0, 9, 1, 7, 0),
+        ], &legend);
+
+        let summary: Vec<(u32, u32, &str, Vec<String>)> = tokens.iter()
+            .map(|t| (t.line, t.start, t.token_type.as_str(), t.modifiers.clone()))
+            .collect();
+        assert_eq!(summary, vec![
+            (0, 0, "keyword", vec![]),
+            (0, 3, "function", vec!["declaration".to_string()]),
+            (1, 4, "macro", vec!["mutable".to_string()]),
+            (1, 13, "unknown", vec![]),
+        ]);
+    }
+
+    #[test]
+    fn test_unit_slice() {
+        let utf16 = PositionEncoding::Utf16;
+        assert_eq!(unit_slice("let café = 1;", 4, 4, utf16).as_deref(), Some("café"));
+        // '🦀' is two UTF-16 units and four UTF-8 ones
+        assert_eq!(unit_slice("// 🦀 crab", 6, 4, utf16).as_deref(), Some("crab"));
+        assert_eq!(unit_slice("// 🦀 crab", 8, 4, PositionEncoding::Utf8).as_deref(), Some("crab"));
+        assert_eq!(unit_slice("let café = 1;", 4, 5, PositionEncoding::Utf8).as_deref(), Some("café"));
+        assert_eq!(unit_slice("x", 0, 1, utf16).as_deref(), Some("x"));
+        assert_eq!(unit_slice("x", 0, 5, utf16), None);
+    }
+}
@@ -0,0 +1,387 @@
+//! 🌳 LSP Type Hierarchy Tool - Supertypes and subtypes of the type at a position
+//!
+//! Runs `textDocument/prepareTypeHierarchy` and then walks
+//! `typeHierarchy/supertypes` or `typeHierarchy/subtypes` from each prepared
+//! item, returning a nested tree. For Rust this surfaces trait/impl
+//! relationships. The walk stops at `max_depth`, and a type reached twice is
+//! listed again but not re-expanded, so cycles and diamonds stay finite.
+
+use super::base::{BaseLspTool, LspInput, LspOutput, get_lsp_manager};
+use crate::config::Config;
+use crate::error::{EmpathicError, EmpathicResult};
+use crate::lsp::types::LspError;
+use async_trait::async_trait;
+use lsp_types::*;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashSet;
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+use url::Url;
+
+/// Levels expanded when max_depth is not given
+const DEFAULT_MAX_DEPTH: usize = 3;
+
+/// Deepest expansion a caller may ask for
+const MAX_DEPTH_LIMIT: usize = 10;
+
+/// Most types listed in one hierarchy, across all levels
+const MAX_ITEMS: usize = 500;
+
+/// 🌳 LSP Type Hierarchy Tool implementation
+pub struct LspTypeHierarchyTool;
+
+/// Which way to walk from the prepared type
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HierarchyDirection {
+    /// Traits a type implements, supertraits of a trait
+    #[default]
+    Supertypes,
+    /// Implementors of a trait, subtraits
+    Subtypes,
+}
+
+/// Input parameters for lsp_type_hierarchy tool
+#[derive(Debug, Deserialize)]
+pub struct TypeHierarchyInput {
+    file_path: String,
+    project: String,
+    line: u32,
+    character: u32,
+    #[serde(default)]
+    direction: HierarchyDirection,
+    max_depth: Option<usize>,
+}
+
+impl LspInput for TypeHierarchyInput {
+    fn file_path(&self) -> &str {
+        &self.file_path
+    }
+
+    fn project(&self) -> &str {
+        &self.project
+    }
+}
+
+/// One type in the hierarchy, with the next level in `children`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypeHierarchyNode {
+    pub name: String,
+    pub kind: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    pub file_path: String,
+    pub line: u32,
+    pub character: u32,
+    pub end_line: u32,
+    pub end_character: u32,
+    /// Supertypes or subtypes of this type, depending on the direction
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<TypeHierarchyNode>,
+    /// Already listed elsewhere in the tree, so not expanded again
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub repeated: bool,
+}
+
+impl TypeHierarchyNode {
+    fn from_item(item: &TypeHierarchyItem) -> Self {
+        Self {
+            name: item.name.clone(),
+            kind: format!("{:?}", item.kind),
+            detail: item.detail.clone(),
+            file_path: Url::parse(item.uri.as_str()).ok().and_then(|u| u.to_file_path().ok())
+                .map_or_else(|| item.uri.to_string(), |path| path.to_string_lossy().to_string()),
+            line: item.selection_range.start.line,
+            character: item.selection_range.start.character,
+            end_line: item.selection_range.end.line,
+            end_character: item.selection_range.end.character,
+            children: Vec::new(),
+            repeated: false,
+        }
+    }
+}
+
+/// Output format for type hierarchy results
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TypeHierarchyOutput {
+    pub file_path: String,
+    pub project: String,
+    pub position: super::base::Position,
+    pub direction: HierarchyDirection,
+    /// Types at the position (usually one), each the root of a tree
+    pub items: Vec<TypeHierarchyNode>,
+    /// Distinct types in the tree
+    pub total_items: usize,
+    /// Expansion stopped at max_depth or the item cap; deeper types may exist
+    pub truncated: bool,
+}
+
+impl LspOutput for TypeHierarchyOutput {
+    fn set_file_path(&mut self, path: String) {
+        self.file_path = path;
+    }
+
+    fn set_project(&mut self, project: String) {
+        self.project = project;
+    }
+}
+
+/// State shared across one walk
+#[derive(Default)]
+struct Walk {
+    /// (uri, selection start) of every type already expanded
+    seen: HashSet<(String, u32, u32)>,
+    truncated: bool,
+}
+
+/// 🌳 Expand `item` up to `remaining` levels, asking `fetch` for each level
+fn expand<'a, F, Fut>(item: TypeHierarchyItem, remaining: usize, fetch: &'a F, walk: &'a mut Walk) -> Pin<Box<dyn Future<Output = EmpathicResult<TypeHierarchyNode>> + Send + 'a>>
+where
+    F: Fn(TypeHierarchyItem) -> Fut + Sync,
+    Fut: Future<Output = EmpathicResult<Vec<TypeHierarchyItem>>> + Send,
+{
+    Box::pin(async move {
+        let mut node = TypeHierarchyNode::from_item(&item);
+        let start = item.selection_range.start;
+        if !walk.seen.insert((item.uri.to_string(), start.line, start.character)) {
+            node.repeated = true;
+            return Ok(node);
+        }
+        if remaining == 0 {
+            walk.truncated = true;
+            return Ok(node);
+        }
+
+        for child in fetch(item).await? {
+            if walk.seen.len() >= MAX_ITEMS {
+                walk.truncated = true;
+                break;
+            }
+            node.children.push(expand(child, remaining - 1, fetch, &mut *walk).await?);
+        }
+        Ok(node)
+    })
+}
+
+/// Map a failed request; servers without type hierarchy answer MethodNotFound
+///
+/// lsp-types' `ServerCapabilities` has no `typeHierarchyProvider` field, so
+/// the capability can't be checked before asking.
+fn request_error(method: &str, file_path: &std::path::Path, error: LspError) -> EmpathicError {
+    match error {
+        LspError::JsonRpcError { message } if message.starts_with("LSP error -32601") => EmpathicError::NotSupported {
+            operation: method.to_string(),
+            platform: "the running language server".to_string(),
+        },
+        e => EmpathicError::tool_failed(
+            "lsp_type_hierarchy",
+            format!("{} request failed for {}: {}", method, file_path.display(), e)
+        ),
+    }
+}
+
+#[async_trait]
+impl BaseLspTool for LspTypeHierarchyTool {
+    type Input = TypeHierarchyInput;
+    type Output = TypeHierarchyOutput;
+
+    fn name() -> &'static str {
+        "lsp_type_hierarchy"
+    }
+
+    fn description() -> &'static str {
+        "🌳 Show the supertypes or subtypes of the type at a position as a nested tree (trait/impl relationships in Rust)"
+    }
+
+    fn additional_schema() -> serde_json::Value {
+        json!({
+            "line": {
+                "type": "integer",
+                "minimum": 0,
+                "description": "Line number (0-indexed)"
+            },
+            "character": {
+                "type": "integer",
+                "minimum": 0,
+                "description": "Character position (0-indexed, UTF-16 code units)"
+            },
+            "direction": {
+                "type": "string",
+                "enum": ["supertypes", "subtypes"],
+                "default": "supertypes",
+                "description": "Walk up to supertypes (implemented traits, supertraits) or down to subtypes (implementors)"
+            },
+            "max_depth": {
+                "type": "integer",
+                "minimum": 1,
+                "maximum": MAX_DEPTH_LIMIT,
+                "description": "Levels to expand below each type at the position (default: 3)"
+            }
+        })
+    }
+
+    fn additional_required() -> Vec<&'static str> {
+        vec!["line", "character"]
+    }
+
+    async fn execute_lsp(
+        &self,
+        input: Self::Input,
+        file_path: PathBuf,
+        config: &Config,
+    ) -> EmpathicResult<Self::Output> {
+        let max_depth = input.max_depth.unwrap_or(DEFAULT_MAX_DEPTH);
+        if !(1..=MAX_DEPTH_LIMIT).contains(&max_depth) {
+            return Err(EmpathicError::mcp_parameter("max_depth", format!("must be between 1 and {}", MAX_DEPTH_LIMIT)));
+        }
+
+        let lsp_manager = get_lsp_manager(config)?;
+
+        // Ensure document is open/synced
+        lsp_manager.ensure_document_open(&file_path).await
+            .map_err(|e| EmpathicError::tool_failed(
+                "lsp_type_hierarchy",
+                format!("Failed to sync document {}: {}", file_path.display(), e)
+            ))?;
+
+        // Get LSP client
+        let client = lsp_manager.get_client(&file_path).await
+            .map_err(|e| EmpathicError::tool_failed(
+                "lsp_type_hierarchy",
+                format!("Failed to get LSP client for {}: {}", file_path.display(), e)
+            ))?;
+
+        log::info!("🌳 Type hierarchy ({:?}) at {}:{}:{}",
+            input.direction, file_path.display(), input.line, input.character);
+
+        let uri = Url::from_file_path(&file_path)
+            .map_err(|_| EmpathicError::InvalidPath { path: file_path.clone() })?;
+
+        let params = TypeHierarchyPrepareParams {
+            text_document_position_params: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier {
+                    uri: uri.to_string().parse().unwrap()
+                },
+                position: lsp_types::Position {
+                    line: input.line,
+                    character: input.character,
+                },
+            },
+            work_done_progress_params: WorkDoneProgressParams::default(),
+        };
+
+        let prepared = client.prepare_type_hierarchy(params).await
+            .map_err(|e| request_error("textDocument/prepareTypeHierarchy", &file_path, e))?
+            .unwrap_or_default();
+
+        let direction = input.direction;
+        let fetch = |item: TypeHierarchyItem| {
+            let client = client.clone();
+            let file_path = file_path.clone();
+            async move {
+                let result = match direction {
+                    HierarchyDirection::Supertypes => client.type_hierarchy_supertypes(TypeHierarchySupertypesParams {
+                        item,
+                        work_done_progress_params: WorkDoneProgressParams::default(),
+                        partial_result_params: PartialResultParams::default(),
+                    }).await.map_err(|e| request_error("typeHierarchy/supertypes", &file_path, e)),
+                    HierarchyDirection::Subtypes => client.type_hierarchy_subtypes(TypeHierarchySubtypesParams {
+                        item,
+                        work_done_progress_params: WorkDoneProgressParams::default(),
+                        partial_result_params: PartialResultParams::default(),
+                    }).await.map_err(|e| request_error("typeHierarchy/subtypes", &file_path, e)),
+                };
+                result.map(Option::unwrap_or_default)
+            }
+        };
+
+        let mut walk = Walk::default();
+        let mut items = Vec::new();
+        for item in prepared {
+            items.push(expand(item, max_depth, &fetch, &mut walk).await?);
+        }
+
+        Ok(TypeHierarchyOutput {
+            file_path: String::new(), // Set by base trait
+            project: String::new(),   // Set by base trait
+            position: super::base::Position::new(input.line, input.character),
+            direction,
+            items,
+            total_items: walk.seen.len(),
+            truncated: walk.truncated,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::str::FromStr;
+
+    fn item(name: &str, line: u32) -> TypeHierarchyItem {
+        let range = Range::new(lsp_types::Position::new(line, 0), lsp_types::Position::new(line, name.len() as u32));
+        TypeHierarchyItem {
+            name: name.to_string(),
+            kind: SymbolKind::INTERFACE,
+            tags: None,
+            detail: None,
+            uri: Uri::from_str("file:///src/lib.rs").unwrap(),
+            range,
+            selection_range: range,
+            data: None,
+        }
+    }
+
+    fn names(node: &TypeHierarchyNode) -> Vec<&str> {
+        node.children.iter().map(|child| child.name.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_expand_dedupes_and_caps_depth() {
+        // Copy: Clone, Eq: PartialEq, Ord: Eq + PartialOrd, PartialOrd: PartialEq, Clone: Copy (a cycle)
+        let graph: HashMap<&str, Vec<TypeHierarchyItem>> = HashMap::from([
+            ("Ord", vec![item("Eq", 2), item("PartialOrd", 3)]),
+            ("Eq", vec![item("PartialEq", 4)]),
+            ("PartialOrd", vec![item("PartialEq", 4)]),
+            ("PartialEq", vec![]),
+            ("Copy", vec![item("Clone", 6)]),
+            ("Clone", vec![item("Copy", 5)]),
+        ]);
+        let fetch = |parent: TypeHierarchyItem| {
+            let children = graph[parent.name.as_str()].clone();
+            async move { Ok(children) }
+        };
+
+        let mut walk = Walk::default();
+        let ord = expand(item("Ord", 1), 5, &fetch, &mut walk).await.unwrap();
+        assert_eq!(names(&ord), vec!["Eq", "PartialOrd"]);
+        assert!(!ord.children[0].children[0].repeated);
+        // PartialEq is reached twice but expanded once
+        assert!(ord.children[1].children[0].repeated);
+        assert_eq!(walk.seen.len(), 4);
+        assert!(!walk.truncated);
+
+        let mut walk = Walk::default();
+        let copy = expand(item("Copy", 5), 5, &fetch, &mut walk).await.unwrap();
+        assert!(copy.children[0].children[0].repeated);
+
+        let mut walk = Walk::default();
+        let ord = expand(item("Ord", 1), 1, &fetch, &mut walk).await.unwrap();
+        assert_eq!(names(&ord), vec!["Eq", "PartialOrd"]);
+        assert!(ord.children[0].children.is_empty());
+        assert!(walk.truncated);
+    }
+
+    #[test]
+    fn test_method_not_found_is_not_supported() {
+        let path = std::path::Path::new("src/lib.rs");
+        let missing = LspError::JsonRpcError { message: "LSP error -32601: unknown request".to_string() };
+        assert!(matches!(request_error("typeHierarchy/subtypes", path, missing), EmpathicError::NotSupported { .. }));
+
+        let failed = LspError::JsonRpcError { message: "LSP error -32603: internal".to_string() };
+        assert!(matches!(request_error("typeHierarchy/subtypes", path, failed), EmpathicError::ToolExecutionFailed { .. }));
+    }
+}
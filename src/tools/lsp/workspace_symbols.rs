@@ -1,8 +1,14 @@
 //! 🔍 LSP Workspace Symbols Tool - Search for symbols across the entire project
 //!
-//! Provides project-wide symbol search capabilities for Rust workspaces
+//! Provides project-wide symbol search capabilities for Rust workspaces.
+//! When the caller is listening for progress, symbols the server reports as
+//! partial results are forwarded as `notifications/progress` while the search
+//! runs; the response still carries the full, consolidated list.
 
 use crate::error::EmpathicResult;
+use crate::lsp::PositionEncoding;
+use crate::mcp::progress::ProgressReporter;
+use super::offsets::{self, OffsetResolver, RangeOffsets};
 use async_trait::async_trait;
 use lsp_types::*;
 use serde::{Deserialize, Serialize};
@@ -17,6 +23,8 @@ pub struct LspWorkspaceSymbolsTool;
 struct WorkspaceSymbolsInput {
     query: String,
     project: String,
+    limit: Option<usize>,
+    cursor: Option<String>,
 }
 
 /// Output format for workspace symbols
@@ -25,7 +33,26 @@ struct WorkspaceSymbolsOutput {
     query: String,
     project: String,
     symbols: Vec<WorkspaceSymbolInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_cursor: Option<String>,
     summary: WorkspaceSymbolsSummary,
+    /// Partial result batches forwarded as progress before the final answer
+    #[serde(skip_serializing_if = "is_zero")]
+    streamed_batches: usize,
+}
+
+fn is_zero(count: &usize) -> bool {
+    *count == 0
+}
+
+/// Progress message for one partial batch: running total and the new names
+fn partial_message(batch: &[SymbolInformation], total: usize) -> String {
+    const SHOWN: usize = 10;
+    let mut names: Vec<&str> = batch.iter().take(SHOWN).map(|symbol| symbol.name.as_str()).collect();
+    if batch.len() > SHOWN {
+        names.push("…");
+    }
+    format!("🔍 {total} symbols so far (+{}): {}", batch.len(), names.join(", "))
 }
 
 /// Simplified workspace symbol information for MCP output
@@ -46,6 +73,9 @@ struct LocationInfo {
     character: u32,
     end_line: u32,
     end_character: u32,
+    /// Byte offsets and char columns of the range, for editing tools
+    #[serde(flatten)]
+    offsets: Option<RangeOffsets>,
 }
 
 /// Summary statistics for workspace symbols
@@ -71,6 +101,7 @@ impl WorkspaceSymbolInfo {
                 character: symbol.location.range.start.character,
                 end_line: symbol.location.range.end.line,
                 end_character: symbol.location.range.end.character,
+                offsets: None,
             },
             container_name: symbol.container_name.clone(),
             detail: None,
@@ -118,6 +149,15 @@ impl crate::tools::Tool for LspWorkspaceSymbolsTool {
                 "project": {
                     "type": "string",
                     "description": "Project name for path resolution"
+                },
+                "limit": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Maximum symbols per page; a next_cursor is returned when more remain"
+                },
+                "cursor": {
+                    "type": "string",
+                    "description": "next_cursor from a previous call to fetch the following page"
                 }
             },
             "required": ["query", "project"],
@@ -156,6 +196,8 @@ impl crate::tools::Tool for LspWorkspaceSymbolsTool {
             })?;
 
         let client = lsp_manager.get_client(&working_dir).await?;
+        // ⏱️ The query spans every file, so no debounced edit may still be pending
+        lsp_manager.flush_pending_changes().await?;
 
         // Create WorkspaceSymbolParams
         let params = WorkspaceSymbolParams {
@@ -164,8 +206,19 @@ impl crate::tools::Tool for LspWorkspaceSymbolsTool {
             partial_result_params: PartialResultParams::default(),
         };
 
-        // Call LSP server
-        let response = client.workspace_symbols(params).await?;
+        // Call LSP server, streaming partial results when someone is listening
+        let mut streamed_batches = 0;
+        let response = match ProgressReporter::current() {
+            Some(reporter) => {
+                let mut total = 0;
+                client.workspace_symbols_streaming(params, |batch| {
+                    total += batch.len();
+                    streamed_batches += 1;
+                    reporter.report(&partial_message(batch, total));
+                }).await?
+            }
+            None => client.workspace_symbols(params).await?,
+        };
 
         // Convert response to our format
         let symbols: Vec<WorkspaceSymbolInfo> = match response {
@@ -177,16 +230,53 @@ impl crate::tools::Tool for LspWorkspaceSymbolsTool {
             None => Vec::new(),
         };
 
+        // 📊 Summary covers every match; only the symbols list is paged
         let summary = WorkspaceSymbolsSummary::from_symbols(&symbols, &input.query, symbols.len());
+        let (mut symbols, next_cursor) = crate::mcp::protocol::paginate(symbols, input.cursor.as_deref(), input.limit)?;
+
+        // 📏 Offsets only for the returned page: each file is read once
+        let mut resolver = OffsetResolver::new(PositionEncoding::TOOL);
+        for symbol in &mut symbols {
+            let location = &mut symbol.location;
+            let range = offsets::range(location.line, location.character, location.end_line, location.end_character);
+            location.offsets = resolver.offsets(std::path::Path::new(&location.file_path), range).await;
+        }
 
         let output = WorkspaceSymbolsOutput {
             query: input.query.clone(),
             project: input.project.clone(),
             symbols,
+            next_cursor,
             summary,
+            streamed_batches,
         };
 
         crate::tools::format_json_response(&output)
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_message_lists_new_names() {
+        let symbol = |name: &str| SymbolInformation {
+            name: name.to_string(),
+            kind: SymbolKind::FUNCTION,
+            tags: None,
+            #[allow(deprecated)]
+            deprecated: None,
+            location: Location {
+                uri: "file:///tmp/lib.rs".parse().unwrap(),
+                range: Range::default(),
+            },
+            container_name: None,
+        };
+        assert_eq!(partial_message(&[symbol("parse"), symbol("render")], 5), "🔍 5 symbols so far (+2): parse, render");
+
+        let many: Vec<SymbolInformation> = (0..12).map(|i| symbol(&format!("f{i}"))).collect();
+        assert!(partial_message(&many, 12).ends_with("f9, …"));
+    }
+}
@@ -1,12 +1,17 @@
 //! 🔨 Make Tool - Clean ToolBuilder implementation
 
 use async_trait::async_trait;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 use crate::tools::{ToolBuilder, SchemaBuilder};
 use crate::config::Config;
-use crate::error::EmpathicResult;
-use super::executor_utils::{execute_command, CommandOutput};
+use crate::error::{EmpathicError, EmpathicResult};
+use super::build_targets::{find_makefile, mtimes, parse_makefile, MakeTargets, MtimeCache};
+use super::executor_utils::{execute_command_with, resolve_working_dir, CommandOptions, CommandOutput};
+
+/// 🗄️ Parsed Makefiles, reused until the file changes
+static MAKE_TARGETS: MtimeCache<MakeTargets> = MtimeCache::new();
 
 /// 🔨 Make Tool using modern ToolBuilder pattern
 pub struct MakeTool;
@@ -15,10 +20,24 @@ pub struct MakeTool;
 pub struct MakeArgs {
     #[serde(default)]
     args: Vec<String>,
+    /// List the Makefile's targets instead of running make
+    #[serde(default)]
+    list_targets: bool,
+    #[serde(default)]
+    stream: bool,
+    cwd: Option<String>,
+    #[serde(default)]
+    env: HashMap<String, String>,
     project: Option<String>,
 }
 
-pub type MakeOutput = CommandOutput;
+/// 🔨 Command output, or the target listing in `list_targets` mode
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum MakeOutput {
+    Run(CommandOutput),
+    Targets(MakeTargets),
+}
 
 #[async_trait]
 impl ToolBuilder for MakeTool {
@@ -36,14 +55,47 @@ impl ToolBuilder for MakeTool {
     fn schema() -> serde_json::Value {
         SchemaBuilder::new()
             .optional_array("args", "Make targets and arguments (e.g., ['build'], ['clean', 'install'])")
+            .optional_bool("list_targets", "List targets (with .PHONY flag, line and '## description') parsed from the Makefile instead of running make", Some(false))
+            .optional_bool("stream", "Send stdout/stderr lines as notifications/progress while the command runs (full output is still returned)", Some(false))
+            .optional_string("cwd", "Subdirectory to run in, relative to the project (must stay inside the root)")
+            .optional_string_map("env", "Extra environment variables, e.g. {\"RUST_LOG\": \"debug\"}")
             .optional_string("project", "Project name for execution directory")
             .build()
     }
     
     async fn run(args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
-        execute_command("make", args.args, args.project.as_deref(), config).await
+        if args.list_targets {
+            let working_dir = resolve_working_dir(config, args.project.as_deref(), args.cwd.as_deref())?;
+            return list_targets(&working_dir).map(MakeOutput::Targets);
+        }
+        
+        let options = CommandOptions { cwd: args.cwd, env: args.env, stream: args.stream };
+        let output = execute_command_with("make", args.args, args.project.as_deref(), config, &options).await?;
+        Ok(MakeOutput::Run(output))
     }
 }
 
+/// 🎯 Targets of the Makefile in `dir`, from cache when unchanged
+fn list_targets(dir: &std::path::Path) -> EmpathicResult<MakeTargets> {
+    let makefile = find_makefile(dir)
+        .ok_or_else(|| EmpathicError::FileNotFound { path: dir.join("Makefile") })?;
+    let file_name = makefile.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let stamp = mtimes(dir, &[file_name.as_str()]);
+    if let Some(targets) = MAKE_TARGETS.get(&makefile, &stamp) {
+        return Ok(targets);
+    }
+    
+    let content = std::fs::read_to_string(&makefile)
+        .map_err(|e| EmpathicError::FileOperationFailed {
+            operation: "read".to_string(),
+            path: makefile.clone(),
+            reason: e.to_string(),
+        })?;
+    let (default_target, targets) = parse_makefile(&content);
+    let targets = MakeTargets { makefile: file_name, default_target, targets };
+    MAKE_TARGETS.insert(makefile, stamp, targets.clone());
+    Ok(targets)
+}
+
 // 🔧 Implement Tool trait using the builder pattern
 crate::impl_tool_for_builder!(MakeTool);
@@ -46,6 +46,7 @@ impl ToolBuilder for MkdirTool {
     async fn run(args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
         let working_dir = config.project_path(args.project.as_deref());
         let create_path = working_dir.join(&args.path);
+        config.validate_path(&create_path)?;
         
         tokio::fs::create_dir_all(&create_path).await
             .map_err(|e| EmpathicError::DirectoryCreationFailed {
@@ -5,23 +5,42 @@ use crate::config::Config;
 use crate::error::EmpathicResult;
 
 pub mod tool_base;
+pub mod arg_validation;
 pub mod env;
 pub mod read_file;
 pub mod write_file;
+pub mod insert_at_line;
 pub mod list_files;
+pub mod file_info;
 pub mod delete_file;
+pub mod copy_file;
+pub mod move_file;
+pub mod trash;
 pub mod replace;
+pub mod search_files;
+pub mod search_symbols;
 pub mod str_replace;
 pub mod mkdir;
 pub mod symlink;
+pub mod watch;
+pub mod tool_queue;
+pub mod ping;
 pub mod executor_utils;
+pub mod diff_utils;
 pub mod shell;
 pub mod bash_tool;
 pub mod git;
+pub mod git_history;
+pub mod git_preview;
 pub mod cargo;
+pub mod cargo_output;
+pub mod cargo_toolchain;
+pub mod build_targets;
 pub mod make;
 pub mod gradle;
 pub mod npm;
+pub mod test_reports;
+pub mod run_tests;
 pub mod lsp;
 
 /// Tool trait for MCP tools 🔧
@@ -37,36 +56,101 @@ pub trait Tool: Send + Sync {
 pub use tool_base::{
     ToolBuilder, SchemaBuilder,
     require_string, optional_string, optional_int, bool_param_or,
-    default_fs_path, resolve_file_path, validate_file_exists, validate_dir_exists, validate_file_extension,
-    format_text_response, format_json_response
+    default_fs_path, resolve_file_path, validate_file_exists, validate_dir_exists, validate_file_extension, parse_encoding, sync_lsp_document,
+    format_text_response, format_json_response, truncate_response, narrowing_hint
 };
 
+/// ✍️ Tools that modify files or run arbitrary commands - disabled in read-only mode
+pub const MUTATING_TOOLS: &[&str] = &[
+    "write_file",
+    "insert_at_line",
+    "delete_file",
+    "restore_file",
+    "purge_trash",
+    "copy_file",
+    "move_file",
+    "replace",
+    "str_replace",
+    "mkdir",
+    "symlink",
+    "shell",
+    "bash_tool",
+    "git",
+    "cargo",
+    "make",
+    "gradle",
+    "npm",
+    "run_tests",
+    "lsp_execute_command",
+    // Its save action writes <root>/.empathic/lsp_state.json
+    "lsp_document_state",
+];
+
+/// 🔒 Registered tools allowed by the config's tool policy
+pub fn get_enabled_tools(config: &Config) -> Vec<Box<dyn Tool>> {
+    get_all_tools().into_iter()
+        .filter(|tool| config.tool_policy.allows(tool.name()))
+        .collect()
+}
+
 /// Get all registered tools
 pub fn get_all_tools() -> Vec<Box<dyn Tool>> {
     vec![
         Box::new(env::EnvTool),
         Box::new(read_file::ReadFileTool),
+        Box::new(read_file::ReadFileChunkTool),
         Box::new(write_file::WriteFileTool),
+        Box::new(insert_at_line::InsertAtLineTool),
         Box::new(list_files::ListFilesTool),
+        Box::new(file_info::FileInfoTool),
         Box::new(delete_file::DeleteFileTool),
+        Box::new(trash::RestoreFileTool),
+        Box::new(trash::PurgeTrashTool),
+        Box::new(copy_file::CopyFileTool),
+        Box::new(move_file::MoveFileTool),
         Box::new(replace::ReplaceTool),
+        Box::new(search_files::SearchFilesTool),
+        Box::new(search_symbols::SearchSymbolsTool),
         Box::new(str_replace::StrReplaceTool),
         Box::new(mkdir::MkdirTool),
         Box::new(symlink::SymlinkTool),
+        Box::new(watch::WatchPathsTool),
+        Box::new(watch::UnwatchPathsTool),
+        Box::new(tool_queue::ToolQueueTool),
+        Box::new(ping::PingTool),
         Box::new(shell::ShellTool),
         Box::new(bash_tool::BashTool),
         Box::new(git::GitTool),
+        Box::new(git_history::GitDiffTool),
+        Box::new(git_history::GitLogTool),
+        Box::new(git_history::GitBlameTool),
         Box::new(cargo::CargoTool),
         Box::new(make::MakeTool),
         Box::new(gradle::GradleTool),
         Box::new(npm::NpmTool),
+        Box::new(run_tests::RunTestsTool),
         // 🧠 LSP Tools
         Box::new(lsp::LspDiagnosticsTool),
+        Box::new(lsp::LspProjectDiagnosticsTool),
         Box::new(lsp::LspHoverTool),
+        Box::new(lsp::LspHoverBatchTool),
         Box::new(lsp::LspCompletionTool),
         Box::new(lsp::LspGotoDefinitionTool),
         Box::new(lsp::LspFindReferencesTool),
+        Box::new(lsp::LspTypeHierarchyTool),
         Box::new(lsp::LspDocumentSymbolsTool),
+        Box::new(lsp::LspDocumentHighlightTool),
+        Box::new(lsp::LspDocumentStateTool),
+        Box::new(lsp::LspFoldingRangeTool),
+        Box::new(lsp::LspSelectionRangeTool),
+        Box::new(lsp::LspPrepareRenameTool),
+        Box::new(lsp::LspSemanticTokensTool),
+        Box::new(lsp::LspCodeLensTool),
+        Box::new(lsp::LspCapabilitiesTool),
         Box::new(lsp::LspWorkspaceSymbolsTool),
+        Box::new(lsp::LspExecuteCommandTool),
+        Box::new(lsp::LspColdStartTool),
+        Box::new(lsp::CacheStatsTool),
+        Box::new(lsp::LspMetricsTool),
     ]
 }
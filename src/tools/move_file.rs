@@ -0,0 +1,117 @@
+//! 🚚 Move File Tool - Rename files and directories, across filesystems too
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::tools::{ToolBuilder, SchemaBuilder};
+use crate::config::Config;
+use crate::fs::{FileOps, MoveMethod};
+use crate::error::{EmpathicResult, EmpathicError};
+
+/// 🚚 Move File Tool using modern ToolBuilder pattern
+pub struct MoveFileTool;
+
+#[derive(Deserialize)]
+pub struct MoveFileArgs {
+    from: String,
+    to: String,
+    #[serde(default)]
+    overwrite: bool,
+    project: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct MoveFileOutput {
+    success: bool,
+    from: String,
+    to: String,
+    was_directory: bool,
+    overwritten: bool,
+    /// "rename", or "copy_delete" when the move crossed filesystems
+    method: &'static str,
+    /// Bytes copied by a cross-device move (0 for a rename)
+    bytes_moved: u64,
+}
+
+#[async_trait]
+impl ToolBuilder for MoveFileTool {
+    type Args = MoveFileArgs;
+    type Output = MoveFileOutput;
+
+    fn name() -> &'static str {
+        "move_file"
+    }
+
+    fn description() -> &'static str {
+        "🚚 Move or rename a file or directory; falls back to copy-and-delete across filesystems"
+    }
+
+    fn schema() -> serde_json::Value {
+        SchemaBuilder::new()
+            .required_string("from", "Source file or directory path")
+            .required_string("to", "Destination path (parent directories are created)")
+            .optional_bool("overwrite", "Replace the destination if it already exists", Some(false))
+            .optional_string("project", "Project name for path resolution")
+            .build()
+    }
+
+    async fn run(args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
+        let working_dir = config.safe_project_path(args.project.as_deref())?;
+        let from_path = working_dir.join(&args.from);
+        let to_path = working_dir.join(&args.to);
+        config.validate_path(&from_path)?;
+        config.validate_path(&to_path)?;
+
+        let metadata = tokio::fs::symlink_metadata(&from_path).await
+            .map_err(|_| EmpathicError::FileNotFound { path: from_path.clone() })?;
+
+        let overwritten = tokio::fs::symlink_metadata(&to_path).await.is_ok();
+        if overwritten && !args.overwrite {
+            return Err(EmpathicError::FileOperationFailed {
+                operation: "move".to_string(),
+                path: to_path,
+                reason: "destination already exists (set overwrite: true)".to_string(),
+            });
+        }
+
+        // 🛡️ An overwritten destination survives until the move has succeeded
+        let method = if overwritten {
+            FileOps::move_replacing(&from_path, &to_path).await?
+        } else {
+            FileOps::move_file(&from_path, &to_path).await?
+        };
+
+        // 🗑️ Neither the old nor the new location may serve stale LSP responses
+        if let Some(lsp_manager) = config.lsp_manager() {
+            for path in [&from_path, &to_path] {
+                if metadata.is_dir() {
+                    lsp_manager.invalidate_project_cache(path).await;
+                } else {
+                    lsp_manager.invalidate_file_cache(path).await;
+                }
+            }
+            // 🔄 Open documents follow the move: didClose the old path, didOpen the new one
+            if let Err(e) = lsp_manager.rename_documents(&from_path, &to_path).await {
+                log::warn!("⚠️ LSP rename sync failed for {}: {}", from_path.display(), e);
+            }
+        }
+
+        let (method, bytes_moved) = match method {
+            MoveMethod::Rename => ("rename", 0),
+            MoveMethod::CopyDelete { bytes } => ("copy_delete", bytes),
+        };
+
+        Ok(MoveFileOutput {
+            success: true,
+            from: from_path.to_string_lossy().to_string(),
+            to: to_path.to_string_lossy().to_string(),
+            was_directory: metadata.is_dir(),
+            overwritten,
+            method,
+            bytes_moved,
+        })
+    }
+}
+
+// 🔧 Implement Tool trait using the builder pattern
+crate::impl_tool_for_builder!(MoveFileTool);
@@ -1,13 +1,19 @@
 //! 📦 NPM Tool - Clean ToolBuilder implementation
+//!
+//! Despite the name, the tool dispatches to whichever JS package manager the
+//! project uses: the nearest lockfile picks yarn, pnpm, bun or npm, and common
+//! npm subcommands are translated to the chosen manager's spelling.
 
 use async_trait::async_trait;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
 
-use crate::error::EmpathicResult;
+use crate::error::{EmpathicError, EmpathicResult};
 
 use crate::tools::{ToolBuilder, SchemaBuilder};
 use crate::config::Config;
-use super::executor_utils::{execute_command, CommandOutput};
+use super::executor_utils::{execute_command_with, resolve_working_dir, CommandOptions, CommandOutput};
 
 /// 📦 NPM Tool using modern ToolBuilder pattern
 pub struct NpmTool;
@@ -16,10 +22,110 @@ pub struct NpmTool;
 pub struct NpmArgs {
     #[serde(default)]
     args: Vec<String>,
+    /// Force a package manager instead of detecting it from lockfiles
+    manager: Option<String>,
+    #[serde(default)]
+    stream: bool,
+    cwd: Option<String>,
+    #[serde(default)]
+    env: HashMap<String, String>,
     project: Option<String>,
 }
 
-pub type NpmOutput = CommandOutput;
+/// 📦 Command output plus the package manager that ran it
+#[derive(Serialize)]
+pub struct NpmOutput {
+    manager: &'static str,
+    /// Lockfile that selected the manager (None for overrides and the npm fallback)
+    detected_from: Option<String>,
+    #[serde(flatten)]
+    output: CommandOutput,
+}
+
+/// 🧶 Supported JS package managers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageManager {
+    Npm,
+    Yarn,
+    Pnpm,
+    Bun,
+}
+
+/// Lockfiles in priority order for a single directory
+const LOCKFILES: &[(&str, PackageManager)] = &[
+    ("bun.lockb", PackageManager::Bun),
+    ("bun.lock", PackageManager::Bun),
+    ("pnpm-lock.yaml", PackageManager::Pnpm),
+    ("yarn.lock", PackageManager::Yarn),
+    ("package-lock.json", PackageManager::Npm),
+];
+
+impl PackageManager {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "npm" => Some(Self::Npm),
+            "yarn" => Some(Self::Yarn),
+            "pnpm" => Some(Self::Pnpm),
+            "bun" => Some(Self::Bun),
+            _ => None,
+        }
+    }
+
+    /// Binary name, which doubles as the reported manager name
+    pub fn binary(&self) -> &'static str {
+        match self {
+            Self::Npm => "npm",
+            Self::Yarn => "yarn",
+            Self::Pnpm => "pnpm",
+            Self::Bun => "bun",
+        }
+    }
+
+    /// 🔍 Find the nearest lockfile from `dir` upwards, stopping at `root`
+    pub fn detect(dir: &Path, root: &Path) -> Option<(Self, String)> {
+        let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+        let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+        dir.ancestors()
+            .take_while(|ancestor| ancestor.starts_with(&root))
+            .find_map(|ancestor| LOCKFILES.iter()
+                .find(|(lockfile, _)| ancestor.join(lockfile).is_file())
+                .map(|(lockfile, manager)| (*manager, lockfile.to_string())))
+    }
+
+    /// 🔀 Translate npm-style arguments to this manager
+    ///
+    /// Only the subcommands that differ are rewritten (`install <pkg>`, `ci`,
+    /// `uninstall`); `run`, `test` and everything else pass through unchanged.
+    pub fn translate_args(&self, args: Vec<String>) -> Vec<String> {
+        if *self == Self::Npm {
+            return args;
+        }
+        let Some((subcommand, rest)) = args.split_first() else {
+            return args;
+        };
+
+        let rest: Vec<String> = rest.iter()
+            .map(|arg| match arg.as_str() {
+                "--save-dev" => "-D".to_string(),
+                _ => arg.clone(),
+            })
+            .collect();
+        let has_packages = rest.iter().any(|arg| !arg.starts_with('-'));
+
+        let (subcommand, extra): (&str, &[&str]) = match subcommand.as_str() {
+            "install" | "i" | "add" if has_packages => ("add", &[]),
+            "install" | "i" => ("install", &[]),
+            "ci" => ("install", &["--frozen-lockfile"]),
+            "uninstall" | "remove" | "rm" | "un" => ("remove", &[]),
+            other => (other, &[]),
+        };
+
+        std::iter::once(subcommand.to_string())
+            .chain(extra.iter().map(|arg| arg.to_string()))
+            .chain(rest)
+            .collect()
+    }
+}
 
 #[async_trait]
 impl ToolBuilder for NpmTool {
@@ -29,22 +135,89 @@ impl ToolBuilder for NpmTool {
     fn name() -> &'static str {
         "npm"
     }
-    
+
     fn description() -> &'static str {
-        "📦 Execute npm commands in project directory"
+        "📦 Execute npm commands in project directory (auto-detects yarn/pnpm/bun from lockfiles)"
     }
-    
+
     fn schema() -> serde_json::Value {
-        SchemaBuilder::new()
-            .optional_array("args", "NPM command arguments (e.g., ['install'], ['run', 'build'])")
+        let mut schema = SchemaBuilder::new()
+            .optional_array("args", "NPM command arguments (e.g., ['install'], ['run', 'build']); install/ci/uninstall are translated for other managers")
+            .optional_string("manager", "Package manager override: npm, yarn, pnpm or bun (default: detect from lockfile, else npm)")
+            .optional_bool("stream", "Send stdout/stderr lines as notifications/progress while the command runs (full output is still returned)", Some(false))
+            .optional_string("cwd", "Subdirectory to run in, relative to the project (must stay inside the root)")
+            .optional_string_map("env", "Extra environment variables, e.g. {\"RUST_LOG\": \"debug\"}")
             .optional_string("project", "Project name for execution directory")
-            .build()
+            .build();
+        schema["properties"]["manager"]["enum"] = serde_json::json!(["npm", "yarn", "pnpm", "bun"]);
+        schema
     }
-    
+
     async fn run(args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
-        execute_command("npm", args.args, args.project.as_deref(), config).await
+        let (manager, detected_from) = match args.manager.as_deref() {
+            Some(name) => {
+                let manager = PackageManager::from_name(name)
+                    .ok_or_else(|| EmpathicError::InvalidArgument {
+                        arg: "manager".to_string(),
+                        reason: format!("unknown package manager '{}' (expected npm, yarn, pnpm or bun)", name),
+                    })?;
+                (manager, None)
+            }
+            None => {
+                let working_dir = resolve_working_dir(config, args.project.as_deref(), args.cwd.as_deref())?;
+                match PackageManager::detect(&working_dir, &config.root_dir) {
+                    Some((manager, lockfile)) => (manager, Some(lockfile)),
+                    None => (PackageManager::Npm, None),
+                }
+            }
+        };
+
+        let command_args = manager.translate_args(args.args);
+        let options = CommandOptions { cwd: args.cwd, env: args.env, stream: args.stream };
+        let output = execute_command_with(manager.binary(), command_args, args.project.as_deref(), config, &options).await?;
+
+        Ok(NpmOutput {
+            manager: manager.binary(),
+            detected_from,
+            output,
+        })
     }
 }
 
 // 🔧 Implement Tool trait using the builder pattern
 crate::impl_tool_for_builder!(NpmTool);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_translate_args() {
+        let yarn = PackageManager::Yarn;
+        assert_eq!(yarn.translate_args(strings(&["install"])), strings(&["install"]));
+        assert_eq!(yarn.translate_args(strings(&["install", "--save-dev", "jest"])), strings(&["add", "-D", "jest"]));
+        assert_eq!(PackageManager::Pnpm.translate_args(strings(&["ci"])), strings(&["install", "--frozen-lockfile"]));
+        assert_eq!(PackageManager::Bun.translate_args(strings(&["uninstall", "lodash"])), strings(&["remove", "lodash"]));
+        assert_eq!(yarn.translate_args(strings(&["run", "build"])), strings(&["run", "build"]));
+        assert_eq!(PackageManager::Npm.translate_args(strings(&["i", "react"])), strings(&["i", "react"]));
+    }
+
+    #[test]
+    fn test_detect_prefers_nearest_lockfile() {
+        let root = tempfile::TempDir::new().unwrap();
+        let package = root.path().join("packages/web");
+        std::fs::create_dir_all(&package).unwrap();
+
+        assert_eq!(PackageManager::detect(&package, root.path()), None);
+
+        std::fs::write(root.path().join("pnpm-lock.yaml"), "").unwrap();
+        assert_eq!(PackageManager::detect(&package, root.path()), Some((PackageManager::Pnpm, "pnpm-lock.yaml".to_string())));
+
+        std::fs::write(package.join("yarn.lock"), "").unwrap();
+        assert_eq!(PackageManager::detect(&package, root.path()), Some((PackageManager::Yarn, "yarn.lock".to_string())));
+    }
+}
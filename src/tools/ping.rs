@@ -0,0 +1,77 @@
+//! 🏓 Ping Tool - Cheap liveness check for MCP clients
+//!
+//! Reads a few counters and touches nothing: no language server is spawned
+//! and no file is opened, so clients can call it before expensive requests.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::tools::{ToolBuilder, SchemaBuilder};
+use crate::config::Config;
+use crate::error::EmpathicResult;
+
+/// 🏓 Report server version, uptime and LSP load
+pub struct PingTool;
+
+#[derive(Deserialize)]
+pub struct PingArgs {
+    // No arguments needed
+}
+
+#[derive(Serialize)]
+pub struct PingOutput {
+    server: &'static str,
+    version: &'static str,
+    uptime_secs: u64,
+    /// RFC 3339 server time
+    timestamp: String,
+    /// Running language servers (0 when LSP is off)
+    lsp_servers: usize,
+    /// Documents open in language servers
+    open_documents: usize,
+    /// Entries in the LSP response cache
+    cached_responses: usize,
+}
+
+#[async_trait]
+impl ToolBuilder for PingTool {
+    type Args = PingArgs;
+    type Output = PingOutput;
+
+    fn name() -> &'static str {
+        "ping"
+    }
+
+    fn description() -> &'static str {
+        "🏓 Check the server is alive: version, uptime, timestamp, running LSP servers and cached files"
+    }
+
+    fn schema() -> serde_json::Value {
+        SchemaBuilder::new()
+            .build()
+    }
+
+    async fn run(_args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
+        let (lsp_servers, open_documents, cached_responses) = match config.lsp_manager() {
+            Some(manager) => (
+                manager.get_server_status().await.len(),
+                manager.get_document_stats().await.values().sum(),
+                manager.cache().stats().await.total_entries,
+            ),
+            None => (0, 0, 0),
+        };
+
+        Ok(PingOutput {
+            server: env!("CARGO_PKG_NAME"),
+            version: env!("CARGO_PKG_VERSION"),
+            uptime_secs: config.started_at.elapsed().as_secs(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            lsp_servers,
+            open_documents,
+            cached_responses,
+        })
+    }
+}
+
+// 🔧 Implement Tool trait using the builder pattern
+crate::impl_tool_for_builder!(PingTool);
@@ -1,32 +1,68 @@
 //! 📖 Read File Tool - Clean ToolBuilder implementation with custom text formatting
+//!
+//! `read_file` loads whole files, so it refuses files over `MAX_READ_BYTES`;
+//! `read_file_chunk` pages through files of any size by byte range.
 
 use async_trait::async_trait;
-use serde::Deserialize;
-use serde_json::Value;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::Path;
 
-use crate::tools::{Tool, ToolBuilder, SchemaBuilder, format_text_response, default_fs_path};
+use crate::tools::{Tool, ToolBuilder, SchemaBuilder, format_text_response, default_fs_path, parse_encoding};
 use crate::config::Config;
-use crate::fs::FileOps;
-use crate::error::EmpathicResult;
+use crate::fs::{FileChunk, FileOps, FileRange, TextEncoding};
+use crate::error::{EmpathicError, EmpathicResult};
 
 /// 📖 Read File Tool using modern ToolBuilder pattern (with custom text output)
 pub struct ReadFileTool;
 
+/// 📦 Read File Chunk Tool - one byte range of a file per call
+pub struct ReadFileChunkTool;
+
+/// Bytes returned when length is not given
+const DEFAULT_CHUNK_BYTES: usize = 64 * 1024;
+
+/// Largest chunk a single call may ask for
+const MAX_CHUNK_BYTES: usize = 1024 * 1024;
+
 #[derive(Deserialize)]
 pub struct ReadFileArgs {
     path: Option<String>,
     #[serde(default)]
     line_offset: Option<usize>,
     line_length: Option<usize>,
+    start_line: Option<usize>,
+    end_line: Option<usize>,
+    max_bytes: Option<usize>,
+    encoding: Option<String>,
+    /// Wrap the content in a Markdown code fence tagged by file extension
+    #[serde(default)]
+    fenced: bool,
+    /// Prefix each line with its 1-based line number
+    #[serde(default)]
+    line_numbers: bool,
     project: Option<String>,
 }
 
-pub type ReadFileOutput = String;
+/// 📖 File text plus paging info when a line range or byte cap was requested
+#[derive(Serialize)]
+pub struct ReadFileOutput {
+    pub content: String,
+    pub range: Option<FileRange>,
+    /// Encoding the file was decoded from (None for directory listings)
+    pub encoding: Option<TextEncoding>,
+}
+
+impl From<String> for ReadFileOutput {
+    fn from(content: String) -> Self {
+        Self { content, range: None, encoding: None }
+    }
+}
 
 #[async_trait]
 impl ToolBuilder for ReadFileTool {
     type Args = ReadFileArgs;
-    type Output = String;
+    type Output = ReadFileOutput;
 
     fn name() -> &'static str {
         "read_file"
@@ -41,6 +77,12 @@ impl ToolBuilder for ReadFileTool {
             .optional_string("path", "Path to the file to read (default: project root \".\"). If path is a directory, lists contents instead")
             .optional_integer("line_offset", "Starting line number (0-indexed)", Some(0))
             .optional_integer("line_length", "Number of lines to read", Some(1))
+            .optional_integer("start_line", "First line to read (1-based, inclusive)", Some(1))
+            .optional_integer("end_line", "Last line to read (1-based, inclusive)", Some(1))
+            .optional_integer("max_bytes", "Maximum bytes of content to return (cut at a UTF-8 boundary)", Some(1))
+            .optional_string("encoding", "Force the file encoding: utf-8, utf-8-bom, utf-16le, utf-16be, latin1 (default: auto-detect)")
+            .optional_bool("fenced", "Wrap content in a ```lang Markdown fence inferred from the file extension", Some(false))
+            .optional_bool("line_numbers", "Prefix each line with its 1-based line number", Some(false))
             .optional_string("project", "Project name for path resolution")
             .build()
     }
@@ -49,11 +91,13 @@ impl ToolBuilder for ReadFileTool {
         let path = default_fs_path(args.path, args.project.as_deref());
         let working_dir = config.project_path(args.project.as_deref());
         let file_path = working_dir.join(&path);
+        config.validate_path(&file_path)?;
+        let encoding = parse_encoding(args.encoding.as_deref())?;
         
         // 🎯 AI Enhancement: Auto-detect directories and list contents instead of erroring
         if file_path.is_dir() {
             // List directory contents (non-recursive) when path is a directory
-            let files = FileOps::list_files(&file_path, false, false, None).await?;
+            let files = FileOps::list_files(&file_path, false, false, None, false, None, &config.skip_dirs).await?;
             
             // Format as readable directory listing
             let mut listing = format!("📁 Directory listing for: {}\n\n", file_path.display());
@@ -70,21 +114,76 @@ impl ToolBuilder for ReadFileTool {
                 listing.push_str(&format!("\nTotal: {} items", file_count));
             }
             
-            return Ok(listing);
+            return Ok(listing.into());
+        }
+        
+        // 🛡️ Everything below loads the whole file
+        check_read_size(&file_path, config).await?;
+
+        // 📄 Paged read: reports total lines and whether the byte cap cut it short
+        if args.start_line.is_some() || args.end_line.is_some() || args.max_bytes.is_some() {
+            let start_line = args.start_line.unwrap_or(1);
+            if start_line == 0 {
+                return Err(EmpathicError::mcp_parameter("start_line", "must be >= 1 (lines are 1-based)"));
+            }
+            if let Some(end_line) = args.end_line
+                && end_line < start_line
+            {
+                return Err(EmpathicError::mcp_parameter(
+                    "end_line",
+                    format!("{} is before start_line {}", end_line, start_line),
+                ));
+            }
+
+            let range = FileOps::read_file_range(&file_path, start_line, args.end_line, args.max_bytes, encoding).await?;
+            return Ok(ReadFileOutput {
+                content: decorate(&range.content, start_line, &file_path, args.fenced, args.line_numbers),
+                encoding: Some(range.encoding),
+                range: Some(range),
+            });
         }
         
         // Original file reading logic
-        let content = if let Some(offset) = args.line_offset {
-            if offset > 0 || args.line_length.is_some() {
-                FileOps::read_file_chunk(&file_path, offset, args.line_length).await?
-            } else {
-                FileOps::read_file(&file_path).await?
+        let (content, encoding) = FileOps::read_file_with_encoding(&file_path, encoding).await?;
+        let (content, first_line) = match args.line_offset {
+            Some(offset) if offset > 0 || args.line_length.is_some() => {
+                (FileOps::chunk_lines(&content, offset, args.line_length), offset + 1)
             }
-        } else {
-            FileOps::read_file(&file_path).await?
+            _ => (content, 1),
         };
+        let content = decorate(&content, first_line, &file_path, args.fenced, args.line_numbers);
+
+        Ok(ReadFileOutput { content, range: None, encoding: Some(encoding) })
+    }
+}
 
-        Ok(content)
+/// 🛡️ Refuse files larger than `max_read_bytes` before loading them
+async fn check_read_size(path: &Path, config: &Config) -> EmpathicResult<()> {
+    let Ok(metadata) = tokio::fs::metadata(path).await else {
+        // Missing files get the regular read error
+        return Ok(());
+    };
+    if config.max_read_bytes > 0 && metadata.len() > config.max_read_bytes {
+        return Err(EmpathicError::FileTooLarge {
+            path: path.to_path_buf(),
+            size: metadata.len(),
+            limit: config.max_read_bytes,
+        });
+    }
+    Ok(())
+}
+
+/// 🏷️ Apply the optional line numbers and Markdown fence (raw content when neither is set)
+fn decorate(content: &str, first_line: usize, path: &Path, fenced: bool, line_numbers: bool) -> String {
+    let content = if line_numbers {
+        FileOps::number_lines(content, first_line)
+    } else {
+        content.to_string()
+    };
+    if fenced {
+        FileOps::fence(&content, FileOps::fence_language(path))
+    } else {
+        content
     }
 }
 
@@ -110,9 +209,118 @@ impl Tool for ReadFileTool {
                 value: format!("Invalid arguments for {}: {}", <ReadFileTool as ToolBuilder>::name(), e)
             })?;
         
-        let content = Self::run(parsed_args, config).await?;
+        let output = Self::run(parsed_args, config).await?;
         
         // 📝 Use text formatting for raw file content (not JSON)
-        Ok(format_text_response(&content))
+        let mut response = format_text_response(&output.content);
+        
+        // 📊 Paging info goes in a second content item so the file text stays verbatim
+        if let Some(range) = output.range
+            && let Some(items) = response["content"].as_array_mut()
+        {
+            items.push(json!({
+                "type": "text",
+                "text": serde_json::to_string(&range)?
+            }));
+        } else if let Some(encoding) = output.encoding
+            && encoding != TextEncoding::Utf8
+            && let Some(items) = response["content"].as_array_mut()
+        {
+            // 🔤 Plain UTF-8 is the norm; anything else is worth pointing out
+            items.push(json!({
+                "type": "text",
+                "text": json!({"encoding": encoding}).to_string()
+            }));
+        }
+        
+        Ok(response)
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ReadFileChunkArgs {
+    path: String,
+    #[serde(default)]
+    offset: u64,
+    length: Option<usize>,
+    project: Option<String>,
+}
+
+#[async_trait]
+impl ToolBuilder for ReadFileChunkTool {
+    type Args = ReadFileChunkArgs;
+    type Output = FileChunk;
+
+    fn name() -> &'static str {
+        "read_file_chunk"
+    }
+
+    fn description() -> &'static str {
+        "📦 Read one byte range of a file of any size (logs, generated code); returns total_size and next_offset for paging"
+    }
+
+    fn schema() -> serde_json::Value {
+        SchemaBuilder::new()
+            .required_string("path", "Path to the file to read")
+            .optional_integer("offset", "Byte offset to start at (default: 0)", Some(0))
+            .optional_integer("length", "Bytes to read (default: 65536, at most 1048576)", Some(1))
+            .optional_string("project", "Project name for path resolution")
+            .build()
+    }
+
+    async fn run(args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
+        let length = args.length.unwrap_or(DEFAULT_CHUNK_BYTES);
+        if length == 0 || length > MAX_CHUNK_BYTES {
+            return Err(EmpathicError::mcp_parameter("length", format!("must be between 1 and {}", MAX_CHUNK_BYTES)));
+        }
+        // 📏 Invalid bytes may decode to 3-byte replacement characters; keep the
+        // chunk and its paging info inside the response cap
+        let length = match config.max_response_bytes {
+            0 => length,
+            limit => length.min((limit / 4).max(1)),
+        };
+
+        let working_dir = config.project_path(args.project.as_deref());
+        let file_path = working_dir.join(&args.path);
+        config.validate_path(&file_path)?;
+        if file_path.is_dir() {
+            return Err(EmpathicError::mcp_parameter("path", format!("{} is a directory", args.path)));
+        }
+
+        FileOps::read_file_bytes(&file_path, args.offset, length).await
+    }
+}
+
+// 🎯 Chunk text verbatim, paging info in a second content item (as read_file does)
+#[async_trait]
+impl Tool for ReadFileChunkTool {
+    fn name(&self) -> &'static str {
+        <ReadFileChunkTool as ToolBuilder>::name()
+    }
+
+    fn description(&self) -> &'static str {
+        <ReadFileChunkTool as ToolBuilder>::description()
+    }
+
+    fn schema(&self) -> Value {
+        <ReadFileChunkTool as ToolBuilder>::schema()
+    }
+
+    async fn execute(&self, args: Value, config: &Config) -> EmpathicResult<Value> {
+        let parsed_args = serde_json::from_value(args)
+            .map_err(|e| EmpathicError::McpParameterInvalid {
+                parameter: "args".to_string(),
+                value: format!("Invalid arguments for {}: {}", <ReadFileChunkTool as ToolBuilder>::name(), e)
+            })?;
+
+        let chunk = Self::run(parsed_args, config).await?;
+        let mut response = format_text_response(&chunk.content);
+        if let Some(items) = response["content"].as_array_mut() {
+            items.push(json!({
+                "type": "text",
+                "text": serde_json::to_string(&chunk)?
+            }));
+        }
+        Ok(response)
     }
 }
@@ -6,9 +6,10 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
-use crate::tools::ToolBuilder;
+use crate::tools::{ToolBuilder, sync_lsp_document};
+use crate::tools::diff_utils::unified_diff;
 use crate::config::Config;
-use crate::fs::FileOps;
+use crate::fs::{FileOps, LineLayout, TextEncoding};
 use crate::error::{EmpathicResult, EmpathicError};
 
 /// 🔧 Advanced Replace Tool using modern ToolBuilder pattern
@@ -21,6 +22,7 @@ pub struct ReplaceArgs {
     operation: OperationMode,
     #[serde(default)]
     dry_run: bool,
+    max_preview_matches: Option<usize>,
     project: Option<String>,
 }
 
@@ -79,9 +81,29 @@ pub struct ReplaceOutput {
     statistics: ReplaceStatistics,
     #[serde(skip_serializing_if = "Option::is_none")]
     preview: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    diff: Option<String>,
+    /// Dry run only: line, column and snippet of each match
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    match_preview: Vec<MatchPreview>,
+    /// More matches than `max_preview_matches`
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    match_preview_truncated: bool,
+    encoding: TextEncoding,
     lsp_synced: bool,
 }
 
+/// 🎯 Where a dry-run match sits, with a caret under it like a search_files hit
+#[derive(Serialize)]
+pub struct MatchPreview {
+    operation_index: usize,
+    line: usize,
+    /// 1-based character column
+    column: usize,
+    text: String,
+    highlight: String,
+}
+
 #[derive(Serialize)]
 pub struct ReplaceStatistics {
     original_lines: usize,
@@ -92,10 +114,31 @@ pub struct ReplaceStatistics {
     chars_changed: i64,
 }
 
+/// Matches listed in a dry run when max_preview_matches is not given
+const DEFAULT_MAX_PREVIEW_MATCHES: usize = 20;
+
 fn default_true() -> bool {
     true
 }
 
+/// Locate the match at byte range `start..end` of `content`; `None` if the range isn't on char boundaries
+fn locate_match(content: &str, start: usize, end: usize, operation_index: usize) -> Option<MatchPreview> {
+    let before = content.get(..start)?;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let line_end = content[start..].find('\n').map_or(content.len(), |i| start + i);
+    let column = before[line_start..].chars().count() + 1;
+    // Multi-line matches are marked up to the end of their first line
+    let width = content.get(start..end.min(line_end))?.chars().count().max(1);
+
+    Some(MatchPreview {
+        operation_index,
+        line: before.matches('\n').count() + 1,
+        column,
+        text: content[line_start..line_end].trim_end_matches('\r').to_string(),
+        highlight: format!("{}{}", " ".repeat(column - 1), "^".repeat(width)),
+    })
+}
+
 impl OperationMode {
     // This enum handles the two operation modes elegantly through serde
 }
@@ -181,6 +224,11 @@ impl ToolBuilder for ReplaceTool {
                     "description": "Preview changes without modifying the file (default: false)",
                     "default": false
                 },
+                "max_preview_matches": {
+                    "type": "integer",
+                    "description": "Dry run: most matches to list with line, column and snippet (default: 20)",
+                    "minimum": 0
+                },
                 "project": {
                     "type": "string",
                     "description": "Project name for path resolution"
@@ -195,13 +243,18 @@ impl ToolBuilder for ReplaceTool {
     
     async fn run(args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
         let working_dir = config.project_path(args.project.as_deref());
-        let file_path = working_dir.join(&args.path);
+        let file_path = config.validate_path(&working_dir.join(&args.path))?;
         
         // Read the file content
-        let original_content = FileOps::read_file(&file_path).await?;
+        let (file_content, encoding) = FileOps::read_file_with_encoding(&file_path, None).await?;
+        // ↩️ Operations run on LF text; the file's own line endings are restored on write
+        let layout = LineLayout::detect(&file_content);
+        let original_content = LineLayout::normalize(&file_content);
         let mut current_content = original_content.clone();
         let mut all_matches = Vec::new();
         let mut total_replacements = 0;
+        let max_preview_matches = args.max_preview_matches.unwrap_or(DEFAULT_MAX_PREVIEW_MATCHES);
+        let mut match_preview = Vec::new();
         
         // Convert operation mode to a consistent list of operations
         let operations = match &args.operation {
@@ -219,6 +272,13 @@ impl ToolBuilder for ReplaceTool {
             }
             OperationMode::Batch { operations } => operations.clone(),
         };
+        let operations: Vec<ReplaceOperation> = operations.into_iter()
+            .map(|op| ReplaceOperation {
+                search: LineLayout::normalize(&op.search),
+                replace: LineLayout::normalize(&op.replace),
+                ..op
+            })
+            .collect();
         
         // Apply each operation sequentially (preserving original complex logic)
         for (i, op) in operations.iter().enumerate() {
@@ -335,17 +395,30 @@ impl ToolBuilder for ReplaceTool {
                 (new_content, match_info)
             };
             
+            // 🎯 Offsets refer to the content this operation searched, before it replaced anything
+            if args.dry_run {
+                let room = max_preview_matches.saturating_sub(match_preview.len());
+                match_preview.extend(matches.iter().take(room).filter_map(|m| {
+                    let start = m["start"].as_u64()? as usize;
+                    let end = m["end"].as_u64()? as usize;
+                    locate_match(&current_content, start, end, i)
+                }));
+            }
+            
             current_content = new_content;
             total_replacements += matches.len();
             all_matches.extend(matches);
         }
         
         let changes_made = current_content != original_content;
+        let current_content = if changes_made { layout.restore(&current_content) } else { file_content.clone() };
+        let original_content = file_content;
         
         // Write the file if not dry run and changes were made
         let lsp_synced = if !args.dry_run && changes_made {
-            FileOps::write_file(&file_path, &current_content).await?;
-            false // 🚀 LSP sync removed for performance
+            FileOps::write_file_encoded(&file_path, &current_content, encoding).await?;
+            // 🔄 The server must see the new content, and cached responses are stale now
+            sync_lsp_document(config, &file_path, &current_content).await
         } else {
             false
         };
@@ -376,6 +449,8 @@ impl ToolBuilder for ReplaceTool {
         } else {
             None
         };
+        let diff = (args.dry_run && changes_made)
+            .then(|| unified_diff(&original_content, &current_content, &args.path));
         
         Ok(ReplaceOutput {
             success: true,
@@ -387,6 +462,10 @@ impl ToolBuilder for ReplaceTool {
             matches: all_matches,
             statistics,
             preview,
+            diff,
+            match_preview_truncated: args.dry_run && total_replacements > max_preview_matches,
+            match_preview,
+            encoding,
             lsp_synced,
         })
     }
@@ -0,0 +1,351 @@
+//! 🧪 Run Tests Tool - One test command for cargo, gradle and JS projects
+//!
+//! The project kind is detected from the nearest `Cargo.toml`, Gradle build
+//! file or `package.json`. Each runner is asked for machine-readable results
+//! (cargo's JSON messages, Gradle's JUnit XML, Jest/Vitest/Mocha JSON, TAP from
+//! `node --test`) and the results are reported in the same shape.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::error::{EmpathicError, EmpathicResult};
+
+use crate::tools::{ToolBuilder, SchemaBuilder};
+use crate::config::Config;
+use super::build_targets::GRADLE_BUILD_FILES;
+use super::cargo::with_json_messages;
+use super::cargo_output::{CargoTestReport, CompilerDiagnostic};
+use super::executor_utils::{execute_command_with, resolve_working_dir, CommandOptions, CommandOutput};
+use super::npm::PackageManager;
+use super::test_reports::{self, TestCounts, TestFailure, TestResults, TestRunner};
+
+/// 🧪 Run Tests Tool using modern ToolBuilder pattern
+pub struct RunTestsTool;
+
+#[derive(Deserialize)]
+pub struct RunTestsArgs {
+    /// Only run tests whose name matches
+    filter: Option<String>,
+    /// Extra arguments for the underlying test command
+    #[serde(default)]
+    args: Vec<String>,
+    /// Force the project kind instead of detecting it
+    kind: Option<String>,
+    #[serde(default)]
+    stream: bool,
+    cwd: Option<String>,
+    #[serde(default)]
+    env: HashMap<String, String>,
+    project: Option<String>,
+}
+
+/// 🧪 Normalized results plus the raw command output
+#[derive(Serialize)]
+pub struct RunTestsOutput {
+    runner: TestRunner,
+    success: bool,
+    /// The command failed before any results were produced (compile error, bad config)
+    build_failed: bool,
+    /// Whether machine-readable results were found; counts are zero otherwise
+    parsed: bool,
+    summary: TestCounts,
+    failures: Vec<TestFailure>,
+    /// Compiler errors (cargo only)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    build_errors: Vec<CompilerDiagnostic>,
+    raw: CommandOutput,
+}
+
+/// 📁 Kinds of project the tool can test
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ProjectKind {
+    Cargo,
+    Gradle,
+    Node,
+}
+
+impl ProjectKind {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "cargo" => Some(Self::Cargo),
+            "gradle" => Some(Self::Gradle),
+            "npm" | "node" => Some(Self::Node),
+            _ => None,
+        }
+    }
+
+    /// 🔍 Nearest project from `dir` upwards, stopping at `root`
+    fn detect(dir: &Path, root: &Path) -> Option<(Self, PathBuf)> {
+        let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+        let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+        dir.ancestors()
+            .take_while(|ancestor| ancestor.starts_with(&root))
+            .find_map(|ancestor| {
+                let kind = if ancestor.join("Cargo.toml").is_file() {
+                    Self::Cargo
+                } else if GRADLE_BUILD_FILES.iter().any(|file| ancestor.join(file).is_file()) {
+                    Self::Gradle
+                } else if ancestor.join("package.json").is_file() {
+                    Self::Node
+                } else {
+                    return None;
+                };
+                Some((kind, ancestor.to_path_buf()))
+            })
+    }
+}
+
+#[async_trait]
+impl ToolBuilder for RunTestsTool {
+    type Args = RunTestsArgs;
+    type Output = RunTestsOutput;
+
+    fn name() -> &'static str {
+        "run_tests"
+    }
+
+    fn description() -> &'static str {
+        "🧪 Run the project's tests (cargo, gradle, jest, vitest, mocha or node --test) and report totals and failures in one format"
+    }
+
+    fn schema() -> serde_json::Value {
+        let mut schema = SchemaBuilder::new()
+            .optional_string("filter", "Only run tests matching this name (cargo test filter, gradle --tests, jest/vitest -t, mocha --grep, node --test-name-pattern)")
+            .optional_array("args", "Extra arguments passed to the test command")
+            .optional_string("kind", "Project kind override: cargo, gradle or npm (default: nearest Cargo.toml, build.gradle or package.json)")
+            .optional_bool("stream", "Send stdout/stderr lines as notifications/progress while the tests run (full output is still returned)", Some(false))
+            .optional_string("cwd", "Subdirectory to run in, relative to the project (must stay inside the root)")
+            .optional_string_map("env", "Extra environment variables, e.g. {\"RUST_LOG\": \"debug\"}")
+            .optional_string("project", "Project name for execution directory")
+            .build();
+        schema["properties"]["kind"]["enum"] = serde_json::json!(["cargo", "gradle", "npm"]);
+        schema
+    }
+
+    async fn run(args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
+        let working_dir = resolve_working_dir(config, args.project.as_deref(), args.cwd.as_deref())?;
+        let detected = ProjectKind::detect(&working_dir, &config.root_dir);
+        let (kind, project_dir) = match args.kind.as_deref() {
+            Some(name) => {
+                let kind = ProjectKind::from_name(name).ok_or_else(|| EmpathicError::InvalidArgument {
+                    arg: "kind".to_string(),
+                    reason: format!("unknown project kind '{}' (expected cargo, gradle or npm)", name),
+                })?;
+                (kind, working_dir.clone())
+            }
+            None => detected.ok_or_else(|| EmpathicError::InvalidArgument {
+                arg: "cwd".to_string(),
+                reason: format!("no Cargo.toml, Gradle build file or package.json found from {}", working_dir.display()),
+            })?,
+        };
+
+        let project = args.project.clone();
+        let options = CommandOptions { cwd: args.cwd, env: args.env, stream: args.stream };
+        let run = TestRun { project: project.as_deref(), config, options: &options };
+        match kind {
+            ProjectKind::Cargo => run.cargo(args.filter, args.args).await,
+            ProjectKind::Gradle => run.gradle(&project_dir, args.filter, args.args).await,
+            ProjectKind::Node => run.node(&project_dir, &working_dir, args.filter, args.args).await,
+        }
+    }
+}
+
+/// Shared context for running one project's tests
+struct TestRun<'a> {
+    project: Option<&'a str>,
+    config: &'a Config,
+    options: &'a CommandOptions,
+}
+
+impl TestRun<'_> {
+    async fn execute(&self, command: &str, args: Vec<String>) -> EmpathicResult<CommandOutput> {
+        execute_command_with(command, args, self.project, self.config, self.options).await
+    }
+
+    /// 🦀 `cargo test` with JSON compiler messages
+    async fn cargo(&self, filter: Option<String>, extra: Vec<String>) -> EmpathicResult<RunTestsOutput> {
+        let mut cargo_args = vec!["test".to_string()];
+        cargo_args.extend(filter);
+        cargo_args.extend(extra);
+        let output = self.execute("cargo", with_json_messages(cargo_args)).await?;
+
+        let report = CargoTestReport::from_output(output);
+        let results = test_reports::from_cargo(&report);
+        Ok(RunTestsOutput {
+            runner: TestRunner::Cargo,
+            success: report.success,
+            build_failed: report.build_failed,
+            parsed: results.parsed,
+            summary: results.counts,
+            failures: results.failures,
+            build_errors: report.compile_errors,
+            raw: report.raw,
+        })
+    }
+
+    /// 🐘 `gradle test`, then the JUnit XML reports it wrote
+    async fn gradle(&self, project_dir: &Path, filter: Option<String>, extra: Vec<String>) -> EmpathicResult<RunTestsOutput> {
+        let mut gradle_args = vec!["test".to_string(), "--console=plain".to_string()];
+        if let Some(filter) = filter {
+            gradle_args.extend(["--tests".to_string(), filter]);
+        }
+        gradle_args.extend(extra);
+
+        let started = SystemTime::now();
+        let output = self.execute("gradle", gradle_args).await?;
+
+        // Up-to-date tasks don't rewrite their reports, so fall back to what's there
+        let reports = junit_reports(project_dir);
+        let fresh: Vec<&PathBuf> = reports.iter()
+            .filter(|path| modified(path).is_some_and(|time| time >= started))
+            .collect();
+        let reports = if fresh.is_empty() { reports.iter().collect() } else { fresh };
+
+        let mut results = TestResults::default();
+        for path in reports {
+            if let Ok(xml) = tokio::fs::read_to_string(path).await {
+                results.merge(test_reports::parse_junit_xml(&xml));
+            }
+        }
+        Ok(finish(TestRunner::Gradle, results, output))
+    }
+
+    /// 📦 The package.json `test` script, with the runner's machine-readable reporter
+    async fn node(&self, project_dir: &Path, working_dir: &Path, filter: Option<String>, extra: Vec<String>) -> EmpathicResult<RunTestsOutput> {
+        let package_json = tokio::fs::read_to_string(project_dir.join("package.json")).await
+            .map_err(|_| EmpathicError::FileNotFound { path: project_dir.join("package.json") })?;
+        let script = serde_json::from_str::<serde_json::Value>(&package_json).ok()
+            .and_then(|package| package["scripts"]["test"].as_str().map(str::to_string))
+            .ok_or_else(|| EmpathicError::InvalidArgument {
+                arg: "kind".to_string(),
+                reason: format!("{} has no \"test\" script", project_dir.join("package.json").display()),
+            })?;
+        let runner = TestRunner::from_test_script(&script);
+        let manager = PackageManager::detect(working_dir, &self.config.root_dir)
+            .map_or(PackageManager::Npm, |(manager, _)| manager);
+
+        let output_file = std::env::temp_dir().join(format!(
+            "empathic-tests-{}-{}.json",
+            std::process::id(),
+            SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_nanos())
+        ));
+        let mut flags = reporter_flags(runner, &output_file);
+        if let Some(filter) = filter {
+            flags.extend(filter_flags(runner, filter));
+        }
+        flags.extend(extra);
+
+        let output = self.execute(manager.binary(), script_args(manager, flags)).await?;
+
+        let results = match runner {
+            TestRunner::Jest | TestRunner::Vitest => {
+                let report = tokio::fs::read_to_string(&output_file).await.ok();
+                let _ = tokio::fs::remove_file(&output_file).await;
+                report.as_deref().and_then(test_reports::parse_jest_json)
+            }
+            TestRunner::Mocha => test_reports::parse_mocha_json(&output.stdout),
+            _ => Some(test_reports::parse_tap(&output.stdout)),
+        };
+        Ok(finish(runner, results.unwrap_or_default(), output))
+    }
+}
+
+/// Build the report for runners without compiler diagnostics
+fn finish(runner: TestRunner, results: TestResults, raw: CommandOutput) -> RunTestsOutput {
+    RunTestsOutput {
+        runner,
+        success: raw.success,
+        build_failed: !raw.success && !results.parsed,
+        parsed: results.parsed,
+        summary: results.counts,
+        failures: results.failures,
+        build_errors: Vec::new(),
+        raw,
+    }
+}
+
+/// Flags selecting the runner's machine-readable reporter
+fn reporter_flags(runner: TestRunner, output_file: &Path) -> Vec<String> {
+    let output_file = output_file.to_string_lossy();
+    match runner {
+        TestRunner::Jest => vec!["--json".to_string(), format!("--outputFile={}", output_file)],
+        TestRunner::Vitest => vec!["--reporter=json".to_string(), format!("--outputFile={}", output_file)],
+        TestRunner::Mocha => vec!["--reporter".to_string(), "json".to_string()],
+        TestRunner::NodeTest => vec!["--test-reporter=tap".to_string()],
+        TestRunner::Cargo | TestRunner::Gradle | TestRunner::Script => Vec::new(),
+    }
+}
+
+/// Flags restricting the run to tests matching `filter`
+fn filter_flags(runner: TestRunner, filter: String) -> Vec<String> {
+    match runner {
+        TestRunner::Jest | TestRunner::Vitest => vec!["-t".to_string(), filter],
+        TestRunner::Mocha => vec!["--grep".to_string(), filter],
+        TestRunner::NodeTest => vec![format!("--test-name-pattern={}", filter)],
+        _ => vec![filter],
+    }
+}
+
+/// Run the `test` script with extra flags in the manager's spelling
+fn script_args(manager: PackageManager, flags: Vec<String>) -> Vec<String> {
+    let mut args = match manager {
+        PackageManager::Bun => vec!["run".to_string(), "test".to_string()],
+        _ => vec!["test".to_string()],
+    };
+    if manager == PackageManager::Npm && !flags.is_empty() {
+        args.push("--".to_string());
+    }
+    args.extend(flags);
+    args
+}
+
+/// JUnit XML files under `build/test-results` of the project and its direct subprojects
+fn junit_reports(project_dir: &Path) -> Vec<PathBuf> {
+    let root = glob::Pattern::escape(&project_dir.to_string_lossy());
+    ["build/test-results/**/*.xml", "*/build/test-results/**/*.xml"].iter()
+        .filter_map(|pattern| glob::glob(&format!("{}/{}", root, pattern)).ok())
+        .flatten()
+        .filter_map(Result::ok)
+        .collect()
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+// 🔧 Implement Tool trait using the builder pattern
+crate::impl_tool_for_builder!(RunTestsTool);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_script_args_per_manager() {
+        let flags = strings(&["--json"]);
+        assert_eq!(script_args(PackageManager::Npm, flags.clone()), strings(&["test", "--", "--json"]));
+        assert_eq!(script_args(PackageManager::Yarn, flags.clone()), strings(&["test", "--json"]));
+        assert_eq!(script_args(PackageManager::Bun, flags), strings(&["run", "test", "--json"]));
+        assert_eq!(script_args(PackageManager::Npm, Vec::new()), strings(&["test"]));
+    }
+
+    #[test]
+    fn test_detect_nearest_project() {
+        let root = tempfile::TempDir::new().unwrap();
+        let web = root.path().join("web");
+        std::fs::create_dir_all(web.join("src")).unwrap();
+        std::fs::write(root.path().join("settings.gradle"), "").unwrap();
+        std::fs::write(web.join("package.json"), "{}").unwrap();
+
+        let (kind, dir) = ProjectKind::detect(&web.join("src"), root.path()).unwrap();
+        assert_eq!((kind, dir), (ProjectKind::Node, web.canonicalize().unwrap()));
+        assert_eq!(ProjectKind::detect(root.path(), root.path()).map(|(kind, _)| kind), Some(ProjectKind::Gradle));
+    }
+}
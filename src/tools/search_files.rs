@@ -0,0 +1,281 @@
+//! 🔍 Search Files Tool - Content search across a directory tree
+//!
+//! Literal substring search by default; `regex: true` switches to the regex
+//! crate. Each hit carries a caret line marking the matched span. Files that
+//! look binary are skipped unless `search_binary` is set; UTF-16 and Latin-1
+//! files are decoded and their hits tagged with the encoding.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::tools::{ToolBuilder, SchemaBuilder};
+use crate::config::Config;
+use crate::fs::{FileOps, TextEncoding};
+use crate::error::{EmpathicResult, EmpathicError};
+
+/// 🔍 Search Files Tool using modern ToolBuilder pattern
+pub struct SearchFilesTool;
+
+/// Hits returned when max_results is not given
+const DEFAULT_MAX_RESULTS: usize = 500;
+
+#[derive(Deserialize)]
+pub struct SearchFilesArgs {
+    query: String,
+    #[serde(default = "default_path")]
+    path: String,
+    pattern: Option<String>,
+    #[serde(default)]
+    regex: bool,
+    #[serde(default)]
+    case_insensitive: bool,
+    max_results: Option<usize>,
+    #[serde(default)]
+    include_ignored: bool,
+    #[serde(default)]
+    search_binary: bool,
+    project: Option<String>,
+}
+
+/// 🎯 Span of a match or capture group (1-based character columns, end exclusive)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct MatchSpan {
+    start: usize,
+    end: usize,
+    text: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SearchMatch {
+    path: String,
+    line: usize,
+    column: usize,
+    text: String,
+    highlight: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    captures: Vec<Option<MatchSpan>>,
+    /// Set for files that aren't plain UTF-8
+    #[serde(skip_serializing_if = "Option::is_none")]
+    encoding: Option<TextEncoding>,
+}
+
+#[derive(Serialize)]
+pub struct SearchFilesOutput {
+    query: String,
+    path: String,
+    regex: bool,
+    matches: Vec<SearchMatch>,
+    total_matches: usize,
+    files_searched: usize,
+    files_matched: usize,
+    files_skipped_binary: usize,
+    truncated: bool,
+}
+
+fn default_path() -> String {
+    ".".to_string()
+}
+
+#[async_trait]
+impl ToolBuilder for SearchFilesTool {
+    type Args = SearchFilesArgs;
+    type Output = SearchFilesOutput;
+
+    fn name() -> &'static str {
+        "search_files"
+    }
+
+    fn description() -> &'static str {
+        "🔍 Search file contents recursively (literal or regex) with line/column and caret highlights"
+    }
+
+    fn schema() -> serde_json::Value {
+        SchemaBuilder::new()
+            .required_string("query", "Text to search for (literal by default, regex if regex=true)")
+            .optional_string("path", "Directory to search (default: '.')")
+            .optional_string("pattern", "Glob filter for files to search, e.g. *.rs or src/**/mod.rs")
+            .optional_bool("regex", "Treat query as a regular expression", Some(false))
+            .optional_bool("case_insensitive", "Case-insensitive matching", Some(false))
+            .optional_integer("max_results", "Maximum number of matches to return (default: 500)", Some(1))
+            .optional_bool("include_ignored", "Also search files excluded by .gitignore/.ignore/.empathicignore and hidden files", Some(false))
+            .optional_bool("search_binary", "Also search files that look binary (lossy UTF-8 decoding)", Some(false))
+            .optional_string("project", "Project name for path resolution")
+            .build()
+    }
+
+    async fn run(args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
+        if args.query.is_empty() {
+            return Err(EmpathicError::mcp_parameter("query", "must not be empty"));
+        }
+
+        let matcher = build_matcher(&args.query, args.regex, args.case_insensitive)?;
+        let max_results = args.max_results.unwrap_or(DEFAULT_MAX_RESULTS);
+
+        let working_dir = config.project_path(args.project.as_deref());
+        let search_path = working_dir.join(&args.path);
+        config.validate_path(&search_path)?;
+        crate::tools::validate_dir_exists(&search_path)?;
+
+        let files = FileOps::list_files(&search_path, true, false, args.pattern.as_deref(), args.include_ignored, None, &config.skip_dirs).await?;
+
+        let mut matches = Vec::new();
+        let mut total_matches = 0;
+        let mut files_searched = 0;
+        let mut files_matched = 0;
+        let mut files_skipped_binary = 0;
+
+        for file in files.iter().filter(|f| !f.is_dir) {
+            let (content, encoding) = if args.search_binary {
+                match tokio::fs::read(&file.path).await {
+                    Ok(bytes) => (String::from_utf8_lossy(&bytes).into_owned(), TextEncoding::Utf8),
+                    Err(_) => continue,
+                }
+            } else {
+                // 🚫 Cheap sniff before paying for a full read of images, .so files etc.
+                if FileOps::is_binary(&file.path).await.unwrap_or(true) {
+                    files_skipped_binary += 1;
+                    continue;
+                }
+                // 🔤 UTF-16/Latin-1 sources are decoded rather than skipped
+                let Ok(decoded) = FileOps::read_file_with_encoding(&file.path, None).await else {
+                    continue;
+                };
+                decoded
+            };
+            let encoding = (encoding != TextEncoding::Utf8).then_some(encoding);
+            files_searched += 1;
+
+            let relative = file.path.strip_prefix(&search_path).unwrap_or(&file.path)
+                .to_string_lossy().to_string();
+            let before = total_matches;
+
+            for (line_index, line) in content.lines().enumerate() {
+                for found in search_line(&matcher, line, args.regex) {
+                    total_matches += 1;
+                    if matches.len() < max_results {
+                        matches.push(SearchMatch {
+                            path: relative.clone(),
+                            line: line_index + 1,
+                            encoding,
+                            ..found
+                        });
+                    }
+                }
+            }
+
+            if total_matches > before {
+                files_matched += 1;
+            }
+        }
+
+        Ok(SearchFilesOutput {
+            query: args.query,
+            path: search_path.to_string_lossy().to_string(),
+            regex: args.regex,
+            truncated: total_matches > matches.len(),
+            matches,
+            total_matches,
+            files_searched,
+            files_matched,
+            files_skipped_binary,
+        })
+    }
+}
+
+/// Compile the query; literal queries are escaped so both modes share one matcher
+fn build_matcher(query: &str, regex: bool, case_insensitive: bool) -> EmpathicResult<regex::Regex> {
+    let source = if regex { query.to_string() } else { regex::escape(query) };
+
+    regex::RegexBuilder::new(&source)
+        .case_insensitive(case_insensitive)
+        .build()
+        .map_err(|e| EmpathicError::InvalidRegexPattern {
+            pattern: query.to_string(),
+            reason: e.to_string(),
+        })
+}
+
+/// Find all matches in one line (line number is filled in by the caller)
+fn search_line(matcher: &regex::Regex, line: &str, with_captures: bool) -> Vec<SearchMatch> {
+    matcher.captures_iter(line)
+        .filter_map(|caps| {
+            let whole = caps.get(0)?;
+            // Empty regex matches (e.g. `^`) carry no information
+            if whole.is_empty() {
+                return None;
+            }
+
+            let span = char_span(line, whole);
+            let captures = if with_captures {
+                caps.iter().skip(1).map(|group| group.map(|m| char_span(line, m))).collect()
+            } else {
+                Vec::new()
+            };
+
+            Some(SearchMatch {
+                path: String::new(),
+                line: 0,
+                column: span.start,
+                text: line.to_string(),
+                highlight: caret_line(&span),
+                captures,
+                encoding: None,
+            })
+        })
+        .collect()
+}
+
+/// Convert a byte-offset match into 1-based character columns
+fn char_span(line: &str, m: regex::Match<'_>) -> MatchSpan {
+    let start = line[..m.start()].chars().count() + 1;
+    MatchSpan {
+        start,
+        end: start + m.as_str().chars().count(),
+        text: m.as_str().to_string(),
+    }
+}
+
+/// Caret marker aligned under the match, e.g. "    ^^^^"
+fn caret_line(span: &MatchSpan) -> String {
+    format!("{}{}", " ".repeat(span.start - 1), "^".repeat((span.end - span.start).max(1)))
+}
+
+// 🔧 Implement Tool trait using the builder pattern
+crate::impl_tool_for_builder!(SearchFilesTool);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_literal_mode_escapes_regex_syntax() {
+        let matcher = build_matcher("a.b(", false, false).unwrap();
+        assert_eq!(search_line(&matcher, "axb( a.b(", false).len(), 1);
+    }
+
+    #[test]
+    fn test_regex_mode_reports_capture_spans() {
+        let matcher = build_matcher(r"fn\s+(\w+)_test", true, false).unwrap();
+        let found = search_line(&matcher, "    fn parse_test() {}", true);
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].column, 5);
+        assert_eq!(found[0].highlight, "    ^^^^^^^^^^^^^");
+        assert_eq!(found[0].captures, vec![Some(MatchSpan { start: 8, end: 13, text: "parse".to_string() })]);
+    }
+
+    #[test]
+    fn test_columns_count_characters_not_bytes() {
+        let matcher = build_matcher("wörld", false, true).unwrap();
+        let found = search_line(&matcher, "héllo WÖRLD", false);
+
+        assert_eq!(found[0].column, 7);
+        assert_eq!(found[0].highlight, "      ^^^^^");
+    }
+
+    #[test]
+    fn test_invalid_regex_is_an_error() {
+        let err = build_matcher("fn(", true, false).unwrap_err();
+        assert!(matches!(err, EmpathicError::InvalidRegexPattern { .. }));
+    }
+}
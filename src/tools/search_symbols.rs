@@ -0,0 +1,270 @@
+//! 🔎 Search Symbols Tool - Rust symbol search via LSP with a textual fallback
+//!
+//! When a language server is already running for the project, results come
+//! from `workspace/symbol`, which understands multi-line signatures, macros
+//! and re-exports. The line-based heuristic answers instead when no server
+//! is running or the request fails. Every result says which source found it.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::LazyLock;
+
+use crate::config::Config;
+use crate::error::EmpathicResult;
+use crate::fs::{FileOps, SkipDirs};
+use crate::tools::{SchemaBuilder, ToolBuilder};
+use crate::tools::lsp::offsets::OffsetResolver;
+use crate::lsp::PositionEncoding;
+
+/// Default cap on returned symbols
+const DEFAULT_MAX_RESULTS: usize = 200;
+
+/// Item definitions at the start of a line, after visibility and qualifiers
+static DEFINITION: LazyLock<regex::Regex> = LazyLock::new(|| {
+    regex::Regex::new(concat!(
+        r"^\s*(?:pub(?:\s*\([^)]*\))?\s+)?",
+        r#"(?:(?:async|const|unsafe|default|extern\s+"[^"]*")\s+)*"#,
+        r"(fn|struct|enum|union|trait|type|const|static|mod|macro_rules!)\s*(?:mut\s+)?([A-Za-z_][A-Za-z0-9_]*)",
+    )).expect("valid definition regex")
+});
+
+/// 🔎 Search Symbols Tool
+pub struct SearchSymbolsTool;
+
+#[derive(Deserialize)]
+pub struct SearchSymbolsArgs {
+    query: String,
+    #[serde(default = "default_path")]
+    path: String,
+    max_results: Option<usize>,
+    /// Ask a running language server (default: true)
+    #[serde(default = "default_true")]
+    lsp: bool,
+    project: Option<String>,
+}
+
+/// Which search produced a symbol
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SymbolSource {
+    Lsp,
+    Text,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SymbolMatch {
+    name: String,
+    /// LSP symbol kind name, e.g. "Function", "Struct", "Interface" (trait)
+    kind: String,
+    /// Path relative to the searched directory
+    path: String,
+    /// 1-based line
+    line: usize,
+    /// 1-based column
+    column: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    container: Option<String>,
+    source: SymbolSource,
+}
+
+#[derive(Serialize)]
+pub struct SearchSymbolsOutput {
+    query: String,
+    path: String,
+    symbols: Vec<SymbolMatch>,
+    total_symbols: usize,
+    truncated: bool,
+    /// Whether a running language server answered
+    lsp_used: bool,
+    /// Why the language server wasn't used, when it wasn't
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lsp_unavailable_reason: Option<String>,
+}
+
+fn default_path() -> String {
+    ".".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[async_trait]
+impl ToolBuilder for SearchSymbolsTool {
+    type Args = SearchSymbolsArgs;
+    type Output = SearchSymbolsOutput;
+
+    fn name() -> &'static str {
+        "search_symbols"
+    }
+
+    fn description() -> &'static str {
+        "🔎 Find Rust symbol definitions by name - via a running rust-analyzer, or a text scan when none is running or it fails; each result is tagged lsp or text"
+    }
+
+    fn schema() -> serde_json::Value {
+        SchemaBuilder::new()
+            .required_string("query", "Case-insensitive substring of the symbol name (empty lists all)")
+            .optional_string("path", "Directory to search (default: '.')")
+            .optional_integer("max_results", "Maximum number of symbols to return (default: 200)", Some(1))
+            .optional_bool("lsp", "Use a running language server for the project (never starts one)", Some(true))
+            .optional_string("project", "Project name for path resolution")
+            .build()
+    }
+
+    async fn run(args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
+        let working_dir = config.project_path(args.project.as_deref());
+        let search_path = config.validate_path(&working_dir.join(&args.path))?;
+        crate::tools::validate_dir_exists(&search_path)?;
+        let max_results = args.max_results.unwrap_or(DEFAULT_MAX_RESULTS);
+
+        let (mut symbols, lsp_unavailable_reason) = if args.lsp {
+            match lsp_symbols(&args.query, &search_path, config).await {
+                Ok(symbols) => (symbols, None),
+                Err(reason) => (Vec::new(), Some(reason)),
+            }
+        } else {
+            (Vec::new(), Some("disabled by request".to_string()))
+        };
+        let lsp_used = lsp_unavailable_reason.is_none();
+
+        // 📝 The text scan only stands in for a server that couldn't answer
+        if !lsp_used {
+            symbols = text_symbols(&args.query, &search_path, &config.skip_dirs).await?;
+        }
+        symbols.sort_by(|a, b| (&a.path, a.line, &a.name).cmp(&(&b.path, b.line, &b.name)));
+
+        let total_symbols = symbols.len();
+        symbols.truncate(max_results);
+
+        Ok(SearchSymbolsOutput {
+            query: args.query,
+            path: search_path.to_string_lossy().to_string(),
+            truncated: total_symbols > symbols.len(),
+            symbols,
+            total_symbols,
+            lsp_used,
+            lsp_unavailable_reason,
+        })
+    }
+}
+
+/// 🧠 Ask the project's running server; Err explains why it couldn't
+async fn lsp_symbols(query: &str, search_path: &Path, config: &Config) -> Result<Vec<SymbolMatch>, String> {
+    let lsp_manager = config.lsp_manager().ok_or("LSP manager not available")?;
+    let project_root = search_path.ancestors()
+        .find(|dir| dir.join("Cargo.toml").is_file())
+        .ok_or("not inside a Cargo project")?;
+    let client = lsp_manager.running_client(project_root).await
+        .ok_or("no language server running for the project")?;
+    lsp_manager.flush_pending_changes().await
+        .map_err(|e| format!("flushing pending edits failed: {e}"))?;
+
+    let params = lsp_types::WorkspaceSymbolParams {
+        query: query.to_string(),
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+    let symbols = client.workspace_symbols(params).await
+        .map_err(|e| format!("workspace/symbol failed: {e}"))?
+        .unwrap_or_default();
+
+    // 📏 Columns in chars, like the text scan, whatever encoding the server counts in
+    let mut resolver = OffsetResolver::new(PositionEncoding::TOOL);
+    let mut matches = Vec::new();
+    for symbol in &symbols {
+        let Some(file) = url::Url::parse(symbol.location.uri.as_str()).ok().and_then(|uri| uri.to_file_path().ok()) else {
+            continue;
+        };
+        // workspace/symbol covers the whole workspace, including dependencies
+        let Ok(relative) = file.strip_prefix(search_path) else {
+            continue;
+        };
+        let range = symbol.location.range;
+        let column = match resolver.offsets(&file, range).await {
+            Some(offsets) => offsets.start_char as usize,
+            None => range.start.character as usize,
+        };
+        matches.push(SymbolMatch {
+            name: symbol.name.clone(),
+            kind: format!("{:?}", symbol.kind),
+            path: relative.to_string_lossy().to_string(),
+            line: range.start.line as usize + 1,
+            column: column + 1,
+            container: symbol.container_name.clone(),
+            source: SymbolSource::Lsp,
+        });
+    }
+    Ok(matches)
+}
+
+/// 📝 Scan `.rs` files for definitions whose name contains `query`
+async fn text_symbols(query: &str, search_path: &Path, skip_dirs: &SkipDirs) -> EmpathicResult<Vec<SymbolMatch>> {
+    let needle = query.to_lowercase();
+    let files = FileOps::list_files(search_path, true, false, Some("*.rs"), false, None, skip_dirs).await?;
+
+    let mut symbols = Vec::new();
+    for file in files.iter().filter(|file| !file.is_dir) {
+        let Ok(content) = tokio::fs::read_to_string(&file.path).await else {
+            continue;
+        };
+        let relative = file.path.strip_prefix(search_path).unwrap_or(&file.path)
+            .to_string_lossy().to_string();
+        for (index, line) in content.lines().enumerate() {
+            let Some(symbol) = parse_definition(line) else {
+                continue;
+            };
+            if symbol.name.to_lowercase().contains(&needle) {
+                symbols.push(SymbolMatch { path: relative.clone(), line: index + 1, ..symbol });
+            }
+        }
+    }
+    Ok(symbols)
+}
+
+/// Recognize an item definition on one line (path and line left blank)
+fn parse_definition(line: &str) -> Option<SymbolMatch> {
+    let captures = DEFINITION.captures(line)?;
+    let name = captures.get(2)?;
+    let kind = match &captures[1] {
+        "fn" | "macro_rules!" => "Function",
+        "struct" | "union" => "Struct",
+        "enum" => "Enum",
+        "trait" => "Interface",
+        "type" => "TypeParameter",
+        "const" | "static" => "Constant",
+        "mod" => "Module",
+        _ => return None,
+    };
+    Some(SymbolMatch {
+        name: name.as_str().to_string(),
+        kind: kind.to_string(),
+        path: String::new(),
+        line: 0,
+        column: line[..name.start()].chars().count() + 1,
+        container: None,
+        source: SymbolSource::Text,
+    })
+}
+
+// 🔧 Implement Tool trait using the builder pattern
+crate::impl_tool_for_builder!(SearchSymbolsTool);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_definition() {
+        let parsed = |line: &str| parse_definition(line).map(|s| (s.kind, s.name, s.column));
+        assert_eq!(parsed("pub(crate) async fn load_config("), Some(("Function".into(), "load_config".into(), 21)));
+        assert_eq!(parsed("    pub const fn new() -> Self {"), Some(("Function".into(), "new".into(), 18)));
+        assert_eq!(parsed("const LIMIT: usize = 3;"), Some(("Constant".into(), "LIMIT".into(), 7)));
+        assert_eq!(parsed("static mut COUNTER: u32 = 0;"), Some(("Constant".into(), "COUNTER".into(), 12)));
+        assert_eq!(parsed("pub trait Tool: Send {"), Some(("Interface".into(), "Tool".into(), 11)));
+        assert_eq!(parsed("macro_rules! json_rpc_error {"), Some(("Function".into(), "json_rpc_error".into(), 14)));
+        assert_eq!(parsed("let fn_name = 1;"), None);
+        assert_eq!(parsed("// fn commented()"), None);
+    }
+}
@@ -1,15 +1,14 @@
 //! 🐚 Shell Tool - Clean ToolBuilder implementation
 
 use async_trait::async_trait;
-use crate::error::EmpathicError;
 use serde::{Deserialize, Serialize};
-use std::env;
-use std::process::Stdio;
+use std::collections::HashMap;
 use tokio::process::Command;
 
 use crate::tools::{ToolBuilder, SchemaBuilder};
 use crate::config::Config;
 use crate::error::EmpathicResult;
+use super::executor_utils::{command_env, resolve_timeout, resolve_working_dir, run_with_timeout};
 
 /// 🐚 Shell Tool using modern ToolBuilder pattern
 pub struct ShellTool;
@@ -17,6 +16,12 @@ pub struct ShellTool;
 #[derive(Deserialize)]
 pub struct ShellArgs {
     command: String,
+    timeout_secs: Option<u64>,
+    #[serde(default)]
+    stream: bool,
+    cwd: Option<String>,
+    #[serde(default)]
+    env: HashMap<String, String>,
     project: Option<String>,
 }
 
@@ -47,56 +52,40 @@ impl ToolBuilder for ShellTool {
     fn schema() -> serde_json::Value {
         SchemaBuilder::new()
             .required_string("command", "Shell command to execute")
+            .optional_integer("timeout_secs", "Kill the command and its child processes after this many seconds (default: 120, max: 1800)", Some(1))
+            .optional_bool("stream", "Send stdout/stderr lines as notifications/progress while the command runs (full output is still returned)", Some(false))
+            .optional_string("cwd", "Subdirectory to run in, relative to the project (must stay inside the root)")
+            .optional_string_map("env", "Extra environment variables, e.g. {\"RUST_LOG\": \"debug\"}")
             .optional_string("project", "Project name for execution directory")
             .build()
     }
     
     async fn run(args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
-        let working_dir = config.project_path(args.project.as_deref());
+        let timeout_secs = resolve_timeout(args.timeout_secs)?;
         
-        // Prepare environment with additional paths
-        let mut env_vars = std::collections::HashMap::new();
-        let path_enhanced = if !config.add_path.is_empty() {
-            let current_path = env::var("PATH").unwrap_or_default();
-            let additional_paths: Vec<String> = config.add_path
-                .iter()
-                .map(|p| p.to_string_lossy().to_string())
-                .collect();
-            let new_path = format!("{}:{}", additional_paths.join(":"), current_path);
-            env_vars.insert("PATH".to_string(), new_path);
-            true
-        } else {
-            false
-        };
+        let working_dir = resolve_working_dir(config, args.project.as_deref(), args.cwd.as_deref())?;
+        
+        let (env_vars, path_enhanced) = command_env(config, &args.env)?;
         
         // Use bash for shell command execution
         let mut cmd = Command::new("bash");
         cmd.arg("-c")
            .arg(&args.command)
-           .current_dir(&working_dir)
-           .stdout(Stdio::piped())
-           .stderr(Stdio::piped());
+           .current_dir(&working_dir);
         
         for (key, value) in env_vars {
             cmd.env(key, value);
         }
         
-        let output = cmd.output().await
-            .map_err(|e| EmpathicError::ToolExecutionFailed {
-                tool_name: "shell".to_string(),
-                message: format!("Failed to execute command '{}': {}", args.command, e),
-            })?;
-        
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let stderr = String::from_utf8_lossy(&output.stderr);
+        let output = run_with_timeout(cmd, "shell", &args.command, timeout_secs, args.stream).await?;
         
         Ok(ShellOutput {
             command: args.command,
             working_dir: working_dir.to_string_lossy().to_string(),
             exit_code: output.status.code().unwrap_or(-1),
-            stdout: stdout.trim_end().to_string(),
-            stderr: stderr.trim_end().to_string(),
             success: output.status.success(),
+            stdout: output.stdout,
+            stderr: output.stderr,
             path_enhanced,
         })
     }
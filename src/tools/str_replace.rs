@@ -2,6 +2,8 @@
 //! 
 //! Replaces text that appears EXACTLY ONCE in a file.
 //! Errors if the old string appears 0 or >1 times for safety.
+//! `occurrence` picks the Nth match (or `all`) when the string repeats, and
+//! `expected_count` fails the edit unless the file has exactly that many matches.
 //! 
 //! This is the tool you reach for when you know exactly what to change
 //! and want the operation to fail if your assumption is wrong.
@@ -10,14 +12,43 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
-use crate::tools::ToolBuilder;
+use crate::tools::{ToolBuilder, sync_lsp_document};
+use crate::tools::diff_utils::unified_diff;
 use crate::config::Config;
-use crate::fs::FileOps;
+use crate::fs::{FileOps, LineLayout, TextEncoding};
 use crate::error::{EmpathicResult, EmpathicError};
 
 /// ✂️ Simple String Replace - surgical edits with safety
 pub struct StrReplaceTool;
 
+/// 🎯 Which match to replace: the 1-based Nth, or `"all"`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Occurrence {
+    Nth(usize),
+    All,
+}
+
+impl<'de> Deserialize<'de> for Occurrence {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Index(u64),
+            Name(String),
+        }
+
+        let n = match Raw::deserialize(deserializer)? {
+            Raw::Name(name) if name.eq_ignore_ascii_case("all") => return Ok(Occurrence::All),
+            Raw::Name(name) => name.trim().parse::<usize>().ok(),
+            Raw::Index(n) => usize::try_from(n).ok(),
+        };
+        match n {
+            Some(n) if n >= 1 => Ok(Occurrence::Nth(n)),
+            _ => Err(serde::de::Error::custom("occurrence must be a 1-based match number or \"all\"")),
+        }
+    }
+}
+
 #[derive(Deserialize)]
 pub struct StrReplaceArgs {
     /// Path to the file to edit
@@ -27,6 +58,13 @@ pub struct StrReplaceArgs {
     /// New string to replace with (empty string deletes)
     #[serde(default)]
     new_str: String,
+    /// Replace only this match (1-based) or all of them, instead of requiring uniqueness
+    occurrence: Option<Occurrence>,
+    /// Fail unless the file contains exactly this many matches
+    expected_count: Option<usize>,
+    /// Return a diff instead of writing
+    #[serde(default)]
+    dry_run: bool,
     /// Optional project name for path resolution
     project: Option<String>,
 }
@@ -38,9 +76,20 @@ pub struct StrReplaceOutput {
     old_str: String,
     new_str: String,
     replaced: bool,
+    /// Matches of old_str found in the file
+    count: usize,
+    /// Matches replaced (or that would be, in a dry run)
+    replacements: usize,
+    /// Line of the first replaced match
     line_number: usize,
+    /// Lines of every replaced match, in the original file
+    line_numbers: Vec<usize>,
     context_before: String,
     context_after: String,
+    dry_run: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    diff: Option<String>,
+    encoding: TextEncoding,
 }
 
 #[async_trait]
@@ -53,7 +102,7 @@ impl ToolBuilder for StrReplaceTool {
     }
     
     fn description() -> &'static str {
-        "✂️ Replace a unique string in a file with another string. The string to replace must appear exactly once in the file."
+        "✂️ Replace a unique string in a file with another string. The string to replace must appear exactly once in the file, unless `occurrence` selects the Nth match or `all`; `expected_count` asserts the number of matches first."
     }
     
     fn schema() -> Value {
@@ -74,6 +123,23 @@ impl ToolBuilder for StrReplaceTool {
                     "description": "String to replace with (empty to delete)",
                     "default": ""
                 },
+                "occurrence": {
+                    "oneOf": [
+                        {"type": "integer", "minimum": 1},
+                        {"type": "string", "enum": ["all"]}
+                    ],
+                    "description": "Replace only the Nth match (1-based) or \"all\" matches; by default old_str must be unique"
+                },
+                "expected_count": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Fail without editing unless old_str appears exactly this many times"
+                },
+                "dry_run": {
+                    "type": "boolean",
+                    "description": "Return a unified diff of the change without writing",
+                    "default": false
+                },
                 "project": {
                     "type": "string",
                     "description": "Project name for path resolution"
@@ -93,44 +159,74 @@ impl ToolBuilder for StrReplaceTool {
         
         // Resolve file path
         let working_dir = config.project_path(args.project.as_deref());
-        let file_path = working_dir.join(&args.path);
+        let file_path = config.validate_path(&working_dir.join(&args.path))?;
         
         // Read file content
-        let original_content = FileOps::read_file(&file_path).await?;
+        let (file_content, encoding) = FileOps::read_file_with_encoding(&file_path, None).await?;
+        
+        // ↩️ Match and splice on LF text; the file's own line endings are restored on write
+        let layout = LineLayout::detect(&file_content);
+        let original_content = LineLayout::normalize(&file_content);
+        let (old_str, new_str) = (LineLayout::normalize(&args.old_str), LineLayout::normalize(&args.new_str));
         
         // Count occurrences of old_str
         let occurrences: Vec<usize> = original_content
-            .match_indices(&args.old_str)
+            .match_indices(&old_str)
             .map(|(idx, _)| idx)
             .collect();
         
-        // Safety check: must appear exactly once
-        match occurrences.len() {
-            0 => {
-                return Err(EmpathicError::StrReplaceNotFound {
-                    path: file_path.to_string_lossy().to_string(),
-                    search_str: args.old_str.clone(),
-                });
-            }
-            1 => {
-                // Perfect! Proceed with replacement
-            }
-            n => {
+        let count = occurrences.len();
+        
+        // Count assertion comes first, so a wrong assumption never edits
+        if let Some(expected) = args.expected_count
+            && expected != count
+        {
+            return Err(EmpathicError::StrReplaceCountMismatch {
+                path: file_path.to_string_lossy().to_string(),
+                search_str: args.old_str.clone(),
+                expected,
+                found: count,
+            });
+        }
+        if count == 0 {
+            return Err(EmpathicError::StrReplaceNotFound {
+                path: file_path.to_string_lossy().to_string(),
+                search_str: args.old_str.clone(),
+            });
+        }
+        
+        // Safety check: must appear exactly once unless a match was picked
+        let targets: Vec<usize> = match args.occurrence {
+            None if count > 1 => {
                 return Err(EmpathicError::StrReplaceMultipleMatches {
                     path: file_path.to_string_lossy().to_string(),
                     search_str: args.old_str.clone(),
-                    count: n,
+                    count,
                 });
             }
-        }
+            None => occurrences.clone(),
+            Some(Occurrence::All) => occurrences.clone(),
+            Some(Occurrence::Nth(n)) => match occurrences.get(n - 1) {
+                Some(&pos) => vec![pos],
+                None => {
+                    return Err(EmpathicError::InvalidArgument {
+                        arg: "occurrence".to_string(),
+                        reason: format!("match {} requested but '{}' appears {} time(s)", n, args.old_str, count),
+                    });
+                }
+            },
+        };
         
-        // Perform the replacement (we know it's unique)
-        let new_content = original_content.replacen(&args.old_str, &args.new_str, 1);
+        // Splice the new string in at each target, back to front so offsets stay valid
+        let mut new_content = original_content.clone();
+        for &pos in targets.iter().rev() {
+            new_content.replace_range(pos..pos + old_str.len(), &new_str);
+        }
         
-        // Calculate line number and context
-        let match_pos = occurrences[0];
-        let before_match = &original_content[..match_pos];
-        let line_number = before_match.matches('\n').count() + 1;
+        // Calculate line numbers and context (around the first replaced match)
+        let line_of = |pos: usize| original_content[..pos].matches('\n').count() + 1;
+        let line_numbers: Vec<usize> = targets.iter().map(|&pos| line_of(pos)).collect();
+        let line_number = line_numbers[0];
         
         // Extract context (up to 2 lines before and after)
         let lines: Vec<&str> = original_content.lines().collect();
@@ -143,18 +239,32 @@ impl ToolBuilder for StrReplaceTool {
         let new_context_end = (match_line_idx + 3).min(new_lines.len());
         let context_after = new_lines[match_line_idx..new_context_end].join("\n");
         
-        // Write the modified content back to file
-        FileOps::write_file(&file_path, &new_content).await?;
+        // Write the modified content back to file (dry run only previews)
+        let new_content = layout.restore(&new_content);
+        let diff = if args.dry_run {
+            Some(unified_diff(&file_content, &new_content, &args.path))
+        } else {
+            FileOps::write_file_encoded(&file_path, &new_content, encoding).await?;
+            // 🔄 The server must see the new content, and cached responses are stale now
+            sync_lsp_document(config, &file_path, &new_content).await;
+            None
+        };
         
         Ok(StrReplaceOutput {
             success: true,
             path: file_path.to_string_lossy().to_string(),
             old_str: args.old_str,
             new_str: args.new_str,
-            replaced: true,
+            replaced: !args.dry_run,
+            count,
+            replacements: targets.len(),
             line_number,
+            line_numbers,
             context_before,
             context_after,
+            dry_run: args.dry_run,
+            diff,
+            encoding,
         })
     }
 }
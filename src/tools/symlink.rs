@@ -1,4 +1,9 @@
 //! 🔗 Symlink Tool - Modern ToolBuilder implementation
+//!
+//! Both the link location and the target it resolves to must stay inside
+//! the allowed roots. Targets are given relative to the project; the link
+//! stores either the resolved absolute target or, with `relative`, the path
+//! from the link's directory, so the pair can move together.
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -6,6 +11,7 @@ use serde::{Deserialize, Serialize};
 use crate::tools::{ToolBuilder, SchemaBuilder};
 use crate::config::Config;
 use crate::error::{EmpathicResult, EmpathicError};
+use crate::fs::FileOps;
 
 /// 🔗 Create Symbolic Link Tool using modern ToolBuilder pattern
 pub struct SymlinkTool;
@@ -14,6 +20,9 @@ pub struct SymlinkTool;
 pub struct SymlinkArgs {
     target: String,
     link: String,
+    /// Store the target relative to the link's directory
+    #[serde(default)]
+    relative: bool,
     project: Option<String>,
 }
 
@@ -22,6 +31,11 @@ pub struct SymlinkOutput {
     success: bool,
     target: String,
     link: String,
+    /// Path stored in the link
+    link_target: String,
+    relative: bool,
+    /// Whether the target is a directory (false for files and missing targets)
+    target_is_dir: bool,
     working_dir: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     link_type: Option<String>,
@@ -37,13 +51,14 @@ impl ToolBuilder for SymlinkTool {
     }
     
     fn description() -> &'static str {
-        "🔗 Create symbolic links"
+        "🔗 Create symbolic links to files or directories inside the allowed roots (absolute or relative)"
     }
     
     fn schema() -> serde_json::Value {
         SchemaBuilder::new()
             .required_string("target", "Target path (what the symlink points to)")
             .required_string("link", "Symlink path (where to create the link)")
+            .optional_bool("relative", "Store the target relative to the link's directory instead of as an absolute path", Some(false))
             .optional_string("project", "Project name for path resolution")
             .build()
     }
@@ -52,6 +67,9 @@ impl ToolBuilder for SymlinkTool {
         let working_dir = config.project_path(args.project.as_deref());
         let target_path = working_dir.join(&args.target);
         let link_path = working_dir.join(&args.link);
+        // 🔒 Resolved through existing links, so a target can't escape via one
+        let target_resolved = config.validate_path(&target_path)?;
+        let link_resolved = config.validate_path(&link_path)?;
         
         // Create parent directory for the symlink if needed
         if let Some(parent) = link_path.parent() {
@@ -61,14 +79,25 @@ impl ToolBuilder for SymlinkTool {
                     reason: e.to_string(),
                 })?;
         }
+
+        let link_target = if args.relative {
+            let link_dir = link_resolved.parent().unwrap_or(&link_resolved);
+            FileOps::relative_path(link_dir, &target_resolved)
+        } else {
+            target_resolved.clone()
+        };
+        let target_is_dir = tokio::fs::metadata(&target_resolved).await.is_ok_and(|m| m.is_dir());
         
         // Create the symbolic link with platform-specific logic
-        let link_type = create_symlink(&target_path, &link_path).await?;
+        let link_type = create_symlink(&link_target, &link_path, target_is_dir).await?;
         
         Ok(SymlinkOutput {
             success: true,
-            target: target_path.to_string_lossy().to_string(),
+            target: target_resolved.to_string_lossy().to_string(),
             link: link_path.to_string_lossy().to_string(),
+            link_target: link_target.to_string_lossy().to_string(),
+            relative: args.relative,
+            target_is_dir,
             working_dir: working_dir.to_string_lossy().to_string(),
             link_type,
         })
@@ -76,9 +105,13 @@ impl ToolBuilder for SymlinkTool {
 }
 
 /// Cross-platform symbolic link creation
-async fn create_symlink(target: &std::path::Path, link: &std::path::Path) -> EmpathicResult<Option<String>> {
+///
+/// `target` is stored as given (possibly relative to the link's directory);
+/// `target_is_dir` picks the link kind on Windows, where it can't be inferred.
+async fn create_symlink(target: &std::path::Path, link: &std::path::Path, target_is_dir: bool) -> EmpathicResult<Option<String>> {
     #[cfg(unix)]
     {
+        let _ = target_is_dir;
         tokio::fs::symlink(target, link).await
             .map_err(|e| EmpathicError::FileOperationFailed {
                 operation: "create symlink".to_string(),
@@ -91,8 +124,7 @@ async fn create_symlink(target: &std::path::Path, link: &std::path::Path) -> Emp
     #[cfg(windows)]
     {
         // Windows requires different calls for files vs directories
-        let metadata = tokio::fs::metadata(target).await;
-        if metadata.map(|m| m.is_dir()).unwrap_or(false) {
+        if target_is_dir {
             tokio::fs::symlink_dir(target, link).await
                 .map_err(|e| EmpathicError::FileOperationFailed {
                     operation: "create symlink dir".to_string(),
@@ -113,6 +145,7 @@ async fn create_symlink(target: &std::path::Path, link: &std::path::Path) -> Emp
     
     #[cfg(not(any(unix, windows)))]
     {
+        let _ = (target, link, target_is_dir);
         Err(EmpathicError::NotSupported {
             operation: "symbolic links".to_string(),
             platform: std::env::consts::OS.to_string(),
@@ -0,0 +1,373 @@
+//! 🧪 Test Reports - One result shape for cargo, gradle and JS test runners
+//!
+//! Each runner reports in its own machine-readable format: cargo's libtest
+//! output, Gradle's JUnit XML files, Jest/Vitest `--json` files, Mocha's JSON
+//! reporter and TAP from `node --test`. They are all reduced to the same
+//! counts plus a list of failures with names and messages.
+
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::OnceLock;
+
+use super::cargo_output::{CargoTestReport, TestStatus};
+
+/// 🏃 Test runner a project was detected to use
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TestRunner {
+    Cargo,
+    Gradle,
+    Jest,
+    Vitest,
+    Mocha,
+    NodeTest,
+    /// A `test` script with no recognized runner; TAP output is still parsed
+    Script,
+}
+
+impl TestRunner {
+    /// Runner behind a package.json `test` script
+    pub fn from_test_script(script: &str) -> Self {
+        let words: Vec<&str> = script.split(|c: char| c.is_whitespace() || c == '&' || c == ';').collect();
+        if words.contains(&"vitest") {
+            Self::Vitest
+        } else if words.contains(&"jest") {
+            Self::Jest
+        } else if words.contains(&"mocha") {
+            Self::Mocha
+        } else if script.contains("node --test") {
+            Self::NodeTest
+        } else {
+            Self::Script
+        }
+    }
+}
+
+/// 🔢 Totals across all tests that ran
+#[derive(Serialize, Debug, Default, Clone, PartialEq)]
+pub struct TestCounts {
+    pub total: usize,
+    pub passed: usize,
+    pub failed: usize,
+    pub skipped: usize,
+}
+
+/// ❌ One failed test
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct TestFailure {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Source or test file, when the runner reports it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+}
+
+/// 🧪 Normalized results of one run
+#[derive(Serialize, Debug, Default, Clone, PartialEq)]
+pub struct TestResults {
+    pub counts: TestCounts,
+    pub failures: Vec<TestFailure>,
+    /// Whether machine-readable results were found at all
+    pub parsed: bool,
+}
+
+impl TestResults {
+    /// Add another file's or suite's results to these
+    pub fn merge(&mut self, other: TestResults) {
+        self.counts.total += other.counts.total;
+        self.counts.passed += other.counts.passed;
+        self.counts.failed += other.counts.failed;
+        self.counts.skipped += other.counts.skipped;
+        self.failures.extend(other.failures);
+        self.parsed |= other.parsed;
+    }
+}
+
+/// 🦀 From a parsed `cargo test` run
+pub fn from_cargo(report: &CargoTestReport) -> TestResults {
+    TestResults {
+        counts: TestCounts {
+            total: report.summary.total,
+            passed: report.summary.passed,
+            failed: report.summary.failed,
+            skipped: report.summary.ignored,
+        },
+        failures: report.tests.iter()
+            .filter(|test| test.status == TestStatus::Failed)
+            .map(|test| TestFailure { name: test.name.clone(), message: test.output.clone(), file: None })
+            .collect(),
+        parsed: report.summary.total > 0 || !report.build_failed,
+    }
+}
+
+/// 🐘 From one JUnit XML report (`build/test-results/<task>/TEST-*.xml`)
+pub fn parse_junit_xml(xml: &str) -> TestResults {
+    static TESTCASE: OnceLock<Regex> = OnceLock::new();
+    static PROBLEM: OnceLock<Regex> = OnceLock::new();
+    let testcase = TESTCASE.get_or_init(|| {
+        Regex::new(r"(?s)<testcase\b([^>]*?)(?:/>|>(.*?)</testcase>)").unwrap()
+    });
+    let problem = PROBLEM.get_or_init(|| Regex::new(r"(?s)<(failure|error)\b([^>]*?)(?:/>|>(.*?)</(?:failure|error)>)").unwrap());
+
+    let mut results = TestResults { parsed: true, ..Default::default() };
+    for case in testcase.captures_iter(xml) {
+        let attributes = &case[1];
+        let body = case.get(2).map_or("", |body| body.as_str());
+        results.counts.total += 1;
+
+        if let Some(failure) = problem.captures(body) {
+            results.counts.failed += 1;
+            let name = xml_attribute(attributes, "name").unwrap_or_default();
+            let name = match xml_attribute(attributes, "classname") {
+                Some(class) => format!("{}.{}", class, name),
+                None => name,
+            };
+            let message = xml_attribute(&failure[2], "message")
+                .or_else(|| failure.get(3).map(|text| xml_unescape(text.as_str().trim())))
+                .filter(|message| !message.is_empty());
+            results.failures.push(TestFailure { name, message, file: None });
+        } else if body.contains("<skipped") {
+            results.counts.skipped += 1;
+        } else {
+            results.counts.passed += 1;
+        }
+    }
+    results
+}
+
+/// Value of `name="..."` in an XML attribute list
+fn xml_attribute(attributes: &str, name: &str) -> Option<String> {
+    let pattern = format!(r#"\b{}="([^"]*)""#, regex::escape(name));
+    Regex::new(&pattern).ok()?
+        .captures(attributes)
+        .map(|captures| xml_unescape(&captures[1]))
+}
+
+fn xml_unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#10;", "\n")
+        .replace("&#13;", "\r")
+        .replace("&#9;", "\t")
+        .replace("&amp;", "&")
+}
+
+/// 🃏 From a Jest or Vitest `--json` report
+///
+/// Suites that failed to run at all (syntax errors, missing modules) have no
+/// assertion results; they are reported as a failure named after the file.
+pub fn parse_jest_json(json: &str) -> Option<TestResults> {
+    let report: Value = serde_json::from_str(json).ok()?;
+    let mut results = TestResults { parsed: true, ..Default::default() };
+
+    for suite in report["testResults"].as_array()? {
+        let file = suite["name"].as_str().map(str::to_string);
+        let assertions = suite["assertionResults"].as_array().map(Vec::as_slice).unwrap_or_default();
+        if assertions.is_empty() && suite["status"] == "failed" {
+            results.counts.total += 1;
+            results.counts.failed += 1;
+            results.failures.push(TestFailure {
+                name: file.clone().unwrap_or_else(|| "test suite".to_string()),
+                message: suite["message"].as_str().filter(|m| !m.is_empty()).map(str::to_string),
+                file,
+            });
+            continue;
+        }
+        for assertion in assertions {
+            results.counts.total += 1;
+            match assertion["status"].as_str() {
+                Some("passed") => results.counts.passed += 1,
+                Some("failed") => {
+                    results.counts.failed += 1;
+                    let messages: Vec<&str> = assertion["failureMessages"].as_array()
+                        .into_iter()
+                        .flatten()
+                        .filter_map(Value::as_str)
+                        .collect();
+                    results.failures.push(TestFailure {
+                        name: assertion["fullName"].as_str()
+                            .or_else(|| assertion["title"].as_str())
+                            .unwrap_or_default()
+                            .to_string(),
+                        message: (!messages.is_empty()).then(|| messages.join("\n")),
+                        file: file.clone(),
+                    });
+                }
+                _ => results.counts.skipped += 1, // pending, skipped, todo, disabled
+            }
+        }
+    }
+    Some(results)
+}
+
+/// ☕ From Mocha's `--reporter json` output (the JSON object may follow other output)
+pub fn parse_mocha_json(stdout: &str) -> Option<TestResults> {
+    let start = stdout.lines()
+        .scan(0, |offset, line| {
+            let line_start = *offset;
+            *offset += line.len() + 1;
+            Some((line_start, line))
+        })
+        .find(|(_, line)| line.trim_end() == "{")
+        .map(|(offset, _)| offset)?;
+    let report: Value = serde_json::Deserializer::from_str(&stdout[start..])
+        .into_iter::<Value>()
+        .next()?
+        .ok()?;
+    let stats = &report["stats"];
+    let count = |key: &str| stats[key].as_u64().unwrap_or(0) as usize;
+
+    Some(TestResults {
+        counts: TestCounts {
+            total: count("tests"),
+            passed: count("passes"),
+            failed: count("failures"),
+            skipped: count("pending"),
+        },
+        failures: report["failures"].as_array()?
+            .iter()
+            .map(|failure| TestFailure {
+                name: failure["fullTitle"].as_str().unwrap_or_default().to_string(),
+                message: failure["err"]["message"].as_str().map(str::to_string),
+                file: failure["file"].as_str().map(str::to_string),
+            })
+            .collect(),
+        parsed: true,
+    })
+}
+
+/// 📜 From TAP output (`node --test --test-reporter=tap` and other TAP producers)
+///
+/// Subtests are reported before their parent at a deeper indent; a result
+/// line following deeper ones is the parent and isn't counted itself.
+pub fn parse_tap(stdout: &str) -> TestResults {
+    static RESULT: OnceLock<Regex> = OnceLock::new();
+    let result = RESULT.get_or_init(|| {
+        Regex::new(r"^(\s*)(not ok|ok)\b(?:\s+\d+)?(?:\s+-)?\s*(.*?)(?:\s+#\s*(SKIP|TODO)\b.*)?$").unwrap()
+    });
+
+    let lines: Vec<&str> = stdout.lines().collect();
+    let mut results = TestResults::default();
+    let mut previous_indent: Option<usize> = None;
+    for (index, line) in lines.iter().enumerate() {
+        let Some(captures) = result.captures(line) else {
+            continue;
+        };
+        let indent = captures[1].len();
+        let is_parent = previous_indent.is_some_and(|previous| previous > indent);
+        previous_indent = Some(indent);
+        results.parsed = true;
+        if is_parent {
+            continue;
+        }
+
+        results.counts.total += 1;
+        let name = captures[3].to_string();
+        if captures.get(4).is_some() {
+            results.counts.skipped += 1;
+        } else if &captures[2] == "ok" {
+            results.counts.passed += 1;
+        } else {
+            results.counts.failed += 1;
+            results.failures.push(TestFailure { name, message: tap_error(&lines[index + 1..]), file: None });
+        }
+    }
+    results
+}
+
+/// `error:` from the YAML diagnostics block after a `not ok` line
+fn tap_error(following: &[&str]) -> Option<String> {
+    let block = following.iter().skip_while(|line| line.trim() == "---");
+    for line in block {
+        let line = line.trim();
+        if line == "..." || line.starts_with("ok ") || line.starts_with("not ok ") {
+            break;
+        }
+        if let Some(error) = line.strip_prefix("error:") {
+            let error = error.trim();
+            let error = error.strip_prefix('\'').and_then(|e| e.strip_suffix('\'')).unwrap_or(error);
+            return Some(error.replace("''", "'"));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_runner_from_test_script() {
+        assert_eq!(TestRunner::from_test_script("jest --coverage"), TestRunner::Jest);
+        assert_eq!(TestRunner::from_test_script("vitest run"), TestRunner::Vitest);
+        assert_eq!(TestRunner::from_test_script("tsc && mocha 'test/**/*.js'"), TestRunner::Mocha);
+        assert_eq!(TestRunner::from_test_script("node --test"), TestRunner::NodeTest);
+        assert_eq!(TestRunner::from_test_script("echo \"Error: no test specified\" && exit 1"), TestRunner::Script);
+    }
+
+    #[test]
+    fn test_junit_xml() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuite name="com.example.MathTest" tests="4" skipped="1" failures="1" errors="0">
+  <testcase name="adds" classname="com.example.MathTest" time="0.01"/>
+  <testcase name="divides" classname="com.example.MathTest" time="0.01">
+    <failure message="expected: &lt;2&gt; but was: &lt;3&gt;" type="org.opentest4j.AssertionFailedError">stack</failure>
+  </testcase>
+  <testcase name="later" classname="com.example.MathTest" time="0.0">
+    <skipped/>
+  </testcase>
+  <testcase name="subtracts" classname="com.example.MathTest" time="0.0"></testcase>
+</testsuite>"#;
+        let results = parse_junit_xml(xml);
+        assert_eq!(results.counts, TestCounts { total: 4, passed: 2, failed: 1, skipped: 1 });
+        assert_eq!(results.failures, vec![TestFailure {
+            name: "com.example.MathTest.divides".to_string(),
+            message: Some("expected: <2> but was: <3>".to_string()),
+            file: None,
+        }]);
+    }
+
+    #[test]
+    fn test_jest_json() {
+        let json = r#"{"numTotalTests": 3, "testResults": [
+            {"name": "/app/sum.test.js", "status": "failed", "assertionResults": [
+                {"fullName": "sum adds", "title": "adds", "status": "passed", "failureMessages": []},
+                {"fullName": "sum breaks", "title": "breaks", "status": "failed", "failureMessages": ["Expected 2, received 3"]},
+                {"fullName": "sum later", "title": "later", "status": "pending", "failureMessages": []}
+            ]},
+            {"name": "/app/broken.test.js", "status": "failed", "message": "SyntaxError: Unexpected token", "assertionResults": []}
+        ]}"#;
+        let results = parse_jest_json(json).unwrap();
+        assert_eq!(results.counts, TestCounts { total: 4, passed: 1, failed: 2, skipped: 1 });
+        assert_eq!(results.failures[0].name, "sum breaks");
+        assert_eq!(results.failures[0].message.as_deref(), Some("Expected 2, received 3"));
+        assert_eq!(results.failures[1].name, "/app/broken.test.js");
+        assert!(parse_jest_json("not json").is_none());
+    }
+
+    #[test]
+    fn test_mocha_json_after_script_banner() {
+        let stdout = "\n> demo@1.0.0 test\n> mocha --reporter json\n\n{\n  \"stats\": {\"tests\": 2, \"passes\": 1, \"failures\": 1, \"pending\": 0},\n  \"failures\": [{\"fullTitle\": \"math divides\", \"file\": \"/app/test.js\", \"err\": {\"message\": \"expected 3 to equal 2\"}}]\n}\n";
+        let results = parse_mocha_json(stdout).unwrap();
+        assert_eq!(results.counts, TestCounts { total: 2, passed: 1, failed: 1, skipped: 0 });
+        assert_eq!(results.failures[0].name, "math divides");
+        assert_eq!(results.failures[0].message.as_deref(), Some("expected 3 to equal 2"));
+    }
+
+    #[test]
+    fn test_tap_with_subtests() {
+        let tap = "TAP version 13\n# Subtest: math\n    # Subtest: adds\n    ok 1 - adds\n    # Subtest: breaks\n    not ok 2 - breaks\n      ---\n      error: 'it''s 1 == 2'\n      ...\n    1..2\nnot ok 1 - math\n  ---\n  error: '1 subtest failed'\n  ...\nok 2 - later # SKIP\n1..2\n";
+        let results = parse_tap(tap);
+        assert_eq!(results.counts, TestCounts { total: 3, passed: 1, failed: 1, skipped: 1 });
+        assert_eq!(results.failures, vec![TestFailure {
+            name: "breaks".to_string(),
+            message: Some("it's 1 == 2".to_string()),
+            file: None,
+        }]);
+        assert!(!parse_tap("no tests here").parsed);
+    }
+}
@@ -8,6 +8,7 @@ use std::path::{Path, PathBuf};
 
 use crate::config::Config;
 use crate::error::{EmpathicResult, EmpathicError};
+use crate::fs::TextEncoding;
 
 /// 🏗️ Tool Builder trait - simplifies tool creation
 #[async_trait]
@@ -44,7 +45,10 @@ macro_rules! impl_tool_for_builder {
             
             async fn execute(&self, args: serde_json::Value, config: &$crate::config::Config) -> $crate::error::EmpathicResult<serde_json::Value> {
                 let parsed_args = serde_json::from_value(args)
-                    .map_err(|e| $crate::error::EmpathicError::JsonProcessing { source: e })?;
+                    .map_err(|e| $crate::error::EmpathicError::ToolArgumentsInvalid {
+                        tool_name: <$tool_type as $crate::tools::ToolBuilder>::name().to_string(),
+                        problems: vec![e.to_string()],
+                    })?;
                 
                 let output = <$tool_type as $crate::tools::ToolBuilder>::run(parsed_args, config).await?;
                 $crate::tools::format_json_response(&output)
@@ -101,6 +105,177 @@ pub fn format_json_response<T: serde::Serialize>(data: &T) -> EmpathicResult<Val
     }))
 }
 
+/// 📏 Cap the text of an MCP response at `max_bytes` (0 = unlimited)
+///
+/// Text items are kept in order until the budget runs out; later text items
+/// are dropped. The item that crosses it is trimmed: a JSON payload stays
+/// valid JSON (see `truncate_json`), plain text is cut at a UTF-8 boundary
+/// and ends with a marker saying how many bytes were omitted (plus `hint` on
+/// narrowing the request). Returns the number of bytes omitted.
+pub fn truncate_response(response: &mut Value, max_bytes: usize, hint: Option<&str>) -> Option<usize> {
+    let items = response.get_mut("content")?.as_array_mut()?;
+    let total: usize = items.iter().filter_map(|item| item["text"].as_str()).map(str::len).sum();
+    if max_bytes == 0 || total <= max_bytes {
+        return None;
+    }
+
+    let mut remaining = max_bytes;
+    let mut kept = 0;
+    let mut omitted = total;
+    for item in items.iter_mut() {
+        let Some(text) = item["text"].as_str() else {
+            kept += 1;
+            continue;
+        };
+        if text.len() <= remaining {
+            remaining -= text.len();
+            omitted -= text.len();
+            kept += 1;
+            continue;
+        }
+
+        let trimmed = match serde_json::from_str::<Value>(text) {
+            Ok(payload @ (Value::Object(_) | Value::Array(_))) => {
+                let trimmed = truncate_json(payload, remaining, hint);
+                omitted = omitted.saturating_sub(trimmed.len());
+                trimmed
+            }
+            _ => {
+                let mut end = remaining;
+                while !text.is_char_boundary(end) {
+                    end -= 1;
+                }
+                omitted -= end;
+                let mut marker = format!("\n\n... output truncated, {} bytes omitted", omitted);
+                if let Some(hint) = hint {
+                    marker.push_str(&format!(" ({})", hint));
+                }
+                format!("{}{}", &text[..end], marker)
+            }
+        };
+        item["text"] = Value::String(trimmed);
+        kept += 1;
+        break;
+    }
+    items.truncate(kept);
+    Some(omitted)
+}
+
+/// 📏 Trim a JSON payload until it pretty-prints within `max_bytes`, keeping it valid
+///
+/// Elements are dropped from the end of the largest array first; once no
+/// array is left to trim, the longest string is shortened. The payload gets
+/// `truncated: true`, `omitted` (array elements dropped) and `hint`; a
+/// top-level array is wrapped as `items` to carry them.
+fn truncate_json(payload: Value, max_bytes: usize, hint: Option<&str>) -> String {
+    let mut payload = match payload {
+        Value::Object(_) => payload,
+        items => json!({ "items": items }),
+    };
+    let render = |payload: &mut Value, omitted: usize| {
+        payload["truncated"] = json!(true);
+        payload["omitted"] = json!(omitted);
+        if let Some(hint) = hint {
+            payload["hint"] = json!(hint);
+        }
+        serde_json::to_string_pretty(payload).unwrap_or_default()
+    };
+
+    let mut omitted = 0;
+    loop {
+        let text = render(&mut payload, omitted);
+        if text.len() <= max_bytes {
+            return text;
+        }
+        // The markers are re-added by every render, so they must never be picked for trimming
+        if let Some(map) = payload.as_object_mut() {
+            map.remove("hint");
+        }
+
+        if let Some((_, pointer)) = largest_json_node(&payload, String::new(), &|node| matches!(node, Value::Array(items) if !items.is_empty()))
+            && let Some(Value::Array(items)) = payload.pointer_mut(&pointer)
+        {
+            // Bisect for the longest prefix that fits; if none does, empty it and trim elsewhere
+            let items = std::mem::take(items);
+            let (mut low, mut high) = (0, items.len() - 1);
+            while low < high {
+                let mid = (low + high).div_ceil(2);
+                if let Some(slot) = payload.pointer_mut(&pointer) {
+                    *slot = Value::Array(items[..mid].to_vec());
+                }
+                if render(&mut payload, omitted + items.len() - mid).len() <= max_bytes {
+                    low = mid;
+                } else {
+                    high = mid - 1;
+                }
+            }
+            if let Some(slot) = payload.pointer_mut(&pointer) {
+                *slot = Value::Array(items[..low].to_vec());
+            }
+            omitted += items.len() - low;
+            continue;
+        }
+
+        if let Some((_, pointer)) = largest_json_node(&payload, String::new(), &|node| matches!(node, Value::String(s) if !s.is_empty()))
+            && let Some(Value::String(string)) = payload.pointer_mut(&pointer)
+        {
+            let excess = text.len() - max_bytes;
+            let mut end = string.len().saturating_sub(excess + "…".len()).min(string.len() - 1);
+            while !string.is_char_boundary(end) {
+                end -= 1;
+            }
+            string.truncate(end);
+            if end > 0 {
+                string.push('…');
+            }
+            continue;
+        }
+
+        // Nothing left to trim: better valid and over the cap than cut mid-token
+        return text;
+    }
+}
+
+/// 🔍 Serialized size and JSON pointer of the largest node matching `accept`
+fn largest_json_node(node: &Value, pointer: String, accept: &dyn Fn(&Value) -> bool) -> Option<(usize, String)> {
+    let children: Vec<(String, &Value)> = match node {
+        Value::Object(map) => map.iter()
+            .map(|(key, child)| (format!("{}/{}", pointer, key.replace('~', "~0").replace('/', "~1")), child))
+            .collect(),
+        Value::Array(items) => items.iter().enumerate()
+            .map(|(index, child)| (format!("{}/{}", pointer, index), child))
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let mut best = accept(node).then(|| (serde_json::to_string(node).map_or(0, |text| text.len()), pointer));
+    for (child_pointer, child) in children {
+        if let Some(found) = largest_json_node(child, child_pointer, accept)
+            && best.as_ref().is_none_or(|(size, _)| found.0 > *size)
+        {
+            best = Some(found);
+        }
+    }
+    best
+}
+
+/// 🔍 How to ask a tool for less output, shown when its response was truncated
+pub fn narrowing_hint(tool_name: &str) -> Option<&'static str> {
+    Some(match tool_name {
+        "read_file" => "use start_line/end_line or max_bytes to read a smaller part",
+        "read_file_chunk" => "use a smaller length",
+        "list_files" => "use pattern, max_depth or limit/cursor to list fewer entries",
+        "search_files" => "use a more specific query, a narrower path or pattern, or a smaller max_results",
+        "search_symbols" => "use a more specific query or a smaller max_results",
+        "lsp_workspace_symbols" => "use a more specific query or a smaller limit",
+        "git_diff" => "limit the diff with paths",
+        "git_log" => "use a smaller max_count or a path",
+        "lsp_project_diagnostics" => "use path or a smaller max_files",
+        "shell" | "bash_tool" | "cargo" | "make" | "gradle" | "npm" => "filter the command output, e.g. with head or grep",
+        _ => return None,
+    })
+}
+
 /// 🎯 Path validation and resolution helpers
 /// Get default path for file operations when project is set but path is not provided
 /// Returns "." (project root) if project is set and path is None, otherwise returns the provided path
@@ -125,14 +300,29 @@ pub fn resolve_file_path(
     
     let resolved_path = working_dir.join(file_path);
     
-    // Validate path is within working directory (security check)
-    if !resolved_path.starts_with(&working_dir) {
-        return Err(EmpathicError::InvalidPath { path: resolved_path });
-    }
+    // Validate the resolved path stays inside the allowed roots (security check)
+    config.validate_path(&resolved_path)?;
     
     Ok(resolved_path)
 }
 
+/// 🔄 Push a file an edit tool just wrote to the LSP server, if it has it open
+///
+/// Returns whether the server was synced. Failures are logged, not returned:
+/// the write itself already succeeded.
+pub async fn sync_lsp_document(config: &Config, path: &Path, content: &str) -> bool {
+    let Some(lsp_manager) = config.lsp_manager() else {
+        return false;
+    };
+    match lsp_manager.sync_saved_document(path, content).await {
+        Ok(synced) => synced,
+        Err(e) => {
+            log::warn!("⚠️ LSP sync failed for {}: {}", path.display(), e);
+            false
+        }
+    }
+}
+
 /// Validate file exists and return canonical path
 pub fn validate_file_exists(path: &Path) -> EmpathicResult<PathBuf> {
     if !path.exists() {
@@ -159,6 +349,16 @@ pub fn validate_dir_exists(path: &Path) -> EmpathicResult<PathBuf> {
     Ok(path.to_path_buf())
 }
 
+/// 🔤 Parse an optional `encoding` argument (None means auto-detect)
+pub fn parse_encoding(name: Option<&str>) -> EmpathicResult<Option<TextEncoding>> {
+    name.map(|name| TextEncoding::from_name(name)
+            .ok_or_else(|| EmpathicError::mcp_parameter(
+                "encoding",
+                format!("unsupported encoding '{}' (use utf-8, utf-8-bom, utf-16le, utf-16be or latin1)", name),
+            )))
+        .transpose()
+}
+
 /// Check if file has expected extension
 pub fn validate_file_extension(path: &Path, expected: &str) -> EmpathicResult<()> {
     match path.extension().and_then(|ext| ext.to_str()) {
@@ -226,6 +426,11 @@ impl SchemaBuilder {
         self
     }
     
+    pub fn required_integer(mut self, name: &'static str, desc: &str, minimum: Option<i64>) -> Self {
+        self.required.push(name);
+        self.optional_integer(name, desc, minimum)
+    }
+    
     pub fn optional_integer(mut self, name: &'static str, desc: &str, minimum: Option<i64>) -> Self {
         let mut prop = json!({
             "type": "integer",
@@ -254,6 +459,15 @@ impl SchemaBuilder {
         self
     }
     
+    pub fn optional_string_map(mut self, name: &'static str, desc: &str) -> Self {
+        self.properties.insert(name.to_string(), json!({
+            "type": "object",
+            "additionalProperties": {"type": "string"},
+            "description": desc
+        }));
+        self
+    }
+    
     pub fn build(self) -> Value {
         json!({
             "type": "object",
@@ -340,6 +554,48 @@ mod tests {
         assert_eq!(schema["properties"]["recursive"]["default"], json!(false));
     }
     
+    #[test]
+    fn test_truncate_response() {
+        let mut small = format_text_response("short");
+        assert_eq!(truncate_response(&mut small, 100, None), None);
+        assert_eq!(truncate_response(&mut small, 0, None), None);
+
+        let mut response = json!({"content": [
+            {"type": "text", "text": "héllo world"},
+            {"type": "text", "text": "{\"page\": 2}"}
+        ]});
+        // 'é' is two bytes: a 2-byte budget can't split it
+        assert_eq!(truncate_response(&mut response, 2, Some("narrow it")), Some(22));
+        let items = response["content"].as_array().unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0]["text"], "h\n\n... output truncated, 22 bytes omitted (narrow it)");
+    }
+
+    #[test]
+    fn test_truncate_json_response_stays_valid() {
+        let files: Vec<Value> = (0..100).map(|i| json!({"path": format!("src/file_{i}.rs"), "size": i})).collect();
+        let mut response = format_json_response(&json!({"files": files, "total": 100})).unwrap();
+        assert!(truncate_response(&mut response, 1000, Some("use limit")).is_some());
+
+        let text = response["content"][0]["text"].as_str().unwrap();
+        assert!(text.len() <= 1000, "{} bytes", text.len());
+        let payload: Value = serde_json::from_str(text).unwrap();
+        let kept = payload["files"].as_array().unwrap().len();
+        assert!(kept > 0 && kept < 100);
+        assert_eq!(payload["truncated"], true);
+        assert_eq!(payload["omitted"], 100 - kept);
+        assert_eq!(payload["hint"], "use limit");
+        assert_eq!(payload["total"], 100);
+        assert_eq!(payload["files"][0]["path"], "src/file_0.rs");
+
+        // A long string is shortened once there is no array left to trim
+        let mut response = format_json_response(&json!({"content": "x".repeat(500)})).unwrap();
+        truncate_response(&mut response, 200, None);
+        let payload: Value = serde_json::from_str(response["content"][0]["text"].as_str().unwrap()).unwrap();
+        assert!(payload["content"].as_str().unwrap().ends_with('…'));
+        assert_eq!(payload["truncated"], true);
+    }
+
     #[test]
     fn test_parameter_extraction() {
         let args = json!({
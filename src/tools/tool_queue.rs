@@ -0,0 +1,59 @@
+//! 🚦 Tool Queue Tool - Concurrency limits and current load
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::tools::{ToolBuilder, SchemaBuilder};
+use crate::config::Config;
+use crate::error::EmpathicResult;
+use crate::limiter::LimiterStats;
+
+/// 🚦 Report in-flight and queued tool calls
+pub struct ToolQueueTool;
+
+#[derive(Deserialize)]
+pub struct ToolQueueArgs {
+    // No arguments needed
+}
+
+#[derive(Serialize)]
+pub struct ToolQueueOutput {
+    /// Tool calls currently executing (this call excluded)
+    in_flight: usize,
+    /// Tool calls waiting for a slot
+    queued: usize,
+    /// Global and per-tool limits with their current load
+    #[serde(flatten)]
+    stats: LimiterStats,
+}
+
+#[async_trait]
+impl ToolBuilder for ToolQueueTool {
+    type Args = ToolQueueArgs;
+    type Output = ToolQueueOutput;
+
+    fn name() -> &'static str {
+        "tool_queue"
+    }
+
+    fn description() -> &'static str {
+        "🚦 Show tool concurrency limits with in-flight and queued call counts"
+    }
+
+    fn schema() -> serde_json::Value {
+        SchemaBuilder::new()
+            .build()
+    }
+
+    async fn run(_args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
+        let stats = config.tool_limiter.stats();
+        Ok(ToolQueueOutput {
+            in_flight: stats.global.in_flight,
+            queued: stats.global.queued,
+            stats,
+        })
+    }
+}
+
+// 🔧 Implement Tool trait using the builder pattern
+crate::impl_tool_for_builder!(ToolQueueTool);
@@ -0,0 +1,163 @@
+//! ♻️ Trash Tools - Undo and clean up soft deletes
+//!
+//! `restore_file` moves a trash entry back to where it was deleted from (or
+//! elsewhere); `purge_trash` lists entries with `dry_run` and otherwise
+//! deletes them permanently.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::tools::{ToolBuilder, SchemaBuilder};
+use crate::config::Config;
+use crate::error::EmpathicResult;
+use crate::trash::{Trash, TrashEntry};
+
+/// ⏳ Largest `older_than_hours` accepted (a century)
+const MAX_OLDER_THAN_HOURS: u64 = 100 * 365 * 24;
+
+/// ♻️ Restore File Tool using modern ToolBuilder pattern
+pub struct RestoreFileTool;
+
+/// 🔥 Purge Trash Tool using modern ToolBuilder pattern
+pub struct PurgeTrashTool;
+
+#[derive(Deserialize)]
+pub struct RestoreFileArgs {
+    name: String,
+    /// Restore here instead of the original location
+    to: Option<String>,
+    #[serde(default)]
+    overwrite: bool,
+    project: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct RestoreFileOutput {
+    success: bool,
+    name: String,
+    restored_to: String,
+    was_directory: bool,
+}
+
+#[derive(Deserialize)]
+pub struct PurgeTrashArgs {
+    name: Option<String>,
+    older_than_hours: Option<u64>,
+    #[serde(default)]
+    dry_run: bool,
+}
+
+#[derive(Serialize)]
+pub struct PurgeTrashOutput {
+    success: bool,
+    dry_run: bool,
+    /// Entries deleted, or that would be with dry_run
+    entries: Vec<TrashEntry>,
+    count: usize,
+}
+
+#[async_trait]
+impl ToolBuilder for RestoreFileTool {
+    type Args = RestoreFileArgs;
+    type Output = RestoreFileOutput;
+
+    fn name() -> &'static str {
+        "restore_file"
+    }
+
+    fn description() -> &'static str {
+        "♻️ Restore a soft-deleted file or directory from the trash to its original location"
+    }
+
+    fn schema() -> serde_json::Value {
+        SchemaBuilder::new()
+            .required_string("name", "Trash entry name, as returned by delete_file (trash_name) or purge_trash with dry_run")
+            .optional_string("to", "Restore to this path instead of the original location")
+            .optional_bool("overwrite", "Replace the destination if it already exists", Some(false))
+            .optional_string("project", "Project name for resolving 'to'")
+            .build()
+    }
+
+    async fn run(args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
+        let to = match &args.to {
+            Some(to) => Some(config.safe_project_path(args.project.as_deref())?.join(to)),
+            None => None,
+        };
+        let trash = Trash::new(&config.root_dir);
+
+        // The original location is checked too: roots may have changed since the delete
+        let (entry, destination) = match to {
+            Some(to) => {
+                config.validate_path(&to)?;
+                trash.restore(&args.name, Some(&to), args.overwrite).await?
+            }
+            None => {
+                let original = trash.list().await?.into_iter()
+                    .find(|entry| entry.name == args.name)
+                    .map(|entry| entry.original_path);
+                if let Some(original) = original {
+                    config.validate_path(&original)?;
+                }
+                trash.restore(&args.name, None, args.overwrite).await?
+            }
+        };
+
+        if let Some(lsp_manager) = config.lsp_manager() {
+            if entry.is_dir {
+                lsp_manager.invalidate_project_cache(&destination).await;
+            } else {
+                lsp_manager.invalidate_file_cache(&destination).await;
+            }
+        }
+
+        Ok(RestoreFileOutput {
+            success: true,
+            name: entry.name,
+            restored_to: destination.to_string_lossy().to_string(),
+            was_directory: entry.is_dir,
+        })
+    }
+}
+
+#[async_trait]
+impl ToolBuilder for PurgeTrashTool {
+    type Args = PurgeTrashArgs;
+    type Output = PurgeTrashOutput;
+
+    fn name() -> &'static str {
+        "purge_trash"
+    }
+
+    fn description() -> &'static str {
+        "🔥 Permanently delete soft-deleted entries from the trash (all, one, or older than N hours); dry_run lists them"
+    }
+
+    fn schema() -> serde_json::Value {
+        let mut schema = SchemaBuilder::new()
+            .optional_string("name", "Only this trash entry")
+            .optional_integer("older_than_hours", "Only entries deleted at least this many hours ago", Some(0))
+            .optional_bool("dry_run", "List matching entries without deleting them", Some(false))
+            .build();
+        schema["properties"]["older_than_hours"]["maximum"] = MAX_OLDER_THAN_HOURS.into();
+        schema
+    }
+
+    async fn run(args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
+        let older_than = args.older_than_hours
+            .map(|hours| Duration::from_secs(hours.min(MAX_OLDER_THAN_HOURS).saturating_mul(3600)));
+        let entries = Trash::new(&config.root_dir)
+            .purge(args.name.as_deref(), older_than, args.dry_run).await?;
+
+        Ok(PurgeTrashOutput {
+            success: true,
+            dry_run: args.dry_run,
+            count: entries.len(),
+            entries,
+        })
+    }
+}
+
+// 🔧 Implement Tool trait using the builder pattern
+crate::impl_tool_for_builder!(RestoreFileTool);
+crate::impl_tool_for_builder!(PurgeTrashTool);
@@ -0,0 +1,163 @@
+//! 👀 Watch Tools - Register paths for change notifications
+//!
+//! `watch_paths` adds files or directories to the shared `FileWatcher`; the
+//! server then sends `notifications/resources/updated` for every change it
+//! detects. `unwatch_paths` removes them again.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+
+use crate::tools::ToolBuilder;
+use crate::config::Config;
+use crate::error::{EmpathicResult, EmpathicError};
+
+/// 👀 Watch Paths Tool using modern ToolBuilder pattern
+pub struct WatchPathsTool;
+
+/// 🙈 Unwatch Paths Tool using modern ToolBuilder pattern
+pub struct UnwatchPathsTool;
+
+#[derive(Deserialize)]
+pub struct WatchPathsArgs {
+    paths: Vec<String>,
+    project: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct UnwatchPathsArgs {
+    /// Paths to stop watching - all of them when omitted
+    paths: Option<Vec<String>>,
+    project: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct WatchOutput {
+    success: bool,
+    /// Paths added (watch) or removed (unwatch) by this call
+    changed: Vec<String>,
+    watched: Vec<String>,
+    max_watchers: usize,
+}
+
+fn paths_schema(description: &str, required: bool) -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "paths": {
+                "type": "array",
+                "items": {"type": "string"},
+                "description": description
+            },
+            "project": {
+                "type": "string",
+                "description": "Project name for path resolution"
+            }
+        },
+        "required": if required { json!(["paths"]) } else { json!([]) },
+        "additionalProperties": false
+    })
+}
+
+/// Resolve paths inside the project, refusing anything outside the allowed roots
+pub(crate) fn resolve_paths(paths: &[String], project: Option<&str>, config: &Config) -> EmpathicResult<Vec<PathBuf>> {
+    let working_dir = config.safe_project_path(project)?;
+
+    paths.iter()
+        .map(|path| {
+            let joined = working_dir.join(path);
+            let canonical = joined.canonicalize()
+                .map_err(|_| EmpathicError::FileNotFound { path: joined.clone() })?;
+            if !config.is_path_allowed(&canonical) {
+                return Err(EmpathicError::InvalidPath { path: joined });
+            }
+            Ok(canonical)
+        })
+        .collect()
+}
+
+fn to_strings(paths: Vec<PathBuf>) -> Vec<String> {
+    paths.into_iter().map(|p| p.to_string_lossy().to_string()).collect()
+}
+
+#[async_trait]
+impl ToolBuilder for WatchPathsTool {
+    type Args = WatchPathsArgs;
+    type Output = WatchOutput;
+
+    fn name() -> &'static str {
+        "watch_paths"
+    }
+
+    fn description() -> &'static str {
+        "👀 Watch files/directories for external changes; sends notifications/resources/updated on change"
+    }
+
+    fn schema() -> Value {
+        paths_schema("Files or directories to watch (directories are watched recursively, honouring .gitignore)", true)
+    }
+
+    async fn run(args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
+        if args.paths.is_empty() {
+            return Err(EmpathicError::mcp_parameter("paths", "at least one path is required"));
+        }
+
+        let paths = resolve_paths(&args.paths, args.project.as_deref(), config)?;
+        let watcher = &config.file_watcher;
+        let added = watcher.watch(&paths)?;
+
+        Ok(WatchOutput {
+            success: true,
+            changed: to_strings(added),
+            watched: to_strings(watcher.watched()),
+            max_watchers: watcher.max_watchers(),
+        })
+    }
+}
+
+#[async_trait]
+impl ToolBuilder for UnwatchPathsTool {
+    type Args = UnwatchPathsArgs;
+    type Output = WatchOutput;
+
+    fn name() -> &'static str {
+        "unwatch_paths"
+    }
+
+    fn description() -> &'static str {
+        "🙈 Stop watching paths registered with watch_paths (all when paths is omitted)"
+    }
+
+    fn schema() -> Value {
+        paths_schema("Paths to stop watching (omit to remove all watches)", false)
+    }
+
+    async fn run(args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
+        let watcher = &config.file_watcher;
+        let removed = match args.paths {
+            Some(paths) => {
+                // Watched paths may have been deleted since, so don't require existence
+                let working_dir = config.safe_project_path(args.project.as_deref())?;
+                let paths: Vec<PathBuf> = paths.iter()
+                    .map(|path| {
+                        let joined = working_dir.join(path);
+                        joined.canonicalize().unwrap_or(joined)
+                    })
+                    .collect();
+                watcher.unwatch(Some(&paths))
+            }
+            None => watcher.unwatch(None),
+        };
+
+        Ok(WatchOutput {
+            success: true,
+            changed: to_strings(removed),
+            watched: to_strings(watcher.watched()),
+            max_watchers: watcher.max_watchers(),
+        })
+    }
+}
+
+crate::impl_tool_for_builder!(WatchPathsTool);
+crate::impl_tool_for_builder!(UnwatchPathsTool);
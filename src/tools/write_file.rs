@@ -3,10 +3,11 @@
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
-use crate::tools::{ToolBuilder, SchemaBuilder, default_fs_path};
+use crate::tools::{ToolBuilder, SchemaBuilder, default_fs_path, parse_encoding, sync_lsp_document};
+use crate::tools::diff_utils::unified_diff;
 use crate::config::Config;
-use crate::fs::FileOps;
-use crate::error::EmpathicResult;
+use crate::fs::{FileOps, TextEncoding};
+use crate::error::{EmpathicError, EmpathicResult};
 
 /// ✍️ Write File Tool using modern ToolBuilder pattern
 pub struct WriteFileTool;
@@ -17,6 +18,13 @@ pub struct WriteFileArgs {
     content: String,
     start: Option<usize>,
     end: Option<usize>,
+    #[serde(default)]
+    backup: bool,
+    #[serde(default)]
+    dry_run: bool,
+    #[serde(default)]
+    append: bool,
+    encoding: Option<String>,
     project: Option<String>,
 }
 
@@ -27,6 +35,16 @@ pub struct WriteFileOutput {
     bytes_written: usize,
     start: Option<usize>,
     end: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    backup_path: Option<String>,
+    dry_run: bool,
+    append: bool,
+    /// File size after an append
+    #[serde(skip_serializing_if = "Option::is_none")]
+    total_size: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    diff: Option<String>,
+    encoding: TextEncoding,
     lsp_synced: bool,
 }
 
@@ -40,7 +58,7 @@ impl ToolBuilder for WriteFileTool {
     }
     
     fn description() -> &'static str {
-        "✍️ Write file content with optional line-based replacement"
+        "✍️ Write file content atomically with optional line-based replacement, append mode and backup"
     }
     
     fn schema() -> serde_json::Value {
@@ -49,6 +67,10 @@ impl ToolBuilder for WriteFileTool {
             .required_string("content", "Content to write to the file")
             .optional_integer("start", "Starting line number (0-indexed) for replacement", Some(0))
             .optional_integer("end", "Ending line number (exclusive) for replacement", Some(0))
+            .optional_bool("backup", "Save the previous content to <path>.bak before writing", Some(false))
+            .optional_bool("dry_run", "Return a unified diff of the change without writing", Some(false))
+            .optional_bool("append", "Append content to the end of the file (created if missing) instead of replacing it; cannot be combined with start/end", Some(false))
+            .optional_string("encoding", "Encoding for full writes: utf-8, utf-8-bom, utf-16le, utf-16be, latin1 (default: keep the existing file's encoding)")
             .optional_string("project", "Project name for path resolution")
             .build()
     }
@@ -56,16 +78,74 @@ impl ToolBuilder for WriteFileTool {
     async fn run(args: Self::Args, config: &Config) -> EmpathicResult<Self::Output> {
         let path = default_fs_path(args.path, args.project.as_deref());
         let working_dir = config.project_path(args.project.as_deref());
-        let file_path = working_dir.join(&path);
+        // 🔗 Writes land on the resolved target, so in-root symlinks survive
+        let file_path = config.validate_path(&working_dir.join(&path))?;
+        
+        if args.append && (args.start.is_some() || args.end.is_some()) {
+            return Err(EmpathicError::InvalidArgument {
+                arg: "append".to_string(),
+                reason: "cannot be combined with start/end".to_string(),
+            });
+        }
+        
+        // 🔤 Existing files keep their encoding; line-range edits always do
+        let encoding = match (parse_encoding(args.encoding.as_deref())?, args.start) {
+            (Some(encoding), None) => encoding,
+            _ => FileOps::detect_encoding(&file_path).await,
+        };
+        
+        // 🔍 Dry run: show what would change, touch nothing
+        if args.dry_run {
+            let existing_content = FileOps::read_file(&file_path).await.unwrap_or_default();
+            let new_content = match args.start {
+                Some(start_line) => FileOps::apply_line_range(&existing_content, &args.content, start_line, args.end),
+                None if args.append => format!("{}{}", existing_content, args.content),
+                None => args.content.clone(),
+            };
+            
+            return Ok(WriteFileOutput {
+                success: true,
+                path: file_path.to_string_lossy().to_string(),
+                bytes_written: 0,
+                start: args.start,
+                end: args.end,
+                backup_path: None,
+                dry_run: true,
+                append: args.append,
+                total_size: None,
+                diff: Some(unified_diff(&existing_content, &new_content, &path)),
+                encoding,
+                lsp_synced: false,
+            });
+        }
+        
+        let backup_path = if args.backup {
+            FileOps::backup_file(&file_path).await?
+        } else {
+            None
+        };
         
         // Write the file
+        let mut total_size = None;
         if let Some(start_line) = args.start {
             FileOps::write_file_range(&file_path, &args.content, start_line, args.end).await?;
+        } else if args.append {
+            total_size = Some(FileOps::append_file(&file_path, &args.content, encoding).await?);
         } else {
-            FileOps::write_file(&file_path, &args.content).await?;
+            FileOps::write_file_encoded(&file_path, &args.content, encoding).await?;
         }
         
-        // 🚀 No LSP sync - let rust-analyzer detect changes via file watchers
+        // 🔄 The server must see the new content, and cached responses are stale now.
+        // Range and append writes only know part of the file, so read it back.
+        let written = match args.start {
+            _ if config.lsp_manager().is_none() => None,
+            None if !args.append => Some(args.content.clone()),
+            _ => FileOps::read_file_with_encoding(&file_path, Some(encoding)).await.ok().map(|(content, _)| content),
+        };
+        let lsp_synced = match written {
+            Some(content) => sync_lsp_document(config, &file_path, &content).await,
+            None => false,
+        };
         
         Ok(WriteFileOutput {
             success: true,
@@ -73,7 +153,13 @@ impl ToolBuilder for WriteFileTool {
             bytes_written: args.content.len(),
             start: args.start,
             end: args.end,
-            lsp_synced: false, // 🚀 LSP sync removed for performance
+            backup_path: backup_path.map(|p| p.to_string_lossy().to_string()),
+            dry_run: false,
+            append: args.append,
+            total_size,
+            diff: None,
+            encoding,
+            lsp_synced,
         })
     }
 }
@@ -0,0 +1,235 @@
+//! 🗑️ Trash - Recoverable deletes under `$ROOT_DIR/.empathic/trash`
+//!
+//! A soft delete moves the target into `files/` under a timestamped name and
+//! records where it came from in `info/<name>.json`, so `restore_file` can put
+//! it back and `purge_trash` can drop it for good. Unlike the OS trash this
+//! works in headless containers, and across filesystems via `move_file`.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::error::{EmpathicError, EmpathicResult};
+use crate::fs::FileOps;
+
+/// Trash location relative to the root directory
+pub const TRASH_DIR: &str = ".empathic/trash";
+
+/// 📇 One trashed file or directory
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrashEntry {
+    /// Name inside the trash, used to restore or purge it
+    pub name: String,
+    pub original_path: PathBuf,
+    pub deleted_at: DateTime<Utc>,
+    pub is_dir: bool,
+}
+
+/// 🗑️ The trash of one root directory
+#[derive(Debug, Clone)]
+pub struct Trash {
+    dir: PathBuf,
+}
+
+impl Trash {
+    pub fn new(root_dir: &Path) -> Self {
+        Self { dir: root_dir.join(TRASH_DIR) }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn files_dir(&self) -> PathBuf {
+        self.dir.join("files")
+    }
+
+    fn info_path(&self, name: &str) -> PathBuf {
+        self.dir.join("info").join(format!("{name}.json"))
+    }
+
+    /// Whether `path` is the trash itself or inside it
+    pub fn contains(&self, path: &Path) -> bool {
+        match (path.canonicalize(), self.dir.canonicalize()) {
+            (Ok(path), Ok(dir)) => path.starts_with(dir),
+            _ => path.starts_with(&self.dir),
+        }
+    }
+
+    /// 🗑️ Move `path` into the trash
+    pub async fn put(&self, path: &Path) -> EmpathicResult<TrashEntry> {
+        let metadata = tokio::fs::symlink_metadata(path).await
+            .map_err(|_| EmpathicError::FileNotFound { path: path.to_path_buf() })?;
+        let file_name = path.file_name()
+            .ok_or_else(|| EmpathicError::InvalidPath { path: path.to_path_buf() })?
+            .to_string_lossy()
+            .to_string();
+
+        let deleted_at = Utc::now();
+        let stamp = deleted_at.format("%Y%m%dT%H%M%S%.3fZ");
+        let mut name = format!("{stamp}-{file_name}");
+        let mut attempt = 1;
+        while tokio::fs::symlink_metadata(self.files_dir().join(&name)).await.is_ok() {
+            attempt += 1;
+            name = format!("{stamp}-{attempt}-{file_name}");
+        }
+
+        let entry = TrashEntry {
+            name,
+            original_path: path.to_path_buf(),
+            deleted_at,
+            is_dir: metadata.is_dir(),
+        };
+        // The record goes first: a crash in between leaves a dangling record, never an orphan
+        self.write_info(&entry).await?;
+        if let Err(e) = FileOps::move_file(path, &self.files_dir().join(&entry.name)).await {
+            let _ = tokio::fs::remove_file(self.info_path(&entry.name)).await;
+            return Err(e);
+        }
+        log::info!("🗑️ Moved {} to trash as {}", path.display(), entry.name);
+        Ok(entry)
+    }
+
+    async fn write_info(&self, entry: &TrashEntry) -> EmpathicResult<()> {
+        let info_path = self.info_path(&entry.name);
+        if let Some(parent) = info_path.parent() {
+            tokio::fs::create_dir_all(parent).await
+                .map_err(|e| EmpathicError::DirectoryCreationFailed {
+                    path: parent.to_path_buf(),
+                    reason: e.to_string(),
+                })?;
+        }
+        tokio::fs::write(&info_path, serde_json::to_vec_pretty(entry)?).await
+            .map_err(|e| EmpathicError::FileOperationFailed {
+                operation: "write trash record".to_string(),
+                path: info_path,
+                reason: e.to_string(),
+            })
+    }
+
+    /// 📋 Entries whose content is still in the trash, oldest first
+    pub async fn list(&self) -> EmpathicResult<Vec<TrashEntry>> {
+        let mut entries = Vec::new();
+        let Ok(mut records) = tokio::fs::read_dir(self.dir.join("info")).await else {
+            return Ok(entries);
+        };
+        while let Some(record) = records.next_entry().await? {
+            let Ok(content) = tokio::fs::read(record.path()).await else {
+                continue;
+            };
+            match serde_json::from_slice::<TrashEntry>(&content) {
+                Ok(entry) if tokio::fs::symlink_metadata(self.files_dir().join(&entry.name)).await.is_ok() => {
+                    entries.push(entry);
+                }
+                Ok(_) => {}
+                Err(e) => log::warn!("⚠️ Skipping unreadable trash record {}: {}", record.path().display(), e),
+            }
+        }
+        entries.sort_by(|a, b| (a.deleted_at, &a.name).cmp(&(b.deleted_at, &b.name)));
+        Ok(entries)
+    }
+
+    async fn entry(&self, name: &str) -> EmpathicResult<TrashEntry> {
+        if name.is_empty() || name.contains(['/', '\\']) || name == "." || name == ".." {
+            return Err(EmpathicError::InvalidArgument {
+                arg: "name".to_string(),
+                reason: format!("'{name}' is not a trash entry name"),
+            });
+        }
+        self.list().await?
+            .into_iter()
+            .find(|entry| entry.name == name)
+            .ok_or_else(|| EmpathicError::InvalidArgument {
+                arg: "name".to_string(),
+                reason: format!("no trash entry named '{name}'"),
+            })
+    }
+
+    /// ♻️ Move entry `name` back to `to`, or to where it was deleted from
+    pub async fn restore(&self, name: &str, to: Option<&Path>, overwrite: bool) -> EmpathicResult<(TrashEntry, PathBuf)> {
+        let entry = self.entry(name).await?;
+        let destination = to.map(Path::to_path_buf).unwrap_or_else(|| entry.original_path.clone());
+
+        if let Ok(existing) = tokio::fs::symlink_metadata(&destination).await {
+            if !overwrite {
+                return Err(EmpathicError::FileOperationFailed {
+                    operation: "restore".to_string(),
+                    path: destination,
+                    reason: "destination already exists (set overwrite: true)".to_string(),
+                });
+            }
+            if existing.is_dir() {
+                FileOps::delete_file(&destination, true).await?;
+            }
+        }
+
+        FileOps::move_file(&self.files_dir().join(&entry.name), &destination).await?;
+        let _ = tokio::fs::remove_file(self.info_path(&entry.name)).await;
+        log::info!("♻️ Restored {} to {}", entry.name, destination.display());
+        Ok((entry, destination))
+    }
+
+    /// 🔥 Permanently delete entries, optionally only `name` or those older than `older_than`
+    pub async fn purge(&self, name: Option<&str>, older_than: Option<Duration>, dry_run: bool) -> EmpathicResult<Vec<TrashEntry>> {
+        let entries = match name {
+            Some(name) => vec![self.entry(name).await?],
+            None => self.list().await?,
+        };
+        let cutoff = older_than.and_then(|age| chrono::Duration::from_std(age).ok())
+            .map(|age| Utc::now() - age);
+        let purged: Vec<TrashEntry> = entries.into_iter()
+            .filter(|entry| cutoff.is_none_or(|cutoff| entry.deleted_at <= cutoff))
+            .collect();
+
+        if !dry_run {
+            for entry in &purged {
+                FileOps::delete_file(&self.files_dir().join(&entry.name), true).await?;
+                let _ = tokio::fs::remove_file(self.info_path(&entry.name)).await;
+            }
+        }
+        Ok(purged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_put_restore_and_purge() {
+        let root = tempfile::tempdir().unwrap();
+        let trash = Trash::new(root.path());
+        let file = root.path().join("notes.txt");
+
+        std::fs::write(&file, "first").unwrap();
+        let first = trash.put(&file).await.unwrap();
+        std::fs::write(&file, "second").unwrap();
+        let second = trash.put(&file).await.unwrap();
+        assert!(!file.exists());
+        // Same file deleted twice keeps both copies
+        assert_ne!(first.name, second.name);
+        assert!(first.name.ends_with("-notes.txt"));
+        assert_eq!(trash.list().await.unwrap(), vec![first.clone(), second.clone()]);
+
+        let (_, restored) = trash.restore(&first.name, None, false).await.unwrap();
+        assert_eq!(restored, file);
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "first");
+        assert!(trash.restore(&second.name, None, false).await.is_err(), "restore must not clobber");
+
+        assert!(trash.purge(None, Some(Duration::from_secs(3600)), false).await.unwrap().is_empty());
+        assert_eq!(trash.purge(None, None, false).await.unwrap(), vec![second]);
+        assert!(trash.list().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_entry_names_cannot_escape_trash() {
+        let root = tempfile::tempdir().unwrap();
+        let trash = Trash::new(root.path());
+        for name in ["", "..", "../secret", "a/b"] {
+            assert!(trash.restore(name, None, false).await.is_err(), "{name}");
+        }
+        assert!(trash.contains(&root.path().join(TRASH_DIR).join("files")));
+        assert!(!trash.contains(&root.path().join("src")));
+    }
+}
@@ -0,0 +1,540 @@
+//! 👀 File Watcher - Change detection for watched paths
+//!
+//! Watched files and directories are snapshotted (mtime + size per file) once;
+//! after that the OS watcher (`notify`, falling back to its polling backend
+//! where native watches are unavailable) marks the paths that changed and
+//! `poll()` re-checks only those. The MCP server turns the resulting events
+//! into `notifications/resources/updated` messages. Snapshots honour
+//! `.gitignore` so build output doesn't flood clients with events.
+
+use notify::{EventKind, PollWatcher, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime};
+
+use crate::error::{EmpathicError, EmpathicResult};
+
+/// Default cap on simultaneously watched paths
+pub const DEFAULT_MAX_WATCHERS: usize = 32;
+
+/// Default settle delay before reporting a burst of changes (also the scan
+/// interval of the polling fallback)
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 🔔 Kind of change observed for a path
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Created,
+    Modified,
+    Removed,
+}
+
+/// 🔔 A single observed change
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FileChangeEvent {
+    pub path: PathBuf,
+    pub kind: ChangeKind,
+    /// Watched path that produced the event
+    pub watch_root: PathBuf,
+}
+
+/// Per-file fingerprint used to spot modifications
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Fingerprint {
+    modified: Option<SystemTime>,
+    size: u64,
+}
+
+type Snapshot = BTreeMap<PathBuf, Fingerprint>;
+
+/// Paths reported by the OS watcher since the last `poll()`
+#[derive(Debug, Default)]
+struct Pending {
+    paths: Vec<PathBuf>,
+    /// Events were dropped (queue overflow, watcher error): re-scan every root
+    rescan: bool,
+}
+
+/// A path registered with a `notify` backend, shared by the roots under it
+struct Target {
+    mode: RecursiveMode,
+    roots: usize,
+    polled: bool,
+}
+
+/// A watched path with its last snapshot
+struct Watch {
+    snapshot: Snapshot,
+    /// `notify` path backing this watch (the parent for files)
+    target: PathBuf,
+    is_dir: bool,
+}
+
+#[derive(Default)]
+struct State {
+    watches: HashMap<PathBuf, Watch>,
+    targets: HashMap<PathBuf, Target>,
+    native: Option<RecommendedWatcher>,
+    poll: Option<PollWatcher>,
+}
+
+/// 👀 File watcher shared by the watch tools and the server loop
+pub struct FileWatcher {
+    state: Mutex<State>,
+    pending: Arc<Mutex<Pending>>,
+    wake: Arc<tokio::sync::Notify>,
+    max_watchers: usize,
+    poll_interval: Duration,
+}
+
+impl FileWatcher {
+    /// Create a watcher with an explicit cap and settle interval
+    ///
+    /// OS watches are only set up once the first path is watched.
+    pub fn new(max_watchers: usize, poll_interval: Duration) -> Self {
+        Self {
+            state: Mutex::new(State::default()),
+            pending: Arc::new(Mutex::new(Pending::default())),
+            wake: Arc::new(tokio::sync::Notify::new()),
+            max_watchers,
+            poll_interval,
+        }
+    }
+
+    pub fn poll_interval(&self) -> Duration {
+        self.poll_interval
+    }
+
+    pub fn max_watchers(&self) -> usize {
+        self.max_watchers
+    }
+
+    /// ➕ Start watching paths, returning the ones newly added
+    ///
+    /// Fails without registering anything if the cap would be exceeded.
+    pub fn watch(&self, paths: &[PathBuf]) -> EmpathicResult<Vec<PathBuf>> {
+        for path in paths {
+            if !path.exists() {
+                return Err(EmpathicError::FileNotFound { path: path.clone() });
+            }
+        }
+
+        let mut state = self.lock();
+        let mut new_paths: Vec<PathBuf> = Vec::new();
+        for path in paths {
+            if !state.watches.contains_key(path) && !new_paths.contains(path) {
+                new_paths.push(path.clone());
+            }
+        }
+
+        if state.watches.len() + new_paths.len() > self.max_watchers {
+            return Err(EmpathicError::InvalidArgument {
+                arg: "paths".to_string(),
+                reason: format!(
+                    "watching {} more path(s) would exceed the limit of {} (currently {})",
+                    new_paths.len(), self.max_watchers, state.watches.len()
+                ),
+            });
+        }
+
+        for (index, path) in new_paths.iter().enumerate() {
+            let (target, mode) = watch_target(path);
+            if let Err(e) = self.add_target(&mut state, &target, mode) {
+                for added in &new_paths[..index] {
+                    if let Some(watch) = state.watches.remove(added) {
+                        Self::release_target(&mut state, &watch.target);
+                    }
+                }
+                return Err(EmpathicError::FileOperationFailed {
+                    operation: "watch".to_string(),
+                    path: path.clone(),
+                    reason: e.to_string(),
+                });
+            }
+            let watch = Watch { snapshot: scan(path), target, is_dir: mode == RecursiveMode::Recursive };
+            state.watches.insert(path.clone(), watch);
+        }
+        Ok(new_paths)
+    }
+
+    /// ➖ Stop watching paths (all of them when `paths` is None), returning those removed
+    pub fn unwatch(&self, paths: Option<&[PathBuf]>) -> Vec<PathBuf> {
+        let mut state = self.lock();
+        let removed: Vec<(PathBuf, Watch)> = match paths {
+            Some(paths) => paths.iter()
+                .filter_map(|path| state.watches.remove_entry(path))
+                .collect(),
+            None => state.watches.drain().collect(),
+        };
+        removed.into_iter()
+            .map(|(path, watch)| {
+                Self::release_target(&mut state, &watch.target);
+                path
+            })
+            .collect()
+    }
+
+    /// 📋 Currently watched paths (sorted)
+    pub fn watched(&self) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = self.lock().watches.keys().cloned().collect();
+        paths.sort();
+        paths
+    }
+
+    /// ⏳ Resolve once the OS watcher has reported something since the last wake-up
+    pub async fn changed(&self) {
+        self.wake.notified().await;
+    }
+
+    /// 🔍 Re-check the paths the OS watcher flagged and report what changed since
+    /// the last snapshot
+    ///
+    /// Flagged directories are re-scanned as a whole, so files created before
+    /// their new directory got its own watch are not missed.
+    pub fn poll(&self) -> Vec<FileChangeEvent> {
+        let Pending { paths, rescan } = std::mem::take(&mut *lock(&self.pending));
+        if paths.is_empty() && !rescan {
+            return Vec::new();
+        }
+
+        let mut state = self.lock();
+        let mut events = Vec::new();
+        for (root, watch) in state.watches.iter_mut() {
+            let dirty: Vec<PathBuf> = if rescan || paths.iter().any(|path| path == root) {
+                vec![root.clone()]
+            } else if watch.is_dir {
+                paths.iter().filter(|path| path.starts_with(root)).cloned().collect()
+            } else {
+                continue; // File roots only care about themselves
+            };
+            refresh(root, &mut watch.snapshot, dirty, &mut events);
+        }
+
+        events.sort_by(|a, b| a.path.cmp(&b.path));
+        events
+    }
+
+    /// Register an OS watch, or share the one already on `target`
+    ///
+    /// The native backend is tried first; paths it can't watch (unsupported
+    /// filesystem, inotify limits) fall back to the polling backend.
+    fn add_target(&self, state: &mut State, target: &Path, mode: RecursiveMode) -> notify::Result<()> {
+        if let Some(existing) = state.targets.get(target) {
+            let upgrade = existing.mode == RecursiveMode::NonRecursive && mode == RecursiveMode::Recursive;
+            if upgrade {
+                // A parent-of-file watch now has to cover the whole tree
+                let polled = existing.polled;
+                self.backend(state, polled)?.watch(target, mode)?;
+            }
+            if let Some(existing) = state.targets.get_mut(target) {
+                if upgrade {
+                    existing.mode = mode;
+                }
+                existing.roots += 1;
+            }
+            return Ok(());
+        }
+
+        let polled = match self.backend(state, false).and_then(|backend| backend.watch(target, mode)) {
+            Ok(()) => false,
+            Err(e) => {
+                log::warn!("👀 Native watch on {} failed, polling instead: {}", target.display(), e);
+                self.backend(state, true)?.watch(target, mode)?;
+                true
+            }
+        };
+        state.targets.insert(target.to_path_buf(), Target { mode, roots: 1, polled });
+        Ok(())
+    }
+
+    fn release_target(state: &mut State, target: &Path) {
+        let Some(existing) = state.targets.get_mut(target) else {
+            return;
+        };
+        existing.roots -= 1;
+        if existing.roots > 0 {
+            return;
+        }
+
+        let polled = existing.polled;
+        state.targets.remove(target);
+        let backend: Option<&mut dyn Watcher> = if polled {
+            state.poll.as_mut().map(|watcher| watcher as &mut dyn Watcher)
+        } else {
+            state.native.as_mut().map(|watcher| watcher as &mut dyn Watcher)
+        };
+        if let Some(backend) = backend {
+            // Fails when the target itself is gone - nothing left to release then
+            backend.unwatch(target).ok();
+        }
+    }
+
+    /// The native or polling backend, created on first use
+    fn backend<'a>(&self, state: &'a mut State, polled: bool) -> notify::Result<&'a mut dyn Watcher> {
+        if polled {
+            let watcher = match state.poll.take() {
+                Some(watcher) => watcher,
+                None => PollWatcher::new(self.handler(), notify::Config::default().with_poll_interval(self.poll_interval))?,
+            };
+            Ok(state.poll.insert(watcher))
+        } else {
+            let watcher = match state.native.take() {
+                Some(watcher) => watcher,
+                None => RecommendedWatcher::new(self.handler(), notify::Config::default())?,
+            };
+            Ok(state.native.insert(watcher))
+        }
+    }
+
+    /// Collect event paths for the next `poll()` and wake the server loop
+    fn handler(&self) -> impl notify::EventHandler {
+        let pending = Arc::clone(&self.pending);
+        let wake = Arc::clone(&self.wake);
+        move |result: notify::Result<notify::Event>| {
+            {
+                let mut pending = lock(&pending);
+                match result {
+                    Ok(event) => {
+                        pending.rescan |= event.need_rescan();
+                        if matches!(event.kind, EventKind::Access(_)) {
+                            return; // Reads never change a fingerprint
+                        }
+                        pending.paths.extend(event.paths);
+                    }
+                    Err(e) => {
+                        log::warn!("👀 File watcher error: {}", e);
+                        pending.rescan = true;
+                    }
+                }
+            }
+            wake.notify_one();
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        lock(&self.state)
+    }
+}
+
+impl std::fmt::Debug for FileWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FileWatcher")
+            .field("watched", &self.watched())
+            .field("max_watchers", &self.max_watchers)
+            .field("poll_interval", &self.poll_interval)
+            .finish()
+    }
+}
+
+impl Default for FileWatcher {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_WATCHERS, DEFAULT_POLL_INTERVAL)
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    // A panic mid-update leaves at worst a stale snapshot
+    mutex.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Directories are watched recursively; files through their parent
+fn watch_target(path: &Path) -> (PathBuf, RecursiveMode) {
+    match path.parent() {
+        Some(parent) if !path.is_dir() => (parent.to_path_buf(), RecursiveMode::NonRecursive),
+        _ => (path.to_path_buf(), RecursiveMode::Recursive),
+    }
+}
+
+/// Bring the flagged parts of `snapshot` up to date, recording the differences
+fn refresh(root: &Path, snapshot: &mut Snapshot, mut dirty: Vec<PathBuf>, events: &mut Vec<FileChangeEvent>) {
+    // A flagged directory covers everything below it
+    dirty.sort();
+    dirty.dedup_by(|path, kept| path.starts_with(kept));
+
+    let mut listings = HashMap::new();
+    for path in dirty {
+        let current = if path == root {
+            scan(root)
+        } else if visible(root, &path, &mut listings) {
+            scan(&path)
+        } else {
+            Snapshot::new()
+        };
+        let previous: Vec<(PathBuf, Fingerprint)> = snapshot.range(path.clone()..)
+            .take_while(|(known, _)| known.starts_with(&path))
+            .map(|(known, fingerprint)| (known.clone(), *fingerprint))
+            .collect();
+
+        for (known, fingerprint) in &previous {
+            let kind = match current.get(known) {
+                None => ChangeKind::Removed,
+                Some(now) if now != fingerprint => ChangeKind::Modified,
+                Some(_) => continue,
+            };
+            events.push(FileChangeEvent { path: known.clone(), kind, watch_root: root.to_path_buf() });
+        }
+        for created in current.keys().filter(|path| !previous.iter().any(|(known, _)| known == *path)) {
+            events.push(FileChangeEvent { path: created.clone(), kind: ChangeKind::Created, watch_root: root.to_path_buf() });
+        }
+
+        for (known, _) in previous {
+            snapshot.remove(&known);
+        }
+        snapshot.extend(current);
+    }
+}
+
+/// Whether `path` (below `root`) would show up in a scan of `root`
+///
+/// Checks each directory level with a one-deep walk so ignore rules from any
+/// ancestor apply, caching the listings for the rest of the refresh.
+fn visible(root: &Path, path: &Path, listings: &mut HashMap<PathBuf, Vec<PathBuf>>) -> bool {
+    if path == root {
+        return true;
+    }
+    let Some(parent) = path.parent().filter(|parent| parent.starts_with(root)) else {
+        return false;
+    };
+    if !visible(root, parent, listings) {
+        return false;
+    }
+    listings.entry(parent.to_path_buf())
+        .or_insert_with(|| walker(parent).max_depth(Some(1)).build()
+            .flatten()
+            .map(|entry| entry.into_path())
+            .collect())
+        .iter()
+        .any(|entry| entry == path)
+}
+
+fn walker(path: &Path) -> ignore::WalkBuilder {
+    let mut builder = ignore::WalkBuilder::new(path);
+    builder.hidden(false)
+        .require_git(false)
+        .add_custom_ignore_filename(crate::fs::EMPATHIC_IGNORE);
+    builder
+}
+
+/// Fingerprint a file, or every non-ignored file under a directory
+fn scan(path: &Path) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+
+    if path.is_file() {
+        if let Some(fingerprint) = fingerprint(path) {
+            snapshot.insert(path.to_path_buf(), fingerprint);
+        }
+        return snapshot;
+    }
+    if !path.is_dir() {
+        return snapshot; // Gone
+    }
+
+    for entry in walker(path).build().flatten() {
+        if entry.file_type().is_some_and(|ft| ft.is_file())
+            && let Some(fingerprint) = fingerprint(entry.path())
+        {
+            snapshot.insert(entry.path().to_path_buf(), fingerprint);
+        }
+    }
+    snapshot
+}
+
+fn fingerprint(path: &Path) -> Option<Fingerprint> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some(Fingerprint {
+        modified: metadata.modified().ok(),
+        size: metadata.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    /// Give the OS watcher time to deliver, then collect like the server loop does
+    fn settle_and_poll(watcher: &FileWatcher) -> Vec<(PathBuf, ChangeKind)> {
+        std::thread::sleep(Duration::from_millis(500));
+        watcher.poll().into_iter()
+            .map(|event| (event.path, event.kind))
+            .collect()
+    }
+
+    #[test]
+    fn test_poll_reports_created_modified_removed() {
+        let dir = tempdir().unwrap();
+        let kept = dir.path().join("kept.rs");
+        let removed = dir.path().join("removed.rs");
+        std::fs::write(&kept, "fn a() {}").unwrap();
+        std::fs::write(&removed, "fn b() {}").unwrap();
+
+        let watcher = FileWatcher::new(4, DEFAULT_POLL_INTERVAL);
+        watcher.watch(&[dir.path().to_path_buf()]).unwrap();
+        assert!(watcher.poll().is_empty());
+
+        // Size change guarantees detection even with coarse mtimes
+        std::fs::write(&kept, "fn a() { changed(); }").unwrap();
+        std::fs::remove_file(&removed).unwrap();
+        let created = dir.path().join("sub/new.rs");
+        std::fs::create_dir_all(created.parent().unwrap()).unwrap();
+        std::fs::write(&created, "").unwrap();
+
+        assert_eq!(settle_and_poll(&watcher), vec![
+            (kept, ChangeKind::Modified),
+            (removed, ChangeKind::Removed),
+            (created, ChangeKind::Created),
+        ]);
+        assert!(watcher.poll().is_empty());
+    }
+
+    #[test]
+    fn test_file_root_survives_atomic_save_and_ignored_files_stay_quiet() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("main.rs");
+        std::fs::write(&file, "fn main() {}").unwrap();
+        std::fs::write(dir.path().join(".gitignore"), "target/\n").unwrap();
+        std::fs::create_dir(dir.path().join("target")).unwrap();
+
+        let watcher = FileWatcher::new(4, DEFAULT_POLL_INTERVAL);
+        watcher.watch(&[file.clone(), dir.path().to_path_buf()]).unwrap();
+
+        // Temp file + rename, the way the editing tools save
+        let temp = dir.path().join(".main.rs.tmp");
+        std::fs::write(&temp, "fn main() { saved(); }").unwrap();
+        std::fs::rename(&temp, &file).unwrap();
+        std::fs::write(dir.path().join("target/out.o"), "obj").unwrap();
+
+        // Once per root: the file itself and the directory holding it
+        assert_eq!(settle_and_poll(&watcher), vec![(file.clone(), ChangeKind::Modified), (file, ChangeKind::Modified)]);
+    }
+
+    #[test]
+    fn test_watch_cap_and_unwatch() {
+        let dir = tempdir().unwrap();
+        let paths: Vec<PathBuf> = (0..3).map(|i| {
+            let path = dir.path().join(format!("{}.txt", i));
+            std::fs::write(&path, "x").unwrap();
+            path
+        }).collect();
+
+        let watcher = FileWatcher::new(2, DEFAULT_POLL_INTERVAL);
+        assert_eq!(watcher.watch(&paths[..2]).unwrap().len(), 2);
+        // Re-watching is a no-op, not a cap violation
+        assert!(watcher.watch(&paths[..1]).unwrap().is_empty());
+        assert!(watcher.watch(&paths[2..]).is_err());
+
+        assert_eq!(watcher.unwatch(Some(&paths[..1])), vec![paths[0].clone()]);
+        assert_eq!(watcher.watch(&paths[2..]).unwrap().len(), 1);
+        assert_eq!(watcher.unwatch(None).len(), 2);
+        assert!(watcher.watched().is_empty());
+    }
+
+    #[test]
+    fn test_watch_missing_path_fails() {
+        let watcher = FileWatcher::new(2, DEFAULT_POLL_INTERVAL);
+        assert!(watcher.watch(&[PathBuf::from("/definitely/not/here")]).is_err());
+    }
+}
@@ -0,0 +1,54 @@
+//! 📂 Allowed roots tests - Paths under any configured root are accepted
+
+mod common;
+
+use anyhow::Result;
+use common::*;
+use empathic::tools::{Tool, shell::ShellTool, watch::WatchPathsTool};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_path_under_second_root_is_allowed() -> Result<()> {
+    let mut env = TestEnv::new()?;
+    let second = tempfile::TempDir::new()?;
+    std::fs::create_dir(second.path().join("shared"))?;
+    let shared = second.path().join("shared").to_string_lossy().to_string();
+
+    // Only ROOT_DIR configured: the second root is off limits
+    let result = ShellTool.execute(json!({"command": "pwd", "cwd": shared}), &env.config).await;
+    assert!(result.is_err());
+
+    env.config.allowed_dirs.push(second.path().to_path_buf());
+    let result = ShellTool.execute(json!({"command": "pwd", "cwd": shared}), &env.config).await?;
+    assert!(McpResult::parse(result)?.content["stdout"].as_str().unwrap().ends_with("/shared"));
+
+    let result = WatchPathsTool.execute(json!({"paths": [shared]}), &env.config).await?;
+    assert_eq!(McpResult::parse(result)?.content["watched"].as_array().unwrap().len(), 1);
+
+    println!("✅ Second root accepted");
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_symlink_cannot_escape_all_roots() -> Result<()> {
+    let mut env = TestEnv::new()?;
+    let second = tempfile::TempDir::new()?;
+    let outside = tempfile::TempDir::new()?;
+    env.config.allowed_dirs.push(second.path().to_path_buf());
+
+    // 🔗 A link into another allowed root resolves fine
+    std::os::unix::fs::symlink(second.path(), env.root_path.join("to_second"))?;
+    let result = ShellTool.execute(json!({"command": "true", "cwd": "to_second"}), &env.config).await;
+    assert!(result.is_ok());
+
+    // 🔗 A link out of every root is rejected after canonicalization
+    std::os::unix::fs::symlink(outside.path(), second.path().join("escape"))?;
+    let escape = second.path().join("escape").to_string_lossy().to_string();
+    let result = ShellTool.execute(json!({"command": "true", "cwd": escape}), &env.config).await;
+    assert!(result.is_err());
+
+    let result = WatchPathsTool.execute(json!({"paths": [escape]}), &env.config).await;
+    assert!(result.is_err());
+    Ok(())
+}
@@ -0,0 +1,64 @@
+//! 📦 Batch request tests - JSON-RPC arrays through the request handler
+
+mod common;
+
+use anyhow::Result;
+use common::*;
+use empathic::mcp::handlers::RequestHandler;
+use empathic::tools::get_all_tools;
+use serde_json::json;
+use std::collections::HashMap;
+
+#[tokio::test]
+async fn test_batch_preserves_ids_and_skips_notifications() -> Result<()> {
+    let env = TestEnv::new()?;
+    let tools = get_all_tools().into_iter()
+        .map(|tool| (tool.name().to_string(), tool))
+        .collect::<HashMap<_, _>>();
+    let handler = RequestHandler::new(&env.config, &tools);
+
+    let batch = vec![
+        json!({"jsonrpc": "2.0", "id": "a", "method": "tools/list"}),
+        json!({"jsonrpc": "2.0", "method": "notifications/initialized"}),
+        json!({"jsonrpc": "2.0", "id": 7, "method": "prompts/list"}),
+        json!({"jsonrpc": "2.0", "id": 8, "method": "no/such/method"}),
+    ];
+    let responses = handler.handle_batch(batch).await.unwrap();
+
+    let ids: Vec<_> = responses.iter().map(|r| r.id.clone().unwrap()).collect();
+    assert_eq!(ids, vec![json!("a"), json!(7), json!(8)]);
+    assert!(responses[0].result.as_ref().unwrap()["tools"].is_array());
+    assert!(responses[1].error.is_none());
+    assert_eq!(responses[2].error.as_ref().unwrap().code, -32601);
+
+    println!("✅ Batch responses correlate by id");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_malformed_member_gets_its_own_error() -> Result<()> {
+    let env = TestEnv::new()?;
+    let tools = HashMap::new();
+    let handler = RequestHandler::new(&env.config, &tools);
+
+    let batch = vec![
+        json!({"jsonrpc": "2.0", "id": 1, "params": {}}),
+        json!(42),
+        json!({"jsonrpc": "2.0", "id": 2, "method": "prompts/list"}),
+    ];
+    let responses = handler.handle_batch(batch).await.unwrap();
+    assert_eq!(responses.len(), 3);
+    assert_eq!(responses[0].id, Some(json!(1)));
+    assert_eq!(responses[0].error.as_ref().unwrap().code, -32600);
+    assert_eq!(responses[1].id, None);
+    assert_eq!(responses[1].error.as_ref().unwrap().code, -32600);
+    assert!(responses[2].result.is_some());
+
+    // Notifications only: nothing to send back
+    let only_notifications = vec![json!({"jsonrpc": "2.0", "method": "notifications/initialized"})];
+    assert!(handler.handle_batch(only_notifications).await.is_none());
+
+    let empty = handler.handle_batch(Vec::new()).await.unwrap();
+    assert_eq!(empty[0].error.as_ref().unwrap().code, -32600);
+    Ok(())
+}
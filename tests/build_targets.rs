@@ -0,0 +1,43 @@
+//! 🎯 Target discovery tests - make list_targets and gradle list_tasks
+
+mod common;
+
+use anyhow::Result;
+use common::*;
+use empathic::tools::{Tool, make::MakeTool, gradle::GradleTool};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_make_list_targets() -> Result<()> {
+    let env = TestEnv::new()?;
+    env.create_file("Makefile", ".PHONY: all lint\nall: app ## Build the app\n\ttouch app\n\napp:\n\ttrue\n\nlint:\n\ttrue\n").await?;
+
+    let result = MakeTool.execute(json!({"list_targets": true}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["makefile"], "Makefile");
+    assert_eq!(parsed.content["default_target"], "all");
+    let targets = parsed.content["targets"].as_array().unwrap();
+    assert_eq!(targets.len(), 3);
+    assert_eq!(targets[0]["description"], "Build the app");
+    assert_eq!(targets[1]["phony"], false);
+    assert!(!env.root_path.join("app").exists(), "listing must not run make");
+
+    // 🗄️ An edited Makefile invalidates the cached listing
+    std::thread::sleep(std::time::Duration::from_millis(20));
+    env.create_file("Makefile", "release:\n\ttrue\n").await?;
+    let result = MakeTool.execute(json!({"list_targets": true}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["targets"][0]["name"], "release");
+
+    println!("✅ make target listing works");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_listing_requires_build_files() -> Result<()> {
+    let env = TestEnv::new()?;
+
+    assert!(MakeTool.execute(json!({"list_targets": true}), &env.config).await.is_err());
+    assert!(GradleTool.execute(json!({"list_tasks": true}), &env.config).await.is_err());
+    Ok(())
+}
@@ -0,0 +1,162 @@
+//! 🦀 Cargo tool tests - Structured test results and compile errors
+
+mod common;
+
+use anyhow::Result;
+use common::*;
+use empathic::tools::{Tool, cargo::CargoTool};
+use serde_json::json;
+
+const MANIFEST: &str = "[package]\nname = \"sample\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[workspace]\n";
+
+#[tokio::test]
+async fn test_cargo_structured_test_results() -> Result<()> {
+    let env = TestEnv::new()?;
+    env.create_files(&[
+        ("sample/Cargo.toml", MANIFEST),
+        ("sample/src/lib.rs", "#[cfg(test)]\nmod tests {\n    #[test]\n    fn passes() {}\n\n    #[test]\n    fn fails() {\n        println!(\"captured\");\n        assert_eq!(1, 2);\n    }\n\n    #[test]\n    #[ignore]\n    fn skipped() {}\n}\n"),
+    ]).await?;
+
+    let result = CargoTool.execute(json!({"args": ["test"], "structured": true, "project": "sample"}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+
+    assert_eq!(parsed.content["success"], false);
+    assert_eq!(parsed.content["build_failed"], false);
+    assert_eq!(parsed.content["summary"], json!({"passed": 1, "failed": 1, "ignored": 1, "total": 3}));
+    let failed = parsed.content["tests"].as_array().unwrap().iter()
+        .find(|t| t["status"] == "failed")
+        .unwrap();
+    assert_eq!(failed["name"], "tests::fails");
+    assert!(failed["output"].as_str().unwrap().contains("captured"));
+    assert!(parsed.content["raw"]["stdout"].is_string());
+
+    println!("✅ Structured cargo test works");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cargo_structured_compile_error() -> Result<()> {
+    let env = TestEnv::new()?;
+    env.create_files(&[
+        ("broken/Cargo.toml", &MANIFEST.replace("sample", "broken")),
+        ("broken/src/lib.rs", "pub fn value() -> u32 {\n    \"nope\"\n}\n"),
+    ]).await?;
+
+    let result = CargoTool.execute(json!({"args": ["test"], "structured": true, "project": "broken"}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+
+    assert_eq!(parsed.content["build_failed"], true);
+    assert_eq!(parsed.content["summary"]["total"], 0);
+    let error = &parsed.content["compile_errors"][0];
+    assert_eq!(error["code"], "E0308");
+    assert_eq!(error["line"], 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cargo_structured_requires_supported_subcommand() -> Result<()> {
+    let env = TestEnv::new()?;
+    let result = CargoTool.execute(json!({"args": ["build"], "structured": true}), &env.config).await;
+    assert!(result.is_err());
+
+    let result = CargoTool.execute(json!({"args": ["clippy"], "apply_fixable": true}), &env.config).await;
+    assert!(result.unwrap_err().to_string().contains("apply_fixable"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cargo_structured_clippy_and_fix() -> Result<()> {
+    // 📎 Lints come back with spans and suggestions; apply_fixable applies them
+    let env = TestEnv::new()?;
+    env.create_files(&[
+        ("linty/Cargo.toml", &MANIFEST.replace("sample", "linty")),
+        ("linty/src/lib.rs", "pub fn one() -> u32 {\n    return 1;\n}\n"),
+    ]).await?;
+
+    let result = CargoTool.execute(json!({"args": ["clippy"], "structured": true, "project": "linty"}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["fixed"], false);
+    let lint = &parsed.content["lints"][0];
+    assert_eq!(lint["code"], "clippy::needless_return");
+    assert_eq!(lint["line"], 2);
+    assert!(!lint["suggestions"].as_array().unwrap().is_empty());
+    assert_eq!(parsed.content["summary"]["fixable"], 1);
+
+    let result = CargoTool.execute(
+        json!({"args": ["clippy", "--allow-no-vcs"], "structured": true, "apply_fixable": true, "project": "linty"}),
+        &env.config
+    ).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["fixed"], true);
+    let fixed = tokio::fs::read_to_string(env.root_path.join("linty/src/lib.rs")).await?;
+    assert!(!fixed.contains("return"));
+
+    println!("✅ Structured clippy works");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cargo_structured_fmt_check_and_apply() -> Result<()> {
+    // 🎨 --check reports the diff without writing; apply formats
+    let env = TestEnv::new()?;
+    let messy = "pub fn one()->u32{1}\n";
+    env.create_files(&[
+        ("messy/Cargo.toml", &MANIFEST.replace("sample", "messy")),
+        ("messy/src/lib.rs", messy),
+    ]).await?;
+
+    let result = CargoTool.execute(json!({"args": ["fmt"], "structured": true, "project": "messy"}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["status"], "would_change");
+    assert_eq!(parsed.content["formatted"], false);
+    assert!(parsed.content["files"][0].as_str().unwrap().ends_with("src/lib.rs"));
+    assert!(parsed.content["diff"].as_str().unwrap().contains("+pub fn one() -> u32 {"));
+    verify_file_content(&env.root_path.join("messy/src/lib.rs"), messy).await?;
+
+    let result = CargoTool.execute(json!({"args": ["fmt"], "structured": true, "apply": true, "project": "messy"}), &env.config).await?;
+    assert_eq!(McpResult::parse(result)?.content["status"], "applied");
+
+    let result = CargoTool.execute(json!({"args": ["fmt"], "structured": true, "project": "messy"}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["status"], "formatted");
+    assert!(parsed.content.get("diff").is_none());
+
+    env.create_files(&[("messy/src/lib.rs", "pub fn one() -> u32 { ; }}\n")]).await?;
+    let result = CargoTool.execute(json!({"args": ["fmt"], "structured": true, "project": "messy"}), &env.config).await?;
+    assert_eq!(McpResult::parse(result)?.content["status"], "error");
+
+    println!("✅ Structured cargo fmt works");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cargo_toolchain_info() -> Result<()> {
+    // 🧰 Versions, the pinned channel and the inherited edition of a workspace member
+    let env = TestEnv::new()?;
+    env.create_files(&[
+        ("ws/Cargo.toml", "[workspace]\nmembers = [\"member\"]\n\n[workspace.package]\nedition = \"2021\"\nrust-version = \"1.75\"\n"),
+        ("ws/rust-toolchain.toml", "[toolchain]\nchannel = \"stable\"\n"),
+        ("ws/member/Cargo.toml", "[package]\nname = \"member\"\nversion = \"0.1.0\"\nedition.workspace = true\nrust-version = { workspace = true }\n"),
+        ("plain/Cargo.toml", "[package]\nname = \"plain\"\nversion = \"0.1.0\"\n"),
+    ]).await?;
+
+    let result = CargoTool.execute(json!({"args": ["toolchain"], "project": "ws", "cwd": "member"}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert!(parsed.content["rustc_version"].as_str().unwrap().starts_with("rustc "));
+    assert!(parsed.content["cargo_version"].as_str().unwrap().starts_with("cargo "));
+    assert_eq!(parsed.content["pinned"], true);
+    assert_eq!(parsed.content["pinned_channel"], "stable");
+    assert!(parsed.content["toolchain_file"].as_str().unwrap().ends_with("rust-toolchain.toml"));
+    assert!(parsed.content["manifest"].as_str().unwrap().ends_with("member/Cargo.toml"));
+    assert_eq!(parsed.content["edition"], "2021");
+    assert_eq!(parsed.content["rust_version"], "1.75");
+
+    // No edition declared means cargo's 2015 default
+    let result = CargoTool.execute(json!({"args": ["toolchain"], "project": "plain"}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["pinned"], false);
+    assert_eq!(parsed.content["edition"], "2015");
+
+    println!("✅ Cargo toolchain info works");
+    Ok(())
+}
@@ -0,0 +1,121 @@
+//! 📋 Copy file tool tests - Binary safety, directories and overwrite protection
+
+mod common;
+
+use anyhow::Result;
+use common::*;
+use empathic::tools::{Tool, copy_file::CopyFileTool};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_copy_file_binary_with_parent_creation() -> Result<()> {
+    // 🎯 Binary content survives the copy byte-for-byte
+    let env = TestEnv::new()?;
+    let bytes: Vec<u8> = (0..=255u8).cycle().take(1024).collect();
+    tokio::fs::write(env.root_path.join("blob.bin"), &bytes).await?;
+
+    let result = CopyFileTool.execute(
+        json!({"from": "blob.bin", "to": "nested/dir/blob_copy.bin"}),
+        &env.config
+    ).await?;
+
+    let parsed = McpResult::parse(result)?;
+    assert_mcp_success(&parsed);
+    assert_eq!(parsed.content["bytes_copied"], 1024);
+    assert_eq!(parsed.content["overwritten"], false);
+
+    let copied = tokio::fs::read(env.root_path.join("nested/dir/blob_copy.bin")).await?;
+    assert_eq!(copied, bytes);
+
+    println!("✅ Binary copy with parent creation works");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_copy_file_overwrite_protection() -> Result<()> {
+    // 🛡️ Existing destinations are only replaced with overwrite: true
+    let env = TestEnv::new()?;
+    env.create_file("a.txt", "new content").await?;
+    let dest = env.create_file("b.txt", "old content").await?;
+
+    let refused = CopyFileTool.execute(json!({"from": "a.txt", "to": "b.txt"}), &env.config).await;
+    assert!(refused.unwrap_err().to_string().contains("already exists"));
+    assert!(verify_file_content(&dest, "old content").await?);
+
+    let result = CopyFileTool.execute(
+        json!({"from": "a.txt", "to": "b.txt", "overwrite": true}),
+        &env.config
+    ).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["overwritten"], true);
+    assert!(verify_file_content(&dest, "new content").await?);
+
+    println!("✅ Overwrite protection works");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_copy_directory_requires_recursive() -> Result<()> {
+    // 📁 Directories copy only when recursive is set
+    let env = TestEnv::new()?;
+    env.create_files(&[("src/main.rs", "fn main() {}"), ("src/util/mod.rs", "pub fn util() {}")]).await?;
+
+    let refused = CopyFileTool.execute(json!({"from": "src", "to": "backup"}), &env.config).await;
+    assert!(refused.unwrap_err().to_string().contains("recursive"));
+
+    let result = CopyFileTool.execute(
+        json!({"from": "src", "to": "backup", "recursive": true}),
+        &env.config
+    ).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["was_directory"], true);
+    assert_eq!(parsed.content["bytes_copied"], ("fn main() {}".len() + "pub fn util() {}".len()) as u64);
+    assert!(verify_file_content(&env.root_path.join("backup/util/mod.rs"), "pub fn util() {}").await?);
+
+    println!("✅ Recursive directory copy works");
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_copy_directory_recreates_symlinks() -> Result<()> {
+    // 🔗 Links are copied as links; a link back up the tree can't loop the walk
+    let env = TestEnv::new()?;
+    env.create_files(&[("src/main.rs", "fn main() {}"), ("shared/lib.rs", "pub fn lib() {}")]).await?;
+    std::os::unix::fs::symlink("..", env.root_path.join("src/parent"))?;
+    std::os::unix::fs::symlink("../shared/lib.rs", env.root_path.join("src/lib.rs"))?;
+
+    let result = CopyFileTool.execute(json!({"from": "src", "to": "backup", "recursive": true}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["bytes_copied"], "fn main() {}".len() as u64);
+
+    let backup = env.root_path.join("backup");
+    assert_eq!(std::fs::read_link(backup.join("parent"))?, std::path::PathBuf::from(".."));
+    assert_eq!(std::fs::read_link(backup.join("lib.rs"))?, std::path::PathBuf::from("../shared/lib.rs"));
+    assert!(verify_file_content(&backup.join("lib.rs"), "pub fn lib() {}").await?);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_copy_directory_into_itself_is_refused() -> Result<()> {
+    // ♾️ Copying a tree into its own subdirectory would never finish
+    let env = TestEnv::new()?;
+    env.create_file("src/main.rs", "fn main() {}").await?;
+
+    let refused = CopyFileTool.execute(json!({"from": "src", "to": "src/backup", "recursive": true}), &env.config).await;
+    assert!(refused.unwrap_err().to_string().contains("inside the source"));
+    assert!(!env.root_path.join("src/backup").exists());
+
+    let refused = CopyFileTool.execute(json!({"from": "src", "to": "src", "recursive": true, "overwrite": true}), &env.config).await;
+    assert!(refused.unwrap_err().to_string().contains("inside the source"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_copy_file_missing_source() -> Result<()> {
+    let env = TestEnv::new()?;
+
+    let result = CopyFileTool.execute(json!({"from": "missing.txt", "to": "copy.txt"}), &env.config).await;
+    assert!(result.unwrap_err().to_string().contains("not found"));
+    Ok(())
+}
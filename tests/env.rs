@@ -138,3 +138,35 @@ async fn test_env_response_structure() -> Result<()> {
     
     Ok(())
 }
+
+#[tokio::test]
+async fn test_env_policy_filters_and_masks() -> Result<()> {
+    // 🔒 Allowlist/denylist hide variables; secret-looking values are masked
+    let mut env = TestEnv::new()?;
+    unsafe { std::env::set_var("EMPATHIC_TEST_API_KEY", "sk-very-secret") };
+    unsafe { std::env::set_var("EMPATHIC_TEST_PLAIN", "visible") };
+    env.config.env_policy.allow = Some(vec!["EMPATHIC_TEST_*".to_string(), "PATH".to_string()]);
+    env.config.env_policy.deny = vec!["PATH".to_string()];
+
+    let result = McpResult::parse(EnvTool.execute(json!({}), &env.config).await?)?;
+    let variables = result.content["env_vars"].as_object().unwrap();
+    assert_eq!(variables["EMPATHIC_TEST_PLAIN"], "visible");
+    assert_eq!(variables["EMPATHIC_TEST_API_KEY"], "********");
+    assert!(!variables.contains_key("PATH"), "deny wins over allow");
+    assert!(!variables.contains_key("HOME"));
+    assert!(variables.contains_key("ROOT_DIR"));
+    assert_eq!(result.content["masked"], json!(["EMPATHIC_TEST_API_KEY"]));
+    assert!(result.content["hidden"].as_u64().unwrap() > 0);
+
+    // 🚫 show_secrets is refused unless the server enables it
+    let refused = EnvTool.execute(json!({"show_secrets": true}), &env.config).await;
+    assert!(refused.unwrap_err().to_string().contains("show_secrets"));
+
+    env.config.env_policy.show_secrets = true;
+    let revealed = McpResult::parse(EnvTool.execute(json!({"show_secrets": true}), &env.config).await?)?;
+    assert_eq!(revealed.content["env_vars"]["EMPATHIC_TEST_API_KEY"], "sk-very-secret");
+    assert_eq!(revealed.content["masked"], json!([]));
+
+    println!("✅ Env policy filters and masks variables");
+    Ok(())
+}
@@ -0,0 +1,64 @@
+//! ℹ️ File info tool tests - Metadata for files, directories and symlinks
+
+mod common;
+
+use anyhow::Result;
+use common::*;
+use empathic::tools::{Tool, file_info::FileInfoTool};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_file_info_for_file_and_directory() -> Result<()> {
+    let env = TestEnv::new()?;
+    env.create_file("src/main.rs", "fn main() {}").await?;
+
+    let result = FileInfoTool.execute(json!({"path": "src/main.rs"}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["is_file"], true);
+    assert_eq!(parsed.content["is_dir"], false);
+    assert_eq!(parsed.content["is_symlink"], false);
+    assert_eq!(parsed.content["size"], 12);
+    assert!(parsed.content["modified"].as_u64().unwrap() > 0);
+
+    let result = FileInfoTool.execute(json!({"path": "src"}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["is_dir"], true);
+
+    println!("✅ File and directory metadata works");
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_file_info_symlinks() -> Result<()> {
+    // 🔗 Links report their target; dangling links are flagged
+    let env = TestEnv::new()?;
+    let target = env.create_file("data.txt", "hello").await?;
+    create_symlink(&target, &env.root_path.join("link.txt")).await?;
+    create_symlink(&env.root_path.join("missing.txt"), &env.root_path.join("dangling.txt")).await?;
+
+    let result = FileInfoTool.execute(json!({"path": "link.txt"}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["is_symlink"], true);
+    assert_eq!(parsed.content["is_file"], true);
+    assert_eq!(parsed.content["size"], 5);
+    assert_eq!(parsed.content["symlink_target"], target.to_string_lossy().as_ref());
+
+    let result = FileInfoTool.execute(json!({"path": "dangling.txt"}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["broken_symlink"], true);
+    assert_eq!(parsed.content["exists"], false);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_file_info_missing_path() -> Result<()> {
+    let env = TestEnv::new()?;
+
+    let result = FileInfoTool.execute(json!({"path": "nope.txt"}), &env.config).await;
+    assert!(result.unwrap_err().to_string().contains("not found"));
+
+    let result = FileInfoTool.execute(json!({"path": "x", "project": "../escape"}), &env.config).await;
+    assert!(result.is_err());
+    Ok(())
+}
@@ -370,3 +370,102 @@ async fn test_git_large_output() -> Result<()> {
     
     Ok(())
 }
+
+#[tokio::test]
+async fn test_git_structured_status() -> Result<()> {
+    // 📋 Branch, staged/unstaged/untracked state and renames as JSON
+    let env = TestEnv::new()?;
+    let tool = GitTool;
+
+    let project_dir = env.create_project("status_repo").await?;
+    init_git_repo(&env, "status_repo").await?;
+    create_test_files(&project_dir, &[("keep.txt", "keep\n"), ("old.txt", "the same long content for rename detection\n")]).await?;
+    for args in [json!(["checkout", "-b", "work"]), json!(["add", "."]), json!(["commit", "-m", "Initial"])] {
+        let result = tool.execute(json!({"args": args, "project": "status_repo"}), &env.config).await?;
+        assert_mcp_success(&McpResult::parse(result)?);
+    }
+
+    let result = tool.execute(json!({"args": ["mv", "old.txt", "new.txt"], "project": "status_repo"}), &env.config).await?;
+    assert_mcp_success(&McpResult::parse(result)?);
+    create_test_files(&project_dir, &[("keep.txt", "changed\n"), ("scratch.txt", "new\n")]).await?;
+
+    let result = tool.execute(json!({"args": ["status"], "structured": true, "project": "status_repo"}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    let status = &parsed.content;
+    assert_eq!(status["branch"], "work");
+    assert_eq!(status["clean"], false);
+    assert!(status["commit"].is_string());
+    assert_eq!(status["summary"], json!({"staged": 1, "unstaged": 1, "untracked": 1, "conflicted": 0}));
+
+    let files = status["files"].as_array().unwrap();
+    let file = |path: &str| files.iter().find(|f| f["path"] == path).unwrap().clone();
+    assert_eq!(file("keep.txt")["unstaged"], "modified");
+    assert_eq!(file("new.txt")["state"], "renamed");
+    assert_eq!(file("new.txt")["orig_path"], "old.txt");
+    assert_eq!(file("scratch.txt")["state"], "untracked");
+
+    let result = tool.execute(json!({"args": ["log"], "structured": true, "project": "status_repo"}), &env.config).await;
+    assert!(result.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_git_dry_run_and_confirm() -> Result<()> {
+    // 🛡️ Destructive commands are previewed or need confirm before they run
+    let env = TestEnv::new()?;
+    let tool = GitTool;
+
+    let project_dir = env.create_project("guard_repo").await?;
+    init_git_repo(&env, "guard_repo").await?;
+    create_test_files(&project_dir, &[("tracked.txt", "v1\n")]).await?;
+    for args in [json!(["checkout", "-b", "main"]), json!(["add", "."]), json!(["commit", "-m", "Initial"])] {
+        let result = tool.execute(json!({"args": args, "project": "guard_repo"}), &env.config).await?;
+        assert_mcp_success(&McpResult::parse(result)?);
+    }
+    create_test_files(&project_dir, &[("tracked.txt", "v2\n"), ("scratch.txt", "temp\n")]).await?;
+
+    // clean: native dry run lists the file and leaves it alone
+    let result = tool.execute(json!({"args": ["clean", "-f"], "dry_run": true, "project": "guard_repo"}), &env.config).await?;
+    let preview = McpResult::parse(result)?.content;
+    assert_eq!(preview["dry_run"], true);
+    assert_eq!(preview["would_remove"], json!(["scratch.txt"]));
+    assert_eq!(preview["requires_confirm"], true);
+    let result = tool.execute(json!({"args": ["clean", "-fd"], "project": "guard_repo"}), &env.config).await;
+    assert!(result.unwrap_err().to_string().contains("confirm"));
+    assert!(project_dir.join("scratch.txt").exists());
+
+    // checkout <path> without `--` restores the file, so it is guarded like `checkout -- <path>`
+    let result = tool.execute(json!({"args": ["checkout", "tracked.txt"], "project": "guard_repo"}), &env.config).await;
+    assert!(result.unwrap_err().to_string().contains("confirm"));
+    assert_eq!(std::fs::read_to_string(project_dir.join("tracked.txt"))?, "v2\n");
+    let result = tool.execute(json!({"args": ["checkout", "main"], "project": "guard_repo"}), &env.config).await?;
+    assert_mcp_success(&McpResult::parse(result)?);
+
+    // push: ref updates against a local bare remote
+    let remote = env.temp_dir.path().join("remote.git");
+    std::process::Command::new("git").args(["init", "--bare", "-q"]).arg(&remote).status()?;
+    let result = tool.execute(json!({"args": ["remote", "add", "origin", remote.to_string_lossy()], "project": "guard_repo"}), &env.config).await?;
+    assert_mcp_success(&McpResult::parse(result)?);
+    let result = tool.execute(json!({"args": ["push", "origin", "main"], "dry_run": true, "project": "guard_repo"}), &env.config).await?;
+    let preview = McpResult::parse(result)?.content;
+    assert_eq!(preview["refs"][0]["flag"], "*");
+    assert_eq!(preview["refs"][0]["to"], "refs/heads/main");
+
+    // reset --hard: refused without confirm, previewed with the changes at stake
+    let result = tool.execute(json!({"args": ["reset", "--hard"], "project": "guard_repo"}), &env.config).await;
+    assert!(result.is_err());
+    assert_eq!(std::fs::read_to_string(project_dir.join("tracked.txt"))?, "v2\n");
+
+    let result = tool.execute(json!({"args": ["reset", "--hard"], "dry_run": true, "project": "guard_repo"}), &env.config).await?;
+    let preview = McpResult::parse(result)?.content;
+    assert_eq!(preview["requires_confirm"], true);
+    assert!(preview["uncommitted"].as_array().unwrap().iter().any(|line| line.as_str().unwrap().ends_with("tracked.txt")));
+
+    let result = tool.execute(json!({"args": ["reset", "--hard"], "confirm": true, "project": "guard_repo"}), &env.config).await?;
+    assert_mcp_success(&McpResult::parse(result)?);
+    assert_eq!(std::fs::read_to_string(project_dir.join("tracked.txt"))?, "v1\n");
+
+    let result = tool.execute(json!({"args": ["status"], "dry_run": true, "project": "guard_repo"}), &env.config).await;
+    assert!(result.is_err());
+    Ok(())
+}
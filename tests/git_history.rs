@@ -0,0 +1,108 @@
+//! 📜 Git history tool tests - Structured diff, log and blame
+
+mod common;
+
+use anyhow::Result;
+use common::*;
+use empathic::tools::{Tool, git::GitTool, git_history::{GitDiffTool, GitLogTool, GitBlameTool}};
+use serde_json::json;
+
+async fn git(env: &TestEnv, args: &[&str]) -> Result<()> {
+    let result = GitTool.execute(json!({"args": args, "project": "repo"}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_mcp_success(&parsed);
+    Ok(())
+}
+
+/// Repo with two commits to src/lib.rs
+async fn create_repo(env: &TestEnv) -> Result<()> {
+    env.create_project("repo").await?;
+    git(env, &["init"]).await?;
+    git(env, &["config", "user.name", "Test User"]).await?;
+    git(env, &["config", "user.email", "test@example.com"]).await?;
+
+    env.create_file("repo/src/lib.rs", "fn a() {}\nfn b() {}\n").await?;
+    git(env, &["add", "."]).await?;
+    git(env, &["commit", "-m", "Add lib", "-m", "With a body"]).await?;
+
+    env.create_file("repo/src/lib.rs", "fn a() {}\nfn b() { todo!() }\n").await?;
+    git(env, &["commit", "-am", "Implement b"]).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_git_log_returns_commits() -> Result<()> {
+    let env = TestEnv::new()?;
+    create_repo(&env).await?;
+
+    let result = GitLogTool.execute(json!({"project": "repo"}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["count"], 2);
+    let commits = parsed.content["commits"].as_array().unwrap();
+    assert_eq!(commits[0]["subject"], "Implement b");
+    assert_eq!(commits[1]["body"], "With a body");
+    assert_eq!(commits[1]["author"], "Test User");
+    assert_eq!(commits[0]["hash"].as_str().unwrap().len(), 40);
+
+    let result = GitLogTool.execute(json!({"project": "repo", "max_count": 1}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["count"], 1);
+
+    println!("✅ git_log works");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_git_diff_unstaged_staged_and_refs() -> Result<()> {
+    let env = TestEnv::new()?;
+    create_repo(&env).await?;
+
+    let result = GitDiffTool.execute(json!({"project": "repo", "from": "HEAD~1", "to": "HEAD"}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["files_changed"], 1);
+    let file = &parsed.content["files"][0];
+    assert_eq!(file["path"], "src/lib.rs");
+    assert_eq!(file["hunks"][0]["lines"], json!([" fn a() {}", "-fn b() {}", "+fn b() { todo!() }"]));
+
+    env.create_file("repo/notes.md", "draft\n").await?;
+    git(&env, &["add", "notes.md"]).await?;
+    env.create_file("repo/src/lib.rs", "fn a() {}\n").await?;
+
+    let result = GitDiffTool.execute(json!({"project": "repo"}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["files"][0]["path"], "src/lib.rs");
+    assert_eq!(parsed.content["deletions"], 1);
+
+    let result = GitDiffTool.execute(json!({"project": "repo", "staged": true}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["files"][0]["path"], "notes.md");
+    assert_eq!(parsed.content["files"][0]["status"], "added");
+
+    println!("✅ git_diff works");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_git_blame_attributes_lines() -> Result<()> {
+    let env = TestEnv::new()?;
+    create_repo(&env).await?;
+
+    let result = GitBlameTool.execute(json!({"project": "repo", "path": "src/lib.rs"}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    let lines = parsed.content["lines"].as_array().unwrap();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["summary"], "Add lib");
+    assert_eq!(lines[1]["summary"], "Implement b");
+    assert_eq!(lines[1]["content"], "fn b() { todo!() }");
+    assert_eq!(lines[1]["email"], "test@example.com");
+
+    let result = GitBlameTool.execute(json!({"project": "repo", "path": "src/lib.rs", "start_line": 2}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["lines"].as_array().unwrap().len(), 1);
+
+    let result = GitBlameTool.execute(json!({"project": "repo", "path": "missing.rs"}), &env.config).await;
+    assert!(result.is_err());
+
+    println!("✅ git_blame works");
+    Ok(())
+}
@@ -0,0 +1,53 @@
+//! 🛑 Graceful shutdown tests - the server binary exits cleanly on signals
+#![cfg(unix)]
+
+use anyhow::Result;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_sigterm_shuts_down_cleanly() -> Result<()> {
+    let root = tempfile::TempDir::new()?;
+    let mut child = Command::new(env!("CARGO_BIN_EXE_empathic"))
+        .env("ROOT_DIR", root.path())
+        .env("LOGLEVEL", "info")
+        .env("SHUTDOWN_TIMEOUT", "2")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // A response proves the read loop, and so the signal handler, is running
+    let mut stdin = child.stdin.take().unwrap();
+    writeln!(stdin, r#"{{"jsonrpc":"2.0","id":1,"method":"ping"}}"#)?;
+    let mut response = String::new();
+    BufReader::new(child.stdout.take().unwrap()).read_line(&mut response)?;
+    assert!(response.contains("\"id\":1"), "unexpected response: {}", response);
+
+    let status = Command::new("kill").args(["-TERM", &child.id().to_string()]).status()?;
+    assert!(status.success());
+
+    // stdin stays open, so only the signal can end the loop
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if started.elapsed() > Duration::from_secs(10) {
+            child.kill()?;
+            panic!("server ignored SIGTERM");
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    };
+    drop(stdin);
+
+    assert!(status.success(), "exit status: {:?}", status);
+    let mut stderr = String::new();
+    std::io::Read::read_to_string(&mut child.stderr.take().unwrap(), &mut stderr)?;
+    assert!(stderr.contains("Received SIGTERM"), "stderr: {}", stderr);
+    assert!(stderr.contains("shutdown complete"), "stderr: {}", stderr);
+
+    println!("✅ SIGTERM triggers a clean exit");
+    Ok(())
+}
@@ -0,0 +1,57 @@
+//! ➕ insert_at_line tests - Prepend, append past the end and multi-line blocks
+
+mod common;
+
+use anyhow::Result;
+use common::*;
+use empathic::tools::{Tool, insert_at_line::InsertAtLineTool};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_prepend_at_line_zero() -> Result<()> {
+    let env = TestEnv::new()?;
+    let file_path = env.create_file("lib.rs", "fn a() {}\n").await?;
+
+    let result = InsertAtLineTool.execute(json!({"path": "lib.rs", "line": 0, "content": "//! Docs"}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+
+    assert_eq!(parsed.content["line"], 0);
+    assert_eq!(parsed.content["total_lines"], 2);
+    assert!(verify_file_content(&file_path, "//! Docs\nfn a() {}\n").await?);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_append_past_end() -> Result<()> {
+    let env = TestEnv::new()?;
+    let file_path = env.create_file("notes.txt", "one\ntwo\n").await?;
+
+    let result = InsertAtLineTool.execute(json!({"path": "notes.txt", "line": 99, "content": "three"}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+
+    // 📍 Clamped to the end, no blank padding lines
+    assert_eq!(parsed.content["line"], 2);
+    assert!(verify_file_content(&file_path, "one\ntwo\nthree\n").await?);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_multi_line_content_keeps_crlf() -> Result<()> {
+    let env = TestEnv::new()?;
+    let file_path = env.create_file("win.txt", "first\r\nlast\r\n").await?;
+
+    let result = InsertAtLineTool.execute(
+        json!({"path": "win.txt", "line": 1, "content": "second\nthird\n", "dry_run": true}),
+        &env.config
+    ).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["dry_run"], true);
+    assert!(parsed.content["diff"].as_str().unwrap().contains("+third"));
+    assert!(verify_file_content(&file_path, "first\r\nlast\r\n").await?);
+
+    let result = InsertAtLineTool.execute(json!({"path": "win.txt", "line": 1, "content": "second\nthird\n"}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["lines_inserted"], 2);
+    assert!(verify_file_content(&file_path, "first\r\nsecond\r\nthird\r\nlast\r\n").await?);
+    Ok(())
+}
@@ -94,3 +94,162 @@ async fn test_list_files_with_pattern() {
     // Cleanup
     fs::remove_dir_all(&test_dir).await.unwrap();
 }
+
+#[tokio::test]
+async fn test_list_files_with_path_pattern() {
+    use empathic::tools::Tool;
+    use empathic::tools::list_files::ListFilesTool;
+    use serde_json::json;
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let root = temp_dir.path();
+    fs::create_dir_all(root.join("src/lsp/manager")).await.unwrap();
+    fs::write(root.join("mod.rs"), "// top").await.unwrap();
+    fs::write(root.join("src/lsp/mod.rs"), "// lsp").await.unwrap();
+    fs::write(root.join("src/lsp/manager/mod.rs"), "// manager").await.unwrap();
+    fs::write(root.join("src/lsp/cache.rs"), "// cache").await.unwrap();
+
+    let config = Config::new(root.to_path_buf());
+
+    let list = |pattern: &'static str| {
+        let config = config.clone();
+        async move {
+            let result = ListFilesTool.execute(json!({"pattern": pattern}), &config).await.unwrap();
+            let text = result["content"][0]["text"].as_str().unwrap().to_string();
+            let parsed: serde_json::Value = serde_json::from_str(&text).unwrap();
+            let mut paths: Vec<String> = parsed["files"].as_array().unwrap().iter()
+                .map(|f| f["path"].as_str().unwrap().to_string())
+                .collect();
+            paths.sort();
+            paths
+        }
+    };
+
+    // 🎯 `**` spans directories, `*` stays in one
+    assert_eq!(list("src/**/mod.rs").await.len(), 2);
+    let single = list("src/*/mod.rs").await;
+    assert_eq!(single.len(), 1);
+    assert!(single[0].ends_with("src/lsp/mod.rs"));
+    assert_eq!(list("**/*.rs").await.len(), 4);
+}
+
+#[tokio::test]
+async fn test_list_files_pagination() {
+    use empathic::tools::Tool;
+    use empathic::tools::list_files::ListFilesTool;
+    use serde_json::json;
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    for i in 0..5 {
+        fs::write(temp_dir.path().join(format!("file{}.txt", i)), "x").await.unwrap();
+    }
+    let config = Config::new(temp_dir.path().to_path_buf());
+
+    // 📄 Follow next_cursor until it disappears
+    let mut names = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let mut args = json!({"limit": 2});
+        if let Some(cursor) = &cursor {
+            args["cursor"] = json!(cursor);
+        }
+        let result = ListFilesTool.execute(args, &config).await.unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(result["content"][0]["text"].as_str().unwrap()).unwrap();
+        assert_eq!(parsed["total"], 5);
+        names.extend(parsed["files"].as_array().unwrap().iter().map(|f| f["name"].as_str().unwrap().to_string()));
+        match parsed["next_cursor"].as_str() {
+            Some(next) => cursor = Some(next.to_string()),
+            None => break,
+        }
+    }
+    assert_eq!(names, (0..5).map(|i| format!("file{}.txt", i)).collect::<Vec<_>>());
+
+    let result = ListFilesTool.execute(json!({"cursor": "garbage"}), &config).await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_list_files_fuzzy_ranking() {
+    use empathic::tools::Tool;
+    use empathic::tools::list_files::ListFilesTool;
+    use serde_json::json;
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    fs::create_dir_all(temp_dir.path().join("src/services")).await.unwrap();
+    for file in ["src/services/user_service.rs", "src/user.rs", "src/services/usr_svc_legacy.rs", "README.md"] {
+        fs::write(temp_dir.path().join(file), "x").await.unwrap();
+    }
+    let config = Config::new(temp_dir.path().to_path_buf());
+
+    // 🔎 Half-remembered names find their files, best match first, with scores
+    let result = ListFilesTool.execute(json!({"pattern": "usrsvc", "fuzzy": true}), &config).await.unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(result["content"][0]["text"].as_str().unwrap()).unwrap();
+    let names: Vec<&str> = parsed["files"].as_array().unwrap().iter().map(|f| f["name"].as_str().unwrap()).collect();
+    assert_eq!(names, vec!["usr_svc_legacy.rs", "user_service.rs"]);
+    let scores: Vec<f64> = parsed["files"].as_array().unwrap().iter().map(|f| f["score"].as_f64().unwrap()).collect();
+    assert!(scores[0] > scores[1] && scores[1] > 0.0);
+
+    let result = ListFilesTool.execute(json!({"pattern": "user.rs", "fuzzy": true}), &config).await.unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(result["content"][0]["text"].as_str().unwrap()).unwrap();
+    assert_eq!(parsed["files"][0]["name"], "user.rs");
+    assert_eq!(parsed["files"][0]["score"], 1.0);
+
+    // Glob stays the default: "usrsvc" is a literal name there
+    let result = ListFilesTool.execute(json!({"pattern": "usrsvc"}), &config).await.unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(result["content"][0]["text"].as_str().unwrap()).unwrap();
+    assert_eq!(parsed["total"], 0);
+
+    assert!(ListFilesTool.execute(json!({"fuzzy": true}), &config).await.is_err());
+}
+
+#[tokio::test]
+async fn test_list_files_depth_and_tree() {
+    use empathic::tools::Tool;
+    use empathic::tools::list_files::ListFilesTool;
+    use serde_json::json;
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let root = temp_dir.path();
+    fs::create_dir_all(root.join("src/lsp")).await.unwrap();
+    fs::create_dir_all(root.join("target/debug")).await.unwrap();
+    fs::create_dir_all(root.join(".git")).await.unwrap();
+    fs::write(root.join("Cargo.toml"), "[package]").await.unwrap();
+    fs::write(root.join("src/lib.rs"), "pub mod lsp;").await.unwrap();
+    fs::write(root.join("src/lsp/mod.rs"), "").await.unwrap();
+    fs::write(root.join(".git/HEAD"), "ref").await.unwrap();
+    fs::write(root.join("target/debug/app"), "bin").await.unwrap();
+    let config = Config::new(root.to_path_buf());
+
+    let list = |args: serde_json::Value| {
+        let config = config.clone();
+        async move {
+            let result = ListFilesTool.execute(args, &config).await.unwrap();
+            serde_json::from_str::<serde_json::Value>(result["content"][0]["text"].as_str().unwrap()).unwrap()
+        }
+    };
+
+    // 📏 Depth 1 is the directory's own entries; .git and target/ are skipped
+    let parsed = list(json!({"max_depth": 1})).await;
+    let mut paths: Vec<&str> = parsed["files"].as_array().unwrap().iter()
+        .map(|f| f["relative_path"].as_str().unwrap())
+        .collect();
+    paths.sort();
+    assert_eq!(paths, vec!["Cargo.toml", "src"]);
+    let cargo = parsed["files"].as_array().unwrap().iter().find(|f| f["name"] == "Cargo.toml").unwrap();
+    assert_eq!(cargo["type"], "file");
+    assert_eq!(cargo["size"], 9);
+
+    // 🌳 Tree output indents by depth
+    let parsed = list(json!({"format": "tree"})).await;
+    assert!(parsed.get("files").is_none());
+    assert_eq!(
+        parsed["tree"].as_str().unwrap(),
+        "Cargo.toml (9 bytes)\nsrc/\n  lib.rs (12 bytes)\n  lsp/\n    mod.rs (0 bytes)\n"
+    );
+
+    // include_ignored walks everything
+    let parsed = list(json!({"recursive": true, "include_ignored": true})).await;
+    assert!(parsed["files"].as_array().unwrap().iter().any(|f| f["relative_path"] == "target/debug/app"));
+
+    assert!(ListFilesTool.execute(json!({"format": "xml"}), &config).await.is_err());
+}
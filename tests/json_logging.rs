@@ -0,0 +1,55 @@
+//! 🧾 JSON logging tests - LOG_FORMAT=json keeps stderr machine-readable
+
+use anyhow::Result;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+#[test]
+fn test_json_format_emits_one_object_per_stderr_line() -> Result<()> {
+    let root = tempfile::TempDir::new()?;
+    let mut child = Command::new(env!("CARGO_BIN_EXE_empathic"))
+        .env("ROOT_DIR", root.path())
+        .env("LOGLEVEL", "info")
+        .env("LOG_FORMAT", "json")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // Closing stdin after one request runs the full startup/shutdown path
+    writeln!(child.stdin.take().unwrap(), r#"{{"jsonrpc":"2.0","id":1,"method":"tools/list"}}"#)?;
+    let output = child.wait_with_output()?;
+    assert!(output.status.success());
+
+    // stdout carries only the protocol
+    let stdout = String::from_utf8(output.stdout)?;
+    assert_eq!(stdout.lines().count(), 1);
+    assert!(stdout.contains("\"tools\""));
+
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(stderr.lines().count() > 1, "stderr: {}", stderr);
+    for line in stderr.lines() {
+        let value: serde_json::Value = serde_json::from_str(line)
+            .unwrap_or_else(|e| panic!("not JSON ({}): {}", e, line));
+        for key in ["timestamp", "level", "target", "message"] {
+            assert!(value[key].is_string(), "missing {} in {}", key, line);
+        }
+    }
+    assert!(stderr.contains("shutdown complete"));
+
+    println!("✅ JSON logs are one object per line");
+    Ok(())
+}
+
+#[test]
+fn test_unknown_format_is_rejected() -> Result<()> {
+    let root = tempfile::TempDir::new()?;
+    let output = Command::new(env!("CARGO_BIN_EXE_empathic"))
+        .env("ROOT_DIR", root.path())
+        .env("LOG_FORMAT", "xml")
+        .stdin(Stdio::null())
+        .output()?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)?.contains("LOG_FORMAT"));
+    Ok(())
+}
@@ -0,0 +1,57 @@
+//! 🎚️ logging/setLevel tests - Runtime log level and client log notifications
+
+mod common;
+
+use anyhow::Result;
+use common::*;
+use empathic::logging::ReloadableLogger;
+use empathic::mcp::JsonRpcRequest;
+use empathic::mcp::handlers::RequestHandler;
+use serde_json::json;
+use std::collections::HashMap;
+
+fn request(method: &str, params: serde_json::Value) -> JsonRpcRequest {
+    serde_json::from_value(json!({"jsonrpc": "2.0", "id": 1, "method": method, "params": params})).unwrap()
+}
+
+#[tokio::test]
+async fn test_set_level_reloads_filter_and_notifies_client() -> Result<()> {
+    // 🎚️ Same setup as main: env_logger passes everything, the wrapper filters
+    let inner = env_logger::Builder::new().is_test(true).filter_level(log::LevelFilter::Trace).build();
+    let filter = env_filter::Builder::new().filter_level(log::LevelFilter::Warn).build();
+    ReloadableLogger::new(inner, filter).install()?;
+    assert!(!log::log_enabled!(log::Level::Debug));
+
+    let env = TestEnv::new()?;
+    let tools = HashMap::new();
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+    let handler = RequestHandler::new(&env.config, &tools).with_notifications(sender);
+
+    let response = handler.handle_request(request("initialize", json!({}))).await.unwrap();
+    assert_eq!(response.result.unwrap()["capabilities"]["logging"], json!({}));
+
+    let response = handler.handle_request(request("logging/setLevel", json!({"level": "loud"}))).await.unwrap();
+    assert_eq!(response.error.unwrap().code, -32602);
+
+    let response = handler.handle_request(request("logging/setLevel", json!({"level": "debug"}))).await.unwrap();
+    assert_eq!(response.result.unwrap(), json!({}));
+    assert!(log::log_enabled!(log::Level::Debug));
+
+    while receiver.try_recv().is_ok() {}
+    log::debug!(target: "empathic::lsp", "indexing {} crates", 3);
+    let notification = receiver.try_recv()?;
+    assert_eq!(notification.method, "notifications/message");
+    let params = notification.params.unwrap();
+    assert_eq!(params["level"], "debug");
+    assert_eq!(params["logger"], "empathic::lsp");
+    assert_eq!(params["data"], "indexing 3 crates");
+
+    // ⬇️ Back down: debug records are dropped again, warnings still go out
+    handler.handle_request(request("logging/setLevel", json!({"level": "warning"}))).await.unwrap();
+    while receiver.try_recv().is_ok() {}
+    log::debug!("not sent");
+    assert!(receiver.try_recv().is_err());
+    log::warn!("disk almost full");
+    assert_eq!(receiver.try_recv()?.params.unwrap()["level"], "warning");
+    Ok(())
+}
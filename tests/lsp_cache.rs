@@ -0,0 +1,153 @@
+//! 🗑️ LSP cache tests - Hover answers are cached; writes evict them; cache_stats reports size and hit rate
+#![cfg(unix)]
+
+mod common;
+
+use anyhow::Result;
+use common::*;
+use empathic::lsp::LspManager;
+use empathic::lsp::cache::CacheKey;
+use empathic::tools::{Tool, delete_file::DeleteFileTool, replace::ReplaceTool, str_replace::StrReplaceTool};
+use serde_json::{Value, json};
+use empathic::lsp::ServerOverride;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Cache a hover response for `file` and return how many entries the cache holds
+async fn cache_hover(manager: &LspManager, file: &Path) -> Result<usize> {
+    let key = CacheKey::Hover { file_path: file.to_path_buf(), line: 0, character: 3 };
+    manager.cache().set(key, json!({"contents": "fn main()"})).await?;
+    Ok(manager.cache().stats().await.total_entries)
+}
+
+async fn assert_evicted(tool: &dyn Tool, args: Value) -> Result<()> {
+    let mut env = TestEnv::new()?;
+    let manager = Arc::new(LspManager::new(env.root_path.clone()));
+    env.config.set_lsp_manager(Arc::clone(&manager));
+    let file = env.create_file("src/main.rs", "fn main() { old(); }\n").await?;
+    assert_eq!(cache_hover(&manager, &file).await?, 1);
+
+    let result = tool.execute(args, &env.config).await?;
+    assert_mcp_success(&McpResult::parse(result)?);
+
+    assert_eq!(manager.cache().stats().await.total_entries, 0, "{} left a stale entry", tool.name());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_str_replace_evicts_cached_responses() -> Result<()> {
+    assert_evicted(&StrReplaceTool, json!({"path": "src/main.rs", "old_str": "old", "new_str": "new"})).await
+}
+
+#[tokio::test]
+async fn test_replace_evicts_cached_responses() -> Result<()> {
+    assert_evicted(&ReplaceTool, json!({"path": "src/main.rs", "search": "old", "replace": "new"})).await
+}
+
+#[tokio::test]
+async fn test_delete_file_evicts_cached_responses() -> Result<()> {
+    assert_evicted(&DeleteFileTool, json!({"path": "src/main.rs"})).await
+}
+
+#[tokio::test]
+async fn test_dry_run_keeps_cached_responses() -> Result<()> {
+    // 👀 Previews don't touch the file, so the cache stays valid
+    let mut env = TestEnv::new()?;
+    let manager = Arc::new(LspManager::new(env.root_path.clone()));
+    env.config.set_lsp_manager(Arc::clone(&manager));
+    let file = env.create_file("src/main.rs", "fn main() { old(); }\n").await?;
+    cache_hover(&manager, &file).await?;
+
+    StrReplaceTool.execute(
+        json!({"path": "src/main.rs", "old_str": "old", "new_str": "new", "dry_run": true}),
+        &env.config
+    ).await?;
+
+    assert_eq!(manager.cache().stats().await.total_entries, 1);
+    Ok(())
+}
+
+/// 📜 A "rust-analyzer" that answers initialize, then the first request with `result`, then
+/// only logs: a repeated request can only succeed if it never reaches the server
+async fn one_answer_server(env: &TestEnv, result: Value) -> Result<(Arc<LspManager>, PathBuf)> {
+    let log = env.root_path.join("server.log");
+    let frame = |message: Value| {
+        let body = message.to_string();
+        format!("Content-Length: {}\r\n\r\n{}", body.len(), body)
+    };
+    let initialize = frame(json!({"jsonrpc": "2.0", "id": 1, "result": {"capabilities": {}}}));
+    let answer = frame(json!({"jsonrpc": "2.0", "id": 2, "result": result}));
+    let script = format!(
+        "#!/bin/sh\nsleep 0.3\nprintf '%s' '{initialize}'\nsleep 1\nprintf '%s' '{answer}'\ncat > '{}'\n",
+        log.display()
+    );
+    let fake = env.create_file("toolchain/rust-analyzer", &script).await?;
+    std::fs::set_permissions(&fake, std::fs::Permissions::from_mode(0o755))?;
+
+    let manager = LspManager::new(env.root_path.clone()).with_server_override(
+        &ServerOverride { command: Some(fake.to_string_lossy().to_string()), ..Default::default() },
+        Vec::new(),
+    );
+    Ok((Arc::new(manager), log))
+}
+
+#[tokio::test]
+async fn test_repeated_hover_is_served_from_cache() -> Result<()> {
+    use empathic::tools::lsp::{CacheStatsTool, hover::LspHoverTool};
+
+    let mut env = TestEnv::new()?;
+    env.create_file("app/Cargo.toml", "[package]\nname = \"app\"\n").await?;
+    let file = env.create_file("app/src/lib.rs", "pub fn cached() {}\n").await?;
+    let (manager, log) = one_answer_server(&env, json!({"contents": {"kind": "markdown", "value": "fn cached()"}})).await?;
+    env.config.set_lsp_manager(Arc::clone(&manager));
+    manager.open_document(&file).await?;
+
+    let hover = json!({"file_path": "src/lib.rs", "project": "app", "line": 0, "character": 8});
+    for _ in 0..2 {
+        let parsed = McpResult::parse(LspHoverTool.execute(hover.clone(), &env.config).await?)?;
+        assert!(parsed.content["hover_info"].to_string().contains("fn cached()"), "{}", parsed.content);
+    }
+
+    let logged = std::fs::read_to_string(&log)?;
+    assert_eq!(logged.matches("textDocument/hover").count(), 1, "{logged}");
+
+    let parsed = McpResult::parse(CacheStatsTool.execute(json!({}), &env.config).await?)?;
+    assert_eq!(parsed.content["total_entries"], 1);
+    assert_eq!(parsed.content["hits"], 1);
+    assert_eq!(parsed.content["misses"], 1);
+    assert_eq!(parsed.content["hit_rate"], 0.5);
+    assert!(parsed.content["total_bytes"].as_u64().unwrap() > 0);
+
+    // ✏️ An edit changes the document version, so the entry no longer applies
+    StrReplaceTool.execute(json!({"path": "app/src/lib.rs", "old_str": "cached", "new_str": "edited"}), &env.config).await?;
+    assert_eq!(manager.cache().stats().await.total_entries, 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cache_stats_counts_cached_document_symbols() -> Result<()> {
+    use empathic::tools::lsp::{CacheStatsTool, document_symbols::LspDocumentSymbolsTool};
+
+    let mut env = TestEnv::new()?;
+    env.create_file("app/Cargo.toml", "[package]\nname = \"app\"\n").await?;
+    let file = env.create_file("app/src/lib.rs", "pub fn cached() {}\n").await?;
+    let range = json!({"start": {"line": 0, "character": 0}, "end": {"line": 0, "character": 18}});
+    let symbol = json!({"name": "cached", "kind": 12, "range": range, "selectionRange": range});
+    let (manager, log) = one_answer_server(&env, json!([symbol])).await?;
+    env.config.set_lsp_manager(Arc::clone(&manager));
+    manager.open_document(&file).await?;
+
+    let args = json!({"file_path": "src/lib.rs", "project": "app"});
+    for _ in 0..3 {
+        let parsed = McpResult::parse(LspDocumentSymbolsTool.execute(args.clone(), &env.config).await?)?;
+        assert_eq!(parsed.content["symbols"][0]["name"], "cached", "{}", parsed.content);
+    }
+    let logged = std::fs::read_to_string(&log)?;
+    assert_eq!(logged.matches("textDocument/documentSymbol").count(), 1, "{logged}");
+
+    let parsed = McpResult::parse(CacheStatsTool.execute(json!({}), &env.config).await?)?;
+    assert_eq!(parsed.content["entries_by_type"], json!({"document_symbols": 1}));
+    assert_eq!((parsed.content["hits"].as_u64(), parsed.content["misses"].as_u64()), (Some(2), Some(1)));
+    Ok(())
+}
@@ -0,0 +1,29 @@
+//! 🦀 Tests for LSP Capabilities Tool
+
+use empathic::config::Config;
+use empathic::tools::lsp::capabilities::LspCapabilitiesTool;
+use empathic::tools::Tool;
+use serde_json::json;
+use tempfile::tempdir;
+
+#[tokio::test]
+async fn test_capabilities_schema() {
+    let tool = LspCapabilitiesTool;
+    let schema = tool.schema();
+
+    assert_eq!(tool.name(), "lsp_capabilities");
+    assert_eq!(schema["properties"]["include_raw"]["type"], "boolean");
+    assert_eq!(schema["required"], json!(["project"]));
+}
+
+#[tokio::test]
+async fn test_capabilities_missing_project() {
+    let temp_dir = tempdir().unwrap();
+    let config = Config::new(temp_dir.path().to_path_buf());
+
+    let result = LspCapabilitiesTool.execute(json!({"project": "missing"}), &config).await;
+    assert!(result.is_err());
+
+    let result = LspCapabilitiesTool.execute(json!({"project": "missing", "file_path": "src/lib.rs"}), &config).await;
+    assert!(result.is_err());
+}
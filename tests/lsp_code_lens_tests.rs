@@ -0,0 +1,61 @@
+//! 🦀 Tests for LSP Code Lens Tool
+
+use empathic::config::Config;
+use empathic::tools::lsp::code_lens::{CodeLensOutput, LspCodeLensTool};
+use empathic::tools::Tool;
+use serde_json::json;
+use tempfile::tempdir;
+
+#[tokio::test]
+async fn test_code_lens_schema() {
+    let tool = LspCodeLensTool;
+    let schema = tool.schema();
+
+    assert_eq!(tool.name(), "lsp_code_lens");
+    assert_eq!(schema["properties"]["resolve"]["type"], "boolean");
+    assert_eq!(schema["required"], json!(["file_path", "project"]));
+}
+
+#[tokio::test]
+async fn test_code_lens_rust_file_only() {
+    let temp_dir = tempdir().unwrap();
+    let config = Config::new(temp_dir.path().to_path_buf());
+    let project_dir = temp_dir.path().join("test");
+    std::fs::create_dir_all(&project_dir).unwrap();
+    std::fs::write(project_dir.join("notes.txt"), "hello world").unwrap();
+
+    let result = LspCodeLensTool.execute(json!({"file_path": "notes.txt", "project": "test"}), &config).await;
+    assert!(result.unwrap_err().to_string().contains("Only supports Rust files"));
+}
+
+#[tokio::test]
+async fn test_code_lens_output_format() {
+    let output: CodeLensOutput = serde_json::from_value(json!({
+        "file_path": "src/lib.rs",
+        "project": "test",
+        "lenses": [
+            {
+                "range": {"start_line": 3, "start_character": 4, "end_line": 3, "end_character": 12},
+                "kind": "run",
+                "title": "▶︎ Run Test",
+                "command": "rust-analyzer.runSingle",
+                "arguments": [{"kind": "cargo", "args": {"cargoArgs": ["test"]}}],
+                "resolved": true
+            },
+            {
+                "range": {"start_line": 7, "start_character": 0, "end_line": 7, "end_character": 10},
+                "kind": "other",
+                "title": null,
+                "command": null,
+                "resolved": false
+            }
+        ],
+        "total_lenses": 2,
+        "unresolved": 1
+    })).unwrap();
+    assert_eq!(output.lenses[0].command.as_deref(), Some("rust-analyzer.runSingle"));
+    assert!(output.lenses[1].arguments.is_none());
+    // Lenses without arguments leave the field out
+    let serialized = serde_json::to_value(&output).unwrap();
+    assert!(serialized["lenses"][1].get("arguments").is_none());
+}
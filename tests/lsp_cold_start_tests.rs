@@ -0,0 +1,103 @@
+//! 🦀 Tests for LSP Cold Start Tool
+
+use empathic::config::Config;
+use empathic::lsp::{ColdStartReport, LspManager};
+use empathic::tools::lsp::LspColdStartTool;
+use empathic::tools::Tool;
+use serde_json::{Value, json};
+use std::sync::Arc;
+use tempfile::tempdir;
+
+fn create_rust_project(root: &std::path::Path) {
+    std::fs::create_dir_all(root.join("src")).unwrap();
+    std::fs::write(
+        root.join("Cargo.toml"),
+        "[package]\nname = \"cold\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    )
+    .unwrap();
+    std::fs::write(root.join("src/main.rs"), "fn main() {\n    let x: i32 = \"oops\";\n}\n").unwrap();
+}
+
+#[tokio::test]
+async fn test_cold_start_schema() {
+    let tool = LspColdStartTool;
+    let schema = tool.schema();
+
+    assert_eq!(tool.name(), "lsp_cold_start");
+    assert_eq!(schema["required"], json!(["project"]));
+    assert!(schema["properties"]["ready_timeout_secs"].is_object());
+}
+
+#[tokio::test]
+async fn test_cold_start_rejects_unmanaged_language() {
+    let temp_dir = tempdir().unwrap();
+    let config = Config::new(temp_dir.path().to_path_buf());
+
+    let result = LspColdStartTool
+        .execute(json!({"project": "demo", "language": "python"}), &config)
+        .await;
+    assert!(result.unwrap_err().to_string().contains("not supported"));
+}
+
+/// 🎭 Manager whose rust-analyzer answers `initialize` after 500ms, publishes diagnostics
+/// 300ms after the first `didOpen`, and answers `shutdown`
+#[cfg(unix)]
+fn fake_server(root: &std::path::Path, server_dir: &std::path::Path) -> LspManager {
+    use empathic::lsp::ServerOverride;
+    use std::os::unix::fs::PermissionsExt;
+
+    let frame = |message: Value| {
+        let body = message.to_string();
+        format!("Content-Length: {}\r\n\r\n{}", body.len(), body)
+    };
+    let initialize = frame(json!({"jsonrpc": "2.0", "id": 1, "result": {"capabilities": {}}}));
+    let diagnostics = frame(json!({"jsonrpc": "2.0", "method": "textDocument/publishDiagnostics",
+        "params": {"uri": "file:///demo/src/main.rs", "diagnostics": []}}));
+    let shutdown = frame(json!({"jsonrpc": "2.0", "id": 2, "result": null}));
+    // 📜 fd 3 keeps stdin for the background `cat`; sh would otherwise hand it /dev/null
+    let log = server_dir.join("server.log");
+    let script = format!(
+        "#!/bin/sh\nexec 3<&0\ncat <&3 > '{log}' &\nsleep 0.5\nprintf '%s' '{initialize}'\n\
+         until grep -q 'didOpen' '{log}'; do sleep 0.05; done\nsleep 0.3\nprintf '%s' '{diagnostics}'\n\
+         until grep -q '\"shutdown\"' '{log}'; do sleep 0.05; done\nprintf '%s' '{shutdown}'\nwait\n",
+        log = log.display()
+    );
+    let fake = server_dir.join("rust-analyzer");
+    std::fs::write(&fake, script).unwrap();
+    std::fs::set_permissions(&fake, std::fs::Permissions::from_mode(0o755)).unwrap();
+    LspManager::new(root.to_path_buf()).with_server_override(
+        &ServerOverride { command: Some(fake.to_string_lossy().to_string()), ..Default::default() },
+        Vec::new(),
+    )
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_cold_start_phases_sum_to_total() {
+    let temp_dir = tempdir().unwrap();
+    let server_dir = tempdir().unwrap();
+    let root = temp_dir.path().to_path_buf();
+    create_rust_project(&root.join("demo"));
+
+    let manager = Arc::new(fake_server(&root, server_dir.path()));
+    let config = Config::new_with_lsp(root, manager.clone());
+
+    let response = LspColdStartTool
+        .execute(json!({"project": "demo", "ready_timeout_secs": 30}), &config)
+        .await
+        .unwrap();
+    let report: ColdStartReport =
+        serde_json::from_str(response["content"][0]["text"].as_str().unwrap()).unwrap();
+
+    assert!(report.reached_ready, "{report:?}");
+    assert!(report.spawn_ms > 0.0);
+    // ⏱️ The script's delays show up in the phase they belong to
+    assert!(report.initialize_ms >= 500.0, "{report:?}");
+    assert!(report.ready_ms >= 300.0, "{report:?}");
+    assert!(report.phases_ms() <= report.total_ms);
+    // Only bookkeeping happens between phases
+    assert!(report.total_ms - report.phases_ms() < report.total_ms * 0.1 + 50.0,
+        "phases {:.1}ms vs total {:.1}ms", report.phases_ms(), report.total_ms);
+
+    manager.shutdown_all().await.unwrap();
+}
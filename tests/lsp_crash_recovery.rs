@@ -0,0 +1,48 @@
+//! 🔄 Crash recovery tests - the resource monitor respawns servers that exited
+#![cfg(unix)]
+
+mod common;
+
+use anyhow::Result;
+use common::*;
+use empathic::lsp::resource::ResourceConfig;
+use empathic::lsp::{LspManager, ServerOverride};
+use serde_json::json;
+use std::os::unix::fs::PermissionsExt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+#[tokio::test]
+async fn test_resource_monitor_respawns_crashed_server() -> Result<()> {
+    let env = TestEnv::new()?;
+    env.create_file("Cargo.toml", "[package]\nname = \"app\"\n").await?;
+    let file = env.create_file("src/lib.rs", "pub fn app() {}\n").await?;
+
+    // 💀 A "rust-analyzer" that counts its starts, initializes, then dies
+    let spawns = env.root_path.join("spawns.log");
+    let body = json!({"jsonrpc": "2.0", "id": 1, "result": {"capabilities": {}}}).to_string();
+    let script = format!(
+        "#!/bin/sh\necho started >> '{}'\nsleep 0.3\nprintf 'Content-Length: {}\\r\\n\\r\\n%s' '{}'\nsleep 0.5\n",
+        spawns.display(), body.len(), body
+    );
+    let fake = env.create_file("toolchain/rust-analyzer", &script).await?;
+    std::fs::set_permissions(&fake, std::fs::Permissions::from_mode(0o755))?;
+
+    let config = ResourceConfig { monitor_interval_secs: 1, restart_grace_secs: 1, ..ResourceConfig::default() };
+    let manager = Arc::new(LspManager::with_resource_config(env.root_path.clone(), config).with_server_override(
+        &ServerOverride { command: Some(fake.to_string_lossy().to_string()), ..Default::default() },
+        Vec::new(),
+    ));
+    manager.get_client(&file).await?;
+    let monitor = manager.start_resource_monitor();
+
+    // Nothing asks for the client again: only the monitor can bring it back
+    let started = Instant::now();
+    let count = || std::fs::read_to_string(&spawns).unwrap_or_default().lines().count();
+    while count() < 2 {
+        assert!(started.elapsed() < Duration::from_secs(15), "crashed server was never respawned");
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    monitor.abort();
+    Ok(())
+}
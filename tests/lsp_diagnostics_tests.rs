@@ -103,3 +103,18 @@ edition = "2021"
     let response = result.unwrap();
     assert!(response["content"][0]["text"].is_string());
 }
+
+#[tokio::test]
+async fn test_diagnostics_accepts_unsaved_content() {
+    let temp_dir = tempdir().unwrap();
+    let config = Config::new(temp_dir.path().to_path_buf());
+    let project_dir = temp_dir.path().join("test");
+    std::fs::create_dir_all(&project_dir).unwrap();
+    std::fs::write(project_dir.join("lib.rs"), "pub fn ok() {}\n").unwrap();
+
+    // 📝 `content` is one of the common arguments; without a manager opening the buffer is what fails
+    let args = json!({"file_path": "lib.rs", "project": "test", "content": "pub fn broken( {}\n"});
+    let err = LspDiagnosticsTool.execute(args, &config).await.unwrap_err();
+    assert!(err.to_string().contains("LSP manager not available"), "{err}");
+    assert_eq!(std::fs::read_to_string(project_dir.join("lib.rs")).unwrap(), "pub fn ok() {}\n");
+}
@@ -0,0 +1,69 @@
+//! 🦀 Tests for LSP Document Highlight Tool
+
+use empathic::config::Config;
+use empathic::tools::lsp::document_highlight::{LspDocumentHighlightTool, DocumentHighlightOutput};
+use empathic::tools::Tool;
+use serde_json::json;
+use tempfile::tempdir;
+
+#[tokio::test]
+async fn test_document_highlight_schema() {
+    let tool = LspDocumentHighlightTool;
+    let schema = tool.schema();
+
+    assert_eq!(tool.name(), "lsp_document_highlight");
+    assert!(schema["properties"]["file_path"]["type"].as_str() == Some("string"));
+    assert!(schema["properties"]["line"]["type"].as_str() == Some("integer"));
+    assert!(schema["properties"]["character"]["type"].as_str() == Some("integer"));
+    let required = schema["required"].as_array().unwrap();
+    assert!(required.contains(&json!("file_path")));
+    assert!(required.contains(&json!("project")));
+    assert!(required.contains(&json!("line")));
+    assert!(required.contains(&json!("character")));
+}
+
+#[tokio::test]
+async fn test_document_highlight_file_validation() {
+    let tool = LspDocumentHighlightTool;
+    let temp_dir = tempdir().unwrap();
+    let config = Config::new(temp_dir.path().to_path_buf());
+
+    let args = json!({"file_path": "nonexistent.rs", "project": "test", "line": 0, "character": 0});
+    let result = tool.execute(args, &config).await;
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("not found"));
+}
+
+#[tokio::test]
+async fn test_document_highlight_rust_file_only() {
+    let tool = LspDocumentHighlightTool;
+    let temp_dir = tempdir().unwrap();
+    let config = Config::new(temp_dir.path().to_path_buf());
+
+    let project_dir = temp_dir.path().join("test");
+    std::fs::create_dir_all(&project_dir).unwrap();
+    std::fs::write(project_dir.join("notes.txt"), "hello world").unwrap();
+
+    let args = json!({"file_path": "notes.txt", "project": "test", "line": 0, "character": 0});
+    let result = tool.execute(args, &config).await;
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("Only supports Rust files"));
+}
+
+#[tokio::test]
+async fn test_document_highlight_output_format() {
+    let output: DocumentHighlightOutput = serde_json::from_value(json!({
+        "file_path": "src/main.rs",
+        "project": "test",
+        "position": {"line": 1, "character": 8},
+        "highlights": [
+            {"range": {"start_line": 1, "start_character": 8, "end_line": 1, "end_character": 9}, "kind": "write"},
+            {"range": {"start_line": 2, "start_character": 14, "end_line": 2, "end_character": 15}, "kind": "read"}
+        ],
+        "summary": {"total": 2, "read": 1, "write": 1, "text": 0}
+    })).unwrap();
+
+    assert_eq!(output.highlights.len(), 2);
+    assert_eq!(output.highlights[0].kind, "write");
+    assert_eq!(output.summary.total, 2);
+}
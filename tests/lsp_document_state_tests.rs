@@ -0,0 +1,129 @@
+//! 🦀 Tests for LSP document state persistence across manager restarts
+
+use empathic::lsp::LspManager;
+use empathic::lsp::manager::tracker::{DocumentStateSnapshot, TrackedDocument};
+use serde_json::{Value, json};
+use std::path::Path;
+use tempfile::tempdir;
+
+fn create_rust_project(root: &Path) {
+    std::fs::write(
+        root.join("Cargo.toml"),
+        "[package]\nname = \"state_test\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    )
+    .unwrap();
+    std::fs::create_dir_all(root.join("src")).unwrap();
+    std::fs::write(root.join("src/main.rs"), "fn main() {\n    state_test::greet();\n}\n").unwrap();
+    std::fs::write(root.join("src/lib.rs"), "pub fn greet() {\n    println!(\"hi\");\n}\n").unwrap();
+}
+
+/// 🎭 Manager whose rust-analyzer is a script that answers `initialize` and `shutdown`
+#[cfg(unix)]
+fn fake_server(root: &Path, server_dir: &Path) -> LspManager {
+    use empathic::lsp::ServerOverride;
+    use std::os::unix::fs::PermissionsExt;
+
+    let frame = |message: Value| {
+        let body = message.to_string();
+        format!("Content-Length: {}\r\n\r\n{}", body.len(), body)
+    };
+    let initialize = frame(json!({"jsonrpc": "2.0", "id": 1, "result": {"capabilities": {}}}));
+    let shutdown = frame(json!({"jsonrpc": "2.0", "id": 2, "result": null}));
+    // 📜 Each server logs its own stdin (fd 3 keeps it for the background `cat`) and answers
+    // `shutdown` once the request shows up there
+    let script = format!(
+        "#!/bin/sh\nlog=$(mktemp '{dir}/server.XXXXXX')\nexec 3<&0\ncat <&3 > \"$log\" &\n\
+         printf '%s' '{initialize}'\nuntil grep -q '\"shutdown\"' \"$log\"; do sleep 0.05; done\n\
+         printf '%s' '{shutdown}'\nwait\n",
+        dir = server_dir.display()
+    );
+    let fake = server_dir.join("rust-analyzer");
+    std::fs::write(&fake, script).unwrap();
+    std::fs::set_permissions(&fake, std::fs::Permissions::from_mode(0o755)).unwrap();
+    LspManager::new(root.to_path_buf()).with_server_override(
+        &ServerOverride { command: Some(fake.to_string_lossy().to_string()), ..Default::default() },
+        Vec::new(),
+    )
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_snapshot_restart_restore() {
+    let temp_dir = tempdir().unwrap();
+    let server_dir = tempdir().unwrap();
+    let root = temp_dir.path().to_path_buf();
+    create_rust_project(&root);
+    let main_rs = root.join("src/main.rs");
+    let lib_rs = root.join("src/lib.rs");
+
+    // 📂 First session: open a couple of documents
+    let manager = fake_server(&root, server_dir.path());
+    for file in [&main_rs, &lib_rs] {
+        manager.ensure_document_open(file).await.unwrap();
+    }
+
+    let snapshot = manager.snapshot_documents().await;
+    assert_eq!(snapshot.documents.len(), 2);
+
+    // 🛑 Graceful shutdown persists the open-document set
+    manager.shutdown_all().await.unwrap();
+    let state_file = manager.state_file().to_path_buf();
+    assert!(state_file.exists());
+    drop(manager);
+
+    // ♻️ "Restart": a fresh manager restores the same documents
+    let manager = fake_server(&root, server_dir.path());
+    let reopened = manager.restore_document_state().await.unwrap();
+    assert!(reopened.contains(&main_rs));
+    assert!(reopened.contains(&lib_rs));
+
+    let restored = manager.snapshot_documents().await;
+    assert_eq!(restored.documents, snapshot.documents);
+
+    manager.shutdown_all().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_restore_skips_vanished_documents() {
+    let temp_dir = tempdir().unwrap();
+    let root = temp_dir.path().to_path_buf();
+    create_rust_project(&root);
+
+    let manager = LspManager::new(root.clone());
+    DocumentStateSnapshot::new(vec![TrackedDocument {
+        path: root.join("src/deleted.rs"),
+        version: 2,
+    }])
+    .save(manager.state_file())
+    .unwrap();
+
+    let reopened = manager.restore_document_state().await.unwrap();
+    assert!(reopened.is_empty());
+    assert!(manager.snapshot_documents().await.is_empty());
+}
+
+#[tokio::test]
+async fn test_shutdown_with_nothing_open_clears_state() {
+    let temp_dir = tempdir().unwrap();
+    let manager = LspManager::new(temp_dir.path().to_path_buf());
+
+    DocumentStateSnapshot::new(vec![TrackedDocument {
+        path: temp_dir.path().join("stale.rs"),
+        version: 1,
+    }])
+    .save(manager.state_file())
+    .unwrap();
+    assert!(manager.state_file().exists());
+
+    manager.shutdown_all().await.unwrap();
+    assert!(!manager.state_file().exists());
+}
+
+#[tokio::test]
+async fn test_restore_without_state_file() {
+    let temp_dir = tempdir().unwrap();
+    let manager = LspManager::new(temp_dir.path().to_path_buf());
+
+    assert!(!manager.state_file().exists());
+    assert!(manager.restore_document_state().await.unwrap().is_empty());
+}
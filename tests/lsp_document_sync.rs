@@ -0,0 +1,153 @@
+//! 🔄 Edit tools keep open LSP documents in sync (didChange/didSave, didClose/didOpen on move)
+#![cfg(unix)]
+
+mod common;
+
+use anyhow::Result;
+use common::*;
+use empathic::lsp::{LspManager, ServerOverride};
+use empathic::tools::{
+    Tool, insert_at_line::InsertAtLineTool, lsp::hover::LspHoverTool, move_file::MoveFileTool,
+    replace::ReplaceTool, str_replace::StrReplaceTool, write_file::WriteFileTool,
+};
+use serde_json::{Value, json};
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// 📜 A "rust-analyzer" that answers initialize and logs everything it's sent
+async fn recording_server(env: &TestEnv) -> Result<(Arc<LspManager>, PathBuf)> {
+    let log = env.root_path.join("server.log");
+    let body = json!({"jsonrpc": "2.0", "id": 1, "result": {"capabilities": {}}}).to_string();
+    let script = format!(
+        "#!/bin/sh\nsleep 0.3\nprintf 'Content-Length: {}\\r\\n\\r\\n%s' '{}'\ncat > '{}'\n",
+        body.len(), body, log.display()
+    );
+    let fake = env.create_file("toolchain/rust-analyzer", &script).await?;
+    std::fs::set_permissions(&fake, std::fs::Permissions::from_mode(0o755))?;
+
+    let manager = LspManager::new(env.root_path.clone()).with_server_override(
+        &ServerOverride { command: Some(fake.to_string_lossy().to_string()), ..Default::default() },
+        Vec::new(),
+    );
+    Ok((Arc::new(manager), log))
+}
+
+/// ⏳ Wait until the server log contains every needle
+async fn assert_logged(log: &Path, needles: &[&str]) {
+    let mut logged = String::new();
+    for _ in 0..50 {
+        logged = std::fs::read_to_string(log).unwrap_or_default();
+        if needles.iter().all(|needle| logged.contains(needle)) {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("server never received {needles:?}:\n{logged}");
+}
+
+async fn assert_edit_synced(tool: &dyn Tool, args: Value, expected: &str) -> Result<()> {
+    let mut env = TestEnv::new()?;
+    env.create_file("Cargo.toml", "[package]\nname = \"app\"\n").await?;
+    let file = env.create_file("src/lib.rs", "pub fn old() {}\n").await?;
+    let (manager, log) = recording_server(&env).await?;
+    env.config.set_lsp_manager(Arc::clone(&manager));
+    manager.open_document(&file).await?;
+
+    let result = McpResult::parse(tool.execute(args, &env.config).await?)?;
+    assert_mcp_success(&result);
+
+    assert_logged(&log, &["textDocument/didChange", expected, "textDocument/didSave"]).await;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_str_replace_syncs_open_document() -> Result<()> {
+    assert_edit_synced(&StrReplaceTool, json!({"path": "src/lib.rs", "old_str": "old", "new_str": "renamed"}), "pub fn renamed() {}").await
+}
+
+#[tokio::test]
+async fn test_replace_syncs_open_document() -> Result<()> {
+    assert_edit_synced(&ReplaceTool, json!({"path": "src/lib.rs", "search": "old", "replace": "renamed"}), "pub fn renamed() {}").await
+}
+
+#[tokio::test]
+async fn test_insert_at_line_syncs_open_document() -> Result<()> {
+    assert_edit_synced(&InsertAtLineTool, json!({"path": "src/lib.rs", "line": 0, "content": "pub fn added() {}"}), "pub fn added() {}").await
+}
+
+#[tokio::test]
+async fn test_write_file_syncs_open_document() -> Result<()> {
+    assert_edit_synced(&WriteFileTool, json!({"path": "src/lib.rs", "content": "pub fn written() {}\n"}), "pub fn written() {}").await
+}
+
+#[tokio::test]
+async fn test_write_file_range_syncs_whole_document() -> Result<()> {
+    // Only one line is written, but the server gets the full new content
+    assert_edit_synced(&WriteFileTool, json!({"path": "src/lib.rs", "content": "pub fn ranged() {}", "start": 1, "end": 1}), "pub fn ranged() {}").await
+}
+
+#[tokio::test]
+async fn test_closed_document_is_not_synced() -> Result<()> {
+    // 📄 Nothing open means nothing to sync - and no server gets spawned for the edit
+    let mut env = TestEnv::new()?;
+    env.create_file("Cargo.toml", "[package]\nname = \"app\"\n").await?;
+    env.create_file("src/lib.rs", "pub fn old() {}\n").await?;
+    let (manager, _log) = recording_server(&env).await?;
+    env.config.set_lsp_manager(Arc::clone(&manager));
+
+    let result = McpResult::parse(
+        ReplaceTool.execute(json!({"path": "src/lib.rs", "search": "old", "replace": "new"}), &env.config).await?,
+    )?;
+    assert_eq!(result.content["lsp_synced"], json!(false));
+    assert!(manager.get_server_status().await.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_move_file_reopens_document_at_new_path() -> Result<()> {
+    let mut env = TestEnv::new()?;
+    env.create_file("Cargo.toml", "[package]\nname = \"app\"\n").await?;
+    let file = env.create_file("src/old.rs", "pub fn moved() {}\n").await?;
+    let (manager, log) = recording_server(&env).await?;
+    env.config.set_lsp_manager(Arc::clone(&manager));
+    manager.open_document(&file).await?;
+
+    let result = McpResult::parse(MoveFileTool.execute(json!({"from": "src/old.rs", "to": "src/new.rs"}), &env.config).await?)?;
+    assert_mcp_success(&result);
+
+    let new_path = env.root_path.join("src/new.rs");
+    assert_logged(&log, &["textDocument/didClose", "src/old.rs", "textDocument/didOpen", "src/new.rs"]).await;
+    let open: Vec<PathBuf> = manager.snapshot_documents().await.documents.into_iter().map(|d| d.path).collect();
+    assert_eq!(open, vec![new_path]);
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "needs a working rust-analyzer on PATH"]
+async fn test_hover_reflects_edit() -> Result<()> {
+    let mut env = TestEnv::new()?;
+    env.create_file("app/Cargo.toml", "[package]\nname = \"app\"\nversion = \"0.1.0\"\nedition = \"2021\"\n").await?;
+    env.create_file("app/src/lib.rs", "/// old docs\npub fn target() {}\n").await?;
+    let manager = Arc::new(LspManager::new(env.root_path.clone()));
+    env.config.set_lsp_manager(Arc::clone(&manager));
+
+    let hover = |env: &TestEnv| {
+        let args = json!({"file_path": "src/lib.rs", "project": "app", "line": 1, "character": 7, "wait_ready_ms": 60000});
+        let config = env.config.clone();
+        async move { McpResult::parse(LspHoverTool.execute(args, &config).await?) }
+    };
+    let before = hover(&env).await?;
+    assert!(before.content.to_string().contains("old docs"), "{}", before.content);
+
+    let result = McpResult::parse(
+        StrReplaceTool.execute(json!({"path": "app/src/lib.rs", "old_str": "old docs", "new_str": "new docs"}), &env.config).await?,
+    )?;
+    assert_mcp_success(&result);
+
+    let after = hover(&env).await?;
+    let after = after.content.to_string();
+    assert!(after.contains("new docs") && !after.contains("old docs"), "{after}");
+    Ok(())
+}
@@ -0,0 +1,134 @@
+//! 🦀 Tests for LSP Execute Command Tool
+
+use empathic::config::Config;
+use empathic::lsp::LspManager;
+use empathic::tools::lsp::LspExecuteCommandTool;
+use empathic::tools::Tool;
+use serde_json::{Value, json};
+use std::sync::Arc;
+use tempfile::tempdir;
+
+/// Commands that only read or refresh server state
+const SAFE_COMMANDS: &[&str] = &["rust-analyzer.reloadWorkspace", "rust-analyzer.analyzerStatus"];
+
+fn parse_output(response: &Value) -> Value {
+    serde_json::from_str(response["content"][0]["text"].as_str().unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn test_execute_command_schema() {
+    let tool = LspExecuteCommandTool;
+    let schema = tool.schema();
+
+    assert_eq!(tool.name(), "lsp_execute_command");
+    assert_eq!(schema["required"], json!(["project"]));
+    assert_eq!(schema["properties"]["arguments"]["type"], "array");
+}
+
+#[tokio::test]
+async fn test_execute_command_missing_project() {
+    let temp_dir = tempdir().unwrap();
+    let config = Config::new(temp_dir.path().to_path_buf());
+
+    let result = LspExecuteCommandTool
+        .execute(json!({"project": "missing"}), &config)
+        .await;
+    assert!(result.unwrap_err().to_string().contains("not found"));
+}
+
+/// 📜 A "rust-analyzer" advertising `commands`, answering the first request after initialize with null
+#[cfg(unix)]
+fn fake_server(root: &std::path::Path, commands: &[&str]) -> LspManager {
+    use empathic::lsp::ServerOverride;
+    use std::os::unix::fs::PermissionsExt;
+
+    let frame = |message: Value| {
+        let body = message.to_string();
+        format!("Content-Length: {}\r\n\r\n{}", body.len(), body)
+    };
+    let capabilities = json!({"executeCommandProvider": {"commands": commands}});
+    let initialize = frame(json!({"jsonrpc": "2.0", "id": 1, "result": {"capabilities": capabilities}}));
+    let answer = frame(json!({"jsonrpc": "2.0", "id": 2, "result": null}));
+    let script = format!(
+        "#!/bin/sh\nsleep 0.3\nprintf '%s' '{initialize}'\nsleep 1\nprintf '%s' '{answer}'\ncat > /dev/null\n"
+    );
+    let fake = root.join("rust-analyzer");
+    std::fs::write(&fake, script).unwrap();
+    std::fs::set_permissions(&fake, std::fs::Permissions::from_mode(0o755)).unwrap();
+    LspManager::new(root.to_path_buf()).with_server_override(
+        &ServerOverride { command: Some(fake.to_string_lossy().to_string()), ..Default::default() },
+        Vec::new(),
+    )
+}
+
+fn demo_project(root: &std::path::Path) {
+    let project = root.join("demo");
+    std::fs::create_dir_all(project.join("src")).unwrap();
+    std::fs::write(
+        project.join("Cargo.toml"),
+        "[package]\nname = \"demo\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    )
+    .unwrap();
+    std::fs::write(project.join("src/main.rs"), "fn main() {}\n").unwrap();
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_execute_command_runs_advertised_command() {
+    let temp_dir = tempdir().unwrap();
+    let root = temp_dir.path().to_path_buf();
+    demo_project(&root);
+    let manager = Arc::new(fake_server(&root, &["rust-analyzer.reloadWorkspace"]));
+    let config = Config::new_with_lsp(root, manager.clone());
+    let tool = LspExecuteCommandTool;
+
+    let listed = parse_output(&tool.execute(json!({"project": "demo"}), &config).await.unwrap());
+    assert_eq!(listed["executed"], false);
+    assert_eq!(listed["available_commands"], json!(["rust-analyzer.reloadWorkspace"]));
+
+    let rejected = tool
+        .execute(json!({"project": "demo", "command": "definitely.not.a.command"}), &config)
+        .await;
+    assert!(rejected.unwrap_err().to_string().contains("not supported"));
+
+    let response = tool
+        .execute(json!({"project": "demo", "command": "rust-analyzer.reloadWorkspace"}), &config)
+        .await
+        .unwrap();
+    assert_eq!(parse_output(&response)["executed"], true);
+}
+
+#[tokio::test]
+#[ignore = "needs a working rust-analyzer on PATH"]
+async fn test_execute_command_with_rust_analyzer() {
+    let temp_dir = tempdir().unwrap();
+    let root = temp_dir.path().to_path_buf();
+    demo_project(&root);
+
+    let manager = Arc::new(LspManager::new(root.clone()));
+    let config = Config::new_with_lsp(root, manager.clone());
+    let tool = LspExecuteCommandTool;
+
+    // 📋 List advertised commands
+    let listed = parse_output(&tool.execute(json!({"project": "demo"}), &config).await.unwrap());
+    assert_eq!(listed["executed"], false);
+    let available: Vec<String> = serde_json::from_value(listed["available_commands"].clone()).unwrap();
+
+    // 🛡️ Unadvertised commands are rejected
+    let rejected = tool
+        .execute(json!({"project": "demo", "command": "definitely.not.a.command"}), &config)
+        .await;
+    assert!(rejected.unwrap_err().to_string().contains("not supported"));
+
+    // ⚙️ rust-analyzer advertises at least one safe command
+    let command = SAFE_COMMANDS.iter()
+        .find(|c| available.iter().any(|a| a == *c))
+        .unwrap_or_else(|| panic!("no safe command advertised (available: {available:?})"));
+    let response = tool
+        .execute(json!({"project": "demo", "command": command}), &config)
+        .await
+        .unwrap();
+    assert_eq!(parse_output(&response)["executed"], true);
+
+    manager.shutdown_all().await.unwrap();
+}
@@ -0,0 +1,33 @@
+//! 🦀 Tests for LSP Goto Definition Tool
+
+use empathic::config::Config;
+use empathic::tools::lsp::goto_definition::LspGotoDefinitionTool;
+use empathic::tools::Tool;
+use serde_json::json;
+use tempfile::tempdir;
+
+#[tokio::test]
+async fn test_goto_definition_schema() {
+    let tool = LspGotoDefinitionTool;
+    let schema = tool.schema();
+
+    assert_eq!(tool.name(), "lsp_goto_definition");
+    assert_eq!(schema["properties"]["resolve_reexports"]["type"], "boolean");
+    assert_eq!(schema["properties"]["resolve_reexports"]["default"], false);
+    // Re-export following is opt-in
+    let required = schema["required"].as_array().unwrap();
+    assert!(!required.contains(&json!("resolve_reexports")));
+}
+
+#[tokio::test]
+async fn test_goto_definition_rejects_non_boolean_resolve_reexports() {
+    let temp_dir = tempdir().unwrap();
+    let config = Config::new(temp_dir.path().to_path_buf());
+    let project_dir = temp_dir.path().join("test");
+    std::fs::create_dir_all(&project_dir).unwrap();
+    std::fs::write(project_dir.join("lib.rs"), "pub use inner::Thing;\n").unwrap();
+
+    let args = json!({"file_path": "lib.rs", "project": "test", "line": 0, "character": 15, "resolve_reexports": "yes"});
+    let result = LspGotoDefinitionTool.execute(args, &config).await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,41 @@
+//! 🏓 Health probe tests - responsiveness round-trips, not just process liveness
+#![cfg(unix)]
+
+use anyhow::Result;
+use empathic::lsp::client::LspClient;
+use std::process::Stdio;
+use std::time::Duration;
+
+async fn client_for(program: &str, args: &[&str]) -> Result<(LspClient, tokio::process::Child)> {
+    let mut child = tokio::process::Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+    let client = LspClient::new(
+        child.stdin.take().unwrap(),
+        child.stdout.take().unwrap(),
+        std::env::temp_dir(),
+    ).await?;
+    Ok((client, child))
+}
+
+/// 🔁 `cat` echoes the ping back; the client rejects it as an unknown
+/// method and that rejection is the reply the probe waits for
+#[tokio::test]
+async fn test_ping_measures_round_trip() -> Result<()> {
+    let (client, _child) = client_for("cat", &[]).await?;
+    let latency = client.ping(Duration::from_secs(5)).await?;
+    assert!(latency < Duration::from_secs(5));
+    Ok(())
+}
+
+/// 💤 A live process that never answers is caught by the probe timeout
+#[tokio::test]
+async fn test_ping_times_out_on_hung_server() -> Result<()> {
+    let (client, mut child) = client_for("sleep", &["30"]).await?;
+    assert!(client.ping(Duration::from_millis(200)).await.is_err());
+    assert!(child.try_wait()?.is_none(), "process should still be alive");
+    Ok(())
+}
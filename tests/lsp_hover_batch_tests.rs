@@ -0,0 +1,54 @@
+//! 🦀 Tests for LSP Hover Batch Tool
+
+use empathic::config::Config;
+use empathic::tools::lsp::hover_batch::{HoverBatchOutput, LspHoverBatchTool};
+use empathic::tools::Tool;
+use serde_json::json;
+use tempfile::tempdir;
+
+#[tokio::test]
+async fn test_hover_batch_schema() {
+    let tool = LspHoverBatchTool;
+    let schema = tool.schema();
+
+    assert_eq!(tool.name(), "lsp_hover_batch");
+    assert_eq!(schema["properties"]["positions"]["type"], "array");
+    assert_eq!(schema["properties"]["max_concurrency"]["maximum"], 32);
+    assert!(schema["required"].as_array().unwrap().contains(&json!("positions")));
+}
+
+#[tokio::test]
+async fn test_hover_batch_rejects_empty_positions() {
+    let temp_dir = tempdir().unwrap();
+    let config = Config::new(temp_dir.path().to_path_buf());
+    std::fs::write(temp_dir.path().join("main.rs"), "fn main() {}").unwrap();
+
+    let args = json!({"file_path": "main.rs", "project": "", "positions": []});
+    assert!(LspHoverBatchTool.execute(args, &config).await.is_err());
+}
+
+#[tokio::test]
+async fn test_hover_batch_results_align_with_positions() {
+    let temp_dir = tempdir().unwrap();
+    let config = Config::new(temp_dir.path().to_path_buf());
+    std::fs::write(temp_dir.path().join("main.rs"), "fn main() {\n    let x: i32 = 42;\n    println!(\"{}\", x);\n}").unwrap();
+
+    let positions = json!([{"line": 1, "character": 8}, {"line": 2, "character": 20}, {"line": 0, "character": 3}]);
+    let args = json!({"file_path": "main.rs", "project": "", "positions": positions, "max_concurrency": 2});
+    match LspHoverBatchTool.execute(args, &config).await {
+        Ok(response) => {
+            let output: HoverBatchOutput = serde_json::from_str(response["content"][0]["text"].as_str().unwrap()).unwrap();
+            let requested: Vec<(u32, u32)> = output.results.iter().map(|r| (r.position.line, r.position.character)).collect();
+            assert_eq!(requested, vec![(1, 8), (2, 20), (0, 3)]);
+        }
+        Err(e) => {
+            // Without a running rust-analyzer the batch fails before any request
+            let error_msg = e.to_string();
+            assert!(
+                error_msg.contains("LSP") || error_msg.contains("rust-analyzer") || error_msg.contains("timeout"),
+                "Unexpected error: {}",
+                error_msg
+            );
+        }
+    }
+}
@@ -162,3 +162,61 @@ async fn test_hover_variable_info() {
         }
     }
 }
+
+#[tokio::test]
+#[ignore = "needs a working rust-analyzer on PATH"]
+async fn test_hover_after_emoji_uses_tool_columns() {
+    // 🔤 Tool columns are UTF-16 whatever the server negotiated; the client converts them
+    use empathic::lsp::LspManager;
+    use std::sync::Arc;
+
+    let temp_dir = tempdir().unwrap();
+    let root = temp_dir.path().to_path_buf();
+    std::fs::write(root.join("Cargo.toml"), "[package]\nname = \"emoji\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[workspace]\n").unwrap();
+    std::fs::create_dir_all(root.join("src")).unwrap();
+    let line = "    let crab = \"🦀\"; let count: u64 = 1;";
+    std::fs::write(root.join("src/main.rs"), format!("fn main() {{\n{}\n    println!(\"{{}} {{}}\", crab, count);\n}}\n", line)).unwrap();
+
+    let manager = Arc::new(LspManager::new(root.clone()));
+    let config = Config::new_with_lsp(root.clone(), Arc::clone(&manager));
+
+    let character = line[..line.find("count").unwrap()].encode_utf16().count();
+    let args = json!({"file_path": "src/main.rs", "project": "", "line": 1, "character": character, "wait_ready_ms": 60000});
+    let response = LspHoverTool.execute(args, &config).await.expect("hover failed");
+    let output: HoverOutput = serde_json::from_str(response["content"][0]["text"].as_str().unwrap()).unwrap();
+    // Only `count` is a u64; a misread column would land on `1` or `;`
+    let hover = output.hover_info.expect("no hover at `count`");
+    assert!(hover.markdown.contains("u64"), "hover landed on the wrong column: {}", hover.markdown);
+    manager.shutdown_all().await.ok();
+}
+
+/// 📁 `<tmp>/root/demo` project next to `<tmp>/outside.rs`
+fn escape_fixture() -> (tempfile::TempDir, Config) {
+    let temp_dir = tempdir().unwrap();
+    let root = temp_dir.path().join("root");
+    std::fs::create_dir_all(root.join("demo/src")).unwrap();
+    std::fs::write(root.join("demo/Cargo.toml"), "[package]\nname = \"demo\"\n").unwrap();
+    std::fs::write(temp_dir.path().join("outside.rs"), "fn secret() {}\n").unwrap();
+    let config = Config::new(root);
+    (temp_dir, config)
+}
+
+#[tokio::test]
+async fn test_hover_rejects_dotdot_escape() {
+    let (_temp_dir, config) = escape_fixture();
+
+    let args = json!({"project": "demo", "file_path": "../../outside.rs", "line": 0, "character": 3});
+    let error = LspHoverTool.execute(args, &config).await.unwrap_err().to_string();
+    assert!(error.contains("access denied"), "{error}");
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_hover_rejects_symlink_escape() {
+    let (temp_dir, config) = escape_fixture();
+    std::os::unix::fs::symlink(temp_dir.path().join("outside.rs"), config.root_dir.join("demo/src/lib.rs")).unwrap();
+
+    let args = json!({"project": "demo", "file_path": "src/lib.rs", "line": 0, "character": 3});
+    let error = LspHoverTool.execute(args, &config).await.unwrap_err().to_string();
+    assert!(error.contains("access denied"), "{error}");
+}
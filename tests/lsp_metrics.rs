@@ -0,0 +1,61 @@
+//! 📊 LSP metrics tests - per-method timings recorded by clients, lsp_metrics tool
+#![cfg(unix)]
+
+mod common;
+
+use anyhow::Result;
+use common::*;
+use empathic::lsp::LspManager;
+use empathic::lsp::client::LspClient;
+use empathic::lsp::performance::LspMetrics;
+use empathic::tools::{Tool, lsp::LspMetricsTool};
+use serde_json::json;
+use std::process::Stdio;
+use std::sync::Arc;
+
+/// 🔁 `cat` echoes each request back, which the client rejects as an unknown
+/// server request; that rejection arrives as the error response
+#[tokio::test]
+async fn test_client_records_per_method_timings() -> Result<()> {
+    let mut child = tokio::process::Command::new("cat")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+    let client = LspClient::new(child.stdin.take().unwrap(), child.stdout.take().unwrap(), std::env::temp_dir()).await?;
+
+    let metrics = Arc::new(LspMetrics::default());
+    // Clones share the attachment, as with clients handed out by the manager
+    client.clone().attach_metrics(Arc::clone(&metrics));
+    for _ in 0..3 {
+        let result: Result<serde_json::Value, _> = client.send_request("textDocument/hover", None).await;
+        assert!(result.is_err());
+    }
+
+    let snapshot = metrics.snapshot();
+    let hover = &snapshot.methods["textDocument/hover"];
+    assert_eq!((hover.count, hover.errors), (3, 3));
+    assert!(hover.p50_ms <= hover.p99_ms && hover.p99_ms <= hover.max_ms);
+    assert_eq!(snapshot.queue_depth, 0);
+    assert_eq!(snapshot.peak_queue_depth, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_lsp_metrics_tool_reports_and_resets() -> Result<()> {
+    let mut env = TestEnv::new()?;
+    let manager = Arc::new(LspManager::new(env.root_path.clone()));
+    env.config.set_lsp_manager(Arc::clone(&manager));
+    manager.benchmark_operation("warmup", async { Ok(()) }).await?;
+
+    let first = McpResult::parse(LspMetricsTool.execute(json!({"reset": true}), &env.config).await?)?;
+    assert_eq!(first.content["total_requests"], 1);
+    assert_eq!(first.content["reset"], true);
+    assert_eq!(first.content["active_servers"], json!([]));
+    assert!(first.content["methods"].is_object());
+    assert!(first.content["cache_hit_rate"].is_number());
+
+    let second = McpResult::parse(LspMetricsTool.execute(json!({}), &env.config).await?)?;
+    assert_eq!(second.content["total_requests"], 0);
+    Ok(())
+}
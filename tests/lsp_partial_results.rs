@@ -0,0 +1,92 @@
+//! 🌊 Partial result streaming for workspace/symbol against a scripted server
+#![cfg(unix)]
+
+use anyhow::Result;
+use empathic::lsp::client::LspClient;
+use lsp_types::{SymbolInformation, WorkspaceSymbolParams};
+use serde_json::{json, Value};
+use std::process::Stdio;
+
+fn frame(message: Value) -> String {
+    let body = message.to_string();
+    format!("Content-Length: {}\r\n\r\n{}", body.len(), body)
+}
+
+fn symbol(name: &str) -> Value {
+    json!({
+        "name": name,
+        "kind": 12,
+        "location": {
+            "uri": "file:///tmp/lib.rs",
+            "range": {"start": {"line": 0, "character": 0}, "end": {"line": 0, "character": 1}}
+        }
+    })
+}
+
+/// 📜 A "server" that ignores its input and replays `messages` after a pause,
+/// answering the first request (id 1)
+async fn scripted_client(messages: &[Value]) -> Result<(LspClient, tokio::process::Child)> {
+    let script: String = messages.iter().cloned().map(frame).collect();
+    let mut child = tokio::process::Command::new("sh")
+        .arg("-c")
+        .arg("sleep 0.2; printf '%s' \"$1\"; cat > /dev/null")
+        .arg("sh")
+        .arg(script)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+    let client = LspClient::new(child.stdin.take().unwrap(), child.stdout.take().unwrap(), std::env::temp_dir()).await?;
+    Ok((client, child))
+}
+
+fn params(query: &str) -> WorkspaceSymbolParams {
+    WorkspaceSymbolParams {
+        query: query.to_string(),
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    }
+}
+
+fn names(symbols: &[SymbolInformation]) -> Vec<String> {
+    symbols.iter().map(|symbol| symbol.name.clone()).collect()
+}
+
+#[tokio::test]
+async fn test_partial_batches_are_streamed_then_consolidated() -> Result<()> {
+    let (client, _child) = scripted_client(&[
+        json!({"jsonrpc": "2.0", "method": "$/progress", "params": {"token": "empathic-partial-1", "value": [symbol("alpha"), symbol("beta")]}}),
+        // Progress for someone else's token is not ours
+        json!({"jsonrpc": "2.0", "method": "$/progress", "params": {"token": "other", "value": [symbol("stray")]}}),
+        json!({"jsonrpc": "2.0", "method": "$/progress", "params": {"token": "empathic-partial-1", "value": [symbol("gamma")]}}),
+        json!({"jsonrpc": "2.0", "id": 1, "result": []}),
+    ]).await?;
+
+    let mut batches = Vec::new();
+    let symbols = client.workspace_symbols_streaming(params("a"), |batch| batches.push(names(batch))).await?;
+
+    assert_eq!(batches, vec![vec!["alpha".to_string(), "beta".to_string()], vec!["gamma".to_string()]]);
+    assert_eq!(names(&symbols.unwrap()), vec!["alpha", "beta", "gamma"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_server_without_partial_results_answers_at_the_end() -> Result<()> {
+    let (client, _child) = scripted_client(&[
+        json!({"jsonrpc": "2.0", "id": 1, "result": [symbol("alpha"), symbol("beta")]}),
+    ]).await?;
+
+    let mut batches = 0;
+    let symbols = client.workspace_symbols_streaming(params("a"), |_| batches += 1).await?;
+
+    assert_eq!(batches, 0);
+    assert_eq!(names(&symbols.unwrap()), vec!["alpha", "beta"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_null_result_without_batches_stays_none() -> Result<()> {
+    let (client, _child) = scripted_client(&[json!({"jsonrpc": "2.0", "id": 1, "result": null})]).await?;
+    assert!(client.workspace_symbols_streaming(params("zzz"), |_| {}).await?.is_none());
+    Ok(())
+}
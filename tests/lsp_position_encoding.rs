@@ -0,0 +1,60 @@
+//! 🔤 Tool columns are UTF-16 even when the server negotiated UTF-8
+#![cfg(unix)]
+
+mod common;
+
+use anyhow::Result;
+use common::*;
+use empathic::lsp::{LspManager, ServerOverride};
+use empathic::tools::{Tool, lsp::hover::LspHoverTool};
+use serde_json::{Value, json};
+use std::os::unix::fs::PermissionsExt;
+use std::sync::Arc;
+
+const LINE: &str = "let crab = \"🦀\"; let count: u64 = 1;";
+
+#[tokio::test]
+async fn test_hover_columns_are_converted_to_and_from_utf8() -> Result<()> {
+    let mut env = TestEnv::new()?;
+    env.create_file("app/Cargo.toml", "[package]\nname = \"app\"\n").await?;
+    let file = env.create_file("app/src/lib.rs", &format!("{LINE}\n")).await?;
+
+    // 📜 A UTF-8 server whose hover range covers `count`, counted in bytes
+    let count_utf8 = LINE.find("count").unwrap();
+    let frame = |message: Value| {
+        let body = message.to_string();
+        format!("Content-Length: {}\r\n\r\n{}", body.len(), body)
+    };
+    let initialize = frame(json!({"jsonrpc": "2.0", "id": 1, "result": {"capabilities": {"positionEncoding": "utf-8"}}}));
+    let range = json!({"start": {"line": 0, "character": count_utf8}, "end": {"line": 0, "character": count_utf8 + 5}});
+    let hover = frame(json!({"jsonrpc": "2.0", "id": 2, "result": {"contents": "count: u64", "range": range}}));
+    let log = env.root_path.join("server.log");
+    // ⏳ Answer hover only once the request has reached the log, so the assert never races the logger
+    // (fd 3 keeps stdin for the background `cat`; sh would otherwise hand it /dev/null)
+    let script = format!(
+        "#!/bin/sh\nexec 3<&0\ncat <&3 > '{log}' &\nsleep 0.3\nprintf '%s' '{initialize}'\n\
+         until grep -q 'textDocument/hover' '{log}'; do sleep 0.05; done\nprintf '%s' '{hover}'\nwait\n",
+        log = log.display()
+    );
+    let fake = env.create_file("toolchain/rust-analyzer", &script).await?;
+    std::fs::set_permissions(&fake, std::fs::Permissions::from_mode(0o755))?;
+    let manager = Arc::new(LspManager::new(env.root_path.clone()).with_server_override(
+        &ServerOverride { command: Some(fake.to_string_lossy().to_string()), ..Default::default() },
+        Vec::new(),
+    ));
+    env.config.set_lsp_manager(Arc::clone(&manager));
+    manager.open_document(&file).await?;
+
+    let count_utf16 = LINE[..count_utf8].encode_utf16().count();
+    let args = json!({"file_path": "src/lib.rs", "project": "app", "line": 0, "character": count_utf16});
+    let parsed = McpResult::parse(LspHoverTool.execute(args, &env.config).await?)?;
+
+    // ➡️ The server was asked for the byte column
+    let logged = std::fs::read_to_string(&log)?;
+    assert!(logged.contains(&format!("\"position\":{{\"character\":{count_utf8},\"line\":0}}")), "{logged}");
+    // ⬅️ The tool reports UTF-16 columns back
+    let range = &parsed.content["hover_info"]["range"];
+    assert_eq!(range["start_character"], json!(count_utf16), "{}", parsed.content);
+    assert_eq!(range["end_character"], json!(count_utf16 + 5));
+    Ok(())
+}
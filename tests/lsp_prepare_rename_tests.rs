@@ -0,0 +1,60 @@
+//! 🦀 Tests for LSP Prepare Rename Tool
+
+use empathic::config::Config;
+use empathic::tools::lsp::prepare_rename::{LspPrepareRenameTool, PrepareRenameOutput};
+use empathic::tools::Tool;
+use serde_json::json;
+use tempfile::tempdir;
+
+#[tokio::test]
+async fn test_prepare_rename_schema() {
+    let tool = LspPrepareRenameTool;
+    let schema = tool.schema();
+
+    assert_eq!(tool.name(), "lsp_prepare_rename");
+    assert!(schema["properties"]["line"]["type"].as_str() == Some("integer"));
+    assert!(schema["properties"]["character"]["type"].as_str() == Some("integer"));
+    let required = schema["required"].as_array().unwrap();
+    for field in ["file_path", "project", "line", "character"] {
+        assert!(required.contains(&json!(field)), "{field} should be required");
+    }
+}
+
+#[tokio::test]
+async fn test_prepare_rename_rust_file_only() {
+    let temp_dir = tempdir().unwrap();
+    let config = Config::new(temp_dir.path().to_path_buf());
+    let project_dir = temp_dir.path().join("test");
+    std::fs::create_dir_all(&project_dir).unwrap();
+    std::fs::write(project_dir.join("notes.txt"), "hello world").unwrap();
+
+    let args = json!({"file_path": "notes.txt", "project": "test", "line": 0, "character": 0});
+    let result = LspPrepareRenameTool.execute(args, &config).await;
+    assert!(result.unwrap_err().to_string().contains("Only supports Rust files"));
+}
+
+#[tokio::test]
+async fn test_prepare_rename_output_format() {
+    let renameable: PrepareRenameOutput = serde_json::from_value(json!({
+        "file_path": "src/main.rs",
+        "project": "test",
+        "position": {"line": 1, "character": 8},
+        "renameable": true,
+        "range": {"start_line": 1, "start_character": 8, "end_line": 1, "end_character": 13},
+        "placeholder": "total"
+    })).unwrap();
+    assert!(renameable.renameable);
+    assert_eq!(renameable.range.unwrap().end_character, 13);
+
+    let rejected: PrepareRenameOutput = serde_json::from_value(json!({
+        "file_path": "src/main.rs",
+        "project": "test",
+        "position": {"line": 0, "character": 0},
+        "renameable": false,
+        "reason": "No references found at position"
+    })).unwrap();
+    assert!(rejected.range.is_none());
+    assert!(rejected.reason.is_some());
+    // Absent fields stay absent in the serialized result
+    assert!(serde_json::to_value(&rejected).unwrap().get("range").is_none());
+}
@@ -0,0 +1,45 @@
+//! 🔥 LSP pre-warming: which projects get a server at startup
+
+mod common;
+
+use anyhow::Result;
+use common::*;
+use empathic::lsp::{LspManager, ServerOverride};
+use std::sync::Arc;
+
+#[tokio::test]
+async fn test_prewarm_targets_shallowest_first_and_capped() -> Result<()> {
+    let env = TestEnv::new()?;
+    env.create_file("zeta/Cargo.toml", "[package]\nname = \"zeta\"\n").await?;
+    env.create_file("alpha/Cargo.toml", "[package]\nname = \"alpha\"\n").await?;
+    env.create_file("alpha/crates/inner/Cargo.toml", "[package]\nname = \"inner\"\n").await?;
+    env.create_file("web/package.json", "{}").await?;
+
+    let manager = LspManager::new(env.root_path.clone());
+    let targets = manager.prewarm_targets(10)?;
+    assert_eq!(targets, vec![
+        env.root_path.join("alpha"),
+        env.root_path.join("zeta"),
+        env.root_path.join("alpha/crates/inner"),
+    ]);
+    assert_eq!(manager.prewarm_targets(1)?, vec![env.root_path.join("alpha")]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_prewarm_is_opt_in_and_survives_spawn_failures() -> Result<()> {
+    let env = TestEnv::new()?;
+    env.create_file("app/Cargo.toml", "[package]\nname = \"app\"\n").await?;
+
+    let manager = Arc::new(LspManager::new(env.root_path.clone()).with_server_override(
+        &ServerOverride { command: Some("/nonexistent/rust-analyzer".to_string()), ..Default::default() },
+        Vec::new(),
+    ));
+    assert!(manager.start_prewarm(0).is_none());
+
+    // A server that can't start is logged and skipped, not fatal
+    let handle = manager.start_prewarm(2).expect("pre-warming enabled");
+    handle.await?;
+    assert!(manager.get_server_status().await.is_empty());
+    Ok(())
+}
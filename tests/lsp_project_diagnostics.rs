@@ -0,0 +1,37 @@
+//! 🩺 Project diagnostics tool tests - schema and project validation
+
+mod common;
+
+use anyhow::Result;
+use common::*;
+use empathic::lsp::LspManager;
+use empathic::tools::{Tool, lsp::LspProjectDiagnosticsTool};
+use serde_json::json;
+use std::sync::Arc;
+
+#[tokio::test]
+async fn test_project_diagnostics_schema() {
+    let tool = LspProjectDiagnosticsTool;
+    let schema = tool.schema();
+
+    assert_eq!(tool.name(), "lsp_project_diagnostics");
+    assert_eq!(schema["required"], json!(["project"]));
+    for field in ["path", "max_files", "wait_ms"] {
+        assert!(schema["properties"][field].is_object(), "{field} should be in the schema");
+    }
+}
+
+#[tokio::test]
+async fn test_project_diagnostics_requires_cargo_project() -> Result<()> {
+    let mut env = TestEnv::new()?;
+    env.config.set_lsp_manager(Arc::new(LspManager::new(env.root_path.clone())));
+    env.create_file("notes/readme.txt", "not rust").await?;
+
+    let result = LspProjectDiagnosticsTool.execute(json!({"project": "notes"}), &env.config).await;
+    assert!(result.unwrap_err().to_string().contains("Cargo.toml not found"));
+
+    env.create_file("app/Cargo.toml", "[package]\nname = \"app\"\n").await?;
+    let result = LspProjectDiagnosticsTool.execute(json!({"project": "app", "path": "missing"}), &env.config).await;
+    assert!(result.is_err(), "a missing subdirectory is rejected before any server starts");
+    Ok(())
+}
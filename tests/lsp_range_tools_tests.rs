@@ -0,0 +1,85 @@
+//! 🦀 Tests for LSP Folding Range, Selection Range and Semantic Tokens Tools
+
+use empathic::config::Config;
+use empathic::tools::lsp::{LspFoldingRangeTool, LspSelectionRangeTool, LspSemanticTokensTool};
+use empathic::tools::Tool;
+use serde_json::json;
+use tempfile::tempdir;
+
+#[tokio::test]
+async fn test_folding_range_schema() {
+    let tool = LspFoldingRangeTool;
+    let schema = tool.schema();
+
+    assert_eq!(tool.name(), "lsp_folding_range");
+    let required = schema["required"].as_array().unwrap();
+    assert_eq!(required, &vec![json!("file_path"), json!("project")]);
+}
+
+#[tokio::test]
+async fn test_selection_range_schema() {
+    let tool = LspSelectionRangeTool;
+    let schema = tool.schema();
+
+    assert_eq!(tool.name(), "lsp_selection_range");
+    assert_eq!(schema["properties"]["positions"]["type"], "array");
+    assert_eq!(schema["properties"]["positions"]["items"]["required"], json!(["line", "character"]));
+    assert!(schema["required"].as_array().unwrap().contains(&json!("positions")));
+}
+
+#[tokio::test]
+async fn test_range_tools_rust_file_only() {
+    let temp_dir = tempdir().unwrap();
+    let config = Config::new(temp_dir.path().to_path_buf());
+    let project_dir = temp_dir.path().join("test");
+    std::fs::create_dir_all(&project_dir).unwrap();
+    std::fs::write(project_dir.join("notes.txt"), "hello world").unwrap();
+
+    let folding = LspFoldingRangeTool
+        .execute(json!({"file_path": "notes.txt", "project": "test"}), &config)
+        .await;
+    assert!(folding.unwrap_err().to_string().contains("Only supports Rust files"));
+
+    let selection = LspSelectionRangeTool
+        .execute(json!({
+            "file_path": "notes.txt",
+            "project": "test",
+            "positions": [{"line": 0, "character": 0}]
+        }), &config)
+        .await;
+    assert!(selection.unwrap_err().to_string().contains("Only supports Rust files"));
+}
+
+#[tokio::test]
+async fn test_selection_range_requires_positions() {
+    let temp_dir = tempdir().unwrap();
+    let config = Config::new(temp_dir.path().to_path_buf());
+    let project_dir = temp_dir.path().join("test");
+    std::fs::create_dir_all(&project_dir).unwrap();
+    std::fs::write(project_dir.join("main.rs"), "fn main() {}").unwrap();
+
+    let result = LspSelectionRangeTool
+        .execute(json!({"file_path": "main.rs", "project": "test", "positions": []}), &config)
+        .await;
+    assert!(result.unwrap_err().to_string().contains("positions"));
+}
+
+#[tokio::test]
+async fn test_semantic_tokens_schema_and_range_validation() {
+    let tool = LspSemanticTokensTool;
+    let schema = tool.schema();
+    assert_eq!(tool.name(), "lsp_semantic_tokens");
+    assert_eq!(schema["properties"]["start_line"]["type"], "integer");
+    assert_eq!(schema["required"], json!(["file_path", "project"]));
+
+    let temp_dir = tempdir().unwrap();
+    let config = Config::new(temp_dir.path().to_path_buf());
+    let project_dir = temp_dir.path().join("test");
+    std::fs::create_dir_all(&project_dir).unwrap();
+    std::fs::write(project_dir.join("main.rs"), "fn main() {}").unwrap();
+
+    let result = tool
+        .execute(json!({"file_path": "main.rs", "project": "test", "start_line": 5, "end_line": 2}), &config)
+        .await;
+    assert!(result.unwrap_err().to_string().contains("end_line"));
+}
@@ -0,0 +1,76 @@
+//! ⏳ wait_until_ready against a scripted server reporting indexing progress
+#![cfg(unix)]
+
+use anyhow::Result;
+use empathic::lsp::LspError;
+use empathic::lsp::client::LspClient;
+use serde_json::{json, Value};
+use std::process::Stdio;
+use std::time::Duration;
+
+fn frame(message: Value) -> String {
+    let body = message.to_string();
+    format!("Content-Length: {}\r\n\r\n{}", body.len(), body)
+}
+
+fn progress(token: &str, value: Value) -> Value {
+    json!({"jsonrpc": "2.0", "method": "$/progress", "params": {"token": token, "value": value}})
+}
+
+/// 📜 A "server" that ignores its input and replays `messages` after a pause
+async fn scripted_client(messages: &[Value]) -> Result<(LspClient, tokio::process::Child)> {
+    let script: String = messages.iter().cloned().map(frame).collect();
+    let mut child = tokio::process::Command::new("sh")
+        .arg("-c")
+        .arg("sleep 0.2; printf '%s' \"$1\"; cat > /dev/null")
+        .arg("sh")
+        .arg(script)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+    let client = LspClient::new(child.stdin.take().unwrap(), child.stdout.take().unwrap(), std::env::temp_dir()).await?;
+    Ok((client, child))
+}
+
+#[tokio::test]
+async fn test_ready_once_indexing_ends() -> Result<()> {
+    let (client, _child) = scripted_client(&[
+        json!({"jsonrpc": "2.0", "id": 7, "method": "window/workDoneProgress/create", "params": {"token": "rustAnalyzer/Indexing"}}),
+        progress("rustAnalyzer/Indexing", json!({"kind": "begin", "title": "Indexing", "percentage": 0})),
+        progress("rustAnalyzer/Indexing", json!({"kind": "report", "percentage": 80})),
+        progress("rustAnalyzer/Indexing", json!({"kind": "end"})),
+    ]).await?;
+    assert!(!client.is_ready());
+
+    let waited = client.wait_until_ready(Duration::from_secs(5)).await?;
+    assert!(waited >= Duration::from_millis(100), "waited {waited:?}");
+    assert!(client.is_ready());
+
+    // Already ready: returns at once
+    let waited = client.wait_until_ready(Duration::from_millis(10)).await?;
+    assert!(waited < Duration::from_millis(10));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_still_indexing_times_out() -> Result<()> {
+    let (client, _child) = scripted_client(&[
+        progress("rustAnalyzer/Indexing", json!({"kind": "begin", "title": "Indexing"})),
+    ]).await?;
+
+    let error = client.wait_until_ready(Duration::from_millis(500)).await.unwrap_err();
+    assert!(matches!(error, LspError::RequestTimeout { ref method, timeout_ms: 500 } if method == "wait_until_ready"), "{error}");
+    assert_eq!(client.readiness().active_work(), vec!["Indexing"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_quiescent_server_status_means_ready() -> Result<()> {
+    let (client, _child) = scripted_client(&[
+        json!({"jsonrpc": "2.0", "method": "experimental/serverStatus", "params": {"health": "ok", "quiescent": true}}),
+    ]).await?;
+
+    client.wait_until_ready(Duration::from_secs(5)).await?;
+    Ok(())
+}
@@ -0,0 +1,42 @@
+//! 🛠️ rust-analyzer binary overrides and ADD_PATH lookup
+#![cfg(unix)]
+
+mod common;
+
+use anyhow::Result;
+use common::*;
+use empathic::lsp::{LspManager, ServerOverride};
+use std::os::unix::fs::PermissionsExt;
+
+#[tokio::test]
+async fn test_missing_configured_binary_is_reported() -> Result<()> {
+    let env = TestEnv::new()?;
+    let main = env.create_file("app/src/main.rs", "fn main() {}").await?;
+    env.create_file("app/Cargo.toml", "[package]\nname = \"app\"\n").await?;
+
+    let manager = LspManager::new(env.root_path.clone()).with_server_override(
+        &ServerOverride { command: Some("/nonexistent/rust-analyzer".to_string()), ..Default::default() },
+        Vec::new(),
+    );
+    let error = manager.get_client(&main).await.unwrap_err().to_string();
+    assert!(error.contains("/nonexistent/rust-analyzer not found"), "{error}");
+    assert!(error.contains("LSP_RUST_PATH"), "{error}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_add_path_binary_is_spawned() -> Result<()> {
+    // 🔁 A `cat` stand-in answers nothing sensible, but proves it was found and started
+    let env = TestEnv::new()?;
+    let main = env.create_file("app/src/main.rs", "fn main() {}").await?;
+    env.create_file("app/Cargo.toml", "[package]\nname = \"app\"\n").await?;
+    let fake = env.create_file("toolchain/bin/rust-analyzer", "#!/bin/sh\nexec cat\n").await?;
+    std::fs::set_permissions(&fake, std::fs::Permissions::from_mode(0o755))?;
+
+    let manager = LspManager::new(env.root_path.clone())
+        .with_server_override(&ServerOverride::default(), vec![env.root_path.join("toolchain/bin")]);
+    let error = manager.get_client(&main).await.unwrap_err().to_string();
+    // The echoed initialize comes back rejected, so the failure is past the binary lookup
+    assert!(error.contains("initialize"), "the ADD_PATH binary should have been used: {error}");
+    Ok(())
+}
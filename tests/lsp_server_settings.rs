@@ -0,0 +1,65 @@
+//! ⚙️ Runtime server settings - overrides, didChangeConfiguration and workspace/configuration
+
+use anyhow::Result;
+use empathic::lsp::LspManager;
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn test_overrides_merge_and_persist_without_a_server() -> Result<()> {
+    let root = tempfile::TempDir::new()?;
+    let manager = LspManager::new(root.path().to_path_buf());
+    let project = root.path().join("crate-a");
+
+    // Defaults come from ServerConfig::rust_analyzer
+    assert_eq!(manager.server_settings(&project).await["checkOnSave"], true);
+
+    manager.update_server_config(&project, json!({"checkOnSave": false})).await?;
+    let effective = manager.update_server_config(&project, json!({"cargo": {"features": ["serde"]}})).await?;
+    assert_eq!(effective["checkOnSave"], false);
+    assert_eq!(effective["cargo"]["features"], json!(["serde"]));
+    assert_eq!(manager.server_settings(&project).await, effective);
+
+    // Other projects keep the defaults
+    assert_eq!(manager.server_settings(&root.path().join("crate-b")).await["checkOnSave"], true);
+
+    assert!(manager.update_server_config(&project, json!(["not", "an", "object"])).await.is_err());
+    Ok(())
+}
+
+/// 🔁 `cat` echoes every message back, so the client sees its own request as
+/// one from the server, answers it, and then receives that answer
+#[cfg(unix)]
+#[tokio::test]
+async fn test_client_answers_workspace_configuration() -> Result<()> {
+    use empathic::lsp::client::LspClient;
+    use std::process::Stdio;
+
+    let mut child = tokio::process::Command::new("cat")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+    let client = LspClient::new(
+        child.stdin.take().unwrap(),
+        child.stdout.take().unwrap(),
+        std::env::temp_dir(),
+    ).await?;
+    let mut notifications = client.subscribe_notifications();
+
+    client.did_change_configuration("rust-analyzer", json!({"checkOnSave": false, "cargo": {"target": "wasm32"}})).await?;
+    let notification = notifications.recv().await?;
+    assert_eq!(notification.method, "workspace/didChangeConfiguration");
+    assert_eq!(notification.params.unwrap()["settings"]["rust-analyzer"]["checkOnSave"], false);
+
+    let result: Vec<Value> = client.send_request("workspace/configuration", Some(json!({
+        "items": [
+            {"section": "rust-analyzer"},
+            {"section": "rust-analyzer.cargo"},
+            {"section": "unknown"}
+        ]
+    }))).await?;
+    assert_eq!(result[0]["checkOnSave"], false);
+    assert_eq!(result[1], json!({"target": "wasm32"}));
+    assert_eq!(result[2], Value::Null);
+    Ok(())
+}
@@ -32,6 +32,7 @@ async fn test_long_running_resource_monitoring() {
         monitor_interval_secs: 1,  // Monitor every second
         restart_grace_secs: 2,     // Quick restart for testing
         max_restart_attempts: 5,   // Allow more restarts
+        ..ResourceConfig::default()
     };
     
     let manager = LspManager::with_resource_config(env.root_dir().clone(), resource_config);
@@ -105,6 +106,7 @@ fn main() {
         monitor_interval_secs: 1,  // Fast monitoring
         restart_grace_secs: 1,     // Quick restart
         max_restart_attempts: 10,  // Allow many restarts for testing
+        ..ResourceConfig::default()
     };
     
     let manager = LspManager::with_resource_config(env.root_dir().clone(), resource_config);
@@ -170,6 +172,7 @@ async fn test_memory_limit_enforcement() {
         monitor_interval_secs: 1,
         restart_grace_secs: 1,
         max_restart_attempts: 3,
+        ..ResourceConfig::default()
     };
     
     let monitor = ResourceMonitor::new(config);
@@ -400,6 +403,70 @@ mod tests {{
     println!("✅ Performance under pressure test completed");
 }
 
+/// 🎭 Manager whose rust-analyzer is a script that answers `initialize` and `shutdown`
+#[cfg(unix)]
+fn fake_server(root: &std::path::Path, server_dir: &std::path::Path) -> LspManager {
+    use empathic::lsp::ServerOverride;
+    use serde_json::{Value, json};
+    use std::os::unix::fs::PermissionsExt;
+
+    let frame = |message: Value| {
+        let body = message.to_string();
+        format!("Content-Length: {}\r\n\r\n{}", body.len(), body)
+    };
+    let initialize = frame(json!({"jsonrpc": "2.0", "id": 1, "result": {"capabilities": {}}}));
+    let shutdown = frame(json!({"jsonrpc": "2.0", "id": 2, "result": null}));
+    // 📜 fd 3 keeps stdin for the background `cat`; sh would otherwise hand it /dev/null
+    let script = format!(
+        "#!/bin/sh\nlog=$(mktemp '{dir}/server.XXXXXX')\nexec 3<&0\ncat <&3 > \"$log\" &\n\
+         printf '%s' '{initialize}'\nuntil grep -q '\"shutdown\"' \"$log\"; do sleep 0.05; done\n\
+         printf '%s' '{shutdown}'\nwait\n",
+        dir = server_dir.display()
+    );
+    let fake = server_dir.join("rust-analyzer");
+    std::fs::write(&fake, script).unwrap();
+    std::fs::set_permissions(&fake, std::fs::Permissions::from_mode(0o755)).unwrap();
+    LspManager::new(root.to_path_buf()).with_server_override(
+        &ServerOverride { command: Some(fake.to_string_lossy().to_string()), ..Default::default() },
+        Vec::new(),
+    )
+}
+
+/// 🔗 Concurrent requests for files in one project share a single server
+#[cfg(unix)]
+#[tokio::test]
+async fn test_concurrent_files_share_one_client() {
+    let env = TestEnv::new().expect("Failed to create test environment");
+    let server_dir = tempfile::tempdir().expect("Failed to create server directory");
+    let project = env.create_rust_project("shared_client").await
+        .expect("Failed to create test project");
+    let mut files = Vec::new();
+    for i in 0..4 {
+        let file_path = project.join(format!("src/part_{}.rs", i));
+        env.write_file(&file_path, &format!("pub fn part_{}() {{}}\n", i)).await
+            .expect("Failed to create test file");
+        files.push(file_path);
+    }
+
+    let manager = std::sync::Arc::new(fake_server(env.root_dir(), server_dir.path()));
+    let requests = files.iter().map(|file| {
+        let manager = manager.clone();
+        let file = file.clone();
+        tokio::spawn(async move { manager.get_client(&file).await })
+    });
+    for result in futures::future::join_all(requests).await {
+        result.expect("get_client task panicked").expect("get_client failed");
+    }
+
+    assert_eq!(manager.get_server_status().await.len(), 1, "one server per project");
+    let (created, reused) = manager.client_counts();
+    assert_eq!(created, 1);
+    assert_eq!(reused, files.len() as u64 - 1);
+
+    manager.graceful_shutdown_all().await
+        .expect("Failed to gracefully shutdown");
+}
+
 /// 🔍 Edge case stability test
 /// 
 /// Tests various edge cases that could cause instability including
@@ -548,6 +615,7 @@ impl Utility {
         monitor_interval_secs: 2,  // Monitor every 2 seconds
         restart_grace_secs: 5,     // 5 second grace period
         max_restart_attempts: 3,   // 3 restart attempts
+        ..ResourceConfig::default()
     };
     
     let manager = LspManager::with_resource_config(env.root_dir().clone(), resource_config);
@@ -0,0 +1,65 @@
+//! 🦀 Tests for LSP Type Hierarchy Tool
+
+use empathic::config::Config;
+use empathic::tools::lsp::type_hierarchy::{HierarchyDirection, LspTypeHierarchyTool, TypeHierarchyOutput};
+use empathic::tools::Tool;
+use serde_json::json;
+use tempfile::tempdir;
+
+#[tokio::test]
+async fn test_type_hierarchy_schema() {
+    let tool = LspTypeHierarchyTool;
+    let schema = tool.schema();
+
+    assert_eq!(tool.name(), "lsp_type_hierarchy");
+    assert_eq!(schema["properties"]["direction"]["enum"], json!(["supertypes", "subtypes"]));
+    assert_eq!(schema["properties"]["max_depth"]["maximum"], 10);
+    let required = schema["required"].as_array().unwrap();
+    for field in ["file_path", "project", "line", "character"] {
+        assert!(required.contains(&json!(field)), "{field} should be required");
+    }
+    assert!(!required.contains(&json!("direction")));
+}
+
+#[tokio::test]
+async fn test_type_hierarchy_rejects_bad_arguments() {
+    let temp_dir = tempdir().unwrap();
+    let config = Config::new(temp_dir.path().to_path_buf());
+    let project_dir = temp_dir.path().join("test");
+    std::fs::create_dir_all(&project_dir).unwrap();
+    std::fs::write(project_dir.join("lib.rs"), "pub trait Shape {}\n").unwrap();
+
+    let args = json!({"file_path": "lib.rs", "project": "test", "line": 0, "character": 10, "direction": "sideways"});
+    assert!(LspTypeHierarchyTool.execute(args, &config).await.is_err());
+
+    let args = json!({"file_path": "lib.rs", "project": "test", "line": 0, "character": 10, "max_depth": 11});
+    let err = LspTypeHierarchyTool.execute(args, &config).await.unwrap_err();
+    assert!(err.to_string().contains("max_depth"), "{err}");
+}
+
+#[tokio::test]
+async fn test_type_hierarchy_output_format() {
+    let output: TypeHierarchyOutput = serde_json::from_value(json!({
+        "file_path": "src/lib.rs",
+        "project": "test",
+        "position": {"line": 3, "character": 11},
+        "direction": "subtypes",
+        "items": [{
+            "name": "Shape", "kind": "Interface", "file_path": "src/lib.rs",
+            "line": 3, "character": 10, "end_line": 3, "end_character": 15,
+            "children": [
+                {"name": "Circle", "kind": "Struct", "file_path": "src/lib.rs", "line": 7, "character": 11, "end_line": 7, "end_character": 17},
+                {"name": "Circle", "kind": "Struct", "file_path": "src/lib.rs", "line": 7, "character": 11, "end_line": 7, "end_character": 17, "repeated": true}
+            ]
+        }],
+        "total_items": 2,
+        "truncated": false
+    })).unwrap();
+    assert_eq!(output.direction, HierarchyDirection::Subtypes);
+    assert_eq!(output.items[0].children.len(), 2);
+    assert!(output.items[0].children[1].repeated);
+
+    // Leaves serialize without empty children or a false repeated flag
+    let leaf = serde_json::to_value(&output.items[0].children[0]).unwrap();
+    assert!(leaf.get("children").is_none() && leaf.get("repeated").is_none());
+}
@@ -0,0 +1,101 @@
+//! 🚚 Move file tool tests - Renames, directories, overwrite protection and cross-device moves
+
+mod common;
+
+use anyhow::Result;
+use common::*;
+use empathic::tools::{Tool, move_file::MoveFileTool};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_move_file_renames_within_filesystem() -> Result<()> {
+    // 🎯 Same filesystem: a plain rename, parents created
+    let env = TestEnv::new()?;
+    let source = env.create_file("notes.txt", "hello").await?;
+
+    let result = MoveFileTool.execute(json!({"from": "notes.txt", "to": "archive/notes.txt"}), &env.config).await?;
+
+    let parsed = McpResult::parse(result)?;
+    assert_mcp_success(&parsed);
+    assert_eq!(parsed.content["method"], "rename");
+    assert_eq!(parsed.content["was_directory"], false);
+    assert!(!source.exists());
+    assert!(verify_file_content(&env.root_path.join("archive/notes.txt"), "hello").await?);
+
+    println!("✅ Same-filesystem rename works");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_move_file_overwrite_protection() -> Result<()> {
+    // 🛡️ Existing destinations, directories included, need overwrite: true
+    let env = TestEnv::new()?;
+    env.create_files(&[("new/a.txt", "new"), ("old/b.txt", "old")]).await?;
+
+    let refused = MoveFileTool.execute(json!({"from": "new", "to": "old"}), &env.config).await;
+    assert!(refused.unwrap_err().to_string().contains("already exists"));
+    assert!(env.root_path.join("new/a.txt").exists());
+
+    let result = MoveFileTool.execute(json!({"from": "new", "to": "old", "overwrite": true}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["overwritten"], true);
+    assert_eq!(parsed.content["was_directory"], true);
+    assert!(verify_file_content(&env.root_path.join("old/a.txt"), "new").await?);
+    assert!(!env.root_path.join("old/b.txt").exists());
+
+    // A move that fails keeps the destination it would have replaced
+    env.create_files(&[("outer/inner/keep.txt", "keep")]).await?;
+    let failed = MoveFileTool.execute(json!({"from": "outer", "to": "outer/inner", "overwrite": true}), &env.config).await;
+    assert!(failed.is_err());
+    assert!(verify_file_content(&env.root_path.join("outer/inner/keep.txt"), "keep").await?);
+    assert_eq!(std::fs::read_dir(env.root_path.join("outer"))?.count(), 1);
+
+    println!("✅ Move overwrite protection works");
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+#[tokio::test]
+async fn test_move_directory_across_filesystems() -> Result<()> {
+    // 💾 tmpfs under /dev/shm is a different device than the test root
+    use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+    let mut env = TestEnv::new()?;
+    let Ok(shm) = tempfile::tempdir_in("/dev/shm") else {
+        println!("⚠️ /dev/shm unavailable, skipping cross-device move");
+        return Ok(());
+    };
+    if std::fs::metadata(shm.path())?.dev() == std::fs::metadata(&env.root_path)?.dev() {
+        println!("⚠️ /dev/shm shares a device with the test root, skipping cross-device move");
+        return Ok(());
+    }
+    env.config.allowed_dirs.push(shm.path().to_path_buf());
+
+    env.create_files(&[("data/run.sh", "#!/bin/sh\n"), ("data/deep/x.txt", "x")]).await?;
+    std::fs::set_permissions(env.root_path.join("data/run.sh"), std::fs::Permissions::from_mode(0o700))?;
+    let target = shm.path().join("data");
+
+    let result = MoveFileTool.execute(
+        json!({"from": "data", "to": target.to_string_lossy()}),
+        &env.config
+    ).await?;
+
+    let parsed = McpResult::parse(result)?;
+    assert_mcp_success(&parsed);
+    assert_eq!(parsed.content["method"], "copy_delete");
+    assert_eq!(parsed.content["bytes_moved"], 11);
+    assert!(!env.root_path.join("data").exists());
+    assert!(verify_file_content(&target.join("deep/x.txt"), "x").await?);
+    assert_eq!(std::fs::metadata(target.join("run.sh"))?.permissions().mode() & 0o777, 0o700);
+
+    println!("✅ Cross-device directory move works");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_move_file_missing_source() -> Result<()> {
+    let env = TestEnv::new()?;
+    let result = MoveFileTool.execute(json!({"from": "ghost.txt", "to": "x.txt"}), &env.config).await;
+    assert!(result.is_err());
+    Ok(())
+}
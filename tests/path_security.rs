@@ -0,0 +1,79 @@
+//! 🛡️ Path security tests - Symlinks and absolute paths can't escape the root
+
+#![cfg(unix)]
+
+mod common;
+
+use anyhow::Result;
+use common::*;
+use empathic::tools::{Tool, read_file::ReadFileTool, write_file::WriteFileTool, delete_file::DeleteFileTool};
+use serde_json::json;
+use std::os::unix::fs::symlink;
+
+#[tokio::test]
+async fn test_read_write_delete_through_escaping_symlink() -> Result<()> {
+    let env = TestEnv::new()?;
+    let outside = tempfile::TempDir::new()?;
+    std::fs::write(outside.path().join("secret.txt"), "secret")?;
+    symlink(outside.path(), env.root_path.join("escape"))?;
+
+    let result = ReadFileTool.execute(json!({"path": "escape/secret.txt"}), &env.config).await;
+    assert!(result.is_err(), "read through symlink must be rejected");
+
+    let result = WriteFileTool.execute(json!({"path": "escape/secret.txt", "content": "pwned"}), &env.config).await;
+    assert!(result.is_err(), "overwrite through symlink must be rejected");
+    assert_eq!(std::fs::read_to_string(outside.path().join("secret.txt"))?, "secret");
+
+    // 📝 New files are checked via their parent, which resolves outside
+    let result = WriteFileTool.execute(json!({"path": "escape/new.txt", "content": "x"}), &env.config).await;
+    assert!(result.is_err(), "create through symlink must be rejected");
+    assert!(!outside.path().join("new.txt").exists());
+
+    let result = DeleteFileTool.execute(json!({"path": "escape/secret.txt"}), &env.config).await;
+    assert!(result.is_err(), "delete through symlink must be rejected");
+    assert!(outside.path().join("secret.txt").exists());
+
+    // 🔗 Removing the link itself leaves the target alone and is allowed
+    DeleteFileTool.execute(json!({"path": "escape"}), &env.config).await?;
+    assert!(!env.root_path.join("escape").exists());
+    assert!(outside.path().join("secret.txt").exists());
+
+    println!("✅ Escaping symlinks rejected");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_dangling_symlink_and_absolute_paths_rejected() -> Result<()> {
+    let env = TestEnv::new()?;
+    let outside = tempfile::TempDir::new()?;
+    let target = outside.path().join("created.txt");
+    symlink(&target, env.root_path.join("dangling.txt"))?;
+
+    // Writing would follow the dangling link and create the target outside
+    let result = WriteFileTool.execute(json!({"path": "dangling.txt", "content": "x"}), &env.config).await;
+    assert!(result.is_err());
+    assert!(!target.exists());
+
+    let absolute = outside.path().join("abs.txt");
+    std::fs::write(&absolute, "outside")?;
+    let result = ReadFileTool.execute(json!({"path": absolute.to_string_lossy()}), &env.config).await;
+    assert!(result.is_err());
+
+    let result = ReadFileTool.execute(json!({"path": "missing/../../etc/passwd"}), &env.config).await;
+    assert!(result.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_symlinks_inside_root_still_work() -> Result<()> {
+    let env = TestEnv::new()?;
+    env.create_file("real/data.txt", "inside").await?;
+    symlink(env.root_path.join("real"), env.root_path.join("alias"))?;
+
+    let result = ReadFileTool.execute(json!({"path": "alias/data.txt"}), &env.config).await?;
+    assert!(result.to_string().contains("inside"));
+
+    WriteFileTool.execute(json!({"path": "alias/new.txt", "content": "ok"}), &env.config).await?;
+    assert_eq!(std::fs::read_to_string(env.root_path.join("real/new.txt"))?, "ok");
+    Ok(())
+}
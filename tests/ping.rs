@@ -0,0 +1,25 @@
+//! 🏓 Ping tool tests - Liveness report without side effects
+
+mod common;
+
+use anyhow::Result;
+use common::*;
+use empathic::tools::{Tool, ping::PingTool};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_ping_reports_version_and_load() -> Result<()> {
+    let env = TestEnv::new()?;
+    let result = PingTool.execute(json!({}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+
+    assert_eq!(parsed.content["server"], "empathic");
+    assert_eq!(parsed.content["version"], env!("CARGO_PKG_VERSION"));
+    assert!(parsed.content["uptime_secs"].is_u64());
+    assert!(chrono::DateTime::parse_from_rfc3339(parsed.content["timestamp"].as_str().unwrap()).is_ok());
+    // 🧠 No LSP manager in the test config, so nothing is running or cached
+    assert_eq!(parsed.content["lsp_servers"], 0);
+    assert_eq!(parsed.content["open_documents"], 0);
+    assert_eq!(parsed.content["cached_responses"], 0);
+    Ok(())
+}
@@ -0,0 +1,54 @@
+//! 📝 Prompt tests - prompts/list and prompts/get through the request handler
+
+mod common;
+
+use anyhow::Result;
+use common::*;
+use empathic::mcp::JsonRpcRequest;
+use empathic::mcp::handlers::RequestHandler;
+use serde_json::json;
+use std::collections::HashMap;
+
+fn request(method: &str, params: serde_json::Value) -> JsonRpcRequest {
+    serde_json::from_value(json!({"jsonrpc": "2.0", "id": 1, "method": method, "params": params})).unwrap()
+}
+
+#[tokio::test]
+async fn test_prompts_list_and_get() -> Result<()> {
+    let env = TestEnv::new()?;
+    let tools = HashMap::new();
+    let handler = RequestHandler::new(&env.config, &tools);
+
+    let response = handler.handle_request(request("prompts/list", json!({}))).await.unwrap();
+    let prompts = response.result.unwrap()["prompts"].as_array().unwrap().clone();
+    assert!(prompts.iter().any(|p| p["name"] == "explain_error"));
+
+    let response = handler.handle_request(request("prompts/get", json!({
+        "name": "explain_error",
+        "arguments": {"error": "error[E0308]: mismatched types"}
+    }))).await.unwrap();
+    let result = response.result.unwrap();
+    assert_eq!(result["messages"][0]["role"], "user");
+    assert!(result["messages"][0]["content"]["text"].as_str().unwrap().contains("E0308"));
+
+    println!("✅ Prompts list/get work");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_prompts_get_lists_bad_arguments() -> Result<()> {
+    let env = TestEnv::new()?;
+    let tools = HashMap::new();
+    let handler = RequestHandler::new(&env.config, &tools);
+
+    let response = handler.handle_request(request("prompts/get", json!({
+        "name": "code_review",
+        "arguments": {"file": "src/lib.rs"}
+    }))).await.unwrap();
+    let error = response.error.unwrap();
+    assert_eq!(error.code, -32602);
+    let data = error.data.unwrap();
+    assert_eq!(data["missing"], json!(["path"]));
+    assert_eq!(data["unknown"], json!(["file"]));
+    Ok(())
+}
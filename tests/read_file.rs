@@ -4,7 +4,7 @@ mod common;
 
 use anyhow::Result;
 use common::*;
-use empathic::tools::{Tool, read_file::ReadFileTool};
+use empathic::tools::{Tool, read_file::{ReadFileChunkTool, ReadFileTool}};
 use serde_json::json;
 
 #[tokio::test]
@@ -203,3 +203,131 @@ async fn test_read_file_directory_listing() -> Result<()> {
     
     Ok(())
 }
+
+#[tokio::test]
+async fn test_read_file_line_range_and_max_bytes() -> Result<()> {
+    // 📄 1-based inclusive ranges with paging info in a second content item
+    let env = TestEnv::new()?;
+    let tool = ReadFileTool;
+    let content: String = (1..=10).map(|i| format!("line {}\n", i)).collect();
+    env.create_file("paged.txt", &content).await?;
+
+    let result = tool.execute(json!({"path": "paged.txt", "start_line": 3, "end_line": 5}), &env.config).await?;
+    let items = result["content"].as_array().unwrap();
+    assert_eq!(items[0]["text"], "line 3\nline 4\nline 5");
+    let range: serde_json::Value = serde_json::from_str(items[1]["text"].as_str().unwrap())?;
+    assert_eq!(range["total_lines"], 10);
+    assert_eq!(range["end_line"], 5);
+    assert_eq!(range["truncated"], false);
+
+    let result = tool.execute(json!({"path": "paged.txt", "max_bytes": 10}), &env.config).await?;
+    let items = result["content"].as_array().unwrap();
+    assert_eq!(items[0]["text"], "line 1\nlin");
+    let range: serde_json::Value = serde_json::from_str(items[1]["text"].as_str().unwrap())?;
+    assert_eq!(range["truncated"], true);
+    assert_eq!(range["end_line"], 2);
+
+    // Whole-file reads keep the single verbatim content item
+    let result = tool.execute(json!({"path": "paged.txt"}), &env.config).await?;
+    assert_eq!(result["content"].as_array().unwrap().len(), 1);
+
+    println!("✅ Line range and byte cap work");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_read_file_rejects_inverted_range() -> Result<()> {
+    let env = TestEnv::new()?;
+    env.create_file("paged.txt", "a\nb\nc\n").await?;
+
+    let result = ReadFileTool.execute(json!({"path": "paged.txt", "start_line": 3, "end_line": 2}), &env.config).await;
+    assert!(result.unwrap_err().to_string().contains("end_line"));
+
+    let result = ReadFileTool.execute(json!({"path": "paged.txt", "start_line": 0}), &env.config).await;
+    assert!(result.unwrap_err().to_string().contains("start_line"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_read_file_decodes_legacy_encodings() -> Result<()> {
+    // 🔤 Latin-1 and UTF-16 files are decoded and the encoding is reported
+    let env = TestEnv::new()?;
+    tokio::fs::write(env.root_path.join("latin1.txt"), b"caf\xE9\n").await?;
+    let mut utf16 = vec![0xFF, 0xFE];
+    utf16.extend("héllo\n".encode_utf16().flat_map(|unit| unit.to_le_bytes()));
+    tokio::fs::write(env.root_path.join("utf16.txt"), &utf16).await?;
+
+    let result = ReadFileTool.execute(json!({"path": "latin1.txt"}), &env.config).await?;
+    let items = result["content"].as_array().unwrap();
+    assert_eq!(items[0]["text"], "café\n");
+    assert!(items[1]["text"].as_str().unwrap().contains("latin1"));
+
+    let result = ReadFileTool.execute(json!({"path": "utf16.txt", "start_line": 1}), &env.config).await?;
+    let items = result["content"].as_array().unwrap();
+    assert_eq!(items[0]["text"], "héllo");
+    let range: serde_json::Value = serde_json::from_str(items[1]["text"].as_str().unwrap())?;
+    assert_eq!(range["encoding"], "utf-16le");
+
+    // Explicit override wins over detection
+    let result = ReadFileTool.execute(json!({"path": "latin1.txt", "encoding": "utf-8"}), &env.config).await;
+    assert!(result.is_err());
+    let result = ReadFileTool.execute(json!({"path": "latin1.txt", "encoding": "ebcdic"}), &env.config).await;
+    assert!(result.unwrap_err().to_string().contains("encoding"));
+
+    println!("✅ Encoding detection works");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_read_file_fenced_with_line_numbers() -> Result<()> {
+    // 🏷️ Fenced output is tagged by extension; line numbers follow the requested range
+    let env = TestEnv::new()?;
+    env.create_file("src/main.rs", "fn main() {\n    println!(\"hi\");\n}\n").await?;
+
+    let result = ReadFileTool.execute(json!({"path": "src/main.rs", "fenced": true}), &env.config).await?;
+    assert_eq!(result["content"][0]["text"], "```rust\nfn main() {\n    println!(\"hi\");\n}\n```");
+
+    let result = ReadFileTool.execute(json!({"path": "src/main.rs", "start_line": 2, "fenced": true, "line_numbers": true}), &env.config).await?;
+    assert_eq!(result["content"][0]["text"], "```rust\n2 |     println!(\"hi\");\n3 | }\n```");
+
+    let result = ReadFileTool.execute(json!({"path": "src/main.rs", "line_offset": 2, "line_length": 1, "line_numbers": true}), &env.config).await?;
+    assert_eq!(result["content"][0]["text"], "3 | }");
+
+    // Raw output stays the default
+    let result = ReadFileTool.execute(json!({"path": "src/main.rs"}), &env.config).await?;
+    assert!(result["content"][0]["text"].as_str().unwrap().starts_with("fn main()"));
+
+    println!("✅ Fenced output and line numbers work");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_read_file_refuses_files_over_max_read_bytes() -> Result<()> {
+    // 🛡️ Whole-file reads stop at MAX_READ_BYTES; read_file_chunk still pages through
+    let mut env = TestEnv::new()?;
+    env.create_file("big.log", &"0123456789\n".repeat(100)).await?;
+    env.config.max_read_bytes = 500;
+
+    let err = ReadFileTool.execute(json!({"path": "big.log"}), &env.config).await.unwrap_err();
+    assert!(err.to_string().contains("read_file_chunk"), "{}", err);
+    assert!(ReadFileTool.execute(json!({"path": "big.log", "start_line": 1, "end_line": 2}), &env.config).await.is_err());
+
+    let mut offset = 0;
+    let mut content = String::new();
+    loop {
+        let result = ReadFileChunkTool.execute(json!({"path": "big.log", "offset": offset, "length": 300}), &env.config).await?;
+        let items = result["content"].as_array().unwrap();
+        content.push_str(items[0]["text"].as_str().unwrap());
+        let paging: serde_json::Value = serde_json::from_str(items[1]["text"].as_str().unwrap())?;
+        assert_eq!(paging["total_size"], 1100);
+        offset = paging["next_offset"].as_u64().unwrap();
+        if paging["eof"] == true {
+            break;
+        }
+    }
+    assert_eq!(offset, 1100);
+    assert_eq!(content, "0123456789\n".repeat(100));
+
+    assert!(ReadFileChunkTool.execute(json!({"path": "big.log", "length": 0}), &env.config).await.is_err());
+    Ok(())
+}
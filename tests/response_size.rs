@@ -0,0 +1,78 @@
+//! 📏 Response size tests - Global truncation of oversized tool output
+
+mod common;
+
+use anyhow::Result;
+use common::*;
+use empathic::mcp::JsonRpcRequest;
+use empathic::mcp::handlers::RequestHandler;
+use empathic::tools::get_all_tools;
+use serde_json::json;
+use std::collections::HashMap;
+
+fn call(tool: &str, arguments: serde_json::Value) -> JsonRpcRequest {
+    serde_json::from_value(json!({
+        "jsonrpc": "2.0", "id": 1, "method": "tools/call",
+        "params": {"name": tool, "arguments": arguments}
+    })).unwrap()
+}
+
+#[tokio::test]
+async fn test_oversized_output_is_truncated_with_hint() -> Result<()> {
+    let mut env = TestEnv::new()?;
+    env.config.max_response_bytes = 100;
+    env.create_file("big.txt", &"x".repeat(1000)).await?;
+    env.create_file("small.txt", "tiny").await?;
+
+    let tools: HashMap<_, _> = get_all_tools().into_iter().map(|tool| (tool.name().to_string(), tool)).collect();
+    let handler = RequestHandler::new(&env.config, &tools);
+
+    let response = handler.handle_request(call("read_file", json!({"path": "big.txt"}))).await.unwrap();
+    let text = response.result.unwrap()["content"][0]["text"].as_str().unwrap().to_string();
+    assert!(text.starts_with(&"x".repeat(100)));
+    assert!(text.contains("output truncated, 900 bytes omitted"));
+    assert!(text.contains("start_line/end_line"), "read_file suggests narrowing: {text}");
+
+    // Output under the cap is untouched
+    let response = handler.handle_request(call("read_file", json!({"path": "small.txt"}))).await.unwrap();
+    assert_eq!(response.result.unwrap()["content"][0]["text"], "tiny");
+
+    // 0 disables the cap
+    env.config.max_response_bytes = 0;
+    let handler = RequestHandler::new(&env.config, &tools);
+    let response = handler.handle_request(call("read_file", json!({"path": "big.txt"}))).await.unwrap();
+    assert_eq!(response.result.unwrap()["content"][0]["text"].as_str().unwrap().len(), 1000);
+
+    println!("✅ Oversized output is truncated");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_oversized_json_output_stays_valid_json() -> Result<()> {
+    // 🧾 Structured tools are trimmed inside the payload, never mid-token
+    let mut env = TestEnv::new()?;
+    env.config.max_response_bytes = 2000;
+    for i in 0..200 {
+        env.create_file(&format!("src/module_{i:03}.rs"), "fn needle() {}\n").await?;
+    }
+
+    let tools: HashMap<_, _> = get_all_tools().into_iter().map(|tool| (tool.name().to_string(), tool)).collect();
+    let handler = RequestHandler::new(&env.config, &tools);
+
+    for (tool, arguments, array) in [
+        ("list_files", json!({"path": "src", "limit": 1000}), "files"),
+        ("search_files", json!({"query": "needle", "max_results": 1000}), "matches"),
+    ] {
+        let response = handler.handle_request(call(tool, arguments)).await.unwrap();
+        let text = response.result.unwrap()["content"][0]["text"].as_str().unwrap().to_string();
+        assert!(text.len() <= 2000, "{tool}: {} bytes", text.len());
+
+        let payload: serde_json::Value = serde_json::from_str(&text)
+            .unwrap_or_else(|e| panic!("{tool} output is not JSON ({e}): {text}"));
+        assert_eq!(payload["truncated"], true, "{tool}: {payload}");
+        assert!(payload["omitted"].as_u64().unwrap() > 0, "{tool}: {payload}");
+        assert!(!payload[array].as_array().unwrap().is_empty(), "{tool}: {payload}");
+        assert!(payload["hint"].as_str().is_some(), "{tool}: {payload}");
+    }
+    Ok(())
+}
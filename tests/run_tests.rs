@@ -0,0 +1,64 @@
+//! 🧪 run_tests tool tests - One result format across test runners
+
+mod common;
+
+use anyhow::Result;
+use common::*;
+use empathic::tools::{Tool, run_tests::RunTestsTool};
+use serde_json::json;
+
+const MANIFEST: &str = "[package]\nname = \"sample\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[workspace]\n";
+
+#[tokio::test]
+async fn test_run_tests_cargo() -> Result<()> {
+    let env = TestEnv::new()?;
+    env.create_files(&[
+        ("sample/Cargo.toml", MANIFEST),
+        ("sample/src/lib.rs", "#[cfg(test)]\nmod tests {\n    #[test]\n    fn passes() {}\n\n    #[test]\n    fn fails() {\n        assert_eq!(1, 2, \"numbers differ\");\n    }\n\n    #[test]\n    #[ignore]\n    fn skipped() {}\n}\n"),
+    ]).await?;
+
+    let result = RunTestsTool.execute(json!({"project": "sample", "cwd": "src"}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+
+    assert_eq!(parsed.content["runner"], "cargo");
+    assert_eq!(parsed.content["success"], false);
+    assert_eq!(parsed.content["summary"], json!({"total": 3, "passed": 1, "failed": 1, "skipped": 1}));
+    assert_eq!(parsed.content["failures"][0]["name"], "tests::fails");
+    assert!(parsed.content["failures"][0]["message"].as_str().unwrap().contains("numbers differ"));
+    assert!(parsed.content["raw"]["stdout"].is_string());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_run_tests_node_test_runner() -> Result<()> {
+    if std::process::Command::new("node").arg("--version").output().is_err() {
+        println!("⚠️ node not installed, skipping");
+        return Ok(());
+    }
+    let env = TestEnv::new()?;
+    env.create_files(&[
+        ("web/package.json", "{\"name\": \"web\", \"scripts\": {\"test\": \"node --test\"}}"),
+        ("web/math.test.js", "const test = require('node:test');\nconst assert = require('node:assert');\ntest('adds', () => assert.strictEqual(1 + 1, 2));\ntest('breaks', () => assert.strictEqual(1, 2));\ntest('later', { skip: true }, () => {});\n"),
+    ]).await?;
+
+    let result = RunTestsTool.execute(json!({"project": "web"}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+
+    assert_eq!(parsed.content["runner"], "node_test");
+    assert_eq!(parsed.content["parsed"], true);
+    assert_eq!(parsed.content["summary"], json!({"total": 3, "passed": 1, "failed": 1, "skipped": 1}));
+    assert_eq!(parsed.content["failures"][0]["name"], "breaks");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_run_tests_without_project_fails() -> Result<()> {
+    let env = TestEnv::new()?;
+    env.create_files(&[("empty/readme.txt", "nothing to test")]).await?;
+    let result = RunTestsTool.execute(json!({"project": "empty"}), &env.config).await;
+    assert!(result.is_err());
+
+    let result = RunTestsTool.execute(json!({"project": "empty", "kind": "maven"}), &env.config).await;
+    assert!(result.is_err());
+    Ok(())
+}
@@ -0,0 +1,205 @@
+//! 🔍 Search files tool tests - Literal and regex modes
+
+mod common;
+
+use anyhow::Result;
+use common::*;
+use empathic::fs::SkipDirs;
+use empathic::tools::{Tool, search_files::SearchFilesTool};
+use serde_json::json;
+
+async fn create_sources(env: &TestEnv) -> Result<()> {
+    env.create_files(&[
+        ("src/lib.rs", "fn parse_test() {}\nfn helper() {}\n"),
+        ("src/util.rs", "// parse_test is not a function here\nfn build_test() {}\n"),
+        ("notes.txt", "fn text_test() {}\n"),
+    ]).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_search_files_literal_default() -> Result<()> {
+    // 🎯 Regex metacharacters are literal unless regex=true
+    let env = TestEnv::new()?;
+    create_sources(&env).await?;
+    env.create_file("src/special.rs", "let x = a.b(c);\n").await?;
+
+    let result = SearchFilesTool.execute(json!({"query": "a.b("}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+
+    assert_eq!(parsed.content["regex"], false);
+    assert_eq!(parsed.content["total_matches"], 1);
+    assert_eq!(parsed.content["matches"][0]["column"], 9);
+    assert_eq!(parsed.content["matches"][0]["highlight"], "        ^^^^");
+
+    println!("✅ Literal search works");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_search_files_regex_with_file_pattern() -> Result<()> {
+    // 🔍 Regex mode with capture spans, restricted to Rust files
+    let env = TestEnv::new()?;
+    create_sources(&env).await?;
+
+    let result = SearchFilesTool.execute(
+        json!({"query": r"fn\s+(\w+)_test", "regex": true, "pattern": "*.rs"}),
+        &env.config
+    ).await?;
+    let parsed = McpResult::parse(result)?;
+
+    assert_eq!(parsed.content["total_matches"], 2);
+    assert_eq!(parsed.content["files_matched"], 2);
+    let names: Vec<&str> = parsed.content["matches"].as_array().unwrap().iter()
+        .map(|m| m["captures"][0]["text"].as_str().unwrap())
+        .collect();
+    assert!(names.contains(&"parse") && names.contains(&"build"));
+
+    println!("✅ Regex search works");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_search_files_invalid_regex() -> Result<()> {
+    let env = TestEnv::new()?;
+    create_sources(&env).await?;
+
+    let result = SearchFilesTool.execute(json!({"query": "fn(", "regex": true}), &env.config).await;
+    assert!(result.unwrap_err().to_string().contains("Invalid regex pattern"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_search_files_max_results() -> Result<()> {
+    let env = TestEnv::new()?;
+    create_sources(&env).await?;
+
+    let result = SearchFilesTool.execute(json!({"query": "fn", "max_results": 1}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+
+    assert_eq!(parsed.content["matches"].as_array().unwrap().len(), 1);
+    assert_eq!(parsed.content["total_matches"], 4);
+    assert_eq!(parsed.content["truncated"], true);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_search_files_respects_gitignore() -> Result<()> {
+    // 🙈 Ignored build output is skipped unless include_ignored is set
+    let env = TestEnv::new()?;
+    env.create_files(&[
+        (".gitignore", "target/\ndist/\n"),
+        ("src/main.rs", "fn needle() {}\n"),
+        ("target/debug/build.rs", "fn needle() {}\n"),
+        ("dist/bundle.js", "needle();\n"),
+    ]).await?;
+
+    let result = SearchFilesTool.execute(json!({"query": "needle"}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["total_matches"], 1);
+    assert_eq!(parsed.content["matches"][0]["path"], "src/main.rs");
+
+    let result = SearchFilesTool.execute(json!({"query": "needle", "include_ignored": true}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["total_matches"], 3);
+
+    println!("✅ .gitignore-aware search works");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_search_files_respects_empathicignore() -> Result<()> {
+    // 🙈 .empathicignore hides paths from the agent only, nested files included,
+    // and overrides .gitignore where the two disagree
+    let env = TestEnv::new()?;
+    env.create_files(&[
+        (".gitignore", "generated/\n"),
+        (".empathicignore", "fixtures/\n!generated/\n"),
+        ("src/main.rs", "fn needle() {}\n"),
+        ("fixtures/huge.json", "\"needle\"\n"),
+        ("generated/api.rs", "fn needle() {}\n"),
+        ("vendor/.empathicignore", "*.c\n"),
+        ("vendor/lib.c", "needle();\n"),
+        ("vendor/lib.h", "needle();\n"),
+    ]).await?;
+
+    let result = SearchFilesTool.execute(json!({"query": "needle"}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    let mut paths: Vec<&str> = parsed.content["matches"].as_array().unwrap().iter()
+        .map(|m| m["path"].as_str().unwrap())
+        .collect();
+    paths.sort();
+    assert_eq!(paths, vec!["generated/api.rs", "src/main.rs", "vendor/lib.h"]);
+
+    let result = SearchFilesTool.execute(json!({"query": "needle", "include_ignored": true}), &env.config).await?;
+    assert_eq!(McpResult::parse(result)?.content["total_matches"], 5);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_search_files_skips_binary() -> Result<()> {
+    // 🚫 Binary files are skipped and counted unless search_binary is set
+    let env = TestEnv::new()?;
+    env.create_file("src/main.rs", "const MAGIC: &str = \"needle\";\n").await?;
+    let mut blob = b"\x7fELF\0\0needle\0".to_vec();
+    blob.extend_from_slice(&[0xff, 0xfe, 0x00]);
+    tokio::fs::write(env.root_path.join("libneedle.so"), &blob).await?;
+
+    let result = SearchFilesTool.execute(json!({"query": "needle"}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["total_matches"], 1);
+    assert_eq!(parsed.content["files_skipped_binary"], 1);
+
+    let result = SearchFilesTool.execute(json!({"query": "needle", "search_binary": true}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["total_matches"], 2);
+    assert_eq!(parsed.content["files_skipped_binary"], 0);
+
+    println!("✅ Binary detection works");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_search_files_decodes_legacy_encodings() -> Result<()> {
+    // 🔤 Latin-1 and UTF-16 files are searched instead of silently skipped
+    let env = TestEnv::new()?;
+    env.create_file("plain.txt", "café\n").await?;
+    tokio::fs::write(env.root_path.join("latin1.txt"), b"caf\xE9\n").await?;
+    let mut utf16 = vec![0xFF, 0xFE];
+    utf16.extend("café\n".encode_utf16().flat_map(|unit| unit.to_le_bytes()));
+    tokio::fs::write(env.root_path.join("utf16.txt"), &utf16).await?;
+
+    let result = SearchFilesTool.execute(json!({"query": "café"}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["total_matches"], 3);
+    assert_eq!(parsed.content["files_skipped_binary"], 0);
+
+    let encodings: Vec<(&str, &serde_json::Value)> = parsed.content["matches"].as_array().unwrap().iter()
+        .map(|m| (m["path"].as_str().unwrap(), &m["encoding"]))
+        .collect();
+    assert!(encodings.contains(&("latin1.txt", &json!("latin1"))));
+    assert!(encodings.contains(&("utf16.txt", &json!("utf-16le"))));
+    assert!(encodings.contains(&("plain.txt", &serde_json::Value::Null)));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_search_files_configured_skip_dirs() -> Result<()> {
+    // 🙈 EXTRA_IGNORE_DIRS entries are skipped on top of the defaults
+    let mut env = TestEnv::new()?;
+    env.create_files(&[
+        ("src/app.js", "const needle = 1;\n"),
+        ("node_modules/dep/index.js", "const needle = 2;\n"),
+        ("dist/app.min.js", "const needle = 3;\n"),
+    ]).await?;
+
+    let result = SearchFilesTool.execute(json!({"query": "needle"}), &env.config).await?;
+    assert_eq!(McpResult::parse(result)?.content["total_matches"], 3);
+
+    env.config.skip_dirs = SkipDirs::new(None, &["node_modules".to_string(), "dist".to_string()])?;
+    let result = SearchFilesTool.execute(json!({"query": "needle"}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["total_matches"], 1);
+    assert_eq!(parsed.content["matches"][0]["path"], "src/app.js");
+    Ok(())
+}
@@ -0,0 +1,110 @@
+//! 🔎 search_symbols tests - LSP answers, with a text fallback when no language server is running
+
+mod common;
+
+use anyhow::Result;
+use common::*;
+use empathic::lsp::LspManager;
+use empathic::tools::{Tool, search_symbols::SearchSymbolsTool};
+use serde_json::{Value, json};
+use std::sync::Arc;
+
+const LIB: &str = r#"pub struct Config {
+    pub name: String,
+}
+
+impl Config {
+    pub(crate) async fn load_config(
+        path: &str,
+    ) -> Self {
+        todo!()
+    }
+}
+
+macro_rules! config_value { () => {} }
+// fn config_in_comment()
+"#;
+
+#[tokio::test]
+async fn test_text_fallback_finds_definitions() -> Result<()> {
+    let mut env = TestEnv::new()?;
+    env.create_file("Cargo.toml", "[package]\nname = \"demo\"\n").await?;
+    env.create_file("src/lib.rs", LIB).await?;
+    env.create_file("README.md", "fn config_in_markdown()").await?;
+    // A manager without a running server must not spawn one
+    env.config.set_lsp_manager(Arc::new(LspManager::new(env.root_path.clone())));
+
+    let result = SearchSymbolsTool.execute(json!({"query": "CONFIG"}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+
+    assert_eq!(parsed.content["lsp_used"], false);
+    assert!(parsed.content["lsp_unavailable_reason"].as_str().unwrap().contains("no language server running"));
+    let found: Vec<(String, String, u64)> = parsed.content["symbols"].as_array().unwrap().iter()
+        .map(|symbol| {
+            assert_eq!(symbol["source"], "text");
+            (symbol["name"].as_str().unwrap().to_string(), symbol["kind"].as_str().unwrap().to_string(), symbol["line"].as_u64().unwrap())
+        })
+        .collect();
+    assert_eq!(found, vec![
+        ("Config".to_string(), "Struct".to_string(), 1),
+        ("load_config".to_string(), "Function".to_string(), 6),
+        ("config_value".to_string(), "Function".to_string(), 13),
+    ]);
+    assert_eq!(parsed.content["symbols"][1]["path"], "src/lib.rs");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_max_results_and_lsp_opt_out() -> Result<()> {
+    let env = TestEnv::new()?;
+    env.create_file("a.rs", "fn one() {}\nfn two() {}\nfn three() {}\n").await?;
+
+    let result = SearchSymbolsTool.execute(json!({"query": "", "max_results": 2, "lsp": false}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["total_symbols"], 3);
+    assert_eq!(parsed.content["symbols"].as_array().unwrap().len(), 2);
+    assert_eq!(parsed.content["truncated"], true);
+    assert_eq!(parsed.content["lsp_unavailable_reason"], "disabled by request");
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_lsp_answer_is_not_mixed_with_text_hits() -> Result<()> {
+    use empathic::lsp::ServerOverride;
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut env = TestEnv::new()?;
+    env.create_file("Cargo.toml", "[package]\nname = \"demo\"\n").await?;
+    let lib = env.create_file("src/lib.rs", LIB).await?;
+
+    // 🧠 A "rust-analyzer" that knows only the struct
+    let frame = |message: Value| {
+        let body = message.to_string();
+        format!("Content-Length: {}\r\n\r\n{}", body.len(), body)
+    };
+    let initialize = frame(json!({"jsonrpc": "2.0", "id": 1, "result": {"capabilities": {}}}));
+    let location = json!({
+        "uri": url::Url::from_file_path(&lib).unwrap().as_str(),
+        "range": {"start": {"line": 0, "character": 11}, "end": {"line": 0, "character": 17}}
+    });
+    let symbols = frame(json!({"jsonrpc": "2.0", "id": 2, "result": [{"name": "Config", "kind": 23, "location": location}]}));
+    let script = format!("#!/bin/sh\nsleep 0.3\nprintf '%s' '{initialize}'\nsleep 1\nprintf '%s' '{symbols}'\ncat > /dev/null\n");
+    let fake = env.create_file("toolchain/rust-analyzer", &script).await?;
+    std::fs::set_permissions(&fake, std::fs::Permissions::from_mode(0o755))?;
+    let manager = Arc::new(LspManager::new(env.root_path.clone()).with_server_override(
+        &ServerOverride { command: Some(fake.to_string_lossy().to_string()), ..Default::default() },
+        Vec::new(),
+    ));
+    manager.get_client(&lib).await?;
+    env.config.set_lsp_manager(manager);
+
+    let result = SearchSymbolsTool.execute(json!({"query": "config"}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+
+    assert_eq!(parsed.content["lsp_used"], true);
+    assert_eq!(parsed.content["total_symbols"], 1);
+    assert_eq!(parsed.content["symbols"][0]["source"], "lsp");
+    assert_eq!(parsed.content["symbols"][0]["column"], 12);
+    Ok(())
+}
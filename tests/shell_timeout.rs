@@ -0,0 +1,57 @@
+//! ⏱️ Shell timeout tests - Process-group kill and partial output
+
+mod common;
+
+use anyhow::Result;
+use common::*;
+use empathic::tools::{Tool, shell::ShellTool, bash_tool::BashTool};
+use serde_json::json;
+use std::time::{Duration, Instant};
+
+#[tokio::test]
+async fn test_shell_timeout_kills_sleep() -> Result<()> {
+    let env = TestEnv::new()?;
+    let started = Instant::now();
+
+    let result = ShellTool.execute(json!({"command": "echo before; sleep 10", "timeout_secs": 1}), &env.config).await;
+
+    let message = result.unwrap_err().to_string();
+    assert!(message.contains("timed out after 1s"), "{}", message);
+    assert!(message.contains("before"), "partial output kept: {}", message);
+    assert!(started.elapsed() < Duration::from_secs(5));
+
+    println!("✅ Shell timeout works");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_timeout_kills_whole_process_group() -> Result<()> {
+    // 🧹 A background child must die with the shell, not finish later
+    let env = TestEnv::new()?;
+
+    let result = BashTool.execute(
+        json!({"command": "(sleep 2; touch leaked) & sleep 10", "description": "leak check", "timeout_secs": 1}),
+        &env.config
+    ).await;
+    assert!(result.is_err());
+
+    tokio::time::sleep(Duration::from_secs(3)).await;
+    assert!(!env.root_path.join("leaked").exists());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_shell_timeout_validation_and_fast_commands() -> Result<()> {
+    let env = TestEnv::new()?;
+
+    let result = ShellTool.execute(json!({"command": "echo ok", "timeout_secs": 0}), &env.config).await;
+    assert!(result.is_err());
+    let result = ShellTool.execute(json!({"command": "echo ok", "timeout_secs": 999999}), &env.config).await;
+    assert!(result.is_err());
+
+    // stdin is closed, so commands waiting for input see EOF instead of hanging
+    let result = ShellTool.execute(json!({"command": "read line; echo done", "timeout_secs": 5}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["stdout"], "done");
+    Ok(())
+}
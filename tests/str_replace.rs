@@ -0,0 +1,86 @@
+//! ✂️ str_replace tests - Uniqueness, occurrence targeting and count assertions
+
+mod common;
+
+use anyhow::Result;
+use common::*;
+use empathic::tools::{Tool, str_replace::StrReplaceTool};
+use serde_json::json;
+
+const SOURCE: &str = "let a = 1;\nlet b = 1;\nlet c = 1;\nlet d = 2;\n";
+
+#[tokio::test]
+async fn test_unique_match_reports_count() -> Result<()> {
+    let env = TestEnv::new()?;
+    let file_path = env.create_file("lib.rs", SOURCE).await?;
+
+    let result = StrReplaceTool.execute(
+        json!({"path": "lib.rs", "old_str": "= 2", "new_str": "= 3", "expected_count": 1}),
+        &env.config
+    ).await?;
+    let parsed = McpResult::parse(result)?;
+
+    assert_eq!(parsed.content["count"], 1);
+    assert_eq!(parsed.content["replacements"], 1);
+    assert_eq!(parsed.content["line_number"], 4);
+    assert!(verify_file_content(&file_path, &SOURCE.replace("= 2", "= 3")).await?);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ambiguous_match_needs_occurrence() -> Result<()> {
+    let env = TestEnv::new()?;
+    let file_path = env.create_file("lib.rs", SOURCE).await?;
+
+    // 🚫 Three matches and no occurrence: refuse
+    let error = StrReplaceTool.execute(json!({"path": "lib.rs", "old_str": "= 1", "new_str": "= 9"}), &env.config)
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("appears 3 times"), "{}", error);
+
+    // 🎯 Only the second match
+    let result = StrReplaceTool.execute(
+        json!({"path": "lib.rs", "old_str": "= 1", "new_str": "= 9", "occurrence": 2}),
+        &env.config
+    ).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["count"], 3);
+    assert_eq!(parsed.content["line_numbers"], json!([2]));
+    assert!(verify_file_content(&file_path, "let a = 1;\nlet b = 9;\nlet c = 1;\nlet d = 2;\n").await?);
+
+    // 🔁 All remaining matches, with the count asserted first
+    let result = StrReplaceTool.execute(
+        json!({"path": "lib.rs", "old_str": "= 1", "new_str": "= 0", "occurrence": "all", "expected_count": 2}),
+        &env.config
+    ).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["replacements"], 2);
+    assert_eq!(parsed.content["line_numbers"], json!([1, 3]));
+    assert!(verify_file_content(&file_path, "let a = 0;\nlet b = 9;\nlet c = 0;\nlet d = 2;\n").await?);
+
+    // Out-of-range and malformed occurrences
+    assert!(StrReplaceTool.execute(json!({"path": "lib.rs", "old_str": "= 0", "occurrence": 3}), &env.config).await.is_err());
+    assert!(StrReplaceTool.execute(json!({"path": "lib.rs", "old_str": "= 0", "occurrence": 0}), &env.config).await.is_err());
+    assert!(StrReplaceTool.execute(json!({"path": "lib.rs", "old_str": "= 0", "occurrence": "first"}), &env.config).await.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_count_mismatch_and_not_found_leave_file_untouched() -> Result<()> {
+    let env = TestEnv::new()?;
+    let file_path = env.create_file("lib.rs", SOURCE).await?;
+
+    let error = StrReplaceTool.execute(
+        json!({"path": "lib.rs", "old_str": "= 1", "new_str": "= 5", "occurrence": "all", "expected_count": 2}),
+        &env.config
+    ).await.unwrap_err();
+    assert!(error.to_string().contains("appears 3 times in file, expected 2"), "{}", error);
+
+    let error = StrReplaceTool.execute(json!({"path": "lib.rs", "old_str": "let e"}), &env.config)
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("does not appear"), "{}", error);
+
+    assert!(verify_file_content(&file_path, SOURCE).await?);
+    Ok(())
+}
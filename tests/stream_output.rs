@@ -0,0 +1,55 @@
+//! 📡 Streaming tests - Output lines as progress notifications
+
+mod common;
+
+use anyhow::Result;
+use common::*;
+use empathic::mcp::progress::ProgressReporter;
+use empathic::tools::{Tool, shell::ShellTool, make::MakeTool};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_shell_stream_reports_lines() -> Result<()> {
+    let env = TestEnv::new()?;
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+
+    let result = ProgressReporter::new(json!(7), sender).scope(
+        ShellTool.execute(json!({"command": "echo first; echo second >&2; printf third", "stream": true}), &env.config)
+    ).await?;
+
+    let mut messages = Vec::new();
+    while let Ok(notification) = receiver.try_recv() {
+        let params = notification.params.unwrap();
+        assert_eq!(params["progressToken"], 7);
+        messages.push(params["message"].as_str().unwrap().to_string());
+    }
+    messages.sort();
+    assert_eq!(messages, vec!["first", "second", "third"]);
+
+    // 📦 Buffered output is still complete
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["stdout"], "first\nthird");
+    assert_eq!(parsed.content["stderr"], "second");
+
+    println!("✅ Shell streaming works");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_stream_is_opt_in() -> Result<()> {
+    let env = TestEnv::new()?;
+    env.create_file("Makefile", "all:\n\t@echo built\n").await?;
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+
+    let result = ProgressReporter::new(json!("tok"), sender.clone()).scope(
+        MakeTool.execute(json!({}), &env.config)
+    ).await?;
+    assert!(receiver.try_recv().is_err());
+    assert_eq!(McpResult::parse(result)?.content["stdout"], "built");
+
+    ProgressReporter::new(json!("tok"), sender).scope(
+        MakeTool.execute(json!({"stream": true}), &env.config)
+    ).await?;
+    assert_eq!(receiver.try_recv()?.params.unwrap()["message"], "built");
+    Ok(())
+}
@@ -0,0 +1,62 @@
+//! 🔗 Symlink tool tests - Relative and directory links inside the allowed roots
+
+#![cfg(unix)]
+
+mod common;
+
+use anyhow::Result;
+use common::*;
+use empathic::tools::{Tool, symlink::SymlinkTool};
+use serde_json::json;
+use std::path::PathBuf;
+
+#[tokio::test]
+async fn test_symlink_absolute_and_relative() -> Result<()> {
+    let env = TestEnv::new()?;
+    env.create_files(&[("src/lib.rs", "pub fn f() {}\n")]).await?;
+
+    let result = SymlinkTool.execute(json!({"target": "src/lib.rs", "link": "lib_link.rs"}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["target_is_dir"], false);
+    assert!(PathBuf::from(parsed.content["link_target"].as_str().unwrap()).is_absolute());
+    assert_eq!(std::fs::read_to_string(env.root_path.join("lib_link.rs"))?, "pub fn f() {}\n");
+
+    // 📁 Directory link stored relative to the link's own directory
+    let result = SymlinkTool.execute(json!({"target": "src", "link": "links/src_dir", "relative": true}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["target_is_dir"], true);
+    assert_eq!(std::fs::read_link(env.root_path.join("links/src_dir"))?, PathBuf::from("../src"));
+    assert!(env.root_path.join("links/src_dir/lib.rs").is_file());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_symlink_rejects_escaping_target() -> Result<()> {
+    let env = TestEnv::new()?;
+    let outside = tempfile::TempDir::new()?;
+    std::fs::write(outside.path().join("secret.txt"), "secret")?;
+
+    let outside_target = outside.path().join("secret.txt").to_string_lossy().to_string();
+    let result = SymlinkTool.execute(json!({"target": outside_target, "link": "secret"}), &env.config).await;
+    assert!(result.is_err());
+
+    let result = SymlinkTool.execute(json!({"target": "../../../../../../etc/passwd", "link": "passwd", "relative": true}), &env.config).await;
+    assert!(result.is_err());
+
+    // 🔗 A target reached through an existing escaping link is caught after resolution
+    std::os::unix::fs::symlink(outside.path(), env.root_path.join("escape"))?;
+    let result = SymlinkTool.execute(json!({"target": "escape/secret.txt", "link": "via_escape"}), &env.config).await;
+    assert!(result.is_err());
+
+    // 📍 The link location must stay inside the root as well
+    env.create_files(&[("inside.txt", "ok")]).await?;
+    let outside_link = outside.path().join("link").to_string_lossy().to_string();
+    let result = SymlinkTool.execute(json!({"target": "inside.txt", "link": outside_link}), &env.config).await;
+    assert!(result.is_err());
+    let result = SymlinkTool.execute(json!({"target": "inside.txt", "link": "escape/link"}), &env.config).await;
+    assert!(result.is_err());
+
+    assert!(!env.root_path.join("secret").exists() && !env.root_path.join("passwd").exists());
+    assert!(!outside.path().join("link").exists());
+    Ok(())
+}
@@ -0,0 +1,80 @@
+//! 🧾 Tool argument validation tests - Field-level errors from the dispatch path
+
+mod common;
+
+use anyhow::Result;
+use common::*;
+use empathic::mcp::JsonRpcRequest;
+use empathic::mcp::handlers::RequestHandler;
+use empathic::tools::get_all_tools;
+use serde_json::json;
+use std::collections::HashMap;
+
+fn request(method: &str, params: serde_json::Value) -> JsonRpcRequest {
+    serde_json::from_value(json!({"jsonrpc": "2.0", "id": 1, "method": method, "params": params})).unwrap()
+}
+
+#[tokio::test]
+async fn test_malformed_call_names_each_bad_field() -> Result<()> {
+    let env = TestEnv::new()?;
+    let tools: HashMap<_, _> = get_all_tools().into_iter().map(|tool| (tool.name().to_string(), tool)).collect();
+    let handler = RequestHandler::new(&env.config, &tools);
+
+    let response = handler.handle_request(request("tools/call", json!({
+        "name": "write_file",
+        "arguments": {"path": "x.txt", "start": "ten", "backup": "yes"}
+    }))).await.unwrap();
+
+    let error = response.error.unwrap();
+    assert_eq!(error.code, -32602);
+    assert!(error.message.contains("missing required field 'content' (string)"), "{}", error.message);
+    assert!(error.message.contains("field 'start' must be integer, got string \"ten\""), "{}", error.message);
+    assert!(error.message.contains("field 'backup' must be boolean"), "{}", error.message);
+    assert!(!env.root_path.join("x.txt").exists());
+    assert!(error.message.contains("category: protocol"), "{}", error.message);
+    assert_eq!(error.data.unwrap(), json!({"kind": "invalid_params", "category": "protocol"}));
+
+    println!("✅ Malformed calls get field-level errors");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_well_formed_call_reaches_tool() -> Result<()> {
+    // 🧪 A well-formed call still reaches the tool
+    let env = TestEnv::new()?;
+    env.create_file("notes.txt", "hello").await?;
+    let tools: HashMap<_, _> = get_all_tools().into_iter().map(|tool| (tool.name().to_string(), tool)).collect();
+    let handler = RequestHandler::new(&env.config, &tools);
+
+    let response = handler.handle_request(request("tools/call", json!({
+        "name": "read_file",
+        "arguments": {"path": "notes.txt", "start_line": 1}
+    }))).await.unwrap();
+    assert!(response.error.is_none(), "{:?}", response.error);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_tool_failures_carry_error_kind() -> Result<()> {
+    let env = TestEnv::new()?;
+    let tools: HashMap<_, _> = get_all_tools().into_iter().map(|tool| (tool.name().to_string(), tool)).collect();
+    let handler = RequestHandler::new(&env.config, &tools);
+
+    let response = handler.handle_request(request("tools/call", json!({
+        "name": "read_file",
+        "arguments": {"path": "missing.txt"}
+    }))).await.unwrap();
+    let error = response.error.unwrap();
+    assert_eq!(error.code, -32003, "{}", error.message);
+    assert_eq!(error.data.unwrap(), json!({"kind": "not_found", "category": "filesystem"}));
+
+    let response = handler.handle_request(request("tools/call", json!({
+        "name": "read_file",
+        "arguments": {"path": "../../etc/passwd"}
+    }))).await.unwrap();
+    assert_eq!(response.error.unwrap().code, -32002);
+
+    let response = handler.handle_request(request("tools/call", json!({"name": "no_such_tool"}))).await.unwrap();
+    assert_eq!(response.error.unwrap().code, -32601);
+    Ok(())
+}
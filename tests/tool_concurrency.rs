@@ -0,0 +1,110 @@
+//! 🚦 Tool concurrency tests - excess calls queue behind the limits
+
+mod common;
+
+use anyhow::Result;
+use common::*;
+use empathic::limiter::ToolLimiter;
+use empathic::mcp::JsonRpcRequest;
+use empathic::mcp::handlers::RequestHandler;
+use empathic::tools::get_all_tools;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+fn call_message(id: u64, name: &str, arguments: serde_json::Value) -> serde_json::Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "method": "tools/call",
+        "params": {"name": name, "arguments": arguments}
+    })
+}
+
+fn call(id: u64, name: &str, arguments: serde_json::Value) -> JsonRpcRequest {
+    serde_json::from_value(call_message(id, name, arguments)).unwrap()
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_calls_over_the_limit_are_queued() -> Result<()> {
+    let mut env = TestEnv::new()?;
+    env.config.tool_limiter = Arc::new(ToolLimiter::new(4, &[("shell".to_string(), 1)]));
+    let tools: HashMap<_, _> = get_all_tools().into_iter().map(|tool| (tool.name().to_string(), tool)).collect();
+    let handler = RequestHandler::new(&env.config, &tools);
+
+    let sleep = || json!({"command": "sleep 0.4"});
+    let started = Instant::now();
+    let (first, second, status) = tokio::join!(
+        handler.handle_request(call(1, "shell", sleep())),
+        handler.handle_request(call(2, "shell", sleep())),
+        async {
+            // Observe the queue while both shell calls are pending
+            tokio::time::sleep(Duration::from_millis(150)).await;
+            handler.handle_request(call(3, "tool_queue", json!({}))).await
+        },
+    );
+
+    assert!(first.unwrap().error.is_none());
+    assert!(second.unwrap().error.is_none());
+    assert!(started.elapsed() >= Duration::from_millis(800), "shell calls ran in parallel");
+
+    let status = McpResult::parse(status.unwrap().result.unwrap())?;
+    assert_eq!(status.content["in_flight"], 1);
+    assert_eq!(status.content["queued"], 1);
+    assert_eq!(status.content["per_tool"]["shell"], json!({"limit": 1, "in_flight": 1, "queued": 1}));
+    assert_eq!(env.config.tool_limiter.stats().global.in_flight, 0);
+
+    println!("✅ Excess calls wait for a slot");
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_batch_members_run_concurrently() -> Result<()> {
+    let env = TestEnv::new()?;
+    let tools: HashMap<_, _> = get_all_tools().into_iter().map(|tool| (tool.name().to_string(), tool)).collect();
+    let handler = RequestHandler::new(&env.config, &tools);
+
+    let sleep = |id: u64| call_message(id, "shell", json!({"command": "sleep 0.5"}));
+    let started = Instant::now();
+    let responses = handler.handle_batch(vec![sleep(1), sleep(2)]).await.unwrap();
+
+    assert!(started.elapsed() < Duration::from_millis(1000), "batch members ran one after another");
+    let ids: Vec<_> = responses.iter().map(|response| response.id.clone()).collect();
+    assert_eq!(ids, vec![Some(json!(1)), Some(json!(2))]);
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_server_answers_while_a_slow_call_runs() -> Result<()> {
+    use std::io::{BufRead, BufReader, Write};
+    use std::process::{Command, Stdio};
+
+    let root = tempfile::TempDir::new()?;
+    let mut child = Command::new(env!("CARGO_BIN_EXE_empathic"))
+        .env("ROOT_DIR", root.path())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().unwrap();
+    writeln!(stdin, "{}", call_message(1, "shell", json!({"command": "sleep 2"})))?;
+    writeln!(stdin, r#"{{"jsonrpc":"2.0","id":2,"method":"tools/list"}}"#)?;
+
+    // ⚡ tools/list is answered first, while the shell call still sleeps
+    let mut stdout = BufReader::new(child.stdout.take().unwrap());
+    let mut first = String::new();
+    stdout.read_line(&mut first)?;
+    let mut second = String::new();
+    stdout.read_line(&mut second)?;
+    drop(stdin);
+    child.wait()?;
+
+    assert!(first.contains("\"id\":2"), "first response: {first}");
+    assert!(second.contains("\"id\":1"), "second response: {second}");
+    Ok(())
+}
@@ -0,0 +1,63 @@
+//! 🔒 Tool policy tests - Allowlist, denylist and read-only mode
+
+mod common;
+
+use anyhow::Result;
+use common::*;
+use empathic::config::ToolPolicy;
+use empathic::mcp::JsonRpcRequest;
+use empathic::mcp::handlers::RequestHandler;
+use empathic::tools::{get_all_tools, get_enabled_tools};
+use serde_json::json;
+use std::collections::HashMap;
+
+fn request(method: &str, params: serde_json::Value) -> JsonRpcRequest {
+    serde_json::from_value(json!({"jsonrpc": "2.0", "id": 1, "method": method, "params": params})).unwrap()
+}
+
+#[tokio::test]
+async fn test_read_only_hides_and_blocks_mutating_tools() -> Result<()> {
+    let mut env = TestEnv::new()?;
+    env.config.tool_policy = ToolPolicy { read_only: true, ..Default::default() };
+
+    let names: Vec<&str> = get_enabled_tools(&env.config).iter().map(|tool| tool.name()).collect();
+    assert!(names.contains(&"read_file"));
+    assert!(!names.contains(&"write_file"));
+    assert!(!names.contains(&"shell"));
+    assert!(!names.contains(&"lsp_document_state"));
+
+    // 🔒 Even a handler holding every tool refuses disabled ones
+    let tools: HashMap<_, _> = get_all_tools().into_iter().map(|tool| (tool.name().to_string(), tool)).collect();
+    let handler = RequestHandler::new(&env.config, &tools);
+
+    let response = handler.handle_request(request("tools/list", json!({}))).await.unwrap();
+    let listed = response.result.unwrap()["tools"].as_array().unwrap().clone();
+    assert!(listed.iter().all(|tool| tool["name"] != "delete_file"));
+
+    let response = handler.handle_request(request("tools/call", json!({
+        "name": "write_file",
+        "arguments": {"path": "x.txt", "content": "nope"}
+    }))).await.unwrap();
+    let error = response.error.unwrap();
+    assert_eq!(error.code, -32004);
+    assert_eq!(error.data.unwrap()["kind"], "tool_disabled");
+    assert!(error.message.contains("disabled by policy"));
+    assert!(!env.root_path.join("x.txt").exists());
+
+    println!("✅ Read-only mode works");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_allowlist_and_denylist() -> Result<()> {
+    let mut env = TestEnv::new()?;
+    env.config.tool_policy = ToolPolicy {
+        enabled: Some(vec!["read_file".to_string(), "list_files".to_string()]),
+        disabled: vec!["list_files".to_string()],
+        read_only: false,
+    };
+
+    let names: Vec<&str> = get_enabled_tools(&env.config).iter().map(|tool| tool.name()).collect();
+    assert_eq!(names, vec!["read_file"]);
+    Ok(())
+}
@@ -0,0 +1,88 @@
+//! 🗑️ Trash tests - Soft deletes, restore and purge
+
+mod common;
+
+use anyhow::Result;
+use common::*;
+use empathic::tools::{Tool, delete_file::DeleteFileTool, trash::{PurgeTrashTool, RestoreFileTool}};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_soft_delete_and_restore_file() -> Result<()> {
+    // ♻️ A soft-deleted file comes back with its content
+    let env = TestEnv::new()?;
+    let file = env.create_file("src/lib.rs", "pub fn kept() {}").await?;
+
+    let deleted = McpResult::parse(DeleteFileTool.execute(json!({"path": "src/lib.rs", "soft": true}), &env.config).await?)?;
+    assert_mcp_success(&deleted);
+    assert_eq!(deleted.content["soft"], true);
+    let name = deleted.content["trash_name"].as_str().unwrap().to_string();
+    assert!(!file.exists());
+    assert!(env.root_path.join(".empathic/trash/files").join(&name).exists());
+
+    let restored = McpResult::parse(RestoreFileTool.execute(json!({"name": name}), &env.config).await?)?;
+    assert_mcp_success(&restored);
+    assert!(verify_file_content(&file, "pub fn kept() {}").await?);
+
+    let listed = McpResult::parse(PurgeTrashTool.execute(json!({"dry_run": true}), &env.config).await?)?;
+    assert_eq!(listed.content["count"], 0);
+
+    println!("✅ Soft delete and restore work");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_soft_delete_default_and_hard_override() -> Result<()> {
+    // ⚙️ SOFT_DELETE makes trash the default; soft: false still unlinks
+    let mut env = TestEnv::new()?;
+    env.config.soft_delete = true;
+    env.create_files(&[("dir/a.txt", "a"), ("gone.txt", "x")]).await?;
+
+    let refused = DeleteFileTool.execute(json!({"path": "dir"}), &env.config).await;
+    assert!(refused.unwrap_err().to_string().contains("not empty"));
+
+    let deleted = McpResult::parse(DeleteFileTool.execute(json!({"path": "dir", "recursive": true}), &env.config).await?)?;
+    assert_eq!(deleted.content["soft"], true);
+    assert_eq!(deleted.content["was_directory"], true);
+
+    let hard = McpResult::parse(DeleteFileTool.execute(json!({"path": "gone.txt", "soft": false}), &env.config).await?)?;
+    assert_eq!(hard.content["soft"], false);
+    assert!(hard.content.get("trash_name").is_none());
+
+    let listed = McpResult::parse(PurgeTrashTool.execute(json!({"dry_run": true}), &env.config).await?)?;
+    assert_eq!(listed.content["count"], 1);
+    assert_eq!(listed.content["entries"][0]["is_dir"], true);
+
+    println!("✅ Soft delete default respected");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_restore_refuses_to_clobber_and_purge_empties_trash() -> Result<()> {
+    // 🛡️ Restoring over a recreated file needs overwrite; purge is permanent
+    let env = TestEnv::new()?;
+    let file = env.create_file("notes.txt", "old").await?;
+    let deleted = McpResult::parse(DeleteFileTool.execute(json!({"path": "notes.txt", "soft": true}), &env.config).await?)?;
+    let name = deleted.content["trash_name"].as_str().unwrap().to_string();
+    env.create_file("notes.txt", "new").await?;
+
+    let refused = RestoreFileTool.execute(json!({"name": name}), &env.config).await;
+    assert!(refused.unwrap_err().to_string().contains("already exists"));
+
+    let elsewhere = McpResult::parse(RestoreFileTool.execute(json!({"name": name, "to": "notes.old.txt"}), &env.config).await?)?;
+    assert!(elsewhere.content["restored_to"].as_str().unwrap().ends_with("notes.old.txt"));
+    assert!(verify_file_content(&file, "new").await?);
+
+    DeleteFileTool.execute(json!({"path": "notes.txt", "soft": true}), &env.config).await?;
+    let kept = McpResult::parse(PurgeTrashTool.execute(json!({"older_than_hours": 24}), &env.config).await?)?;
+    assert_eq!(kept.content["count"], 0);
+    let ancient = McpResult::parse(PurgeTrashTool.execute(json!({"older_than_hours": u64::MAX}), &env.config).await?)?;
+    assert_eq!(ancient.content["count"], 0);
+    assert!(PurgeTrashTool.schema()["properties"]["older_than_hours"]["maximum"].is_u64());
+    let purged = McpResult::parse(PurgeTrashTool.execute(json!({}), &env.config).await?)?;
+    assert_eq!(purged.content["count"], 1);
+    assert!(std::fs::read_dir(env.root_path.join(".empathic/trash/files"))?.next().is_none());
+
+    println!("✅ Restore protection and purge work");
+    Ok(())
+}
@@ -0,0 +1,102 @@
+//! 👀 Watch tools tests - Registering, polling and unwatching paths
+
+mod common;
+
+use anyhow::Result;
+use common::*;
+use empathic::tools::{Tool, watch::{WatchPathsTool, UnwatchPathsTool}};
+use empathic::watcher::ChangeKind;
+use serde_json::json;
+
+#[tokio::test]
+async fn test_watch_paths_detects_changes() -> Result<()> {
+    let env = TestEnv::new()?;
+    env.create_file("src/main.rs", "fn main() {}").await?;
+
+    let result = WatchPathsTool.execute(json!({"paths": ["src"]}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["changed"].as_array().unwrap().len(), 1);
+    assert_eq!(parsed.content["watched"].as_array().unwrap().len(), 1);
+
+    // 📝 External edit shows up on the next poll (size change beats coarse mtimes)
+    env.create_file("src/main.rs", "fn main() { println!(\"hi\"); }").await?;
+    let events = env.config.file_watcher.poll();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].kind, ChangeKind::Modified);
+    assert!(events[0].path.ends_with("src/main.rs"));
+
+    let result = UnwatchPathsTool.execute(json!({}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["changed"].as_array().unwrap().len(), 1);
+    assert!(parsed.content["watched"].as_array().unwrap().is_empty());
+
+    println!("✅ Watching and unwatching works");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_watch_paths_rejects_escape_and_missing() -> Result<()> {
+    let env = TestEnv::new()?;
+
+    let result = WatchPathsTool.execute(json!({"paths": ["../"]}), &env.config).await;
+    assert!(result.is_err());
+
+    let result = WatchPathsTool.execute(json!({"paths": ["missing.rs"]}), &env.config).await;
+    assert!(result.is_err());
+
+    let result = WatchPathsTool.execute(json!({"paths": []}), &env.config).await;
+    assert!(result.is_err());
+
+    assert!(env.config.file_watcher.watched().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_unwatch_deleted_path() -> Result<()> {
+    // 🗑️ A watched file that was removed can still be unwatched by name
+    let env = TestEnv::new()?;
+    env.create_file("gone.txt", "bye").await?;
+
+    WatchPathsTool.execute(json!({"paths": ["gone.txt"]}), &env.config).await?;
+    tokio::fs::remove_file(env.root_path.join("gone.txt")).await?;
+
+    let result = UnwatchPathsTool.execute(json!({"paths": ["gone.txt"]}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["changed"].as_array().unwrap().len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_resources_subscribe_uses_watcher() -> Result<()> {
+    use empathic::mcp::handlers::RequestHandler;
+    use empathic::mcp::JsonRpcRequest;
+    use std::collections::HashMap;
+
+    let env = TestEnv::new()?;
+    env.create_file("notes.md", "# notes").await?;
+    let tools = HashMap::new();
+    let handler = RequestHandler::new(&env.config, &tools);
+    let request = |method: &str, uri: String| -> JsonRpcRequest {
+        serde_json::from_value(json!({"jsonrpc": "2.0", "id": 1, "method": method, "params": {"uri": uri}})).unwrap()
+    };
+    let uri = url::Url::from_file_path(env.root_path.join("notes.md").canonicalize()?).unwrap().to_string();
+
+    let init: JsonRpcRequest = serde_json::from_value(json!({"jsonrpc": "2.0", "id": 0, "method": "initialize"}))?;
+    let response = handler.handle_request(init).await.unwrap();
+    assert_eq!(response.result.unwrap()["capabilities"]["resources"]["subscribe"], true);
+
+    let response = handler.handle_request(request("resources/subscribe", uri.clone())).await.unwrap();
+    assert!(response.error.is_none());
+    assert_eq!(env.config.file_watcher.watched().len(), 1);
+
+    // 🚫 Outside the root and non-file URIs are refused
+    let response = handler.handle_request(request("resources/subscribe", "file:///etc/hostname".to_string())).await.unwrap();
+    assert!(response.error.is_some());
+    let response = handler.handle_request(request("resources/subscribe", "https://example.com".to_string())).await.unwrap();
+    assert!(response.error.is_some());
+
+    let response = handler.handle_request(request("resources/unsubscribe", uri)).await.unwrap();
+    assert!(response.error.is_none());
+    assert!(env.config.file_watcher.watched().is_empty());
+    Ok(())
+}
@@ -238,3 +238,256 @@ async fn test_write_file_large_content() -> Result<()> {
     
     Ok(())
 }
+
+#[tokio::test]
+async fn test_write_file_backup() -> Result<()> {
+    // 💾 backup: true keeps the previous content at <path>.bak
+    let env = TestEnv::new()?;
+    let tool = WriteFileTool;
+    let file_path = env.create_file("notes.md", "first draft").await?;
+
+    let result = tool.execute(
+        json!({"path": "notes.md", "content": "second draft", "backup": true}),
+        &env.config
+    ).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_mcp_success(&parsed);
+
+    let backup_path = env.root_path.join("notes.md.bak");
+    assert_eq!(parsed.content["backup_path"], backup_path.to_string_lossy().as_ref());
+    assert!(verify_file_content(&backup_path, "first draft").await?);
+    assert!(verify_file_content(&file_path, "second draft").await?);
+
+    // Without the flag no backup is reported
+    let result = tool.execute(json!({"path": "notes.md", "content": "final"}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert!(parsed.content.get("backup_path").is_none());
+    assert!(verify_file_content(&backup_path, "first draft").await?);
+
+    println!("✅ Backup before overwrite works");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_write_file_range_preserves_crlf() -> Result<()> {
+    // ↩️ Windows-authored files keep CRLF endings and their trailing newline
+    let env = TestEnv::new()?;
+    let crlf_fixture = "[package]\r\nname = \"demo\"\r\nversion = \"0.1.0\"\r\n";
+    let file_path = env.create_file("Cargo.toml", crlf_fixture).await?;
+
+    let result = WriteFileTool.execute(
+        json!({"path": "Cargo.toml", "content": "version = \"0.2.0\"", "start": 2, "end": 3}),
+        &env.config
+    ).await?;
+    assert_mcp_success(&McpResult::parse(result)?);
+
+    let bytes = tokio::fs::read(&file_path).await?;
+    assert_eq!(bytes, b"[package]\r\nname = \"demo\"\r\nversion = \"0.2.0\"\r\n");
+
+    println!("✅ CRLF endings preserved");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_str_replace_preserves_crlf() -> Result<()> {
+    // ↩️ Multi-line old/new strings written with LF still match and land as CRLF
+    use empathic::tools::str_replace::StrReplaceTool;
+
+    let env = TestEnv::new()?;
+    let file_path = env.create_file("lib.rs", "fn a() {\r\n    1\r\n}\r\n").await?;
+
+    let result = StrReplaceTool.execute(
+        json!({"path": "lib.rs", "old_str": "{\n    1\n}", "new_str": "{\n    2\n    + 3\n}"}),
+        &env.config
+    ).await?;
+    assert_mcp_success(&McpResult::parse(result)?);
+
+    assert_eq!(tokio::fs::read(&file_path).await?, b"fn a() {\r\n    2\r\n    + 3\r\n}\r\n");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_replace_preserves_crlf_and_missing_trailing_newline() -> Result<()> {
+    use empathic::tools::replace::ReplaceTool;
+
+    let env = TestEnv::new()?;
+    let file_path = env.create_file("notes.txt", "one\r\ntwo\r\nthree").await?;
+
+    let result = ReplaceTool.execute(
+        json!({"path": "notes.txt", "search": "two\nthree", "replace": "2\n3\n"}),
+        &env.config
+    ).await?;
+    assert_mcp_success(&McpResult::parse(result)?);
+
+    assert_eq!(tokio::fs::read(&file_path).await?, b"one\r\n2\r\n3");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_insert_at_line_keeps_missing_trailing_newline() -> Result<()> {
+    use empathic::tools::insert_at_line::InsertAtLineTool;
+
+    let env = TestEnv::new()?;
+    let file_path = env.create_file("win.txt", "first\r\nlast").await?;
+
+    let result = InsertAtLineTool.execute(json!({"path": "win.txt", "line": 2, "content": "after\n"}), &env.config).await?;
+    assert_mcp_success(&McpResult::parse(result)?);
+
+    assert_eq!(tokio::fs::read(&file_path).await?, b"first\r\nlast\r\nafter");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_write_file_dry_run_diff() -> Result<()> {
+    // 🔍 dry_run returns a unified diff and leaves the file untouched
+    let env = TestEnv::new()?;
+    let original = "fn a() {}\nfn b() {}\nfn c() {}\n";
+    let file_path = env.create_file("lib.rs", original).await?;
+
+    let result = WriteFileTool.execute(
+        json!({"path": "lib.rs", "content": "fn bee() {}", "start": 1, "end": 2, "dry_run": true}),
+        &env.config
+    ).await?;
+    let parsed = McpResult::parse(result)?;
+
+    assert_eq!(parsed.content["dry_run"], true);
+    assert_eq!(
+        parsed.content["diff"],
+        "--- a/lib.rs\n+++ b/lib.rs\n@@ -1,3 +1,3 @@\n fn a() {}\n-fn b() {}\n+fn bee() {}\n fn c() {}\n"
+    );
+    assert!(verify_file_content(&file_path, original).await?);
+
+    println!("✅ Dry-run diff works");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_str_replace_dry_run_diff() -> Result<()> {
+    use empathic::tools::str_replace::StrReplaceTool;
+
+    let env = TestEnv::new()?;
+    let original = "let x = 1;\nlet y = 2;\n";
+    let file_path = env.create_file("main.rs", original).await?;
+
+    let result = StrReplaceTool.execute(
+        json!({"path": "main.rs", "old_str": "y = 2", "new_str": "y = 3", "dry_run": true}),
+        &env.config
+    ).await?;
+    let parsed = McpResult::parse(result)?;
+
+    assert_eq!(parsed.content["replaced"], false);
+    let diff = parsed.content["diff"].as_str().unwrap();
+    assert!(diff.contains("-let y = 2;\n+let y = 3;"));
+    assert!(verify_file_content(&file_path, original).await?);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_replace_dry_run_locates_matches() -> Result<()> {
+    use empathic::tools::replace::ReplaceTool;
+
+    let env = TestEnv::new()?;
+    let original = "fn old() {}\nlet héllo = old();\nold();\n";
+    let file_path = env.create_file("lib.rs", original).await?;
+
+    let result = ReplaceTool.execute(
+        json!({"path": "lib.rs", "search": "old", "replace": "new", "dry_run": true, "max_preview_matches": 2}),
+        &env.config
+    ).await?;
+    let parsed = McpResult::parse(result)?;
+
+    let preview = parsed.content["match_preview"].as_array().unwrap();
+    assert_eq!(preview.len(), 2);
+    assert_eq!(preview[0]["line"], 1);
+    assert_eq!(preview[1]["line"], 2);
+    assert_eq!(preview[1]["column"], 13);
+    assert_eq!(preview[1]["text"], "let héllo = old();");
+    assert_eq!(preview[1]["highlight"], "            ^^^");
+    assert_eq!(parsed.content["match_preview_truncated"], true);
+    assert!(verify_file_content(&file_path, original).await?);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_write_file_keeps_existing_encoding() -> Result<()> {
+    // 🔤 Overwriting a UTF-16 file re-encodes to UTF-16 unless told otherwise
+    let env = TestEnv::new()?;
+    let path = env.root_path.join("wide.txt");
+    tokio::fs::write(&path, [0xFF, 0xFE, b'a', 0]).await?;
+
+    let result = WriteFileTool.execute(json!({"path": "wide.txt", "content": "é"}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["encoding"], "utf-16le");
+    assert_eq!(tokio::fs::read(&path).await?, vec![0xFF, 0xFE, 0xE9, 0x00]);
+
+    WriteFileTool.execute(json!({"path": "wide.txt", "content": "é", "encoding": "utf-8"}), &env.config).await?;
+    assert_eq!(tokio::fs::read(&path).await?, "é".as_bytes());
+
+    // Unrepresentable text fails instead of corrupting the file
+    tokio::fs::write(&path, b"caf\xE9").await?;
+    let result = WriteFileTool.execute(json!({"path": "wide.txt", "content": "🦀"}), &env.config).await;
+    assert!(result.is_err());
+    assert_eq!(tokio::fs::read(&path).await?, b"caf\xE9");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_write_file_append_mode() -> Result<()> {
+    // ➕ Appending adds to the end and reports the new size
+    let env = TestEnv::new()?;
+    let file_path = env.create_file("app.log", "first\n").await?;
+
+    let result = WriteFileTool.execute(json!({"path": "app.log", "content": "second\n", "append": true}), &env.config).await?;
+    let parsed = McpResult::parse(result)?;
+    assert_eq!(parsed.content["append"], true);
+    assert_eq!(parsed.content["total_size"], 13);
+    assert!(verify_file_content(&file_path, "first\nsecond\n").await?);
+
+    // Missing files are created
+    let result = WriteFileTool.execute(json!({"path": "logs/new.log", "content": "x", "append": true}), &env.config).await?;
+    assert_eq!(McpResult::parse(result)?.content["total_size"], 1);
+
+    // Dry run previews the appended content
+    let result = WriteFileTool.execute(json!({"path": "app.log", "content": "third\n", "append": true, "dry_run": true}), &env.config).await?;
+    assert!(McpResult::parse(result)?.content["diff"].as_str().unwrap().contains("+third"));
+    assert!(verify_file_content(&file_path, "first\nsecond\n").await?);
+
+    // Line ranges and append don't mix
+    let result = WriteFileTool.execute(json!({"path": "app.log", "content": "x", "append": true, "start": 1}), &env.config).await;
+    assert!(result.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_write_file_append_keeps_encoding_without_second_bom() -> Result<()> {
+    let env = TestEnv::new()?;
+    let path = env.root_path.join("wide.txt");
+    tokio::fs::write(&path, [0xFF, 0xFE, b'a', 0]).await?;
+
+    WriteFileTool.execute(json!({"path": "wide.txt", "content": "b", "append": true}), &env.config).await?;
+    assert_eq!(tokio::fs::read(&path).await?, vec![0xFF, 0xFE, b'a', 0, b'b', 0]);
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_writes_go_through_in_root_symlinks() -> Result<()> {
+    // 🔗 Editing through a link updates its target and keeps the link
+    use empathic::tools::str_replace::StrReplaceTool;
+
+    let env = TestEnv::new()?;
+    let target = env.create_file("real/config.txt", "v1\n").await?;
+    let link = env.root_path.join("config.txt");
+    std::os::unix::fs::symlink(&target, &link)?;
+
+    let result = WriteFileTool.execute(json!({"path": "config.txt", "content": "v2\n"}), &env.config).await?;
+    assert_mcp_success(&McpResult::parse(result)?);
+    assert!(std::fs::symlink_metadata(&link)?.file_type().is_symlink());
+    assert!(verify_file_content(&target, "v2\n").await?);
+
+    let result = StrReplaceTool.execute(json!({"path": "config.txt", "old_str": "v2", "new_str": "v3"}), &env.config).await?;
+    assert_mcp_success(&McpResult::parse(result)?);
+    assert!(std::fs::symlink_metadata(&link)?.file_type().is_symlink());
+    assert!(verify_file_content(&target, "v3\n").await?);
+    Ok(())
+}